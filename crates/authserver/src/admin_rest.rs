@@ -0,0 +1,589 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Request, State},
+    http::StatusCode,
+    middleware::{from_fn_with_state, Next},
+    response::{IntoResponse, Response},
+    routing::{delete, get, post, put},
+    Json,
+    Router,
+};
+use axum_extra::headers::{authorization::Bearer, Authorization, HeaderMapExt};
+use azothacore_common::{
+    bevy_app::{az_startup_succeeded, AzStartupFailedEvent, TokioRuntime},
+    configuration::ConfigMgr,
+    AccountTypes,
+};
+use azothacore_database::{
+    args,
+    database_env::{LoginDatabase, LoginPreparedStmts},
+};
+use azothacore_server::{
+    game::accounts::{account_mgr::AccountMgr, battlenet_account_mgr::BattlenetAccountMgr, AccountOpError},
+    shared::realms::RealmFlags,
+};
+use bevy::{
+    app::AppExit,
+    ecs::schedule::common_conditions::resource_exists,
+    prelude::{App, Commands, EventReader, EventWriter, IntoSystemConfigs, PostUpdate, Res, Resource, Startup, SystemSet},
+};
+use flagset::FlagSet;
+use hyper::{body::Incoming, service::service_fn};
+use hyper_util::{
+    rt::{TokioExecutor, TokioIo},
+    server::conn::auto::Builder as HyperServerConnBuilder,
+};
+use sha2::{Digest, Sha256};
+use tokio::{
+    net::TcpListener,
+    sync::mpsc::{unbounded_channel, UnboundedSender},
+};
+use tower_service::Service as TowerService;
+use tracing::{debug, error, info, warn};
+
+use crate::{config::AuthserverConfig, ssl_context::SslContext};
+
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AdminRESTServiceSystemSets {
+    Start,
+    Terminate,
+}
+
+/// REST service for operators to manage accounts, bans and realms, so that they do not have to edit the auth DB by
+/// hand. It goes through [AccountMgr] / [BattlenetAccountMgr] so that it behaves the same as the console commands.
+///
+/// Only started if `AdminREST.Enabled` is set, every request must carry `AdminREST.Token` as a bearer token.
+pub fn admin_rest_service_plugin(app: &mut App) {
+    app.add_systems(Startup, AdminRESTService::start.in_set(AdminRESTServiceSystemSets::Start))
+        .add_systems(
+            PostUpdate,
+            AdminRESTService::terminate
+                .run_if(az_startup_succeeded())
+                .run_if(resource_exists::<AdminRestTermSender>)
+                .in_set(AdminRESTServiceSystemSets::Terminate),
+        );
+}
+
+#[derive(Resource)]
+struct AdminRestTermSender(UnboundedSender<()>);
+
+struct AdminRESTService;
+
+impl AdminRESTService {
+    fn start(
+        mut commands: Commands,
+        cfg: Res<ConfigMgr<AuthserverConfig>>,
+        rt: Res<TokioRuntime>,
+        ssl_ctx: Res<SslContext>,
+        login_db: Res<LoginDatabase>,
+        mut ev_startup_failed: EventWriter<AzStartupFailedEvent>,
+    ) {
+        if !cfg.AdminREST.Enabled {
+            return;
+        }
+        if cfg.AdminREST.Token.is_empty() {
+            error!(target:"server::rest", "AdminREST.Token must be set when the admin REST service is enabled");
+            ev_startup_failed.send_default();
+            return;
+        }
+        let acceptor = match rt.block_on(TcpListener::bind(cfg.admin_rest_bind_addr())) {
+            Ok(a) => a,
+            Err(e) => {
+                error!(target:"server::rest", cause=?e, "Couldn't bind to {}", cfg.admin_rest_bind_addr());
+                ev_startup_failed.send_default();
+                return;
+            },
+        };
+        info!(target:"server::rest", "Admin service bound to https://{}", cfg.admin_rest_bind_addr());
+
+        let (term_snd, mut term_rcv) = unbounded_channel();
+        commands.insert_resource(AdminRestTermSender(term_snd));
+
+        let ssl_ctx = ssl_ctx.clone();
+        let state = AdminServiceRequestState {
+            login_db: Arc::new(login_db.clone()),
+            token:    cfg.AdminREST.Token.as_str().into(),
+        };
+        let router = Self::router(state);
+
+        let handler = rt.handle().clone();
+        rt.spawn(async move {
+            loop {
+                let (cnx, remote_addr) = tokio::select! {
+                    _ = term_rcv.recv() => {
+                        debug!("termination triggered, quitting admin rest service loop");
+                        break
+                    }
+                    accepted = acceptor.accept() => match accepted {
+                        Ok(a) => a,
+                        Err(e) => {
+                            error!(target:"server::rest", "error encountered when accepting admin request: {e}");
+                            continue;
+                        },
+                    }
+                };
+                debug!(target:"server::rest", "Accepted admin connection from Addr {remote_addr}");
+
+                let stream = match ssl_ctx.accept(cnx).await {
+                    Ok(s) => TokioIo::new(s),
+                    Err(e) => {
+                        error!(target:"server::rest", "Failed SSL handshake from Addr {remote_addr}, err: {e}");
+                        continue;
+                    },
+                };
+                let router = router.clone();
+                let hyper_svc = service_fn(move |request: Request<Incoming>| router.clone().call(request));
+                handler.spawn(async {
+                    let _ = HyperServerConnBuilder::new(TokioExecutor::new()).serve_connection(stream, hyper_svc).await;
+                });
+            }
+            info!(target:"server::rest", "Admin service exiting...");
+        });
+    }
+
+    fn router(state: AdminServiceRequestState) -> Router {
+        Router::new()
+            .route("/admin/bnetAccounts/", post(Self::handle_post_bnet_account))
+            .route("/admin/bnetAccounts/{id}/lock/", post(Self::handle_post_bnet_account_lock))
+            .route("/admin/bnetAccounts/{id}/unlock/", post(Self::handle_post_bnet_account_unlock))
+            .route("/admin/accounts/", post(Self::handle_post_account))
+            .route("/admin/accounts/{id}/lock/", post(Self::handle_post_account_lock))
+            .route("/admin/accounts/{id}/unlock/", post(Self::handle_post_account_unlock))
+            .route("/admin/accounts/{id}/access/", put(Self::handle_put_account_access))
+            .route("/admin/bans/accounts/", get(Self::handle_get_account_bans))
+            .route("/admin/bans/accounts/{id}/", delete(Self::handle_delete_account_ban))
+            .route("/admin/bans/ips/", get(Self::handle_get_ip_bans))
+            .route("/admin/bans/ips/{ip}/", delete(Self::handle_delete_ip_ban))
+            .route("/admin/realms/", get(Self::handle_get_realms))
+            .route_layer(from_fn_with_state(state.clone(), Self::require_token))
+            .fallback(|| async { AdminError(StatusCode::NOT_FOUND, "not found".to_string()) })
+            .with_state(state)
+    }
+
+    fn terminate(mut app_exit_events: EventReader<AppExit>, term_snd: Res<AdminRestTermSender>) {
+        if app_exit_events.read().count() == 0 {
+            return;
+        }
+        if let Err(e) = term_snd.0.send(()) {
+            debug!(cause=?e, "send terminate error, admin service receiving channel half may be dropped or closed");
+        }
+    }
+
+    async fn require_token(State(state): State<AdminServiceRequestState>, request: Request, next: Next) -> Response {
+        let authorised = request
+            .headers()
+            .typed_get::<Authorization<Bearer>>()
+            .is_some_and(|auth| tokens_match(auth.token(), &state.token));
+        if !authorised {
+            warn!(target:"server::rest", uri=%request.uri(), "admin request without a valid token");
+            return AdminError(StatusCode::UNAUTHORIZED, "invalid token".to_string()).into_response();
+        }
+        next.run(request).await
+    }
+
+    async fn handle_post_bnet_account(
+        State(state): State<AdminServiceRequestState>,
+        Json(req): Json<CreateBnetAccountRequest>,
+    ) -> AdminResult<Json<CreatedAccount>> {
+        let game_account = BattlenetAccountMgr::create_battlenet_account(&**state.login_db, &req.email, &req.password, req.create_game_account).await?;
+        let id = BattlenetAccountMgr::get_id(&**state.login_db, &req.email.to_ascii_uppercase())
+            .await?
+            .ok_or(AccountOpError::NameNotExist)?;
+        info!(target:"server::rest", id, "created Battle.net account through the admin service");
+        Ok(Json(CreatedAccount { id, game_account }))
+    }
+
+    async fn handle_post_bnet_account_lock(State(state): State<AdminServiceRequestState>, Path(id): Path<u32>) -> AdminResult<StatusCode> {
+        BattlenetAccountMgr::set_locked(&**state.login_db, id, true).await?;
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    async fn handle_post_bnet_account_unlock(State(state): State<AdminServiceRequestState>, Path(id): Path<u32>) -> AdminResult<StatusCode> {
+        BattlenetAccountMgr::set_locked(&**state.login_db, id, false).await?;
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    async fn handle_post_account(State(state): State<AdminServiceRequestState>, Json(req): Json<CreateAccountRequest>) -> AdminResult<Json<CreatedAccount>> {
+        AccountMgr::create_account(&**state.login_db, &req.username, &req.password, &req.email, None).await?;
+        let id = AccountMgr::get_id(&**state.login_db, &req.username.to_ascii_uppercase())
+            .await?
+            .ok_or(AccountOpError::NameNotExist)?;
+        info!(target:"server::rest", id, "created game account through the admin service");
+        Ok(Json(CreatedAccount { id, game_account: None }))
+    }
+
+    async fn handle_post_account_lock(State(state): State<AdminServiceRequestState>, Path(id): Path<u32>) -> AdminResult<StatusCode> {
+        AccountMgr::set_locked(&**state.login_db, id, true).await?;
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    async fn handle_post_account_unlock(State(state): State<AdminServiceRequestState>, Path(id): Path<u32>) -> AdminResult<StatusCode> {
+        AccountMgr::set_locked(&**state.login_db, id, false).await?;
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    async fn handle_put_account_access(
+        State(state): State<AdminServiceRequestState>,
+        Path(id): Path<u32>,
+        Json(req): Json<AccountAccessRequest>,
+    ) -> AdminResult<StatusCode> {
+        let security_level = AccountTypes::try_from(req.gm_level).map_err(|e| AdminError(StatusCode::BAD_REQUEST, format!("invalid gm_level: {e}")))?;
+        if AccountMgr::get_name(&**state.login_db, id).await?.is_none() {
+            return Err(AccountOpError::NameNotExist.into());
+        }
+        AccountMgr::update_account_access(&**state.login_db, id, security_level, req.realm_id).await?;
+        info!(target:"server::rest", id, ?security_level, realm_id=?req.realm_id, "updated account access through the admin service");
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    async fn handle_get_account_bans(State(state): State<AdminServiceRequestState>) -> AdminResult<Json<Vec<AccountBan>>> {
+        let bans = LoginDatabase::sel_account_banned_all::<_, AccountBan>(&**state.login_db, args!()?).await?;
+        Ok(Json(bans))
+    }
+
+    async fn handle_delete_account_ban(State(state): State<AdminServiceRequestState>, Path(id): Path<u32>) -> AdminResult<StatusCode> {
+        let res = LoginDatabase::upd_account_not_banned(&**state.login_db, args!(id)?).await?;
+        if res.rows_affected() == 0 {
+            return Err(AdminError(StatusCode::NOT_FOUND, "account is not banned".to_string()));
+        }
+        info!(target:"server::rest", id, "lifted account ban through the admin service");
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    async fn handle_get_ip_bans(State(state): State<AdminServiceRequestState>) -> AdminResult<Json<Vec<IpBan>>> {
+        let bans = LoginDatabase::sel_ip_banned_all::<_, IpBan>(&**state.login_db, args!()?).await?;
+        Ok(Json(bans))
+    }
+
+    async fn handle_delete_ip_ban(State(state): State<AdminServiceRequestState>, Path(ip): Path<String>) -> AdminResult<StatusCode> {
+        let res = LoginDatabase::del_ip_not_banned(&**state.login_db, args!(&ip)?).await?;
+        if res.rows_affected() == 0 {
+            return Err(AdminError(StatusCode::NOT_FOUND, "IP is not banned".to_string()));
+        }
+        info!(target:"server::rest", ip, "lifted IP ban through the admin service");
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    async fn handle_get_realms(State(state): State<AdminServiceRequestState>) -> AdminResult<Json<Vec<RealmInfo>>> {
+        let realms = LoginDatabase::sel_realmlist_all::<_, RealmRow>(&**state.login_db, args!()?).await?;
+        Ok(Json(realms.into_iter().map(RealmInfo::from).collect()))
+    }
+}
+
+/// Compares the digests of the tokens in constant time, so that neither the configured token nor its length can be
+/// guessed from response times
+fn tokens_match(got: &str, expected: &str) -> bool {
+    let (got, expected) = (Sha256::digest(got), Sha256::digest(expected));
+    got.iter().zip(expected.iter()).fold(0, |acc, (g, e)| acc | (g ^ e)) == 0
+}
+
+#[derive(Clone)]
+struct AdminServiceRequestState {
+    login_db: Arc<LoginDatabase>,
+    token:    Arc<str>,
+}
+
+type AdminResult<T> = Result<T, AdminError>;
+
+#[derive(Debug)]
+struct AdminError(StatusCode, String);
+
+impl IntoResponse for AdminError {
+    fn into_response(self) -> Response {
+        let AdminError(status, error) = self;
+        (status, Json(ErrorBody { error })).into_response()
+    }
+}
+
+impl From<AccountOpError> for AdminError {
+    fn from(value: AccountOpError) -> Self {
+        let status = match value {
+            AccountOpError::NameNotExist => StatusCode::NOT_FOUND,
+            AccountOpError::NameAlreadyExist => StatusCode::CONFLICT,
+            AccountOpError::DbInternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            _ => StatusCode::BAD_REQUEST,
+        };
+        Self(status, value.to_string())
+    }
+}
+
+impl From<sqlx::Error> for AdminError {
+    fn from(value: sqlx::Error) -> Self {
+        error!(target:"server::rest", cause=%value, "DB error on admin request");
+        Self(StatusCode::INTERNAL_SERVER_ERROR, "AOR_DB_INTERNAL_ERROR".to_string())
+    }
+}
+
+#[derive(serde::Serialize)]
+struct ErrorBody {
+    error: String,
+}
+
+#[derive(serde::Deserialize)]
+struct CreateBnetAccountRequest {
+    email:               String,
+    password:            String,
+    #[serde(default)]
+    create_game_account: bool,
+}
+
+#[derive(serde::Deserialize)]
+struct CreateAccountRequest {
+    username: String,
+    password: String,
+    email:    String,
+}
+
+#[derive(serde::Serialize)]
+struct CreatedAccount {
+    id:           u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    game_account: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+struct AccountAccessRequest {
+    gm_level: u8,
+    /// None for all realms
+    #[serde(default)]
+    realm_id: Option<u32>,
+}
+
+#[derive(serde::Serialize, sqlx::FromRow)]
+struct AccountBan {
+    id:       u32,
+    username: String,
+}
+
+#[derive(serde::Serialize, sqlx::FromRow)]
+struct IpBan {
+    ip:        String,
+    bandate:   u32,
+    unbandate: u32,
+    bannedby:  String,
+    banreason: String,
+}
+
+#[derive(sqlx::FromRow)]
+struct RealmRow {
+    id:                     u32,
+    name:                   String,
+    address:                String,
+    port:                   u16,
+    flag:                   u8,
+    gamebuild:              u32,
+    #[sqlx(rename = "allowedSecurityLevel")]
+    allowed_security_level: u8,
+    population:             f32,
+}
+
+#[derive(serde::Serialize)]
+struct RealmInfo {
+    id:                     u32,
+    name:                   String,
+    address:                String,
+    port:                   u16,
+    flags:                  Vec<String>,
+    build:                  u32,
+    allowed_security_level: u8,
+    population:             f32,
+}
+
+impl From<RealmRow> for RealmInfo {
+    fn from(value: RealmRow) -> Self {
+        let flags: FlagSet<RealmFlags> = FlagSet::new_truncated(value.flag.into());
+        Self {
+            id:                     value.id,
+            name:                   value.name,
+            address:                value.address,
+            port:                   value.port,
+            flags:                  flags.into_iter().filter(|f| *f != RealmFlags::None).map(|f| format!("{f:?}")).collect(),
+            build:                  value.gamebuild,
+            allowed_security_level: value.allowed_security_level,
+            population:             value.population,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::Future;
+
+    use axum::body::{to_bytes, Body};
+    use azothacore_tests_utils::{random_alpanum, test_db_pool_auth, SHARED_TEST_DB_PERMITS};
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    const TOKEN: &str = "admin-token";
+
+    fn test_router(login_db: &LoginDatabase) -> Router {
+        let state = AdminServiceRequestState {
+            login_db: Arc::new(login_db.clone()),
+            token:    TOKEN.into(),
+        };
+        AdminRESTService::router(state)
+    }
+
+    /// Sends the request through the router, returning the status and the JSON body of the response, if any
+    async fn send(router: &mut Router, method: &str, uri: &str, token: Option<&str>, body: Option<Value>) -> (StatusCode, Option<Value>) {
+        let mut request = axum::http::Request::builder().method(method).uri(uri);
+        if let Some(token) = token {
+            request = request.header("Authorization", format!("Bearer {token}"));
+        }
+        let request = match body {
+            Some(body) => request.header("Content-Type", "application/json").body(Body::from(body.to_string())),
+            None => request.body(Body::empty()),
+        }
+        .unwrap();
+        let response = router.call(request).await.unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).ok())
+    }
+
+    #[test]
+    fn it_matches_tokens() {
+        assert!(tokens_match("secret", "secret"));
+        assert!(!tokens_match("secreT", "secret"));
+        assert!(!tokens_match("secret2", "secret"));
+        assert!(!tokens_match("", "secret"));
+    }
+
+    #[test]
+    fn it_lists_realm_flags_by_name() {
+        let realm = RealmInfo::from(RealmRow {
+            id:                     1,
+            name:                   "Realm".to_string(),
+            address:                "127.0.0.1".to_string(),
+            port:                   8085,
+            flag:                   0x22,
+            gamebuild:              26972,
+            allowed_security_level: 0,
+            population:             0.0,
+        });
+        assert_eq!(realm.flags, ["Offline", "Recommended"]);
+    }
+
+    #[tokio::test]
+    async fn it_rejects_requests_without_the_token() {
+        let _p = SHARED_TEST_DB_PERMITS.acquire().await.unwrap();
+        let login_db = LoginDatabase(test_db_pool_auth(None).await);
+        let mut router = test_router(&login_db);
+
+        for token in [None, Some("wrong-token"), Some("admin-tokeN")] {
+            let (status, body) = send(&mut router, "GET", "/admin/realms/", token, None).await;
+            assert_eq!(status, StatusCode::UNAUTHORIZED);
+            assert_eq!(body, Some(json!({ "error": "invalid token" })));
+        }
+        let (status, body) = send(&mut router, "GET", "/admin/realms/", Some(TOKEN), None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.unwrap().is_array());
+    }
+
+    /// Runs the scenario against the router, deleting the accounts it created afterwards even if it fails, as the
+    /// router commits them to the shared test DB
+    async fn with_account_cleanup(
+        login_db: &LoginDatabase,
+        username: Option<&str>,
+        bnet_email: Option<&str>,
+        scenario: impl Future<Output = ()> + Send + 'static,
+    ) {
+        let result = tokio::spawn(scenario).await;
+
+        let account_ids = sqlx::query_scalar::<_, u32>(
+            "SELECT id FROM account WHERE username = ? OR battlenet_account IN (SELECT id FROM battlenet_accounts WHERE email = ?)",
+        )
+        .bind(username.map(str::to_ascii_uppercase))
+        .bind(bnet_email.map(str::to_ascii_uppercase))
+        .fetch_all(&**login_db)
+        .await
+        .unwrap();
+        for id in account_ids {
+            for query in [
+                "DELETE FROM account_access WHERE id = ?",
+                "DELETE FROM realmcharacters WHERE acctid = ?",
+                "DELETE FROM account WHERE id = ?",
+            ] {
+                sqlx::query(query).bind(id).execute(&**login_db).await.unwrap();
+            }
+        }
+        sqlx::query("DELETE FROM battlenet_accounts WHERE email = ?")
+            .bind(bnet_email.map(str::to_ascii_uppercase))
+            .execute(&**login_db)
+            .await
+            .unwrap();
+
+        if let Err(e) = result {
+            std::panic::resume_unwind(e.into_panic());
+        }
+    }
+
+    #[tokio::test]
+    async fn it_creates_bnet_accounts() {
+        let _p = SHARED_TEST_DB_PERMITS.acquire().await.unwrap();
+        let login_db = LoginDatabase(test_db_pool_auth(None).await);
+        let mut router = test_router(&login_db);
+        let email = format!("{}@example.com", random_alpanum(10));
+        let request = json!({ "email": email, "password": "password", "create_game_account": true });
+
+        with_account_cleanup(&login_db, None, Some(&email), async move {
+            let (status, body) = send(&mut router, "POST", "/admin/bnetAccounts/", Some(TOKEN), Some(request.clone())).await;
+            assert_eq!(status, StatusCode::OK);
+            let body = body.unwrap();
+            let id = body["id"].as_u64().unwrap();
+            assert_eq!(body["game_account"], json!(format!("{id}#1")));
+
+            let (status, _) = send(&mut router, "POST", "/admin/bnetAccounts/", Some(TOKEN), Some(request)).await;
+            assert_eq!(status, StatusCode::CONFLICT);
+
+            let (status, _) = send(&mut router, "POST", &format!("/admin/bnetAccounts/{id}/lock/"), Some(TOKEN), None).await;
+            assert_eq!(status, StatusCode::NO_CONTENT);
+            let (status, _) = send(&mut router, "POST", "/admin/bnetAccounts/4294967295/lock/", Some(TOKEN), None).await;
+            assert_eq!(status, StatusCode::NOT_FOUND);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn it_creates_game_accounts_and_updates_their_access() {
+        let _p = SHARED_TEST_DB_PERMITS.acquire().await.unwrap();
+        let login_db = LoginDatabase(test_db_pool_auth(None).await);
+        let mut router = test_router(&login_db);
+        let username = random_alpanum(10);
+        let request = json!({ "username": username, "password": "password", "email": "mail@example.com" });
+
+        let scenario_login_db = login_db.clone();
+        with_account_cleanup(&login_db, Some(&username), None, async move {
+            let (status, body) = send(&mut router, "POST", "/admin/accounts/", Some(TOKEN), Some(request)).await;
+            assert_eq!(status, StatusCode::OK);
+            let body = body.unwrap();
+            assert_eq!(body.get("game_account"), None);
+            let id = u32::try_from(body["id"].as_u64().unwrap()).unwrap();
+
+            let access_uri = format!("/admin/accounts/{id}/access/");
+            let (status, _) = send(&mut router, "PUT", &access_uri, Some(TOKEN), Some(json!({ "gm_level": 3 }))).await;
+            assert_eq!(status, StatusCode::NO_CONTENT);
+            assert_eq!(
+                AccountMgr::get_security(&*scenario_login_db, id, None).await.unwrap(),
+                AccountTypes::SecAdministrator
+            );
+
+            let (status, _) = send(&mut router, "PUT", &access_uri, Some(TOKEN), Some(json!({ "gm_level": 200 }))).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            let (status, _) = send(
+                &mut router,
+                "PUT",
+                "/admin/accounts/4294967295/access/",
+                Some(TOKEN),
+                Some(json!({ "gm_level": 1 })),
+            )
+            .await;
+            assert_eq!(status, StatusCode::NOT_FOUND);
+            let (status, _) = send(&mut router, "POST", &format!("/admin/accounts/{id}/lock/"), Some(TOKEN), None).await;
+            assert_eq!(status, StatusCode::NO_CONTENT);
+        })
+        .await;
+    }
+}
@@ -6,15 +6,16 @@ use std::{
 
 use azothacore_common::{
     bounded_nums::LowerBoundedNum,
-    configuration::{Config, DatabaseInfo, DbUpdates, LogAppender, LogFlags, LogLevel, LogLoggerConfig},
+    configuration::{Config, DatabaseInfo, DbUpdates, LogAppender, LogFlags, LogLevel, LogLevelOverride, LogLoggerConfig},
     durationb_hours,
     durationb_mins,
     durationb_s,
     log::LoggingConfig,
 };
 use azothacore_server::shared::{
-    networking::{socket::AddressOrName, socket_mgr::SocketMgrConfig},
+    networking::{packet_log::PacketLogConfig, socket::AddressOrName, socket_mgr::SocketMgrConfig},
     realms::realm_list::RealmListConfig,
+    secrets::SecretMgrConfig,
 };
 use ipnet::IpNet;
 use serde::{Deserialize, Serialize};
@@ -45,10 +46,21 @@ pub struct AuthserverConfig {
     #[serde_inline_default("logs".into())] pub LogsDir: PathBuf,
     #[serde(default="default_authserver_log_appenders")] pub Appender: Vec<LogAppender>,
     #[serde(default="default_authserver_log_configs")] pub Logger: Vec<LogLoggerConfig>,
+    /// Targets logged at a more verbose level for a while, i.e. `{ target = "server::rest", level = "Debug", duration = "10m" }`.
+    /// Applied on startup and whenever they change on a config reload.
+    #[serde(default)] pub LogLevelOverride: Vec<LogLevelOverride>,
+    /// Packet logging file for the Battle.net connections. Filename is relative to LogsDir.
+    ///
+    /// Default "" - (Disabled), "Bnet.pkt" - (Enabled)
+    #[serde(default)] pub PacketLogFile: String,
     /// Bind auth server to IP/hostname
     #[serde_inline_default("0.0.0.0".parse().unwrap())] pub BindIP: IpAddr,
     /// TCP port to reach the auth server for battle.net connections.
     #[serde_inline_default(1119)] pub BattlenetPort: u16,
+    /// Largest frame in bytes that a battle.net client may send before it is disconnected.
+    ///
+    /// Default 65536, 0 - (Unlimited)
+    #[serde_inline_default(0x10000)] pub MaxFrameSize: usize,
     /// Login REST service - this is used by the client to log in.
     #[serde(default)] pub LoginREST: pub struct AuthserverConfigLoginREST {
         /// TCP port to reach the REST login method.
@@ -72,6 +84,17 @@ pub struct AuthserverConfig {
         /// When using client -launcherlogin feature it is recommended to set it to a high value (like a week)
         #[serde(default)] pub TicketDuration: LowerBoundedNum<Duration, { durationb_s!(0) }, { durationb_hours!(15) }>,
     },
+    /// Admin REST service - used by operators to manage accounts, bans and realms.
+    #[serde(default)] pub AdminREST: pub struct AuthserverConfigAdminREST {
+        /// Enable the admin REST service.
+        #[serde_inline_default(false)] pub Enabled: bool,
+        /// TCP port to reach the admin REST service.
+        #[serde_inline_default(8082)] pub Port: u16,
+        /// Token that every request must send as `Authorization: Bearer <Token>`
+        ///
+        /// The service refuses to start if it is enabled without one.
+        #[serde(default)] pub Token: String,
+    },
     #[serde(default)] pub WrongPass: pub struct AuthserverConfigWrongPass {
         #[serde(default)] pub Enabled: bool,
         /// Number of login attempts with wrong password before the account or IP will be banned.
@@ -98,6 +121,14 @@ pub struct AuthserverConfig {
     #[serde(default)] pub RealmsStateUpdateDelay: LowerBoundedNum<Duration, { durationb_s!(0) }, { durationb_s!(10) }>,
     /// Time between checks for expired bans
     #[serde(default)] pub BanExpiryCheckInterval: LowerBoundedNum<Duration, { durationb_s!(0) }, { durationb_mins!(1) }>,
+    /// The hex encoded 128 bit key that the TOTP secrets of Battle.net accounts are encrypted with. Empty to disable
+    /// authenticators.
+    ///
+    /// Once set, it must not be lost. Changing it requires setting TOTPOldMasterSecret to the previous key for the next
+    /// startup.
+    #[serde(default)] pub TOTPMasterSecret: String,
+    /// The previous TOTPMasterSecret, only needed on the first startup after it has been changed
+    #[serde(default)] pub TOTPOldMasterSecret: String,
 }
 }
 
@@ -121,11 +152,14 @@ pub fn default_authserver_log_appenders() -> Vec<LogAppender> {
             // ],
         },
         LogAppender::File {
-            name:      String::from("Auth"),
-            min_level: Warning,
-            max_level: Error,
-            flags:     AddLogLevel | AddLogFilter | TruncateFile | BackupBeforeOverwrite | AddLogTimestamps,
-            file:      String::from("Auth.log"),
+            name:          String::from("Auth"),
+            min_level:     Warning,
+            max_level:     Error,
+            flags:         AddLogLevel | AddLogFilter | TruncateFile | BackupBeforeOverwrite | AddLogTimestamps,
+            file:          String::from("Auth.log"),
+            max_file_size: 0,
+            max_files:     0,
+            compress:      false,
         },
     ]
 }
@@ -140,12 +174,32 @@ pub fn default_authserver_log_configs() -> Vec<LogLoggerConfig> {
     }]
 }
 
-impl Config for AuthserverConfig {}
+impl Config for AuthserverConfig {
+    const RESTART_REQUIRED_KEYS: &'static [&'static str] = &[
+        "LoginDatabaseInfo",
+        "Updates",
+        "LogsDir",
+        "PacketLogFile",
+        "BindIP",
+        "BattlenetPort",
+        "MaxFrameSize",
+        "LoginREST",
+        "AdminREST",
+        "CertificatesFile",
+        "PrivateKeyFile",
+        "TOTPMasterSecret",
+        "TOTPOldMasterSecret",
+    ];
+}
 
 impl SocketMgrConfig<SessionInner> for AuthserverConfig {
     fn retrieve_bind_addr(&self) -> impl ToSocketAddrs {
         (self.BindIP, self.BattlenetPort)
     }
+
+    fn retrieve_max_frame_size(&self) -> Option<usize> {
+        (self.MaxFrameSize > 0).then_some(self.MaxFrameSize)
+    }
 }
 
 impl LoggingConfig for AuthserverConfig {
@@ -160,6 +214,16 @@ impl LoggingConfig for AuthserverConfig {
     fn retrieve_logs_dir(&self) -> PathBuf {
         self.LogsDir.clone()
     }
+
+    fn retrieve_level_overrides(&self) -> &[LogLevelOverride] {
+        &self.LogLevelOverride
+    }
+}
+
+impl PacketLogConfig for AuthserverConfig {
+    fn packet_log_file(&self) -> Option<PathBuf> {
+        (!self.PacketLogFile.is_empty()).then(|| self.LogsDir.join(&self.PacketLogFile))
+    }
 }
 
 impl SslContextConfig for AuthserverConfig {
@@ -178,7 +242,21 @@ impl RealmListConfig for AuthserverConfig {
     }
 }
 
+impl SecretMgrConfig for AuthserverConfig {
+    fn totp_master_secret(&self) -> &str {
+        &self.TOTPMasterSecret
+    }
+
+    fn totp_old_master_secret(&self) -> &str {
+        &self.TOTPOldMasterSecret
+    }
+}
+
 impl AuthserverConfig {
+    pub fn admin_rest_bind_addr(&self) -> SocketAddr {
+        SocketAddr::new(self.BindIP, self.AdminREST.Port)
+    }
+
     pub fn login_rest_bind_addr(&self) -> SocketAddr {
         SocketAddr::new(self.BindIP, self.LoginREST.Port)
     }
@@ -216,3 +294,57 @@ impl AuthserverConfig {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use azothacore_common::configuration::{ConfigChanges, ConfigMgr};
+
+    use super::*;
+
+    #[test]
+    fn it_keeps_every_restart_required_key_on_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let cfg_path = dir.path().join("authserver.toml");
+        fs::write(&cfg_path, "").unwrap();
+        let mut cfg_mgr = ConfigMgr::<AuthserverConfig>::load(cfg_path.clone(), None, false).unwrap();
+        let before = (*cfg_mgr).clone();
+
+        fs::write(
+            &cfg_path,
+            r#"
+LogsDir = "/srv/azcore/logs"
+PacketLogFile = "Bnet.pkt"
+BindIP = "127.0.0.1"
+BattlenetPort = 2119
+MaxFrameSize = 1024
+CertificatesFile = "/srv/azcore/bnetserver.cert.pem"
+PrivateKeyFile = "/srv/azcore/bnetserver.key.pem"
+TOTPMasterSecret = "000102030405060708090A0B0C0D0E0F"
+TOTPOldMasterSecret = "0F0E0D0C0B0A09080706050403020100"
+
+[LoginDatabaseInfo]
+Address = "db:3306"
+
+[Updates]
+AutoSetup = false
+
+[LoginREST]
+Port = 9081
+
+[AdminREST]
+Port = 9082
+"#,
+        )
+        .unwrap();
+        let changes = cfg_mgr.reload_from_path().unwrap();
+        for key in AuthserverConfig::RESTART_REQUIRED_KEYS {
+            assert!(
+                changes.restart_required.iter().any(|k| k == key || k.starts_with(&format!("{key}."))),
+                "{key} is not a key of the config, or was not changed by the test"
+            );
+        }
+        assert_eq!(ConfigChanges::between(&before, &*cfg_mgr).unwrap(), ConfigChanges::default());
+    }
+}
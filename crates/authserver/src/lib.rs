@@ -1,3 +1,4 @@
+pub mod admin_rest;
 #[allow(non_snake_case)]
 pub mod config;
 pub mod rest;
@@ -1,6 +1,7 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use authserver::{
+    admin_rest::{admin_rest_service_plugin, AdminRESTServiceSystemSets},
     config::AuthserverConfig,
     rest::{login_rest_service_plugin, LoginRESTServiceSystemSets},
     session::{bnet_session_handling_plugin, SessionInner},
@@ -9,7 +10,7 @@ use authserver::{
 use azothacore_common::{
     banner,
     bevy_app::{az_startup_succeeded, bevy_app, AzStartupFailedEvent, TokioRuntime},
-    configuration::{config_mgr_plugin, ConfigMgr, ConfigMgrSet, DatabaseType},
+    configuration::{config_mgr_plugin_with_includes, config_watcher_plugin, ConfigMgr, ConfigMgrSet, DatabaseType, DEFAULT_CONFIG_WATCH_DEBOUNCE},
     log::{logging_plugin, LoggingSetupSet},
     AzResult,
     AZOTHA_REALM_CONFIG,
@@ -19,10 +20,13 @@ use azothacore_database::{
     args_unwrap,
     database_env::{LoginDatabase, LoginPreparedStmts},
     database_loader::DatabaseLoader,
+    db_log::db_log_plugin,
+    query_processor::{db_query_queue_plugin, DbQueryQueue},
 };
 use azothacore_server::shared::{
-    networking::socket_mgr::socket_mgr_plugin,
-    realms::realm_list::realm_list_plugin,
+    networking::{packet_log::packet_log_plugin, socket_mgr::socket_mgr_plugin},
+    realms::realm_list::{realm_list_plugin, RealmListStartSet},
+    secrets::{secret_mgr_plugin, SecretMgrInitSet},
     shared_defines::{set_server_process, ServerProcessType},
     tokio_signal_handling_bevy_plugin,
 };
@@ -57,19 +61,25 @@ fn main() -> AzResult<()> {
     let rt = tokio::runtime::Builder::new_multi_thread().enable_all().build().unwrap();
 
     let mut app = bevy_app();
+    if vm.watch_config {
+        app.add_plugins(config_watcher_plugin::<AuthserverConfig>(DEFAULT_CONFIG_WATCH_DEBOUNCE));
+    }
     app.insert_resource(TokioRuntime(rt))
         .add_plugins((
             tokio_signal_handling_bevy_plugin,
-            config_mgr_plugin::<AuthserverConfig, _>(vm.config, vm.dry_run),
+            config_mgr_plugin_with_includes::<AuthserverConfig, _>(vm.config, vm.config_dir, vm.dry_run),
             logging_plugin::<AuthserverConfig>,
+            packet_log_plugin::<AuthserverConfig>,
             ssl_context_plugin::<AuthserverConfig>,
             login_rest_service_plugin,
+            admin_rest_service_plugin,
             // Get the list of realms for the server
             realm_list_plugin::<AuthserverConfig>,
             socket_mgr_plugin::<AuthserverConfig, SessionInner>,
             bnet_session_handling_plugin,
-            // // TODO: Impl me? Init Secret Manager
-            // sSecretMgr->Initialize();
+            secret_mgr_plugin::<AuthserverConfig>,
+            db_query_queue_plugin::<LoginDatabase>,
+            db_log_plugin,
         ))
         .add_systems(
             Startup,
@@ -83,7 +93,15 @@ fn main() -> AzResult<()> {
         .add_systems(FixedUpdate, ban_expiry_task.run_if(az_startup_succeeded()).in_set(AuthserverSet::BanExpiryTask))
         // Init logging right after config management
         .configure_sets(PreStartup, ConfigMgrSet::<AuthserverConfig>::load_initial().before(LoggingSetupSet))
-        .configure_sets(Startup, ((SetSslContextSet, AuthserverSet::StartDB).before(LoginRESTServiceSystemSets::Start),))
+        .configure_sets(
+            Startup,
+            (
+                (SetSslContextSet, AuthserverSet::StartDB, SecretMgrInitSet).before(LoginRESTServiceSystemSets::Start),
+                (SetSslContextSet, AuthserverSet::StartDB).before(AdminRESTServiceSystemSets::Start),
+                RealmListStartSet.after(AuthserverSet::StartDB),
+                SecretMgrInitSet.after(AuthserverSet::StartDB),
+            ),
+        )
         .add_systems(PostUpdate, stop_db)
         .run();
 
@@ -106,19 +124,19 @@ fn insert_ban_expiry_timer(mut commands: Commands, cfg: Res<ConfigMgr<Authserver
     commands.insert_resource(BanExpiryTimer(Timer::new(*cfg.BanExpiryCheckInterval, TimerMode::Repeating)));
 }
 
-fn ban_expiry_task(mut timer: ResMut<BanExpiryTimer>, time: Res<Time<Real>>, login_db: Res<LoginDatabase>, rt: Res<TokioRuntime>) {
+fn ban_expiry_task(mut timer: ResMut<BanExpiryTimer>, time: Res<Time<Real>>, login_db_queue: Res<DbQueryQueue<LoginDatabase>>) {
     timer.0.tick(time.delta());
     if !timer.0.finished() {
         return;
     }
-    rt.block_on(async {
-        if let Err(e) = LoginDatabase::del_expired_ip_bans(&**login_db, args_unwrap!()).await {
+    login_db_queue.execute(|login_db| async move {
+        if let Err(e) = LoginDatabase::del_expired_ip_bans(&*login_db, args_unwrap!()).await {
             error!(target:"bnetserver", cause=?e, "del_expired_ip_bans err");
         };
-        if let Err(e) = LoginDatabase::upd_expired_account_bans(&**login_db, args_unwrap!()).await {
+        if let Err(e) = LoginDatabase::upd_expired_account_bans(&*login_db, args_unwrap!()).await {
             error!(target:"bnetserver", cause=?e, "upd_expired_account_bans err");
         };
-        if let Err(e) = LoginDatabase::del_bnet_expired_account_banned(&**login_db, args_unwrap!()).await {
+        if let Err(e) = LoginDatabase::del_bnet_expired_account_banned(&*login_db, args_unwrap!()).await {
             error!(target:"bnetserver", cause=?e, "del_bnet_expired_account_banned err");
         };
     });
@@ -145,7 +163,9 @@ fn start_db(mut commands: Commands, cfg: Res<ConfigMgr<AuthserverConfig>>, rt: R
         },
         Ok(d) => d,
     };
-    commands.insert_resource(LoginDatabase(auth_db));
+    let auth_db = LoginDatabase(auth_db);
+    commands.insert_resource(DbQueryQueue::new(auth_db.clone(), rt.handle().clone()));
+    commands.insert_resource(auth_db);
 }
 
 fn stop_db(rt: Res<TokioRuntime>, login_db: Option<Res<LoginDatabase>>, mut app_exit_events: EventReader<AppExit>) {
@@ -168,10 +188,16 @@ fn stop_db(rt: Res<TokioRuntime>, login_db: Option<Res<LoginDatabase>>, mut app_
 struct ConsoleArgs {
     /// Dry run
     #[arg(short, long = "dry-run")]
-    dry_run: bool,
+    dry_run:      bool,
     /// use <arg> as configuration file
     #[arg(short, long, default_value_t = Path::new(CONF_DIR).join(AZOTHA_REALM_CONFIG).to_str().unwrap().to_string())]
-    config:  String,
+    config:       String,
+    /// Directory of toml files merged over the configuration file in order of their names, i.e. conf.d
+    #[arg(long)]
+    config_dir:   Option<PathBuf>,
+    /// Reload the configuration when the configuration file or the files in the configuration directory change
+    #[arg(long)]
+    watch_config: bool,
     #[arg(short, long, default_value_t = String::new())]
-    service: String,
+    service:      String,
 }
@@ -16,6 +16,7 @@ use axum_extra::{
 use azothacore_common::{
     bevy_app::{az_startup_succeeded, AzStartupFailedEvent, TokioRuntime},
     configuration::ConfigMgr,
+    crypto::totp,
     hex_str,
     utils::unix_now,
 };
@@ -23,7 +24,10 @@ use azothacore_database::{
     args_unwrap,
     database_env::{LoginDatabase, LoginPreparedStmts},
 };
-use azothacore_server::{game::accounts::battlenet_account_mgr::BattlenetAccountMgr, shared::networking::socket::AddressOrName};
+use azothacore_server::{
+    game::accounts::battlenet_account_mgr::{BattlenetAccountMgr, BnetAccountLogon},
+    shared::{networking::socket::AddressOrName, secrets::SecretMgr},
+};
 use bevy::{
     app::AppExit,
     prelude::{App, Commands, EventReader, EventWriter, IntoSystemConfigs, PostUpdate, Res, Resource, Startup, SystemSet},
@@ -109,6 +113,7 @@ impl LoginRESTService {
         rt: Res<TokioRuntime>,
         ssl_ctx: Res<SslContext>,
         login_db: Res<LoginDatabase>,
+        secret_mgr: Res<SecretMgr>,
         mut ev_startup_failed: EventWriter<AzStartupFailedEvent>,
     ) {
         let (term_snd, mut term_rcv) = unbounded_channel();
@@ -126,6 +131,7 @@ impl LoginRESTService {
         let cfg = Arc::new((**cfg).clone());
         let ssl_ctx = ssl_ctx.clone();
         let login_db = Arc::new(login_db.clone());
+        let secret_mgr = Arc::new(secret_mgr.clone());
 
         let handler = rt.handle().clone();
         rt.spawn(async move {
@@ -168,9 +174,10 @@ impl LoginRESTService {
                 // `TokioIo` converts between them.
                 let stream = TokioIo::new(stream);
                 let state = LoginServiceRequestState {
-                    source_ip: remote_addr.into(),
-                    login_db:  login_db.clone(),
-                    cfg:       cfg.clone(),
+                    source_ip:  remote_addr.into(),
+                    login_db:   login_db.clone(),
+                    cfg:        cfg.clone(),
+                    secret_mgr: secret_mgr.clone(),
                 };
 
                 let router = router.clone();
@@ -238,6 +245,12 @@ impl LoginRESTService {
                     label:      "Password".to_string(),
                     max_length: Some(16),
                 },
+                FormInput {
+                    input_id:   "authenticator".to_string(),
+                    r#type:     "text".to_string(),
+                    label:      "Authenticator".to_string(),
+                    max_length: Some(6),
+                },
                 FormInput {
                     input_id:   "log_in_submit".to_string(),
                     r#type:     "submit".to_string(),
@@ -315,7 +328,12 @@ impl LoginRESTService {
     }
 
     async fn handle_post_login(
-        State(LoginServiceRequestState { login_db, source_ip, cfg, .. }): State<LoginServiceRequestState>,
+        State(LoginServiceRequestState {
+            login_db,
+            source_ip,
+            cfg,
+            secret_mgr,
+        }): State<LoginServiceRequestState>,
         WithRejection(Json(login_form), _): WithRejection<Json<LoginForm>, PostLoginError>,
     ) -> impl IntoResponse {
         // following similar to TC's logic
@@ -340,15 +358,18 @@ impl LoginRESTService {
 
         let mut login = None;
         let mut password = None;
+        let mut authenticator = None;
         for input in login_form.inputs {
             if login.is_none() && input.input_id == "account_name" {
                 login = Some(input.value)
             } else if password.is_none() && input.input_id == "password" {
                 password = Some(input.value)
+            } else if authenticator.is_none() && input.input_id == "authenticator" {
+                authenticator = Some(input.value)
             }
         }
         let (login, password) = match (login, password) {
-            (Some(l), Some(p)) => (l.to_ascii_uppercase(), p.to_ascii_uppercase()),
+            (Some(l), Some(p)) => (l.to_ascii_uppercase(), p),
             _ => {
                 error!(target:"server::rest", "no login details found in request");
                 return (
@@ -368,8 +389,10 @@ impl LoginRESTService {
         #[derive(sqlx::FromRow)]
         struct BnetAuth {
             account_id:          u32,
-            pass_hash:           String,
+            #[sqlx(flatten)]
+            logon:               BnetAccountLogon,
             failed_logins:       u64,
+            totp_secret:         Option<Vec<u8>>,
             login_ticket:        Option<String>,
             login_ticket_expiry: Option<u64>,
             is_banned:           Option<bool>,
@@ -385,20 +408,37 @@ impl LoginRESTService {
             },
             Some(o) => o,
         };
-        let sent_password_hash = BattlenetAccountMgr::calculate_sha_pass_hash(&login, &password);
-
         let BnetAuth {
             account_id,
-            pass_hash,
+            logon,
             mut failed_logins,
+            totp_secret,
             mut login_ticket,
             login_ticket_expiry,
             is_banned,
         } = fields;
         let is_banned = is_banned.is_some_and(|b| b);
 
+        let password_ok = handle_login_err!(
+            BattlenetAccountMgr::check_logon(&**login_db, account_id, &login, &password, &logon).await,
+            "DB error when checking password for post login"
+        );
+        // Accounts with an authenticator also need a valid token, anything else counts as a wrong password
+        let login_ok = match totp_secret {
+            Some(encrypted) if password_ok => {
+                let Some(secret) = secret_mgr.decrypt_totp_secret(&encrypted) else {
+                    error!(target:"server::rest", account_id=account_id, "unable to decrypt TOTP secret, is TOTPMasterSecret set?");
+                    return (StatusCode::OK, [("Content-Type", "application/json;charset=utf-8")], Json(error_response));
+                };
+                authenticator
+                    .and_then(|token| token.trim().parse().ok())
+                    .is_some_and(|token| totp::validate_token(&secret, token))
+            },
+            _ => password_ok,
+        };
+
         let now = unix_now().as_secs();
-        if sent_password_hash == pass_hash {
+        if login_ok {
             if login_ticket.is_none() || login_ticket_expiry.is_none_or(|exp_ts| exp_ts < now) {
                 login_ticket = Some(format!("AZ-{}", hex_str!(OsRng.unwrap_err().random::<[u8; 20]>())));
             }
@@ -620,7 +660,8 @@ struct Empty {}
 
 #[derive(Clone)]
 struct LoginServiceRequestState {
-    source_ip: AddressOrName,
-    login_db:  Arc<LoginDatabase>,
-    cfg:       Arc<AuthserverConfig>,
+    source_ip:  AddressOrName,
+    login_db:   Arc<LoginDatabase>,
+    cfg:        Arc<AuthserverConfig>,
+    secret_mgr: Arc<SecretMgr>,
 }
@@ -1,5 +1,5 @@
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     io::{self, Write},
     ops::{Deref, DerefMut},
     process,
@@ -15,6 +15,7 @@ use azothacore_common::{
     bevy_app::{az_startup_succeeded, TokioRuntime},
     configuration::ConfigMgr,
     deref_boilerplate,
+    hex_str,
     utils::{unix_now, BufferDecodeError, BufferResult, DecodeValueFromBytes, MessageBuffer},
     AccountTypes,
     AzResult,
@@ -29,6 +30,7 @@ use azothacore_database::{
 use azothacore_server::shared::{
     bnetrpc_zcompress,
     networking::{
+        packet_log::{Direction, LoggedFrame, PacketLog},
         socket::{AddressOrName, Socket},
         socket_mgr::{ConnectionComponent, NewTcpConnection, RunStartTcpSocketTask, SocketReceiver},
     },
@@ -59,15 +61,24 @@ use bnet_rpc::{
         },
         authentication::v1::{AuthenticationListener, AuthenticationService, LogonRequest, LogonResult, VerifyWebCredentialsRequest},
         challenge::v1::{ChallengeExternalRequest, ChallengeListener},
-        channel::v1::ChannelService,
+        channel::v1::{
+            ChannelListener,
+            ChannelService,
+            DissolveRequest,
+            RemoveMemberRequest,
+            SendMessageRequest,
+            UpdateChannelStateRequest,
+            UpdateMemberStateRequest,
+        },
         connection::v1::{ConnectRequest, ConnectResponse, ConnectionService, DisconnectNotification, DisconnectRequest},
-        friends::v1::FriendsService,
+        friends::v1::{self as friends, FriendsService},
         game_utilities::v1::{ClientRequest, ClientResponse, GameUtilitiesService, GetAllValuesForAttributeRequest, GetAllValuesForAttributeResponse},
-        presence::v1::PresenceService,
-        report::v1::ReportService,
-        resources::v1::ResourcesService,
-        user_manager::v1::UserManagerService,
+        presence::v1::{self as presence, Field, FieldKey, FieldOperation, PresenceService},
+        report::v1::{report_type, ReportService, SendReportRequest, SubmitReportRequest},
+        resources::v1::{ContentHandleRequest, ResourcesService},
+        user_manager::v1::{self as user_manager, UserManagerService},
         Attribute,
+        ContentHandle,
         EntityId,
         Header,
         NoData,
@@ -80,7 +91,7 @@ use bnet_rpc::{
     BnetRpcService,
     BnetServiceWrapper,
 };
-use bytes::{Buf, Bytes};
+use bytes::{Buf, BufMut, Bytes, BytesMut};
 use prost::Message;
 use rand::{rngs::OsRng, RngCore, TryRngCore};
 use sqlx::{Pool, Row};
@@ -94,6 +105,9 @@ use tracing::{debug, error, warn};
 
 use crate::{config::AuthserverConfig, ssl_context::SslContext};
 
+#[cfg(test)]
+mod replay;
+
 fn handle_game_utils_service_compress_response<T>(prefix: &[u8], data: &T) -> BnetRpcResult<Vec<u8>>
 where
     T: ?Sized + serde::Serialize,
@@ -113,6 +127,18 @@ where
     }
 }
 
+/// The high part of the [EntityId] of Battle.net accounts
+const BNET_ACCOUNT_ENTITY_HIGH: u64 = 0x100000000000000;
+/// The high part of the [EntityId] of WoW game accounts
+const GAME_ACCOUNT_ENTITY_HIGH: u64 = 0x200000200576F57;
+
+/// The presence program that the Battle.net specific fields live in, i.e. "BN"
+const PRESENCE_PROGRAM_BN: u32 = 0x424E;
+/// Presence groups of the BN program, the field ID of the online state is the same for both
+const PRESENCE_GROUP_ACCOUNT: u32 = 1;
+const PRESENCE_GROUP_GAME_ACCOUNT: u32 = 2;
+const PRESENCE_FIELD_IS_ONLINE: u32 = 1;
+
 #[derive(Debug, sqlx::FromRow)]
 struct AccountInfo {
     // void LoadResult(PreparedQueryResult result),
@@ -233,17 +259,25 @@ fn handle_bnet_authserver_socket(
     login_db: Res<LoginDatabase>,
     rt: Res<TokioRuntime>,
     ssl_ctx: Res<SslContext>,
+    packet_log: Option<Res<PacketLog>>,
     mut sock_recv: ResMut<SocketReceiver<SessionInner>>,
 ) {
     let ssl_ctx = ssl_ctx.clone();
     let login_db = login_db.clone();
-    while let Ok(NewTcpConnection { permit, name, conn }) = sock_recv.0.try_recv() {
+    while let Ok(NewTcpConnection {
+        permit,
+        name,
+        conn,
+        max_frame_size,
+    }) = sock_recv.0.try_recv()
+    {
         let entity = commands.spawn_empty().id();
         let ssl_ctx = ssl_ctx.clone();
         let login_db = login_db.clone();
+        let packet_log = packet_log.as_deref().cloned();
 
         let task = rt.spawn(async move {
-            let sess = match SessionInner::start_from_tcp(login_db, ssl_ctx, permit, name, conn).await {
+            let sess = match SessionInner::start_from_tcp(login_db, ssl_ctx, permit, name, conn, max_frame_size, packet_log).await {
                 Err(e) => {
                     error!(cause=?e, "error starting session from new TCP connections");
                     return CommandQueue::default();
@@ -286,16 +320,8 @@ fn read_handler(
             inner:      &mut inner,
         };
 
-        for AuthserverPacket { header, packet_buffer } in packets {
-            let res: io::Result<()> = rt.block_on(async {
-                if header.service_id != 0xFE {
-                    sess.dispatch(header.service_hash(), header.token, header.method_id(), packet_buffer).await?;
-                } else {
-                    sess.receive(header.service_hash(), header.token, header.method_id(), packet_buffer).await?;
-                }
-                Ok(())
-            });
-            if let Err(err) = res {
+        for packet in packets {
+            if let Err(err) = rt.block_on(sess.handle_packet(packet)) {
                 error!(cause=?err, caller_info=%sess.caller_info(), "session dispatch packets/receive packets err, terminating socket");
                 sess.close();
                 commands.entity(e).remove::<SessionInner>();
@@ -363,6 +389,43 @@ impl DecodeValueFromBytes for AuthserverPacket {
     }
 }
 
+impl AuthserverPacket {
+    /// The packet as it is written on the wire, header length followed by the header and the packet data
+    fn to_frame(&self) -> Bytes {
+        let header_length = u16::try_from(self.header.encoded_len()).expect("header length should always fit in a u16");
+        let mut frame = BytesMut::with_capacity(2 + self.header.encoded_len() + self.packet_buffer.len());
+        frame.put_u16(header_length);
+        self.header.encode(&mut frame).expect("frame should have been allocated with enough capacity");
+        frame.put_slice(&self.packet_buffer);
+        frame.freeze()
+    }
+}
+
+impl LoggedFrame for AuthserverPacket {
+    fn to_log_bytes(&self) -> Bytes {
+        self.to_frame()
+    }
+
+    fn describe(_direction: Direction, frame: &[u8]) -> String {
+        let header = frame
+            .split_first_chunk::<2>()
+            .and_then(|(header_length, rest)| rest.get(..u16::from_be_bytes(*header_length).into()))
+            .and_then(|header| Header::decode(header).ok());
+        match header {
+            None => format!("malformed header in frame of {} bytes", frame.len()),
+            Some(h) if h.service_id == 0xFE => format!("response token={} status={} size={}", h.token, h.status(), h.size()),
+            Some(h) => format!(
+                "service_id={} service_hash=0x{:08X} method_id={} token={} size={}",
+                h.service_id,
+                h.service_hash(),
+                h.method_id(),
+                h.token,
+                h.size()
+            ),
+        }
+    }
+}
+
 pub struct Session<'a> {
     login_db:   &'a LoginDatabase,
     cfg:        &'a AuthserverConfig,
@@ -389,21 +452,23 @@ pub struct SessionInner {
     /// Contains a mapping of tokens expecting a return from a previous client
     /// call to service hash and method_id
     ///
-    response_callbacks: Mutex<BTreeMap<u32, (u32, u32)>>,
-    _permit:            Option<OwnedSemaphorePermit>,
-    socket:             Socket<AuthserverPacket>,
+    response_callbacks:     Mutex<BTreeMap<u32, (u32, u32)>>,
+    _permit:                Option<OwnedSemaphorePermit>,
+    socket:                 Socket<AuthserverPacket>,
     /// the current session's account information. If this is set, then we can treat it as _authed=True
     /// like in TC/AC
-    account_info:       OnceLock<AccountInfo>,
+    account_info:           OnceLock<AccountInfo>,
     /// Game account info, saved during Command_RealmListTicketRequest_v1_b9
     /// for now its a cloned value from `account_info`
-    game_account_info:  OnceLock<GameAccountInfo>,
-    locale:             OnceLock<Locale>,
-    os:                 OnceLock<String>,
-    build:              AtomicU32,
-    ip_country:         OnceLock<String>,
-    client_secret:      OnceLock<[u8; 32]>,
-    request_token:      AtomicU32,
+    game_account_info:      OnceLock<GameAccountInfo>,
+    locale:                 OnceLock<Locale>,
+    os:                     OnceLock<String>,
+    build:                  AtomicU32,
+    ip_country:             OnceLock<String>,
+    client_secret:          OnceLock<[u8; 32]>,
+    request_token:          AtomicU32,
+    /// Entities (high, low) whose presence the client has subscribed to
+    presence_subscriptions: Mutex<BTreeSet<(u64, u64)>>,
 }
 
 deref_boilerplate!(SessionInner, Socket<AuthserverPacket>, socket);
@@ -421,6 +486,14 @@ fn map_err_to_internal<E: std::error::Error>(e: E) -> BattlenetRpcErrorCode {
 }
 
 impl Session<'_> {
+    async fn handle_packet(&mut self, AuthserverPacket { header, packet_buffer }: AuthserverPacket) -> io::Result<()> {
+        if header.service_id != 0xFE {
+            self.dispatch(header.service_hash(), header.token, header.method_id(), packet_buffer).await
+        } else {
+            self.receive(header.service_hash(), header.token, header.method_id(), packet_buffer).await
+        }
+    }
+
     async fn receive(&mut self, service_hash: u32, token: u32, method_id: u32, _packet_buffer: Bytes) -> io::Result<()> {
         let (stored_service_hash, stored_method_id) = match self.response_callbacks.lock().unwrap().remove(&token) {
             None => return Ok(()),
@@ -566,7 +639,7 @@ impl Session<'_> {
             .values()
             .map(|ga| EntityId {
                 low:  ga.id.into(),
-                high: 0x200000200576F57,
+                high: GAME_ACCOUNT_ENTITY_HIGH,
             })
             .collect();
 
@@ -576,7 +649,7 @@ impl Session<'_> {
             error_code: 0,
             account_id: Some(EntityId {
                 low:  account_info.id.into(),
-                high: 0x100000000000000,
+                high: BNET_ACCOUNT_ENTITY_HIGH,
             }),
             geoip_country: self.ip_country.get().cloned(),
             game_account_id,
@@ -601,16 +674,26 @@ impl SessionInner {
         permit: Option<OwnedSemaphorePermit>,
         addr: AddressOrName,
         tcp_conn: TcpStream,
+        max_frame_size: Option<usize>,
+        packet_log: Option<PacketLog>,
     ) -> AzResult<Self>
     where
         Self: std::marker::Sized,
     {
         let conn = ssl_ctx.accept(tcp_conn).await?;
         let (rd, wr) = tokio::io::split(conn);
-        Self::start(login_db, permit, addr, rd, wr).await
+        Self::start(login_db, permit, addr, rd, wr, max_frame_size, packet_log).await
     }
 
-    async fn start<R, W>(login_db: LoginDatabase, _permit: Option<OwnedSemaphorePermit>, name: AddressOrName, rd: R, wr: W) -> AzResult<Self>
+    async fn start<R, W>(
+        login_db: LoginDatabase,
+        _permit: Option<OwnedSemaphorePermit>,
+        name: AddressOrName,
+        rd: R,
+        wr: W,
+        max_frame_size: Option<usize>,
+        packet_log: Option<PacketLog>,
+    ) -> AzResult<Self>
     where
         R: AsyncRead + Unpin + Send + Sync + 'static,
         W: AsyncWrite + Unpin + Send + Sync + 'static,
@@ -628,7 +711,7 @@ impl SessionInner {
             }
         }
         // begin AsyncRead => ReadHandler routine
-        let socket = Socket::<AuthserverPacket>::new(&Handle::current(), name, rd, wr);
+        let socket = Socket::<AuthserverPacket>::new(&Handle::current(), name, rd, wr, max_frame_size, packet_log);
 
         let s = SessionInner {
             socket,
@@ -643,6 +726,7 @@ impl SessionInner {
             client_secret: OnceLock::new(),
             ip_country: OnceLock::new(),
             request_token: AtomicU32::new(0),
+            presence_subscriptions: Mutex::new(BTreeSet::new()),
         };
         debug!(target:"session", caller=%s.remote_name(), "Accepted connection");
         Ok(s)
@@ -707,33 +791,7 @@ impl BnetRpcService for Session<'_> {
     where
         M: prost::Message,
     {
-        let BnetServiceWrapper {
-            service_hash,
-            method_id,
-            token,
-            result,
-        } = request;
-        let request = result.expect("client request should always be set, check impl of `pre_send_store_client_request`");
-
-        let header = Header {
-            service_id: 0,
-            service_hash: Some(service_hash),
-            method_id: Some(method_id),
-            size: request.encoded_len().try_into().ok(),
-            token,
-            ..Default::default()
-        };
-        let header_size = u16::try_from(header.encoded_len())
-            .unwrap_or_else(|e| panic!("send header size error size convert. should never fail here; err={e}"))
-            .to_be_bytes();
-        let mut packet = Vec::with_capacity(header_size.len() + header.encoded_len() + request.encoded_len());
-        packet.write_all(&header_size)?;
-        header.encode(&mut packet)?;
-        request.encode(&mut packet)?;
-        // AsyncWrite
-        self.write(packet)?;
-
-        Ok(())
+        self.write_client_request(request, None)
     }
 
     async fn pre_send_store_client_request<M>(&self, service_hash: u32, method_id: u32, request: M) -> io::Result<BnetServiceWrapper<M>>
@@ -759,6 +817,10 @@ impl ChallengeListener for Session<'_> {
     const USE_ORIGINAL_HASH: bool = true;
 }
 
+impl ChannelListener for Session<'_> {
+    const USE_ORIGINAL_HASH: bool = true;
+}
+
 impl AccountService for Session<'_> {
     const USE_ORIGINAL_HASH: bool = true;
 
@@ -909,8 +971,44 @@ impl AuthenticationService for Session<'_> {
 //     const USE_ORIGINAL_HASH: bool = true;
 // }
 
+/// There are no channels for now, other than the ones of presence which are handled by [PresenceService]
 impl ChannelService for Session<'_> {
     const USE_ORIGINAL_HASH: bool = true;
+
+    async fn handle_srv_req_remove_member(&self, _request: RemoveMemberRequest) -> BnetRpcResult<NoData>
+    where
+        Self: Sync,
+    {
+        Err(BattlenetRpcErrorCode::ChannelNoChannel)
+    }
+
+    async fn handle_srv_req_send_message(&self, _request: SendMessageRequest) -> BnetRpcResult<NoData>
+    where
+        Self: Sync,
+    {
+        Err(BattlenetRpcErrorCode::ChannelNoChannel)
+    }
+
+    async fn handle_srv_req_update_channel_state(&self, _request: UpdateChannelStateRequest) -> BnetRpcResult<NoData>
+    where
+        Self: Sync,
+    {
+        Err(BattlenetRpcErrorCode::ChannelNoChannel)
+    }
+
+    async fn handle_srv_req_update_member_state(&self, _request: UpdateMemberStateRequest) -> BnetRpcResult<NoData>
+    where
+        Self: Sync,
+    {
+        Err(BattlenetRpcErrorCode::ChannelNoChannel)
+    }
+
+    async fn handle_srv_req_dissolve(&self, _request: DissolveRequest) -> BnetRpcResult<NoData>
+    where
+        Self: Sync,
+    {
+        Err(BattlenetRpcErrorCode::ChannelNoChannel)
+    }
 }
 
 impl ConnectionService for Session<'_> {
@@ -955,8 +1053,46 @@ impl ConnectionService for Session<'_> {
     }
 }
 
+/// Friends are not supported yet, so the friend list is always empty
 impl FriendsService for Session<'_> {
     const USE_ORIGINAL_HASH: bool = true;
+
+    async fn handle_srv_req_subscribe(&self, _request: friends::SubscribeRequest) -> BnetRpcResult<friends::SubscribeResponse>
+    where
+        Self: Sync,
+    {
+        if self.account_info.get().is_none() {
+            return Err(BattlenetRpcErrorCode::Denied);
+        }
+        Ok(friends::SubscribeResponse::default())
+    }
+
+    async fn handle_srv_req_unsubscribe(&self, _request: friends::UnsubscribeRequest) -> BnetRpcResult<NoData>
+    where
+        Self: Sync,
+    {
+        Ok(NoData {})
+    }
+
+    async fn handle_srv_req_view_friends(&self, _request: friends::ViewFriendsRequest) -> BnetRpcResult<friends::ViewFriendsResponse>
+    where
+        Self: Sync,
+    {
+        if self.account_info.get().is_none() {
+            return Err(BattlenetRpcErrorCode::Denied);
+        }
+        Ok(friends::ViewFriendsResponse::default())
+    }
+
+    async fn handle_srv_req_get_friend_list(&self, _request: friends::GetFriendListRequest) -> BnetRpcResult<friends::GetFriendListResponse>
+    where
+        Self: Sync,
+    {
+        if self.account_info.get().is_none() {
+            return Err(BattlenetRpcErrorCode::Denied);
+        }
+        Ok(friends::GetFriendListResponse::default())
+    }
 }
 
 impl GameUtilitiesService for Session<'_> {
@@ -1026,20 +1162,309 @@ impl GameUtilitiesService for Session<'_> {
     }
 }
 
+/// Only the presence of the account itself and its game accounts is known, as there are no friends yet
 impl PresenceService for Session<'_> {
     const USE_ORIGINAL_HASH: bool = true;
+
+    async fn handle_srv_req_subscribe(&self, request: presence::SubscribeRequest) -> BnetRpcResult<NoData>
+    where
+        Self: Sync,
+    {
+        let entity = &request.entity_id;
+        if !self.owns_presence_entity(entity)? {
+            return Err(BattlenetRpcErrorCode::Denied);
+        }
+        if !self.presence_subscriptions.lock().unwrap().insert((entity.high, entity.low)) {
+            return Err(BattlenetRpcErrorCode::PresenceAlreadySubscribed);
+        }
+        // The current state is pushed as the subscriber joining the presence channel of the entity
+        // TODO: Implement me: Push later changes of the state, e.g. game accounts entering the world, through
+        // ChannelListener::OnUpdateChannelState
+        let fields = self.presence_fields(entity, &request.key).await?;
+        let notification = PresenceJoinNotification {
+            channel_state: PresenceChannelState {
+                presence: Some(presence::ChannelState {
+                    entity_id:       Some(entity.clone()),
+                    field_operation: fields.into_iter().map(|field| FieldOperation { field, operation: None }).collect(),
+                    healing:         None,
+                }),
+            },
+        };
+        // OnJoin
+        self.notify_listener(ChannelListener::service_hash(self), 1, request.object_id, notification)
+            .await
+            .map_err(map_err_to_internal)?;
+        Ok(NoData {})
+    }
+
+    async fn handle_srv_req_unsubscribe(&self, request: presence::UnsubscribeRequest) -> BnetRpcResult<NoData>
+    where
+        Self: Sync,
+    {
+        let entity = &request.entity_id;
+        self.presence_subscriptions.lock().unwrap().remove(&(entity.high, entity.low));
+        Ok(NoData {})
+    }
+
+    async fn handle_srv_req_query(&self, request: presence::QueryRequest) -> BnetRpcResult<presence::QueryResponse>
+    where
+        Self: Sync,
+    {
+        let entity = &request.entity_id;
+        if !self.owns_presence_entity(entity)? {
+            return Err(BattlenetRpcErrorCode::Denied);
+        }
+        Ok(presence::QueryResponse {
+            field: self.presence_fields(entity, &request.key).await?,
+        })
+    }
+}
+
+/// channel.v1.JoinNotification with only the presence.v1.ChannelState extension of its channel state set, as prost
+/// does not generate extensions
+#[derive(Clone, PartialEq, Message)]
+struct PresenceJoinNotification {
+    #[prost(message, required, tag = "3")]
+    channel_state: PresenceChannelState,
+}
+
+/// channel.v1.ChannelState, extended by presence.v1.ChannelState
+#[derive(Clone, PartialEq, Message)]
+struct PresenceChannelState {
+    #[prost(message, optional, tag = "101")]
+    presence: Option<presence::ChannelState>,
 }
 
 impl ReportService for Session<'_> {
     const USE_ORIGINAL_HASH: bool = true;
+
+    async fn handle_srv_req_send_report(&self, request: SendReportRequest) -> BnetRpcResult<NoData>
+    where
+        Self: Sync,
+    {
+        let Some(account_info) = self.account_info.get() else {
+            return Err(BattlenetRpcErrorCode::Denied);
+        };
+        let report = request.report;
+        let game_account_id = report
+            .reporting_game_account
+            .as_ref()
+            .and_then(|e| u32::try_from(e.low).ok())
+            .filter(|id| account_info.game_accounts.contains_key(id));
+        let attributes = format_attributes(&report.attribute);
+        self.save_report(game_account_id, None, &report.report_type, report.report_qos, &attributes)
+            .await?;
+        Ok(NoData {})
+    }
+
+    async fn handle_srv_req_submit_report(&self, request: SubmitReportRequest) -> BnetRpcResult<NoData>
+    where
+        Self: Sync,
+    {
+        let Some(account_info) = self.account_info.get() else {
+            return Err(BattlenetRpcErrorCode::Denied);
+        };
+        // Reports can only be filed on behalf of one of our own game accounts
+        let game_account_id = request.agent_id.map(|a| a.id);
+        if game_account_id.is_some_and(|id| !account_info.game_accounts.contains_key(&id)) {
+            return Err(BattlenetRpcErrorCode::Denied);
+        }
+        let Some(report_type) = request.report_type else {
+            return Err(BattlenetRpcErrorCode::RpcMalformedRequest);
+        };
+        let (typ, target, details) = match &report_type.r#type {
+            None => ("Unknown".to_string(), None, String::new()),
+            Some(report_type::Type::CustomReport(r)) => (format!("Custom:{}", r.r#type()), None, format_attributes(&r.attribute)),
+            Some(report_type::Type::SpamReport(r)) => ("Spam".to_string(), r.target, String::new()),
+            Some(report_type::Type::HarassmentReport(r)) => ("Harassment".to_string(), r.target, r.text().to_string()),
+            Some(report_type::Type::RealLifeThreatReport(r)) => ("RealLifeThreat".to_string(), r.target, r.text().to_string()),
+            Some(report_type::Type::InappropriateBattleTagReport(r)) => ("InappropriateBattleTag".to_string(), r.target, r.battle_tag().to_string()),
+            Some(report_type::Type::HackingReport(r)) => ("Hacking".to_string(), r.target, String::new()),
+            Some(report_type::Type::BottingReport(r)) => ("Botting".to_string(), r.target, String::new()),
+        };
+        let attributes = match report_type.note() {
+            "" => details,
+            note => format!("note={note}\n{details}"),
+        };
+        self.save_report(game_account_id, target.map(|t| t.id), &typ, None, &attributes).await?;
+        Ok(NoData {})
+    }
 }
 
+/// Content handles are not hosted by us
 impl ResourcesService for Session<'_> {
     const USE_ORIGINAL_HASH: bool = true;
+
+    async fn handle_srv_req_get_content_handle(&self, request: ContentHandleRequest) -> BnetRpcResult<ContentHandle>
+    where
+        Self: Sync,
+    {
+        debug!(target:"session::rpc", client=self.caller_info(), program=request.program, stream=request.stream, "client requested unknown content handle");
+        Err(BattlenetRpcErrorCode::NotExists)
+    }
 }
 
+/// Neither recent players nor blocked players are tracked yet, so both lists are always empty
 impl UserManagerService for Session<'_> {
     const USE_ORIGINAL_HASH: bool = true;
+
+    async fn handle_srv_req_subscribe(&self, _request: user_manager::SubscribeRequest) -> BnetRpcResult<user_manager::SubscribeResponse>
+    where
+        Self: Sync,
+    {
+        if self.account_info.get().is_none() {
+            return Err(BattlenetRpcErrorCode::Denied);
+        }
+        Ok(user_manager::SubscribeResponse::default())
+    }
+
+    async fn handle_srv_req_unsubscribe(&self, _request: user_manager::UnsubscribeRequest) -> BnetRpcResult<NoData>
+    where
+        Self: Sync,
+    {
+        Ok(NoData {})
+    }
+}
+
+impl Session<'_> {
+    /// Sends the notification to the listener object that the client subscribed with, i.e. the `object_id` of its
+    /// subscribe request
+    async fn notify_listener<M: Message>(&self, service_hash: u32, method_id: u32, object_id: u64, notification: M) -> io::Result<()> {
+        let request = self.pre_send_store_client_request(service_hash, method_id, notification).await?;
+        self.write_client_request(request, Some(object_id))
+    }
+
+    fn write_client_request<M: Message>(&self, request: BnetServiceWrapper<M>, object_id: Option<u64>) -> io::Result<()> {
+        let BnetServiceWrapper {
+            service_hash,
+            method_id,
+            token,
+            result,
+        } = request;
+        let request = result.expect("client request should always be set, check impl of `pre_send_store_client_request`");
+
+        let header = Header {
+            service_id: 0,
+            service_hash: Some(service_hash),
+            method_id: Some(method_id),
+            size: request.encoded_len().try_into().ok(),
+            token,
+            object_id,
+            ..Default::default()
+        };
+        let header_size = u16::try_from(header.encoded_len())
+            .unwrap_or_else(|e| panic!("send header size error size convert. should never fail here; err={e}"))
+            .to_be_bytes();
+        let mut packet = Vec::with_capacity(header_size.len() + header.encoded_len() + request.encoded_len());
+        packet.write_all(&header_size)?;
+        header.encode(&mut packet)?;
+        request.encode(&mut packet)?;
+        // AsyncWrite
+        self.write(packet)?;
+
+        Ok(())
+    }
+
+    /// Whether the entity is the account of this session or one of its game accounts, Denied if not logged in yet
+    fn owns_presence_entity(&self, entity: &EntityId) -> BnetRpcResult<bool> {
+        let Some(account_info) = self.account_info.get() else {
+            return Err(BattlenetRpcErrorCode::Denied);
+        };
+        Ok(match entity.high {
+            BNET_ACCOUNT_ENTITY_HIGH => entity.low == u64::from(account_info.id),
+            GAME_ACCOUNT_ENTITY_HIGH => u32::try_from(entity.low).is_ok_and(|id| account_info.game_accounts.contains_key(&id)),
+            _ => false,
+        })
+    }
+
+    /// The presence fields of an entity owned by this session with the given keys, all of them if none are given.
+    /// Unknown keys are left out.
+    async fn presence_fields(&self, entity: &EntityId, keys: &[FieldKey]) -> BnetRpcResult<Vec<Field>> {
+        let group = if entity.high == BNET_ACCOUNT_ENTITY_HIGH {
+            PRESENCE_GROUP_ACCOUNT
+        } else {
+            PRESENCE_GROUP_GAME_ACCOUNT
+        };
+        let online_key = FieldKey {
+            program: PRESENCE_PROGRAM_BN,
+            group,
+            field: PRESENCE_FIELD_IS_ONLINE,
+            unique_id: None,
+        };
+        if !keys.is_empty()
+            && !keys
+                .iter()
+                .any(|k| (k.program, k.group, k.field) == (online_key.program, online_key.group, online_key.field))
+        {
+            return Ok(vec![]);
+        }
+        let is_online = if group == PRESENCE_GROUP_ACCOUNT {
+            // The account is connected to us
+            true
+        } else {
+            let account_id = self.account_info.get().map_or(0, |ai| ai.id);
+            LoginDatabase::sel_account_online_by_bnet::<_, (u8,)>(&**self.login_db, args_unwrap!(entity.low, account_id))
+                .await
+                .map_err(map_err_to_internal)?
+                .is_some_and(|(online,)| online != 0)
+        };
+        Ok(vec![Field {
+            key:   online_key,
+            value: Variant {
+                bool_value: Some(is_online),
+                ..Default::default()
+            },
+        }])
+    }
+
+    async fn save_report(
+        &self,
+        game_account_id: Option<u32>,
+        target_game_account_id: Option<u32>,
+        report_type: &str,
+        report_qos: Option<i32>,
+        attributes: &str,
+    ) -> BnetRpcResult<()> {
+        let account_id = self.account_info.get().map_or(0, |ai| ai.id);
+        LoginDatabase::ins_bnet_report(
+            &**self.login_db,
+            args_unwrap!(account_id, game_account_id, target_game_account_id, report_type, report_qos, attributes),
+        )
+        .await
+        .map_err(map_err_to_internal)?;
+        debug!(target:"session::rpc", client=self.caller_info(), report_type, "saved report");
+        Ok(())
+    }
+}
+
+/// Formats attributes as `name=value` lines, for storing them in a human readable way
+fn format_attributes(attributes: &[Attribute]) -> String {
+    attributes
+        .iter()
+        .map(|Attribute { name, value }| {
+            let value = if let Some(v) = value.bool_value {
+                v.to_string()
+            } else if let Some(v) = value.int_value {
+                v.to_string()
+            } else if let Some(v) = value.float_value {
+                v.to_string()
+            } else if let Some(v) = &value.string_value {
+                v.clone()
+            } else if let Some(v) = &value.fourcc_value {
+                v.clone()
+            } else if let Some(v) = value.uint_value {
+                v.to_string()
+            } else if let Some(v) = &value.entity_id_value {
+                format!("{:X}:{:X}", v.high, v.low)
+            } else if let Some(v) = value.blob_value.as_ref().or(value.message_value.as_ref()) {
+                hex_str!(v)
+            } else {
+                String::new()
+            };
+            format!("{name}={value}")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 #[derive(serde::Serialize, serde::Deserialize)]
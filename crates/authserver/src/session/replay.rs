@@ -0,0 +1,491 @@
+use std::time::Duration;
+
+use azothacore_server::{
+    game::accounts::{account_mgr::AccountMgr, battlenet_account_mgr::BattlenetAccountMgr},
+    shared::networking::packet_log::{replay_client, PacketLogEntry},
+};
+use azothacore_tests_utils::{random_alpanum, test_db_pool_auth, SHARED_TEST_DB_PERMITS};
+use bnet_rpc::bgs::protocol::{
+    account::v1::GameAccountHandle,
+    report::v1::{HarassmentReport, Report, ReportType},
+};
+use tokio::io::DuplexStream;
+
+use super::*;
+
+// bgs.protocol.channel.v1.ChannelListener
+const CHANNEL_LISTENER_HASH: u32 = 0xBF8C8094;
+// bgs.protocol.connection.v1.ConnectionService
+const CONNECTION_SERVICE_HASH: u32 = 0x65446991;
+// bgs.protocol.presence.v1.PresenceService
+const PRESENCE_SERVICE_HASH: u32 = 0xFA0796FF;
+// bgs.protocol.report.v1.ReportService
+const REPORT_SERVICE_HASH: u32 = 0x7CAF61C9;
+
+/// Starts a [SessionInner] over in-memory streams, returning the client end of the streams alongside it. If given,
+/// the session is logged in as `account_info`.
+async fn start_session(login_db: &LoginDatabase, account_info: Option<AccountInfo>) -> AzResult<(DuplexStream, SessionInner)> {
+    let (client, server) = tokio::io::duplex(64 * 1024);
+    let (rd, wr) = tokio::io::split(server);
+    let inner = SessionInner::start(login_db.clone(), None, AddressOrName::Name("replay".to_string()), rd, wr, None, None).await?;
+    if let Some(account_info) = account_info {
+        inner.account_info.set(account_info).unwrap();
+    }
+    Ok((client, inner))
+}
+
+/// Feeds the client frames of a capture into a new [Session] over in-memory streams, returning the frames that the
+/// session answered with. This stands in for [read_handler], so that [Session::dispatch] can be tested without a
+/// client.
+async fn replay_session(
+    login_db: LoginDatabase,
+    cfg: &AuthserverConfig,
+    realm_list: &RealmList,
+    account_info: Option<AccountInfo>,
+    entries: &[PacketLogEntry],
+) -> AzResult<Vec<Bytes>> {
+    let (client, mut inner) = start_session(&login_db, account_info).await?;
+
+    let client = replay_client::<AuthserverPacket, _>(client, entries, Duration::from_millis(500));
+    tokio::pin!(client);
+    loop {
+        tokio::select! {
+            answers = &mut client => return Ok(answers?),
+            _ = tokio::time::sleep(Duration::from_millis(10)) => {},
+        }
+        let packets = inner.receive(None)?;
+        let mut sess = Session {
+            cfg,
+            login_db: &login_db,
+            realm_list,
+            inner: &mut inner,
+        };
+        for packet in packets {
+            sess.handle_packet(packet).await?;
+        }
+    }
+}
+
+fn client_request(service_hash: u32, method_id: u32, token: u32, request: impl Message) -> PacketLogEntry {
+    let packet_buffer = Bytes::from(request.encode_to_vec());
+    let header = Header {
+        service_id: 0,
+        service_hash: Some(service_hash),
+        method_id: Some(method_id),
+        token,
+        size: Some(packet_buffer.len().try_into().unwrap()),
+        ..Default::default()
+    };
+    PacketLogEntry {
+        timestamp:   unix_now(),
+        socket:      "replay".to_string(),
+        direction:   Direction::ClientToServer,
+        description: String::new(),
+        frame:       AuthserverPacket { header, packet_buffer }.to_frame(),
+    }
+}
+
+fn decode_packet(frame: &Bytes) -> AuthserverPacket {
+    let mut buffer = MessageBuffer::default();
+    buffer.extend_from_slice(frame);
+    AuthserverPacket::decode_from_bytes(&mut buffer).unwrap()
+}
+
+fn decode_header(frame: &Bytes) -> Header {
+    decode_packet(frame).header
+}
+
+/// Appends an answer frame to `capture` for each entry in it, only the number of server frames in a capture matter
+/// for replaying it
+fn with_answers(mut capture: Vec<PacketLogEntry>) -> Vec<PacketLogEntry> {
+    let answers = capture
+        .iter()
+        .map(|e| PacketLogEntry {
+            direction: Direction::ServerToClient,
+            ..e.clone()
+        })
+        .collect::<Vec<_>>();
+    capture.extend(answers);
+    capture
+}
+
+/// Creates a Battle.net account with a single game account, returning the account info of a session logged in to it
+async fn create_account_info(login_db: &LoginDatabase) -> AccountInfo {
+    let email = format!("{}@example.com", random_alpanum(10));
+    BattlenetAccountMgr::create_battlenet_account(&**login_db, &email, "password", true)
+        .await
+        .unwrap();
+    let id = BattlenetAccountMgr::get_id(&**login_db, &email).await.unwrap().unwrap();
+    let game_account_name = format!("{id}#1");
+    let game_account_id = AccountMgr::get_id(&**login_db, &game_account_name).await.unwrap().unwrap();
+    let game_account = GameAccountInfo::load_result(DbGameAccountInfo {
+        id:                    game_account_id,
+        name:                  game_account_name,
+        unban_date:            None,
+        is_permanently_banned: None,
+        security_level:        None,
+    });
+    AccountInfo {
+        id,
+        login: email.to_ascii_uppercase(),
+        is_locked_to_ip: false,
+        lock_country: "00".to_string(),
+        last_ip: "127.0.0.1".to_string(),
+        login_ticket_expiry: 0,
+        is_banned: None,
+        is_permanently_banned: None,
+        game_accounts: BTreeMap::from([(game_account_id, game_account)]),
+    }
+}
+
+fn game_account_id(account_info: &AccountInfo) -> u32 {
+    *account_info.game_accounts.keys().next().unwrap()
+}
+
+fn game_account_handle(id: u32) -> GameAccountHandle {
+    GameAccountHandle {
+        id,
+        program: 0x576F57,
+        region: 1,
+    }
+}
+
+#[tokio::test]
+async fn it_replays_captured_connection_requests() {
+    let _p = SHARED_TEST_DB_PERMITS.acquire().await.unwrap();
+    let login_db = LoginDatabase(test_db_pool_auth(None).await);
+    let cfg = AuthserverConfig::default();
+    let realm_list = RealmList::new(Duration::from_secs(10));
+
+    let mut capture = vec![
+        // Connect
+        client_request(CONNECTION_SERVICE_HASH, 1, 0, ConnectRequest::default()),
+        // Unknown services are skipped without an answer
+        client_request(0xDEADBEEF, 1, 1, NoData {}),
+        // KeepAlive, which has no response
+        client_request(CONNECTION_SERVICE_HASH, 5, 2, NoData {}),
+        client_request(CONNECTION_SERVICE_HASH, 1, 3, ConnectRequest::default()),
+    ];
+    // Only the number of server frames in a capture matter for replaying it
+    let answer = PacketLogEntry {
+        direction: Direction::ServerToClient,
+        ..capture[0].clone()
+    };
+    capture.extend([answer.clone(), answer]);
+
+    let answers = replay_session(login_db, &cfg, &realm_list, None, &capture).await.unwrap();
+    let headers = answers.iter().map(decode_header).collect::<Vec<_>>();
+    assert_eq!(
+        headers.iter().map(|h| (h.service_id, h.token, h.status)).collect::<Vec<_>>(),
+        vec![(0xFE, 0, None), (0xFE, 3, None)]
+    );
+    assert!(answers[0].starts_with(&u16::try_from(headers[0].encoded_len()).unwrap().to_be_bytes()));
+}
+
+#[tokio::test]
+async fn it_saves_reports_of_own_game_accounts_only() {
+    let _p = SHARED_TEST_DB_PERMITS.acquire().await.unwrap();
+    let login_db = LoginDatabase(test_db_pool_auth(None).await);
+    let cfg = AuthserverConfig::default();
+    let realm_list = RealmList::new(Duration::from_secs(10));
+    let account_info = create_account_info(&login_db).await;
+    let account_id = account_info.id;
+    let own_game_account_id = game_account_id(&account_info);
+    let foreign_game_account_id = game_account_id(&create_account_info(&login_db).await);
+
+    let harassment = ReportType {
+        note:   Some("in chat".to_string()),
+        r#type: Some(report_type::Type::HarassmentReport(HarassmentReport {
+            target: Some(game_account_handle(foreign_game_account_id)),
+            text:   Some("rude".to_string()),
+        })),
+    };
+    let capture = with_answers(vec![
+        // SubmitReport
+        client_request(
+            REPORT_SERVICE_HASH,
+            2,
+            0,
+            SubmitReportRequest {
+                agent_id:    Some(game_account_handle(own_game_account_id)),
+                report_type: Some(harassment.clone()),
+            },
+        ),
+        // Reporting on behalf of someone else's game account
+        client_request(
+            REPORT_SERVICE_HASH,
+            2,
+            1,
+            SubmitReportRequest {
+                agent_id:    Some(game_account_handle(foreign_game_account_id)),
+                report_type: Some(harassment),
+            },
+        ),
+        // SendReport, where reporting game accounts that are not our own are dropped instead
+        client_request(
+            REPORT_SERVICE_HASH,
+            1,
+            2,
+            SendReportRequest {
+                report: Report {
+                    report_type: "Custom".to_string(),
+                    attribute: vec![Attribute {
+                        name:  "count".to_string(),
+                        value: Variant {
+                            int_value: Some(3),
+                            ..Default::default()
+                        },
+                    }],
+                    report_qos: Some(2),
+                    reporting_game_account: Some(EntityId {
+                        high: GAME_ACCOUNT_ENTITY_HIGH,
+                        low:  foreign_game_account_id.into(),
+                    }),
+                    ..Default::default()
+                },
+            },
+        ),
+    ]);
+
+    let answers = replay_session(login_db.clone(), &cfg, &realm_list, Some(account_info), &capture).await.unwrap();
+    assert_eq!(
+        answers.iter().map(decode_header).map(|h| (h.token, h.status)).collect::<Vec<_>>(),
+        vec![(0, None), (1, Some(BattlenetRpcErrorCode::Denied as u32)), (2, None)]
+    );
+
+    let reports = sqlx::query_as::<_, (Option<u32>, Option<u32>, String, Option<i32>, String)>(
+        "SELECT game_account_id, target_game_account_id, report_type, report_qos, attributes FROM battlenet_reports WHERE account_id = ? ORDER BY id",
+    )
+    .bind(account_id)
+    .fetch_all(&*login_db)
+    .await
+    .unwrap();
+    assert_eq!(
+        reports,
+        vec![
+            (
+                Some(own_game_account_id),
+                Some(foreign_game_account_id),
+                "Harassment".to_string(),
+                None,
+                "note=in chat\nrude".to_string()
+            ),
+            (None, None, "Custom".to_string(), Some(2), "count=3".to_string()),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn it_queries_presence_of_own_accounts_only() {
+    let _p = SHARED_TEST_DB_PERMITS.acquire().await.unwrap();
+    let login_db = LoginDatabase(test_db_pool_auth(None).await);
+    let cfg = AuthserverConfig::default();
+    let realm_list = RealmList::new(Duration::from_secs(10));
+    let account_info = create_account_info(&login_db).await;
+    let own_game_account_id = game_account_id(&account_info);
+    let foreign_game_account_id = game_account_id(&create_account_info(&login_db).await);
+    sqlx::query("UPDATE account SET online = 1 WHERE id = ?")
+        .bind(own_game_account_id)
+        .execute(&*login_db)
+        .await
+        .unwrap();
+
+    let query = |token, high, low: u32| {
+        client_request(
+            PRESENCE_SERVICE_HASH,
+            4,
+            token,
+            presence::QueryRequest {
+                entity_id: EntityId { high, low: low.into() },
+                ..Default::default()
+            },
+        )
+    };
+    let capture = with_answers(vec![
+        query(0, BNET_ACCOUNT_ENTITY_HIGH, account_info.id),
+        query(1, GAME_ACCOUNT_ENTITY_HIGH, own_game_account_id),
+        query(2, GAME_ACCOUNT_ENTITY_HIGH, foreign_game_account_id),
+    ]);
+
+    let answers = replay_session(login_db, &cfg, &realm_list, Some(account_info), &capture).await.unwrap();
+    let packets = answers.iter().map(decode_packet).collect::<Vec<_>>();
+    assert_eq!(
+        packets.iter().map(|p| (p.header.token, p.header.status)).collect::<Vec<_>>(),
+        vec![(0, None), (1, None), (2, Some(BattlenetRpcErrorCode::Denied as u32))]
+    );
+    for (packet, group) in packets.iter().zip([PRESENCE_GROUP_ACCOUNT, PRESENCE_GROUP_GAME_ACCOUNT]) {
+        let response = presence::QueryResponse::decode(packet.packet_buffer.clone()).unwrap();
+        assert_eq!(response.field.len(), 1);
+        assert_eq!((response.field[0].key.program, response.field[0].key.group), (PRESENCE_PROGRAM_BN, group));
+        assert_eq!(response.field[0].value.bool_value, Some(true));
+    }
+}
+
+#[tokio::test]
+async fn it_pushes_presence_on_subscribe() {
+    let _p = SHARED_TEST_DB_PERMITS.acquire().await.unwrap();
+    let login_db = LoginDatabase(test_db_pool_auth(None).await);
+    let cfg = AuthserverConfig::default();
+    let realm_list = RealmList::new(Duration::from_secs(10));
+    let account_info = create_account_info(&login_db).await;
+    let own_game_account_id = game_account_id(&account_info);
+    let foreign_game_account_id = game_account_id(&create_account_info(&login_db).await);
+    sqlx::query("UPDATE account SET online = 1 WHERE id = ?")
+        .bind(own_game_account_id)
+        .execute(&*login_db)
+        .await
+        .unwrap();
+
+    let subscribe = |token, low: u32| {
+        client_request(
+            PRESENCE_SERVICE_HASH,
+            1,
+            token,
+            presence::SubscribeRequest {
+                entity_id: EntityId {
+                    high: GAME_ACCOUNT_ENTITY_HIGH,
+                    low:  low.into(),
+                },
+                object_id: 7,
+                ..Default::default()
+            },
+        )
+    };
+    let mut capture = with_answers(vec![
+        subscribe(0, own_game_account_id),
+        subscribe(1, own_game_account_id),
+        subscribe(2, foreign_game_account_id),
+    ]);
+    // The OnJoin notification of the first subscription
+    capture.push(capture.last().unwrap().clone());
+
+    let answers = replay_session(login_db, &cfg, &realm_list, Some(account_info), &capture).await.unwrap();
+    let packets = answers.iter().map(decode_packet).collect::<Vec<_>>();
+    assert_eq!(
+        packets[1..]
+            .iter()
+            .map(|p| (p.header.service_id, p.header.token, p.header.status))
+            .collect::<Vec<_>>(),
+        vec![
+            (0xFE, 0, None),
+            (0xFE, 1, Some(BattlenetRpcErrorCode::PresenceAlreadySubscribed as u32)),
+            (0xFE, 2, Some(BattlenetRpcErrorCode::Denied as u32)),
+        ]
+    );
+    let header = &packets[0].header;
+    assert_eq!(
+        (header.service_id, header.service_hash, header.method_id, header.object_id),
+        (0, Some(CHANNEL_LISTENER_HASH), Some(1), Some(7))
+    );
+    let notification = PresenceJoinNotification::decode(packets[0].packet_buffer.clone()).unwrap();
+    let state = notification.channel_state.presence.unwrap();
+    assert_eq!(
+        state.entity_id,
+        Some(EntityId {
+            high: GAME_ACCOUNT_ENTITY_HIGH,
+            low:  own_game_account_id.into(),
+        })
+    );
+    assert_eq!(state.field_operation.len(), 1);
+    let field = &state.field_operation[0].field;
+    assert_eq!(
+        (field.key.program, field.key.group, field.key.field),
+        (PRESENCE_PROGRAM_BN, PRESENCE_GROUP_GAME_ACCOUNT, PRESENCE_FIELD_IS_ONLINE)
+    );
+    assert_eq!(field.value.bool_value, Some(true));
+}
+
+#[tokio::test]
+async fn it_owns_only_its_own_presence_entities() {
+    let _p = SHARED_TEST_DB_PERMITS.acquire().await.unwrap();
+    let login_db = LoginDatabase(test_db_pool_auth(None).await);
+    let cfg = AuthserverConfig::default();
+    let realm_list = RealmList::new(Duration::from_secs(10));
+    let account_info = create_account_info(&login_db).await;
+    let account_id = u64::from(account_info.id);
+    let own_game_account_id = u64::from(game_account_id(&account_info));
+
+    let (_client, mut inner) = start_session(&login_db, None).await.unwrap();
+    let entity = |high, low| EntityId { high, low };
+    {
+        let sess = Session {
+            cfg:        &cfg,
+            login_db:   &login_db,
+            realm_list: &realm_list,
+            inner:      &mut inner,
+        };
+        assert_eq!(
+            sess.owns_presence_entity(&entity(BNET_ACCOUNT_ENTITY_HIGH, account_id)),
+            Err(BattlenetRpcErrorCode::Denied),
+            "not logged in yet"
+        );
+    }
+    inner.account_info.set(account_info).unwrap();
+    let sess = Session {
+        cfg:        &cfg,
+        login_db:   &login_db,
+        realm_list: &realm_list,
+        inner:      &mut inner,
+    };
+    for (e, owned) in [
+        (entity(BNET_ACCOUNT_ENTITY_HIGH, account_id), true),
+        (entity(BNET_ACCOUNT_ENTITY_HIGH, account_id + 1), false),
+        (entity(GAME_ACCOUNT_ENTITY_HIGH, own_game_account_id), true),
+        (entity(GAME_ACCOUNT_ENTITY_HIGH, own_game_account_id + 1), false),
+        (entity(GAME_ACCOUNT_ENTITY_HIGH, u64::MAX), false),
+        (entity(0, own_game_account_id), false),
+    ] {
+        assert_eq!(sess.owns_presence_entity(&e), Ok(owned), "{e:?}");
+    }
+}
+
+#[test]
+fn it_formats_attributes_as_lines() {
+    let attribute = |name: &str, value| Attribute { name: name.to_string(), value };
+    let attributes = [
+        attribute(
+            "bool",
+            Variant {
+                bool_value: Some(true),
+                ..Default::default()
+            },
+        ),
+        attribute(
+            "int",
+            Variant {
+                int_value: Some(-3),
+                ..Default::default()
+            },
+        ),
+        attribute(
+            "string",
+            Variant {
+                string_value: Some("text".to_string()),
+                ..Default::default()
+            },
+        ),
+        attribute(
+            "entity",
+            Variant {
+                entity_id_value: Some(EntityId {
+                    high: GAME_ACCOUNT_ENTITY_HIGH,
+                    low:  0x2A,
+                }),
+                ..Default::default()
+            },
+        ),
+        attribute(
+            "blob",
+            Variant {
+                blob_value: Some(Bytes::from_static(&[0xDE, 0xAD])),
+                ..Default::default()
+            },
+        ),
+        attribute("empty", Variant::default()),
+    ];
+    assert_eq!(
+        format_attributes(&attributes),
+        "bool=true\nint=-3\nstring=text\nentity=200000200576F57:2A\nblob=DEAD\nempty="
+    );
+    assert_eq!(format_attributes(&[]), "");
+}
@@ -1,4 +1,4 @@
-use std::{io, path::Path};
+use std::io;
 
 use bevy::ecs::system::SystemParam;
 use flagset::FlagSet;
@@ -50,13 +50,13 @@ pub const VMAP_INVALID_HEIGHT_VALUE: f32 = -200000.0;
 /// This is the minimum interface to the VMapMgr.
 /// Its the equivalent to IVMapManager / IVMapMgr
 pub trait VMapMgr: SystemParam {
-    /// loadMap in TC / ACore
-    fn load_map_tile(&self, p_base_path: &Path, p_map_id: u32, x: u16, y: u16) -> VmapFactoryLoadResult<()>;
-    /// existsMap in TC / ACore
-    fn exists_map_tile(&self, p_base_path: &Path, p_map_id: u32, x: u16, y: u16) -> VmapLoadResult<()>;
+    /// loadMap in TC / ACore, the vmaps are loaded from the configured vmaps directory
+    fn load_map_tile(&mut self, p_map_id: u32, x: u16, y: u16) -> VmapFactoryLoadResult<()>;
+    /// existsMap in TC / ACore, the vmaps are looked up in the configured vmaps directory
+    fn exists_map_tile(&self, p_map_id: u32, x: u16, y: u16) -> VmapLoadResult<()>;
     /// unloadMap in TC / ACore
-    fn unload_map_tile(&self, p_map_id: u32, x: u16, y: u16);
-    fn unload_map(&self, p_map_id: u32);
+    fn unload_map_tile(&mut self, p_map_id: u32, x: u16, y: u16);
+    fn unload_map(&mut self, p_map_id: u32);
 
     #[expect(clippy::too_many_arguments)]
     // TODO: refactor this to return instead of taking in multiple mutable parmas?
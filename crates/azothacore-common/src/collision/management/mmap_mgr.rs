@@ -1,2 +1,558 @@
-// const MAP_FILE_NAME_FORMAT: &str = "%smmaps/%04i.mmap";
-// const TILE_FILE_NAME_FORMAT: &str = "%smmaps/%04i%02i%02i.mmtile";
+use std::{collections::HashMap, path::Path};
+
+use bevy::prelude::Resource;
+use flagset::FlagSet;
+use nalgebra::Vector3;
+use tracing::{debug, error};
+
+use crate::{
+    az_error,
+    collision::maps::map_defines::{MmapNavTerrainFlag, MmapTileFile},
+    recastnavigation_handles::{
+        dtPolyRef,
+        dtTileRef,
+        DetourNavMesh,
+        DetourNavMeshParams,
+        DetourNavMeshQuery,
+        DetourQueryFilter,
+        DetourResult,
+        DetourStatus,
+        DetourStatusDetails,
+        DetourStraightPathPoint,
+    },
+    utils::{bincode_deserialise, buffered_file_open},
+    AzResult,
+};
+
+/// Max number of search nodes for each of the navmesh queries, the same as in TC / AC
+const MAX_NAV_MESH_QUERY_NODES: i32 = 1024;
+/// MAX_PATH_LENGTH in TC / AC
+pub const MAX_PATH_LENGTH: usize = 74;
+/// MAX_POINT_PATH_LENGTH in TC / AC
+pub const MAX_POINT_PATH_LENGTH: usize = 74;
+/// SMOOTH_PATH_STEP_SIZE in TC / AC
+pub const SMOOTH_PATH_STEP_SIZE: f32 = 4.0;
+/// SMOOTH_PATH_SLOP in TC / AC
+pub const SMOOTH_PATH_SLOP: f32 = 0.3;
+/// Bounds of the polygon search area around a position, the `extents` in PathGenerator::BuildPolyPath in TC / AC
+const NEAREST_POLY_EXTENTS: [f32; 3] = [3.0, 5.0, 3.0];
+const MAX_STEER_POINTS: usize = 3;
+const MAX_VISIT_POLY: usize = 16;
+
+/// Creates the navmesh query filter from the terrain types that can be moved on, PathGenerator::CreateFilter in TC / AC.
+///
+/// Units that are forcefully moved into terrain they cannot normally move in should have the terrain at
+/// their position included, see [`MmapNavTerrainFlag::from_liquid_type_flags`] and PathGenerator::UpdateFilter in TC / AC.
+pub fn nav_terrain_query_filter(include: FlagSet<MmapNavTerrainFlag>, exclude: FlagSet<MmapNavTerrainFlag>) -> DetourQueryFilter {
+    let mut filter = DetourQueryFilter::default();
+    filter.set_include_flags(include.bits());
+    filter.set_exclude_flags(exclude.bits());
+    filter
+}
+
+/// MMapData in TC / AC
+struct MMapData {
+    /// navMeshQueries in TC / AC, keyed by instance ID.
+    ///
+    /// Declared before the navmesh so that the queries are dropped before the navmesh they were initialised with.
+    nav_mesh_queries: HashMap<u32, DetourNavMeshQuery>,
+    nav_mesh:         DetourNavMesh,
+    /// loadedTileRefs in TC / AC, keyed by the packed tile ID
+    loaded_tile_refs: HashMap<u32, dtTileRef>,
+}
+
+/// MMapManager in TC / AC
+#[derive(Resource, Default)]
+pub struct MMapMgr {
+    loaded_mmaps: HashMap<u32, MMapData>,
+    loaded_tiles: u32,
+}
+
+impl MMapMgr {
+    /// MMapManager::packTileID in TC / AC
+    fn pack_tile_id(x: u16, y: u16) -> u32 {
+        u32::from(x) << 16 | u32::from(y)
+    }
+
+    /// MMapManager::loadMapData in TC / AC
+    fn load_map_data<P: AsRef<Path>>(&mut self, mmaps_dir: P, map_id: u32) -> AzResult<&mut MMapData> {
+        // we already have this map loaded?
+        if self.loaded_mmaps.contains_key(&map_id) {
+            return Ok(self.loaded_mmaps.get_mut(&map_id).unwrap());
+        }
+        // load and init dtNavMesh - read parameters from file
+        let file_name = MmapTileFile::mmap_filepath(mmaps_dir, map_id);
+        let mut file = buffered_file_open(&file_name).map_err(|e| az_error!("MMAP:loadMapData: Could not open mmap file '{}': {e}", file_name.display()))?;
+        let params: DetourNavMeshParams =
+            bincode_deserialise(&mut file).map_err(|e| az_error!("MMAP:loadMapData: Could not read params from file '{}': {e}", file_name.display()))?;
+        let (nav_mesh, _) = DetourNavMesh::init(&params).map_err(|e| {
+            az_error!(
+                "MMAP:loadMapData: Failed to initialize dtNavMesh for mmap {map_id:04} from file {}: {e}",
+                file_name.display()
+            )
+        })?;
+        debug!(target:"maps", "MMAP:loadMapData: Loaded {map_id:04}.mmap");
+        Ok(self.loaded_mmaps.entry(map_id).or_insert(MMapData {
+            nav_mesh_queries: HashMap::new(),
+            nav_mesh,
+            loaded_tile_refs: HashMap::new(),
+        }))
+    }
+
+    /// MMapManager::loadMap in TC / AC, loads the navmesh tile into the map's navmesh, loading the navmesh first if needed.
+    pub fn load_map_tile<P: AsRef<Path>>(&mut self, mmaps_dir: P, map_id: u32, x: u16, y: u16) -> AzResult<()> {
+        let mmaps_dir = mmaps_dir.as_ref();
+        let mmap = self.load_map_data(mmaps_dir, map_id)?;
+        // check if we already have this tile loaded
+        let packed_grid_pos = Self::pack_tile_id(x, y);
+        if mmap.loaded_tile_refs.contains_key(&packed_grid_pos) {
+            debug!(target:"maps", "MMAP:loadMap: Asked to load already loaded mmtile {map_id:04}[{x:02}, {y:02}]");
+            return Ok(());
+        }
+        // load this tile :: mmaps/MMMMXXYY.mmtile, the generator's tile Y is the grid X
+        let tile = MmapTileFile::read_from_mmtile(mmaps_dir, map_id, x, y)
+            .map_err(|e| az_error!("MMAP:loadMap: Could not load mmtile {map_id:04}[{x:02}, {y:02}]: {e}"))?;
+        // memory allocated for data is now managed by detour, and will be deallocated when the tile is removed
+        let (tile_ref, _) = mmap
+            .nav_mesh
+            .add_tile(tile.data.bytes)
+            .map_err(|e| az_error!("MMAP:loadMap: Could not load mmtile {map_id:04}[{x:02}, {y:02}] into navmesh: {e}"))?;
+        mmap.loaded_tile_refs.insert(packed_grid_pos, tile_ref);
+        self.loaded_tiles += 1;
+        debug!(target:"maps", "MMAP:loadMap: Loaded mmtile {map_id:04}[{x:02}, {y:02}]");
+        Ok(())
+    }
+
+    /// MMapManager::loadMapInstance in TC / AC, creates the navmesh query for the given instance of the map.
+    pub fn load_map_instance<P: AsRef<Path>>(&mut self, mmaps_dir: P, map_id: u32, instance_id: u32) -> AzResult<()> {
+        let mmap = self.load_map_data(mmaps_dir, map_id)?;
+        if mmap.nav_mesh_queries.contains_key(&instance_id) {
+            return Ok(());
+        }
+        // allocate mesh query
+        let (query, _) = DetourNavMeshQuery::init(&mmap.nav_mesh, MAX_NAV_MESH_QUERY_NODES)
+            .map_err(|e| az_error!("MMAP:GetNavMeshQuery: Failed to initialize dtNavMeshQuery for mapId {map_id:04} instanceId {instance_id}: {e}"))?;
+        debug!(target:"maps", "MMAP:GetNavMeshQuery: created dtNavMeshQuery for mapId {map_id:04} instanceId {instance_id}");
+        mmap.nav_mesh_queries.insert(instance_id, query);
+        Ok(())
+    }
+
+    /// MMapManager::unloadMap(mapId, x, y) in TC / AC, returns false if the tile was not loaded.
+    pub fn unload_map_tile(&mut self, map_id: u32, x: u16, y: u16) -> bool {
+        // check if we have this map loaded
+        let Some(mmap) = self.loaded_mmaps.get_mut(&map_id) else {
+            debug!(target:"maps", "MMAP:unloadMap: Asked to unload not loaded navmesh map. {map_id:04}[{x:02}, {y:02}]");
+            return false;
+        };
+        // check if we have this tile loaded
+        let packed_grid_pos = Self::pack_tile_id(x, y);
+        let Some(tile_ref) = mmap.loaded_tile_refs.get(&packed_grid_pos).copied() else {
+            debug!(target:"maps", "MMAP:unloadMap: Asked to unload not loaded navmesh tile. {map_id:04}[{x:02}, {y:02}]");
+            return false;
+        };
+        // unload, and mark as non loaded
+        if let Err(e) = mmap.nav_mesh.remove_tile(tile_ref) {
+            // this is technically a memory leak
+            // if the grid is later reloaded, dtNavMesh::addTile will return error but no extra memory is used
+            // TC / AC asserts out here, we keep the tile marked as loaded instead
+            error!(target:"maps", "MMAP:unloadMap: Could not unload mmtile {map_id:04}[{x:02}, {y:02}] from navmesh: {e}");
+            return false;
+        }
+        mmap.loaded_tile_refs.remove(&packed_grid_pos);
+        self.loaded_tiles -= 1;
+        debug!(target:"maps", "MMAP:unloadMap: Unloaded mmtile {map_id:04}[{x:02}, {y:02}]");
+        true
+    }
+
+    /// MMapManager::unloadMap(mapId) in TC / AC, unloads all tiles and queries of the map together with its navmesh.
+    pub fn unload_map(&mut self, map_id: u32) -> bool {
+        let Some(mut mmap) = self.loaded_mmaps.remove(&map_id) else {
+            debug!(target:"maps", "MMAP:unloadMap: Asked to unload not loaded navmesh map {map_id:04}");
+            return false;
+        };
+        // unload all tiles from given map
+        for (packed_grid_pos, tile_ref) in mmap.loaded_tile_refs.drain() {
+            let (x, y) = (packed_grid_pos >> 16, packed_grid_pos & 0x0000FFFF);
+            if let Err(e) = mmap.nav_mesh.remove_tile(tile_ref) {
+                error!(target:"maps", "MMAP:unloadMap: Could not unload mmtile {map_id:04}[{x:02}, {y:02}] from navmesh: {e}");
+            } else {
+                self.loaded_tiles -= 1;
+                debug!(target:"maps", "MMAP:unloadMap: Unloaded mmtile {map_id:04}[{x:02}, {y:02}]");
+            }
+        }
+        debug!(target:"maps", "MMAP:unloadMap: Unloaded {map_id:04}.mmap");
+        true
+    }
+
+    /// MMapManager::unloadMapInstance in TC / AC
+    pub fn unload_map_instance(&mut self, map_id: u32, instance_id: u32) -> bool {
+        let Some(mmap) = self.loaded_mmaps.get_mut(&map_id) else {
+            debug!(target:"maps", "MMAP:unloadMapInstance: Asked to unload not loaded navmesh map {map_id:04}");
+            return false;
+        };
+        if mmap.nav_mesh_queries.remove(&instance_id).is_none() {
+            debug!(target:"maps", "MMAP:unloadMapInstance: Asked to unload not loaded dtNavMeshQuery mapId {map_id:04} instanceId {instance_id}");
+            return false;
+        }
+        debug!(target:"maps", "MMAP:unloadMapInstance: Unloaded mapId {map_id:04} instanceId {instance_id}");
+        true
+    }
+
+    /// MMapManager::GetNavMesh in TC / AC
+    pub fn get_nav_mesh(&self, map_id: u32) -> Option<&DetourNavMesh> {
+        self.loaded_mmaps.get(&map_id).map(|m| &m.nav_mesh)
+    }
+
+    /// MMapManager::GetNavMeshQuery in TC / AC, the query has to be created first with [`MMapMgr::load_map_instance`].
+    pub fn get_nav_mesh_query(&mut self, map_id: u32, instance_id: u32) -> Option<MMapNavMeshQuery<'_>> {
+        let mmap = self.loaded_mmaps.get_mut(&map_id)?;
+        let query = mmap.nav_mesh_queries.get_mut(&instance_id)?;
+        Some(MMapNavMeshQuery {
+            nav_mesh: &mmap.nav_mesh,
+            query,
+        })
+    }
+
+    /// MMapManager::getLoadedTilesCount in TC / AC
+    pub fn loaded_tiles_count(&self) -> u32 {
+        self.loaded_tiles
+    }
+
+    /// MMapManager::getLoadedMapsCount in TC / AC
+    pub fn loaded_maps_count(&self) -> usize {
+        self.loaded_mmaps.len()
+    }
+}
+
+/// Detour uses a y-up coordinate system, i.e. (y, z, x) in world coordinates
+fn to_detour_pos(pos: &Vector3<f32>) -> [f32; 3] {
+    [pos.y, pos.z, pos.x]
+}
+
+fn from_detour_pos(pos: &[f32; 3]) -> Vector3<f32> {
+    Vector3::new(pos[2], pos[0], pos[1])
+}
+
+/// PathGenerator::InRangeYZX in TC / AC, the positions are in Detour coordinates
+fn in_range_yzx(v1: &[f32; 3], v2: &[f32; 3], r: f32, h: f32) -> bool {
+    let dx = v2[0] - v1[0];
+    let dy = v2[1] - v1[1]; // elevation
+    let dz = v2[2] - v1[2];
+    (dx * dx + dz * dz) < r * r && dy.abs() < h
+}
+
+/// PathGenerator::FixupCorridor in TC / AC, replaces the start of the polygon corridor with the polygons
+/// visited while moving along the surface, up until the furthest polygon they have in common.
+fn fixup_corridor(path: &[dtPolyRef], visited: &[dtPolyRef], max_path: usize) -> Vec<dtPolyRef> {
+    // Find furthest common polygon.
+    let Some((furthest_path, furthest_visited)) = path
+        .iter()
+        .enumerate()
+        .rev()
+        .find_map(|(i, p)| visited.iter().position(|v| v == p).map(|j| (i, j)))
+    else {
+        // If no intersection found just return current path.
+        return path.to_vec();
+    };
+    // Concatenate paths, the visited polygons (in reverse) followed by the rest of the path after the common polygon
+    let mut res = visited[furthest_visited..].iter().rev().copied().collect::<Vec<_>>();
+    res.extend_from_slice(&path[(furthest_path + 1).min(path.len())..]);
+    res.truncate(max_path);
+    res
+}
+
+/// Path finding on the navmesh of a map instance, obtained through [`MMapMgr::get_nav_mesh_query`].
+///
+/// This is the Detour side of PathGenerator in TC / AC, all positions given and returned are in world coordinates.
+pub struct MMapNavMeshQuery<'a> {
+    nav_mesh: &'a DetourNavMesh,
+    query:    &'a mut DetourNavMeshQuery,
+}
+
+impl MMapNavMeshQuery<'_> {
+    /// dtNavMeshQuery::findNearestPoly in Detour, searching within the same bounds as PathGenerator::BuildPolyPath in TC / AC.
+    ///
+    /// Returns the polygon and the nearest point on it, or `None` if there are no polygons near the position.
+    pub fn find_nearest_poly(&mut self, pos: &Vector3<f32>, filter: &DetourQueryFilter) -> Option<(dtPolyRef, Vector3<f32>)> {
+        self.find_nearest_poly_detour(&to_detour_pos(pos), filter)
+            .map(|(poly_ref, nearest)| (poly_ref, from_detour_pos(&nearest)))
+    }
+
+    fn find_nearest_poly_detour(&mut self, pos: &[f32; 3], filter: &DetourQueryFilter) -> Option<(dtPolyRef, [f32; 3])> {
+        match self.query.find_nearest_poly(pos, &NEAREST_POLY_EXTENTS, filter) {
+            Ok(((poly_ref, nearest), _)) if poly_ref != 0 => Some((poly_ref, nearest)),
+            _ => None,
+        }
+    }
+
+    /// Finds the polygon corridor between the start and end positions, the positions are in Detour coordinates.
+    fn find_poly_path(&mut self, start_pos: &[f32; 3], end_pos: &[f32; 3], filter: &DetourQueryFilter) -> DetourResult<Vec<dtPolyRef>> {
+        let Some((start_ref, _)) = self.find_nearest_poly_detour(start_pos, filter) else {
+            return Err(DetourStatus::failure(DetourStatusDetails::InvalidParam.into()));
+        };
+        let Some((end_ref, _)) = self.find_nearest_poly_detour(end_pos, filter) else {
+            return Err(DetourStatus::failure(DetourStatusDetails::InvalidParam.into()));
+        };
+        self.query.find_path(start_ref, end_ref, start_pos, end_pos, filter, MAX_PATH_LENGTH)
+    }
+
+    /// Finds the straight path from start to end, i.e. only the corners of the polygon corridor, like
+    /// PathGenerator::BuildPointPath in TC / AC when using straight paths.
+    ///
+    /// The status contains [`DetourStatusDetails::PartialResult`] if the end could not be reached, in which case
+    /// the path leads to the closest reachable position instead.
+    pub fn find_path(&mut self, start: &Vector3<f32>, end: &Vector3<f32>, filter: &DetourQueryFilter) -> DetourResult<Vec<Vector3<f32>>> {
+        let (start_pos, end_pos) = (to_detour_pos(start), to_detour_pos(end));
+        let (poly_path, status) = self.find_poly_path(&start_pos, &end_pos, filter)?;
+        let (points, _) = self.query.find_straight_path(&start_pos, &end_pos, &poly_path, MAX_POINT_PATH_LENGTH)?;
+        Ok((points.iter().map(|p| from_detour_pos(&p.pos)).collect(), status))
+    }
+
+    /// Finds a path from start to end which follows the navmesh surface in steps of [`SMOOTH_PATH_STEP_SIZE`],
+    /// like PathGenerator::BuildPointPath in TC / AC when not using straight paths.
+    ///
+    /// The status of the polygon path finding is returned, see [`MMapNavMeshQuery::find_path`].
+    pub fn find_smooth_path(&mut self, start: &Vector3<f32>, end: &Vector3<f32>, filter: &DetourQueryFilter) -> DetourResult<Vec<Vector3<f32>>> {
+        let (start_pos, end_pos) = (to_detour_pos(start), to_detour_pos(end));
+        let (poly_path, status) = self.find_poly_path(&start_pos, &end_pos, filter)?;
+        let points = self.find_smooth_path_detour(&start_pos, &end_pos, poly_path, filter, MAX_POINT_PATH_LENGTH)?;
+        Ok((points.iter().map(from_detour_pos).collect(), status))
+    }
+
+    /// PathGenerator::FindSmoothPath in TC / AC, the positions are in Detour coordinates
+    fn find_smooth_path_detour(
+        &mut self,
+        start_pos: &[f32; 3],
+        end_pos: &[f32; 3],
+        mut polys: Vec<dtPolyRef>,
+        filter: &DetourQueryFilter,
+        max_smooth_path_size: usize,
+    ) -> Result<Vec<[f32; 3]>, DetourStatus> {
+        let (Some(&first), Some(&last)) = (polys.first(), polys.last()) else {
+            return Err(DetourStatus::failure(DetourStatusDetails::InvalidParam.into()));
+        };
+        let (mut iter_pos, _) = self.query.closest_point_on_poly_boundary(first, start_pos)?;
+        let (target_pos, _) = self.query.closest_point_on_poly_boundary(last, end_pos)?;
+
+        let mut smooth_path = vec![iter_pos];
+        // Move towards target a small advancement at a time until target reached or
+        // when ran out of memory to store the path.
+        while !polys.is_empty() && smooth_path.len() < max_smooth_path_size {
+            // Find location to steer towards.
+            let Some(steer) = self.get_steer_target(&iter_pos, &target_pos, SMOOTH_PATH_SLOP, &polys) else {
+                break;
+            };
+            let end_of_path = steer.is_end();
+            let off_mesh_connection = steer.is_off_mesh_connection();
+
+            // Find movement delta.
+            let delta = [steer.pos[0] - iter_pos[0], steer.pos[1] - iter_pos[1], steer.pos[2] - iter_pos[2]];
+            let mut len = (delta[0] * delta[0] + delta[1] * delta[1] + delta[2] * delta[2]).sqrt();
+            // If the steer target is end of path or off-mesh link, do not move past the location.
+            if (end_of_path || off_mesh_connection) && len < SMOOTH_PATH_STEP_SIZE {
+                len = 1.0;
+            } else {
+                len = SMOOTH_PATH_STEP_SIZE / len;
+            }
+            let move_tgt = [iter_pos[0] + delta[0] * len, iter_pos[1] + delta[1] * len, iter_pos[2] + delta[2] * len];
+
+            // Move
+            let ((mut result, visited), _) = self.query.move_along_surface(polys[0], &iter_pos, &move_tgt, filter, MAX_VISIT_POLY)?;
+            polys = fixup_corridor(&polys, &visited, MAX_PATH_LENGTH);
+
+            match self.query.get_poly_height(polys[0], &result) {
+                Ok((h, _)) => result[1] = h,
+                Err(e) => {
+                    debug!(target:"maps", "PathGenerator::FindSmoothPath: Cannot find height at position X: {} Y: {} Z: {}: {e}", result[2], result[0], result[1])
+                },
+            }
+            result[1] += 0.5;
+            iter_pos = result;
+
+            // Handle end of path and off-mesh links when close enough.
+            if end_of_path && in_range_yzx(&iter_pos, &steer.pos, SMOOTH_PATH_SLOP, 1.0) {
+                // Reached end of path.
+                iter_pos = target_pos;
+                if smooth_path.len() < max_smooth_path_size {
+                    smooth_path.push(iter_pos);
+                }
+                break;
+            } else if off_mesh_connection && in_range_yzx(&iter_pos, &steer.pos, SMOOTH_PATH_SLOP, 1.0) {
+                // Advance the path up to and over the off-mesh connection.
+                let mut prev_ref = 0;
+                let mut poly_ref = polys[0];
+                let mut npos = 0;
+                while npos < polys.len() && poly_ref != steer.poly_ref {
+                    prev_ref = poly_ref;
+                    poly_ref = polys[npos];
+                    npos += 1;
+                }
+                polys.drain(..npos);
+                if polys.is_empty() {
+                    break;
+                }
+
+                // Handle the connection.
+                if let Ok(((connection_start_pos, connection_end_pos), _)) = self.nav_mesh.get_off_mesh_connection_poly_end_points(prev_ref, poly_ref) {
+                    if smooth_path.len() < max_smooth_path_size {
+                        smooth_path.push(connection_start_pos);
+                    }
+                    // Move position at the other side of the off-mesh link.
+                    iter_pos = connection_end_pos;
+                    let (h, _) = self.query.get_poly_height(polys[0], &iter_pos)?;
+                    iter_pos[1] = h + 0.5;
+                }
+            }
+
+            // Store results.
+            if smooth_path.len() < max_smooth_path_size {
+                smooth_path.push(iter_pos);
+            }
+        }
+
+        // this is most likely a loop
+        if smooth_path.len() < max_smooth_path_size {
+            Ok(smooth_path)
+        } else {
+            Err(DetourStatus::failure(DetourStatusDetails::BufferTooSmall.into()))
+        }
+    }
+
+    /// PathGenerator::GetSteerTarget in TC / AC, the positions are in Detour coordinates
+    fn get_steer_target(&mut self, start_pos: &[f32; 3], end_pos: &[f32; 3], min_target_dist: f32, path: &[dtPolyRef]) -> Option<DetourStraightPathPoint> {
+        // Find steer target.
+        let Ok((steer_path, _)) = self.query.find_straight_path(start_pos, end_pos, path, MAX_STEER_POINTS) else {
+            return None;
+        };
+        // Find vertex far enough to steer to.
+        // Stop at Off-Mesh link or when point is further than slop away.
+        let mut steer = *steer_path
+            .iter()
+            .find(|p| p.is_off_mesh_connection() || !in_range_yzx(&p.pos, start_pos, min_target_dist, 1000.0))?;
+        // keep Z value
+        steer.pos[1] = start_pos[1];
+        Some(steer)
+    }
+
+    /// dtNavMeshQuery::findRandomPointAroundCircle in Detour, starting from the polygon nearest to the center.
+    ///
+    /// Returns `None` if there is no navmesh around the center.
+    pub fn find_random_point_around_circle(&mut self, center: &Vector3<f32>, radius: f32, filter: &DetourQueryFilter) -> Option<Vector3<f32>> {
+        let center_pos = to_detour_pos(center);
+        let (start_ref, _) = self.find_nearest_poly_detour(&center_pos, filter)?;
+        let ((_, random_pt), _) = self.query.find_random_point_around_circle(start_ref, &center_pos, radius, filter).ok()?;
+        Some(from_detour_pos(&random_pt))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use recastnavigation_sys::dtNavMeshCreateParams;
+
+    use super::*;
+    use crate::{
+        recastnavigation_handles::detour_create_nav_mesh_data,
+        utils::{bincode_serialise, buffered_file_create},
+    };
+
+    /// Writes the navmesh params of the map along with a tile of a single square polygon, as the mmaps generator
+    /// does for the tile at `(tile_x, tile_y)`
+    fn write_generated_tile(mmaps_dir: &Path, map_id: u32, tile_x: u16, tile_y: u16) {
+        const TILE_SIZE: f32 = 1600.0 / 3.0;
+        let params = DetourNavMeshParams::new(&[0.0; 3], TILE_SIZE, TILE_SIZE, 4, 16);
+        let mut file = buffered_file_create(MmapTileFile::mmap_filepath(mmaps_dir, map_id)).unwrap();
+        bincode_serialise(&mut file, &params).unwrap();
+        drop(file);
+
+        let verts: [u16; 12] = [0, 0, 0, 0, 0, 10, 10, 0, 10, 10, 0, 0];
+        let mut polys = [0xffff_u16; 2 * DT_VERTS_PER_POLYGON as usize];
+        polys[..4].copy_from_slice(&[0, 1, 2, 3]);
+        let areas = [MmapNavTerrainFlag::Ground.area_id()];
+        let flags = [MmapNavTerrainFlag::Ground.flags().bits()];
+        let mut create_params = dtNavMeshCreateParams {
+            verts:            verts.as_ptr(),
+            vertCount:        4,
+            polys:            polys.as_ptr(),
+            polyAreas:        areas.as_ptr(),
+            polyFlags:        flags.as_ptr(),
+            polyCount:        1,
+            nvp:              DT_VERTS_PER_POLYGON,
+            detailMeshes:     std::ptr::null(),
+            detailVerts:      std::ptr::null(),
+            detailVertsCount: 0,
+            detailTris:       std::ptr::null(),
+            detailTriCount:   0,
+            offMeshConVerts:  std::ptr::null(),
+            offMeshConCount:  0,
+            offMeshConRad:    std::ptr::null(),
+            offMeshConDir:    std::ptr::null(),
+            offMeshConAreas:  std::ptr::null(),
+            offMeshConFlags:  std::ptr::null(),
+            walkableHeight:   2.0,
+            walkableRadius:   0.5,
+            walkableClimb:    1.0,
+            tileX:            0,
+            tileY:            0,
+            bmin:             [0.0; 3],
+            bmax:             [10.0, 1.0, 10.0],
+            cs:               1.0,
+            ch:               1.0,
+            tileLayer:        0,
+            buildBvTree:      true,
+            offMeshConUserID: std::ptr::null(),
+            userId:           0,
+        };
+        let mut nav_data = vec![];
+        detour_create_nav_mesh_data(&mut create_params, &mut nav_data).unwrap();
+        // The generator names the tile files by its tile Y first, see TileBuilder::build_move_map_tile
+        MmapTileFile::new(false, nav_data).write_to_mmtile(mmaps_dir, map_id, tile_y, tile_x).unwrap();
+    }
+
+    #[test]
+    fn it_loads_generated_tiles_at_their_grid_coordinates() {
+        let mmaps_dir = tempfile::tempdir().unwrap();
+        // The generator's tile Y is the grid X, i.e. the grid map files it reads are named by its tile Y first
+        let (grid_x, grid_y) = (30, 40);
+        write_generated_tile(mmaps_dir.path(), 1, grid_y, grid_x);
+
+        let mut mgr = MMapMgr::default();
+        assert!(mgr.load_map_tile(mmaps_dir.path(), 1, grid_y, grid_x).is_err());
+        mgr.load_map_tile(mmaps_dir.path(), 1, grid_x, grid_y).unwrap();
+        assert_eq!(mgr.loaded_tiles_count(), 1);
+        assert!(mgr.unload_map_tile(1, grid_x, grid_y));
+        assert_eq!(mgr.loaded_tiles_count(), 0);
+    }
+
+    #[test]
+    fn it_builds_nav_terrain_query_filters() {
+        let filter = nav_terrain_query_filter(MmapNavTerrainFlag::Ground | MmapNavTerrainFlag::Water, MmapNavTerrainFlag::MagmaSlime.into());
+        assert!(filter.pass_filter(MmapNavTerrainFlag::Ground.flags().bits()));
+        assert!(filter.pass_filter((MmapNavTerrainFlag::Ground | MmapNavTerrainFlag::GroundSteep).bits()));
+        assert!(!filter.pass_filter(MmapNavTerrainFlag::GroundSteep.flags().bits()));
+        assert!(!filter.pass_filter((MmapNavTerrainFlag::Water | MmapNavTerrainFlag::MagmaSlime).bits()));
+        assert_eq!(filter.area_cost(MmapNavTerrainFlag::Water.area_id()), 1.0);
+    }
+
+    #[test]
+    fn it_fixes_up_the_path_corridor_with_visited_polygons() {
+        // Moved from polygon 1 through 2 into 3, the corridor continues from 3
+        assert_eq!(fixup_corridor(&[1, 2, 3, 4, 5], &[1, 2, 3], MAX_PATH_LENGTH), vec![3, 4, 5]);
+        // Left the corridor and moved back into it, the corridor continues from where it was re-entered
+        assert_eq!(fixup_corridor(&[1, 2, 3], &[1, 9, 2], MAX_PATH_LENGTH), vec![2, 3]);
+        assert_eq!(fixup_corridor(&[1, 2, 3], &[9, 8, 1], MAX_PATH_LENGTH), vec![1, 2, 3]);
+        assert_eq!(fixup_corridor(&[1, 2, 3], &[1, 8, 9], MAX_PATH_LENGTH), vec![9, 8, 1, 2, 3]);
+        // No common polygon, nothing changes
+        assert_eq!(fixup_corridor(&[1, 2, 3], &[7, 8], MAX_PATH_LENGTH), vec![1, 2, 3]);
+        // Stays within the max path length
+        assert_eq!(fixup_corridor(&[1, 2, 3], &[1, 8, 9], 4), vec![9, 8, 1, 2]);
+    }
+
+    #[test]
+    fn it_checks_range_in_detour_coordinates() {
+        let pos = to_detour_pos(&Vector3::new(10.0, 20.0, 30.0));
+        assert_eq!(from_detour_pos(&pos), Vector3::new(10.0, 20.0, 30.0));
+        assert!(in_range_yzx(&pos, &to_detour_pos(&Vector3::new(10.2, 20.0, 30.5)), 0.3, 1.0));
+        assert!(!in_range_yzx(&pos, &to_detour_pos(&Vector3::new(10.2, 20.0, 31.5)), 0.3, 1.0));
+        assert!(!in_range_yzx(&pos, &to_detour_pos(&Vector3::new(10.5, 20.0, 30.0)), 0.3, 1.0));
+    }
+}
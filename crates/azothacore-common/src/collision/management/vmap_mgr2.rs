@@ -12,11 +12,16 @@ use bevy::{
     prelude::{EventWriter, Handle, IntoSystemConfigs, Res, ResMut, Resource, SystemSet},
 };
 use flagset::FlagSet;
+use nalgebra::Vector3;
 use tracing::{debug, error, instrument};
 
 use crate::{
     bevy_app::{az_startup_succeeded, AzStartupFailedEvent},
-    collision::{management::VMapMgr, maps::map_tree::StaticMapTree, models::world_model::WorldModel},
+    collision::{
+        management::{VMapMgr, VMAP_INVALID_HEIGHT_VALUE},
+        maps::map_tree::StaticMapTree,
+        models::{world_model::WorldModel, ModelIgnoreFlags},
+    },
     configuration::ConfigMgr,
     deref_boilerplate,
     utils::buffered_file_open,
@@ -95,6 +100,22 @@ pub trait LiquidFlagsGetter: Resource {
     }
 }
 
+flagset::flags! {
+    /// VmapDisableTypes in TC / AC
+    pub enum VmapDisableTypes: u8 {
+        AreaFlag     = 0x1,
+        Height       = 0x2,
+        Los          = 0x4,
+        LiquidStatus = 0x8,
+    }
+}
+
+/// VMapManager2::convertPositionToInternalRep in TC / AC, converting back to world coordinates is the same operation.
+pub fn convert_position_to_internal_rep(x: f32, y: f32, z: f32) -> Vector3<f32> {
+    let mid = 0.5 * 64.0 * (1600.0 / 3.0);
+    Vector3::new(mid - x, mid - y, z)
+}
+
 pub trait VmapDisabledChecker: Resource {
     /// DisableMgr::IsVMAPDisabledFor in TC/AC
     fn is_vmap_disabled_for(&self, _entry: u32, _flags: u8) -> bool {
@@ -132,9 +153,9 @@ where
 {
     pub cfg_mgr:             Res<'w, ConfigMgr<C>>,
     pub liquid_flags_getter: Res<'w, L>,
-    _vmap_disabled_checker:  Res<'w, V>,
+    vmap_disabled_checker:   Res<'w, V>,
     /// Child map data, containings map_ids to their children IDs.
-    _child_map_data:         Res<'w, ChildMapData>,
+    pub child_map_data:      Res<'w, ChildMapData>,
     /// Parent map data, containings map_ids to their parent ID.
     pub parent_map_data:     Res<'w, ParentMapData>,
 }
@@ -269,6 +290,13 @@ where
     pub fn instance_map_tree(&self, map_id: u32) -> Option<&StaticMapTree> {
         self.instance_map_trees.get(&map_id)
     }
+
+    /// IsVMAPDisabledForPtr in TC / AC
+    fn is_vmap_disabled_for(&self, map_id: u32, disable_type: VmapDisableTypes) -> bool {
+        self.helper
+            .vmap_disabled_checker
+            .is_vmap_disabled_for(map_id, FlagSet::from(disable_type).bits())
+    }
 }
 
 impl<C, L, V> VMapMgr for VMapMgr2<'_, C, L, V>
@@ -277,82 +305,432 @@ where
     L: LiquidFlagsGetter,
     V: VmapDisabledChecker,
 {
-    fn load_map_tile(&self, _p_base_path: &Path, _p_map_id: u32, _x: u16, _y: u16) -> super::VmapFactoryLoadResult<()> {
-        todo!()
+    fn load_map_tile(&mut self, p_map_id: u32, x: u16, y: u16) -> super::VmapFactoryLoadResult<()> {
+        if !self.is_map_loading_enabled() {
+            return Err(super::VmapFactoryLoadError::Ignored);
+        }
+        self.load_single_map_tile(p_map_id, x, y, false)?;
+        let child_map_ids = self.helper.child_map_data.get(&p_map_id).cloned().unwrap_or_default();
+        for child_map_id in child_map_ids {
+            self.load_single_map_tile(child_map_id, x, y, false)?;
+        }
+        Ok(())
     }
 
-    fn exists_map_tile(&self, _p_base_path: &Path, _p_map_id: u32, _x: u16, _y: u16) -> super::VmapLoadResult<()> {
-        todo!()
+    fn exists_map_tile(&self, p_map_id: u32, x: u16, y: u16) -> super::VmapLoadResult<()> {
+        StaticMapTree::can_load_map(p_map_id, x, y, &self.helper)
     }
 
-    fn unload_map_tile(&self, _p_map_id: u32, _x: u16, _y: u16) {
-        todo!()
+    fn unload_map_tile(&mut self, p_map_id: u32, x: u16, y: u16) {
+        let child_map_ids = self.helper.child_map_data.get(&p_map_id).cloned().unwrap_or_default();
+        for child_map_id in child_map_ids {
+            self.unload_single_map_tile(child_map_id, x, y);
+        }
+        self.unload_single_map_tile(p_map_id, x, y);
     }
 
-    fn unload_map(&self, _p_map_id: u32) {
-        todo!()
+    fn unload_map(&mut self, p_map_id: u32) {
+        let child_map_ids = self.helper.child_map_data.get(&p_map_id).cloned().unwrap_or_default();
+        for child_map_id in child_map_ids {
+            self.instance_map_trees.remove(&child_map_id);
+        }
+        self.instance_map_trees.remove(&p_map_id);
     }
 
-    fn is_in_line_of_sight(
-        &self,
-        _p_map_id: u32,
-        _x1: f32,
-        _y1: f32,
-        _z1: f32,
-        _x2: f32,
-        _y2: f32,
-        _z2: f32,
-        _ignore_flags: flagset::FlagSet<crate::collision::models::ModelIgnoreFlags>,
-    ) -> bool {
-        todo!()
+    fn is_in_line_of_sight(&self, p_map_id: u32, x1: f32, y1: f32, z1: f32, x2: f32, y2: f32, z2: f32, ignore_flags: FlagSet<ModelIgnoreFlags>) -> bool {
+        if !self.is_line_of_sight_calc_enabled() || self.is_vmap_disabled_for(p_map_id, VmapDisableTypes::Los) {
+            return true;
+        }
+        let Some(tree) = self.instance_map_tree(p_map_id) else {
+            return true;
+        };
+        let pos1 = convert_position_to_internal_rep(x1, y1, z1);
+        let pos2 = convert_position_to_internal_rep(x2, y2, z2);
+        if pos1 == pos2 {
+            return true;
+        }
+        tree.is_in_line_of_sight(&self.model_store.loaded_model_files, &pos1, &pos2, ignore_flags)
     }
 
-    fn get_height(&self, _p_map_id: u32, _x: f32, _y: f32, _z: f32, _max_search_dist: f32) -> f32 {
-        todo!()
+    fn get_height(&self, p_map_id: u32, x: f32, y: f32, z: f32, max_search_dist: f32) -> f32 {
+        if !self.is_height_calc_enabled() || self.is_vmap_disabled_for(p_map_id, VmapDisableTypes::Height) {
+            return VMAP_INVALID_HEIGHT_VALUE;
+        }
+        let Some(tree) = self.instance_map_tree(p_map_id) else {
+            return VMAP_INVALID_HEIGHT_VALUE;
+        };
+        let pos = convert_position_to_internal_rep(x, y, z);
+        tree.get_height(&self.model_store.loaded_model_files, &pos, max_search_dist)
+            .filter(|h| h.is_finite())
+            .unwrap_or(VMAP_INVALID_HEIGHT_VALUE)
     }
 
     fn get_object_hit_pos(
         &self,
-        _p_map_id: u32,
-        _x1: f32,
-        _y1: f32,
-        _z1: f32,
-        _x2: f32,
-        _y2: f32,
-        _z2: f32,
-        _rx: &mut f32,
-        _ry: &mut f32,
-        _rz: &mut f32,
-        _p_modify_dist: f32,
+        p_map_id: u32,
+        x1: f32,
+        y1: f32,
+        z1: f32,
+        x2: f32,
+        y2: f32,
+        z2: f32,
+        rx: &mut f32,
+        ry: &mut f32,
+        rz: &mut f32,
+        p_modify_dist: f32,
     ) -> bool {
-        // self.get_liquid_level(p_map_id, x, y, z, req_liquid_type, level, floor, typ)
-        // let _a = (self.get_liquid_flags)(3);
-        todo!()
+        (*rx, *ry, *rz) = (x2, y2, z2);
+        if !self.is_line_of_sight_calc_enabled() || self.is_vmap_disabled_for(p_map_id, VmapDisableTypes::Los) {
+            return false;
+        }
+        let Some(tree) = self.instance_map_tree(p_map_id) else {
+            return false;
+        };
+        let pos1 = convert_position_to_internal_rep(x1, y1, z1);
+        let pos2 = convert_position_to_internal_rep(x2, y2, z2);
+        let Some(result_pos) = tree.get_object_hit_pos(&self.model_store.loaded_model_files, &pos1, &pos2, p_modify_dist) else {
+            return false;
+        };
+        // converting back is the same operation
+        let result_pos = convert_position_to_internal_rep(result_pos.x, result_pos.y, result_pos.z);
+        (*rx, *ry, *rz) = (result_pos.x, result_pos.y, result_pos.z);
+        true
     }
 
     fn is_line_of_sight_calc_enabled(&self) -> bool {
-        todo!()
+        self.helper.cfg_mgr.enable_line_of_sight_calc()
     }
 
     fn is_height_calc_enabled(&self) -> bool {
-        todo!()
+        self.helper.cfg_mgr.enable_height_calc()
     }
 
-    fn get_area_info(
-        &self,
-        _p_map_id: u32,
-        _x: f32,
-        _y: f32,
-        _z: &mut f32,
-        _flags: &mut u32,
-        _adt_id: &mut u16,
-        _root_id: &mut u32,
-        _group_id: &mut u32,
-    ) -> bool {
-        todo!()
+    fn get_area_info(&self, p_map_id: u32, x: f32, y: f32, z: &mut f32, flags: &mut u32, adt_id: &mut u16, root_id: &mut u32, group_id: &mut u32) -> bool {
+        if self.is_vmap_disabled_for(p_map_id, VmapDisableTypes::AreaFlag) {
+            return false;
+        }
+        let Some(tree) = self.instance_map_tree(p_map_id) else {
+            return false;
+        };
+        let pos = convert_position_to_internal_rep(x, y, *z);
+        let Some(info) = tree.get_area_info(&self.model_store.loaded_model_files, &pos) else {
+            return false;
+        };
+        *z = info.ground_z;
+        *flags = info.flags;
+        *adt_id = info.adt_id;
+        *root_id = info.root_id;
+        *group_id = info.group_id;
+        true
+    }
+
+    fn get_liquid_level(&self, p_map_id: u32, x: f32, y: f32, z: f32, req_liquid_type: u8, level: &mut f32, floor: &mut f32, typ: &mut u32) -> bool {
+        if self.is_vmap_disabled_for(p_map_id, VmapDisableTypes::LiquidStatus) {
+            return false;
+        }
+        let Some(tree) = self.instance_map_tree(p_map_id) else {
+            return false;
+        };
+        let pos = convert_position_to_internal_rep(x, y, z);
+        let Some(info) = tree.get_location_info(&self.model_store.loaded_model_files, &pos) else {
+            return false;
+        };
+        let (Some(hit_instance), Some(hit_model)) = (info.hit_instance, info.hit_model) else {
+            return false;
+        };
+        *floor = info.ground_z;
+        *typ = hit_model.get_liquid_type();
+        if req_liquid_type != 0 && (self.helper.liquid_flags_getter.get_liquid_flags(*typ).bits() & req_liquid_type) == 0 {
+            return false;
+        }
+        let Some(liquid_level) = hit_instance.get_liquid_level(&pos, &info) else {
+            return false;
+        };
+        *level = liquid_level;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+    use figment::Jail;
+    use parry3d::{bounding_volume::Aabb, partitioning::Qbvh};
+
+    use super::*;
+    use crate::{
+        bevy_app::bevy_app,
+        collision::{
+            management::{VmapFactoryLoadError, VmapFactoryLoadResult, VmapLoadError},
+            models::{
+                model_instance::{ModelFlags, VmapModelSpawn},
+                world_model::{GroupModel, WmoLiquid},
+            },
+        },
+        configuration::{config_mgr_plugin, Config},
+    };
+
+    #[derive(serde::Deserialize, serde::Serialize)]
+    struct TestVmapConfig {
+        vmaps_dir:                 PathBuf,
+        enable_line_of_sight_calc: bool,
+        enable_height_calc:        bool,
+    }
+
+    impl Config for TestVmapConfig {}
+
+    impl VmapConfig for TestVmapConfig {
+        fn vmaps_dir(&self) -> PathBuf {
+            self.vmaps_dir.clone()
+        }
+
+        fn enable_line_of_sight_calc(&self) -> bool {
+            self.enable_line_of_sight_calc
+        }
+
+        fn enable_height_calc(&self) -> bool {
+            self.enable_height_calc
+        }
+    }
+
+    #[derive(Resource)]
+    struct TestLiquidFlags;
+
+    impl LiquidFlagsGetter for TestLiquidFlags {
+        fn get_liquid_flags(&self, liquid_type_id: u32) -> FlagSet<MapLiquidTypeFlag> {
+            if liquid_type_id == 1 {
+                MapLiquidTypeFlag::Water.into()
+            } else {
+                None.into()
+            }
+        }
+    }
+
+    #[derive(Resource)]
+    struct TestVmapNotDisabled;
+
+    impl VmapDisabledChecker for TestVmapNotDisabled {}
+
+    type TestVMapMgr2<'w> = VMapMgr2<'w, TestVmapConfig, TestLiquidFlags, TestVmapNotDisabled>;
+
+    const MAP_ID: u32 = 1;
+    const TILE: (u16, u16) = (32, 32);
+    /// Center of the synthetic tile contents, in the internal vmap representation
+    const CENTER: [f32; 3] = [17000.0, 17000.0, 100.0];
+
+    /// Converts an offset from [CENTER] to world coordinates
+    fn world_pos(dx: f32, dy: f32, z: f32) -> (f32, f32, f32) {
+        let p = convert_position_to_internal_rep(CENTER[0] + dx, CENTER[1] + dy, z);
+        (p.x, p.y, p.z)
+    }
+
+    fn quad(mogp_flags: u32, group_wmoid: u32, bound: [[f32; 3]; 2], vertices: [[f32; 3]; 4], liquid: Option<WmoLiquid>) -> GroupModel {
+        GroupModel::new(
+            mogp_flags,
+            group_wmoid,
+            Aabb::new(bound[0].into(), bound[1].into()),
+            vec![Vector3::new(0, 1, 2), Vector3::new(0, 2, 3)],
+            vertices.into_iter().map(Vector3::from).collect(),
+            liquid,
+        )
+        .unwrap()
+    }
+
+    fn spawn(id: u32, flags: FlagSet<ModelFlags>, offset: [f32; 3], model_bound: [[f32; 3]; 2], name: &str) -> VmapModelSpawn {
+        let i_pos = Vector3::from(CENTER) + Vector3::from(offset);
+        VmapModelSpawn {
+            flags,
+            adt_id: 3,
+            id,
+            i_pos,
+            i_rot: Vector3::zeros(),
+            i_scale: 1.0,
+            bound: Some(Aabb::new(
+                (i_pos + Vector3::from(model_bound[0])).into(),
+                (i_pos + Vector3::from(model_bound[1])).into(),
+            )),
+            name: name.to_string(),
+        }
+    }
+
+    /// Writes a single tile containing a 20x20 WMO floor with water 2 units above it, and an M2 wall standing on the
+    /// floor 5 units away from its center along the x axis.
+    fn write_synthetic_vmaps(vmaps_dir: &Path) {
+        std::fs::create_dir_all(vmaps_dir).unwrap();
+        let floor_bound = [[-10.0, -10.0, -1.0], [10.0, 10.0, 30.0]];
+        let floor = WorldModel::new(
+            7,
+            vec![quad(
+                0x8,
+                42,
+                floor_bound,
+                [[-10.0, -10.0, 0.0], [10.0, -10.0, 0.0], [10.0, 10.0, 0.0], [-10.0, 10.0, 0.0]],
+                Some(WmoLiquid::new(1, Err(2.0))),
+            )],
+        );
+        let wall_bound = [[-0.5, -10.0, 0.0], [0.5, 10.0, 20.0]];
+        let wall = WorldModel::new(
+            0,
+            vec![quad(
+                0,
+                0,
+                wall_bound,
+                [[0.0, -10.0, 0.0], [0.0, 10.0, 0.0], [0.0, 10.0, 20.0], [0.0, -10.0, 20.0]],
+                None,
+            )],
+        );
+        floor.write_file(&mut std::fs::File::create(vmaps_dir.join("floor.wmo.vmo")).unwrap()).unwrap();
+        wall.write_file(&mut std::fs::File::create(vmaps_dir.join("wall.m2.vmo")).unwrap()).unwrap();
+
+        let spawns = [
+            spawn(1, None.into(), [0.0, 0.0, 0.0], floor_bound, "floor.wmo"),
+            spawn(2, ModelFlags::ModM2.into(), [5.0, 0.0, 0.0], wall_bound, "wall.m2"),
+        ];
+        let spawns = spawns.iter().collect::<Vec<_>>();
+        let mut tree = Qbvh::new();
+        tree.clear_and_rebuild(spawns.iter().enumerate().map(|(i, s)| (i, s.bound.unwrap())), 0.0);
+        StaticMapTree::write_map_tree_to_file(vmaps_dir, MAP_ID, &tree, &spawns).unwrap();
+        StaticMapTree::write_map_tile_spawns_file(vmaps_dir, MAP_ID, TILE.0, TILE.1, &spawns).unwrap();
+    }
+
+    fn test_app(jail: &mut Jail, enable_line_of_sight_calc: bool, enable_height_calc: bool) -> App {
+        write_synthetic_vmaps(&jail.directory().join("vmaps"));
+        jail.create_file(
+            "config.toml",
+            &format!("vmaps_dir = \"vmaps\"\nenable_line_of_sight_calc = {enable_line_of_sight_calc}\nenable_height_calc = {enable_height_calc}"),
+        )
+        .unwrap();
+        let mut app = bevy_app();
+        app.add_plugins((
+            config_mgr_plugin::<TestVmapConfig, _>("config.toml", false),
+            vmap_mgr2_plugin::<TestVmapConfig, TestLiquidFlags, TestVmapNotDisabled>,
+        ))
+        .insert_resource(TestLiquidFlags)
+        .insert_resource(TestVmapNotDisabled)
+        .insert_resource(ChildMapData(HashMap::new()))
+        .insert_resource(ParentMapData(HashMap::new()));
+        app.update();
+        app
+    }
+
+    fn load_tile(app: &mut App) -> VmapFactoryLoadResult<()> {
+        app.world_mut()
+            .run_system_once(|mut vm: TestVMapMgr2| vm.load_map_tile(MAP_ID, TILE.0, TILE.1))
+            .unwrap()
+    }
+
+    fn assert_close(got: f32, expected: f32) {
+        assert!((got - expected).abs() < 1e-2, "got {got}, expected {expected}");
+    }
+
+    #[test]
+    fn it_answers_queries_against_a_synthetic_tile() {
+        Jail::expect_with(|jail| {
+            let mut app = test_app(jail, true, true);
+            load_tile(&mut app).unwrap();
+            app.world_mut()
+                .run_system_once(|vm: TestVMapMgr2| {
+                    let (x1, y1, z1) = world_pos(0.0, 0.0, 105.0);
+                    let (x2, y2, z2) = world_pos(10.0, 0.0, 105.0);
+                    // the wall blocks line of sight, unless M2s are ignored
+                    assert!(!vm.is_in_line_of_sight(MAP_ID, x1, y1, z1, x2, y2, z2, ModelIgnoreFlags::Nothing.into()));
+                    assert!(vm.is_in_line_of_sight(MAP_ID, x1, y1, z1, x2, y2, z2, ModelIgnoreFlags::M2.into()));
+                    // above the wall
+                    assert!(vm.is_in_line_of_sight(MAP_ID, x1, y1, 125.0, x2, y2, 125.0, ModelIgnoreFlags::Nothing.into()));
+                    // unknown maps never block
+                    assert!(vm.is_in_line_of_sight(MAP_ID + 1, x1, y1, z1, x2, y2, z2, ModelIgnoreFlags::Nothing.into()));
+
+                    let (mut rx, mut ry, mut rz) = (0.0, 0.0, 0.0);
+                    assert!(vm.get_object_hit_pos(MAP_ID, x1, y1, z1, x2, y2, z2, &mut rx, &mut ry, &mut rz, -1.0));
+                    let (ex, ey, ez) = world_pos(4.0, 0.0, 105.0);
+                    assert_close(rx, ex);
+                    assert_close(ry, ey);
+                    assert_close(rz, ez);
+                    assert!(!vm.get_object_hit_pos(MAP_ID, x1, y1, 125.0, x2, y2, 125.0, &mut rx, &mut ry, &mut rz, -1.0));
+                    assert_eq!((rx, ry, rz), (x2, y2, 125.0));
+
+                    assert_close(vm.get_height(MAP_ID, x1, y1, 110.0, 50.0), 100.0);
+                    // out of search distance
+                    assert_eq!(vm.get_height(MAP_ID, x1, y1, 110.0, 5.0), VMAP_INVALID_HEIGHT_VALUE);
+                    let (ox, oy, _) = world_pos(50.0, 0.0, 110.0);
+                    assert_eq!(vm.get_height(MAP_ID, ox, oy, 110.0, 50.0), VMAP_INVALID_HEIGHT_VALUE);
+
+                    let (mut z, mut flags, mut adt_id, mut root_id, mut group_id) = (z1, 0, 0, 0, 0);
+                    assert!(vm.get_area_info(MAP_ID, x1, y1, &mut z, &mut flags, &mut adt_id, &mut root_id, &mut group_id));
+                    assert_close(z, 100.0);
+                    assert_eq!((flags, adt_id, root_id, group_id), (0x8, 3, 7, 42));
+                    let mut z = 105.0;
+                    assert!(!vm.get_area_info(MAP_ID, ox, oy, &mut z, &mut flags, &mut adt_id, &mut root_id, &mut group_id));
+
+                    let (mut level, mut floor, mut typ) = (0.0, 0.0, 0);
+                    let water = FlagSet::from(MapLiquidTypeFlag::Water).bits();
+                    assert!(vm.get_liquid_level(MAP_ID, x1, y1, z1, water, &mut level, &mut floor, &mut typ));
+                    assert_close(level, 102.0);
+                    assert_close(floor, 100.0);
+                    assert_eq!(typ, 1);
+                    let magma = FlagSet::from(MapLiquidTypeFlag::Magma).bits();
+                    assert!(!vm.get_liquid_level(MAP_ID, x1, y1, z1, magma, &mut level, &mut floor, &mut typ));
+                })
+                .unwrap();
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn it_honours_disabled_line_of_sight_and_height_calc() {
+        Jail::expect_with(|jail| {
+            let mut app = test_app(jail, false, true);
+            load_tile(&mut app).unwrap();
+            app.world_mut()
+                .run_system_once(|vm: TestVMapMgr2| {
+                    let (x1, y1, z1) = world_pos(0.0, 0.0, 105.0);
+                    let (x2, y2, z2) = world_pos(10.0, 0.0, 105.0);
+                    assert!(vm.is_in_line_of_sight(MAP_ID, x1, y1, z1, x2, y2, z2, ModelIgnoreFlags::Nothing.into()));
+                    let (mut rx, mut ry, mut rz) = (0.0, 0.0, 0.0);
+                    assert!(!vm.get_object_hit_pos(MAP_ID, x1, y1, z1, x2, y2, z2, &mut rx, &mut ry, &mut rz, -1.0));
+                    assert_eq!((rx, ry, rz), (x2, y2, z2));
+                    assert_close(vm.get_height(MAP_ID, x1, y1, 110.0, 50.0), 100.0);
+                })
+                .unwrap();
+
+            // nothing is loaded if neither line of sight nor height calculations are enabled
+            let mut app = test_app(jail, false, false);
+            assert!(matches!(load_tile(&mut app), Err(VmapFactoryLoadError::Ignored)));
+            app.world_mut()
+                .run_system_once(|vm: TestVMapMgr2| {
+                    assert!(vm.instance_map_tree(MAP_ID).is_none());
+                    let (x1, y1, _) = world_pos(0.0, 0.0, 105.0);
+                    assert_eq!(vm.get_height(MAP_ID, x1, y1, 110.0, 50.0), VMAP_INVALID_HEIGHT_VALUE);
+                })
+                .unwrap();
+            Ok(())
+        });
     }
 
-    fn get_liquid_level(&self, _p_map_id: u32, _x: f32, _y: f32, _z: f32, _req_liquid_type: u8, _level: &mut f32, _floor: &mut f32, _typ: &mut u32) -> bool {
-        todo!()
+    #[test]
+    fn it_checks_existence_and_unloads_tiles() {
+        Jail::expect_with(|jail| {
+            let mut app = test_app(jail, true, true);
+            load_tile(&mut app).unwrap();
+            app.world_mut()
+                .run_system_once(|mut vm: TestVMapMgr2| {
+                    assert!(vm.exists_map_tile(MAP_ID, TILE.0, TILE.1).is_ok());
+                    assert!(matches!(vm.exists_map_tile(MAP_ID, TILE.0 + 1, TILE.1), Err(VmapLoadError::FileNotFound(_))));
+                    assert!(matches!(vm.exists_map_tile(MAP_ID + 1, TILE.0, TILE.1), Err(VmapLoadError::FileNotFound(_))));
+
+                    let (x1, y1, z1) = world_pos(0.0, 0.0, 105.0);
+                    let (x2, y2, z2) = world_pos(10.0, 0.0, 105.0);
+                    vm.unload_map_tile(MAP_ID, TILE.0, TILE.1);
+                    assert!(vm.instance_map_tree(MAP_ID).is_none());
+                    assert!(vm.is_in_line_of_sight(MAP_ID, x1, y1, z1, x2, y2, z2, ModelIgnoreFlags::Nothing.into()));
+
+                    vm.load_map_tile(MAP_ID, TILE.0, TILE.1).unwrap();
+                    assert!(!vm.is_in_line_of_sight(MAP_ID, x1, y1, z1, x2, y2, z2, ModelIgnoreFlags::Nothing.into()));
+                    vm.unload_map(MAP_ID);
+                    assert!(vm.instance_map_tree(MAP_ID).is_none());
+                })
+                .unwrap();
+            Ok(())
+        });
     }
 }
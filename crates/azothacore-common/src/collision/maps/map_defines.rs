@@ -11,6 +11,7 @@ use crate::{
     sanity_check_read_all_bytes_from_reader,
     utils::{bincode_deserialise, bincode_serialise, buffered_file_create, buffered_file_open},
     AzResult,
+    MapLiquidTypeFlag,
 };
 
 const MMAP_MAGIC: &[u8; 4] = b"MMAP"; // 'MMAP'
@@ -57,6 +58,18 @@ impl MmapNavTerrainFlag {
         RC_WALKABLE_AREA - res
     }
 
+    /// The terrain type part of PathGenerator::GetNavTerrain in TC / AC, given the liquid type flags at a position.
+    /// No liquid is treated as ground.
+    pub fn from_liquid_type_flags(liquid_type_flags: FlagSet<MapLiquidTypeFlag>) -> Self {
+        if liquid_type_flags.contains(MapLiquidTypeFlag::Water) || liquid_type_flags.contains(MapLiquidTypeFlag::Ocean) {
+            Self::Water
+        } else if liquid_type_flags.contains(MapLiquidTypeFlag::Magma) || liquid_type_flags.contains(MapLiquidTypeFlag::Slime) {
+            Self::MagmaSlime
+        } else {
+            Self::Ground
+        }
+    }
+
     pub fn from_area_id(area_id: u8) -> FlagSet<Self> {
         if area_id == 0 {
             None.into()
@@ -77,6 +90,23 @@ mod tests {
         assert_eq!(MmapNavTerrainFlag::Water.area_id(), RC_WALKABLE_AREA - 2);
         assert_eq!(MmapNavTerrainFlag::MagmaSlime.area_id(), RC_WALKABLE_AREA - 3);
     }
+
+    #[test]
+    fn it_maps_liquid_type_flags_to_nav_terrain() {
+        assert_eq!(MmapNavTerrainFlag::from_liquid_type_flags(None.into()), MmapNavTerrainFlag::Ground);
+        assert_eq!(
+            MmapNavTerrainFlag::from_liquid_type_flags(MapLiquidTypeFlag::Ocean | MapLiquidTypeFlag::DarkWater),
+            MmapNavTerrainFlag::Water
+        );
+        assert_eq!(
+            MmapNavTerrainFlag::from_liquid_type_flags(MapLiquidTypeFlag::Slime.into()),
+            MmapNavTerrainFlag::MagmaSlime
+        );
+        assert_eq!(
+            MmapNavTerrainFlag::from_liquid_type_flags(MapLiquidTypeFlag::DarkWater.into()),
+            MmapNavTerrainFlag::Ground
+        );
+    }
 }
 
 #[derive(serde::Deserialize, serde::Serialize)]
@@ -131,7 +161,11 @@ impl MmapTileFile {
         }
     }
 
-    // static char const* const MAP_FILE_NAME_FORMAT = "%smmaps/%04i.mmap";
+    /// equivalent to the MAP_FILE_NAME_FORMAT in TC/AC, the file contains the navmesh params for the whole map
+    pub fn mmap_filepath<P: AsRef<Path>>(mmap_dir_path: P, map_id: u32) -> PathBuf {
+        mmap_dir_path.as_ref().join(format!("{map_id:04}.mmap"))
+    }
+
     /// equivalent to the TILE_FILE_NAME_FORMAT in TC/AC
     pub fn mmap_tile_filepath<P: AsRef<Path>>(mmap_dir_path: P, map_id: u32, tile_y: u16, tile_x: u16) -> PathBuf {
         mmap_dir_path.as_ref().join(format!("{map_id:04}{tile_y:02}{tile_x:02}.mmtile"))
@@ -1,20 +1,37 @@
 use std::{
     collections::{BTreeSet, HashMap},
     fmt::Display,
-    io,
+    io::{self, Read},
     path::{Path, PathBuf},
 };
 
+use bevy::asset::Assets;
+use flagset::FlagSet;
+use nalgebra::Vector3;
 use num::Num;
-use parry3d::partitioning::Qbvh;
+use parry3d::{
+    partitioning::Qbvh,
+    query::{
+        visitors::{PointIntersectionsVisitor, RayIntersectionsVisitor},
+        Ray,
+    },
+};
 use tracing::{debug, error};
 
 use crate::{
     az_error,
     cmp_or_return,
     collision::{
-        management::vmap_mgr2::{LiquidFlagsGetter, VMapMgr2Helper, VMapModelStore, VmapConfig, VmapDisabledChecker},
-        models::model_instance::{ModelInstance, VmapModelSpawn},
+        management::{
+            vmap_mgr2::{LiquidFlagsGetter, VMapMgr2Helper, VMapModelStore, VmapConfig, VmapDisabledChecker},
+            VmapLoadError,
+            VmapLoadResult,
+        },
+        models::{
+            model_instance::{ModelInstance, VmapModelSpawn},
+            world_model::{GroupModel, WorldModel},
+            ModelIgnoreFlags,
+        },
         vmap_definitions::VMAP_MAGIC,
     },
     sanity_check_read_all_bytes_from_reader,
@@ -22,9 +39,51 @@ use crate::{
     AzResult,
 };
 
+/// AreaInfo in TC / AC
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AreaInfo {
+    pub ground_z: f32,
+    pub flags:    u32,
+    pub adt_id:   u16,
+    pub root_id:  u32,
+    pub group_id: u32,
+}
+
+impl Default for AreaInfo {
+    fn default() -> Self {
+        Self {
+            ground_z: f32::NEG_INFINITY,
+            flags:    0,
+            adt_id:   0,
+            root_id:  0,
+            group_id: 0,
+        }
+    }
+}
+
+/// LocationInfo in TC / AC
+#[derive(Clone, Copy)]
+pub struct LocationInfo<'a> {
+    pub root_id:      u32,
+    pub hit_instance: Option<&'a ModelInstance>,
+    pub hit_model:    Option<&'a GroupModel>,
+    pub ground_z:     f32,
+}
+
+impl Default for LocationInfo<'_> {
+    fn default() -> Self {
+        Self {
+            root_id:      0,
+            hit_instance: None,
+            hit_model:    None,
+            ground_z:     f32::NEG_INFINITY,
+        }
+    }
+}
+
 pub struct StaticMapTree {
     map_id:        u32,
-    _tree:         Qbvh<usize>,
+    tree:          Qbvh<usize>,
     /// the tree entries
     tree_values:   HashMap<usize, (ModelInstance, BTreeSet<(u16, u16)>)>,
     /// mapping between spawn IDs and BH indices
@@ -43,10 +102,10 @@ impl StaticMapTree {
     }
 
     fn init_from_reader<R: io::Read>(map_id: u32, mut r: &mut R) -> AzResult<Self> {
-        let (_tree, spawn_indices) = Self::read_map_tree(&mut r)?;
+        let (tree, spawn_indices) = Self::read_map_tree(&mut r)?;
         Ok(Self {
             map_id,
-            _tree,
+            tree,
             tree_values: HashMap::new(),
             spawn_indices,
         })
@@ -175,6 +234,118 @@ impl StaticMapTree {
         self.tree_values.is_empty()
     }
 
+    /// Loaded model instances along with their models, skipping the ones whose models have not finished loading yet.
+    fn loaded_instance<'a>(&'a self, models: &'a Assets<WorldModel>, idx: usize) -> Option<(&'a ModelInstance, &'a WorldModel)> {
+        let (instance, _) = self.tree_values.get(&idx)?;
+        let model = models.get(&instance.model)?;
+        Some((instance, model))
+    }
+
+    /// getIntersectionTime in TC / AC, returns the distance to the hit if there is one within `max_dist`.
+    fn get_intersection_time(
+        &self,
+        models: &Assets<WorldModel>,
+        ray: &Ray,
+        max_dist: f32,
+        stop_at_first_hit: bool,
+        ignore_flags: FlagSet<ModelIgnoreFlags>,
+    ) -> Option<f32> {
+        // MapRayCallback in TC / AC
+        let mut distance = max_dist;
+        let mut hit = false;
+        let mut callback = |idx: &usize| {
+            let Some((instance, model)) = self.loaded_instance(models, *idx) else {
+                return true;
+            };
+            if let Some(d) = instance.intersect_ray(model, ray, distance, stop_at_first_hit, ignore_flags) {
+                distance = d;
+                hit = true;
+                return !stop_at_first_hit;
+            }
+            true
+        };
+        self.tree.traverse_depth_first(&mut RayIntersectionsVisitor::new(ray, max_dist, &mut callback));
+        hit.then_some(distance)
+    }
+
+    /// isInLineOfSight in TC / AC. Positions are in the internal vmap representation.
+    pub fn is_in_line_of_sight(&self, models: &Assets<WorldModel>, pos1: &Vector3<f32>, pos2: &Vector3<f32>, ignore_flags: FlagSet<ModelIgnoreFlags>) -> bool {
+        let max_dist = (pos2 - pos1).magnitude();
+        // return false if distance is over max float, in case of cheater teleporting to the end of the universe
+        if max_dist == f32::MAX || !max_dist.is_finite() {
+            return false;
+        }
+        // prevent NaN values which can cause BIH intersection to enter infinite loop
+        if max_dist < 1e-10 {
+            return true;
+        }
+        // direction with length of 1
+        let ray = Ray::new((*pos1).into(), (pos2 - pos1) / max_dist);
+        self.get_intersection_time(models, &ray, max_dist, true, ignore_flags).is_none()
+    }
+
+    /// getObjectHitPos in TC / AC. Positions are in the internal vmap representation.
+    ///
+    /// Returns the hit position moved `modify_dist` along the ray if an object was hit, or [None] otherwise.
+    pub fn get_object_hit_pos(&self, models: &Assets<WorldModel>, pos1: &Vector3<f32>, pos2: &Vector3<f32>, modify_dist: f32) -> Option<Vector3<f32>> {
+        let max_dist = (pos2 - pos1).magnitude();
+        // prevent NaN values which can cause BIH intersection to enter infinite loop
+        if max_dist < 1e-10 || !max_dist.is_finite() {
+            return None;
+        }
+        // direction with length of 1
+        let dir = (pos2 - pos1) / max_dist;
+        let ray = Ray::new((*pos1).into(), dir);
+        let dist = self.get_intersection_time(models, &ray, max_dist, false, ModelIgnoreFlags::Nothing.into())?;
+        let hit_pos = pos1 + dir * dist;
+        if modify_dist < 0.0 && (hit_pos - pos1).magnitude() <= -modify_dist {
+            Some(*pos1)
+        } else {
+            Some(hit_pos + dir * modify_dist)
+        }
+    }
+
+    /// getHeight in TC / AC. Position is in the internal vmap representation.
+    pub fn get_height(&self, models: &Assets<WorldModel>, pos: &Vector3<f32>, max_search_dist: f32) -> Option<f32> {
+        let ray = Ray::new((*pos).into(), Vector3::new(0.0, 0.0, -1.0));
+        self.get_intersection_time(models, &ray, max_search_dist, false, ModelIgnoreFlags::Nothing.into())
+            .map(|dist| pos.z - dist)
+    }
+
+    /// getAreaInfo in TC / AC. Position is in the internal vmap representation.
+    pub fn get_area_info(&self, models: &Assets<WorldModel>, pos: &Vector3<f32>) -> Option<AreaInfo> {
+        // AreaInfoCallback in TC / AC
+        let mut info = AreaInfo::default();
+        let mut result = false;
+        let mut callback = |idx: &usize| {
+            if let Some((instance, model)) = self.loaded_instance(models, *idx) {
+                let before = info.ground_z;
+                instance.intersect_point(model, pos, &mut info);
+                result |= info.ground_z > before;
+            }
+            true
+        };
+        self.tree
+            .traverse_depth_first(&mut PointIntersectionsVisitor::new(&(*pos).into(), &mut callback));
+        result.then_some(info)
+    }
+
+    /// GetLocationInfo in TC / AC. Position is in the internal vmap representation.
+    pub fn get_location_info<'a>(&'a self, models: &'a Assets<WorldModel>, pos: &Vector3<f32>) -> Option<LocationInfo<'a>> {
+        // LocationInfoCallback in TC / AC
+        let mut info = LocationInfo::default();
+        let mut result = false;
+        let mut callback = |idx: &usize| {
+            if let Some((instance, model)) = self.loaded_instance(models, *idx) {
+                result |= instance.get_location_info(model, pos, &mut info);
+            }
+            true
+        };
+        self.tree
+            .traverse_depth_first(&mut PointIntersectionsVisitor::new(&(*pos).into(), &mut callback));
+        result.then_some(info)
+    }
+
     pub fn map_file_name<P: AsRef<Path>, M: Num + Display>(dir: P, map_id: M) -> PathBuf {
         dir.as_ref().join(format!("{map_id:04}.vmtree"))
     }
@@ -255,6 +426,41 @@ impl StaticMapTree {
         Ok(())
     }
 
+    /// CanLoadMap in TC / AC, checks that both the map tree and the tile files (or the ones of its parent maps) exist and
+    /// are of the expected version.
+    pub fn can_load_map<C, L, V>(map_id: u32, tile_x: u16, tile_y: u16, vm: &VMapMgr2Helper<C, L, V>) -> VmapLoadResult<()>
+    where
+        C: VmapConfig,
+        L: LiquidFlagsGetter,
+        V: VmapDisabledChecker,
+    {
+        Self::check_vmap_file_version(Self::map_file_name(vm.cfg_mgr.vmaps_dir(), map_id))?;
+        let mut not_found = io::Error::from(io::ErrorKind::NotFound);
+        let mut used_map_id = Some(&map_id);
+        while let Some(map_id) = used_map_id {
+            match Self::check_vmap_file_version(Self::get_tile_file_name(vm.cfg_mgr.vmaps_dir(), *map_id, tile_x, tile_y)) {
+                Err(VmapLoadError::FileNotFound(e)) => {
+                    not_found = e;
+                    used_map_id = vm.parent_map_data.get(map_id);
+                },
+                res => return res,
+            }
+        }
+        Err(VmapLoadError::FileNotFound(not_found))
+    }
+
+    fn check_vmap_file_version(path: PathBuf) -> VmapLoadResult<()> {
+        let mut f = buffered_file_open(path)?;
+        let mut magic = [0u8; VMAP_MAGIC.len()];
+        if f.read_exact(&mut magic).is_err() || &magic != VMAP_MAGIC {
+            return Err(VmapLoadError::VersionMismatched {
+                got:      String::from_utf8_lossy(&magic).to_string(),
+                expected: String::from_utf8_lossy(VMAP_MAGIC).to_string(),
+            });
+        }
+        Ok(())
+    }
+
     fn open_map_tile_spawns_file<C, L, V>(map_id: u32, x: u16, y: u16, vm: &VMapMgr2Helper<C, L, V>) -> AzResult<TileFileOpenResult>
     where
         C: VmapConfig,
@@ -313,21 +519,13 @@ struct TileFileOpenResult {
 //         private:
 
 //         private:
-//             bool getIntersectionTime(const G3D::Ray& pRay, float &pMaxDist, bool pStopAtFirstHit, ModelIgnoreFlags ignoreFlags) const;
 //             //bool containsLoadedMapTile(unsigned int pTileIdent) const { return(iLoadedMapTiles.containsKey(pTileIdent)); }
 //         public:
 
 //             StaticMapTree(uint32 mapID, const std::string &basePath);
 //             ~StaticMapTree();
 
-//             bool isInLineOfSight(const G3D::Vector3& pos1, const G3D::Vector3& pos2, ModelIgnoreFlags ignoreFlags) const;
-//             bool getObjectHitPos(const G3D::Vector3& pos1, const G3D::Vector3& pos2, G3D::Vector3& pResultHitPos, float pModifyDist) const;
-//             float getHeight(const G3D::Vector3& pPos, float maxSearchDist) const;
-//             bool getAreaInfo(G3D::Vector3 &pos, uint32 &flags, int32 &adtId, int32 &rootId, int32 &groupId) const;
-//             bool GetLocationInfo(const G3D::Vector3 &pos, LocationInfo &info) const;
-
 //             bool InitMap(std::string const& fname);
-//             void UnloadMap(VMapManager2* vm);
 //             uint32 numLoadedTiles() const { return uint32(iLoadedTiles.size()); }
 
 //         private:
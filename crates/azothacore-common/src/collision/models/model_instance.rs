@@ -1,9 +1,22 @@
 use bevy::asset::Handle as AssetHandle;
 use flagset::{flags, FlagSet};
 use nalgebra::{Matrix3, Vector3};
-use parry3d::bounding_volume::Aabb;
+use parry3d::{
+    bounding_volume::Aabb,
+    query::{Ray, RayCast},
+};
 
-use crate::{collision::models::world_model::WorldModel, deref_boilerplate, g3dlite_copied::matrix3_from_euler_angles_zyx};
+use crate::{
+    collision::{
+        maps::map_tree::{AreaInfo, LocationInfo},
+        models::{
+            world_model::{GroupModel, WorldModel},
+            ModelIgnoreFlags,
+        },
+    },
+    deref_boilerplate,
+    g3dlite_copied::matrix3_from_euler_angles_zyx,
+};
 
 #[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize)]
 pub struct VmapModelSpawnWithMapId {
@@ -91,4 +104,83 @@ impl ModelInstance {
             model,
         }
     }
+
+    /// ModelInstance::intersectRay in TC / AC, returns the distance to the hit if there is one within `max_dist`.
+    pub fn intersect_ray(&self, model: &WorldModel, ray: &Ray, max_dist: f32, stop_at_first_hit: bool, ignore_flags: FlagSet<ModelIgnoreFlags>) -> Option<f32> {
+        // M2 models are not taken into account for LoS calculation if caller requested their ignoring.
+        if ignore_flags.contains(ModelIgnoreFlags::M2) && self.flags.contains(ModelFlags::ModM2) {
+            return None;
+        }
+        if let Some(bound) = &self.bound {
+            bound.cast_local_ray(ray, f32::MAX, true)?;
+        }
+        // child bounds are defined in object space:
+        let p = self.inv_rot * (ray.origin.coords - self.i_pos) * self.inv_scale;
+        let mod_ray = Ray::new(p.into(), self.inv_rot * ray.dir);
+        let distance = max_dist * self.inv_scale;
+        model.intersect_ray(&mod_ray, distance, stop_at_first_hit).map(|d| d * self.i_scale)
+    }
+
+    /// ModelInstance::intersectPoint in TC / AC
+    pub fn intersect_point(&self, model: &WorldModel, p: &Vector3<f32>, info: &mut AreaInfo) {
+        let Some((group_model, world_z)) = self.model_ground(model, p) else {
+            return;
+        };
+        if info.ground_z < world_z {
+            info.ground_z = world_z;
+            info.adt_id = self.adt_id;
+            info.root_id = model.root_wmoid();
+            info.group_id = group_model.i_group_wmoid;
+            info.flags = group_model.i_mogp_flags;
+        }
+    }
+
+    /// ModelInstance::GetLocationInfo in TC / AC
+    pub fn get_location_info<'a>(&'a self, model: &'a WorldModel, p: &Vector3<f32>, info: &mut LocationInfo<'a>) -> bool {
+        let Some((group_model, world_z)) = self.model_ground(model, p) else {
+            return false;
+        };
+        if info.ground_z < world_z {
+            info.root_id = model.root_wmoid();
+            info.hit_model = Some(group_model);
+            info.hit_instance = Some(self);
+            info.ground_z = world_z;
+            return true;
+        }
+        false
+    }
+
+    /// ModelInstance::GetLiquidLevel in TC / AC
+    pub fn get_liquid_level(&self, p: &Vector3<f32>, info: &LocationInfo) -> Option<f32> {
+        // child bounds are defined in object space:
+        let p_model = self.inv_rot * (p - self.i_pos) * self.inv_scale;
+        let z_dist = info.hit_model?.get_liquid_level(&p_model)?;
+        // calculate world height (zDist in model coords):
+        // assume WMO not tilted (wouldn't make much sense anyway)
+        Some(z_dist * self.i_scale + self.i_pos.z)
+    }
+
+    /// Shared lookup of ModelInstance::intersectPoint and ModelInstance::GetLocationInfo, returning the group model
+    /// hit below `p` along with the world height of its floor.
+    fn model_ground<'a>(&self, model: &'a WorldModel, p: &Vector3<f32>) -> Option<(&'a GroupModel, f32)> {
+        // M2 files don't contain area info, only WMO files
+        if self.flags.contains(ModelFlags::ModM2) {
+            return None;
+        }
+        if let Some(bound) = &self.bound {
+            if !bound.contains_local_point(&(*p).into()) {
+                return None;
+            }
+        }
+        // child bounds are defined in object space:
+        let p_model = self.inv_rot * (p - self.i_pos) * self.inv_scale;
+        let z_dir_model = self.inv_rot * Vector3::new(0.0, 0.0, -1.0);
+        let (group_model, z_dist) = model.intersect_point(&p_model, &z_dir_model)?;
+        let model_ground = p_model + z_dist * z_dir_model;
+        // Transform back to world space. Note that:
+        // Mat * vec == vec * Mat.transpose()
+        // and for rotation matrices: Mat.inverse() == Mat.transpose()
+        let world_z = ((self.inv_rot.transpose() * model_ground) * self.i_scale + self.i_pos).z;
+        Some((group_model, world_z))
+    }
 }
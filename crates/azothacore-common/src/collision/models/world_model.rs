@@ -2,7 +2,16 @@ use std::{io, marker::PhantomData};
 
 use bevy::{asset::Asset, reflect::TypePath};
 use nalgebra::{DMatrix, Vector3};
-use parry3d::{bounding_volume::Aabb, partitioning::Qbvh, shape::TriMesh};
+use parry3d::{
+    bounding_volume::Aabb,
+    partitioning::Qbvh,
+    query::{
+        visitors::{PointIntersectionsVisitor, RayIntersectionsVisitor},
+        Ray,
+        RayCast,
+    },
+    shape::TriMesh,
+};
 
 use crate::{
     cmp_or_return,
@@ -91,13 +100,62 @@ impl WorldModel {
             group_tree,
         })
     }
-    //     //! pass group models to WorldModel and create BIH. Passed vector is swapped with old geometry!
-    //     bool IntersectRay(const G3D::Ray &ray, float &distance, bool stopAtFirstHit, ModelIgnoreFlags ignoreFlags) const;
-    //     bool IntersectPoint(const G3D::Vector3 &p, const G3D::Vector3 &down, float &dist, AreaInfo &info) const;
-    //     bool GetLocationInfo(const G3D::Vector3 &p, const G3D::Vector3 &down, float &dist, LocationInfo &info) const;
-    //     void getGroupModels(std::vector<GroupModel>& outGroupModels);
-    //     uint32 Flags;
-    // protected:
+
+    pub fn root_wmoid(&self) -> u32 {
+        self.root_wmoid
+    }
+
+    /// WorldModel::IntersectRay in TC / AC, returns the distance to the hit if there is one within `max_dist`.
+    ///
+    /// The M2 ignore flag check is done by [ModelInstance::intersect_ray](crate::collision::models::model_instance::ModelInstance::intersect_ray)
+    /// instead, as the model flags are kept on the spawn.
+    pub fn intersect_ray(&self, ray: &Ray, max_dist: f32, stop_at_first_hit: bool) -> Option<f32> {
+        // small M2 workaround, maybe better make separate class with virtual intersection funcs
+        // in any case, there's no need to use a bound tree if we only have one submodel
+        if let [group_model] = self.group_models.as_slice() {
+            return group_model.intersect_ray(ray, max_dist);
+        }
+        // WModelRayCallBack in TC / AC
+        let mut distance = max_dist;
+        let mut hit = false;
+        let mut callback = |idx: &usize| {
+            if let Some(d) = self.group_models[*idx].intersect_ray(ray, distance) {
+                distance = d;
+                hit = true;
+                return !stop_at_first_hit;
+            }
+            true
+        };
+        self.group_tree
+            .traverse_depth_first(&mut RayIntersectionsVisitor::new(ray, max_dist, &mut callback));
+        hit.then_some(distance)
+    }
+
+    /// WorldModel::IntersectPoint / WorldModel::GetLocationInfo in TC / AC
+    ///
+    /// Both look for the closest group model below `p` in the `down` direction, returning it along with the distance
+    /// to its floor.
+    pub fn intersect_point(&self, p: &Vector3<f32>, down: &Vector3<f32>) -> Option<(&GroupModel, f32)> {
+        if self.group_models.is_empty() {
+            return None;
+        }
+        // WModelAreaCallback in TC / AC
+        let mut hit = None;
+        let mut z_dist = f32::INFINITY;
+        let mut callback = |idx: &usize| {
+            let group_model = &self.group_models[*idx];
+            if let Some(group_z) = group_model.is_inside_object(p, down) {
+                if group_z < z_dist {
+                    z_dist = group_z;
+                    hit = Some(group_model);
+                }
+            }
+            true
+        };
+        self.group_tree
+            .traverse_depth_first(&mut PointIntersectionsVisitor::new(&(*p).into(), &mut callback));
+        hit.map(|g| (g, z_dist))
+    }
 }
 
 /// holding additional info for WMO group files
@@ -153,6 +211,32 @@ impl GroupModel {
         Ok(s)
     }
 
+    /// GroupModel::IntersectRay in TC / AC, returns the distance to the closest triangle hit within `max_dist`.
+    pub fn intersect_ray(&self, ray: &Ray, max_dist: f32) -> Option<f32> {
+        self.mesh.as_ref()?.cast_local_ray(ray, max_dist, false)
+    }
+
+    /// GroupModel::IsInsideObject in TC / AC, returns the distance from `pos` to the floor of this group if
+    /// there is one below it.
+    pub fn is_inside_object(&self, pos: &Vector3<f32>, down: &Vector3<f32>) -> Option<f32> {
+        if self.mesh.is_none() || !self.i_bound.contains_local_point(&(*pos).into()) {
+            return None;
+        }
+        let r_pos = pos - 0.1 * down;
+        let ray = Ray::new(r_pos.into(), *down);
+        self.intersect_ray(&ray, f32::MAX).map(|dist| dist - 0.1)
+    }
+
+    /// GroupModel::GetLiquidLevel in TC / AC
+    pub fn get_liquid_level(&self, pos: &Vector3<f32>) -> Option<f32> {
+        self.i_liquid.as_ref()?.get_liquid_height(pos)
+    }
+
+    /// GroupModel::GetLiquidType in TC / AC
+    pub fn get_liquid_type(&self) -> u32 {
+        self.i_liquid.as_ref().map_or(0, |l| l.i_type)
+    }
+
     // public:
     // GroupModel() : iBound(), iMogpFlags(0), iGroupWMOID(0), iLiquid(NULL) { }
     // GroupModel(const GroupModel &other);
@@ -163,10 +247,6 @@ impl GroupModel {
     // //! pass mesh data to object and create BIH. Passed vectors get get swapped with old geometry!
     // void setMeshData(std::vector<G3D::Vector3> &vert, std::vector<MeshTriangle> &tri);
     // void setLiquidData(WmoLiquid*& liquid) { iLiquid = liquid; liquid = NULL; }
-    // bool IntersectRay(const G3D::Ray &ray, float &distance, bool stopAtFirstHit) const;
-    // bool IsInsideObject(const G3D::Vector3 &pos, const G3D::Vector3 &down, float &z_dist) const;
-    // bool GetLiquidLevel(const G3D::Vector3 &pos, float &liqHeight) const;
-    // uint32 GetLiquidType() const;
     // const G3D::AABox& GetBound() const { return iBound; }
     // uint32 GetMogpFlags() const { return iMogpFlags; }
     // uint32 GetWmoID() const { return iGroupWMOID; }
@@ -1,13 +1,21 @@
 use std::{
+    collections::{BTreeMap, BTreeSet},
     fmt::Debug,
+    fs,
     hash::Hash,
     marker::PhantomData,
     ops::{Deref, DerefMut},
     path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime},
 };
 
-use bevy::prelude::{App, Commands, Event, EventReader, EventWriter, FixedUpdate, IntoSystemConfigs, PreStartup, ResMut, Resource, SystemSet};
-use tracing::{error, warn};
+use bevy::prelude::{App, Commands, Event, EventReader, EventWriter, FixedUpdate, IntoSystemConfigs, PreStartup, Res, ResMut, Resource, SystemSet};
+use figment::{
+    providers::Serialized,
+    value::{Dict, Value},
+    Figment,
+};
+use tracing::{error, info, warn};
 
 #[allow(non_snake_case)]
 mod structs;
@@ -19,9 +27,29 @@ use crate::{
     AzResult,
 };
 
-pub trait Config: serde::de::DeserializeOwned + Send + Sync + 'static {
+pub trait Config: serde::de::DeserializeOwned + serde::Serialize + Send + Sync + 'static {
+    /// Keys of the values that only take effect after a restart, i.e. `BindIP`. A key covers the values
+    /// nested in it too, i.e. `LoginDatabaseInfo` covers `LoginDatabaseInfo.Address`.
+    const RESTART_REQUIRED_KEYS: &'static [&'static str] = &[];
+
     fn load<P: AsRef<Path>>(config_toml: P) -> AzResult<Self> {
-        from_env_toml(config_toml)
+        Self::load_with_includes(config_toml, None)
+    }
+
+    /// Loads the config with the toml files of `include_dir` merged over it, see [from_env_toml_with_includes]
+    fn load_with_includes<P: AsRef<Path>>(config_toml: P, include_dir: Option<&Path>) -> AzResult<Self> {
+        Self::load_from_figment(&env_toml_figment_with_includes(config_toml, include_dir))
+    }
+
+    /// Deserialises the config from its merged sources and validates it
+    fn load_from_figment(fig: &Figment) -> AzResult<Self> {
+        extract_config::<Self>(fig)?.validate()
+    }
+
+    /// Checks and adjusts a freshly loaded config, i.e. clamping values that depend on each other.
+    /// A config is only used or reloaded if it passes.
+    fn validate(self) -> AzResult<Self> {
+        Ok(self)
     }
 
     fn reload(&mut self, new: Self) {
@@ -31,9 +59,13 @@ pub trait Config: serde::de::DeserializeOwned + Send + Sync + 'static {
 
 #[derive(Resource)]
 pub struct ConfigMgr<C> {
-    pub filename:   PathBuf,
-    pub is_dry_run: bool,
-    config:         C,
+    pub filename:    PathBuf,
+    /// Directory of toml files merged over the config file, i.e. `conf.d`
+    pub include_dir: Option<PathBuf>,
+    pub is_dry_run:  bool,
+    config:          C,
+    /// The values that the config was deserialised from, see [env_toml_figment_with_includes]
+    source:          Dict,
 }
 
 impl<C> Deref for ConfigMgr<C> {
@@ -53,12 +85,61 @@ impl<C> DerefMut for ConfigMgr<C> {
 #[derive(Event)]
 pub struct ConfigReloadEvent;
 
+/// The keys that differ between two configs, see [ConfigChanges::between]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ConfigChanges {
+    /// Keys of the values that changed, i.e. `LoginREST.Port`
+    pub changed:          Vec<String>,
+    /// The changed keys that keep their current value until a restart, see [Config::RESTART_REQUIRED_KEYS]
+    pub restart_required: Vec<String>,
+}
+
+impl ConfigChanges {
+    /// Compares the serialised values of both configs. Arrays are compared as a whole, so a change to any of the
+    /// appenders of a config reports `Appender`.
+    pub fn between<C: Config>(old: &C, new: &C) -> AzResult<Self> {
+        let mut changed = vec![];
+        diff_config_keys("", &serde_json::to_value(old)?, &serde_json::to_value(new)?, &mut changed);
+        let restart_required = changed
+            .iter()
+            .filter(|k| {
+                C::RESTART_REQUIRED_KEYS
+                    .iter()
+                    .any(|r| k.as_str() == *r || k.strip_prefix(*r).is_some_and(|rest| rest.starts_with('.')))
+            })
+            .cloned()
+            .collect();
+        Ok(Self { changed, restart_required })
+    }
+}
+
+fn diff_config_keys(prefix: &str, old: &serde_json::Value, new: &serde_json::Value, out: &mut Vec<String>) {
+    let (serde_json::Value::Object(old), serde_json::Value::Object(new)) = (old, new) else {
+        if old != new {
+            out.push(prefix.to_string());
+        }
+        return;
+    };
+    for k in old.keys().chain(new.keys()).collect::<BTreeSet<_>>() {
+        let key = if prefix.is_empty() { k.clone() } else { format!("{prefix}.{k}") };
+        match (old.get(k), new.get(k)) {
+            (Some(o), Some(n)) => diff_config_keys(&key, o, n, out),
+            _ => out.push(key),
+        }
+    }
+}
+
+/// Sent once a reload of the config `C` was attempted, see [config_mgr_plugin]
 #[derive(Event)]
-pub struct ConfigReloadFinishedEvent<C>(PhantomData<C>);
+pub struct ConfigReloadFinishedEvent<C> {
+    /// What changed, or why the new config was rejected, in which case the current one is kept
+    pub result: Result<ConfigChanges, String>,
+    _config:    PhantomData<C>,
+}
 
-impl<C> Default for ConfigReloadFinishedEvent<C> {
-    fn default() -> Self {
-        Self(PhantomData)
+impl<C> ConfigReloadFinishedEvent<C> {
+    pub fn new(result: Result<ConfigChanges, String>) -> Self {
+        Self { result, _config: PhantomData }
     }
 }
 
@@ -130,8 +211,18 @@ impl<C> Eq for ConfigMgrSet<C> {}
 /// Users can emit an [ConfigReloadEvent] if they would like to attempt to
 /// reload their config file. This is reload will be done during the [FixedUpdate]
 /// schedule and a corresponding [ConfigReloadFinishedEvent<C>] will be emitted
-/// to show that a reload was attempted successfully
+/// with the keys that changed, or why the reload failed.
 pub fn config_mgr_plugin<C, P>(init_file_name: P, dry_run: bool) -> impl Fn(&mut App)
+where
+    C: Config,
+    P: AsRef<Path>,
+{
+    config_mgr_plugin_with_includes::<C, P>(init_file_name, None, dry_run)
+}
+
+/// Like [config_mgr_plugin], but with the toml files of `include_dir` merged over the config file,
+/// see [from_env_toml_with_includes]
+pub fn config_mgr_plugin_with_includes<C, P>(init_file_name: P, include_dir: Option<PathBuf>, dry_run: bool) -> impl Fn(&mut App)
 where
     C: Config,
     P: AsRef<Path>,
@@ -141,7 +232,10 @@ where
         let f = filename.clone();
         app.add_event::<ConfigReloadEvent>()
             .add_event::<ConfigReloadFinishedEvent<C>>()
-            .add_systems(PreStartup, load_initial_configs::<_, C>(f, dry_run).in_set(ConfigMgrSet::<C>::load_initial()))
+            .add_systems(
+                PreStartup,
+                load_initial_configs::<_, C>(f, include_dir.clone(), dry_run).in_set(ConfigMgrSet::<C>::load_initial()),
+            )
             .add_systems(
                 FixedUpdate,
                 reload_config::<C>.run_if(az_startup_succeeded()).in_set(ConfigMgrSet::<C>::reload()),
@@ -149,14 +243,98 @@ where
     }
 }
 
+/// How long edits to watched config files have to settle before they are reloaded, see [config_watcher_plugin]
+pub const DEFAULT_CONFIG_WATCH_DEBOUNCE: Duration = Duration::from_secs(1);
+
+/// Opt-in watcher for the config file of [ConfigMgr<C>] and the files in its include directory, registered in
+/// addition to [config_mgr_plugin].
+///
+/// Once an edit to any of them, or a file being added to or removed from the include directory, has settled
+/// for `debounce`, i.e. an editor is done saving it, a [ConfigReloadEvent] is sent.
+/// The files are polled four times per `debounce`.
+pub fn config_watcher_plugin<C: Config>(debounce: Duration) -> impl Fn(&mut App) {
+    move |app: &mut App| {
+        app.insert_resource(ConfigWatcher::<C> {
+            debounce,
+            last_poll: None,
+            snapshot: None,
+            pending: None,
+            _config: PhantomData,
+        })
+        .add_systems(
+            FixedUpdate,
+            watch_config_files::<C>.run_if(az_startup_succeeded()).before(ConfigMgrSet::<C>::reload()),
+        );
+    }
+}
+
+/// Modification time and length of each watched file, [None] if it couldn't be read
+type ConfigFilesSnapshot = BTreeMap<PathBuf, Option<(SystemTime, u64)>>;
+
+#[derive(Resource)]
+struct ConfigWatcher<C> {
+    debounce:  Duration,
+    last_poll: Option<Instant>,
+    /// The files as of the last reload
+    snapshot:  Option<ConfigFilesSnapshot>,
+    /// The files as they were when they were first seen to differ from the snapshot, and when that was
+    pending:   Option<(Instant, ConfigFilesSnapshot)>,
+    _config:   PhantomData<C>,
+}
+
+fn config_files_snapshot(filename: &Path, include_dir: Option<&Path>) -> ConfigFilesSnapshot {
+    std::iter::once(filename.to_path_buf())
+        .chain(include_dir.map(config_include_files).unwrap_or_default())
+        .map(|p| {
+            let meta = fs::metadata(&p).and_then(|m| m.modified().map(|t| (t, m.len()))).ok();
+            (p, meta)
+        })
+        .collect()
+}
+
+fn watch_config_files<C: Config>(mut watcher: ResMut<ConfigWatcher<C>>, cfg: Res<ConfigMgr<C>>, mut ev_reload: EventWriter<ConfigReloadEvent>) {
+    let watcher = &mut *watcher;
+    let now = Instant::now();
+    if watcher.last_poll.is_some_and(|l| now.duration_since(l) < watcher.debounce / 4) {
+        return;
+    }
+    watcher.last_poll = Some(now);
+    let current = config_files_snapshot(&cfg.filename, cfg.include_dir.as_deref());
+    let Some(snapshot) = &watcher.snapshot else {
+        watcher.snapshot = Some(current);
+        return;
+    };
+    if *snapshot == current {
+        watcher.pending = None;
+        return;
+    }
+    match &watcher.pending {
+        Some((since, pending)) if *pending == current => {
+            if now.duration_since(*since) < watcher.debounce {
+                return;
+            }
+            info!(path=%cfg.filename.display(), "config files changed, reloading");
+            watcher.snapshot = Some(current);
+            watcher.pending = None;
+            ev_reload.send(ConfigReloadEvent);
+        },
+        // Still being written to, wait for it to settle
+        _ => watcher.pending = Some((now, current)),
+    }
+}
+
 // Loads the main app configuration.
-fn load_initial_configs<P, C>(path: P, is_dry_run: bool) -> impl FnMut(Commands, EventWriter<AzStartupDryRunEvent>, EventWriter<AzStartupFailedEvent>)
+fn load_initial_configs<P, C>(
+    path: P,
+    include_dir: Option<PathBuf>,
+    is_dry_run: bool,
+) -> impl FnMut(Commands, EventWriter<AzStartupDryRunEvent>, EventWriter<AzStartupFailedEvent>)
 where
     P: AsRef<Path>,
     C: Config,
 {
     move |mut commands: Commands, mut ev_startup_dryrun: EventWriter<AzStartupDryRunEvent>, mut ev_startup_failed: EventWriter<AzStartupFailedEvent>| {
-        let cfg: C = match Config::load(&path) {
+        let cfg_mgr = match ConfigMgr::<C>::load(path.as_ref().to_path_buf(), include_dir.clone(), is_dry_run) {
             Err(e) => {
                 ev_startup_failed.send_default();
                 error!(cause=?e, "error initialising config");
@@ -164,11 +342,7 @@ where
             },
             Ok(c) => c,
         };
-        commands.insert_resource(ConfigMgr {
-            filename: path.as_ref().to_path_buf(),
-            config: cfg,
-            is_dry_run,
-        });
+        commands.insert_resource(cfg_mgr);
 
         if is_dry_run {
             ev_startup_dryrun.send_default();
@@ -183,32 +357,86 @@ fn reload_config<C>(
 ) where
     C: Config,
 {
-    let mut reloaded = false;
-    let mut attempted_reload = false;
-    for _ev in reload_cfg_events.read() {
-        if !attempted_reload {
-            // Reloading configuraion should not fail at all, for now we just log
-            if let Err(err) = cfg.reload_from_path() {
-                warn!(cause=?err, path=%cfg.filename.display(), "error reloading from path, using old configs");
-            } else {
-                reloaded = true;
-            }
-            attempted_reload = true;
-        }
-    }
-    if reloaded {
-        ev_cfg_reload_finished.send_default();
+    if reload_cfg_events.read().count() == 0 {
+        return;
     }
+    let result = match cfg.reload_from_path() {
+        Err(err) => {
+            error!(cause=?err, path=%cfg.filename.display(), "error reloading from path, using old configs");
+            Err(format!("{err:#}"))
+        },
+        Ok(changes) => {
+            info!(path=%cfg.filename.display(), changed=?changes.changed, "reloaded config");
+            if !changes.restart_required.is_empty() {
+                warn!(keys=?changes.restart_required, "some of the changed config values can't be changed on reload, using their current values until a restart");
+            }
+            Ok(changes)
+        },
+    };
+    ev_cfg_reload_finished.send(ConfigReloadFinishedEvent::new(result));
 }
 
 impl<C> ConfigMgr<C>
 where
     C: Config,
 {
-    pub fn reload_from_path(&mut self) -> AzResult<()> {
-        let new = C::load(&self.filename)?;
+    /// Loads the config from `filename`, with the toml files of `include_dir` merged over it
+    pub fn load(filename: PathBuf, include_dir: Option<PathBuf>, is_dry_run: bool) -> AzResult<Self> {
+        let fig = env_toml_figment_with_includes(&filename, include_dir.as_deref());
+        let config = C::load_from_figment(&fig)?;
+        let source = extract_config(&fig)?;
+        Ok(Self {
+            filename,
+            include_dir,
+            is_dry_run,
+            config,
+            source,
+        })
+    }
+
+    /// Loads the config again, only replacing the current one if the new config is valid as a whole.
+    ///
+    /// The values of [Config::RESTART_REQUIRED_KEYS] keep their current values. They are restored in the sources of
+    /// the new config rather than in the config itself, as not every value deserialises from what it serialises to.
+    pub fn reload_from_path(&mut self) -> AzResult<ConfigChanges> {
+        let fig = env_toml_figment_with_includes(&self.filename, self.include_dir.as_deref());
+        let mut new = C::load_from_figment(&fig)?;
+        let mut source = extract_config::<Dict>(&fig)?;
+        let changes = ConfigChanges::between(&self.config, &new)?;
+        if !changes.restart_required.is_empty() {
+            for key in C::RESTART_REQUIRED_KEYS {
+                restore_source_value(&self.source, &mut source, key);
+            }
+            new = C::load_from_figment(&Figment::from(Serialized::defaults(&source)))?;
+        }
+        self.source = source;
         self.config.reload(new);
-        Ok(())
+        Ok(changes)
+    }
+}
+
+/// Sets the value of the dotted `key` in `new` to its value in `old`, removing it if `old` has none so that it
+/// falls back to its default again
+fn restore_source_value(old: &Dict, new: &mut Dict, key: &str) {
+    match key.split_once('.') {
+        None => match old.get(key) {
+            Some(v) => {
+                new.insert(key.to_string(), v.clone());
+            },
+            None => {
+                new.remove(key);
+            },
+        },
+        Some((head, rest)) => {
+            let empty = Dict::new();
+            let old = match old.get(head) {
+                Some(Value::Dict(_, d)) => d,
+                _ => &empty,
+            };
+            if let Value::Dict(_, new) = new.entry(head.to_string()).or_insert_with(|| Value::from(Dict::new())) {
+                restore_source_value(old, new, rest);
+            }
+        },
     }
 }
 
@@ -223,7 +451,11 @@ mod tests {
     use tokio::sync::mpsc::{channel, error::TryRecvError};
 
     use super::*;
-    use crate::bevy_app::{bevy_app, AzStartupState, DEFAULT_FRAME_RATE};
+    use crate::{
+        bevy_app::{bevy_app, AzStartupState, DEFAULT_FRAME_RATE},
+        bounded_nums::LowerBoundedNum,
+        durationb_s,
+    };
 
     #[derive(Deserialize, Serialize, DefaultFromSerde, PartialEq)]
     pub struct TestConfig {
@@ -279,6 +511,184 @@ mod tests {
         });
     }
 
+    #[test]
+    fn it_keeps_the_cfg_if_the_reloaded_one_is_invalid() {
+        Jail::expect_with(|jail| {
+            let cfg_path = "config.toml";
+            jail.create_file(cfg_path, "integer = 3")?;
+            let mut app = bevy_app();
+            let (snd, mut rcv) = channel(1);
+            let closure = move |mut ev_reloaded: EventReader<ConfigReloadFinishedEvent<TestConfig>>| {
+                for ev in ev_reloaded.read() {
+                    snd.try_send(ev.result.clone()).unwrap();
+                }
+            };
+            app.add_plugins(config_mgr_plugin::<TestConfig, _>(cfg_path, false))
+                .add_systems(Update, closure);
+            app.update();
+
+            jail.create_file(cfg_path, "integer = \"three\"")?;
+            app.world_mut().send_event(ConfigReloadEvent);
+            // Simulate fixed update
+            sleep(Duration::from_secs_f64(2.0 / DEFAULT_FRAME_RATE));
+            app.update();
+            assert!(rcv.try_recv().unwrap().is_err());
+            assert_eq!(app.world().resource::<ConfigMgr<TestConfig>>().integer, 3);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn it_reloads_cfg_mgr_when_watched_files_change() {
+        Jail::expect_with(|jail| {
+            let cfg_path = "config.toml";
+            jail.create_file(cfg_path, "integer = 3")?;
+            jail.create_dir("conf.d")?;
+            let mut app = bevy_app();
+            let (snd, mut rcv) = channel(1);
+            let closure = move |mut ev_reloaded: EventReader<ConfigReloadFinishedEvent<TestConfig>>| {
+                for ev in ev_reloaded.read() {
+                    snd.try_send(ev.result.clone()).unwrap();
+                }
+            };
+            app.add_plugins((
+                config_mgr_plugin_with_includes::<TestConfig, _>(cfg_path, Some("conf.d".into()), false),
+                config_watcher_plugin::<TestConfig>(Duration::ZERO),
+            ))
+            .add_systems(Update, closure);
+            // Let the watcher take its first look at the files
+            for _ in 0..3 {
+                sleep(Duration::from_secs_f64(2.0 / DEFAULT_FRAME_RATE));
+                app.update();
+            }
+            assert_eq!(rcv.try_recv(), Err(TryRecvError::Empty));
+
+            jail.create_file("conf.d/override.toml", "integer = 5")?;
+            let mut res = Err(TryRecvError::Empty);
+            for _ in 0..20 {
+                sleep(Duration::from_secs_f64(2.0 / DEFAULT_FRAME_RATE));
+                app.update();
+                res = rcv.try_recv();
+                if res.is_ok() {
+                    break;
+                }
+            }
+            assert_eq!(
+                res,
+                Ok(Ok(ConfigChanges {
+                    changed:          vec!["integer".to_string()],
+                    restart_required: vec![],
+                }))
+            );
+            assert_eq!(app.world().resource::<ConfigMgr<TestConfig>>().integer, 5);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn it_reports_changed_and_restart_required_keys() {
+        #[derive(Deserialize, Serialize, DefaultFromSerde, Clone)]
+        struct NestedConfig {
+            #[serde(default)]
+            port: u16,
+            #[serde(default)]
+            name: String,
+        }
+
+        #[derive(Deserialize, Serialize, DefaultFromSerde, Clone)]
+        struct RestartConfig {
+            #[serde(default)]
+            db:      NestedConfig,
+            #[serde(default)]
+            rate:    f32,
+            #[serde(default)]
+            bind_ip: String,
+            #[serde(default)]
+            list:    Vec<u32>,
+        }
+
+        impl Config for RestartConfig {
+            const RESTART_REQUIRED_KEYS: &'static [&'static str] = &["db", "bind_ip"];
+        }
+
+        let old = RestartConfig::default();
+        let mut new = old.clone();
+        new.db.port = 1;
+        new.rate = 2.0;
+        new.list.push(3);
+        assert_eq!(
+            ConfigChanges::between(&old, &new).unwrap(),
+            ConfigChanges {
+                changed:          vec!["db.port".to_string(), "list".to_string(), "rate".to_string()],
+                restart_required: vec!["db.port".to_string()],
+            }
+        );
+        new = old.clone();
+        new.bind_ip = "127.0.0.1".to_string();
+        assert_eq!(ConfigChanges::between(&old, &new).unwrap().restart_required, vec!["bind_ip".to_string()]);
+    }
+
+    #[test]
+    fn it_keeps_restart_required_values_on_reload() {
+        #[derive(Deserialize, Serialize, DefaultFromSerde)]
+        struct NestedConfig {
+            #[serde(default)]
+            port:    u16,
+            #[serde(default)]
+            name:    String,
+            /// Serialises to something it cannot be deserialised from again
+            #[serde(default)]
+            timeout: LowerBoundedNum<Duration, { durationb_s!(0) }, { durationb_s!(5) }>,
+        }
+
+        #[derive(Deserialize, Serialize, DefaultFromSerde)]
+        struct RestartConfig {
+            #[serde(default)]
+            db:      NestedConfig,
+            #[serde(default)]
+            rate:    f32,
+            #[serde(default)]
+            bind_ip: String,
+        }
+
+        impl Config for RestartConfig {
+            const RESTART_REQUIRED_KEYS: &'static [&'static str] = &["db", "bind_ip"];
+        }
+
+        Jail::expect_with(|jail| {
+            let cfg_path = "config.toml";
+            jail.create_file(cfg_path, "rate = 1.0\n[db]\nport = 1\ntimeout = \"10s\"")?;
+            let mut cfg_mgr = ConfigMgr::<RestartConfig>::load(cfg_path.into(), None, false).unwrap();
+
+            jail.create_file(
+                cfg_path,
+                "rate = 2.0\nbind_ip = \"127.0.0.1\"\n[db]\nport = 2\nname = \"db\"\ntimeout = \"20s\"",
+            )?;
+            let changes = cfg_mgr.reload_from_path().unwrap();
+            assert_eq!(changes.restart_required, vec!["bind_ip", "db.name", "db.port", "db.timeout"]);
+            assert_eq!(cfg_mgr.rate, 2.0);
+            assert_eq!(cfg_mgr.bind_ip, "", "values that were left out before keep their defaults");
+            assert_eq!(
+                (cfg_mgr.db.port, cfg_mgr.db.name.as_str(), *cfg_mgr.db.timeout),
+                (1, "", Duration::from_secs(10))
+            );
+
+            // The kept values are not applied by a later reload either
+            jail.create_file(
+                cfg_path,
+                "rate = 3.0\nbind_ip = \"127.0.0.1\"\n[db]\nport = 2\nname = \"db\"\ntimeout = \"20s\"",
+            )?;
+            let changes = cfg_mgr.reload_from_path().unwrap();
+            assert_eq!(changes.changed, vec!["bind_ip", "db.name", "db.port", "db.timeout", "rate"]);
+            assert_eq!(cfg_mgr.rate, 3.0);
+            assert_eq!((cfg_mgr.db.port, cfg_mgr.bind_ip.as_str()), (1, ""));
+
+            Ok(())
+        });
+    }
+
     #[test]
     fn app_does_not_run_update_as_cfg_mgr_is_dry_run() {
         let mut app = bevy_app();
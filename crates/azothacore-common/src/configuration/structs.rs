@@ -1,7 +1,9 @@
 use std::{
     fmt::Debug,
+    fs,
     hash::Hash,
     path::{Path, PathBuf},
+    time::Duration,
 };
 
 use figment::{
@@ -14,16 +16,31 @@ use serde::{Deserialize, Serialize};
 use serde_default::DefaultFromSerde;
 use serde_inline_default::serde_inline_default;
 
-use crate::{AzResult, BASE_DIR};
+use crate::{bounded_nums::LowerBoundedNum, durationb_mins, durationb_s, AzResult, BASE_DIR};
 
 /// Gets a given config from both the env var and a toml file
 /// For env vars, key paths are split by double underscores "__"
 /// and are CASE-SENSITIVE
 pub fn from_env_toml<C: serde::de::DeserializeOwned, P: AsRef<Path>>(filepath: P) -> AzResult<C> {
-    let fig = Figment::new()
-        .merge(Toml::file(filepath))
-        .admerge(Env::prefixed("AZ__").split("__").lowercase(false));
+    from_env_toml_with_includes(filepath, None)
+}
+
+/// Like [from_env_toml], but the toml files found by [config_include_files] in `include_dir`
+/// are merged over the toml file first, i.e. to keep per-host overrides in a `conf.d` directory.
+pub fn from_env_toml_with_includes<C: serde::de::DeserializeOwned, P: AsRef<Path>>(filepath: P, include_dir: Option<&Path>) -> AzResult<C> {
+    extract_config(&env_toml_figment_with_includes(filepath, include_dir))
+}
 
+/// The sources of [from_env_toml_with_includes] merged together, before they are deserialised into a config
+pub fn env_toml_figment_with_includes<P: AsRef<Path>>(filepath: P, include_dir: Option<&Path>) -> Figment {
+    let mut fig = Figment::new().merge(Toml::file(filepath));
+    for include in include_dir.map(config_include_files).unwrap_or_default() {
+        fig = fig.merge(Toml::file(include));
+    }
+    fig.admerge(Env::prefixed("AZ__").split("__").lowercase(false))
+}
+
+pub fn extract_config<C: serde::de::DeserializeOwned>(fig: &Figment) -> AzResult<C> {
     Ok(fig.extract().map_err(|mut e| {
         // Replace the figment profile here b/c the error message produced
         //
@@ -34,6 +51,22 @@ pub fn from_env_toml<C: serde::de::DeserializeOwned, P: AsRef<Path>>(filepath: P
     })?)
 }
 
+/// The `*.toml` files in the include directory, in the order they are merged in, i.e. by their names.
+/// A missing directory has none.
+pub fn config_include_files(include_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(include_dir) else {
+        return vec![];
+    };
+    let mut files = entries
+        .filter_map(|e| {
+            let p = e.ok()?.path();
+            (p.is_file() && p.extension().is_some_and(|ext| ext == "toml")).then_some(p)
+        })
+        .collect::<Vec<_>>();
+    files.sort();
+    files
+}
+
 pub trait DataDirConfig {
     fn db2_dir(&self) -> PathBuf;
     fn cameras_dir(&self) -> PathBuf;
@@ -126,9 +159,10 @@ impl DatabaseType {
         }
     }
 
-    pub fn base_files_directory(&self) -> Option<PathBuf> {
+    /// Directory of the base SQL files for this database, in the given dialect's subdirectory
+    pub fn base_files_directory(&self, dialect_dir: &str) -> Option<PathBuf> {
         self.db_module_name()
-            .map(|db_module_name| format!("{BASE_DIR}/data/sql/base/{db_module_name}").into())
+            .map(|db_module_name| format!("{BASE_DIR}/data/sql/base/{dialect_dir}/{db_module_name}").into())
     }
 }
 
@@ -187,11 +221,12 @@ flags! {
     ///
     /// AddLogFilter (4) - Prefix Log Filter type to the text
     ///
-    /// AppendFileTimestamps (8) - Append timestamp to the log file name. This causes the file to roll daily
+    /// AppendFileTimestamps (8) - Append the date to the log file name. This causes the file to roll daily
     ///
-    /// TruncateFile (16) - Truncate file before writing
+    /// TruncateFile (16) - Truncate file before writing, instead of appending to it
     ///
-    /// BackupBeforeOverwrite (32) - Make a backup of existing file before overwrite, TruncateFile must be set
+    /// BackupBeforeOverwrite (32) - Make a backup of existing file before overwrite, TruncateFile must be set.
+    ///                              The backup is kept like a rotated file
     pub enum LogFlags: u8 {
         AddLogTimestamps        = 0b000001,
         AddLogLevel             = 0b000010,
@@ -232,15 +267,47 @@ pub enum LogAppender {
         // colours: Vec<(LogLevel, LogConsoleColours)>,
     },
     File {
+        name:          String,
+        min_level:     LogLevel,
+        max_level:     LogLevel,
+        flags:         FlagSet<LogFlags>,
+        file:          String,
+        // mode:  String,
+        /// Size in bytes the file is rotated at, 0 to never rotate it by size
+        #[serde(default)]
+        max_file_size: u64,
+        /// How many rotated files are kept, the oldest are removed first. 0 to keep all of them
+        #[serde(default)]
+        max_files:     usize,
+        /// Gzip the rotated files
+        #[serde(default)]
+        compress:      bool,
+    },
+    /// Writes one JSON object per event to `file`, including the fields of the spans the event is in.
+    /// Level, target and timestamp are always written, so only the file related flags apply.
+    /// The file is rotated like the one of [LogAppender::File].
+    Json {
+        name:          String,
+        min_level:     LogLevel,
+        max_level:     LogLevel,
+        flags:         FlagSet<LogFlags>,
+        file:          String,
+        #[serde(default)]
+        max_file_size: u64,
+        #[serde(default)]
+        max_files:     usize,
+        #[serde(default)]
+        compress:      bool,
+    },
+    /// Writes to the `logs` table of the auth DB in batches, without blocking the logging thread.
+    /// Events about writing the entries and the statements run by sqlx are never written, so that failing to
+    /// write logs doesn't log again, see [crate::log::DB_LOG_WRITER_TARGET].
+    /// AppenderDB in TC / AC
+    Db {
         name:      String,
         min_level: LogLevel,
         max_level: LogLevel,
-        flags:     FlagSet<LogFlags>,
-        file:      String,
-        // mode:  String,
     },
-    // TODO:
-    // Db {},
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
@@ -251,9 +318,25 @@ pub struct LogLoggerConfig {
     pub appenders: Vec<String>,
 }
 
+/// A target that is logged at a more verbose level for a while, i.e. to debug `server::rest` without a restart.
+///
+/// The appenders a logger already routes the target to log it down to `level`, even below their own `min_level`.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct LogLevelOverride {
+    /// The target, covering the targets nested in it too, i.e. `server::rest` covers `server::rest::login`
+    pub target:   String,
+    pub level:    LogLevel,
+    /// How long the override lasts once applied
+    #[serde(default)]
+    pub duration: LowerBoundedNum<Duration, { durationb_s!(0) }, { durationb_mins!(15) }>,
+}
+
 #[cfg(test)]
 mod tests {
-    use std::{path::PathBuf, time::Duration};
+    use std::{
+        path::{Path, PathBuf},
+        time::Duration,
+    };
 
     use figment::Jail;
     use flagset::FlagSet;
@@ -396,6 +479,32 @@ mod tests {
         });
     }
 
+    #[test]
+    fn from_env_toml_deserialises_with_include_overrides() {
+        Jail::expect_with(|jail| {
+            jail.create_file("config.toml", "Str = \"TOML_OVERWRITTEN\"\nUInt32 = 1")?;
+            jail.create_dir("conf.d")?;
+            jail.create_file("conf.d/20-second.toml", "UInt32 = 3")?;
+            jail.create_file("conf.d/10-first.toml", "UInt32 = 2\nBool = true")?;
+            jail.create_file("conf.d/ignored.conf", "UInt32 = 4")?;
+
+            assert_eq!(
+                config_include_files(Path::new("conf.d")),
+                vec![PathBuf::from("conf.d/10-first.toml"), PathBuf::from("conf.d/20-second.toml")]
+            );
+            let cfg: ConfigTest = from_env_toml_with_includes("config.toml", Some(Path::new("conf.d"))).unwrap();
+            let expected_cfg = ConfigTest {
+                Str: "TOML_OVERWRITTEN".into(),
+                UInt32: 3,
+                Bool: true,
+                ..Default::default()
+            };
+            assert_eq!(cfg, expected_cfg);
+
+            Ok(())
+        });
+    }
+
     #[test]
     fn from_env_toml_deserialises_with_env_overrides() {
         Jail::expect_with(|jail| {
@@ -0,0 +1,6 @@
+pub mod aes;
+pub mod arc4;
+pub mod session_key_generator;
+pub mod srp6;
+pub mod totp;
+pub mod world_packet_crypt;
@@ -0,0 +1,57 @@
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_128_GCM, NONCE_LEN};
+
+pub const KEY_SIZE_BYTES: usize = 16;
+pub const IV_SIZE_BYTES: usize = NONCE_LEN;
+pub const TAG_SIZE_BYTES: usize = 16;
+
+pub type Key = [u8; KEY_SIZE_BYTES];
+
+/// AEEncryptWithRandomIV<AES> in TC, encrypts the data with AES-128-GCM using a random IV
+///
+/// The IV and the authentication tag are appended to the encrypted data, i.e. `ciphertext | IV | tag`.
+pub fn encrypt_with_random_iv(data: &[u8], key: &Key) -> Vec<u8> {
+    let key = LessSafeKey::new(UnboundKey::new(&AES_128_GCM, key).expect("AES-128 key is always 16 bytes"));
+    let iv = rand::random::<[u8; IV_SIZE_BYTES]>();
+    let mut out = data.to_vec();
+    let tag = key
+        .seal_in_place_separate_tag(Nonce::assume_unique_for_key(iv), Aad::empty(), &mut out)
+        .expect("AES-GCM can encrypt data of any reasonable size");
+    out.extend_from_slice(&iv);
+    out.extend_from_slice(tag.as_ref());
+    out
+}
+
+/// AEDecrypt<AES> in TC, the reverse of [encrypt_with_random_iv]
+///
+/// Returns None if the data is too short, or if it was not encrypted with this key or has been tampered with.
+pub fn decrypt(data: &[u8], key: &Key) -> Option<Vec<u8>> {
+    let ciphertext_len = data.len().checked_sub(IV_SIZE_BYTES + TAG_SIZE_BYTES)?;
+    let (ciphertext, rest) = data.split_at(ciphertext_len);
+    let (iv, tag) = rest.split_at(IV_SIZE_BYTES);
+    let key = LessSafeKey::new(UnboundKey::new(&AES_128_GCM, key).expect("AES-128 key is always 16 bytes"));
+    let mut in_out = [ciphertext, tag].concat();
+    let plaintext = key.open_in_place(Nonce::try_assume_unique_for_key(iv).ok()?, Aad::empty(), &mut in_out).ok()?;
+    Some(plaintext.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypts_and_decrypts_with_same_key_only() {
+        let key = [7; KEY_SIZE_BYTES];
+        let data = b"12345678901234567890";
+        let encrypted = encrypt_with_random_iv(data, &key);
+        assert_eq!(encrypted.len(), data.len() + IV_SIZE_BYTES + TAG_SIZE_BYTES);
+        assert_ne!(&encrypted[..data.len()], data);
+        assert_ne!(encrypted, encrypt_with_random_iv(data, &key), "IVs must be random");
+        assert_eq!(decrypt(&encrypted, &key).as_deref(), Some(&data[..]));
+
+        assert_eq!(decrypt(&encrypted, &[8; KEY_SIZE_BYTES]), None);
+        let mut tampered = encrypted.clone();
+        tampered[0] ^= 1;
+        assert_eq!(decrypt(&tampered, &key), None);
+        assert_eq!(decrypt(&encrypted[..IV_SIZE_BYTES], &key), None);
+    }
+}
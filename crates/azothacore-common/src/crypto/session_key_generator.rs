@@ -0,0 +1,37 @@
+use sha2::{digest::Output, Digest};
+
+/// SessionKeyGenerator in TC/AC
+///
+/// Stretches the given seed into an arbitrarily long key by repeatedly hashing
+/// the two halves of the seed together with the previous output.
+pub struct SessionKeyGenerator<D: Digest> {
+    o0:     Output<D>,
+    o1:     Output<D>,
+    o2:     Output<D>,
+    o0_pos: usize,
+}
+
+impl<D: Digest> SessionKeyGenerator<D> {
+    pub fn new(seed: &[u8]) -> Self {
+        let half_len = seed.len() / 2;
+        let o1 = D::digest(&seed[..half_len]);
+        let o2 = D::digest(&seed[half_len..]);
+        let o0 = Self::next_o0(&o1, &Output::<D>::default(), &o2);
+        Self { o0, o1, o2, o0_pos: 0 }
+    }
+
+    fn next_o0(o1: &Output<D>, o0: &Output<D>, o2: &Output<D>) -> Output<D> {
+        D::new().chain_update(o1).chain_update(o0).chain_update(o2).finalize()
+    }
+
+    pub fn generate(&mut self, buf: &mut [u8]) {
+        for b in buf.iter_mut() {
+            if self.o0_pos == self.o0.len() {
+                self.o0 = Self::next_o0(&self.o1, &self.o0, &self.o2);
+                self.o0_pos = 0;
+            }
+            *b = self.o0[self.o0_pos];
+            self.o0_pos += 1;
+        }
+    }
+}
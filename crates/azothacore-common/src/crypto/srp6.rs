@@ -0,0 +1,197 @@
+use hmac::{Hmac, Mac};
+use num::{BigUint, One};
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha512};
+
+use crate::hex_str;
+
+pub const SALT_LENGTH: usize = 32;
+pub type Salt = [u8; SALT_LENGTH];
+
+/// SRP6 in TC / AC, only the registration half of it
+///
+/// Passwords are never stored, only a random per-account salt along with the verifier `v = g^x mod N`, where `x` is
+/// derived from the salt and the credentials of the account. This is all that is needed to check a password that is
+/// sent in plain text (i.e. over TLS) by calculating the verifier again.
+pub trait Srp6 {
+    /// The safe prime `N`, as a big endian hex string
+    const N_HEX: &'static str;
+    /// The generator `g`
+    const G: u32;
+    /// The length of the stored verifier, in bytes. It is the length of `N` so that verifiers have a fixed size.
+    const VERIFIER_LENGTH: usize;
+
+    /// CalculateX in TC, the private key `x` of the given credentials
+    fn calculate_x(username: &str, password: &str, salt: &Salt) -> BigUint;
+
+    fn n() -> BigUint {
+        BigUint::parse_bytes(Self::N_HEX.as_bytes(), 16).expect("SRP6 N must be valid hex")
+    }
+
+    /// CalculateVerifier in TC, the verifier is stored little endian
+    fn calculate_verifier(username: &str, password: &str, salt: &Salt) -> Vec<u8> {
+        let v = BigUint::from(Self::G).modpow(&Self::calculate_x(username, password, salt), &Self::n());
+        let mut v = v.to_bytes_le();
+        v.resize(Self::VERIFIER_LENGTH, 0);
+        v
+    }
+
+    /// MakeRegistrationData in TC, generates a new random salt and the verifier of the credentials with it
+    fn make_registration_data(username: &str, password: &str) -> (Salt, Vec<u8>) {
+        let salt = rand::random::<Salt>();
+        let verifier = Self::calculate_verifier(username, password, &salt);
+        (salt, verifier)
+    }
+
+    /// CheckLogin in TC
+    fn check_login(username: &str, password: &str, salt: &Salt, verifier: &[u8]) -> bool {
+        Self::calculate_verifier(username, password, salt) == verifier
+    }
+}
+
+/// SRP6 (a.k.a. GruntSRP6) in TC, used for game accounts
+///
+/// The username and password are expected to be upper-cased already.
+pub struct GruntSrp6;
+
+impl Srp6 for GruntSrp6 {
+    const G: u32 = 7;
+    const N_HEX: &'static str = "894B645E89E1535BBDAD5B8B290650530801B18EBFBF5E8FAB3C82872A3E9BB7";
+    const VERIFIER_LENGTH: usize = 32;
+
+    /// `x = H(s | H(USERNAME:PASSWORD))`
+    fn calculate_x(username: &str, password: &str, salt: &Salt) -> BigUint {
+        let h = Sha1::new().chain_update(username).chain_update(":").chain_update(password).finalize();
+        let x = Sha1::new().chain_update(salt).chain_update(h).finalize();
+        BigUint::from_bytes_le(&x)
+    }
+}
+
+/// The 2048 bit group of RFC 5054, which is used by both Battle.net SRP6 versions
+const BNET_N_HEX: &str = "AC6BDB41324A9A9BF166DE5E1389582FAF72B6651987EE07FC3192943DB56050A37329CBB4A099ED8193E0757767A13D\
+                          D52312AB4B03310DCD7F48A9DA04FD50E8083969EDB767B0CF6095179A163AB3661A05FBD5FAAAE82918A9962F0B93B8\
+                          55F97993EC975EEAA80D740ADBF4FF747359D041D5C33EA71D281E446B14773BCA97B43A23FB801676BD207A436C6481\
+                          F1D2B9078717461A5B9D32E688F87748544523B524B0D57D5EA77A2775D2ECFA032CFBDBF52FB3786160279004E57AE6\
+                          AF874E7303CE53299CCC041C7BC308D82A5698F3A8D0C38271AE35F8E9DBFBB694B5C803D89F7AE435DE236D525F5475\
+                          9B65E372FCD68EF20FA7111F9E4AFF73";
+
+/// BnetSRP6v1 in TC, used for Battle.net accounts whose password is case insensitive
+///
+/// The username is the SRP username of the account (see [bnet_srp_username]) and the password is expected to be
+/// upper-cased already. `H(username:PASSWORD)` is exactly the (byte reversed) legacy unsalted hash that Battle.net
+/// accounts used to be stored with.
+pub struct BnetSrp6V1;
+
+impl Srp6 for BnetSrp6V1 {
+    const G: u32 = 2;
+    const N_HEX: &'static str = BNET_N_HEX;
+    const VERIFIER_LENGTH: usize = 256;
+
+    /// `x = H(s | H(username:PASSWORD))`
+    fn calculate_x(username: &str, password: &str, salt: &Salt) -> BigUint {
+        let h = Sha256::new().chain_update(username).chain_update(":").chain_update(password).finalize();
+        let x = Sha256::new().chain_update(salt).chain_update(h).finalize();
+        BigUint::from_bytes_be(&x)
+    }
+}
+
+/// BnetSRP6v2 in TC, used for Battle.net accounts whose password is case sensitive
+///
+/// The username is the SRP username of the account (see [bnet_srp_username]), the password is used as is.
+pub struct BnetSrp6V2;
+
+impl BnetSrp6V2 {
+    const PBKDF2_ITERATIONS: u32 = 15000;
+}
+
+impl Srp6 for BnetSrp6V2 {
+    const G: u32 = 2;
+    const N_HEX: &'static str = BNET_N_HEX;
+    const VERIFIER_LENGTH: usize = 256;
+
+    /// `x = PBKDF2<SHA512>(username:password, s)`, interpreted as a signed big endian number modulo `N - 1`
+    fn calculate_x(username: &str, password: &str, salt: &Salt) -> BigUint {
+        let x_bytes = pbkdf2_sha512(format!("{username}:{password}").as_bytes(), salt, Self::PBKDF2_ITERATIONS);
+        let n_minus_one = Self::n() - 1u32;
+        let x = BigUint::from_bytes_be(&x_bytes);
+        if x_bytes[0] & 0x80 == 0 {
+            x % n_minus_one
+        } else {
+            // negative, i.e. x - 2^512 which is always larger than -(N - 1)
+            (x + &n_minus_one - (BigUint::one() << (x_bytes.len() * 8))) % n_minus_one
+        }
+    }
+}
+
+/// GetSrpUsername in TC, the hex encoded SHA256 of the upper-cased email of a Battle.net account
+pub fn bnet_srp_username(email: &str) -> String {
+    let email = email.to_ascii_uppercase();
+    hex_str!(&Sha256::digest(email.as_bytes())[..])
+}
+
+/// PBKDF2 with HMAC-SHA512, producing a single block of output
+fn pbkdf2_sha512(password: &[u8], salt: &[u8], iterations: u32) -> [u8; 64] {
+    let prf = Hmac::<Sha512>::new_from_slice(password).expect("HMAC accepts keys of any length");
+    let mut u: [u8; 64] = prf.clone().chain_update(salt).chain_update(1u32.to_be_bytes()).finalize().into_bytes().into();
+    let mut t = u;
+    for _ in 1..iterations {
+        u = prf.clone().chain_update(u).finalize().into_bytes().into();
+        for (t, u) in t.iter_mut().zip(u) {
+            *t ^= u;
+        }
+    }
+    t
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fermat test with base 2, enough to catch a typo in the constants
+    fn is_probable_prime(n: &BigUint) -> bool {
+        BigUint::from(2u32).modpow(&(n - 1u32), n) == BigUint::one()
+    }
+
+    #[test]
+    fn n_are_safe_primes() {
+        for n in [GruntSrp6::n(), BnetSrp6V1::n(), BnetSrp6V2::n()] {
+            assert!(is_probable_prime(&n));
+            assert!(is_probable_prime(&((n - 1u32) >> 1)));
+        }
+        assert_eq!(BnetSrp6V2::n().bits(), 2048);
+    }
+
+    #[test]
+    fn pbkdf2_sha512_matches_known_answer() {
+        let got = pbkdf2_sha512(b"password", b"salt", 1);
+        let expected = "867F70CF1ADE02CFF3752599A3A53DC4AF34C7A669815AE5D513554E1C8CF252\
+                        C02D470A285A0501BAD999BFE943C08F050235D7D68B1DA55E63F73B60A57FCE";
+        assert_eq!(hex_str!(got), expected);
+    }
+
+    fn check_registration<S: Srp6>(username: &str, password: &str) {
+        let (salt, verifier) = S::make_registration_data(username, password);
+        assert_eq!(verifier.len(), S::VERIFIER_LENGTH);
+        assert!(S::check_login(username, password, &salt, &verifier));
+        assert!(!S::check_login(username, "WRONG", &salt, &verifier));
+        assert!(!S::check_login(username, password, &[0; SALT_LENGTH], &verifier));
+        let (other_salt, other_verifier) = S::make_registration_data(username, password);
+        assert_ne!(salt, other_salt, "salts must be random");
+        assert_ne!(verifier, other_verifier);
+    }
+
+    #[test]
+    fn registration_data_checks_out() {
+        check_registration::<GruntSrp6>("USER", "PASSWORD");
+        check_registration::<BnetSrp6V1>(&bnet_srp_username("user@example.com"), "PASSWORD");
+        check_registration::<BnetSrp6V2>(&bnet_srp_username("user@example.com"), "Password");
+    }
+
+    #[test]
+    fn bnet_srp6_v2_is_case_sensitive() {
+        let username = bnet_srp_username("user@example.com");
+        let (salt, verifier) = BnetSrp6V2::make_registration_data(&username, "Password");
+        assert!(!BnetSrp6V2::check_login(&username, "PASSWORD", &salt, &verifier));
+        assert_eq!(username, bnet_srp_username("USER@example.COM"));
+    }
+}
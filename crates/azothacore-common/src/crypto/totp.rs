@@ -0,0 +1,111 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+
+/// The number of seconds that a token is valid for
+pub const TOTP_INTERVAL: u64 = 30;
+/// The length of the secrets generated by [generate_secret], which is the output size of HMAC-SHA1 as per RFC 4226
+pub const SECRET_LENGTH: usize = 20;
+const TOKEN_DIGITS: u32 = 6;
+
+/// Generates a new random TOTP secret
+pub fn generate_secret() -> [u8; SECRET_LENGTH] {
+    rand::random()
+}
+
+/// TOTP::GenerateToken in TC, the 6 digit token of the given secret at the given unix timestamp (RFC 6238)
+pub fn generate_token(secret: &[u8], timestamp: u64) -> u32 {
+    let step = timestamp / TOTP_INTERVAL;
+    let digest = Hmac::<Sha1>::new_from_slice(secret)
+        .expect("HMAC accepts keys of any length")
+        .chain_update(step.to_be_bytes())
+        .finalize()
+        .into_bytes();
+    let offset = usize::from(digest[digest.len() - 1] & 0xF);
+    let truncated = u32::from_be_bytes(digest[offset..offset + 4].try_into().unwrap()) & 0x7FFF_FFFF;
+    truncated % 10u32.pow(TOKEN_DIGITS)
+}
+
+/// TOTP::ValidateToken in TC, checks the token against the current time step as well as the ones right before and
+/// after it to account for clock drift
+pub fn validate_token(secret: &[u8], token: u32) -> bool {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    validate_token_at(secret, token, now)
+}
+
+fn validate_token_at(secret: &[u8], token: u32, timestamp: u64) -> bool {
+    [timestamp.saturating_sub(TOTP_INTERVAL), timestamp, timestamp + TOTP_INTERVAL]
+        .into_iter()
+        .any(|t| generate_token(secret, t) == token)
+}
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Base32 (RFC 4648, without padding) encoding of a secret, which is what authenticator apps expect it to be entered as
+pub fn encode_base32(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(5) * 8);
+    let (mut buffer, mut bits) = (0u32, 0);
+    for b in data {
+        buffer = (buffer << 8) | u32::from(*b);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(char::from(BASE32_ALPHABET[((buffer >> bits) & 0x1F) as usize]));
+        }
+    }
+    if bits > 0 {
+        out.push(char::from(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1F) as usize]));
+    }
+    out
+}
+
+/// The reverse of [encode_base32], ignoring case, whitespace and padding
+pub fn decode_base32(data: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(data.len() * 5 / 8);
+    let (mut buffer, mut bits) = (0u32, 0);
+    for c in data.bytes().filter(|c| !c.is_ascii_whitespace() && *c != b'=') {
+        let value = BASE32_ALPHABET.iter().position(|a| *a == c.to_ascii_uppercase())?;
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generates_rfc6238_tokens() {
+        // The SHA1 test vectors of RFC 6238, truncated to 6 digits
+        let secret = b"12345678901234567890";
+        for (timestamp, token) in [(59, 287082), (1111111109, 81804), (1111111111, 50471), (1234567890, 5924), (2000000000, 279037)] {
+            assert_eq!(generate_token(secret, timestamp), token, "timestamp {timestamp}");
+        }
+    }
+
+    #[test]
+    fn validates_tokens_within_one_step() {
+        let secret = b"12345678901234567890";
+        let token = generate_token(secret, 1111111109);
+        assert!(validate_token_at(secret, token, 1111111109));
+        assert!(validate_token_at(secret, token, 1111111109 + TOTP_INTERVAL));
+        assert!(validate_token_at(secret, token, 1111111109 - TOTP_INTERVAL));
+        assert!(!validate_token_at(secret, token, 1111111109 + 3 * TOTP_INTERVAL));
+        assert!(!validate_token_at(b"another secret", token, 1111111109));
+    }
+
+    #[test]
+    fn base32_round_trips() {
+        assert_eq!(encode_base32(b"foobar"), "MZXW6YTBOI");
+        assert_eq!(decode_base32("mzxw 6ytb oi======").as_deref(), Some(&b"foobar"[..]));
+        assert_eq!(decode_base32("not base32!"), None);
+        let secret = generate_secret();
+        assert_eq!(decode_base32(&encode_base32(&secret)).as_deref(), Some(&secret[..]));
+    }
+}
@@ -4,6 +4,7 @@ pub mod bounded_nums;
 pub mod collision;
 pub mod compile_options;
 pub mod configuration;
+pub mod crypto;
 pub mod g3dlite_copied;
 pub mod log;
 pub mod macros;
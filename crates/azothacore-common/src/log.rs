@@ -1,27 +1,62 @@
 use std::{
     collections::BTreeMap,
-    fs,
-    io::{self, Read},
+    fmt::{self, Write},
+    io,
     path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU32, AtomicU64, Ordering},
+        Arc,
+    },
+    time::Instant,
 };
 
-use bevy::prelude::{App, Commands, IntoSystemConfigs, PreStartup, Res, Resource, SystemSet};
+use bevy::prelude::{App, Commands, Event, EventReader, FixedUpdate, IntoSystemConfigs, PreStartup, Res, ResMut, Resource, SystemSet};
 use chrono::Local;
 use flagset::FlagSet;
-use tracing::subscriber::set_global_default;
+use tracing::{
+    error,
+    field::{Field, Visit},
+    info,
+    span,
+    subscriber::{set_global_default, Interest},
+    warn,
+    Event as TracingEvent,
+    Metadata,
+};
 use tracing_appender::non_blocking as tanb;
 use tracing_subscriber::{
     filter::{self as tsfil},
     fmt::{self as tsfmt},
+    layer::Context,
     prelude::*,
+    reload,
     {self as ts},
 };
 
-use crate::configuration::{ConfigMgr, LogAppender, LogFlags, LogLevel, LogLoggerConfig};
+use crate::{
+    configuration::{ConfigMgr, ConfigMgrSet, ConfigReloadFinishedEvent, LogAppender, LogFlags, LogLevel, LogLevelOverride, LogLoggerConfig},
+    log::rolling_file::{RollingFileOptions, RollingFileWriter},
+    AzContext,
+    AzResult,
+};
+
+mod rolling_file;
 
+/// Keeps the appenders' writers alive, and allows the appenders, loggers and level overrides to be
+/// changed at runtime, see [LogGuard::reload] and [LogGuard::override_level]
 #[derive(Resource)]
 pub struct LogGuard {
-    _guards: Vec<tanb::WorkerGuard>,
+    handle:           reload::Handle<AppenderLayers, ts::Registry>,
+    logs_dir:         PathBuf,
+    /// Writer guards of each appender, by appender name
+    guards:           BTreeMap<String, Vec<tanb::WorkerGuard>>,
+    loggers:          Vec<LogLoggerConfig>,
+    overrides:        Vec<ActiveLevelOverride>,
+    /// The overrides of the config as of the last time they were applied, so that a config reload
+    /// only (re)applies the ones that changed
+    config_overrides: Vec<LogLevelOverride>,
+    db_sink:          DbLogSink,
+    db_receiver:      Option<tokio::sync::mpsc::Receiver<DbLogEntry>>,
 }
 
 pub struct ConsoleWriter {
@@ -103,71 +138,78 @@ impl tsfmt::MakeWriter<'_> for ConsoleWriterOrNonBlocking {
 }
 
 struct ProcessedAppenderPart {
-    make_writer:        ConsoleWriterOrNonBlocking,
-    f_guard:            Vec<tanb::WorkerGuard>,
-    name:               String,
-    appender_min_level: Option<tracing::Level>,
-    appender_max_level: Option<tracing::Level>,
-    flags:              FlagSet<LogFlags>,
-    is_console:         bool,
+    make_writer: ConsoleWriterOrNonBlocking,
+    f_guard:     Vec<tanb::WorkerGuard>,
+    flags:       FlagSet<LogFlags>,
+    is_console:  bool,
 }
 
-fn construct_appender_parts<P>(logs_dir: P, a: &LogAppender) -> ProcessedAppenderPart
+/// The writer of an appender, [None] for the appenders that don't write to the console or a file
+fn construct_appender_parts<P>(logs_dir: P, a: &LogAppender) -> io::Result<Option<ProcessedAppenderPart>>
 where
     P: AsRef<Path>,
 {
-    match a {
+    let part = match a {
         LogAppender::Console {
             // colours,
             flags,
-            min_level,
-            max_level,
-            name,
+            ..
         } => {
             let (stdout, stdout_g) = tracing_appender::non_blocking(io::stdout());
             let (stderr, stderr_g) = tracing_appender::non_blocking(io::stderr());
             ProcessedAppenderPart {
-                make_writer:        ConsoleWriterOrNonBlocking(Ok(ConsoleWriter { stdout, stderr })),
-                f_guard:            vec![stdout_g, stderr_g],
-                name:               name.clone(),
-                appender_min_level: (*min_level).into(),
-                appender_max_level: (*max_level).into(),
-                flags:              *flags,
-                is_console:         true,
+                make_writer: ConsoleWriterOrNonBlocking(Ok(ConsoleWriter { stdout, stderr })),
+                f_guard:     vec![stdout_g, stderr_g],
+                flags:       *flags,
+                is_console:  true,
             }
         },
         LogAppender::File {
             file,
-            name,
-            min_level,
-            max_level,
             flags,
             // mode,
+            max_file_size,
+            max_files,
+            compress,
+            ..
+        }
+        | LogAppender::Json {
+            file,
+            flags,
+            max_file_size,
+            max_files,
+            compress,
+            ..
         } => {
-            // let dest_log_file_name = logs_dir.as_ref().join(name);
-            let f = if flags.contains(LogFlags::AppendFileTimestamps) {
-                tracing_appender::rolling::daily(logs_dir, file)
-            } else {
-                tracing_appender::rolling::never(logs_dir, file)
-            };
+            let f = RollingFileWriter::new(
+                logs_dir,
+                file,
+                RollingFileOptions {
+                    daily:         flags.contains(LogFlags::AppendFileTimestamps),
+                    truncate:      flags.contains(LogFlags::TruncateFile),
+                    backup:        flags.contains(LogFlags::BackupBeforeOverwrite),
+                    max_file_size: *max_file_size,
+                    max_files:     *max_files,
+                    compress:      *compress,
+                },
+            )?;
             let (w, g) = tracing_appender::non_blocking(f);
             ProcessedAppenderPart {
-                make_writer:        ConsoleWriterOrNonBlocking(Err(w)),
-                f_guard:            vec![g],
-                name:               name.clone(),
-                appender_min_level: (*min_level).into(),
-                appender_max_level: (*max_level).into(),
-                flags:              *flags,
-                is_console:         false,
+                make_writer: ConsoleWriterOrNonBlocking(Err(w)),
+                f_guard:     vec![g],
+                flags:       *flags,
+                is_console:  false,
             }
         },
-    }
+        LogAppender::Db { .. } => return Ok(None),
+    };
+    Ok(Some(part))
 }
 
 const LOGGER_ROOT: &str = "root";
 
-fn is_target_in_logger_targets(m: &tracing::Metadata, logger_target: &str) -> bool {
-    let mut t = m.target();
+fn is_target_in_logger_targets(target: &str, logger_target: &str) -> bool {
+    let mut t = target;
     loop {
         if logger_target == LOGGER_ROOT || logger_target == t {
             // always allow logger if the logger target is root, or the logger target
@@ -183,6 +225,358 @@ fn is_target_in_logger_targets(m: &tracing::Metadata, logger_target: &str) -> bo
     }
 }
 
+fn level_filter(level: LogLevel) -> tsfil::LevelFilter {
+    let level: Option<tracing::Level> = level.into();
+    tsfil::LevelFilter::from(level)
+}
+
+fn appender_name(a: &LogAppender) -> &str {
+    match a {
+        LogAppender::Console { name, .. } | LogAppender::File { name, .. } | LogAppender::Json { name, .. } | LogAppender::Db { name, .. } => name,
+    }
+}
+
+/// Whether both appenders write the same way to the same place, i.e. only their levels differ
+/// and the writer of one can be kept for the other
+fn is_same_appender_writer(a: &LogAppender, b: &LogAppender) -> bool {
+    match (a, b) {
+        (LogAppender::Console { name: n1, flags: f1, .. }, LogAppender::Console { name: n2, flags: f2, .. }) => n1 == n2 && f1 == f2,
+        (
+            LogAppender::File {
+                name: n1,
+                flags: f1,
+                file: file1,
+                max_file_size: size1,
+                max_files: files1,
+                compress: compress1,
+                ..
+            },
+            LogAppender::File {
+                name: n2,
+                flags: f2,
+                file: file2,
+                max_file_size: size2,
+                max_files: files2,
+                compress: compress2,
+                ..
+            },
+        )
+        | (
+            LogAppender::Json {
+                name: n1,
+                flags: f1,
+                file: file1,
+                max_file_size: size1,
+                max_files: files1,
+                compress: compress1,
+                ..
+            },
+            LogAppender::Json {
+                name: n2,
+                flags: f2,
+                file: file2,
+                max_file_size: size2,
+                max_files: files2,
+                compress: compress2,
+                ..
+            },
+        ) => n1 == n2 && f1 == f2 && file1 == file2 && size1 == size2 && files1 == files2 && compress1 == compress2,
+        (LogAppender::Db { name: n1, .. }, LogAppender::Db { name: n2, .. }) => n1 == n2,
+        _ => false,
+    }
+}
+
+/// A [LogLevelOverride] that has been applied
+#[derive(Clone, Debug)]
+struct ActiveLevelOverride {
+    target: String,
+    level:  tsfil::LevelFilter,
+    until:  Instant,
+}
+
+/// Which events an appender writes, given the loggers that route to it and the active level overrides
+#[derive(Clone, Debug)]
+struct AppenderFilter {
+    min_level: tsfil::LevelFilter,
+    max_level: tsfil::LevelFilter,
+    /// logger targets routed to this appender, with their min and max levels
+    targets:   BTreeMap<String, (tsfil::LevelFilter, tsfil::LevelFilter)>,
+    overrides: Vec<ActiveLevelOverride>,
+}
+
+impl AppenderFilter {
+    fn new(appender: &LogAppender, loggers: &[LogLoggerConfig], overrides: &[ActiveLevelOverride]) -> Self {
+        let (min_level, max_level) = match appender {
+            LogAppender::Console { min_level, max_level, .. }
+            | LogAppender::File { min_level, max_level, .. }
+            | LogAppender::Json { min_level, max_level, .. }
+            | LogAppender::Db { min_level, max_level, .. } => (*min_level, *max_level),
+        };
+        let name = appender_name(appender);
+        let targets = loggers
+            .iter()
+            .filter(|logger_cfg| logger_cfg.appenders.iter().any(|a| a == name))
+            .map(|logger_cfg| {
+                (
+                    logger_cfg.name.clone(),
+                    (level_filter(logger_cfg.min_level), level_filter(logger_cfg.max_level)),
+                )
+            })
+            .collect();
+        Self {
+            min_level: level_filter(min_level),
+            max_level: level_filter(max_level),
+            targets,
+            overrides: overrides.to_vec(),
+        }
+    }
+
+    fn enabled(&self, target: &str, level: &tracing::Level) -> bool {
+        if (self.max_level..=self.min_level).contains(level)
+            && self.targets.iter().any(|(logger_target, (target_min_level, target_max_level))| {
+                (*target_max_level..=*target_min_level).contains(level) && is_target_in_logger_targets(target, logger_target)
+            })
+        {
+            return true;
+        }
+        // Overrides only go below the min levels of the appender and the loggers, for targets that
+        // are routed to the appender in the first place
+        if self.overrides.is_empty() || self.max_level > *level || !self.targets.keys().any(|t| is_target_in_logger_targets(target, t)) {
+            return false;
+        }
+        let now = Instant::now();
+        self.overrides
+            .iter()
+            .any(|o| o.until > now && o.level >= *level && is_target_in_logger_targets(target, &o.target))
+    }
+}
+
+struct AppenderLayer {
+    appender: LogAppender,
+    filter:   AppenderFilter,
+    layer:    Box<dyn ts::Layer<ts::Registry> + Send + Sync>,
+}
+
+/// The appenders of the global subscriber, set behind a [reload::Layer] by [init].
+///
+/// Events only reach the appenders whose filter lets them through; spans reach every appender
+/// so that each of them can format the fields of the spans an event is in.
+pub struct AppenderLayers(Vec<AppenderLayer>);
+
+impl ts::Layer<ts::Registry> for AppenderLayers {
+    fn register_callsite(&self, _metadata: &'static Metadata<'static>) -> Interest {
+        // Level overrides expire, so whether a callsite is enabled is always re-evaluated
+        Interest::sometimes()
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>, _ctx: Context<'_, ts::Registry>) -> bool {
+        self.0.iter().any(|a| a.filter.enabled(metadata.target(), metadata.level()))
+    }
+
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, ts::Registry>) {
+        for a in &self.0 {
+            a.layer.on_new_span(attrs, id, ctx.clone());
+        }
+    }
+
+    fn on_record(&self, span: &span::Id, values: &span::Record<'_>, ctx: Context<'_, ts::Registry>) {
+        for a in &self.0 {
+            a.layer.on_record(span, values, ctx.clone());
+        }
+    }
+
+    fn on_follows_from(&self, span: &span::Id, follows: &span::Id, ctx: Context<'_, ts::Registry>) {
+        for a in &self.0 {
+            a.layer.on_follows_from(span, follows, ctx.clone());
+        }
+    }
+
+    fn on_event(&self, event: &TracingEvent<'_>, ctx: Context<'_, ts::Registry>) {
+        let metadata = event.metadata();
+        for a in self.0.iter().filter(|a| a.filter.enabled(metadata.target(), metadata.level())) {
+            a.layer.on_event(event, ctx.clone());
+        }
+    }
+
+    fn on_enter(&self, id: &span::Id, ctx: Context<'_, ts::Registry>) {
+        for a in &self.0 {
+            a.layer.on_enter(id, ctx.clone());
+        }
+    }
+
+    fn on_exit(&self, id: &span::Id, ctx: Context<'_, ts::Registry>) {
+        for a in &self.0 {
+            a.layer.on_exit(id, ctx.clone());
+        }
+    }
+
+    fn on_close(&self, id: span::Id, ctx: Context<'_, ts::Registry>) {
+        for a in &self.0 {
+            a.layer.on_close(id.clone(), ctx.clone());
+        }
+    }
+
+    fn on_id_change(&self, old: &span::Id, new: &span::Id, ctx: Context<'_, ts::Registry>) {
+        for a in &self.0 {
+            a.layer.on_id_change(old, new, ctx.clone());
+        }
+    }
+}
+
+/// Builds the layer of an appender, with the guards of its writers
+fn construct_appender_layer<P: AsRef<Path>>(
+    logs_dir: P,
+    a: &LogAppender,
+    loggers: &[LogLoggerConfig],
+    overrides: &[ActiveLevelOverride],
+    db_sink: &DbLogSink,
+) -> io::Result<(AppenderLayer, Vec<tanb::WorkerGuard>)> {
+    let filter = AppenderFilter::new(a, loggers, overrides);
+    let Some(ProcessedAppenderPart {
+        make_writer,
+        f_guard,
+        flags,
+        is_console,
+    }) = construct_appender_parts(&logs_dir, a)?
+    else {
+        let layer = AppenderLayer {
+            appender: a.clone(),
+            filter,
+            layer: Box::new(db_sink.clone()),
+        };
+        return Ok((layer, vec![]));
+    };
+
+    let layer = if let LogAppender::Json { .. } = a {
+        tsfmt::Layer::new()
+            .json()
+            .with_current_span(true)
+            .with_span_list(true)
+            .with_file(true)
+            .with_line_number(true)
+            .with_writer(make_writer)
+            .boxed()
+    } else {
+        let layer = tsfmt::Layer::new()
+            .compact()
+            .with_file(true)
+            .with_line_number(true)
+            .with_ansi(is_console)
+            .with_target(flags.contains(LogFlags::AddLogFilter))
+            .with_level(flags.contains(LogFlags::AddLogLevel))
+            .with_writer(make_writer);
+        if !flags.contains(LogFlags::AddLogTimestamps) {
+            layer.without_time().boxed()
+        } else {
+            layer.boxed()
+        }
+    };
+    Ok((
+        AppenderLayer {
+            appender: a.clone(),
+            filter,
+            layer,
+        },
+        f_guard,
+    ))
+}
+
+/// Target of the events about writing the entries of the [LogAppender::Db] appenders to the DB. The appenders
+/// skip them, so that failing to write logs doesn't queue more logs to write.
+pub const DB_LOG_WRITER_TARGET: &str = "sql::db_log";
+
+/// Target of the statements logged by sqlx, which includes the ones writing the entries of the [LogAppender::Db]
+/// appenders, skipped for the same reason as [DB_LOG_WRITER_TARGET]
+const SQLX_QUERY_TARGET: &str = "sqlx::query";
+
+/// How many entries of the Db appenders can wait to be written before new ones are dropped
+const DB_LOG_QUEUE_CAPACITY: usize = 4096;
+
+/// A row of the `logs` table of the auth DB, as queued by the [LogAppender::Db] appenders
+#[derive(Clone, Debug, PartialEq)]
+pub struct DbLogEntry {
+    /// Unix timestamp of the event
+    pub time:   i64,
+    pub realm:  u32,
+    /// The target of the event, the logger name in TC / AC
+    pub r#type: String,
+    /// The [LogLevel] of the event
+    pub level:  u8,
+    pub string: String,
+}
+
+/// Where the [LogAppender::Db] appenders queue their entries, shared by all of them.
+///
+/// The entries are taken from the queue and written to the DB elsewhere, see [LogGuard::take_db_log_receiver],
+/// as the DB isn't available to this crate.
+#[derive(Clone)]
+struct DbLogSink {
+    sender:   tokio::sync::mpsc::Sender<DbLogEntry>,
+    realm_id: Arc<AtomicU32>,
+    dropped:  Arc<AtomicU64>,
+}
+
+impl DbLogSink {
+    fn entry(&self, event: &TracingEvent<'_>) -> DbLogEntry {
+        let metadata = event.metadata();
+        let mut visitor = DbLogMessageVisitor::default();
+        event.record(&mut visitor);
+        let string = if visitor.fields.is_empty() {
+            visitor.message
+        } else if visitor.message.is_empty() {
+            visitor.fields
+        } else {
+            format!("{} {}", visitor.message, visitor.fields)
+        };
+        let level = match *metadata.level() {
+            tracing::Level::TRACE => LogLevel::Trace,
+            tracing::Level::DEBUG => LogLevel::Debug,
+            tracing::Level::INFO => LogLevel::Info,
+            tracing::Level::WARN => LogLevel::Warning,
+            tracing::Level::ERROR => LogLevel::Error,
+        };
+        DbLogEntry {
+            time: Local::now().timestamp(),
+            realm: self.realm_id.load(Ordering::Relaxed),
+            r#type: metadata.target().to_string(),
+            level: level as u8,
+            string,
+        }
+    }
+}
+
+impl ts::Layer<ts::Registry> for DbLogSink {
+    fn on_event(&self, event: &TracingEvent<'_>, _ctx: Context<'_, ts::Registry>) {
+        let target = event.metadata().target();
+        if target == DB_LOG_WRITER_TARGET || target == SQLX_QUERY_TARGET {
+            return;
+        }
+        if self.sender.try_send(self.entry(event)).is_err() {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Formats an event as `message key=value...` for the `string` column of the `logs` table
+#[derive(Default)]
+struct DbLogMessageVisitor {
+    message: String,
+    fields:  String,
+}
+
+impl Visit for DbLogMessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            _ = write!(self.message, "{value:?}");
+            return;
+        }
+        if !self.fields.is_empty() {
+            self.fields.push(' ');
+        }
+        _ = write!(self.fields, "{}={value:?}", field.name());
+    }
+}
+
 pub fn init_console() -> LogGuard {
     use LogFlags::*;
     use LogLevel::*;
@@ -208,118 +602,500 @@ pub trait LoggingConfig: Send + Sync + 'static {
     fn retrieve_loggers(&self) -> &[LogLoggerConfig];
     fn retrieve_appenders(&self) -> &[LogAppender];
     fn retrieve_logs_dir(&self) -> PathBuf;
+    /// The level overrides applied on startup and whenever they change on a config reload
+    fn retrieve_level_overrides(&self) -> &[LogLevelOverride] {
+        &[]
+    }
 }
 
 #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct LoggingSetupSet;
 
+/// Applies a [LogLevelOverride] to the logging set up by [logging_plugin], i.e. to temporarily
+/// log `server::rest` at Debug
+#[derive(Event, Clone, Debug)]
+pub struct LogLevelOverrideEvent(pub LogLevelOverride);
+
+/// Sets up logging from the config `C`.
+///
+/// Appenders, loggers and level overrides are reloaded whenever a [ConfigReloadFinishedEvent<C>] reports a
+/// successful reload. The logs directory only takes effect on restart.
 pub fn logging_plugin<C: LoggingConfig>(app: &mut App) {
-    app.add_systems(
-        PreStartup,
-        (|cfg: Res<ConfigMgr<C>>, mut commands: Commands| {
-            // TODO: Setup DB logging. Original code below
-            // // Init all logs
-            // sLog->RegisterAppender<AppenderDB>();
-            let wg = init(cfg.retrieve_logs_dir(), cfg.retrieve_appenders(), cfg.retrieve_loggers());
-            commands.insert_resource(wg);
-        })
-        .in_set(LoggingSetupSet),
-    );
+    app.add_event::<LogLevelOverrideEvent>()
+        .add_systems(
+            PreStartup,
+            (|cfg: Res<ConfigMgr<C>>, mut commands: Commands| {
+                // TODO: Setup DB logging. Original code below
+                // // Init all logs
+                // sLog->RegisterAppender<AppenderDB>();
+                let mut wg = init(cfg.retrieve_logs_dir(), cfg.retrieve_appenders(), cfg.retrieve_loggers());
+                wg.apply_config_overrides(cfg.retrieve_level_overrides());
+                commands.insert_resource(wg);
+            })
+            .in_set(LoggingSetupSet),
+        )
+        .add_systems(
+            FixedUpdate,
+            (
+                reload_logging::<C>.after(ConfigMgrSet::<C>::reload()),
+                override_log_levels,
+                expire_log_level_overrides,
+            )
+                .chain(),
+        );
+}
+
+fn reload_logging<C: LoggingConfig>(mut ev_reloaded: EventReader<ConfigReloadFinishedEvent<C>>, cfg: Res<ConfigMgr<C>>, log_guard: Option<ResMut<LogGuard>>) {
+    let reloaded = ev_reloaded.read().filter(|ev| ev.result.is_ok()).count() > 0;
+    let Some(mut log_guard) = log_guard else {
+        return;
+    };
+    if !reloaded {
+        return;
+    }
+    if let Err(e) = log_guard.reload(cfg.retrieve_appenders(), cfg.retrieve_loggers()) {
+        error!(target:"server::loading", cause=?e, "error reloading appenders and loggers, keeping the old ones");
+        return;
+    }
+    log_guard.apply_config_overrides(cfg.retrieve_level_overrides());
+    info!(target:"server::loading", "reloaded appenders and loggers");
+}
+
+fn override_log_levels(mut ev_override: EventReader<LogLevelOverrideEvent>, log_guard: Option<ResMut<LogGuard>>) {
+    let Some(mut log_guard) = log_guard else {
+        return;
+    };
+    for LogLevelOverrideEvent(o) in ev_override.read() {
+        log_guard.override_level_logged(o);
+    }
+}
+
+fn expire_log_level_overrides(log_guard: Option<ResMut<LogGuard>>) {
+    let Some(mut log_guard) = log_guard else {
+        return;
+    };
+    let now = Instant::now();
+    if !log_guard.overrides.iter().any(|o| o.until <= now) {
+        return;
+    }
+    match log_guard.expire_overrides(now) {
+        Err(e) => error!(target:"server::loading", cause=?e, "error expiring log level overrides"),
+        Ok(expired) => info!(target:"server::loading", targets=?expired, "log level overrides expired"),
+    }
+}
+
+impl LogGuard {
+    /// Replaces the appenders and loggers. Appenders that still write the same way to the same place keep
+    /// their writers, so their files are not truncated again.
+    pub fn reload(&mut self, appenders: &[LogAppender], loggers: &[LogLoggerConfig]) -> AzResult<()> {
+        let current = self.current_appenders()?;
+        // Build the new writers before swapping, as the subscriber can't be used until the swap is done
+        let mut new_guards = BTreeMap::new();
+        let mut new_layers = BTreeMap::new();
+        for a in appenders {
+            if current.iter().any(|c| is_same_appender_writer(c, a)) {
+                continue;
+            }
+            let (layer, guards) = construct_appender_layer(&self.logs_dir, a, loggers, &self.overrides, &self.db_sink)
+                .with_context(|| format!("error setting up log appender {}", appender_name(a)))?;
+            new_layers.insert(appender_name(a).to_string(), layer);
+            new_guards.insert(appender_name(a).to_string(), guards);
+        }
+        let mut removed_layers = vec![];
+        self.handle.modify(|layers| {
+            let mut old_layers = std::mem::take(&mut layers.0)
+                .into_iter()
+                .map(|l| (appender_name(&l.appender).to_string(), l))
+                .collect::<BTreeMap<_, _>>();
+            for a in appenders {
+                let name = appender_name(a);
+                let layer = match new_layers.remove(name) {
+                    Some(l) => l,
+                    None => {
+                        let Some(mut l) = old_layers.remove(name) else {
+                            continue;
+                        };
+                        l.appender = a.clone();
+                        l.filter = AppenderFilter::new(a, loggers, &self.overrides);
+                        l
+                    },
+                };
+                layers.0.push(layer);
+            }
+            removed_layers.extend(old_layers.into_values());
+        })?;
+        // Only drop the old writers now, flushing whatever they still had buffered
+        drop(removed_layers);
+        self.guards.retain(|name, _| appenders.iter().any(|a| appender_name(a) == name));
+        self.guards.extend(new_guards);
+        self.loggers = loggers.to_vec();
+        Ok(())
+    }
+
+    /// Takes the queue of the entries of the [LogAppender::Db] appenders, to write them to the `logs` table
+    /// of the auth DB. It can only be taken once; if it isn't, the entries are dropped once it is full.
+    pub fn take_db_log_receiver(&mut self) -> Option<tokio::sync::mpsc::Receiver<DbLogEntry>> {
+        self.db_receiver.take()
+    }
+
+    /// The realm written with the entries of the [LogAppender::Db] appenders, 0 until set.
+    /// Log::SetRealmId in TC / AC
+    pub fn set_realm_id(&self, realm_id: u32) {
+        self.db_sink.realm_id.store(realm_id, Ordering::Relaxed);
+    }
+
+    /// Number of entries of the [LogAppender::Db] appenders dropped so far, because the queue was full
+    pub fn dropped_db_log_entries(&self) -> u64 {
+        self.db_sink.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Logs the target of the override down to its level until its duration elapses, replacing any
+    /// override already active for the same target
+    pub fn override_level(&mut self, o: &LogLevelOverride) -> AzResult<()> {
+        self.overrides.retain(|a| a.target != o.target);
+        self.overrides.push(ActiveLevelOverride {
+            target: o.target.clone(),
+            level:  level_filter(o.level),
+            until:  Instant::now() + *o.duration,
+        });
+        self.rebuild_filters()
+    }
+
+    /// Removes the overrides that ended by `now`, returning their targets
+    fn expire_overrides(&mut self, now: Instant) -> AzResult<Vec<String>> {
+        let (expired, active) = std::mem::take(&mut self.overrides).into_iter().partition::<Vec<_>, _>(|o| o.until <= now);
+        self.overrides = active;
+        self.rebuild_filters()?;
+        Ok(expired.into_iter().map(|o| o.target).collect())
+    }
+
+    fn override_level_logged(&mut self, o: &LogLevelOverride) {
+        match self.override_level(o) {
+            Err(e) => error!(target:"server::loading", cause=?e, target_override=%o.target, "error overriding log level"),
+            Ok(()) => {
+                warn!(target:"server::loading", target_override=%o.target, level=?o.level, duration=?*o.duration, "overriding log level")
+            },
+        }
+    }
+
+    /// Applies the overrides of the config that weren't applied from it already, so that reloading an
+    /// unchanged config doesn't extend them
+    fn apply_config_overrides(&mut self, overrides: &[LogLevelOverride]) {
+        for o in overrides {
+            if !self.config_overrides.contains(o) {
+                self.override_level_logged(o);
+            }
+        }
+        self.config_overrides = overrides.to_vec();
+    }
+
+    fn current_appenders(&self) -> AzResult<Vec<LogAppender>> {
+        Ok(self.handle.with_current(|layers| layers.0.iter().map(|l| l.appender.clone()).collect())?)
+    }
+
+    fn rebuild_filters(&self) -> AzResult<()> {
+        self.handle.modify(|layers| {
+            for l in layers.0.iter_mut() {
+                l.filter = AppenderFilter::new(&l.appender, &self.loggers, &self.overrides);
+            }
+        })?;
+        Ok(())
+    }
 }
 
 /// Compose multiple layers into a `tracing`'s subscriber.
 ///
-/// Then register the subscriber as global default to process span data.
+/// Then register the subscriber as global default to process span data. The appenders
+/// are behind a reload handle kept in the returned [LogGuard].
 ///
 /// It should only be called once!
 pub fn init<P: AsRef<Path>>(logs_dir: P, appenders: &[LogAppender], loggers: &[LogLoggerConfig]) -> LogGuard {
+    let (sender, db_receiver) = tokio::sync::mpsc::channel(DB_LOG_QUEUE_CAPACITY);
+    let db_sink = DbLogSink {
+        sender,
+        realm_id: Arc::new(AtomicU32::new(0)),
+        dropped: Arc::new(AtomicU64::new(0)),
+    };
     let mut layers = vec![];
-    let mut guards = vec![];
+    let mut guards = BTreeMap::new();
     for a in appenders {
-        if let LogAppender::File { flags, file, .. } = &a {
-            if flags.contains(LogFlags::TruncateFile) {
-                let original = logs_dir.as_ref().join(file);
-                if flags.contains(LogFlags::BackupBeforeOverwrite) {
-                    let now = Local::now().to_rfc3339();
-                    let dst_base_name = if let Some((left, right)) = file.rsplit_once('.') {
-                        format!("{left}_{now}.{right}")
-                    } else {
-                        format!("{file}.{now}")
-                    };
-                    let dst = logs_dir.as_ref().join(dst_base_name);
-                    let mut should_rename = false;
-                    if let Ok(mut m) = fs::File::open(&original) {
-                        if let Ok(n) = m.read_to_end(&mut vec![]) {
-                            if n > 0 {
-                                should_rename = true;
-                            }
-                        }
-                    }
-                    if should_rename {
-                        _ = fs::rename(&original, &dst);
-                    }
-                } else {
-                    _ = fs::File::create(&original);
-                }
-            }
-        }
-        let ProcessedAppenderPart {
-            make_writer,
-            mut f_guard,
-            name,
-            appender_min_level,
-            appender_max_level,
-            flags,
-            is_console,
-        } = construct_appender_parts(&logs_dir, a);
-        let appender_logger_targets = loggers
-            .iter()
-            .filter_map(|logger_cfg| {
-                if logger_cfg.appenders.contains(&name) {
-                    let min_level: Option<tracing::Level> = logger_cfg.min_level.into();
-                    let max_level: Option<tracing::Level> = logger_cfg.max_level.into();
-                    Some((
-                        logger_cfg.name.clone(),
-                        (tsfil::LevelFilter::from(min_level), tsfil::LevelFilter::from(max_level)),
-                    ))
-                } else {
-                    None
-                }
-            })
-            .collect::<BTreeMap<_, _>>();
+        let (layer, f_guard) = construct_appender_layer(&logs_dir, a, loggers, &[], &db_sink)
+            .unwrap_or_else(|e| panic!("error setting up log appender {}: {e}", appender_name(a)));
+        layers.push(layer);
+        guards.insert(appender_name(a).to_string(), f_guard);
+    }
+    let (layer, handle) = reload::Layer::new(AppenderLayers(layers));
+    let subscriber = ts::Registry::default().with(layer);
+    set_global_default(subscriber).expect("Failed to set subscriber");
+    LogGuard {
+        handle,
+        logs_dir: logs_dir.as_ref().to_path_buf(),
+        guards,
+        loggers: loggers.to_vec(),
+        overrides: vec![],
+        config_overrides: vec![],
+        db_sink,
+        db_receiver: Some(db_receiver),
+    }
+}
 
-        let filter_fn = tsfil::filter_fn(move |m| {
-            let appender_max_level = tsfil::LevelFilter::from(appender_max_level);
-            let appender_min_level = tsfil::LevelFilter::from(appender_min_level);
-            if !(appender_max_level..=appender_min_level).contains(m.level()) {
-                return false;
-            }
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::BTreeMap,
+        fs,
+        sync::{
+            atomic::{AtomicU32, AtomicU64, Ordering},
+            Arc,
+        },
+        time::{Duration, Instant},
+    };
 
-            appender_logger_targets.iter().any(|(target, (target_min_level, target_max_level))| {
-                if !(*target_max_level..=*target_min_level).contains(m.level()) {
-                    return false;
-                }
-                is_target_in_logger_targets(m, target)
-            })
+    use flagset::FlagSet;
+    use tracing::{debug, error, info, info_span, subscriber::with_default, warn, Level};
+    use tracing_subscriber::{prelude::*, reload, Registry};
+
+    use super::{
+        appender_name,
+        construct_appender_layer,
+        ActiveLevelOverride,
+        AppenderFilter,
+        AppenderLayers,
+        DbLogEntry,
+        DbLogSink,
+        LogGuard,
+        DB_LOG_WRITER_TARGET,
+    };
+    use crate::configuration::{LogAppender, LogLevel, LogLoggerConfig};
+
+    fn db_log_sink() -> (DbLogSink, tokio::sync::mpsc::Receiver<DbLogEntry>) {
+        let (sender, receiver) = tokio::sync::mpsc::channel(16);
+        let sink = DbLogSink {
+            sender,
+            realm_id: Arc::new(AtomicU32::new(0)),
+            dropped: Arc::new(AtomicU64::new(0)),
+        };
+        (sink, receiver)
+    }
+
+    #[test]
+    fn db_log_sink_formats_events_as_message_and_fields() {
+        let (sink, mut receiver) = db_log_sink();
+        sink.realm_id.store(3, Ordering::Relaxed);
+        with_default(Registry::default().with(sink.clone()), || {
+            info!(target:"server::authserver", account=7, name="Jaina", "account logged in");
+            warn!(target:"server::authserver", "no fields");
+            error!(target:"server::authserver", account=7);
         });
 
-        let layer = tsfmt::Layer::new()
-            .compact()
-            .with_file(true)
-            .with_line_number(true)
-            .with_ansi(is_console)
-            .with_target(flags.contains(LogFlags::AddLogFilter))
-            .with_level(flags.contains(LogFlags::AddLogLevel))
-            .with_writer(make_writer);
-        let layer = if !flags.contains(LogFlags::AddLogTimestamps) {
-            layer.without_time().boxed()
-        } else {
-            layer.boxed()
+        let entries = std::iter::from_fn(|| receiver.try_recv().ok()).collect::<Vec<_>>();
+        assert_eq!(entries.len(), 3);
+        assert!(entries.iter().all(|e| e.realm == 3 && e.r#type == "server::authserver"));
+        assert_eq!(
+            entries.iter().map(|e| (e.level, e.string.as_str())).collect::<Vec<_>>(),
+            [
+                (LogLevel::Info as u8, r#"account logged in account=7 name="Jaina""#),
+                (LogLevel::Warning as u8, "no fields"),
+                (LogLevel::Error as u8, "account=7"),
+            ]
+        );
+    }
+
+    #[test]
+    fn db_log_sink_skips_only_the_writes_of_its_entries() {
+        let (sink, mut receiver) = db_log_sink();
+        with_default(Registry::default().with(sink.clone()), || {
+            error!(target:DB_LOG_WRITER_TARGET, "error writing entries to the logs table");
+            debug!(target:"sqlx::query", "INSERT INTO logs");
+            error!(target:"sql::sql", "transaction failed");
+            info!(target:"server::mysql", "not about the logs table");
+        });
+
+        let entries = std::iter::from_fn(|| receiver.try_recv().ok()).map(|e| e.r#type).collect::<Vec<_>>();
+        assert_eq!(entries, ["sql::sql", "server::mysql"]);
+        assert_eq!(sink.dropped.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn json_appender_writes_the_fields_of_the_current_spans() {
+        let dir = tempfile::tempdir().unwrap();
+        let appender = LogAppender::Json {
+            name:          "Json".to_string(),
+            min_level:     LogLevel::Debug,
+            max_level:     LogLevel::Error,
+            flags:         FlagSet::default(),
+            file:          "Server.json".to_string(),
+            max_file_size: 0,
+            max_files:     0,
+            compress:      false,
         };
-        layers.push(layer.with_filter(filter_fn));
-        guards.append(&mut f_guard);
+        let loggers = [LogLoggerConfig {
+            name:      "server".to_string(),
+            min_level: LogLevel::Debug,
+            max_level: LogLevel::Error,
+            appenders: vec!["Json".to_string()],
+        }];
+        let (layer, guards) = construct_appender_layer(dir.path(), &appender, &loggers, &[], &db_log_sink().0).unwrap();
+        with_default(Registry::default().with(AppenderLayers(vec![layer])), || {
+            let session = info_span!(target:"server::authserver", "session", account = 7);
+            let _session = session.enter();
+            let request = info_span!(target:"server::authserver", "request", method = "Logon");
+            let _request = request.enter();
+            info!(target:"server::authserver", ticket="abc", "account logged in");
+        });
+        // Flushes the writer
+        drop(guards);
+
+        let contents = fs::read_to_string(dir.path().join("Server.json")).unwrap();
+        let lines = contents.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 1);
+        let event: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(event["level"], "INFO");
+        assert_eq!(event["target"], "server::authserver");
+        assert_eq!(event["fields"]["message"], "account logged in");
+        assert_eq!(event["fields"]["ticket"], "abc");
+        assert_eq!(event["span"]["name"], "request");
+        assert_eq!(event["span"]["method"], "Logon");
+        assert_eq!(event["spans"][0]["name"], "session");
+        assert_eq!(event["spans"][0]["account"], 7);
+        assert_eq!(event["spans"][1]["name"], "request");
+    }
+
+    #[test]
+    fn appender_filter_applies_loggers_and_level_overrides() {
+        let appender = LogAppender::Console {
+            name:      "Console".to_string(),
+            min_level: LogLevel::Info,
+            max_level: LogLevel::Error,
+            flags:     FlagSet::default(),
+        };
+        let loggers = [
+            LogLoggerConfig {
+                name:      "server".to_string(),
+                min_level: LogLevel::Info,
+                max_level: LogLevel::Error,
+                appenders: vec!["Console".to_string()],
+            },
+            LogLoggerConfig {
+                name:      "sql".to_string(),
+                min_level: LogLevel::Trace,
+                max_level: LogLevel::Error,
+                appenders: vec!["DBErrors".to_string()],
+            },
+        ];
+        let filter = AppenderFilter::new(&appender, &loggers, &[]);
+        assert!(filter.enabled("server::rest", &Level::INFO));
+        assert!(!filter.enabled("server::rest", &Level::DEBUG));
+        assert!(!filter.enabled("sql::sql", &Level::ERROR));
+
+        let overrides = [
+            ActiveLevelOverride {
+                target: "server::rest".to_string(),
+                level:  Level::DEBUG.into(),
+                until:  Instant::now() + Duration::from_secs(60),
+            },
+            ActiveLevelOverride {
+                target: "sql".to_string(),
+                level:  Level::TRACE.into(),
+                until:  Instant::now() + Duration::from_secs(60),
+            },
+            ActiveLevelOverride {
+                target: "server::loading".to_string(),
+                level:  Level::DEBUG.into(),
+                until:  Instant::now() - Duration::from_secs(1),
+            },
+        ];
+        let filter = AppenderFilter::new(&appender, &loggers, &overrides);
+        assert!(filter.enabled("server::rest::login", &Level::DEBUG));
+        assert!(!filter.enabled("server::rest", &Level::TRACE));
+        assert!(!filter.enabled("server::authserver", &Level::DEBUG));
+        // expired
+        assert!(!filter.enabled("server::loading", &Level::DEBUG));
+        // not routed to the appender
+        assert!(!filter.enabled("sql::sql", &Level::DEBUG));
+    }
+
+    #[test]
+    fn reload_rebuilds_the_writers_whose_rotation_options_changed() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = |max_level, max_file_size, max_files, compress| LogAppender::File {
+            name: "Server".to_string(),
+            min_level: LogLevel::Debug,
+            max_level,
+            flags: FlagSet::default(),
+            file: "Server.log".to_string(),
+            max_file_size,
+            max_files,
+            compress,
+        };
+        let json = |max_level, max_file_size, max_files, compress| LogAppender::Json {
+            name: "Json".to_string(),
+            min_level: LogLevel::Debug,
+            max_level,
+            flags: FlagSet::default(),
+            file: "Server.json".to_string(),
+            max_file_size,
+            max_files,
+            compress,
+        };
+        let loggers = [LogLoggerConfig {
+            name:      "server".to_string(),
+            min_level: LogLevel::Debug,
+            max_level: LogLevel::Error,
+            appenders: vec!["Server".to_string(), "Json".to_string()],
+        }];
+        let (db_sink, db_receiver) = db_log_sink();
+        let appenders = [file(LogLevel::Error, 0, 0, false), json(LogLevel::Error, 0, 0, false)];
+        let mut layers = vec![];
+        let mut guards = BTreeMap::new();
+        for a in &appenders {
+            let (layer, f_guard) = construct_appender_layer(dir.path(), a, &loggers, &[], &db_sink).unwrap();
+            layers.push(layer);
+            guards.insert(appender_name(a).to_string(), f_guard);
+        }
+        let (layer, handle) = reload::Layer::new(AppenderLayers(layers));
+        // Keeps the reloadable layer alive for the handle
+        let _subscriber = Registry::default().with(layer);
+        let mut guard = LogGuard {
+            handle,
+            logs_dir: dir.path().to_path_buf(),
+            guards,
+            loggers: loggers.to_vec(),
+            overrides: vec![],
+            config_overrides: vec![],
+            db_sink,
+            db_receiver: Some(db_receiver),
+        };
+        // The address of the layer of each appender, which changes when its writer is rebuilt
+        let writers = |guard: &LogGuard| {
+            guard
+                .handle
+                .with_current(|layers| layers.0.iter().map(|l| std::ptr::from_ref(&*l.layer).cast::<()>() as usize).collect::<Vec<_>>())
+                .unwrap()
+        };
+
+        let before = writers(&guard);
+        guard
+            .reload(&[file(LogLevel::Warning, 0, 0, false), json(LogLevel::Warning, 0, 0, false)], &loggers)
+            .unwrap();
+        assert_eq!(writers(&guard), before, "only the levels changed, the writers are kept");
+
+        for (appenders, changed) in [
+            ([file(LogLevel::Warning, 1024, 0, false), json(LogLevel::Warning, 0, 0, false)], [true, false]),
+            ([file(LogLevel::Warning, 1024, 3, false), json(LogLevel::Warning, 0, 0, false)], [true, false]),
+            ([file(LogLevel::Warning, 1024, 3, true), json(LogLevel::Warning, 0, 0, false)], [true, false]),
+            ([file(LogLevel::Warning, 1024, 3, true), json(LogLevel::Warning, 1024, 3, true)], [false, true]),
+        ] {
+            let before = writers(&guard);
+            guard.reload(&appenders, &loggers).unwrap();
+            let after = writers(&guard);
+            assert_eq!(
+                before.iter().zip(&after).map(|(b, a)| b != a).collect::<Vec<_>>(),
+                changed,
+                "reloading {appenders:?}"
+            );
+            let current = guard.current_appenders().unwrap();
+            assert_eq!(current, appenders);
+        }
     }
-    let subscriber = ts::Registry::default().with(layers);
-    set_global_default(subscriber).expect("Failed to set subscriber");
-    LogGuard { _guards: guards }
 }
@@ -0,0 +1,238 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use chrono::Local;
+use flate2::{write::GzEncoder, Compression};
+
+/// How the file of a File or Json appender is opened and rotated, see [crate::configuration::LogAppender::File]
+#[derive(Clone, Debug, Default)]
+pub(super) struct RollingFileOptions {
+    /// Append the date to the file name, rolling over to a new file every day
+    pub daily:         bool,
+    pub truncate:      bool,
+    /// Keep the existing file like a rotated one instead of truncating it, only if `truncate` is set
+    pub backup:        bool,
+    pub max_file_size: u64,
+    pub max_files:     usize,
+    pub compress:      bool,
+}
+
+/// Writes to `{file}`, or `{file}.{date}` when rolling daily.
+///
+/// Files rotated by size or kept as a backup are renamed to `{name}.{time}`, with `.gz` appended if compressed.
+/// Rotated files, including the ones of the previous days, are removed oldest first once there are more
+/// than `max_files` of them. Rotating happens on the writer thread of the appender.
+pub(super) struct RollingFileWriter {
+    dir:          PathBuf,
+    file_name:    String,
+    options:      RollingFileOptions,
+    /// Name of the file currently written to
+    current_name: String,
+    file:         fs::File,
+    size:         u64,
+}
+
+impl RollingFileWriter {
+    pub(super) fn new<P: AsRef<Path>>(dir: P, file_name: &str, options: RollingFileOptions) -> io::Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+        let current_name = current_file_name(file_name, options.daily);
+        let path = dir.join(&current_name);
+        if options.truncate && options.backup && fs::metadata(&path).is_ok_and(|m| m.len() > 0) {
+            rotate_file(&dir, &current_name, options.compress)?;
+        }
+        let file = open_file(&path, options.truncate)?;
+        let size = file.metadata()?.len();
+        let w = Self {
+            dir,
+            file_name: file_name.to_string(),
+            options,
+            current_name,
+            file,
+            size,
+        };
+        w.remove_old_files()?;
+        Ok(w)
+    }
+
+    fn roll_over_if_needed(&mut self, incoming: usize) -> io::Result<()> {
+        if self.options.daily {
+            let name = current_file_name(&self.file_name, true);
+            if name != self.current_name {
+                // A new day, the file of the previous one is kept as it is
+                self.file.flush()?;
+                self.file = open_file(&self.dir.join(&name), false)?;
+                self.size = self.file.metadata()?.len();
+                let previous = std::mem::replace(&mut self.current_name, name);
+                if self.options.compress {
+                    compress_file(&self.dir.join(&previous))?;
+                }
+                self.remove_old_files()?;
+            }
+        }
+        if self.options.max_file_size > 0 && self.size > 0 && self.size + incoming as u64 > self.options.max_file_size {
+            self.file.flush()?;
+            rotate_file(&self.dir, &self.current_name, self.options.compress)?;
+            self.file = open_file(&self.dir.join(&self.current_name), true)?;
+            self.size = 0;
+            self.remove_old_files()?;
+        }
+        Ok(())
+    }
+
+    fn remove_old_files(&self) -> io::Result<()> {
+        if self.options.max_files == 0 {
+            return Ok(());
+        }
+        let prefix = format!("{}.", self.file_name);
+        let mut rotated = fs::read_dir(&self.dir)?
+            .filter_map(|e| e.ok())
+            .filter(|e| {
+                let name = e.file_name();
+                let name = name.to_string_lossy();
+                name.starts_with(&prefix) && name != self.current_name
+            })
+            .map(|e| (e.metadata().and_then(|m| m.modified()).ok(), e.path()))
+            .collect::<Vec<_>>();
+        if rotated.len() <= self.options.max_files {
+            return Ok(());
+        }
+        // Oldest first, the names break ties as they end with the time they were rotated at
+        rotated.sort();
+        for (_, p) in &rotated[..rotated.len() - self.options.max_files] {
+            fs::remove_file(p)?;
+        }
+        Ok(())
+    }
+}
+
+impl Write for RollingFileWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.roll_over_if_needed(buf.len())?;
+        let n = self.file.write(buf)?;
+        self.size += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+fn current_file_name(file_name: &str, daily: bool) -> String {
+    if daily {
+        format!("{file_name}.{}", Local::now().format("%Y-%m-%d"))
+    } else {
+        file_name.to_string()
+    }
+}
+
+fn open_file(path: &Path, truncate: bool) -> io::Result<fs::File> {
+    fs::OpenOptions::new().create(true).write(true).append(!truncate).truncate(truncate).open(path)
+}
+
+/// Moves `{dir}/{name}` to a free `{dir}/{name}.{time}`, gzipping it if `compress` is set
+fn rotate_file(dir: &Path, name: &str, compress: bool) -> io::Result<()> {
+    let now = Local::now().format("%Y-%m-%d_%H-%M-%S");
+    let mut rotated = dir.join(format!("{name}.{now}"));
+    let mut i = 1;
+    while rotated.exists() || gz_path(&rotated).exists() {
+        rotated = dir.join(format!("{name}.{now}.{i}"));
+        i += 1;
+    }
+    fs::rename(dir.join(name), &rotated)?;
+    if compress {
+        compress_file(&rotated)?;
+    }
+    Ok(())
+}
+
+fn gz_path(path: &Path) -> PathBuf {
+    let mut p = path.as_os_str().to_owned();
+    p.push(".gz");
+    p.into()
+}
+
+/// Replaces the file at `path` with a gzipped `{path}.gz`
+fn compress_file(path: &Path) -> io::Result<()> {
+    let mut src = fs::File::open(path)?;
+    let mut e = GzEncoder::new(fs::File::create(gz_path(path))?, Compression::default());
+    io::copy(&mut src, &mut e)?;
+    e.finish()?;
+    fs::remove_file(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        io::{Read, Write},
+    };
+
+    use flate2::read::GzDecoder;
+
+    use super::{RollingFileOptions, RollingFileWriter};
+
+    fn file_names(dir: &std::path::Path) -> Vec<String> {
+        let mut names = fs::read_dir(dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn it_rotates_by_size_and_keeps_max_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut w = RollingFileWriter::new(
+            dir.path(),
+            "Server.log",
+            RollingFileOptions {
+                max_file_size: 10,
+                max_files: 2,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        for line in ["first\n", "second\n", "third\n", "fourth\n"] {
+            w.write_all(line.as_bytes()).unwrap();
+        }
+        w.flush().unwrap();
+
+        let names = file_names(dir.path());
+        assert_eq!(names.len(), 3, "{names:?}");
+        assert_eq!(fs::read_to_string(dir.path().join("Server.log")).unwrap(), "fourth\n");
+        assert!(names.iter().filter(|n| n.as_str() != "Server.log").all(|n| n.starts_with("Server.log.")));
+    }
+
+    #[test]
+    fn it_backs_up_and_compresses_the_existing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("Auth.log"), "previous run\n").unwrap();
+        let mut w = RollingFileWriter::new(
+            dir.path(),
+            "Auth.log",
+            RollingFileOptions {
+                truncate: true,
+                backup: true,
+                compress: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        w.write_all(b"this run\n").unwrap();
+        w.flush().unwrap();
+
+        assert_eq!(fs::read_to_string(dir.path().join("Auth.log")).unwrap(), "this run\n");
+        let backup = file_names(dir.path()).into_iter().find(|n| n.ends_with(".gz")).unwrap();
+        let mut decoded = String::new();
+        GzDecoder::new(fs::File::open(dir.path().join(backup)).unwrap())
+            .read_to_string(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, "previous run\n");
+    }
+}
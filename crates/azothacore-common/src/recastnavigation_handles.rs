@@ -3,7 +3,7 @@ use std::{io, slice};
 use flagset::FlagSet;
 use num_derive::FromPrimitive;
 use recastnavigation_sys::*;
-pub use recastnavigation_sys::{DT_NAVMESH_VERSION, DT_POLY_BITS, DT_VERTS_PER_POLYGON, RC_SPAN_HEIGHT_BITS, RC_WALKABLE_AREA};
+pub use recastnavigation_sys::{dtPolyRef, dtTileRef, DT_NAVMESH_VERSION, DT_POLY_BITS, DT_VERTS_PER_POLYGON, RC_SPAN_HEIGHT_BITS, RC_WALKABLE_AREA};
 use thiserror::Error;
 
 use crate::deref_boilerplate;
@@ -47,6 +47,9 @@ impl Default for RecastConfig {
 // NOTE: DETOUR SECTION
 // ==========
 
+/// The result of a Detour call, the status is returned in both cases as it may contain extra details even on success
+pub type DetourResult<T> = Result<(T, DetourStatus), DetourStatus>;
+
 #[derive(Debug, Clone, Error)]
 #[error("DetourStatus: status {status:?}, details {details:?}")]
 pub struct DetourStatus {
@@ -62,6 +65,20 @@ impl DetourStatus {
         Self { details, status }
     }
 
+    pub fn failure(details: FlagSet<DetourStatusDetails>) -> Self {
+        Self {
+            status: DetourHighLevelStatus::Failure.into(),
+            details,
+        }
+    }
+
+    pub fn success() -> Self {
+        Self {
+            status:  DetourHighLevelStatus::Success.into(),
+            details: None.into(),
+        }
+    }
+
     pub fn wrap_result<T>(self, t: T) -> Result<(T, Self), Self> {
         if self.status.contains(DetourHighLevelStatus::Success) {
             Ok((t, self))
@@ -157,6 +174,282 @@ impl DetourNavMesh {
         let status = unsafe { dtNavMesh_removeTile(self.handle, tile_ref, std::ptr::null_mut(), std::ptr::null_mut()) };
         DetourStatus::from_raw_status(status).wrap_result(())
     }
+
+    /// dtNavMesh::getOffMeshConnectionPolyEndPoints in Detour, returns the start and end positions of the
+    /// off-mesh connection polygon when entered from the previous polygon.
+    pub fn get_off_mesh_connection_poly_end_points(&self, prev_ref: dtPolyRef, poly_ref: dtPolyRef) -> DetourResult<([f32; 3], [f32; 3])> {
+        let mut start_pos = [0.0; 3];
+        let mut end_pos = [0.0; 3];
+        let status = unsafe { dtNavMesh_getOffMeshConnectionPolyEndPoints(self.handle, prev_ref, poly_ref, start_pos.as_mut_ptr(), end_pos.as_mut_ptr()) };
+        DetourStatus::from_raw_status(status).wrap_result((start_pos, end_pos))
+    }
+}
+
+// SAFETY: the navmesh is only mutated through `&mut self` (i.e. adding / removing tiles), queries only read from it.
+unsafe impl Send for DetourNavMesh {}
+unsafe impl Sync for DetourNavMesh {}
+
+/// dtQueryFilter in Detour, defaults to the same values as its C++ constructor, i.e. every area has a cost of 1.0
+/// and all polygons are included.
+#[derive(Debug, Clone)]
+pub struct DetourQueryFilter(dtQueryFilter);
+
+deref_boilerplate!(DetourQueryFilter, dtQueryFilter, 0);
+
+impl Default for DetourQueryFilter {
+    fn default() -> Self {
+        Self(dtQueryFilter {
+            m_areaCost:     [1.0; DT_MAX_AREAS as usize],
+            m_includeFlags: 0xffff,
+            m_excludeFlags: 0,
+        })
+    }
+}
+
+impl DetourQueryFilter {
+    pub fn include_flags(&self) -> u16 {
+        self.0.m_includeFlags
+    }
+
+    pub fn set_include_flags(&mut self, flags: u16) {
+        self.0.m_includeFlags = flags;
+    }
+
+    pub fn exclude_flags(&self) -> u16 {
+        self.0.m_excludeFlags
+    }
+
+    pub fn set_exclude_flags(&mut self, flags: u16) {
+        self.0.m_excludeFlags = flags;
+    }
+
+    pub fn area_cost(&self, area_id: u8) -> f32 {
+        self.0.m_areaCost[usize::from(area_id)]
+    }
+
+    pub fn set_area_cost(&mut self, area_id: u8, cost: f32) {
+        self.0.m_areaCost[usize::from(area_id)] = cost;
+    }
+
+    /// dtQueryFilter::passFilter in Detour
+    pub fn pass_filter(&self, poly_flags: u16) -> bool {
+        (poly_flags & self.0.m_includeFlags) != 0 && (poly_flags & self.0.m_excludeFlags) == 0
+    }
+}
+
+/// A single point returned by [`DetourNavMeshQuery::find_straight_path`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DetourStraightPathPoint {
+    pub pos:      [f32; 3],
+    /// Combination of the DT_STRAIGHTPATH_* flags
+    pub flags:    u8,
+    /// The polygon that is being entered at this point
+    pub poly_ref: dtPolyRef,
+}
+
+impl DetourStraightPathPoint {
+    pub fn is_end(&self) -> bool {
+        u32::from(self.flags) & dtStraightPathFlags_DT_STRAIGHTPATH_END != 0
+    }
+
+    pub fn is_off_mesh_connection(&self) -> bool {
+        u32::from(self.flags) & dtStraightPathFlags_DT_STRAIGHTPATH_OFFMESH_CONNECTION != 0
+    }
+}
+
+/// Wrapper around dtNavMeshQuery.
+///
+/// All the query functions take `&mut self` as Detour keeps its search node pool inside of the query object,
+/// so a single query object cannot be used concurrently.
+///
+/// The query holds onto a raw pointer to the navmesh it was initialised with, so the owner must make sure the
+/// query is dropped before the navmesh is.
+pub struct DetourNavMeshQuery {
+    handle: *mut dtNavMeshQuery,
+}
+
+// SAFETY: see the doc comment on `DetourNavMeshQuery`, mutation only ever happens behind `&mut self`.
+unsafe impl Send for DetourNavMeshQuery {}
+unsafe impl Sync for DetourNavMeshQuery {}
+
+impl Drop for DetourNavMeshQuery {
+    fn drop(&mut self) {
+        unsafe { dtFreeNavMeshQuery(self.handle) };
+    }
+}
+
+extern "C" fn detour_frand() -> f32 {
+    rand::random::<f32>()
+}
+
+impl DetourNavMeshQuery {
+    /// dtAllocNavMeshQuery + dtNavMeshQuery::init in Detour
+    pub fn init(nav_mesh: &DetourNavMesh, max_nodes: i32) -> DetourResult<Self> {
+        let handle = unsafe { dtAllocNavMeshQuery() };
+        if handle.is_null() {
+            return Err(DetourStatus::failure(DetourStatusDetails::OutOfMemory.into()));
+        }
+        // Wrap first so that the handle is freed on failure
+        let query = Self { handle };
+        let res = unsafe { dtNavMeshQuery_init(query.handle, nav_mesh.handle, max_nodes) };
+        DetourStatus::from_raw_status(res).wrap_result(query)
+    }
+
+    /// dtNavMeshQuery::findNearestPoly in Detour, returns the polygon and the nearest point on it.
+    ///
+    /// A polygon ref of 0 means no polygon was found within the extents.
+    pub fn find_nearest_poly(&mut self, center: &[f32; 3], half_extents: &[f32; 3], filter: &DetourQueryFilter) -> DetourResult<(dtPolyRef, [f32; 3])> {
+        let mut nearest_ref = 0;
+        let mut nearest_pt = *center;
+        let status = unsafe {
+            dtNavMeshQuery_findNearestPoly(
+                self.handle,
+                center.as_ptr(),
+                half_extents.as_ptr(),
+                &filter.0,
+                &mut nearest_ref,
+                nearest_pt.as_mut_ptr(),
+            )
+        };
+        DetourStatus::from_raw_status(status).wrap_result((nearest_ref, nearest_pt))
+    }
+
+    /// dtNavMeshQuery::findPath in Detour, returns the polygon corridor from start to end.
+    ///
+    /// The status contains [`DetourStatusDetails::PartialResult`] if the end polygon could not be reached.
+    pub fn find_path(
+        &mut self,
+        start_ref: dtPolyRef,
+        end_ref: dtPolyRef,
+        start_pos: &[f32; 3],
+        end_pos: &[f32; 3],
+        filter: &DetourQueryFilter,
+        max_path: usize,
+    ) -> DetourResult<Vec<dtPolyRef>> {
+        let mut path = vec![0; max_path];
+        let mut path_count = 0;
+        let status = unsafe {
+            dtNavMeshQuery_findPath(
+                self.handle,
+                start_ref,
+                end_ref,
+                start_pos.as_ptr(),
+                end_pos.as_ptr(),
+                &filter.0,
+                path.as_mut_ptr(),
+                &mut path_count,
+                max_path as _,
+            )
+        };
+        path.truncate(path_count.max(0) as usize);
+        DetourStatus::from_raw_status(status).wrap_result(path)
+    }
+
+    /// dtNavMeshQuery::findStraightPath in Detour, finds the straight path points along the given polygon corridor.
+    pub fn find_straight_path(
+        &mut self,
+        start_pos: &[f32; 3],
+        end_pos: &[f32; 3],
+        path: &[dtPolyRef],
+        max_straight_path: usize,
+    ) -> DetourResult<Vec<DetourStraightPathPoint>> {
+        let mut straight_path = vec![0.0; max_straight_path * 3];
+        let mut straight_path_flags = vec![0; max_straight_path];
+        let mut straight_path_refs = vec![0; max_straight_path];
+        let mut straight_path_count = 0;
+        let status = unsafe {
+            dtNavMeshQuery_findStraightPath(
+                self.handle,
+                start_pos.as_ptr(),
+                end_pos.as_ptr(),
+                path.as_ptr(),
+                path.len() as _,
+                straight_path.as_mut_ptr(),
+                straight_path_flags.as_mut_ptr(),
+                straight_path_refs.as_mut_ptr(),
+                &mut straight_path_count,
+                max_straight_path as _,
+                0,
+            )
+        };
+        let points = (0..straight_path_count.max(0) as usize)
+            .map(|i| DetourStraightPathPoint {
+                pos:      [straight_path[i * 3], straight_path[i * 3 + 1], straight_path[i * 3 + 2]],
+                flags:    straight_path_flags[i],
+                poly_ref: straight_path_refs[i],
+            })
+            .collect();
+        DetourStatus::from_raw_status(status).wrap_result(points)
+    }
+
+    /// dtNavMeshQuery::moveAlongSurface in Detour, returns the resulting position and the visited polygons.
+    pub fn move_along_surface(
+        &mut self,
+        start_ref: dtPolyRef,
+        start_pos: &[f32; 3],
+        end_pos: &[f32; 3],
+        filter: &DetourQueryFilter,
+        max_visited: usize,
+    ) -> DetourResult<([f32; 3], Vec<dtPolyRef>)> {
+        let mut result_pos = [0.0; 3];
+        let mut visited = vec![0; max_visited];
+        let mut visited_count = 0;
+        let status = unsafe {
+            dtNavMeshQuery_moveAlongSurface(
+                self.handle,
+                start_ref,
+                start_pos.as_ptr(),
+                end_pos.as_ptr(),
+                &filter.0,
+                result_pos.as_mut_ptr(),
+                visited.as_mut_ptr(),
+                &mut visited_count,
+                max_visited as _,
+            )
+        };
+        visited.truncate(visited_count.max(0) as usize);
+        DetourStatus::from_raw_status(status).wrap_result((result_pos, visited))
+    }
+
+    /// dtNavMeshQuery::findRandomPointAroundCircle in Detour, returns a random polygon and point on it that
+    /// is reachable from the start polygon within the given radius.
+    pub fn find_random_point_around_circle(
+        &mut self,
+        start_ref: dtPolyRef,
+        center_pos: &[f32; 3],
+        max_radius: f32,
+        filter: &DetourQueryFilter,
+    ) -> DetourResult<(dtPolyRef, [f32; 3])> {
+        let mut random_ref = 0;
+        let mut random_pt = [0.0; 3];
+        let status = unsafe {
+            dtNavMeshQuery_findRandomPointAroundCircle(
+                self.handle,
+                start_ref,
+                center_pos.as_ptr(),
+                max_radius,
+                &filter.0,
+                Some(detour_frand),
+                &mut random_ref,
+                random_pt.as_mut_ptr(),
+            )
+        };
+        DetourStatus::from_raw_status(status).wrap_result((random_ref, random_pt))
+    }
+
+    /// dtNavMeshQuery::closestPointOnPolyBoundary in Detour
+    pub fn closest_point_on_poly_boundary(&mut self, poly_ref: dtPolyRef, pos: &[f32; 3]) -> DetourResult<[f32; 3]> {
+        let mut closest = [0.0; 3];
+        let status = unsafe { dtNavMeshQuery_closestPointOnPolyBoundary(self.handle, poly_ref, pos.as_ptr(), closest.as_mut_ptr()) };
+        DetourStatus::from_raw_status(status).wrap_result(closest)
+    }
+
+    /// dtNavMeshQuery::getPolyHeight in Detour
+    pub fn get_poly_height(&mut self, poly_ref: dtPolyRef, pos: &[f32; 3]) -> DetourResult<f32> {
+        let mut height = 0.0;
+        let status = unsafe { dtNavMeshQuery_getPolyHeight(self.handle, poly_ref, pos.as_ptr(), &mut height) };
+        DetourStatus::from_raw_status(status).wrap_result(height)
+    }
 }
 
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
@@ -50,6 +50,63 @@ pub fn unix_now() -> Duration {
     SystemTime::now().duration_since(UNIX_EPOCH).unwrap()
 }
 
+/// isBasicLatinCharacter in TC/AC
+pub fn is_basic_latin_character(c: char) -> bool {
+    c.is_ascii_alphabetic()
+}
+
+/// isExtendedLatinCharacter in TC/AC
+pub fn is_extended_latin_character(c: char) -> bool {
+    is_basic_latin_character(c)
+        || matches!(c as u32,
+            0x00C0..=0x00D6 // LATIN CAPITAL LETTER A WITH GRAVE - LATIN CAPITAL LETTER O WITH DIAERESIS
+            | 0x00D8..=0x00DE // LATIN CAPITAL LETTER O WITH STROKE - LATIN CAPITAL LETTER THORN
+            | 0x00DF // LATIN SMALL LETTER SHARP S
+            | 0x00E0..=0x00F6 // LATIN SMALL LETTER A WITH GRAVE - LATIN SMALL LETTER O WITH DIAERESIS
+            | 0x00F8..=0x00FE // LATIN SMALL LETTER O WITH STROKE - LATIN SMALL LETTER THORN
+            | 0x0100..=0x012F // LATIN CAPITAL LETTER A WITH MACRON - LATIN SMALL LETTER I WITH OGONEK
+            | 0x1E9E // LATIN CAPITAL LETTER SHARP S
+        )
+}
+
+/// isCyrillicCharacter in TC/AC
+pub fn is_cyrillic_character(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x0410
+            ..=0x044F // CYRILLIC CAPITAL LETTER A - CYRILLIC SMALL LETTER YA
+        | 0x0401 // CYRILLIC CAPITAL LETTER IO
+        | 0x0451 // CYRILLIC SMALL LETTER IO
+    )
+}
+
+/// isEastAsianCharacter in TC/AC
+pub fn is_east_asian_character(c: char) -> bool {
+    matches!(c as u32,
+        0x1100..=0x11F9 // Hangul Jamo
+        | 0x3041..=0x30FF // Hiragana + Katakana
+        | 0x3131..=0x318E // Hangul Compatibility Jamo
+        | 0x31F0..=0x31FF // Katakana Phonetic Ext.
+        | 0x3400..=0x4DB5 // CJK Ideographs Ext. A
+        | 0x4E00..=0x9FC3 // Unified CJK Ideographs
+        | 0xAC00..=0xD7A3 // Hangul Syllables
+        | 0xFF01..=0xFFEE // Halfwidth forms
+    )
+}
+
+/// isNumericOrSpace in TC/AC
+pub fn is_numeric_or_space(c: char) -> bool {
+    c.is_ascii_digit() || c == ' '
+}
+
+/// Whether every character in `s` passes `is_valid_char`, additionally allowing
+/// digits and spaces if `numeric_or_space` is set.
+///
+/// Covers isBasicLatinString, isExtendedLatinString, isCyrillicString and isEastAsianString in TC/AC
+pub fn is_string_of(s: &str, numeric_or_space: bool, is_valid_char: impl Fn(char) -> bool) -> bool {
+    s.chars().all(|c| is_valid_char(c) || (numeric_or_space && is_numeric_or_space(c)))
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum BufferDecodeError {
     #[error("insufficient bytes in buffer, have {have} bytes to read but want {wanted} bytes")]
@@ -3,9 +3,17 @@ mod login_db {
 
     use hugsqlx::HugSqlx;
 
-    #[derive(HugSqlx)]
-    #[queries = "data/sql/queries/login_db_prep_stmts.sql"]
-    pub struct LoginStmts {}
+    cfg_if::cfg_if! {
+    if #[cfg(feature = "sqlite")] {
+        #[derive(HugSqlx)]
+        #[queries = "data/sql/queries/sqlite/login_db_prep_stmts.sql"]
+        pub struct LoginStmts {}
+    } else {
+        #[derive(HugSqlx)]
+        #[queries = "data/sql/queries/mysql/login_db_prep_stmts.sql"]
+        pub struct LoginStmts {}
+    }
+    }
 }
 
 mod character_db {
@@ -14,7 +22,7 @@ mod character_db {
     use hugsqlx::HugSqlx;
 
     #[derive(HugSqlx)]
-    #[queries = "data/sql/queries/character_db_prep_stmts.sql"]
+    #[queries = "data/sql/queries/mysql/character_db_prep_stmts.sql"]
     pub struct CharacterStmts {}
 }
 
@@ -24,7 +32,7 @@ mod world_db {
     use hugsqlx::HugSqlx;
 
     #[derive(HugSqlx)]
-    #[queries = "data/sql/queries/world_db_prep_stmts.sql"]
+    #[queries = "data/sql/queries/mysql/world_db_prep_stmts.sql"]
     pub struct WorldStmts {}
 }
 
@@ -34,7 +42,7 @@ mod hotfix_db {
     use hugsqlx::HugSqlx;
 
     #[derive(HugSqlx)]
-    #[queries = "data/sql/queries/hotfix_db_prep_stmts.sql"]
+    #[queries = "data/sql/queries/mysql/hotfix_db_prep_stmts.sql"]
     pub struct HotfixStmts {}
 }
 
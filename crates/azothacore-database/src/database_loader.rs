@@ -11,9 +11,10 @@ use azothacore_common::{
     configuration::{DatabaseInfo, DatabaseType, DbUpdates},
     hex_str,
 };
+use serde::Serialize;
 use sha2::{Digest, Sha256};
 use sqlx::{
-    mysql::MySqlDatabaseError,
+    error::DatabaseError,
     pool::PoolOptions,
     query,
     query_as,
@@ -28,13 +29,19 @@ use walkdir::WalkDir;
 use crate::{
     args,
     database_loader_utils::{apply_file, DatabaseLoaderError},
+    dialect::DbDialect,
     DbDriver,
     ExtendedDBInfo,
+    DB_DIALECT,
 };
 
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Copy)]
-enum FetcherState {
+/// Where an update comes from, i.e. State in TC / AC
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Copy, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum FetcherState {
     Released,
+    /// Updates that are not released yet, see [crate::pending_updates]
+    Pending,
     Custom,
     Module,
     Archived,
@@ -52,6 +59,7 @@ impl FromStr for FetcherState {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "RELEASED" => Ok(FetcherState::Released),
+            "PENDING" => Ok(FetcherState::Pending),
             "CUSTOM" => Ok(FetcherState::Custom),
             "MODULE" => Ok(FetcherState::Module),
             "ARCHIVED" => Ok(FetcherState::Archived),
@@ -69,6 +77,7 @@ impl Display for FetcherState {
             FetcherState::Custom => "CUSTOM",
             FetcherState::Module => "MODULE",
             FetcherState::Released => "RELEASED",
+            FetcherState::Pending => "PENDING",
         };
         write!(f, "{s}")
     }
@@ -81,14 +90,91 @@ enum UpdateMode {
 }
 
 struct AppliedFileEntry {
-    #[expect(dead_code)]
     name:           String,
     hash:           String,
     state:          FetcherState,
-    #[expect(dead_code)]
     unix_timestamp: DateTime<Utc>,
 }
 
+/// A step taken when updating a database, see [DatabaseLoader::plan_updates]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum PlannedUpdate {
+    /// The update was never applied
+    Apply {
+        name:  String,
+        path:  PathBuf,
+        hash:  String,
+        state: FetcherState,
+    },
+    /// The update was applied, but changed since
+    Reapply {
+        name:     String,
+        path:     PathBuf,
+        old_hash: String,
+        hash:     String,
+        state:    FetcherState,
+    },
+    /// The update was applied, only its hash is recorded again
+    Rehash {
+        name:  String,
+        path:  PathBuf,
+        hash:  String,
+        state: FetcherState,
+    },
+    /// The update was applied, but was moved to a directory of another state
+    UpdateState { name: String, from: FetcherState, to: FetcherState },
+    /// The update was applied under another name that is gone now, i.e. a released pending update
+    Rename {
+        from:  String,
+        to:    String,
+        hash:  String,
+        state: FetcherState,
+    },
+    /// The update was applied, but is missing on disk now
+    Cleanup { name: String },
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct UpdatePlan {
+    /// The steps to take, in order
+    pub actions:  Vec<PlannedUpdate>,
+    /// Number of released or pending updates applied before the plan is run
+    pub recent:   usize,
+    /// Number of archived, custom or module updates applied before the plan is run
+    pub archived: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateStatusKind {
+    /// Applied with the same hash as the update on disk
+    Applied,
+    NotApplied,
+    /// Applied with a different hash than the update on disk, or without any hash
+    HashMismatch,
+    /// Applied, but missing on disk
+    Missing,
+}
+
+/// An update on disk and / or in the `updates` table, see [DatabaseLoader::status]
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateStatus {
+    pub name:          String,
+    pub path:          Option<PathBuf>,
+    /// State of the update on disk
+    pub state:         Option<FetcherState>,
+    /// Hash of the update on disk
+    pub hash:          Option<String>,
+    /// State recorded in the `updates` table
+    pub applied_state: Option<FetcherState>,
+    /// Hash recorded in the `updates` table
+    pub applied_hash:  Option<String>,
+    /// When the update was applied, as a unix timestamp
+    pub applied_at:    Option<i64>,
+    pub status:        UpdateStatusKind,
+}
+
 pub struct DatabaseLoader {
     modules_list:    Vec<String>,
     database_config: ExtendedDBInfo,
@@ -154,7 +240,13 @@ impl DatabaseLoader {
             .map(|e| {
                 let mut p = Path::new(DATABASE_FILES_SRC_ROOT).to_path_buf();
 
-                p.extend(&["azothacore-modules", e, "data/sql", self.database_config.db_module_name()]);
+                p.extend(&[
+                    "azothacore-modules",
+                    e,
+                    "data/sql",
+                    DB_DIALECT.dir_name(),
+                    self.database_config.db_module_name(),
+                ]);
                 p
             })
             .collect()
@@ -178,35 +270,48 @@ impl DatabaseLoader {
         Ok(pool)
     }
 
+    /// Connects to the database without creating it if it's missing, unlike [Self::open_database]
+    pub async fn connect(&self) -> Result<sqlx::Pool<DbDriver>, DatabaseLoaderError> {
+        let pool = PoolOptions::<DbDriver>::new()
+            .max_connections(5)
+            .idle_timeout(Some(Duration::from_secs(30)))
+            .connect(&DB_DIALECT.connect_url(&self.database_config))
+            .await?;
+        Ok(pool)
+    }
+
     pub async fn open_database(&self) -> Result<sqlx::Pool<DbDriver>, DatabaseLoaderError> {
+        let connect_url = DB_DIALECT.connect_url(&self.database_config);
         let pool = match PoolOptions::<DbDriver>::new()
             .max_connections(50)
             .idle_timeout(Some(Duration::from_secs(30)))
-            .connect(&self.database_config.connect_url())
+            .connect(&connect_url)
             .await
         {
             Ok(p) => p,
             Err(sqlx::Error::Database(e)) => {
-                let e = e.try_downcast::<MySqlDatabaseError>()?;
-                // i.e. ER_BAD_DB_ERRORl
-                if e.number() == 1049 && self.database_config.updates_enabled() && self.database_config.updates.AutoSetup {
+                let (Some(url_without_db), Some(create_stmt)) = (
+                    DB_DIALECT.connect_url_without_db(&self.database_config),
+                    DB_DIALECT.create_database_statement(&self.database_config.DatabaseName),
+                ) else {
+                    return Err(sqlx::Error::Database(e).into());
+                };
+                if !is_unknown_database_error(e.as_ref()) {
+                    return Err(sqlx::Error::Database(e).into());
+                }
+                if self.database_config.updates_enabled() && self.database_config.updates.AutoSetup {
                     let conn = PoolOptions::<DbDriver>::new()
                         .max_connections(1)
                         .min_connections(1)
-                        .connect(&self.database_config.connect_url_without_db())
+                        .connect(&url_without_db)
                         .await?;
                     warn!("Database {} does not exist!", self.database_config.DatabaseName);
                     info!("Creating database '{}'...", self.database_config.DatabaseName);
-                    query(&format!(
-                        "CREATE DATABASE `{}` DEFAULT CHARACTER SET UTF8MB4 COLLATE utf8mb4_general_ci",
-                        self.database_config.DatabaseName
-                    ))
-                    .execute(&conn)
-                    .await?;
+                    query(&create_stmt).execute(&conn).await?;
                     info!("Done.\n");
                     conn.close().await;
                 }
-                PoolOptions::<DbDriver>::new().connect(&self.database_config.connect_url()).await?
+                PoolOptions::<DbDriver>::new().connect(&connect_url).await?
             },
             Err(e) => return Err(e.into()),
         };
@@ -217,7 +322,7 @@ impl DatabaseLoader {
         if !self.database_config.updates_enabled() {
             return Ok(());
         }
-        let res = query("SHOW TABLES").fetch_optional(&pool).await?;
+        let res = query(DB_DIALECT.list_tables_statement()).fetch_optional(&pool).await?;
         if res.is_some() {
             return Ok(());
         }
@@ -288,15 +393,41 @@ impl DatabaseLoader {
     }
 
     async fn fetch_and_apply_updates(&self, pool: sqlx::Pool<DbDriver>) -> Result<(usize, usize, usize), DatabaseLoaderError> {
+        let plan = self.plan_updates(pool.clone()).await?;
+
+        let mut imported_updates = 0;
+        let mut to_cleanup = vec![];
+        for action in plan.actions {
+            match action {
+                PlannedUpdate::Cleanup { name } => {
+                    info!("Deleting orphaned entry from \'updates\' table in DB: \'{name}\'...");
+                    to_cleanup.push(name);
+                },
+                action => {
+                    if self.execute_planned_update(pool.clone(), &action).await? {
+                        imported_updates += 1;
+                    }
+                },
+            }
+        }
+        Self::clean_up(pool.clone(), to_cleanup).await?;
+        Ok((imported_updates, plan.recent, plan.archived))
+    }
+
+    /// Works out what updating the database would do, in order, without changing the database.
+    ///
+    /// The `updates` and `updates_include` tables are expected to exist already, i.e. the database was updated at
+    /// least once. Unlike [Self::load], the plan is made even if automatic updates are disabled for the database.
+    pub async fn plan_updates(&self, pool: sqlx::Pool<DbDriver>) -> Result<UpdatePlan, DatabaseLoaderError> {
         let available = self.get_file_list(pool.clone()).await?;
         if available.is_empty() {
-            return Ok((0, 0, 0));
+            return Ok(UpdatePlan::default());
         }
         let mut applied = Self::receive_applied_files(pool.clone()).await?;
         let start: (usize, usize) = (0, 0);
         // Count updates
-        let (count_recent_updates, count_archived_updates) = applied.iter().fold(start, |acc: (usize, usize), e| {
-            if let FetcherState::Released = e.1.state {
+        let (recent, archived) = applied.iter().fold(start, |acc: (usize, usize), e| {
+            if let FetcherState::Released | FetcherState::Pending = e.1.state {
                 (acc.0 + 1, acc.1)
             } else {
                 (acc.0, acc.1 + 1)
@@ -305,8 +436,6 @@ impl DatabaseLoader {
 
         let hash_to_filename: BTreeMap<_, _> = applied.iter().map(|e| (e.1.hash.clone(), e.0.clone())).collect();
 
-        let mut imported_updates = 0;
-
         let mut new_available = Vec::with_capacity(available.len());
         let mut custom = vec![];
         for f in available {
@@ -318,40 +447,30 @@ impl DatabaseLoader {
         }
         new_available.append(&mut custom);
 
+        let mut actions = vec![];
         for f in new_available.iter() {
-            let Some(mode) = self
-                .check_if_update_should_be_applied(pool.clone(), &mut applied, &hash_to_filename, &new_available, f)
-                .await?
-            else {
-                continue;
-            };
-
-            self.apply_update_file(pool.clone(), f, mode).await?;
-            applied.remove(&f.name());
-            imported_updates += 1;
+            if let Some(action) = self.plan_update(&mut applied, &hash_to_filename, &new_available, f)? {
+                actions.push(action);
+            }
         }
         let do_cleanup = self.database_config.updates.should_cleanup(applied.len());
         // Cleanup up orphaned entries (if enabled)
-        let mut to_cleanup = vec![];
+        let mut dirty = 0;
         for (filename, file_entry) in applied {
             if file_entry.state == FetcherState::Module {
                 continue;
             }
             warn!(">> The file \'{filename}\' was applied to the database, but is missing in your update directory now!");
             if do_cleanup {
-                info!("Deleting orphaned entry from \'updates\' table in DB: \'{filename}\'...");
-                to_cleanup.push(filename);
+                actions.push(PlannedUpdate::Cleanup { name: filename });
+            } else {
+                dirty += 1;
             }
         }
-        if do_cleanup {
-            Self::clean_up(pool.clone(), to_cleanup).await?;
-        } else if !to_cleanup.is_empty() {
-            error!(
-                "Cleanup is disabled! There were {} dirty files applied to your database, but they are now missing in your source directory!",
-                to_cleanup.len()
-            );
+        if dirty > 0 {
+            error!("Cleanup is disabled! There were {dirty} dirty files applied to your database, but they are now missing in your source directory!",);
         }
-        Ok((imported_updates, count_recent_updates, count_archived_updates))
+        Ok(UpdatePlan { actions, recent, archived })
     }
 
     async fn get_file_list(&self, pool: sqlx::Pool<DbDriver>) -> Result<Vec<DbFile>, DatabaseLoaderError> {
@@ -379,8 +498,10 @@ impl DatabaseLoader {
                     path:  f.path().to_path_buf(),
                     state: dir_state,
                 };
-                if storage.contains(&r) {
-                    return Err(DatabaseLoaderError::Generic { msg: format!("Updating failed due to duplicate filename \"{}\" found. Because updates are ordered by their filenames, every name needs to be unique!", r.path.display()) });
+                // Updates are tracked by their name alone, so two update directories having the same file name
+                // (i.e. two contributors' pending updates) would have one of them shadow the other.
+                if let Some(existing) = storage.iter().find(|e| e.name() == r.name()) {
+                    return Err(DatabaseLoaderError::Generic { msg: format!("Updating failed due to duplicate filename \"{}\" found, it clashes with \"{}\". Because updates are ordered by their filenames, every name needs to be unique!", r.path.display(), existing.path.display()) });
                 }
                 storage.push(r);
             }
@@ -395,9 +516,9 @@ impl DatabaseLoader {
             .await?
             .into_iter()
             .filter_map(|(p, state)| {
-                let p = if p.starts_with("$/") {
+                let p = if let Some(rel) = p.strip_prefix("$/") {
                     let mut pb = Path::new(DATABASE_FILES_SRC_ROOT).to_path_buf();
-                    pb.push(p.trim_start_matches("$/"));
+                    pb.push(source_relative_dialect_path(DB_DIALECT, rel));
                     pb
                 } else {
                     Path::new(&p).to_path_buf()
@@ -469,55 +590,129 @@ impl DatabaseLoader {
         Ok(map)
     }
 
-    async fn check_if_update_should_be_applied(
+    /// Compares the updates on disk against the `updates` table, without changing the database.
+    ///
+    /// Updates on disk come first, in the order they are applied in, followed by the applied updates that are
+    /// missing on disk.
+    pub async fn status(&self, pool: sqlx::Pool<DbDriver>) -> Result<Vec<UpdateStatus>, DatabaseLoaderError> {
+        let available = self.get_file_list(pool.clone()).await?;
+        let mut applied = Self::receive_applied_files(pool).await?;
+
+        let mut res = Vec::with_capacity(available.len());
+        for f in available {
+            let hash = f.hash()?;
+            let entry = applied.remove(&f.name());
+            let status = match &entry {
+                None => UpdateStatusKind::NotApplied,
+                Some(e) if e.hash == hash => UpdateStatusKind::Applied,
+                Some(_) => UpdateStatusKind::HashMismatch,
+            };
+            res.push(UpdateStatus {
+                name: f.name(),
+                path: Some(f.path),
+                state: Some(f.state),
+                hash: Some(hash),
+                applied_state: entry.as_ref().map(|e| e.state),
+                applied_hash: entry.as_ref().map(|e| e.hash.clone()),
+                applied_at: entry.as_ref().map(|e| e.unix_timestamp.timestamp()),
+                status,
+            });
+        }
+        res.extend(applied.into_values().map(|e| UpdateStatus {
+            name:          e.name,
+            path:          None,
+            state:         None,
+            hash:          None,
+            applied_state: Some(e.state),
+            applied_hash:  Some(e.hash),
+            applied_at:    Some(e.unix_timestamp.timestamp()),
+            status:        UpdateStatusKind::Missing,
+        }));
+        Ok(res)
+    }
+
+    /// Checks that every applied update still exists on disk and has the hash it was applied with,
+    /// returning the ones that don't. Nothing is applied or rolled back.
+    ///
+    /// Applied module updates missing on disk are left out, as their module may just be disabled.
+    pub async fn verify(&self, pool: sqlx::Pool<DbDriver>) -> Result<Vec<UpdateStatus>, DatabaseLoaderError> {
+        Ok(self
+            .status(pool)
+            .await?
+            .into_iter()
+            .filter(|s| match s.status {
+                UpdateStatusKind::HashMismatch => true,
+                UpdateStatusKind::Missing => s.applied_state != Some(FetcherState::Module),
+                UpdateStatusKind::Applied | UpdateStatusKind::NotApplied => false,
+            })
+            .collect())
+    }
+
+    /// Records the hash of the files of applied updates that were recorded without one if `Updates.AllowRehash` is
+    /// set, as updating the database would, returning what was re-hashed.
+    ///
+    /// No SQL of the updates is run, and nothing else is applied, renamed or cleaned up.
+    pub async fn rehash(&self, pool: sqlx::Pool<DbDriver>) -> Result<Vec<PlannedUpdate>, DatabaseLoaderError> {
+        let actions = self
+            .plan_updates(pool.clone())
+            .await?
+            .actions
+            .into_iter()
+            .filter(|a| matches!(a, PlannedUpdate::Rehash { .. }))
+            .collect::<Vec<_>>();
+        for action in actions.iter() {
+            self.execute_planned_update(pool.clone(), action).await?;
+        }
+        Ok(actions)
+    }
+
+    fn plan_update(
         &self,
-        pool: sqlx::Pool<DbDriver>,
         applied: &mut BTreeMap<String, AppliedFileEntry>,
         applied_hash_to_name: &BTreeMap<String, String>,
         available: &[DbFile],
         file: &DbFile,
-    ) -> Result<Option<UpdateMode>, DatabaseLoaderError> {
+    ) -> Result<Option<PlannedUpdate>, DatabaseLoaderError> {
         let hash = file.hash()?;
         info!("Checking update \"{}\"...", file.name());
 
-        let mut mode = UpdateMode::Apply;
-        match (applied.get(&file.name()), applied_hash_to_name.get(&hash)) {
+        let action = match (applied.get(&file.name()), applied_hash_to_name.get(&hash)) {
             (Some(_), _) if !self.database_config.updates.Redundancy => {
                 // If redundancy is disabled, skip it, because the update is already applied.
                 info!(">> Update is already applied, skipping redundancy checks.");
-                applied.remove(&file.name());
-                return Ok(None);
+                None
             },
             (Some(iter), _)
                 if !self.database_config.updates.ArchivedRedundancy && iter.state == FetcherState::Archived && file.state == FetcherState::Archived =>
             {
                 // If the update is in an archived directory and is marked as archived in our database, skip redundancy checks (archived updates never change).
                 info!(">> Update is archived and marked as archived in database, skipping redundancy checks.");
-                applied.remove(&file.name());
-                return Ok(None);
-            },
-            (Some(iter), _) if self.database_config.updates.AllowRehash && iter.hash.is_empty() => {
-                mode = UpdateMode::Rehash;
-                info!(">> Re-hashing update \"{}\" \'{}\'...", file.path.to_string_lossy(), &hash);
-            },
-            (Some(iter), _) if iter.hash != hash => {
-                info!(
-                    ">> Reapplying update \"{}\" \'{}\' -> \'{}\' (it changed)...",
-                    file.path.to_string_lossy(),
-                    iter.hash,
-                    hash
-                );
+                None
             },
+            (Some(iter), _) if self.database_config.updates.AllowRehash && iter.hash.is_empty() => Some(PlannedUpdate::Rehash {
+                name: file.name(),
+                path: file.path.clone(),
+                hash,
+                state: file.state,
+            }),
+            (Some(iter), _) if iter.hash != hash => Some(PlannedUpdate::Reapply {
+                name: file.name(),
+                path: file.path.clone(),
+                old_hash: iter.hash.clone(),
+                hash,
+                state: file.state,
+            }),
             (Some(iter), _) => {
                 if iter.state != file.state {
-                    info!(">> Updating the state of \"{}\" to \'{:?}\'...", file.path.to_string_lossy(), file.state);
-                    query_with("UPDATE `updates` SET `state` = ? where `name` = ?", args!(file.state.to_string(), file.name())?)
-                        .execute(&pool)
-                        .await?;
+                    Some(PlannedUpdate::UpdateState {
+                        name: file.name(),
+                        from: iter.state,
+                        to:   file.state,
+                    })
+                } else {
+                    info!(">> Update is already applied and matches the hash \'{}\'.", hash);
+                    None
                 }
-                info!(">> Update is already applied and matches the hash \'{}\'.", hash);
-                applied.remove(&file.name());
-                return Ok(None);
             },
             // Update is not in our applied list
             (None, Some(applied_hash_filename)) => {
@@ -527,17 +722,13 @@ impl DatabaseLoader {
                 let available_local = available.iter().find(|e| e.name() == *applied_hash_filename);
                 let Some(available_local_file) = available_local else {
                     // It is safe to treat the file as renamed here
-                    let from = applied_hash_filename;
-                    let to = file.name();
-                    info!(">> Renaming update \"{from}\" to \"{to}\" \'{hash}\'.");
-                    let mut txn = pool.begin().await?;
-                    query("DELETE FROM `updates` WHERE `name`= ?").bind(&to).execute(&mut *txn).await?;
-                    query_with("UPDATE `updates` SET `name`=? WHERE `name`=?", args!(&to, &from)?)
-                        .execute(&mut *txn)
-                        .await?;
-                    txn.commit().await?;
                     applied.remove(applied_hash_filename);
-                    return Ok(None);
+                    return Ok(Some(PlannedUpdate::Rename {
+                        from: applied_hash_filename.clone(),
+                        to: file.name(),
+                        hash,
+                        state: file.state,
+                    }));
                 };
                 // Conflict!
                 warn!(
@@ -547,25 +738,92 @@ impl DatabaseLoader {
                     hash,
                     available_local_file.path.display(),
                 );
+                Some(PlannedUpdate::Apply {
+                    name: file.name(),
+                    path: file.path.clone(),
+                    hash,
+                    state: file.state,
+                })
             },
-            (None, None) => {
-                // Apply the update if it was never seen before.
-                info!(">> Applying update \"{}\" \'{}\'...", file.path.to_string_lossy(), hash);
-            },
+            // Apply the update if it was never seen before.
+            (None, None) => Some(PlannedUpdate::Apply {
+                name: file.name(),
+                path: file.path.clone(),
+                hash,
+                state: file.state,
+            }),
         };
-        Ok(Some(mode))
+        applied.remove(&file.name());
+        Ok(action)
     }
 
-    async fn apply_update_file(&self, pool: sqlx::Pool<DbDriver>, file: &DbFile, mode: UpdateMode) -> Result<(), DatabaseLoaderError> {
+    /// Runs a planned update against the database, returning if it counts as an imported update.
+    /// Cleanups are batched by the caller instead, see [Self::clean_up]
+    async fn execute_planned_update(&self, pool: sqlx::Pool<DbDriver>, action: &PlannedUpdate) -> Result<bool, DatabaseLoaderError> {
+        match action {
+            PlannedUpdate::Apply { name, path, hash, state } => {
+                info!(">> Applying update \"{}\" \'{}\'...", path.display(), hash);
+                self.apply_update_file(pool, name, path, hash, *state, UpdateMode::Apply).await?;
+            },
+            PlannedUpdate::Reapply {
+                name,
+                path,
+                old_hash,
+                hash,
+                state,
+            } => {
+                info!(">> Reapplying update \"{}\" \'{}\' -> \'{}\' (it changed)...", path.display(), old_hash, hash);
+                self.apply_update_file(pool, name, path, hash, *state, UpdateMode::Apply).await?;
+            },
+            PlannedUpdate::Rehash { name, path, hash, state } => {
+                info!(">> Re-hashing update \"{}\" \'{}\'...", path.display(), hash);
+                self.apply_update_file(pool, name, path, hash, *state, UpdateMode::Rehash).await?;
+            },
+            PlannedUpdate::UpdateState { name, to, .. } => {
+                info!(">> Updating the state of \"{name}\" to \'{to:?}\'...");
+                query_with("UPDATE `updates` SET `state` = ? where `name` = ?", args!(to.to_string(), name)?)
+                    .execute(&pool)
+                    .await?;
+                return Ok(false);
+            },
+            PlannedUpdate::Rename { from, to, hash, state } => {
+                info!(">> Renaming update \"{from}\" to \"{to}\" \'{hash}\'.");
+                let mut txn = pool.begin().await?;
+                query("DELETE FROM `updates` WHERE `name`= ?").bind(to).execute(&mut *txn).await?;
+                // A pending update being released keeps its hash, but is moved to a released directory
+                query_with("UPDATE `updates` SET `name`=?, `state`=? WHERE `name`=?", args!(to, state.to_string(), from)?)
+                    .execute(&mut *txn)
+                    .await?;
+                txn.commit().await?;
+                return Ok(false);
+            },
+            PlannedUpdate::Cleanup { name } => {
+                Self::clean_up(pool, vec![name.clone()]).await?;
+                return Ok(false);
+            },
+        }
+        Ok(true)
+    }
+
+    async fn apply_update_file(
+        &self,
+        pool: sqlx::Pool<DbDriver>,
+        name: &str,
+        path: &Path,
+        hash: &str,
+        state: FetcherState,
+        mode: UpdateMode,
+    ) -> Result<(), DatabaseLoaderError> {
         let mut txn = pool.begin().await?;
         let now = Instant::now();
         if matches!(mode, UpdateMode::Apply) {
-            apply_file(&mut *txn, &file.path, file.is_gz()).await?;
+            let is_gz = path.extension().filter(|ext| *ext == "gz").is_some();
+            apply_file(&mut *txn, path, is_gz).await?;
         }
         let speed = now.elapsed();
         query_with(
             "REPLACE INTO `updates` (`name`, `hash`, `state`, `speed`) VALUES (?,?,?,?)",
-            args!(file.name(), file.hash()?, file.state.to_string(), speed.as_millis().to_string())?,
+            args!(name, hash, state.to_string(), speed.as_millis().to_string())?,
         )
         .execute(&mut *txn)
         .await?;
@@ -578,7 +836,7 @@ impl DatabaseLoader {
             return Ok(());
         }
         let q = format!("DELETE FROM `updates` WHERE `name` IN ({})", vec!["?"; storage.len()].join(","));
-        let mut q = query(q.as_str());
+        let mut q = query(&q);
         for name in storage {
             q = q.bind(name);
         }
@@ -587,16 +845,16 @@ impl DatabaseLoader {
     }
 
     async fn check_update_table(&self, pool: sqlx::Pool<DbDriver>, table_name: &str) -> Result<(), DatabaseLoaderError> {
-        let res = query(&format!("SHOW TABLES LIKE '{}'", table_name)).fetch_optional(&pool).await?;
+        let res = query(DB_DIALECT.table_exists_statement()).bind(table_name).fetch_optional(&pool).await?;
         if res.is_some() {
             return Ok(());
         }
         warn!("> Table '{}' not exist! Trying adding base table", table_name);
 
         let mut is_gz = false;
-        let mut f = self.database_config.base_files_dir().join("{table_name}.sql");
+        let mut f = self.database_config.base_files_dir().join(format!("{table_name}.sql"));
         if !f.exists() {
-            f = self.database_config.base_files_dir().join("{table_name}.sql.gz");
+            f = self.database_config.base_files_dir().join(format!("{table_name}.sql.gz"));
             is_gz = true;
         }
 
@@ -604,7 +862,7 @@ impl DatabaseLoader {
         let mut txn = pool.begin().await?;
         apply_file(&mut *txn, f, is_gz).await.map_err(|e| {
             error!(
-                "Failed apply file to database {db_name} due to error: {e}! Does the user (named in *.conf) have `INSERT` and `DELETE` privileges on the MySQL server?",
+                "Failed apply file to database {db_name} due to error: {e}! Does the user (named in *.conf) have `INSERT` and `DELETE` privileges on the database server?",
             );
             e
         })?;
@@ -612,3 +870,185 @@ impl DatabaseLoader {
         Ok(())
     }
 }
+
+/// Inserts the dialect directory into a source relative update include path, i.e.
+/// `data/sql/updates/db-auth` becomes `data/sql/updates/mysql/db-auth`.
+///
+/// Paths that already name a dialect directory, or that aren't under `data/sql/<kind>`, are returned unchanged.
+fn source_relative_dialect_path(dialect: DbDialect, rel: &str) -> PathBuf {
+    let rel = Path::new(rel);
+    let components = rel.iter().collect::<Vec<_>>();
+    let Some(kind_idx) = components.windows(2).position(|w| w[0] == "data" && w[1] == "sql").map(|i| i + 2) else {
+        return rel.to_path_buf();
+    };
+    if kind_idx + 1 >= components.len() || components[kind_idx + 1] == dialect.dir_name() {
+        return rel.to_path_buf();
+    }
+    let mut pb = PathBuf::new();
+    pb.extend(&components[..=kind_idx]);
+    pb.push(dialect.dir_name());
+    pb.extend(&components[kind_idx + 1..]);
+    pb
+}
+
+cfg_if::cfg_if! {
+if #[cfg(feature = "sqlite")] {
+    fn is_unknown_database_error(_e: &(dyn DatabaseError + 'static)) -> bool {
+        // SQLite creates missing databases on connect
+        false
+    }
+} else {
+    fn is_unknown_database_error(e: &(dyn DatabaseError + 'static)) -> bool {
+        // i.e. ER_BAD_DB_ERROR
+        e.try_downcast_ref::<sqlx::mysql::MySqlDatabaseError>().is_some_and(|e| e.number() == 1049)
+    }
+}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_inserts_the_dialect_into_include_paths() {
+        assert_eq!(
+            source_relative_dialect_path(DbDialect::Sqlite, "data/sql/updates/db-auth"),
+            Path::new("data/sql/updates/sqlite/db-auth")
+        );
+        assert_eq!(
+            source_relative_dialect_path(DbDialect::Sqlite, "data/sql/custom/sqlite/db-auth"),
+            Path::new("data/sql/custom/sqlite/db-auth")
+        );
+        assert_eq!(source_relative_dialect_path(DbDialect::MySql, "some/other/dir"), Path::new("some/other/dir"));
+    }
+
+    #[test]
+    fn it_plans_updates_against_applied_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = |name: &str, content: &str, state| {
+            let path = dir.path().join(name);
+            fs::write(&path, content).unwrap();
+            DbFile { path, state }
+        };
+        let entry = |name: &str, hash: String, state| AppliedFileEntry {
+            name: name.to_string(),
+            hash,
+            state,
+            unix_timestamp: DateTime::UNIX_EPOCH,
+        };
+        let available = vec![
+            file("2026_10_17_00_auth.sql", "SELECT 1;", FetcherState::Released),
+            file("2026_10_17_01_auth.sql", "SELECT 2;", FetcherState::Released),
+            file("2026_10_17_02_auth.sql", "SELECT 3;", FetcherState::Released),
+        ];
+        let hashes = available.iter().map(|f| f.hash().unwrap()).collect::<Vec<_>>();
+        let mut applied = BTreeMap::from([
+            // Unchanged, but moved out of the pending updates
+            (
+                "2026_10_17_00_auth.sql".to_string(),
+                entry("2026_10_17_00_auth.sql", hashes[0].clone(), FetcherState::Pending),
+            ),
+            // Released under another name
+            ("rev_1.sql".to_string(), entry("rev_1.sql", hashes[1].clone(), FetcherState::Pending)),
+            // Changed since it was applied
+            (
+                "2026_10_17_02_auth.sql".to_string(),
+                entry("2026_10_17_02_auth.sql", "abc".to_string(), FetcherState::Released),
+            ),
+        ]);
+        let hash_to_filename: BTreeMap<_, _> = applied.iter().map(|e| (e.1.hash.clone(), e.0.clone())).collect();
+        let loader = DatabaseLoader::new(
+            DatabaseType::Login,
+            DatabaseInfo::default_with_info("azcore_auth"),
+            DbUpdates {
+                Redundancy: true,
+                AllowRehash: true,
+                ..Default::default()
+            },
+            vec![],
+        );
+
+        let plan = available
+            .iter()
+            .map(|f| loader.plan_update(&mut applied, &hash_to_filename, &available, f).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            plan,
+            vec![
+                Some(PlannedUpdate::UpdateState {
+                    name: "2026_10_17_00_auth.sql".to_string(),
+                    from: FetcherState::Pending,
+                    to:   FetcherState::Released,
+                }),
+                Some(PlannedUpdate::Rename {
+                    from:  "rev_1.sql".to_string(),
+                    to:    "2026_10_17_01_auth.sql".to_string(),
+                    hash:  hashes[1].clone(),
+                    state: FetcherState::Released,
+                }),
+                Some(PlannedUpdate::Reapply {
+                    name:     "2026_10_17_02_auth.sql".to_string(),
+                    path:     available[2].path.clone(),
+                    old_hash: "abc".to_string(),
+                    hash:     hashes[2].clone(),
+                    state:    FetcherState::Released,
+                }),
+            ]
+        );
+        assert!(applied.is_empty());
+    }
+
+    #[test]
+    fn it_rehashes_only_empty_hashes_if_allowed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("2026_10_17_00_auth.sql");
+        fs::write(&path, "SELECT 1;").unwrap();
+        let available = vec![DbFile {
+            path:  path.clone(),
+            state: FetcherState::Released,
+        }];
+        let hash = available[0].hash().unwrap();
+        let plan = |allow_rehash, applied_hash: &str| {
+            let mut applied = BTreeMap::from([(
+                "2026_10_17_00_auth.sql".to_string(),
+                AppliedFileEntry {
+                    name:           "2026_10_17_00_auth.sql".to_string(),
+                    hash:           applied_hash.to_string(),
+                    state:          FetcherState::Released,
+                    unix_timestamp: DateTime::UNIX_EPOCH,
+                },
+            )]);
+            let hash_to_filename: BTreeMap<_, _> = applied.iter().map(|e| (e.1.hash.clone(), e.0.clone())).collect();
+            let loader = DatabaseLoader::new(
+                DatabaseType::Login,
+                DatabaseInfo::default_with_info("azcore_auth"),
+                DbUpdates {
+                    Redundancy: true,
+                    AllowRehash: allow_rehash,
+                    ..Default::default()
+                },
+                vec![],
+            );
+            loader.plan_update(&mut applied, &hash_to_filename, &available, &available[0]).unwrap()
+        };
+        let rehash = PlannedUpdate::Rehash {
+            name:  "2026_10_17_00_auth.sql".to_string(),
+            path:  path.clone(),
+            hash:  hash.clone(),
+            state: FetcherState::Released,
+        };
+        let reapply = |old_hash: &str| PlannedUpdate::Reapply {
+            name:     "2026_10_17_00_auth.sql".to_string(),
+            path:     path.clone(),
+            old_hash: old_hash.to_string(),
+            hash:     hash.clone(),
+            state:    FetcherState::Released,
+        };
+
+        assert_eq!(plan(true, ""), Some(rehash));
+        assert_eq!(plan(false, ""), Some(reapply("")));
+        // A changed update is applied again, never re-hashed
+        assert_eq!(plan(true, "abc"), Some(reapply("abc")));
+        assert_eq!(plan(true, &hash), None);
+    }
+}
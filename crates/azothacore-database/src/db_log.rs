@@ -0,0 +1,196 @@
+use std::time::{Duration, Instant};
+
+use azothacore_common::log::{DbLogEntry, LogGuard, DB_LOG_WRITER_TARGET};
+use bevy::prelude::*;
+use tokio::sync::mpsc::Receiver;
+use tracing::{error, warn};
+
+use crate::{
+    args,
+    database_env::{LoginDatabase, LoginPreparedStmts},
+    query_processor::DbQueryQueue,
+};
+
+/// Most entries written in a single transaction
+const DB_LOG_BATCH_SIZE: usize = 100;
+
+/// Longest an entry waits for its batch to fill up before it is written anyway
+const DB_LOG_FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The entries of the Db log appenders that have yet to be written to the `logs` table
+#[derive(Resource)]
+struct DbLogWriter {
+    receiver:       Receiver<DbLogEntry>,
+    pending:        Vec<DbLogEntry>,
+    last_flush:     Instant,
+    /// Dropped entries as of the last time they were reported
+    dropped_so_far: u64,
+}
+
+/// Writes the entries of the Db log appenders to the `logs` table of the auth DB through
+/// [DbQueryQueue<LoginDatabase>], in batches of up to [DB_LOG_BATCH_SIZE] or at least every [DB_LOG_FLUSH_INTERVAL].
+///
+/// Nothing is written until both the logging and the queue are set up, the appenders drop their entries once
+/// too many are waiting.
+pub fn db_log_plugin(app: &mut App) {
+    app.add_systems(
+        FixedUpdate,
+        (
+            take_db_log_receiver.run_if(not(resource_exists::<DbLogWriter>).and(resource_exists::<LogGuard>)),
+            write_db_logs.run_if(resource_exists::<DbLogWriter>.and(resource_exists::<DbQueryQueue<LoginDatabase>>)),
+        )
+            .chain(),
+    );
+}
+
+fn take_db_log_receiver(mut commands: Commands, mut log_guard: ResMut<LogGuard>) {
+    let Some(receiver) = log_guard.take_db_log_receiver() else {
+        return;
+    };
+    commands.insert_resource(DbLogWriter {
+        receiver,
+        pending: vec![],
+        last_flush: Instant::now(),
+        dropped_so_far: 0,
+    });
+}
+
+fn write_db_logs(mut writer: ResMut<DbLogWriter>, login_db_queue: Res<DbQueryQueue<LoginDatabase>>, log_guard: Option<Res<LogGuard>>) {
+    let writer = &mut *writer;
+    while let Ok(entry) = writer.receiver.try_recv() {
+        writer.pending.push(entry);
+    }
+    if let Some(dropped) = log_guard.map(|g| g.dropped_db_log_entries()) {
+        if dropped > writer.dropped_so_far {
+            warn!(target:DB_LOG_WRITER_TARGET, dropped=dropped - writer.dropped_so_far, "too many entries waiting to be written to the logs table, dropped some");
+            writer.dropped_so_far = dropped;
+        }
+    }
+    if writer.pending.is_empty() || (writer.pending.len() < DB_LOG_BATCH_SIZE && writer.last_flush.elapsed() < DB_LOG_FLUSH_INTERVAL) {
+        return;
+    }
+    writer.last_flush = Instant::now();
+    let pending = std::mem::take(&mut writer.pending);
+    for batch in pending.chunks(DB_LOG_BATCH_SIZE) {
+        let batch = batch.to_vec();
+        // Failures are logged under the target the Db appenders skip, so that they don't queue more entries to write
+        login_db_queue.transaction(
+            move |txn| {
+                let batch = batch.clone();
+                Box::pin(async move {
+                    for e in batch {
+                        LoginDatabase::ins_log(&mut *txn, args!(e.time, e.realm, e.r#type, e.level, e.string)?).await?;
+                    }
+                    Ok(())
+                })
+            },
+            |res, _| {
+                if let Err(e) = res {
+                    error!(target:DB_LOG_WRITER_TARGET, cause=?e, "error writing entries to the logs table");
+                }
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use azothacore_common::configuration::DatabaseInfo;
+    use sqlx::{query_scalar, Pool};
+    use tokio::{runtime::Runtime, sync::mpsc::channel};
+
+    use super::*;
+    use crate::{
+        dialect::{DbDialect, SQLITE_IN_MEMORY_ADDRESS},
+        query,
+        query_processor::db_query_queue_plugin,
+        DbDriver,
+        DB_DIALECT,
+    };
+
+    fn entry(i: usize) -> DbLogEntry {
+        DbLogEntry {
+            time:   1_700_000_000,
+            realm:  1,
+            r#type: "server::loading".to_string(),
+            level:  3,
+            string: format!("entry {i}"),
+        }
+    }
+
+    fn logged_entries(rt: &Runtime, pool: &Pool<DbDriver>) -> i64 {
+        rt.block_on(query_scalar("SELECT COUNT(*) FROM logs").fetch_one(pool)).unwrap()
+    }
+
+    /// Updates the app until the queue of `app` is empty
+    fn run_until_idle(app: &mut App) {
+        let start = Instant::now();
+        loop {
+            app.update();
+            if app.world().resource::<DbQueryQueue<LoginDatabase>>().queue_size() == 0 {
+                break;
+            }
+            assert!(start.elapsed() < Duration::from_secs(10), "queries did not complete in time");
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "sqlite"), ignore = "needs an empty test_db_log_batches database")]
+    fn it_writes_entries_in_batches() {
+        let rt = Runtime::new().unwrap();
+        let info = DatabaseInfo {
+            Address: if DB_DIALECT == DbDialect::Sqlite {
+                SQLITE_IN_MEMORY_ADDRESS.to_string()
+            } else {
+                "localhost:8893".to_string()
+            },
+            ..DatabaseInfo::default_with_info("test_db_log_batches")
+        };
+        let pool = rt.block_on(Pool::<DbDriver>::connect(&DB_DIALECT.connect_url(&info))).unwrap();
+        rt.block_on(
+            query("CREATE TABLE logs (time BIGINT NOT NULL, realm INT NOT NULL, type VARCHAR(250) NOT NULL, level TINYINT NOT NULL, string TEXT)")
+                .execute(&pool),
+        )
+        .unwrap();
+
+        let (sender, receiver) = channel(DB_LOG_BATCH_SIZE * 2);
+        let mut app = App::new();
+        app.add_plugins(db_query_queue_plugin::<LoginDatabase>)
+            .insert_resource(DbQueryQueue::new(LoginDatabase(pool.clone()), rt.handle().clone()))
+            .insert_resource(DbLogWriter {
+                receiver,
+                pending: vec![],
+                last_flush: Instant::now(),
+                dropped_so_far: 0,
+            })
+            .add_systems(Update, write_db_logs);
+
+        // Neither a full batch nor a flush interval, the entries wait
+        for i in 0..DB_LOG_BATCH_SIZE - 1 {
+            sender.try_send(entry(i)).unwrap();
+        }
+        run_until_idle(&mut app);
+        assert_eq!(app.world().resource::<DbLogWriter>().pending.len(), DB_LOG_BATCH_SIZE - 1);
+        assert_eq!(logged_entries(&rt, &pool), 0);
+
+        // Filling up a batch writes it right away, the rest waits for the next batch
+        for i in DB_LOG_BATCH_SIZE - 1..DB_LOG_BATCH_SIZE + 10 {
+            sender.try_send(entry(i)).unwrap();
+        }
+        run_until_idle(&mut app);
+        assert!(app.world().resource::<DbLogWriter>().pending.is_empty());
+        assert_eq!(logged_entries(&rt, &pool), DB_LOG_BATCH_SIZE as i64 + 10);
+
+        // Less than a batch is written once the flush interval has passed
+        for i in 0..5 {
+            sender.try_send(entry(i)).unwrap();
+        }
+        run_until_idle(&mut app);
+        assert_eq!(logged_entries(&rt, &pool), DB_LOG_BATCH_SIZE as i64 + 10);
+        app.world_mut().resource_mut::<DbLogWriter>().last_flush = Instant::now() - DB_LOG_FLUSH_INTERVAL;
+        run_until_idle(&mut app);
+        assert!(app.world().resource::<DbLogWriter>().pending.is_empty());
+        assert_eq!(logged_entries(&rt, &pool), DB_LOG_BATCH_SIZE as i64 + 15);
+    }
+}
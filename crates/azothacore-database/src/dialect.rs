@@ -0,0 +1,90 @@
+use azothacore_common::configuration::DatabaseInfo;
+
+/// The SQL dialect spoken by the database engine that azothacore-database was compiled against.
+///
+/// Base, update and prepared statement files live in a directory per dialect, i.e.
+/// `data/sql/base/<dialect>/db-auth`, so that each engine can have its own schema definitions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DbDialect {
+    MySql,
+    Sqlite,
+}
+
+/// Address used in the database info to request an in-memory SQLite database.
+pub const SQLITE_IN_MEMORY_ADDRESS: &str = ":memory:";
+
+impl DbDialect {
+    pub const ALL: [DbDialect; 2] = [Self::MySql, Self::Sqlite];
+
+    /// Name of the directory holding the SQL files for this dialect.
+    pub fn dir_name(&self) -> &'static str {
+        match self {
+            Self::MySql => "mysql",
+            Self::Sqlite => "sqlite",
+        }
+    }
+
+    /// Connection URL for the configured database.
+    ///
+    /// For SQLite, `Address` is the directory the database file is created in, with the file named after
+    /// `DatabaseName`. An `Address` of `:memory:` opens a shared in-memory database named after `DatabaseName`
+    /// instead, which lives for as long as a connection to it stays open.
+    pub fn connect_url(&self, info: &DatabaseInfo) -> String {
+        match self {
+            Self::MySql => info.connect_url(),
+            // Only a `file:` URI names the in-memory database, otherwise each connection of a pool gets its own one
+            Self::Sqlite if info.Address == SQLITE_IN_MEMORY_ADDRESS => format!("sqlite:file:{}?mode=memory&cache=shared", info.DatabaseName),
+            Self::Sqlite => format!("sqlite://{}/{}.db?mode=rwc", info.Address.trim_end_matches('/'), info.DatabaseName),
+        }
+    }
+
+    /// Connection URL to the server without selecting a database, used to create a missing one.
+    ///
+    /// SQLite creates its databases on connect, so there is none.
+    pub fn connect_url_without_db(&self, info: &DatabaseInfo) -> Option<String> {
+        match self {
+            Self::MySql => Some(info.connect_url_without_db()),
+            Self::Sqlite => None,
+        }
+    }
+
+    pub fn create_database_statement(&self, database_name: &str) -> Option<String> {
+        match self {
+            Self::MySql => Some(format!(
+                "CREATE DATABASE `{database_name}` DEFAULT CHARACTER SET UTF8MB4 COLLATE utf8mb4_general_ci"
+            )),
+            Self::Sqlite => None,
+        }
+    }
+
+    /// Statement that returns a row per table in the current database.
+    pub fn list_tables_statement(&self) -> &'static str {
+        match self {
+            Self::MySql => "SHOW TABLES",
+            Self::Sqlite => "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%'",
+        }
+    }
+
+    /// Statement that returns a row if the table named by its single bind argument exists.
+    pub fn table_exists_statement(&self) -> &'static str {
+        match self {
+            Self::MySql => "SHOW TABLES LIKE ?",
+            Self::Sqlite => "SELECT name FROM sqlite_master WHERE type = 'table' AND name = ?",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_builds_sqlite_urls() {
+        let mut info = DatabaseInfo::default_with_info("azcore_auth");
+        info.Address = SQLITE_IN_MEMORY_ADDRESS.to_string();
+        assert_eq!(DbDialect::Sqlite.connect_url(&info), "sqlite:file:azcore_auth?mode=memory&cache=shared");
+        info.Address = "/var/lib/azcore/".to_string();
+        assert_eq!(DbDialect::Sqlite.connect_url(&info), "sqlite:///var/lib/azcore/azcore_auth.db?mode=rwc");
+        assert_eq!(DbDialect::Sqlite.connect_url_without_db(&info), None);
+    }
+}
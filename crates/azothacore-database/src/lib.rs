@@ -1,29 +1,48 @@
 pub mod database_env;
 pub mod database_loader;
 pub mod database_loader_utils;
+pub mod db_log;
+pub mod dialect;
+pub mod pending_updates;
+pub mod query_processor;
 
 use std::{ops, path::PathBuf};
 
 use azothacore_common::configuration::{DatabaseInfo, DatabaseType, DbUpdates};
-use sqlx::{pool::PoolConnection, MySqlConnection};
+use sqlx::pool::PoolConnection;
 pub use sqlx::{query, query_as, query_as_with, query_with};
 
-/// DbDriver used in azothacore -> attempt to abstract out database specific code
-/// is an alias to the underlying sqlx driver implementation used
-// TODO: hirogoro@23dec2023: Can consider abstracting these out and toggle it via feature flag?
-// Potentially giving users a way to specify the DB engine that they wanna use.
-pub type DbDriver = sqlx::MySql;
+use crate::dialect::DbDialect;
+
+// The engine is picked with the `sqlite` and `mysql` (default) features, in that order of precedence when both are
+// enabled. This is the same order that hugsqlx picks its backend in.
+cfg_if::cfg_if! {
+if #[cfg(feature = "sqlite")] {
+    /// DbDriver used in azothacore -> attempt to abstract out database specific code
+    /// is an alias to the underlying sqlx driver implementation used
+    pub type DbDriver = sqlx::Sqlite;
+    pub type DbConnection = sqlx::SqliteConnection;
+    pub type DbArguments<'q> = sqlx::sqlite::SqliteArguments<'q>;
+    pub const DB_DIALECT: DbDialect = DbDialect::Sqlite;
+} else if #[cfg(feature = "mysql")] {
+    /// DbDriver used in azothacore -> attempt to abstract out database specific code
+    /// is an alias to the underlying sqlx driver implementation used
+    pub type DbDriver = sqlx::MySql;
+    pub type DbConnection = sqlx::MySqlConnection;
+    pub type DbArguments<'q> = sqlx::mysql::MySqlArguments;
+    pub const DB_DIALECT: DbDialect = DbDialect::MySql;
+} else {
+    compile_error!("azothacore-database requires one of the `mysql` or `sqlite` features");
+}
+}
 
 pub type DbPoolConnection = PoolConnection<DbDriver>;
 
-pub type DbConnection = MySqlConnection;
 /// Db executor used in azothacore -> attempt to abstract out database specific code
 /// is an alias to the underlying sqlx executor implementation used
-// TODO: hirogoro@23dec2023: Can consider abstracting these out and toggle it via feature flag?
-// Potentially giving users a way to specify the DB engine that they wanna use.
-pub trait DbExecutor<'c>: sqlx::MySqlExecutor<'c> {}
+pub trait DbExecutor<'c>: sqlx::Executor<'c, Database = DbDriver> {}
 
-impl<'c, T: sqlx::MySqlExecutor<'c>> DbExecutor<'c> for T {}
+impl<'c, T: sqlx::Executor<'c, Database = DbDriver>> DbExecutor<'c> for T {}
 
 pub trait DbAcquire<'c>: sqlx::Acquire<'c, Database = DbDriver> {}
 
@@ -55,7 +74,7 @@ impl ExtendedDBInfo {
 
     fn base_files_dir(&self) -> PathBuf {
         self.db_type
-            .base_files_directory()
+            .base_files_directory(DB_DIALECT.dir_name())
             .expect("get base files by db type failed, expected to be one of the given DB and not All")
     }
 
@@ -66,16 +85,6 @@ impl ExtendedDBInfo {
     }
 }
 
-cfg_if::cfg_if! {
-if #[cfg(feature = "postgres")] {
-    pub type DbArguments = sqlx::postgres::PgArguments;
-} else if #[cfg(feature = "mysql")] {
-    pub type DbArguments = sqlx::mysql::MySqlArguments;
-} else {
-    pub type DbArguments = sqlx::sqlite::SqliteArguments;
-}
-}
-
 #[macro_export]
 macro_rules! args {
     ($($arg:expr),*) => {
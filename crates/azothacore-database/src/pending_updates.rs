@@ -0,0 +1,313 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use azothacore_common::configuration::DatabaseType;
+use sqlx::types::chrono::NaiveDate;
+use tracing::info;
+
+use crate::database_loader_utils::DatabaseLoaderError;
+
+/// Directory the pending updates of a database are written to, next to its released updates,
+/// i.e. `data/sql/updates/mysql/pending_db-auth`.
+///
+/// Contributors add their updates in here under any unique name, i.e. `rev_1697500000.sql`. They are applied
+/// like released updates, but keep the `PENDING` state until they are released with [release_pending_updates].
+pub fn pending_updates_dir(root: &Path, dialect_dir: &str, db_module_name: &str) -> PathBuf {
+    root.join("data/sql/updates").join(dialect_dir).join(format!("pending_{db_module_name}"))
+}
+
+pub fn released_updates_dir(root: &Path, dialect_dir: &str, db_module_name: &str) -> PathBuf {
+    root.join("data/sql/updates").join(dialect_dir).join(db_module_name)
+}
+
+pub fn archived_updates_dir(root: &Path, dialect_dir: &str, db_module_name: &str) -> PathBuf {
+    root.join("data/sql/archive").join(dialect_dir).join(db_module_name)
+}
+
+/// A released update file name, i.e. `2026_10_17_00_auth.sql`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct ReleaseName {
+    date:  NaiveDate,
+    index: u8,
+}
+
+impl ReleaseName {
+    fn parse(file_name: &str, suffix: &str) -> Option<Self> {
+        let stem = file_name.strip_suffix(".gz").unwrap_or(file_name).strip_suffix(".sql")?;
+        let (date, index) = stem.strip_suffix(suffix)?.strip_suffix('_')?.rsplit_once('_')?;
+        if index.len() != 2 {
+            return None;
+        }
+        Some(Self {
+            date:  NaiveDate::parse_from_str(date, "%Y_%m_%d").ok()?,
+            index: index.parse().ok()?,
+        })
+    }
+
+    fn file_name(&self, suffix: &str) -> String {
+        format!("{}_{:02}_{suffix}.sql", self.date.format("%Y_%m_%d"), self.index)
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct PendingUpdatesRelease {
+    /// Pending updates and the released update they were moved to, in order
+    pub released: Vec<(PathBuf, PathBuf)>,
+    /// Released updates and the archived update they were moved to
+    pub archived: Vec<(PathBuf, PathBuf)>,
+}
+
+impl PendingUpdatesRelease {
+    /// Moves the pending updates to their released names, then the released updates to the archive
+    pub fn apply(&self) -> Result<(), DatabaseLoaderError> {
+        for (from, to) in &self.released {
+            info!(target:"sql.updates", "Releasing pending update \"{}\" as \"{}\"", from.display(), to.display());
+            move_file(from, to)?;
+        }
+        for (from, to) in &self.archived {
+            info!(target:"sql.updates", "Archiving update \"{}\" to \"{}\"", from.display(), to.display());
+            move_file(from, to)?;
+        }
+        Ok(())
+    }
+}
+
+fn sql_files(dir: &Path) -> Result<Vec<(String, PathBuf)>, DatabaseLoaderError> {
+    if !dir.is_dir() {
+        return Ok(vec![]);
+    }
+    let mut files = vec![];
+    for e in fs::read_dir(dir).map_err(|e| DatabaseLoaderError::OpenApplyFile {
+        file:  dir.display().to_string(),
+        inner: e,
+    })? {
+        let e = e.map_err(|e| DatabaseLoaderError::OpenApplyFile {
+            file:  dir.display().to_string(),
+            inner: e,
+        })?;
+        let Some(name) = e.file_name().to_str().map(|s| s.to_string()) else {
+            continue;
+        };
+        if name.ends_with(".sql") || name.ends_with(".sql.gz") {
+            files.push((name, e.path()));
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn move_file(from: &Path, to: &Path) -> Result<(), DatabaseLoaderError> {
+    if let Some(dir) = to.parent() {
+        fs::create_dir_all(dir).map_err(|e| DatabaseLoaderError::OpenApplyFile {
+            file:  dir.display().to_string(),
+            inner: e,
+        })?;
+    }
+    fs::rename(from, to).map_err(|e| DatabaseLoaderError::OpenApplyFile {
+        file:  from.display().to_string(),
+        inner: e,
+    })
+}
+
+/// Releases the pending updates of a database under `root`, renaming them in order of their file names into
+/// dated release updates, i.e. `2026_10_17_03_auth.sql`, following the latest released update.
+///
+/// The contents of the files are left untouched, so databases that have already applied a pending update
+/// only have its entry in the `updates` table renamed on their next update, as the hash stays the same.
+///
+/// Released updates dated before `archive_before` are moved to the archive directory of the database.
+///
+/// Nothing is moved if the released updates have names that are duplicated or don't follow the release
+/// naming, if there are released updates dated after `date`, or if a pending update has the same name as
+/// any other update.
+pub fn release_pending_updates(
+    root: &Path,
+    dialect_dir: &str,
+    db_type: DatabaseType,
+    date: NaiveDate,
+    archive_before: Option<NaiveDate>,
+) -> Result<PendingUpdatesRelease, DatabaseLoaderError> {
+    let release = plan_pending_updates_release(root, dialect_dir, db_type, date, archive_before)?;
+    release.apply()?;
+    Ok(release)
+}
+
+/// The checks of [release_pending_updates], returning the files that releasing the pending updates would move
+/// without moving them. Call [PendingUpdatesRelease::apply] to move them.
+pub fn plan_pending_updates_release(
+    root: &Path,
+    dialect_dir: &str,
+    db_type: DatabaseType,
+    date: NaiveDate,
+    archive_before: Option<NaiveDate>,
+) -> Result<PendingUpdatesRelease, DatabaseLoaderError> {
+    let db_module_name = db_type
+        .db_module_name()
+        .expect("get db_module_name by db type failed, db type is expected to be one of the given DB and not All");
+    let suffix = db_module_name.trim_start_matches("db-");
+    let pending_dir = pending_updates_dir(root, dialect_dir, db_module_name);
+    let released_dir = released_updates_dir(root, dialect_dir, db_module_name);
+    let archived_dir = archived_updates_dir(root, dialect_dir, db_module_name);
+    let err = |msg: String| DatabaseLoaderError::Generic { msg };
+
+    let released_files = sql_files(&released_dir)?;
+    let mut released = BTreeMap::new();
+    for (name, path) in &released_files {
+        let Some(release) = ReleaseName::parse(name, suffix) else {
+            return Err(err(format!(
+                "released update \"{}\" does not follow the naming YYYY_MM_DD_NN_{suffix}.sql",
+                path.display()
+            )));
+        };
+        if let Some(existing) = released.insert(release, path.clone()) {
+            return Err(err(format!(
+                "released updates \"{}\" and \"{}\" have the same name",
+                existing.display(),
+                path.display()
+            )));
+        }
+    }
+    if let Some((last, path)) = released.last_key_value() {
+        if last.date > date {
+            return Err(err(format!(
+                "released update \"{}\" is dated after {date}, pending updates released on {date} would be out of order",
+                path.display()
+            )));
+        }
+    }
+
+    let archived = sql_files(&archived_dir)?;
+    let pending = sql_files(&pending_dir)?;
+    for (name, path) in pending.iter() {
+        let stem = name.trim_end_matches(".gz");
+        let clash = archived
+            .iter()
+            .chain(released_files.iter())
+            .chain(pending.iter().filter(|(_, p)| p != path))
+            .find(|(n, _)| n.trim_end_matches(".gz") == stem);
+        if let Some((_, other)) = clash {
+            return Err(err(format!(
+                "pending update \"{}\" has the same name as \"{}\"",
+                path.display(),
+                other.display()
+            )));
+        }
+    }
+
+    let mut next = match released.last_key_value() {
+        Some((last, _)) if last.date == date => ReleaseName { date, index: last.index + 1 },
+        _ => ReleaseName { date, index: 0 },
+    };
+    if usize::from(next.index) + pending.len() > 100 {
+        return Err(err(format!(
+            "releasing {} pending updates on {date} would need more than 100 updates on the same day",
+            pending.len()
+        )));
+    }
+
+    let mut res = PendingUpdatesRelease::default();
+    if let Some(archive_before) = archive_before {
+        for (_, path) in released.iter().filter(|(r, _)| r.date < archive_before) {
+            let to = archived_dir.join(path.file_name().unwrap());
+            if to.exists() {
+                return Err(err(format!(
+                    "released update \"{}\" was already archived at \"{}\"",
+                    path.display(),
+                    to.display()
+                )));
+            }
+            res.archived.push((path.clone(), to));
+        }
+    }
+
+    for (name, from) in pending {
+        let mut file_name = next.file_name(suffix);
+        if name.ends_with(".gz") {
+            file_name.push_str(".gz");
+        }
+        res.released.push((from, released_dir.join(file_name)));
+        next.index += 1;
+    }
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn touch(dir: &Path, name: &str, content: &str) -> PathBuf {
+        fs::create_dir_all(dir).unwrap();
+        let p = dir.join(name);
+        fs::write(&p, content).unwrap();
+        p
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn it_releases_pending_updates_in_order() {
+        let root = tempfile::tempdir().unwrap();
+        let released_dir = released_updates_dir(root.path(), "mysql", "db-auth");
+        let pending_dir = pending_updates_dir(root.path(), "mysql", "db-auth");
+        let old = touch(&released_dir, "2026_10_01_00_auth.sql", "SELECT 1;");
+        let last = touch(&released_dir, "2026_10_17_00_auth.sql", "SELECT 2;");
+        let b = touch(&pending_dir, "rev_2.sql", "SELECT 4;");
+        let a = touch(&pending_dir, "rev_1.sql", "SELECT 3;");
+
+        let res = release_pending_updates(root.path(), "mysql", DatabaseType::Login, date("2026-10-17"), Some(date("2026-10-17"))).unwrap();
+        assert_eq!(
+            res,
+            PendingUpdatesRelease {
+                released: vec![
+                    (a, released_dir.join("2026_10_17_01_auth.sql")),
+                    (b, released_dir.join("2026_10_17_02_auth.sql")),
+                ],
+                archived: vec![(old, archived_updates_dir(root.path(), "mysql", "db-auth").join("2026_10_01_00_auth.sql"))],
+            }
+        );
+        assert_eq!(fs::read_to_string(released_dir.join("2026_10_17_01_auth.sql")).unwrap(), "SELECT 3;");
+        assert!(last.exists());
+    }
+
+    #[test]
+    fn it_refuses_to_release_on_conflicts() {
+        let root = tempfile::tempdir().unwrap();
+        let released_dir = released_updates_dir(root.path(), "mysql", "db-world");
+        let pending_dir = pending_updates_dir(root.path(), "mysql", "db-world");
+        touch(&released_dir, "2026_10_17_00_world.sql", "SELECT 1;");
+        let pending = touch(&pending_dir, "2026_10_17_00_world.sql", "SELECT 2;");
+
+        // Same name as a released update
+        assert!(release_pending_updates(root.path(), "mysql", DatabaseType::World, date("2026-10-17"), None).is_err());
+        fs::remove_file(&pending).unwrap();
+        touch(&pending_dir, "rev_1.sql", "SELECT 2;");
+
+        // Released updates dated after the release would sort after it
+        assert!(release_pending_updates(root.path(), "mysql", DatabaseType::World, date("2026-10-16"), None).is_err());
+
+        // Duplicated release names
+        touch(&released_dir, "2026_10_17_00_world.sql.gz", "");
+        assert!(release_pending_updates(root.path(), "mysql", DatabaseType::World, date("2026-10-17"), None).is_err());
+        assert!(pending_dir.join("rev_1.sql").exists());
+    }
+
+    #[test]
+    fn it_plans_releases_without_moving_files() {
+        let root = tempfile::tempdir().unwrap();
+        let released_dir = released_updates_dir(root.path(), "sqlite", "db-characters");
+        let pending = touch(&pending_updates_dir(root.path(), "sqlite", "db-characters"), "rev_1.sql.gz", "");
+
+        let release = plan_pending_updates_release(root.path(), "sqlite", DatabaseType::Character, date("2026-10-17"), None).unwrap();
+        let to = released_dir.join("2026_10_17_00_characters.sql.gz");
+        assert_eq!(release.released, vec![(pending.clone(), to.clone())]);
+        assert!(pending.exists() && !to.exists());
+
+        release.apply().unwrap();
+        assert!(!pending.exists() && to.exists());
+    }
+}
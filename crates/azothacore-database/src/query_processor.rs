@@ -0,0 +1,495 @@
+use std::{
+    future::Future,
+    ops::Deref,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use bevy::{
+    diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic},
+    prelude::*,
+};
+use futures_core::future::BoxFuture;
+use sqlx::Pool;
+use tokio::{
+    runtime::Handle,
+    sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+    time::Instant,
+};
+use tracing::{error, warn};
+
+use crate::{
+    database_env::{CharacterDatabase, HotfixDatabase, LoginDatabase, WorldDatabase},
+    DbConnection,
+    DbDriver,
+};
+
+/// A database resource that can have queries queued against it, one for each of the databases
+/// in [crate::database_env].
+pub trait QueueableDatabase: Resource + Clone + Deref<Target = Pool<DbDriver>> {
+    /// Name used in the metrics reported for this database, i.e. `db_queue_login`
+    const METRIC_NAME: &'static str;
+}
+
+impl QueueableDatabase for LoginDatabase {
+    const METRIC_NAME: &'static str = "login";
+}
+
+impl QueueableDatabase for CharacterDatabase {
+    const METRIC_NAME: &'static str = "character";
+}
+
+impl QueueableDatabase for WorldDatabase {
+    const METRIC_NAME: &'static str = "world";
+}
+
+impl QueueableDatabase for HotfixDatabase {
+    const METRIC_NAME: &'static str = "hotfix";
+}
+
+/// How long a transaction that keeps deadlocking is retried for,
+/// i.e. DEADLOCK_MAX_RETRY_TIME_MS in TC / AC
+const DEADLOCK_MAX_RETRY_TIME: Duration = Duration::from_secs(60);
+
+type DbCallback = Box<dyn FnOnce(&mut World) + Send>;
+
+#[derive(Default)]
+struct DbQueueStats {
+    /// Queries that were submitted but have not completed yet
+    queued:             AtomicUsize,
+    /// Sum of the latencies of the queries completed since the metrics were last reported
+    latency_sum_micros: AtomicU64,
+    /// Number of the queries completed since the metrics were last reported
+    completed:          AtomicU64,
+    /// Number of times a transaction was retried due to a deadlock
+    deadlock_retries:   AtomicU64,
+}
+
+/// The async query processor for the database `D`, i.e. the async half of DatabaseWorkerPool
+/// and QueryCallbackProcessor in TC / AC.
+///
+/// Systems submit queries or transactions to run on the tokio runtime instead of blocking on them.
+/// Once a query completes, its callback is run against the [World] in a later frame by
+/// [DbQueryQueueSet], from which it can update resources or send events.
+#[derive(Resource)]
+pub struct DbQueryQueue<D> {
+    db:     D,
+    handle: Handle,
+    stats:  Arc<DbQueueStats>,
+    tx:     UnboundedSender<DbCallback>,
+    rx:     UnboundedReceiver<DbCallback>,
+}
+
+impl<D: QueueableDatabase> DbQueryQueue<D> {
+    pub fn new(db: D, handle: Handle) -> Self {
+        let (tx, rx) = unbounded_channel();
+        Self {
+            db,
+            handle,
+            stats: Arc::new(DbQueueStats::default()),
+            tx,
+            rx,
+        }
+    }
+
+    /// Path of the diagnostic holding the number of queries in the queue, i.e. `db_queue_login`
+    pub fn queue_size_path() -> DiagnosticPath {
+        DiagnosticPath::new(format!("db_queue_{}", D::METRIC_NAME))
+    }
+
+    /// Path of the diagnostic holding the average latency of completed queries in milliseconds,
+    /// from their submission up to their completion
+    pub fn latency_path() -> DiagnosticPath {
+        DiagnosticPath::new(format!("db_latency_{}", D::METRIC_NAME))
+    }
+
+    /// Number of queries that were submitted but have not completed yet, QueueSize in TC / AC
+    pub fn queue_size(&self) -> usize {
+        self.stats.queued.load(Ordering::Relaxed)
+    }
+
+    /// Number of times a transaction had to be retried because it deadlocked
+    pub fn deadlock_retries(&self) -> u64 {
+        self.stats.deadlock_retries.load(Ordering::Relaxed)
+    }
+
+    /// Runs `f` against the database, passing its output to `callback` in a later frame.
+    /// AsyncQuery in TC / AC
+    pub fn query<T, F, Fut, C>(&self, f: F, callback: C)
+    where
+        T: Send + 'static,
+        F: FnOnce(D) -> Fut + Send + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+        C: FnOnce(T, &mut World) + Send + 'static,
+    {
+        let fut = f(self.db.clone());
+        self.spawn(fut, callback);
+    }
+
+    /// Runs `f` against the database without waiting for its result, which is expected to handle
+    /// its own errors. Execute in TC / AC
+    pub fn execute<F, Fut>(&self, f: F)
+    where
+        F: FnOnce(D) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.query(f, |_, _| {});
+    }
+
+    /// Runs `f` in a transaction, passing the result to `callback` in a later frame once committed.
+    ///
+    /// If the transaction deadlocks, it is rolled back and `f` is run again in a new transaction for up to
+    /// a minute. AsyncCommitTransaction in TC / AC
+    pub fn transaction<T, F, C>(&self, f: F, callback: C)
+    where
+        T: Send + 'static,
+        F: for<'c> Fn(&'c mut DbConnection) -> BoxFuture<'c, Result<T, sqlx::Error>> + Send + Sync + 'static,
+        C: FnOnce(Result<T, sqlx::Error>, &mut World) + Send + 'static,
+    {
+        let db = self.db.clone();
+        let stats = self.stats.clone();
+        let fut = async move {
+            let start = Instant::now();
+            loop {
+                let res = async {
+                    let mut txn = db.begin().await?;
+                    let out = f(&mut *txn).await?;
+                    txn.commit().await?;
+                    Ok::<_, sqlx::Error>(out)
+                }
+                .await;
+                match res {
+                    Err(e) if is_deadlock_error(&e) && start.elapsed() < DEADLOCK_MAX_RETRY_TIME => {
+                        stats.deadlock_retries.fetch_add(1, Ordering::Relaxed);
+                        warn!(target:"sql.sql", cause=?e, db=D::METRIC_NAME, "deadlock found when trying to commit transaction, retrying");
+                    },
+                    res => break res,
+                }
+            }
+        };
+        self.spawn(fut, callback);
+    }
+
+    /// Like [Self::transaction], but only logs the error if the transaction fails.
+    /// CommitTransaction in TC / AC
+    pub fn commit_transaction<F>(&self, f: F)
+    where
+        F: for<'c> Fn(&'c mut DbConnection) -> BoxFuture<'c, Result<(), sqlx::Error>> + Send + Sync + 'static,
+    {
+        self.transaction(f, |res, _| {
+            if let Err(e) = res {
+                error!(target:"sql.sql", cause=?e, db=D::METRIC_NAME, "transaction failed");
+            }
+        });
+    }
+
+    fn spawn<T, Fut, C>(&self, fut: Fut, callback: C)
+    where
+        T: Send + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+        C: FnOnce(T, &mut World) + Send + 'static,
+    {
+        let stats = self.stats.clone();
+        let tx = self.tx.clone();
+        let submitted = Instant::now();
+        stats.queued.fetch_add(1, Ordering::Relaxed);
+        self.handle.spawn(async move {
+            let out = fut.await;
+            let latency = submitted.elapsed();
+            stats.queued.fetch_sub(1, Ordering::Relaxed);
+            stats
+                .latency_sum_micros
+                .fetch_add(latency.as_micros().try_into().unwrap_or(u64::MAX), Ordering::Relaxed);
+            stats.completed.fetch_add(1, Ordering::Relaxed);
+            // The receiver only goes away with the app, by then nobody is around to run the callback anyway
+            _ = tx.send(Box::new(move |world: &mut World| callback(out, world)));
+        });
+    }
+}
+
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DbQueryQueueSet;
+
+/// Runs the callbacks of completed queries submitted to [DbQueryQueue] of `D` and reports its metrics.
+///
+/// The queue itself is expected to be inserted alongside the database resource when it is started.
+pub fn db_query_queue_plugin<D: QueueableDatabase>(app: &mut App) {
+    app.register_diagnostic(Diagnostic::new(DbQueryQueue::<D>::queue_size_path()))
+        .register_diagnostic(Diagnostic::new(DbQueryQueue::<D>::latency_path()).with_suffix("ms"))
+        .add_systems(
+            PreUpdate,
+            process_db_query_callbacks::<D>
+                .run_if(resource_exists::<DbQueryQueue<D>>)
+                .in_set(DbQueryQueueSet),
+        );
+}
+
+fn process_db_query_callbacks<D: QueueableDatabase>(mut commands: Commands, mut queue: ResMut<DbQueryQueue<D>>, mut diagnostics: Diagnostics) {
+    while let Ok(cb) = queue.rx.try_recv() {
+        commands.queue(cb);
+    }
+
+    let queue_size = queue.queue_size();
+    diagnostics.add_measurement(&DbQueryQueue::<D>::queue_size_path(), || queue_size as f64);
+    let completed = queue.stats.completed.swap(0, Ordering::Relaxed);
+    let latency_sum_micros = queue.stats.latency_sum_micros.swap(0, Ordering::Relaxed);
+    if completed > 0 {
+        diagnostics.add_measurement(&DbQueryQueue::<D>::latency_path(), || latency_sum_micros as f64 / completed as f64 / 1000.0);
+    }
+}
+
+cfg_if::cfg_if! {
+if #[cfg(feature = "sqlite")] {
+    fn is_deadlock_error(e: &sqlx::Error) -> bool {
+        // i.e. SQLITE_BUSY and SQLITE_LOCKED, including their extended result codes
+        matches!(
+            e,
+            sqlx::Error::Database(e) if e.code().and_then(|c| c.parse::<i32>().ok()).is_some_and(|c| matches!(c & 0xff, 5 | 6))
+        )
+    }
+} else {
+    fn is_deadlock_error(e: &sqlx::Error) -> bool {
+        // i.e. ER_LOCK_DEADLOCK and ER_LOCK_WAIT_TIMEOUT
+        matches!(
+            e,
+            sqlx::Error::Database(e) if e.try_downcast_ref::<sqlx::mysql::MySqlDatabaseError>().is_some_and(|e| e.number() == 1213 || e.number() == 1205)
+        )
+    }
+}
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use azothacore_common::configuration::DatabaseInfo;
+    use bevy::diagnostic::DiagnosticsStore;
+    use sqlx::query_scalar;
+    use tokio::{runtime::Runtime, sync::oneshot};
+
+    use super::*;
+    use crate::{
+        dialect::{DbDialect, SQLITE_IN_MEMORY_ADDRESS},
+        query,
+        DB_DIALECT,
+    };
+
+    #[derive(Resource, Default)]
+    struct CallbackResults(Vec<i64>);
+
+    fn test_app(rt: &Runtime, database_name: &str) -> App {
+        let info = DatabaseInfo {
+            Address: if DB_DIALECT == DbDialect::Sqlite {
+                SQLITE_IN_MEMORY_ADDRESS.to_string()
+            } else {
+                "localhost:8893".to_string()
+            },
+            ..DatabaseInfo::default_with_info(database_name)
+        };
+        // The queue does not care which database it talks to, so no schema is needed
+        let connect_url = DB_DIALECT.connect_url_without_db(&info).unwrap_or_else(|| DB_DIALECT.connect_url(&info));
+        let pool = rt.block_on(Pool::<DbDriver>::connect(&connect_url)).unwrap();
+
+        let mut app = App::new();
+        app.add_plugins(db_query_queue_plugin::<LoginDatabase>)
+            .init_resource::<CallbackResults>()
+            .insert_resource(DbQueryQueue::new(LoginDatabase(pool), rt.handle().clone()));
+        app
+    }
+
+    /// Updates the app until the queue of `app` is empty and all callbacks have been run
+    fn run_until_idle(app: &mut App) {
+        let start = std::time::Instant::now();
+        loop {
+            app.update();
+            if app.world().resource::<DbQueryQueue<LoginDatabase>>().queue_size() == 0 {
+                break;
+            }
+            assert!(start.elapsed() < Duration::from_secs(10), "queries did not complete in time");
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        // Callbacks of queries that completed during the last update are only picked up in the next one
+        app.update();
+    }
+
+    #[test]
+    fn it_runs_callbacks_in_completion_order() {
+        let rt = Runtime::new().unwrap();
+        let mut app = test_app(&rt, "test_query_queue_order");
+
+        let mut gates = vec![];
+        {
+            let queue = app.world().resource::<DbQueryQueue<LoginDatabase>>();
+            for i in 0..5i64 {
+                let (tx, rx) = oneshot::channel::<()>();
+                gates.push(tx);
+                queue.query(
+                    move |db| async move {
+                        rx.await.unwrap();
+                        query_scalar::<_, i64>("SELECT ?").bind(i).fetch_one(&*db).await.unwrap()
+                    },
+                    |out, world| world.resource_mut::<CallbackResults>().0.push(out),
+                );
+            }
+            assert_eq!(queue.queue_size(), 5);
+        }
+
+        // Complete the queries in the reverse order of their submission, waiting for each one in turn
+        for (completed, gate) in gates.into_iter().enumerate().rev() {
+            gate.send(()).unwrap();
+            let start = std::time::Instant::now();
+            while app.world().resource::<DbQueryQueue<LoginDatabase>>().queue_size() > completed {
+                assert!(start.elapsed() < Duration::from_secs(10), "query did not complete in time");
+                std::thread::sleep(Duration::from_millis(1));
+            }
+        }
+        assert!(app.world().resource::<CallbackResults>().0.is_empty(), "callbacks only run in the app update");
+
+        app.update();
+        assert_eq!(app.world().resource::<CallbackResults>().0, vec![4, 3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn it_reports_queue_diagnostics() {
+        let rt = Runtime::new().unwrap();
+        let mut app = test_app(&rt, "test_query_queue_diagnostics");
+
+        let (tx, rx) = oneshot::channel::<()>();
+        let queue = app.world().resource::<DbQueryQueue<LoginDatabase>>();
+        queue.execute(|db| async move {
+            rx.await.unwrap();
+            query("SELECT 1").execute(&*db).await.unwrap();
+        });
+        app.update();
+        {
+            let store = app.world().resource::<DiagnosticsStore>();
+            let queue_size = store.get(&DbQueryQueue::<LoginDatabase>::queue_size_path()).unwrap();
+            assert_eq!(queue_size.value(), Some(1.0));
+            let latency = store.get(&DbQueryQueue::<LoginDatabase>::latency_path()).unwrap();
+            assert!(latency.measurement().is_none(), "no query completed yet");
+        }
+
+        tx.send(()).unwrap();
+        run_until_idle(&mut app);
+        let store = app.world().resource::<DiagnosticsStore>();
+        let queue_size = store.get(&DbQueryQueue::<LoginDatabase>::queue_size_path()).unwrap();
+        assert_eq!(queue_size.value(), Some(0.0));
+        let latency = store.get(&DbQueryQueue::<LoginDatabase>::latency_path()).unwrap();
+        assert!(latency.value().is_some_and(|v| v > 0.0));
+    }
+
+    /// Error as the database reports it when it gives up on a transaction due to a deadlock
+    #[derive(Debug)]
+    struct DeadlockError;
+
+    impl std::fmt::Display for DeadlockError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "deadlock")
+        }
+    }
+
+    impl std::error::Error for DeadlockError {}
+
+    impl sqlx::error::DatabaseError for DeadlockError {
+        fn message(&self) -> &str {
+            "deadlock"
+        }
+
+        fn code(&self) -> Option<std::borrow::Cow<'_, str>> {
+            match DB_DIALECT {
+                // SQLITE_BUSY
+                DbDialect::Sqlite => Some("5".into()),
+                // Only MySqlDatabaseError is ever recognised as a deadlock, which cannot be built by hand
+                DbDialect::MySql => None,
+            }
+        }
+
+        fn as_error(&self) -> &(dyn std::error::Error + Send + Sync + 'static) {
+            self
+        }
+
+        fn as_error_mut(&mut self) -> &mut (dyn std::error::Error + Send + Sync + 'static) {
+            self
+        }
+
+        fn into_error(self: Box<Self>) -> Box<dyn std::error::Error + Send + Sync + 'static> {
+            self
+        }
+
+        fn kind(&self) -> sqlx::error::ErrorKind {
+            sqlx::error::ErrorKind::Other
+        }
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "sqlite"), ignore = "MySQL deadlocks cannot be simulated")]
+    fn it_retries_deadlocked_transactions() {
+        let rt = Runtime::new().unwrap();
+        let mut app = test_app(&rt, "test_query_queue_deadlock");
+        let db = app.world().resource::<DbQueryQueue<LoginDatabase>>().db.clone();
+        rt.block_on(query("CREATE TABLE query_queue_attempts (attempt INT NOT NULL)").execute(&*db))
+            .unwrap();
+
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let result = Arc::new(Mutex::new(None));
+        {
+            let attempts = attempts.clone();
+            let result = result.clone();
+            let queue = app.world().resource::<DbQueryQueue<LoginDatabase>>();
+            queue.transaction(
+                move |conn| {
+                    let attempt = attempts.fetch_add(1, Ordering::Relaxed) + 1;
+                    Box::pin(async move {
+                        query("INSERT INTO query_queue_attempts (attempt) VALUES (?)")
+                            .bind(attempt as i64)
+                            .execute(&mut *conn)
+                            .await?;
+                        if attempt < 3 {
+                            return Err(sqlx::Error::Database(Box::new(DeadlockError)));
+                        }
+                        Ok(attempt)
+                    })
+                },
+                move |res, _| *result.lock().unwrap() = Some(res.map_err(|e| e.to_string())),
+            );
+        }
+        run_until_idle(&mut app);
+
+        assert_eq!(*result.lock().unwrap(), Some(Ok(3)));
+        assert_eq!(app.world().resource::<DbQueryQueue<LoginDatabase>>().deadlock_retries(), 2);
+        // The deadlocked attempts were rolled back
+        let committed = rt
+            .block_on(query_scalar::<_, i64>("SELECT attempt FROM query_queue_attempts").fetch_all(&*db))
+            .unwrap();
+        assert_eq!(committed, vec![3]);
+    }
+
+    #[test]
+    fn it_does_not_retry_failed_transactions() {
+        let rt = Runtime::new().unwrap();
+        let mut app = test_app(&rt, "test_query_queue_failure");
+
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let result = Arc::new(Mutex::new(None));
+        {
+            let attempts = attempts.clone();
+            let result = result.clone();
+            let queue = app.world().resource::<DbQueryQueue<LoginDatabase>>();
+            queue.transaction(
+                move |conn| {
+                    attempts.fetch_add(1, Ordering::Relaxed);
+                    Box::pin(async move { query_scalar::<_, i64>("SELECT * FROM query_queue_missing_table").fetch_one(&mut *conn).await })
+                },
+                move |res, _| *result.lock().unwrap() = Some(res.is_err()),
+            );
+        }
+        run_until_idle(&mut app);
+
+        assert_eq!(*result.lock().unwrap(), Some(true));
+        assert_eq!(attempts.load(Ordering::Relaxed), 1);
+        assert_eq!(app.world().resource::<DbQueryQueue<LoginDatabase>>().deadlock_retries(), 0);
+    }
+}
@@ -8,6 +8,7 @@ pub mod globals;
 pub mod grid;
 pub mod groups;
 pub mod guilds;
+pub mod handlers;
 pub mod loot;
 pub mod map;
 pub mod scripting;
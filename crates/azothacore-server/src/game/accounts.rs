@@ -20,6 +20,14 @@ pub enum AccountOpError {
     DbInternalError(String),
     #[error("AOR_ACCOUNT_BAD_LINK")]
     AccountBadLink,
+    #[error("AOR_AUTHENTICATOR_ALREADY_SET")]
+    AuthenticatorAlreadySet,
+    #[error("AOR_AUTHENTICATOR_NOT_SET")]
+    AuthenticatorNotSet,
+    #[error("AOR_INVALID_TOKEN")]
+    InvalidToken,
+    #[error("AOR_SECRET_UNAVAILABLE")]
+    SecretUnavailable,
 }
 
 impl From<sqlx::Error> for AccountOpError {
@@ -1,17 +1,22 @@
 use std::collections::{BTreeMap, BTreeSet};
 
-use azothacore_common::{hex_str, AccountTypes};
+use azothacore_common::{
+    crypto::srp6::{GruntSrp6, Salt, Srp6},
+    hex_str,
+    AccountTypes,
+};
 use azothacore_database::{
     args,
     database_env::{CharacterDatabase, CharacterPreparedStmts, LoginDatabase, LoginPreparedStmts},
     DbAcquire,
+    DbConnection,
     DbExecutor,
 };
 use bevy::prelude::Commands;
 use sha2::{Digest, Sha256};
 use sqlx::{query_as, Connection};
 use tokio::{runtime::Runtime, time::Instant};
-use tracing::{debug, error, info, trace};
+use tracing::{debug, error, info, trace, warn};
 
 use super::{
     rbac::{RawRbacPermId, RbacPermission},
@@ -30,6 +35,15 @@ pub struct AccountMgr {
     default_permissions: BTreeMap<AccountTypes, BTreeSet<RawRbacPermId>>,
 }
 
+/// The stored credentials of a game account
+#[derive(sqlx::FromRow)]
+struct AccountLogonRow {
+    salt:          Option<Vec<u8>>,
+    verifier:      Option<Vec<u8>>,
+    /// Legacy unsalted hash, only set for accounts that have yet to be upgraded to SRP6
+    sha_pass_hash: String,
+}
+
 #[derive(sqlx::FromRow)]
 struct RbacPermRow {
     id:   u32,
@@ -60,7 +74,20 @@ impl AccountMgr {
         bnet_account_id_index: Option<(u32, u8)>,
     ) -> AccountOpResult<()> {
         let mut login_db = login_db.acquire().await?;
+        Self::create_account_on_connection(&mut login_db, username, password, email, bnet_account_id_index).await
+    }
 
+    /// [AccountMgr::create_account] on an already acquired connection.
+    ///
+    /// Takes the connection itself rather than a [DbAcquire], as the future of a generic acquire nested in another
+    /// one isn't provably [Send], i.e. the admin REST service couldn't await account creations from Battle.net.
+    pub async fn create_account_on_connection(
+        login_db: &mut DbConnection,
+        username: &str,
+        password: &str,
+        email: &str,
+        bnet_account_id_index: Option<(u32, u8)>,
+    ) -> AccountOpResult<()> {
         if username.len() > MAX_ACCOUNT_STR {
             // username's too long
             return Err(AccountOpError::NameTooLong);
@@ -86,12 +113,12 @@ impl AccountMgr {
         }
 
         let (bnet_account_id, bnet_index) = bnet_account_id_index.map_or((None, None), |i| (Some(i.0), Some(i.1)));
-        let sha_hash = Self::calculate_sha_pass_hash(&username, &password);
+        let (salt, verifier) = GruntSrp6::make_registration_data(&username, &password);
 
         login_db
             .transaction(|txn| {
                 Box::pin(async move {
-                    LoginDatabase::ins_account(&mut **txn, args!(username, sha_hash, &email, &email, bnet_account_id, bnet_index)?).await?;
+                    LoginDatabase::ins_account(&mut **txn, args!(username, &salt[..], verifier, &email, &email, bnet_account_id, bnet_index)?).await?;
                     LoginDatabase::ins_realm_characters_init(&mut **txn, args!()?).await?;
                     Ok(())
                 })
@@ -192,11 +219,8 @@ impl AccountMgr {
         let new_username = new_username.to_ascii_uppercase();
         let new_password = new_password.to_ascii_uppercase();
 
-        LoginDatabase::upd_username(
-            &mut *login_db,
-            args!(&new_username, Self::calculate_sha_pass_hash(&new_username, &new_password), account_id)?,
-        )
-        .await?;
+        let (salt, verifier) = GruntSrp6::make_registration_data(&new_username, &new_password);
+        LoginDatabase::upd_username(&mut *login_db, args!(&new_username, &salt[..], verifier, account_id)?).await?;
 
         Ok(())
     }
@@ -228,12 +252,9 @@ impl AccountMgr {
         }
         let username = username.to_ascii_uppercase();
         let new_password = new_password.to_ascii_uppercase();
-        let mut txn = login_db.begin().await?;
 
-        let new_sha_hash = Self::calculate_sha_pass_hash(&username, &new_password);
-        LoginDatabase::upd_password(&mut *txn, args!(&new_sha_hash, account_id)?).await?;
-        LoginDatabase::upd_vs(&mut *txn, args!("", "", username)?).await?;
-        txn.commit().await?;
+        let (salt, verifier) = GruntSrp6::make_registration_data(&username, &new_password);
+        LoginDatabase::upd_password(&mut *login_db, args!(&salt[..], verifier, account_id)?).await?;
         Ok(())
     }
 
@@ -310,11 +331,48 @@ impl AccountMgr {
         let username = username.to_ascii_uppercase();
         let password = password.to_ascii_uppercase();
 
-        let pass_hash = Self::calculate_sha_pass_hash(&username, &password);
-        let Ok(args) = args!(account_id, pass_hash) else {
+        let Ok(args) = args!(account_id) else {
             return false;
         };
-        LoginDatabase::sel_check_password(&mut *login_db, args).await.ok().flatten().is_some()
+        let Some(logon) = LoginDatabase::sel_check_password::<_, AccountLogonRow>(&mut *login_db, args)
+            .await
+            .ok()
+            .flatten()
+        else {
+            return false;
+        };
+        Self::check_logon(&mut *login_db, account_id, &username, &password, &logon)
+            .await
+            .unwrap_or_else(|e| {
+                warn!(target:"server", account_id, cause=?e, "error when checking the password of account");
+                false
+            })
+    }
+
+    /// Checks the (upper-cased) credentials of a game account against its SRP6 verifier.
+    ///
+    /// Accounts that still have a legacy unsalted hash are upgraded to a salt and verifier once their password checks out.
+    async fn check_logon<'e, E: DbExecutor<'e>>(
+        login_db: E,
+        account_id: u32,
+        username: &str,
+        password: &str,
+        logon: &AccountLogonRow,
+    ) -> AccountOpResult<bool> {
+        if let (Some(salt), Some(verifier)) = (&logon.salt, &logon.verifier) {
+            let Ok(salt) = Salt::try_from(salt.as_slice()) else {
+                error!(target:"server", account_id, salt_len=salt.len(), "account has a salt of unexpected length");
+                return Ok(false);
+            };
+            return Ok(GruntSrp6::check_login(username, password, &salt, verifier));
+        }
+        if logon.sha_pass_hash.is_empty() || !logon.sha_pass_hash.eq_ignore_ascii_case(&Self::calculate_sha_pass_hash(username, password)) {
+            return Ok(false);
+        }
+        info!(target:"server", account_id, "upgrading legacy password hash of account to SRP6");
+        let (salt, verifier) = GruntSrp6::make_registration_data(username, password);
+        LoginDatabase::upd_password(login_db, args!(&salt[..], verifier, account_id)?).await?;
+        Ok(true)
     }
 
     pub async fn check_email<'e, E: DbExecutor<'e>>(login_db: E, account_id: u32, new_email: &str) -> bool {
@@ -357,6 +415,7 @@ impl AccountMgr {
         Ok(counts.map(|n| n.0).unwrap_or(0))
     }
 
+    /// The unsalted hash that accounts were stored with before SRP6, only used to upgrade those accounts
     pub fn calculate_sha_pass_hash(name: &str, password: &str) -> String {
         let mut sha = Sha256::new();
         sha.update(name.as_bytes());
@@ -374,6 +433,16 @@ impl AccountMgr {
         Ok(!is_not_banned)
     }
 
+    /// Locks the account to the IP it last logged in from, i.e. `.account lock` in TC
+    pub async fn set_locked<'a, A: DbAcquire<'a>>(login_db: A, account_id: u32, locked: bool) -> AccountOpResult<()> {
+        let mut login_db = login_db.acquire().await?;
+        if Self::get_name(&mut *login_db, account_id).await?.is_none() {
+            return Err(AccountOpError::NameNotExist);
+        }
+        LoginDatabase::upd_account_lock(&mut *login_db, args!(locked, account_id)?).await?;
+        Ok(())
+    }
+
     pub async fn update_account_access<'a, A: DbAcquire<'a>>(
         login_db: A,
         account_id: u32,
@@ -503,13 +572,15 @@ mod tests {
     use sqlx::query;
 
     use super::*;
-    use crate::game::accounts::rbac::RbacPermId;
+    use crate::game::accounts::{battlenet_account_mgr::BattlenetAccountMgr, rbac::RbacPermId};
 
     async fn create_account_for_test<'a, A: DbAcquire<'a>>(login_db: A, user: &str, email: &str, password: &str) -> u32 {
         let mut login_db = login_db.acquire().await.unwrap();
 
         // Setup a dummy bnet account ID
-        LoginDatabase::ins_bnet_account(&mut *login_db, args!(&email, "dummy").unwrap()).await.unwrap();
+        BattlenetAccountMgr::create_battlenet_account(&mut *login_db, email, "dummy", false)
+            .await
+            .unwrap();
         let (bnet_id,) = LoginDatabase::sel_bnet_account_id_by_email(&mut *login_db, args!(email).unwrap())
             .await
             .ok()
@@ -685,6 +756,44 @@ mod tests {
         assert!(AccountMgr::check_password(&mut *txn, account_id, password2).await);
     }
 
+    #[tokio::test]
+    async fn it_upgrades_legacy_password_hash_on_successful_check() {
+        let _p = SHARED_TEST_DB_PERMITS.acquire().await.unwrap();
+        let pool = test_db_pool_auth(None).await;
+        let mut txn = pool.begin().await.unwrap();
+
+        let account_id = create_account_for_test(&mut *txn, "user1", "example1@example.domain", "password1").await;
+        // Turn the account back into one from before SRP6
+        query("UPDATE account SET salt = NULL, verifier = NULL, sha_pass_hash = ? WHERE id = ?")
+            .bind(AccountMgr::calculate_sha_pass_hash("USER1", "PASSWORD1"))
+            .bind(account_id)
+            .execute(&mut *txn)
+            .await
+            .unwrap();
+
+        // Wrong passwords do not upgrade the account
+        assert!(!AccountMgr::check_password(&mut *txn, account_id, "password2").await);
+        let (salt, sha_pass_hash) = query_as::<_, (Option<Vec<u8>>, String)>("SELECT salt, sha_pass_hash FROM account WHERE id = ?")
+            .bind(account_id)
+            .fetch_one(&mut *txn)
+            .await
+            .unwrap();
+        assert!(salt.is_none());
+        assert!(!sha_pass_hash.is_empty());
+
+        assert!(AccountMgr::check_password(&mut *txn, account_id, "password1").await);
+        let (salt, sha_pass_hash) = query_as::<_, (Option<Vec<u8>>, String)>("SELECT salt, sha_pass_hash FROM account WHERE id = ?")
+            .bind(account_id)
+            .fetch_one(&mut *txn)
+            .await
+            .unwrap();
+        assert!(salt.is_some_and(|s| s.len() == 32));
+        assert!(sha_pass_hash.is_empty());
+        // Checks now go through the verifier
+        assert!(AccountMgr::check_password(&mut *txn, account_id, "PASSWORD1").await);
+        assert!(!AccountMgr::check_password(&mut *txn, account_id, "password2").await);
+    }
+
     #[tokio::test]
     async fn it_does_not_change_password_too_long() {
         let _p = SHARED_TEST_DB_PERMITS.acquire().await.unwrap();
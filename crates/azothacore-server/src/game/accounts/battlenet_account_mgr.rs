@@ -1,21 +1,50 @@
-use azothacore_common::hex_str;
+use azothacore_common::{
+    crypto::{
+        srp6::{bnet_srp_username, BnetSrp6V1, BnetSrp6V2, Salt, Srp6},
+        totp,
+    },
+    hex_str,
+};
 use azothacore_database::{
     args,
     database_env::{LoginDatabase, LoginPreparedStmts},
     DbAcquire,
     DbExecutor,
 };
+use num_derive::{FromPrimitive, ToPrimitive};
+use num_traits::FromPrimitive;
 use sha2::{Digest, Sha256};
-use tracing::{error, warn};
+use tracing::{error, info, warn};
 
 use super::{
     account_mgr::{AccountMgr, MAX_PASS_STR},
     AccountOpError,
     AccountOpResult,
 };
+use crate::shared::secrets::SecretMgr;
 
 pub const MAX_BNET_EMAIL_STR: usize = 320;
 
+/// SrpVersion in TC, along with the legacy unsalted hash that accounts were stored with before SRP6
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromPrimitive, ToPrimitive)]
+pub enum SrpVersion {
+    Legacy = 0,
+    /// Case insensitive password, see [BnetSrp6V1]
+    V1 = 1,
+    /// Case sensitive password, see [BnetSrp6V2]
+    V2 = 2,
+}
+
+/// The stored credentials of a Battle.net account
+#[derive(sqlx::FromRow)]
+pub struct BnetAccountLogon {
+    pub srp_version:   u8,
+    pub salt:          Option<Vec<u8>>,
+    pub verifier:      Option<Vec<u8>>,
+    /// Legacy unsalted hash, only set for accounts that have yet to be upgraded to SRP6
+    pub sha_pass_hash: String,
+}
+
 pub struct BattlenetAccountMgr;
 
 impl BattlenetAccountMgr {
@@ -34,15 +63,14 @@ impl BattlenetAccountMgr {
         }
 
         let email = email.to_ascii_uppercase();
-        let password = password.to_ascii_uppercase();
         let mut login_db = login_db.acquire().await?;
 
         if Self::get_id(&mut *login_db, &email).await?.is_some() {
             return Err(AccountOpError::NameAlreadyExist);
         }
 
-        let pass_hash = Self::calculate_sha_pass_hash(&email, &password);
-        if let Err(e) = LoginDatabase::ins_bnet_account(&mut *login_db, args!(&email, pass_hash)?).await {
+        let (salt, verifier) = Self::make_registration_data(&email, password);
+        if let Err(e) = LoginDatabase::ins_bnet_account(&mut *login_db, args!(&email, SrpVersion::V2 as u8, &salt[..], verifier)?).await {
             warn!(target:"sql::sql", cause=?e, "error when creating bnet account from DB");
             return Err(e.into());
         }
@@ -55,7 +83,7 @@ impl BattlenetAccountMgr {
         if create_game_account {
             let bnet_index = 1;
             let account_name = format!("{new_account_id}#{bnet_index}");
-            AccountMgr::create_account(&mut *login_db, &account_name, &password, &email, Some((new_account_id, bnet_index))).await?;
+            AccountMgr::create_account_on_connection(&mut login_db, &account_name, password, &email, Some((new_account_id, bnet_index))).await?;
             game_account_name = Some(account_name);
         }
 
@@ -69,15 +97,12 @@ impl BattlenetAccountMgr {
             return Err(AccountOpError::NameNotExist);
         };
 
-        let username = username.to_ascii_uppercase();
-        let new_password = new_password.to_ascii_uppercase();
-
         if new_password.len() > MAX_PASS_STR {
             return Err(AccountOpError::PassTooLong);
         }
 
-        let pass_hash = Self::calculate_sha_pass_hash(&username, &new_password);
-        LoginDatabase::upd_bnet_password(&mut *login_db, args!(pass_hash, account_id)?).await?;
+        let (salt, verifier) = Self::make_registration_data(&username, new_password);
+        LoginDatabase::upd_bnet_password(&mut *login_db, args!(SrpVersion::V2 as u8, &salt[..], verifier, account_id)?).await?;
 
         Ok(())
     }
@@ -88,12 +113,62 @@ impl BattlenetAccountMgr {
             return false;
         };
 
-        let username = username.to_ascii_uppercase();
-        let password = password.to_ascii_uppercase();
+        let Ok(args) = args!(account_id) else { return false };
+        let Some(logon) = LoginDatabase::sel_bnet_check_password::<_, BnetAccountLogon>(&mut *login_db, args)
+            .await
+            .ok()
+            .flatten()
+        else {
+            return false;
+        };
+        Self::check_logon(&mut *login_db, account_id, &username, password, &logon)
+            .await
+            .unwrap_or_else(|e| {
+                warn!(target:"server", account_id, cause=?e, "error when checking the password of Battle.net account");
+                false
+            })
+    }
+
+    /// Checks the password of a Battle.net account against its SRP6 verifier, the password is used as is.
+    ///
+    /// Accounts that still have a legacy unsalted hash are upgraded to [SrpVersion::V1] once their password checks out,
+    /// which keeps their password case insensitive.
+    pub async fn check_logon<'e, E: DbExecutor<'e>>(
+        login_db: E,
+        account_id: u32,
+        email: &str,
+        password: &str,
+        logon: &BnetAccountLogon,
+    ) -> AccountOpResult<bool> {
+        let srp_version = SrpVersion::from_u8(logon.srp_version);
+        if srp_version == Some(SrpVersion::Legacy) {
+            let legacy_hash = Self::calculate_sha_pass_hash(&email.to_ascii_uppercase(), &password.to_ascii_uppercase());
+            if logon.sha_pass_hash.is_empty() || !logon.sha_pass_hash.eq_ignore_ascii_case(&legacy_hash) {
+                return Ok(false);
+            }
+            info!(target:"server", account_id, "upgrading legacy password hash of Battle.net account to SRP6");
+            let (salt, verifier) = BnetSrp6V1::make_registration_data(&bnet_srp_username(email), &password.to_ascii_uppercase());
+            LoginDatabase::upd_bnet_password(login_db, args!(SrpVersion::V1 as u8, &salt[..], verifier, account_id)?).await?;
+            return Ok(true);
+        }
+        let (Some(srp_version), Some(salt), Some(verifier)) = (
+            srp_version,
+            logon.salt.as_deref().and_then(|s| Salt::try_from(s).ok()),
+            logon.verifier.as_deref(),
+        ) else {
+            error!(target:"server", account_id, srp_version=logon.srp_version, "Battle.net account has invalid SRP6 credentials");
+            return Ok(false);
+        };
+        let username = bnet_srp_username(email);
+        Ok(match srp_version {
+            SrpVersion::V1 => BnetSrp6V1::check_login(&username, &password.to_ascii_uppercase(), &salt, verifier),
+            _ => BnetSrp6V2::check_login(&username, password, &salt, verifier),
+        })
+    }
 
-        let pass_hash = Self::calculate_sha_pass_hash(&username, &password);
-        let Ok(args) = args!(account_id, pass_hash) else { return false };
-        LoginDatabase::sel_bnet_check_password(&mut *login_db, args).await.ok().flatten().is_some()
+    /// MakeSRP6Registration in TC, always the most recent [SrpVersion]
+    fn make_registration_data(email: &str, password: &str) -> (Salt, Vec<u8>) {
+        BnetSrp6V2::make_registration_data(&bnet_srp_username(email), password)
     }
 
     pub async fn link_with_game_account<'a, A: DbAcquire<'a>>(login_db: A, email: &str, game_account_name: &str) -> AccountOpResult<()> {
@@ -141,6 +216,68 @@ impl BattlenetAccountMgr {
         Ok(())
     }
 
+    /// Locks the account to the IP it last logged in from, i.e. `.bnetaccount lock ip` in TC
+    pub async fn set_locked<'a, A: DbAcquire<'a>>(login_db: A, account_id: u32, locked: bool) -> AccountOpResult<()> {
+        let mut login_db = login_db.acquire().await?;
+        if Self::get_name(&mut *login_db, account_id).await?.is_none() {
+            return Err(AccountOpError::NameNotExist);
+        }
+        LoginDatabase::upd_bnet_account_lock(&mut *login_db, args!(locked, account_id)?).await?;
+        Ok(())
+    }
+
+    /// Sets up an authenticator for the account, i.e. `.account 2fa setup` in TC.
+    ///
+    /// The token must be a valid one for the given secret, which makes sure that the user did enter the secret into
+    /// their authenticator correctly before it is required to log in.
+    pub async fn setup_authenticator<'a, A: DbAcquire<'a>>(
+        login_db: A,
+        secret_mgr: &SecretMgr,
+        account_id: u32,
+        secret: &[u8],
+        token: u32,
+    ) -> AccountOpResult<()> {
+        let mut login_db = login_db.acquire().await?;
+        match Self::get_totp_secret(&mut *login_db, account_id).await? {
+            None => return Err(AccountOpError::NameNotExist),
+            Some(Some(_)) => return Err(AccountOpError::AuthenticatorAlreadySet),
+            Some(None) => {},
+        }
+        if !totp::validate_token(secret, token) {
+            return Err(AccountOpError::InvalidToken);
+        }
+        let encrypted = secret_mgr.encrypt_totp_secret(secret).ok_or(AccountOpError::SecretUnavailable)?;
+        LoginDatabase::upd_bnet_totp_secret(&mut *login_db, args!(encrypted, account_id)?).await?;
+        Ok(())
+    }
+
+    /// Removes the authenticator of the account, i.e. `.account 2fa remove` in TC.
+    ///
+    /// Unless a token is given, it is removed without checking for one, which is what admins need for accounts whose
+    /// authenticator got lost.
+    pub async fn remove_authenticator<'a, A: DbAcquire<'a>>(login_db: A, secret_mgr: &SecretMgr, account_id: u32, token: Option<u32>) -> AccountOpResult<()> {
+        let mut login_db = login_db.acquire().await?;
+        let encrypted = match Self::get_totp_secret(&mut *login_db, account_id).await? {
+            None => return Err(AccountOpError::NameNotExist),
+            Some(None) => return Err(AccountOpError::AuthenticatorNotSet),
+            Some(Some(s)) => s,
+        };
+        if let Some(token) = token {
+            let secret = secret_mgr.decrypt_totp_secret(&encrypted).ok_or(AccountOpError::SecretUnavailable)?;
+            if !totp::validate_token(&secret, token) {
+                return Err(AccountOpError::InvalidToken);
+            }
+        }
+        LoginDatabase::upd_bnet_totp_secret(&mut *login_db, args!(None::<Vec<u8>>, account_id)?).await?;
+        Ok(())
+    }
+
+    /// The encrypted TOTP secret of the account, None if the account doesn't exist
+    async fn get_totp_secret<'e, E: DbExecutor<'e>>(login_db: E, account_id: u32) -> AccountOpResult<Option<Option<Vec<u8>>>> {
+        let secret = LoginDatabase::sel_bnet_totp_secret::<_, (Option<Vec<u8>>,)>(login_db, args!(account_id)?).await?;
+        Ok(secret.map(|v| v.0))
+    }
+
     pub async fn get_id<'e, E: DbExecutor<'e>>(login_db: E, username: &str) -> AccountOpResult<Option<u32>> {
         let id = LoginDatabase::sel_bnet_account_id_by_email::<_, (u32,)>(login_db, args!(username)?).await?;
 
@@ -162,6 +299,7 @@ impl BattlenetAccountMgr {
         Ok(max_index.map(|v| v.0.unwrap_or_default()))
     }
 
+    /// The unsalted hash that accounts were stored with before SRP6, only used to upgrade those accounts
     pub fn calculate_sha_pass_hash(name: &str, password: &str) -> String {
         let mut email = Sha256::new();
         email.update(name.as_bytes());
@@ -254,6 +392,99 @@ mod tests {
         );
         BattlenetAccountMgr::change_password(&mut *txn, bnet_account_id, new_password).await.unwrap();
         assert!(BattlenetAccountMgr::check_password(&mut *txn, bnet_account_id, new_password).await);
+        // Passwords are case sensitive
+        assert!(!BattlenetAccountMgr::check_password(&mut *txn, bnet_account_id, &new_password.to_ascii_uppercase()).await);
+    }
+
+    #[tokio::test]
+    async fn it_upgrades_legacy_password_hash_on_successful_check() {
+        let _p = SHARED_TEST_DB_PERMITS.acquire().await.unwrap();
+        let pool = test_db_pool_auth(None).await;
+        let mut txn = pool.begin().await.unwrap();
+
+        let email = "example@example.com";
+        BattlenetAccountMgr::create_battlenet_account(&mut *txn, email, "abc1234", false).await.unwrap();
+        let bnet_account_id = BattlenetAccountMgr::get_id(&mut *txn, email).await.unwrap().unwrap();
+        // Turn the account back into one from before SRP6
+        sqlx::query("UPDATE battlenet_accounts SET srp_version = 0, salt = NULL, verifier = NULL, sha_pass_hash = ? WHERE id = ?")
+            .bind(BattlenetAccountMgr::calculate_sha_pass_hash(&email.to_ascii_uppercase(), "ABC1234"))
+            .bind(bnet_account_id)
+            .execute(&mut *txn)
+            .await
+            .unwrap();
+
+        assert!(!BattlenetAccountMgr::check_password(&mut *txn, bnet_account_id, "def5678").await);
+        // Legacy hashes are case insensitive
+        assert!(BattlenetAccountMgr::check_password(&mut *txn, bnet_account_id, "Abc1234").await);
+        let (srp_version, sha_pass_hash) = sqlx::query_as::<_, (u8, String)>("SELECT srp_version, sha_pass_hash FROM battlenet_accounts WHERE id = ?")
+            .bind(bnet_account_id)
+            .fetch_one(&mut *txn)
+            .await
+            .unwrap();
+        assert_eq!(SrpVersion::from_u8(srp_version), Some(SrpVersion::V1));
+        assert!(sha_pass_hash.is_empty());
+        // The password stays case insensitive after the upgrade
+        assert!(BattlenetAccountMgr::check_password(&mut *txn, bnet_account_id, "Abc1234").await);
+        assert!(BattlenetAccountMgr::check_password(&mut *txn, bnet_account_id, "abc1234").await);
+        assert!(BattlenetAccountMgr::check_password(&mut *txn, bnet_account_id, "ABC1234").await);
+        assert!(!BattlenetAccountMgr::check_password(&mut *txn, bnet_account_id, "def5678").await);
+    }
+
+    #[tokio::test]
+    async fn it_sets_up_and_removes_authenticator() {
+        let _p = SHARED_TEST_DB_PERMITS.acquire().await.unwrap();
+        let pool = test_db_pool_auth(None).await;
+        let mut txn = pool.begin().await.unwrap();
+
+        let secret_mgr = SecretMgr::new(Some([1; 16]));
+        let secret = totp::generate_secret();
+        let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs();
+        let token = totp::generate_token(&secret, now);
+        let bad_token = (token + 1) % 1_000_000;
+
+        assert_eq!(
+            BattlenetAccountMgr::setup_authenticator(&mut *txn, &secret_mgr, 9999, &secret, token).await,
+            Err(AccountOpError::NameNotExist)
+        );
+        let email = "example@example.com";
+        BattlenetAccountMgr::create_battlenet_account(&mut *txn, email, "1234", false).await.unwrap();
+        let bnet_account_id = BattlenetAccountMgr::get_id(&mut *txn, email).await.unwrap().unwrap();
+
+        assert_eq!(
+            BattlenetAccountMgr::remove_authenticator(&mut *txn, &secret_mgr, bnet_account_id, None).await,
+            Err(AccountOpError::AuthenticatorNotSet)
+        );
+        assert_eq!(
+            BattlenetAccountMgr::setup_authenticator(&mut *txn, &SecretMgr::default(), bnet_account_id, &secret, token).await,
+            Err(AccountOpError::SecretUnavailable)
+        );
+        assert_eq!(
+            BattlenetAccountMgr::setup_authenticator(&mut *txn, &secret_mgr, bnet_account_id, &secret, bad_token).await,
+            Err(AccountOpError::InvalidToken)
+        );
+        BattlenetAccountMgr::setup_authenticator(&mut *txn, &secret_mgr, bnet_account_id, &secret, token)
+            .await
+            .unwrap();
+        assert_eq!(
+            BattlenetAccountMgr::setup_authenticator(&mut *txn, &secret_mgr, bnet_account_id, &secret, token).await,
+            Err(AccountOpError::AuthenticatorAlreadySet)
+        );
+        // Stored encrypted
+        let encrypted = BattlenetAccountMgr::get_totp_secret(&mut *txn, bnet_account_id)
+            .await
+            .unwrap()
+            .flatten()
+            .unwrap();
+        assert_eq!(secret_mgr.decrypt_totp_secret(&encrypted).as_deref(), Some(&secret[..]));
+
+        assert_eq!(
+            BattlenetAccountMgr::remove_authenticator(&mut *txn, &secret_mgr, bnet_account_id, Some(bad_token)).await,
+            Err(AccountOpError::InvalidToken)
+        );
+        BattlenetAccountMgr::remove_authenticator(&mut *txn, &secret_mgr, bnet_account_id, Some(token))
+            .await
+            .unwrap();
+        assert_eq!(BattlenetAccountMgr::get_totp_secret(&mut *txn, bnet_account_id).await.unwrap(), Some(None));
     }
 
     #[tokio::test]
@@ -1,13 +1,277 @@
-use bevy::prelude::Resource;
+use std::{collections::HashMap, time::Instant};
 
-use crate::game::entities::object::object_guid::{HighGuidPlayer, ObjectGuid};
+use azothacore_common::{
+    az_error,
+    bevy_app::{AzStartupFailedEvent, TokioRuntime},
+    AzResult,
+};
+use azothacore_database::database_env::CharacterDatabase;
+use bevy::prelude::{Commands, EventWriter, Res, Resource};
+use sqlx::query_as;
+use tracing::{error, info};
 
-#[derive(Resource)]
-pub struct CharacterCache {}
+use crate::game::{
+    entities::object::object_guid::{HighGuidPlayer, ObjectGuid, ObjectGuidRealmSpecific},
+    world::CurrentRealm,
+};
+
+/// CharacterCacheEntry in TC / AC
+#[derive(Debug, Clone, PartialEq)]
+pub struct CharacterCacheEntry {
+    pub guid:       ObjectGuid<HighGuidPlayer>,
+    pub name:       String,
+    pub account_id: u32,
+    pub race:       u8,
+    pub class:      u8,
+    pub sex:        u8,
+    pub level:      u8,
+    /// low guid of the guild the character is in, 0 if the character is not in a guild
+    pub guild_id:   u64,
+    pub is_deleted: bool,
+}
+
+/// CharacterCache in TC / AC
+///
+/// Entries are indexed by GUID, with a secondary index from the lowercased character name to the GUID so that
+/// name lookups are case insensitive.
+#[derive(Resource, Default)]
+pub struct CharacterCache {
+    by_guid: HashMap<ObjectGuid<HighGuidPlayer>, CharacterCacheEntry>,
+    by_name: HashMap<String, ObjectGuid<HighGuidPlayer>>,
+}
 
 impl CharacterCache {
+    /// CharacterCache::LoadCharacterCacheStorage in TC / AC
+    pub fn load(
+        mut commands: Commands,
+        rt: Res<TokioRuntime>,
+        char_db: Res<CharacterDatabase>,
+        current_realm: Res<CurrentRealm>,
+        mut ev_startup_failed: EventWriter<AzStartupFailedEvent>,
+    ) {
+        info!(target:"server::loading", "Loading Player Name data...");
+        let old_ms_time = Instant::now();
+        let rows = match rt.block_on(
+            query_as::<_, (u64, String, u32, u8, u8, u8, u8, Option<u32>, Option<u64>)>(
+                "SELECT c.guid, c.name, c.account, c.race, c.gender, c.class, c.level, c.deleteDate, gm.guildid FROM characters AS c LEFT JOIN guild_member AS gm ON c.guid = gm.guid",
+            )
+            .fetch_all(&**char_db),
+        ) {
+            Err(e) => {
+                ev_startup_failed.send_default();
+                error!(target:"server::loading", cause=?e, "error retrieving player name data");
+                return;
+            },
+            Ok(r) => r,
+        };
+        let mut cache = Self::default();
+        for (guid, name, account_id, race, sex, class, level, delete_date, guild_id) in rows {
+            cache.add_character_cache_entry(CharacterCacheEntry {
+                guid: ObjectGuid::<HighGuidPlayer>::realm_specific(&current_realm, guid),
+                name,
+                account_id,
+                race,
+                class,
+                sex,
+                level,
+                guild_id: guild_id.unwrap_or(0),
+                is_deleted: delete_date.is_some(),
+            });
+        }
+        let elapsed = Instant::now() - old_ms_time;
+        info!(target:"server::loading", ">> Loaded character infos for {} characters in {} ms", cache.len(), elapsed.as_millis());
+        commands.insert_resource(cache);
+    }
+
+    pub fn len(&self) -> usize {
+        self.by_guid.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_guid.is_empty()
+    }
+
+    /// CharacterCache::AddCharacterCacheEntry in TC / AC
+    ///
+    /// Replaces any existing entry for the same GUID.
+    pub fn add_character_cache_entry(&mut self, entry: CharacterCacheEntry) {
+        if let Some(old) = self.by_guid.remove(&entry.guid) {
+            self.remove_name_index(&old.name, old.guid);
+        }
+        // Unlinked characters have their name cleared, only index characters that actually have one
+        if !entry.name.is_empty() {
+            self.by_name.insert(entry.name.to_lowercase(), entry.guid);
+        }
+        self.by_guid.insert(entry.guid, entry);
+    }
+
+    /// CharacterCache::DeleteCharacterCacheEntry in TC / AC
+    pub fn delete_character_cache_entry(&mut self, guid: ObjectGuid<HighGuidPlayer>) -> Option<CharacterCacheEntry> {
+        let entry = self.by_guid.remove(&guid)?;
+        self.remove_name_index(&entry.name, guid);
+        Some(entry)
+    }
+
+    /// CharacterCache::UpdateCharacterData in TC / AC, used on character rename and race / faction changes.
+    pub fn update_character_data(&mut self, guid: ObjectGuid<HighGuidPlayer>, name: &str, sex: Option<u8>, race: Option<u8>) -> AzResult<()> {
+        let entry = self.entry_mut(guid)?;
+        let old_name = std::mem::replace(&mut entry.name, name.to_string());
+        if let Some(sex) = sex {
+            entry.sex = sex;
+        }
+        if let Some(race) = race {
+            entry.race = race;
+        }
+        self.remove_name_index(&old_name, guid);
+        if !name.is_empty() {
+            self.by_name.insert(name.to_lowercase(), guid);
+        }
+        Ok(())
+    }
+
+    /// CharacterCache::UpdateCharacterGender in TC / AC
+    pub fn update_character_gender(&mut self, guid: ObjectGuid<HighGuidPlayer>, sex: u8) -> AzResult<()> {
+        self.entry_mut(guid)?.sex = sex;
+        Ok(())
+    }
+
+    /// CharacterCache::UpdateCharacterLevel in TC / AC
+    pub fn update_character_level(&mut self, guid: ObjectGuid<HighGuidPlayer>, level: u8) -> AzResult<()> {
+        self.entry_mut(guid)?.level = level;
+        Ok(())
+    }
+
+    /// CharacterCache::UpdateCharacterAccountId in TC / AC
+    pub fn update_character_account_id(&mut self, guid: ObjectGuid<HighGuidPlayer>, account_id: u32) -> AzResult<()> {
+        self.entry_mut(guid)?.account_id = account_id;
+        Ok(())
+    }
+
+    /// CharacterCache::UpdateCharacterGuildId in TC / AC
+    pub fn update_character_guild_id(&mut self, guid: ObjectGuid<HighGuidPlayer>, guild_id: u64) -> AzResult<()> {
+        self.entry_mut(guid)?.guild_id = guild_id;
+        Ok(())
+    }
+
+    /// CharacterCache::UpdateCharacterInfoDeleted in TC
+    ///
+    /// A new name can be given when restoring a character, or an empty one when the character is unlinked from its account.
+    pub fn update_character_info_deleted(&mut self, guid: ObjectGuid<HighGuidPlayer>, deleted: bool, name: Option<&str>) -> AzResult<()> {
+        self.entry_mut(guid)?.is_deleted = deleted;
+        if let Some(name) = name {
+            self.update_character_data(guid, name, None, None)?;
+        }
+        Ok(())
+    }
+
+    /// CharacterCache::HasCharacterCacheEntry in TC / AC
+    pub fn has_character_cache_entry(&self, guid: ObjectGuid<HighGuidPlayer>) -> bool {
+        self.by_guid.contains_key(&guid)
+    }
+
+    /// CharacterCache::GetCharacterCacheByGuid in TC / AC
+    pub fn get_character_cache_by_guid(&self, guid: ObjectGuid<HighGuidPlayer>) -> Option<&CharacterCacheEntry> {
+        self.by_guid.get(&guid)
+    }
+
+    /// CharacterCache::GetCharacterCacheByName in TC / AC, the name is matched case insensitively.
+    pub fn get_character_cache_by_name(&self, name: &str) -> Option<&CharacterCacheEntry> {
+        self.by_name.get(&name.to_lowercase()).and_then(|guid| self.by_guid.get(guid))
+    }
+
+    /// CharacterCache::GetCharacterGuidByName in TC / AC, ObjectMgr::GetPlayerGUIDByName in TC
+    pub fn get_character_guid_by_name(&self, name: &str) -> Option<ObjectGuid<HighGuidPlayer>> {
+        self.by_name.get(&name.to_lowercase()).copied()
+    }
+
     /// CharacterCache::GetCharacterNameByGuid in AC, ObjectMgr::GetPlayerNameByGUID in TC
-    pub fn get_character_name_by_guid(&self, _guid: ObjectGuid<HighGuidPlayer>) -> Option<String> {
-        todo!()
+    pub fn get_character_name_by_guid(&self, guid: ObjectGuid<HighGuidPlayer>) -> Option<String> {
+        self.by_guid.get(&guid).map(|e| e.name.clone())
+    }
+
+    /// CharacterCache::GetCharacterAccountIdByGuid in TC / AC
+    pub fn get_character_account_id_by_guid(&self, guid: ObjectGuid<HighGuidPlayer>) -> Option<u32> {
+        self.by_guid.get(&guid).map(|e| e.account_id)
+    }
+
+    /// CharacterCache::GetCharacterAccountIdByName in TC / AC
+    pub fn get_character_account_id_by_name(&self, name: &str) -> Option<u32> {
+        self.get_character_cache_by_name(name).map(|e| e.account_id)
+    }
+
+    /// CharacterCache::GetCharacterLevelByGuid in TC / AC
+    pub fn get_character_level_by_guid(&self, guid: ObjectGuid<HighGuidPlayer>) -> Option<u8> {
+        self.by_guid.get(&guid).map(|e| e.level)
+    }
+
+    /// CharacterCache::GetCharacterGuildIdByGuid in TC / AC
+    pub fn get_character_guild_id_by_guid(&self, guid: ObjectGuid<HighGuidPlayer>) -> Option<u64> {
+        self.by_guid.get(&guid).map(|e| e.guild_id)
+    }
+
+    fn entry_mut(&mut self, guid: ObjectGuid<HighGuidPlayer>) -> AzResult<&mut CharacterCacheEntry> {
+        self.by_guid
+            .get_mut(&guid)
+            .ok_or_else(|| az_error!("character not found in the character cache, guid={guid}"))
+    }
+
+    /// Only drops the name index if it still points at the given GUID, since the name may already be taken by another character
+    fn remove_name_index(&mut self, name: &str, guid: ObjectGuid<HighGuidPlayer>) {
+        let key = name.to_lowercase();
+        if self.by_name.get(&key).is_some_and(|g| *g == guid) {
+            self.by_name.remove(&key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::entities::object::object_guid::HighGuidTrait;
+
+    fn entry(counter: u64, name: &str) -> CharacterCacheEntry {
+        let mut raw = [0u8; 16];
+        raw[..8].copy_from_slice(&counter.to_le_bytes());
+        raw[8..].copy_from_slice(&(u64::from(HighGuidPlayer::ID) << 58).to_le_bytes());
+        CharacterCacheEntry {
+            guid:       ObjectGuid::<HighGuidPlayer>::try_from(&raw[..]).unwrap(),
+            name:       name.to_string(),
+            account_id: 1,
+            race:       1,
+            class:      1,
+            sex:        0,
+            level:      1,
+            guild_id:   0,
+            is_deleted: false,
+        }
+    }
+
+    #[test]
+    fn lookup_by_name_is_case_insensitive_and_follows_renames() {
+        let mut cache = CharacterCache::default();
+        let jaina = entry(1, "Jaina");
+        let guid = jaina.guid;
+        cache.add_character_cache_entry(jaina);
+        cache.add_character_cache_entry(entry(2, "Thrall"));
+
+        assert_eq!(cache.get_character_guid_by_name("jAINA"), Some(guid));
+        assert_eq!(cache.get_character_name_by_guid(guid).as_deref(), Some("Jaina"));
+
+        cache.update_character_data(guid, "Proudmoore", None, Some(7)).unwrap();
+        assert_eq!(cache.get_character_guid_by_name("jaina"), None);
+        assert_eq!(cache.get_character_guid_by_name("PROUDMOORE"), Some(guid));
+        assert_eq!(cache.get_character_cache_by_guid(guid).unwrap().race, 7);
+
+        cache.update_character_level(guid, 60).unwrap();
+        assert_eq!(cache.get_character_level_by_guid(guid), Some(60));
+
+        cache.update_character_info_deleted(guid, true, Some("")).unwrap();
+        assert!(cache.get_character_cache_by_guid(guid).unwrap().is_deleted);
+        assert_eq!(cache.get_character_guid_by_name("proudmoore"), None);
+
+        assert!(cache.delete_character_cache_entry(guid).is_some());
+        assert!(!cache.has_character_cache_entry(guid));
+        assert_eq!(cache.len(), 1);
+        assert!(cache.update_character_level(guid, 61).is_err());
     }
 }
@@ -1,3 +1,4 @@
+pub mod item;
 pub mod object;
 pub mod player;
 pub mod unit;
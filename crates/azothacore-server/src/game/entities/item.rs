@@ -0,0 +1,85 @@
+use num_derive::{FromPrimitive, ToPrimitive};
+
+use crate::game::entities::player::{
+    EQUIPMENT_SLOT_BACK,
+    EQUIPMENT_SLOT_BODY,
+    EQUIPMENT_SLOT_CHEST,
+    EQUIPMENT_SLOT_FEET,
+    EQUIPMENT_SLOT_FINGER1,
+    EQUIPMENT_SLOT_FINGER2,
+    EQUIPMENT_SLOT_HANDS,
+    EQUIPMENT_SLOT_HEAD,
+    EQUIPMENT_SLOT_LEGS,
+    EQUIPMENT_SLOT_MAINHAND,
+    EQUIPMENT_SLOT_NECK,
+    EQUIPMENT_SLOT_OFFHAND,
+    EQUIPMENT_SLOT_SHOULDERS,
+    EQUIPMENT_SLOT_TABARD,
+    EQUIPMENT_SLOT_TRINKET1,
+    EQUIPMENT_SLOT_TRINKET2,
+    EQUIPMENT_SLOT_WAIST,
+    EQUIPMENT_SLOT_WRISTS,
+};
+
+/// InventoryType in TC / AC
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromPrimitive, ToPrimitive)]
+pub enum InventoryType {
+    NonEquip = 0,
+    Head = 1,
+    Neck = 2,
+    Shoulders = 3,
+    Body = 4,
+    Chest = 5,
+    Waist = 6,
+    Legs = 7,
+    Feet = 8,
+    Wrists = 9,
+    Hands = 10,
+    Finger = 11,
+    Trinket = 12,
+    Weapon = 13,
+    Shield = 14,
+    Ranged = 15,
+    Cloak = 16,
+    TwoHandWeapon = 17,
+    Bag = 18,
+    Tabard = 19,
+    Robe = 20,
+    WeaponMainHand = 21,
+    WeaponOffHand = 22,
+    Holdable = 23,
+    Ammo = 24,
+    Thrown = 25,
+    RangedRight = 26,
+    Quiver = 27,
+    Relic = 28,
+}
+
+impl InventoryType {
+    /// The equipment slots an item of this inventory type can be equipped in, in order of preference.
+    ///
+    /// Player::FindEquipSlot in TC / AC, without the checks that need a player, i.e. one-handed weapons
+    /// only go into the main hand as dual wielding is not known. Bags are not included, they are placed
+    /// in the bag slots instead.
+    pub fn equip_slots(self) -> &'static [u8] {
+        match self {
+            Self::Head => &[EQUIPMENT_SLOT_HEAD],
+            Self::Neck => &[EQUIPMENT_SLOT_NECK],
+            Self::Shoulders => &[EQUIPMENT_SLOT_SHOULDERS],
+            Self::Body => &[EQUIPMENT_SLOT_BODY],
+            Self::Chest | Self::Robe => &[EQUIPMENT_SLOT_CHEST],
+            Self::Waist => &[EQUIPMENT_SLOT_WAIST],
+            Self::Legs => &[EQUIPMENT_SLOT_LEGS],
+            Self::Feet => &[EQUIPMENT_SLOT_FEET],
+            Self::Wrists => &[EQUIPMENT_SLOT_WRISTS],
+            Self::Hands => &[EQUIPMENT_SLOT_HANDS],
+            Self::Finger => &[EQUIPMENT_SLOT_FINGER1, EQUIPMENT_SLOT_FINGER2],
+            Self::Trinket => &[EQUIPMENT_SLOT_TRINKET1, EQUIPMENT_SLOT_TRINKET2],
+            Self::Cloak => &[EQUIPMENT_SLOT_BACK],
+            Self::Weapon | Self::TwoHandWeapon | Self::WeaponMainHand | Self::Ranged | Self::RangedRight | Self::Thrown => &[EQUIPMENT_SLOT_MAINHAND],
+            Self::Shield | Self::WeaponOffHand | Self::Holdable => &[EQUIPMENT_SLOT_OFFHAND],
+            Self::Tabard => &[EQUIPMENT_SLOT_TABARD],
+            Self::NonEquip | Self::Bag | Self::Ammo | Self::Quiver | Self::Relic => &[],
+        }
+    }
+}
@@ -1,2 +1,22 @@
+use bevy::prelude::Component;
+
 pub mod object_defines;
 pub mod object_guid;
+pub mod update_data;
+pub mod update_fields;
+
+/// Position in TC / AC, the position of a world object within the map that it is in
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq)]
+pub struct Position {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    /// orientation
+    pub o: f32,
+}
+
+impl Position {
+    pub fn new(x: f32, y: f32, z: f32, o: f32) -> Self {
+        Self { x, y, z, o }
+    }
+}
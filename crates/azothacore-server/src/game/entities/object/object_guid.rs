@@ -2,6 +2,7 @@ use std::{
     any::Any,
     cmp,
     fmt::{Debug, Display},
+    hash::{Hash, Hasher},
     marker::PhantomData,
     sync::atomic::AtomicU64,
 };
@@ -178,6 +179,13 @@ impl<H1, H2> PartialEq<ObjectGuid<H2>> for ObjectGuid<H1> {
 
 impl<H> Eq for ObjectGuid<H> {}
 
+impl<H> Hash for ObjectGuid<H> {
+    fn hash<S: Hasher>(&self, state: &mut S) {
+        self.high.hash(state);
+        self.low.hash(state);
+    }
+}
+
 impl<H: HighGuidTrait + 'static> Display for ObjectGuid<H> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -432,6 +440,11 @@ impl<H> ObjectGuid<H> {
         self.raw_hightype() == HighGuidCast::ID
     }
 
+    /// The low and high parts of the guid, i.e. ObjectGuid::_data in TC
+    pub const fn raw_parts(&self) -> [u64; 2] {
+        [self.low, self.high]
+    }
+
     pub const fn raw_value(&self) -> [u8; 16] {
         (((self.low as u128) << 64) + (self.high as u128)).to_le_bytes()
     }
@@ -444,7 +457,8 @@ impl<H: 'static> ObjectGuid<H> {
         c.downcast_ref()
     }
 
-    fn counter(&self) -> u64 {
+    /// ObjectGuid::GetCounter in TC / AC
+    pub fn counter(&self) -> u64 {
         if self.as_map_related().is_some() {
             self.low & 0x000000FFFFFFFFFF
         } else {
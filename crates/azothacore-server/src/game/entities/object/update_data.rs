@@ -0,0 +1,332 @@
+use std::collections::BTreeSet;
+
+use flagset::{flags, FlagSet};
+
+use crate::{
+    game::{
+        entities::{
+            object::{
+                object_guid::ObjectGuid,
+                update_fields::{type_id_index, UpdateFields},
+                Position,
+            },
+            unit::{MoveSpeed, UnitMoveType},
+        },
+        server::{protocol::opcodes::OpcodeServer, world_packets::ServerPacket},
+    },
+    shared::packets::byte_buffer::ByteBuffer,
+};
+
+/// OBJECT_UPDATE_TYPE in TC
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpdateType {
+    Values = 0,
+    CreateObject = 1,
+    CreateObject2 = 2,
+    OutOfRangeObjects = 3,
+}
+
+flags! {
+    /// OBJECT_UPDATE_FLAGS in TC
+    pub enum UpdateFlag: u16 {
+        /// UPDATEFLAG_SELF in TC, the object is the player of the client that receives the update
+        ThisIsYou          = 0x0001,
+        Transport          = 0x0002,
+        HasTarget          = 0x0004,
+        Living             = 0x0008,
+        StationaryPosition = 0x0010,
+        Vehicle            = 0x0020,
+        TransportPosition  = 0x0040,
+        Rotation           = 0x0080,
+        AnimKits           = 0x0100,
+        AreaTrigger        = 0x0200,
+        GameObject         = 0x0400,
+    }
+}
+
+/// The state of an object that is sent along with its creation by Object::BuildMovementUpdate in TC
+#[derive(Clone, Copy)]
+pub struct MovementUpdate {
+    /// Object::m_updateFlag in TC
+    pub flags:           FlagSet<UpdateFlag>,
+    pub position:        Position,
+    /// Only used for [`UpdateFlag::Living`]
+    pub speeds:          MoveSpeed,
+    /// GameObject::GetPackedWorldRotation in TC, only used for [`UpdateFlag::Rotation`]
+    pub packed_rotation: i64,
+}
+
+impl MovementUpdate {
+    /// Object::BuildMovementUpdate in TC
+    // TODO: Implement me: Transports, vehicles, anim kits, area triggers, game object world effects and combat victims. Until then their
+    // flags are not sent.
+    pub fn build(&self, guid: &ObjectGuid, buf: &mut ByteBuffer) {
+        let flags = self.flags & (UpdateFlag::ThisIsYou | UpdateFlag::Living | UpdateFlag::StationaryPosition | UpdateFlag::Rotation);
+
+        buf.write_bit(false); // NoBirthAnim
+        buf.write_bit(false); // EnablePortals
+        buf.write_bit(false); // PlayHoverAnim
+        buf.write_bit(flags.contains(UpdateFlag::Living)); // HasMovementUpdate
+        buf.write_bit(flags.contains(UpdateFlag::Transport)); // HasMovementTransport
+        buf.write_bit(flags.contains(UpdateFlag::StationaryPosition));
+        buf.write_bit(flags.contains(UpdateFlag::HasTarget)); // HasCombatVictim
+        buf.write_bit(false); // HasServerTime
+        buf.write_bit(flags.contains(UpdateFlag::Vehicle));
+        buf.write_bit(flags.contains(UpdateFlag::AnimKits));
+        buf.write_bit(flags.contains(UpdateFlag::Rotation));
+        buf.write_bit(flags.contains(UpdateFlag::AreaTrigger));
+        buf.write_bit(flags.contains(UpdateFlag::GameObject));
+        buf.write_bit(false); // SmoothPhasing
+        buf.write_bit(flags.contains(UpdateFlag::ThisIsYou));
+        buf.write_bit(false); // SceneObject
+        buf.write_bit(false); // ScenePendingInstances
+        buf.flush_bits();
+
+        if flags.contains(UpdateFlag::Living) {
+            let Position { x, y, z, o } = self.position;
+            buf.write_packed_guid(guid); // MoverGUID
+            buf.write_u32(0); // MoveTime
+            buf.write_f32(x);
+            buf.write_f32(y);
+            buf.write_f32(z);
+            buf.write_f32(o);
+            buf.write_f32(0.0); // Pitch
+            buf.write_f32(0.0); // StepUpStartElevation
+            buf.write_u32(0); // RemoveForcesIDs.size()
+            buf.write_u32(0); // MoveIndex
+            buf.write_bits(0, 30); // MovementFlags
+            buf.write_bits(0, 18); // MovementFlags2
+            buf.write_bit(false); // HasTransport
+            buf.write_bit(false); // HasFall
+            buf.write_bit(false); // HasSpline - marks that the unit uses spline movement
+            buf.write_bit(false); // HeightChangeFailed
+            buf.write_bit(false); // RemoteTimeValid
+            buf.flush_bits();
+            for mtype in [
+                UnitMoveType::Walk,
+                UnitMoveType::Run,
+                UnitMoveType::RunBack,
+                UnitMoveType::Swim,
+                UnitMoveType::SwimBack,
+                UnitMoveType::Flight,
+                UnitMoveType::FlightBack,
+                UnitMoveType::TurnRate,
+                UnitMoveType::PitchRate,
+            ] {
+                buf.write_f32(self.speeds.get(mtype));
+            }
+            buf.write_u32(0); // MovementForces.size()
+            buf.write_bit(false); // HasMovementSpline
+            buf.flush_bits();
+        }
+
+        buf.write_u32(0); // PauseTimesCount
+
+        if flags.contains(UpdateFlag::StationaryPosition) {
+            let Position { x, y, z, o } = self.position;
+            buf.write_f32(x);
+            buf.write_f32(y);
+            buf.write_f32(z);
+            buf.write_f32(o);
+        }
+
+        if flags.contains(UpdateFlag::Rotation) {
+            buf.write_i64(self.packed_rotation);
+        }
+    }
+}
+
+/// UpdateData in TC, the update blocks for a single client that are sent as one `SMSG_UPDATE_OBJECT`.
+pub struct UpdateData {
+    map_id:             u16,
+    block_count:        u32,
+    destroy_guids:      BTreeSet<ObjectGuid>,
+    out_of_range_guids: BTreeSet<ObjectGuid>,
+    data:               Vec<u8>,
+}
+
+impl UpdateData {
+    pub fn new(map_id: u16) -> Self {
+        Self {
+            map_id,
+            block_count: 0,
+            destroy_guids: BTreeSet::new(),
+            out_of_range_guids: BTreeSet::new(),
+            data: vec![],
+        }
+    }
+
+    /// UpdateData::AddDestroyObject in TC
+    pub fn add_destroy_object(&mut self, guid: ObjectGuid) {
+        self.destroy_guids.insert(guid);
+    }
+
+    /// UpdateData::AddOutOfRangeGUID in TC
+    pub fn add_out_of_range_guid(&mut self, guid: ObjectGuid) {
+        self.out_of_range_guids.insert(guid);
+    }
+
+    pub fn get_out_of_range_guids(&self) -> &BTreeSet<ObjectGuid> {
+        &self.out_of_range_guids
+    }
+
+    pub fn get_destroy_guids(&self) -> &BTreeSet<ObjectGuid> {
+        &self.destroy_guids
+    }
+
+    /// UpdateData::AddUpdateBlock in TC
+    pub fn add_update_block(&mut self, block: ByteBuffer) {
+        self.data.extend_from_slice(&block.into_bytes());
+        self.block_count += 1;
+    }
+
+    pub fn get_block_count(&self) -> u32 {
+        self.block_count
+    }
+
+    /// UpdateData::HasData in TC
+    pub fn has_data(&self) -> bool {
+        self.block_count > 0 || !self.out_of_range_guids.is_empty() || !self.destroy_guids.is_empty()
+    }
+}
+
+/// UpdateData::BuildPacket in TC
+impl ServerPacket for UpdateData {
+    const OPCODE: OpcodeServer = OpcodeServer::UpdateObject;
+
+    fn write(&self, buf: &mut ByteBuffer) {
+        buf.write_u32(self.block_count);
+        buf.write_u16(self.map_id);
+        if buf.write_bit(!self.out_of_range_guids.is_empty() || !self.destroy_guids.is_empty()) {
+            buf.write_u16(self.destroy_guids.len() as u16);
+            buf.write_u32((self.destroy_guids.len() + self.out_of_range_guids.len()) as u32);
+            for guid in self.destroy_guids.iter().chain(self.out_of_range_guids.iter()) {
+                buf.write_packed_guid(guid);
+            }
+        }
+        buf.write_u32(self.data.len() as u32);
+        buf.write_bytes(&self.data);
+    }
+}
+
+impl UpdateFields {
+    /// Object::BuildCreateUpdateBlockForPlayer in TC
+    ///
+    /// Adds [`UpdateFlag::ThisIsYou`] if the target is the object itself.
+    // TODO: Implement me: CREATE_OBJECT2 for summons and game objects owned by players
+    pub fn build_create_update_block_for_player(&self, movement: &MovementUpdate, target: &ObjectGuid, data: &mut UpdateData) {
+        let guid = self.get_guid();
+        let mut movement = *movement;
+        if guid == *target {
+            movement.flags |= UpdateFlag::ThisIsYou;
+        }
+        let update_type =
+            if guid.is_player() || guid.is_pet() || guid.is_corpse() || guid.is_dynamic_object() || guid.is_area_trigger() || guid.is_conversation() {
+                UpdateType::CreateObject2
+            } else {
+                UpdateType::CreateObject
+            };
+
+        let mut buf = ByteBuffer::with_capacity(0x400);
+        buf.write_u8(update_type as u8);
+        buf.write_packed_guid(&guid);
+        buf.write_u8(type_id_index(self.get_type_id()));
+        movement.build(&guid, &mut buf);
+        self.build_values_update(true, &mut buf, self.visible_flags_for(target));
+        self.build_dynamic_values_update(&mut buf);
+        data.add_update_block(buf);
+    }
+
+    /// Object::BuildValuesUpdateBlockForPlayer in TC
+    pub fn build_values_update_block_for_player(&self, target: &ObjectGuid, data: &mut UpdateData) {
+        let mut buf = ByteBuffer::with_capacity(500);
+        buf.write_u8(UpdateType::Values as u8);
+        buf.write_packed_guid(&self.get_guid());
+        self.build_values_update(false, &mut buf, self.visible_flags_for(target));
+        self.build_dynamic_values_update(&mut buf);
+        data.add_update_block(buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::entities::object::{
+        object_guid::TypeId,
+        update_fields::{tests::player_guid, update_mask_block_count, PLAYER_DYNAMIC_END, PLAYER_END},
+    };
+
+    #[test]
+    fn update_object_packet_layout() {
+        let guid = player_guid(1);
+        let gone = player_guid(2);
+        let fields = UpdateFields::new(guid, TypeId::Player, 0).unwrap();
+        let movement = MovementUpdate {
+            flags:           UpdateFlag::Living.into(),
+            position:        Position::new(1.0, 2.0, 3.0, 0.5),
+            speeds:          MoveSpeed::new(),
+            packed_rotation: 0,
+        };
+        let mut data = UpdateData::new(530);
+        fields.build_create_update_block_for_player(&movement, &guid, &mut data);
+        data.add_out_of_range_guid(gone);
+        assert!(data.has_data());
+
+        let mut buf = ByteBuffer::from(data.to_world_packet().into_data());
+        assert_eq!(buf.read_u32().unwrap(), 1);
+        assert_eq!(buf.read_u16().unwrap(), 530);
+        assert!(buf.read_bit().unwrap());
+        assert_eq!(buf.read_u16().unwrap(), 0);
+        assert_eq!(buf.read_u32().unwrap(), 1);
+        assert_eq!(buf.read_packed_guid().unwrap(), gone);
+        let size = buf.read_u32().unwrap();
+        assert_eq!(size as usize, buf.len());
+
+        // The create block of the player itself
+        assert_eq!(buf.read_u8().unwrap(), UpdateType::CreateObject2 as u8);
+        assert_eq!(buf.read_packed_guid().unwrap(), guid);
+        assert_eq!(buf.read_u8().unwrap(), 4);
+        let bits = buf.read_bits(17).unwrap();
+        // HasMovementUpdate and ThisIsYou
+        assert_eq!(bits, (1 << 13) | (1 << 2));
+        assert_eq!(buf.read_packed_guid().unwrap(), guid);
+        buf.read_u32().unwrap();
+        assert_eq!(
+            [
+                buf.read_f32().unwrap(),
+                buf.read_f32().unwrap(),
+                buf.read_f32().unwrap(),
+                buf.read_f32().unwrap()
+            ],
+            [1.0, 2.0, 3.0, 0.5]
+        );
+        for _ in 0..4 {
+            buf.read_u32().unwrap();
+        }
+        assert_eq!(buf.read_bits(30).unwrap(), 0);
+        assert_eq!(buf.read_bits(18).unwrap(), 0);
+        assert_eq!(buf.read_bits(5).unwrap(), 0);
+        assert_eq!(buf.read_f32().unwrap(), 2.5); // walk speed
+        for _ in 0..8 {
+            buf.read_f32().unwrap();
+        }
+        assert_eq!(buf.read_u32().unwrap(), 0); // MovementForces.size()
+        assert!(!buf.read_bit().unwrap());
+        assert_eq!(buf.read_u32().unwrap(), 0); // PauseTimesCount
+        assert_eq!(usize::from(buf.read_u8().unwrap()), update_mask_block_count(usize::from(PLAYER_END)));
+    }
+
+    #[test]
+    fn dynamic_values_are_sent_as_unchanged() {
+        let fields = UpdateFields::new(player_guid(1), TypeId::Player, 0).unwrap();
+        let mut buf = ByteBuffer::new();
+        fields.build_dynamic_values_update(&mut buf);
+        let mut buf = ByteBuffer::from(buf.into_bytes());
+        let block_count = usize::from(buf.read_u8().unwrap());
+        assert_eq!(block_count, update_mask_block_count(usize::from(PLAYER_DYNAMIC_END)));
+        for _ in 0..block_count {
+            assert_eq!(buf.read_u32().unwrap(), 0);
+        }
+        assert!(buf.is_empty());
+    }
+}
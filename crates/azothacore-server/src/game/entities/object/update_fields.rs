@@ -0,0 +1,605 @@
+//! UpdateFields.h / UpdateFieldFlags.cpp in TC, the update field descriptors of 7.3.5 (26972) along with
+//! the values of the fields of a single object.
+//!
+//! The field indices are absolute, i.e. each object type's fields directly follow those of the type that it
+//! extends, e.g. the unit fields start at [OBJECT_END] and the player fields at [UNIT_END].
+
+use std::sync::LazyLock;
+
+use azothacore_common::{az_error, AzResult};
+use bevy::prelude::Component;
+use flagset::{flags, FlagSet};
+
+use crate::{
+    game::entities::object::object_guid::{ObjectGuid, TypeId},
+    shared::packets::byte_buffer::ByteBuffer,
+};
+
+flags! {
+    /// UpdatefieldFlags in TC
+    pub enum UpdateFieldFlags: u16 {
+        Public         = 0x0001,
+        Private        = 0x0002,
+        Owner          = 0x0004,
+        Unused1        = 0x0008,
+        ItemOwner      = 0x0010,
+        SpecialInfo    = 0x0020,
+        PartyMember    = 0x0040,
+        UnitAll        = 0x0080,
+        Dynamic        = 0x0100,
+        Unused2        = 0x0200,
+        Urgent         = 0x0400,
+        UrgentSelfOnly = 0x0800,
+    }
+}
+
+// The raw values of [UpdateFieldFlags] for the descriptor tables, as casting a flagset variant gives its position instead
+const PUBLIC: u16 = 0x0001;
+const PRIVATE: u16 = 0x0002;
+const OWNER: u16 = 0x0004;
+const SPECIAL_INFO: u16 = 0x0020;
+const PARTY_MEMBER: u16 = 0x0040;
+const UNIT_ALL: u16 = 0x0080;
+const DYNAMIC: u16 = 0x0100;
+const URGENT: u16 = 0x0400;
+const URGENT_SELF_ONLY: u16 = 0x0800;
+
+/// A single (possibly multi-value) update field, i.e. the `Size` and `Flags` comments of the fields in TC's UpdateFields.h
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UpdateFieldDescriptor {
+    pub name:   &'static str,
+    /// Absolute index of the first value of the field
+    pub offset: u16,
+    /// Number of u32 values in the field
+    pub size:   u16,
+    /// The [UpdateFieldFlags] of the field, which determine who gets to see it
+    pub flags:  u16,
+}
+
+macro_rules! update_fields {
+    (
+        $(#[$table_meta:meta])*
+        $table:ident, $base:expr;
+        $( $name:ident = $offset:literal, $size:literal, $flags:expr; )*
+    ) => {
+        $( pub const $name: u16 = $base + $offset; )*
+
+        $(#[$table_meta])*
+        pub const $table: &[UpdateFieldDescriptor] = &[
+            $( UpdateFieldDescriptor { name: stringify!($name), offset: $name, size: $size, flags: $flags }, )*
+        ];
+    };
+}
+
+update_fields! {
+    /// EObjectFields in TC
+    OBJECT_FIELDS, 0;
+    OBJECT_FIELD_GUID = 0x000, 4, PUBLIC;
+    OBJECT_FIELD_DATA = 0x004, 4, PUBLIC;
+    OBJECT_FIELD_TYPE = 0x008, 1, PUBLIC;
+    OBJECT_FIELD_ENTRY = 0x009, 1, DYNAMIC;
+    OBJECT_DYNAMIC_FLAGS = 0x00A, 1, DYNAMIC | URGENT;
+    OBJECT_FIELD_SCALE_X = 0x00B, 1, PUBLIC;
+}
+pub const OBJECT_END: u16 = 0x00C;
+
+update_fields! {
+    /// EUnitFields in TC
+    UNIT_FIELDS, OBJECT_END;
+    UNIT_FIELD_CHARM = 0x000, 4, PUBLIC;
+    UNIT_FIELD_SUMMON = 0x004, 4, PUBLIC;
+    UNIT_FIELD_CRITTER = 0x008, 4, PRIVATE;
+    UNIT_FIELD_CHARMEDBY = 0x00C, 4, PUBLIC;
+    UNIT_FIELD_SUMMONEDBY = 0x010, 4, PUBLIC;
+    UNIT_FIELD_CREATEDBY = 0x014, 4, PUBLIC;
+    UNIT_FIELD_DEMON_CREATOR = 0x018, 4, PUBLIC;
+    UNIT_FIELD_LOOK_AT_CONTROLLER_TARGET = 0x01C, 4, PUBLIC;
+    UNIT_FIELD_TARGET = 0x020, 4, PUBLIC;
+    UNIT_FIELD_BATTLE_PET_COMPANION_GUID = 0x024, 4, PUBLIC;
+    UNIT_FIELD_BATTLE_PET_DB_ID = 0x028, 2, PUBLIC;
+    UNIT_FIELD_CHANNEL_DATA = 0x02A, 2, PUBLIC | URGENT;
+    UNIT_FIELD_SUMMONED_BY_HOME_REALM = 0x02C, 1, PUBLIC;
+    UNIT_FIELD_BYTES_0 = 0x02D, 1, PUBLIC;
+    UNIT_FIELD_DISPLAY_POWER = 0x02E, 1, PUBLIC;
+    UNIT_FIELD_OVERRIDE_DISPLAY_POWER_ID = 0x02F, 1, PUBLIC;
+    UNIT_FIELD_HEALTH = 0x030, 2, PUBLIC;
+    UNIT_FIELD_POWER = 0x032, 6, PUBLIC | URGENT_SELF_ONLY;
+    UNIT_FIELD_MAXHEALTH = 0x038, 2, PUBLIC;
+    UNIT_FIELD_MAXPOWER = 0x03A, 6, PUBLIC;
+    UNIT_FIELD_MOD_POWER_REGEN = 0x040, 6, PRIVATE | OWNER | UNIT_ALL;
+    UNIT_FIELD_LEVEL = 0x046, 1, PUBLIC;
+    UNIT_FIELD_EFFECTIVE_LEVEL = 0x047, 1, PUBLIC;
+    UNIT_FIELD_SANDBOX_SCALING_ID = 0x048, 1, PUBLIC;
+    UNIT_FIELD_SCALING_LEVEL_MIN = 0x049, 1, PUBLIC;
+    UNIT_FIELD_SCALING_LEVEL_MAX = 0x04A, 1, PUBLIC;
+    UNIT_FIELD_SCALING_LEVEL_DELTA = 0x04B, 1, PUBLIC;
+    UNIT_FIELD_FACTIONTEMPLATE = 0x04C, 1, PUBLIC;
+    UNIT_VIRTUAL_ITEM_SLOT_ID = 0x04D, 6, PUBLIC;
+    UNIT_FIELD_FLAGS = 0x053, 1, PUBLIC | URGENT;
+    UNIT_FIELD_FLAGS_2 = 0x054, 1, PUBLIC | URGENT;
+    UNIT_FIELD_FLAGS_3 = 0x055, 1, PUBLIC | URGENT;
+    UNIT_FIELD_AURASTATE = 0x056, 1, PUBLIC;
+    UNIT_FIELD_BASEATTACKTIME = 0x057, 2, PUBLIC;
+    UNIT_FIELD_RANGEDATTACKTIME = 0x059, 1, PRIVATE;
+    UNIT_FIELD_BOUNDINGRADIUS = 0x05A, 1, PUBLIC;
+    UNIT_FIELD_COMBATREACH = 0x05B, 1, PUBLIC;
+    UNIT_FIELD_DISPLAYID = 0x05C, 1, DYNAMIC | URGENT;
+    UNIT_FIELD_DISPLAY_SCALE = 0x05D, 1, DYNAMIC | URGENT;
+    UNIT_FIELD_NATIVEDISPLAYID = 0x05E, 1, PUBLIC | URGENT;
+    UNIT_FIELD_NATIVE_X_DISPLAY_SCALE = 0x05F, 1, PUBLIC | URGENT;
+    UNIT_FIELD_MOUNTDISPLAYID = 0x060, 1, PUBLIC | URGENT;
+    UNIT_FIELD_MINDAMAGE = 0x061, 1, PRIVATE | OWNER | SPECIAL_INFO;
+    UNIT_FIELD_MAXDAMAGE = 0x062, 1, PRIVATE | OWNER | SPECIAL_INFO;
+    UNIT_FIELD_MINOFFHANDDAMAGE = 0x063, 1, PRIVATE | OWNER | SPECIAL_INFO;
+    UNIT_FIELD_MAXOFFHANDDAMAGE = 0x064, 1, PRIVATE | OWNER | SPECIAL_INFO;
+    UNIT_FIELD_BYTES_1 = 0x065, 1, PUBLIC;
+    UNIT_FIELD_PETNUMBER = 0x066, 1, PUBLIC;
+    UNIT_FIELD_PET_NAME_TIMESTAMP = 0x067, 1, PUBLIC;
+    UNIT_FIELD_PETEXPERIENCE = 0x068, 1, OWNER;
+    UNIT_FIELD_PETNEXTLEVELEXP = 0x069, 1, OWNER;
+    UNIT_MOD_CAST_SPEED = 0x06A, 1, PUBLIC;
+    UNIT_MOD_CAST_HASTE = 0x06B, 1, PUBLIC;
+    UNIT_FIELD_MOD_HASTE = 0x06C, 1, PUBLIC;
+    UNIT_FIELD_MOD_RANGED_HASTE = 0x06D, 1, PUBLIC;
+    UNIT_FIELD_MOD_HASTE_REGEN = 0x06E, 1, PUBLIC;
+    UNIT_FIELD_MOD_TIME_RATE = 0x06F, 1, PUBLIC;
+    UNIT_CREATED_BY_SPELL = 0x070, 1, PUBLIC;
+    UNIT_NPC_FLAGS = 0x071, 2, PUBLIC | DYNAMIC;
+    UNIT_NPC_EMOTESTATE = 0x073, 1, PUBLIC;
+    UNIT_FIELD_STAT = 0x074, 4, PRIVATE | OWNER;
+    UNIT_FIELD_POSSTAT = 0x078, 4, PRIVATE | OWNER;
+    UNIT_FIELD_NEGSTAT = 0x07C, 4, PRIVATE | OWNER;
+    UNIT_FIELD_RESISTANCES = 0x080, 7, PRIVATE | OWNER | SPECIAL_INFO;
+    UNIT_FIELD_RESISTANCEBUFFMODSPOSITIVE = 0x087, 7, PRIVATE | OWNER;
+    UNIT_FIELD_RESISTANCEBUFFMODSNEGATIVE = 0x08E, 7, PRIVATE | OWNER;
+    UNIT_FIELD_MOD_BONUS_ARMOR = 0x095, 1, PRIVATE | OWNER;
+    UNIT_FIELD_BASE_MANA = 0x096, 1, PUBLIC;
+    UNIT_FIELD_BASE_HEALTH = 0x097, 1, PRIVATE | OWNER;
+    UNIT_FIELD_BYTES_2 = 0x098, 1, PUBLIC;
+    UNIT_FIELD_ATTACK_POWER = 0x099, 1, PRIVATE | OWNER;
+    UNIT_FIELD_ATTACK_POWER_MOD_POS = 0x09A, 1, PRIVATE | OWNER;
+    UNIT_FIELD_ATTACK_POWER_MOD_NEG = 0x09B, 1, PRIVATE | OWNER;
+    UNIT_FIELD_ATTACK_POWER_MULTIPLIER = 0x09C, 1, PRIVATE | OWNER;
+    UNIT_FIELD_RANGED_ATTACK_POWER = 0x09D, 1, PRIVATE | OWNER;
+    UNIT_FIELD_RANGED_ATTACK_POWER_MOD_POS = 0x09E, 1, PRIVATE | OWNER;
+    UNIT_FIELD_RANGED_ATTACK_POWER_MOD_NEG = 0x09F, 1, PRIVATE | OWNER;
+    UNIT_FIELD_RANGED_ATTACK_POWER_MULTIPLIER = 0x0A0, 1, PRIVATE | OWNER;
+    UNIT_FIELD_SET_ATTACK_SPEED_AURA = 0x0A1, 1, PRIVATE | OWNER;
+    UNIT_FIELD_LIFESTEAL = 0x0A2, 1, PRIVATE | OWNER;
+    UNIT_FIELD_MINRANGEDDAMAGE = 0x0A3, 1, PRIVATE | OWNER;
+    UNIT_FIELD_MAXRANGEDDAMAGE = 0x0A4, 1, PRIVATE | OWNER;
+    UNIT_FIELD_POWER_COST_MODIFIER = 0x0A5, 7, PRIVATE | OWNER;
+    UNIT_FIELD_POWER_COST_MULTIPLIER = 0x0AC, 7, PRIVATE | OWNER;
+    UNIT_FIELD_MAXHEALTHMODIFIER = 0x0B3, 1, PRIVATE | OWNER;
+    UNIT_FIELD_HOVERHEIGHT = 0x0B4, 1, PUBLIC;
+    UNIT_FIELD_MIN_ITEM_LEVEL_CUTOFF = 0x0B5, 1, PUBLIC;
+    UNIT_FIELD_MIN_ITEM_LEVEL = 0x0B6, 1, PUBLIC;
+    UNIT_FIELD_MAXITEMLEVEL = 0x0B7, 1, PUBLIC;
+    UNIT_FIELD_WILD_BATTLEPET_LEVEL = 0x0B8, 1, PUBLIC;
+    UNIT_FIELD_BATTLEPET_COMPANION_NAME_TIMESTAMP = 0x0B9, 1, PUBLIC;
+    UNIT_FIELD_INTERACT_SPELLID = 0x0BA, 1, PUBLIC;
+    UNIT_FIELD_STATE_SPELL_VISUAL_ID = 0x0BB, 1, DYNAMIC | URGENT;
+    UNIT_FIELD_STATE_ANIM_ID = 0x0BC, 1, DYNAMIC | URGENT;
+    UNIT_FIELD_STATE_ANIM_KIT_ID = 0x0BD, 1, DYNAMIC | URGENT;
+    UNIT_FIELD_STATE_WORLD_EFFECT_ID = 0x0BE, 4, DYNAMIC | URGENT;
+    UNIT_FIELD_SCALE_DURATION = 0x0C2, 1, PUBLIC;
+    UNIT_FIELD_LOOKS_LIKE_MOUNT_ID = 0x0C3, 1, PUBLIC;
+    UNIT_FIELD_LOOKS_LIKE_CREATURE_ID = 0x0C4, 1, PUBLIC;
+    UNIT_FIELD_LOOK_AT_CONTROLLER_ID = 0x0C5, 1, PUBLIC;
+    UNIT_FIELD_GUILD_GUID = 0x0C6, 4, PUBLIC;
+}
+pub const UNIT_END: u16 = OBJECT_END + 0x0CA;
+
+update_fields! {
+    /// EPlayerFields in TC
+    ///
+    // TODO: Implement me: The private (i.e. active player) fields following PLAYER_FIELD_NEXT_LEVEL_XP are only described
+    // as a single block until they are needed.
+    PLAYER_FIELDS, UNIT_END;
+    PLAYER_DUEL_ARBITER = 0x000, 4, PUBLIC;
+    PLAYER_WOW_ACCOUNT = 0x004, 4, PUBLIC;
+    PLAYER_LOOT_TARGET_GUID = 0x008, 4, PUBLIC;
+    PLAYER_FLAGS = 0x00C, 1, PUBLIC;
+    PLAYER_FLAGS_EX = 0x00D, 1, PUBLIC;
+    PLAYER_GUILDRANK = 0x00E, 1, PUBLIC;
+    PLAYER_GUILDDELETE_DATE = 0x00F, 1, PUBLIC;
+    PLAYER_GUILDLEVEL = 0x010, 1, PUBLIC;
+    PLAYER_BYTES = 0x011, 1, PUBLIC;
+    PLAYER_BYTES_2 = 0x012, 1, PUBLIC;
+    PLAYER_BYTES_3 = 0x013, 1, PUBLIC;
+    PLAYER_BYTES_4 = 0x014, 1, PUBLIC;
+    PLAYER_DUEL_TEAM = 0x015, 1, PUBLIC;
+    PLAYER_GUILD_TIMESTAMP = 0x016, 1, PUBLIC;
+    PLAYER_QUEST_LOG = 0x017, 800, PARTY_MEMBER;
+    PLAYER_VISIBLE_ITEM = 0x337, 38, PUBLIC;
+    PLAYER_CHOSEN_TITLE = 0x35D, 1, PUBLIC;
+    PLAYER_FAKE_INEBRIATION = 0x35E, 1, PUBLIC;
+    PLAYER_FIELD_VIRTUAL_PLAYER_REALM = 0x35F, 1, PUBLIC;
+    PLAYER_FIELD_CURRENT_SPEC_ID = 0x360, 1, PUBLIC;
+    PLAYER_FIELD_TAXI_MOUNT_ANIM_KIT_ID = 0x361, 1, PUBLIC;
+    PLAYER_FIELD_AVG_ITEM_LEVEL = 0x362, 4, PUBLIC;
+    PLAYER_FIELD_CURRENT_BATTLE_PET_BREED_QUALITY = 0x366, 1, PUBLIC;
+    PLAYER_FIELD_PRESTIGE = 0x367, 1, PUBLIC;
+    PLAYER_FIELD_HONOR_LEVEL = 0x368, 1, PUBLIC;
+    PLAYER_FIELD_INV_SLOT_HEAD = 0x369, 780, PRIVATE;
+    PLAYER_FARSIGHT = 0x675, 4, PRIVATE;
+    PLAYER_FIELD_SUMMONED_BATTLE_PET_ID = 0x679, 4, PRIVATE;
+    PLAYER__FIELD_KNOWN_TITLES = 0x67D, 12, PRIVATE;
+    PLAYER_FIELD_COINAGE = 0x689, 2, PRIVATE;
+    PLAYER_XP = 0x68B, 1, PRIVATE;
+    PLAYER_NEXT_LEVEL_XP = 0x68C, 1, PRIVATE;
+    PLAYER_FIELD_ACTIVE_PLAYER_DATA = 0x68D, 2747, PRIVATE;
+}
+pub const PLAYER_END: u16 = UNIT_END + 0x1148;
+
+update_fields! {
+    /// EGameObjectFields in TC
+    GAMEOBJECT_FIELDS, OBJECT_END;
+    GAMEOBJECT_FIELD_CREATED_BY = 0x000, 4, PUBLIC;
+    GAMEOBJECT_DISPLAYID = 0x004, 1, DYNAMIC | URGENT;
+    GAMEOBJECT_FLAGS = 0x005, 1, PUBLIC | URGENT;
+    GAMEOBJECT_PARENTROTATION = 0x006, 4, PUBLIC;
+    GAMEOBJECT_FACTION = 0x00A, 1, PUBLIC;
+    GAMEOBJECT_LEVEL = 0x00B, 1, PUBLIC;
+    GAMEOBJECT_BYTES_1 = 0x00C, 1, PUBLIC | URGENT;
+    GAMEOBJECT_SPELL_VISUAL_ID = 0x00D, 1, PUBLIC | DYNAMIC | URGENT;
+    GAMEOBJECT_STATE_SPELL_VISUAL_ID = 0x00E, 1, DYNAMIC | URGENT;
+    GAMEOBJECT_STATE_ANIM_ID = 0x00F, 1, DYNAMIC | URGENT;
+    GAMEOBJECT_STATE_ANIM_KIT_ID = 0x010, 1, DYNAMIC | URGENT;
+    GAMEOBJECT_STATE_WORLD_EFFECT_ID = 0x011, 4, DYNAMIC | URGENT;
+}
+pub const GAMEOBJECT_END: u16 = OBJECT_END + 0x015;
+
+/// OBJECT_DYNAMIC_END in TC
+pub const OBJECT_DYNAMIC_END: u16 = 0x000;
+/// UNIT_DYNAMIC_END in TC, i.e. passive spells, world effects and channel objects
+pub const UNIT_DYNAMIC_END: u16 = OBJECT_DYNAMIC_END + 0x003;
+/// PLAYER_DYNAMIC_END in TC
+pub const PLAYER_DYNAMIC_END: u16 = UNIT_DYNAMIC_END + 0x00E;
+/// GAMEOBJECT_DYNAMIC_END in TC, i.e. the enabled doodad sets
+pub const GAMEOBJECT_DYNAMIC_END: u16 = OBJECT_DYNAMIC_END + 0x001;
+
+/// UNIT_DYNFLAG_SPECIALINFO in TC
+pub const UNIT_DYNFLAG_SPECIALINFO: u32 = 0x0010;
+
+/// The flags of each update field of an object type, indexed by the field's index, i.e. ObjectUpdateFieldFlags / UnitUpdateFieldFlags /
+/// GameObjectUpdateFieldFlags in TC
+fn field_flags_for(tables: &[&[UpdateFieldDescriptor]], end: u16) -> Vec<u16> {
+    let mut flags = vec![0; usize::from(end)];
+    for f in tables.iter().copied().flatten() {
+        let start = usize::from(f.offset);
+        flags[start..start + usize::from(f.size)].fill(f.flags);
+    }
+    flags
+}
+
+static OBJECT_UPDATE_FIELD_FLAGS: LazyLock<Vec<u16>> = LazyLock::new(|| field_flags_for(&[OBJECT_FIELDS], OBJECT_END));
+static UNIT_UPDATE_FIELD_FLAGS: LazyLock<Vec<u16>> = LazyLock::new(|| field_flags_for(&[OBJECT_FIELDS, UNIT_FIELDS], UNIT_END));
+static PLAYER_UPDATE_FIELD_FLAGS: LazyLock<Vec<u16>> = LazyLock::new(|| field_flags_for(&[OBJECT_FIELDS, UNIT_FIELDS, PLAYER_FIELDS], PLAYER_END));
+static GAMEOBJECT_UPDATE_FIELD_FLAGS: LazyLock<Vec<u16>> = LazyLock::new(|| field_flags_for(&[OBJECT_FIELDS, GAMEOBJECT_FIELDS], GAMEOBJECT_END));
+
+/// UpdateMask::GetBlockCount in TC
+pub const fn update_mask_block_count(values_count: usize) -> usize {
+    values_count.div_ceil(32)
+}
+
+/// Object::m_objectTypeId in TC, i.e. the TypeID that is sent to the client, which is the index of the bit in [TypeId]
+pub fn type_id_index(type_id: TypeId) -> u8 {
+    FlagSet::from(type_id).bits().trailing_zeros() as u8
+}
+
+/// Object::m_objectType in TC, i.e. the type mask of the object along with all the types that it extends
+pub fn type_mask(type_id: TypeId) -> FlagSet<TypeId> {
+    match type_id {
+        TypeId::Player => TypeId::Object | TypeId::Unit | TypeId::Player,
+        TypeId::Container => TypeId::Object | TypeId::Item | TypeId::Container,
+        t => TypeId::Object | t,
+    }
+}
+
+/// The update fields of a single object, i.e. Object::m_uint32Values along with the changes that have yet to be sent to
+/// clients (Object::_changesMask) in TC
+///
+/// Only objects, units, players and game objects are supported for now.
+// TODO: Implement me: Dynamic update fields, i.e. Object::_dynamicValues in TC. These are always sent as unchanged for now.
+#[derive(Component, Debug, Clone)]
+pub struct UpdateFields {
+    guid:                 ObjectGuid,
+    type_id:              TypeId,
+    values:               Vec<u32>,
+    changes:              Vec<bool>,
+    dynamic_values_count: u16,
+}
+
+impl UpdateFields {
+    /// Object::_Create in TC, sets up the fields for an object of the given type with the object fields filled in.
+    ///
+    /// Fails if the type ID is not supported.
+    pub fn new(guid: ObjectGuid, type_id: TypeId, entry: u32) -> AzResult<Self> {
+        let (values_count, dynamic_values_count) = match type_id {
+            TypeId::Object => (OBJECT_END, OBJECT_DYNAMIC_END),
+            TypeId::Unit => (UNIT_END, UNIT_DYNAMIC_END),
+            TypeId::Player => (PLAYER_END, PLAYER_DYNAMIC_END),
+            TypeId::Gameobject => (GAMEOBJECT_END, GAMEOBJECT_DYNAMIC_END),
+            t => return Err(az_error!("update fields of type {t} are not supported yet")),
+        };
+        let mut fields = Self {
+            guid,
+            type_id,
+            values: vec![0; usize::from(values_count)],
+            changes: vec![false; usize::from(values_count)],
+            dynamic_values_count,
+        };
+        fields.set_guid_value(OBJECT_FIELD_GUID, &guid);
+        fields.set_u32_value(OBJECT_FIELD_TYPE, u32::from(type_mask(type_id).bits()));
+        fields.set_u32_value(OBJECT_FIELD_ENTRY, entry);
+        fields.set_f32_value(OBJECT_FIELD_SCALE_X, 1.0);
+        Ok(fields)
+    }
+
+    pub fn get_guid(&self) -> ObjectGuid {
+        self.guid
+    }
+
+    pub fn get_type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// Object::m_valuesCount in TC
+    pub fn values_count(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Object::GetUInt32Value in TC
+    pub fn get_u32_value(&self, index: u16) -> u32 {
+        self.values[usize::from(index)]
+    }
+
+    /// Object::GetFloatValue in TC
+    pub fn get_f32_value(&self, index: u16) -> f32 {
+        f32::from_bits(self.get_u32_value(index))
+    }
+
+    /// Object::GetUInt64Value in TC
+    pub fn get_u64_value(&self, index: u16) -> u64 {
+        u64::from(self.get_u32_value(index)) | (u64::from(self.get_u32_value(index + 1)) << 32)
+    }
+
+    /// Object::GetByteValue in TC
+    pub fn get_byte_value(&self, index: u16, offset: u8) -> u8 {
+        assert!(offset < 4);
+        (self.get_u32_value(index) >> (offset * 8)) as u8
+    }
+
+    /// Object::GetGuidValue in TC, i.e. the low part is stored before the high part.
+    pub fn get_guid_value(&self, index: u16) -> [u64; 2] {
+        [self.get_u64_value(index), self.get_u64_value(index + 2)]
+    }
+
+    /// Object::SetUInt32Value in TC, the field is only marked as changed if the value is different
+    pub fn set_u32_value(&mut self, index: u16, value: u32) {
+        let i = usize::from(index);
+        if self.values[i] != value {
+            self.values[i] = value;
+            self.changes[i] = true;
+        }
+    }
+
+    /// Object::SetFloatValue in TC
+    pub fn set_f32_value(&mut self, index: u16, value: f32) {
+        self.set_u32_value(index, value.to_bits());
+    }
+
+    /// Object::SetUInt64Value in TC
+    pub fn set_u64_value(&mut self, index: u16, value: u64) {
+        self.set_u32_value(index, value as u32);
+        self.set_u32_value(index + 1, (value >> 32) as u32);
+    }
+
+    /// Object::SetByteValue in TC
+    pub fn set_byte_value(&mut self, index: u16, offset: u8, value: u8) {
+        assert!(offset < 4);
+        let shift = offset * 8;
+        let old = self.get_u32_value(index);
+        self.set_u32_value(index, (old & !(0xFF << shift)) | (u32::from(value) << shift));
+    }
+
+    /// Object::SetGuidValue in TC
+    pub fn set_guid_value<H>(&mut self, index: u16, guid: &ObjectGuid<H>) {
+        let [low, high] = guid.raw_parts();
+        self.set_u64_value(index, low);
+        self.set_u64_value(index + 2, high);
+    }
+
+    /// Object::HasFlag in TC
+    pub fn has_flag(&self, index: u16, flag: u32) -> bool {
+        self.get_u32_value(index) & flag != 0
+    }
+
+    /// Object::SetFlag in TC
+    pub fn set_flag(&mut self, index: u16, flag: u32) {
+        self.set_u32_value(index, self.get_u32_value(index) | flag);
+    }
+
+    /// Object::RemoveFlag in TC
+    pub fn remove_flag(&mut self, index: u16, flag: u32) {
+        self.set_u32_value(index, self.get_u32_value(index) & !flag);
+    }
+
+    /// Whether the field at the given index has changed since the changes were last cleared
+    pub fn is_changed(&self, index: u16) -> bool {
+        self.changes[usize::from(index)]
+    }
+
+    /// Whether any field has changed since the changes were last cleared, i.e. whether the object would be in
+    /// Map::_updateObjects in TC
+    pub fn has_changes(&self) -> bool {
+        self.changes.iter().any(|c| *c)
+    }
+
+    /// Object::ClearUpdateMask in TC, called once the changes have been sent to all players that can see the object
+    pub fn clear_changes(&mut self) {
+        self.changes.fill(false);
+    }
+
+    /// The flags of each field, indexed by the field's index
+    fn field_flags(&self) -> &'static [u16] {
+        match self.type_id {
+            TypeId::Unit => &UNIT_UPDATE_FIELD_FLAGS,
+            TypeId::Player => &PLAYER_UPDATE_FIELD_FLAGS,
+            TypeId::Gameobject => &GAMEOBJECT_UPDATE_FIELD_FLAGS,
+            _ => &OBJECT_UPDATE_FIELD_FLAGS,
+        }
+    }
+
+    /// Object::GetUpdateFieldData in TC, the [UpdateFieldFlags] of the fields that the target is allowed to see
+    // TODO: Implement me: UF_FLAG_SPECIAL_INFO (via SPELL_AURA_EMPATHY) and UF_FLAG_PARTY_MEMBER for members of the same group / raid
+    pub fn visible_flags_for(&self, target: &ObjectGuid) -> FlagSet<UpdateFieldFlags> {
+        let mut visible = FlagSet::from(UpdateFieldFlags::Public);
+        let is_self = self.guid == *target;
+        if is_self {
+            visible |= UpdateFieldFlags::Private;
+        }
+        match self.type_id {
+            TypeId::Unit | TypeId::Player => {
+                if !target.is_empty() && self.get_guid_value(UNIT_FIELD_SUMMONEDBY) == target.raw_parts() {
+                    visible |= UpdateFieldFlags::Owner;
+                }
+                // Players are always in the same raid as themselves
+                if self.type_id == TypeId::Player && is_self {
+                    visible |= UpdateFieldFlags::PartyMember;
+                }
+            },
+            TypeId::Gameobject => {
+                if !target.is_empty() && self.get_guid_value(GAMEOBJECT_FIELD_CREATED_BY) == target.raw_parts() {
+                    visible |= UpdateFieldFlags::Owner;
+                }
+            },
+            _ => {},
+        }
+        visible
+    }
+
+    /// Object::BuildValuesUpdate in TC
+    ///
+    /// For creation, all non-zero fields that are visible are sent, otherwise only the changed ones. Fields flagged
+    /// with [`UpdateFieldFlags::Dynamic`] are always sent.
+    pub fn build_values_update(&self, is_create: bool, buf: &mut ByteBuffer, visible: FlagSet<UpdateFieldFlags>) {
+        let flags = self.field_flags();
+        let mut mask = vec![0u32; update_mask_block_count(self.values.len())];
+        let mut values = Vec::new();
+        for (index, value) in self.values.iter().enumerate() {
+            let send = if is_create { *value != 0 } else { self.changes[index] };
+            if flags[index] & DYNAMIC != 0 || (send && flags[index] & visible.bits() != 0) {
+                mask[index / 32] |= 1 << (index % 32);
+                values.push(*value);
+            }
+        }
+        buf.write_u8(mask.len() as u8);
+        for block in mask {
+            buf.write_u32(block);
+        }
+        for value in values {
+            buf.write_u32(value);
+        }
+    }
+
+    /// Object::BuildDynamicValuesUpdate in TC, with no dynamic field ever set
+    pub fn build_dynamic_values_update(&self, buf: &mut ByteBuffer) {
+        let block_count = update_mask_block_count(usize::from(self.dynamic_values_count));
+        buf.write_u8(block_count as u8);
+        for _ in 0..block_count {
+            buf.write_u32(0);
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use flagset::FlagSet;
+
+    use super::*;
+    use crate::game::entities::object::object_guid::{HighGuidPlayer, HighGuidTrait};
+
+    /// The guid of the player with the given counter, as the tests of the objects' updates use it
+    pub(crate) fn player_guid(counter: u64) -> ObjectGuid {
+        let mut raw = [0u8; 16];
+        raw[..8].copy_from_slice(&counter.to_le_bytes());
+        raw[8..].copy_from_slice(&(u64::from(HighGuidPlayer::ID) << 58).to_le_bytes());
+        ObjectGuid::try_from(&raw[..]).unwrap()
+    }
+
+    #[test]
+    fn descriptors_are_contiguous() {
+        for (table, start, end) in [
+            (OBJECT_FIELDS, 0, OBJECT_END),
+            (UNIT_FIELDS, OBJECT_END, UNIT_END),
+            (PLAYER_FIELDS, UNIT_END, PLAYER_END),
+            (GAMEOBJECT_FIELDS, OBJECT_END, GAMEOBJECT_END),
+        ] {
+            let mut next = start;
+            for f in table {
+                assert_eq!(f.offset, next, "field {} is not right after the previous one", f.name);
+                next += f.size;
+            }
+            assert_eq!(
+                next,
+                end,
+                "fields ending with {} do not end at the end of the table",
+                table.last().unwrap().name
+            );
+        }
+    }
+
+    /// The indices of the fields sent in a values update, checking that a value is sent for each of them
+    fn changed_fields(fields: &UpdateFields, visible: FlagSet<UpdateFieldFlags>) -> Vec<usize> {
+        let mut buf = ByteBuffer::new();
+        fields.build_values_update(false, &mut buf, visible);
+        let mut buf = ByteBuffer::from(buf.into_bytes());
+        let block_count = buf.read_u8().unwrap();
+        assert_eq!(usize::from(block_count), update_mask_block_count(fields.values_count()));
+        let mut set = vec![];
+        for block in 0..usize::from(block_count) {
+            let mask = buf.read_u32().unwrap();
+            set.extend((0..32).filter(|b| mask & (1 << b) != 0).map(|b| block * 32 + b));
+        }
+        assert_eq!(buf.len(), set.len() * 4);
+        set
+    }
+
+    #[test]
+    fn values_update_only_sends_changed_visible_fields() {
+        let guid = player_guid(42);
+        let other = player_guid(43);
+        let mut fields = UpdateFields::new(guid, TypeId::Player, 0).unwrap();
+        fields.clear_changes();
+        assert!(!fields.has_changes());
+
+        fields.set_u32_value(UNIT_FIELD_LEVEL, 10);
+        fields.set_u32_value(PLAYER_XP, 100);
+        // Setting the same value again is not a change
+        fields.set_f32_value(OBJECT_FIELD_SCALE_X, 1.0);
+        assert!(fields.is_changed(UNIT_FIELD_LEVEL) && fields.is_changed(PLAYER_XP));
+        assert!(!fields.is_changed(OBJECT_FIELD_SCALE_X));
+
+        let visible = fields.visible_flags_for(&other);
+        assert_eq!(visible, FlagSet::from(UpdateFieldFlags::Public));
+        let set = changed_fields(&fields, visible);
+        // The always sent dynamic fields, along with the level but not the private XP field
+        assert!(set.contains(&usize::from(UNIT_FIELD_LEVEL)));
+        assert!(set.contains(&usize::from(OBJECT_FIELD_ENTRY)));
+        assert!(!set.contains(&usize::from(PLAYER_XP)));
+
+        let visible = fields.visible_flags_for(&guid);
+        assert!(visible.contains(UpdateFieldFlags::Private));
+        let set = changed_fields(&fields, visible);
+        assert!(set.contains(&usize::from(UNIT_FIELD_LEVEL)));
+        assert!(set.contains(&usize::from(PLAYER_XP)));
+    }
+
+    #[test]
+    fn it_rejects_unsupported_types() {
+        assert!(UpdateFields::new(player_guid(1), TypeId::Unit, 0).is_ok());
+        assert!(UpdateFields::new(player_guid(1), TypeId::Item, 0).is_err());
+    }
+}
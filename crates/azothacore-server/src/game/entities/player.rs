@@ -1 +1,86 @@
+use flagset::flags;
+
 pub const MAX_MONEY_AMOUNT: u32 = i32::MAX as _;
+
+flags! {
+    /// PlayerFlags in TC / AC
+    pub enum PlayerFlags: u32 {
+        GroupLeader             = 0x00000001,
+        Afk                     = 0x00000002,
+        Dnd                     = 0x00000004,
+        Gm                      = 0x00000008,
+        Ghost                   = 0x00000010,
+        Resting                 = 0x00000020,
+        Unk6                    = 0x00000040,
+        Unk7                    = 0x00000080,
+        ContestedPvp            = 0x00000100,
+        InPvp                   = 0x00000200,
+        HideHelm                = 0x00000400,
+        HideCloak               = 0x00000800,
+        PlayedLongTime          = 0x00001000,
+        PlayedTooLong           = 0x00002000,
+        IsOutOfBounds           = 0x00004000,
+        Developer               = 0x00008000,
+        Unk16                   = 0x00010000,
+        TaxiBenchmark           = 0x00020000,
+        PvpTimer                = 0x00040000,
+        Uber                    = 0x00080000,
+        Unk20                   = 0x00100000,
+        Unk21                   = 0x00200000,
+        Commentator2            = 0x00400000,
+        HideAccountAchievements = 0x00800000,
+        PetBattlesUnlocked      = 0x01000000,
+        NoXpGain                = 0x02000000,
+        Unk26                   = 0x04000000,
+        AutoDeclineGuild        = 0x08000000,
+        GuildLevelEnabled       = 0x10000000,
+        VoidUnlocked            = 0x20000000,
+        Mentor                  = 0x40000000,
+        Unk31                   = 0x80000000,
+    }
+}
+
+flags! {
+    /// AtLoginFlags in TC / AC, stored in `characters.at_login`
+    pub enum AtLoginFlags: u16 {
+        Rename          = 0x001,
+        ResetSpells     = 0x002,
+        ResetTalents    = 0x004,
+        Customize       = 0x008,
+        ResetPetTalents = 0x010,
+        First           = 0x020,
+        ChangeFaction   = 0x040,
+        ChangeRace      = 0x080,
+        Resurrect       = 0x100,
+    }
+}
+
+/// Equipment slots, EquipmentSlots in TC / AC
+pub const EQUIPMENT_SLOT_HEAD: u8 = 0;
+pub const EQUIPMENT_SLOT_NECK: u8 = 1;
+pub const EQUIPMENT_SLOT_SHOULDERS: u8 = 2;
+pub const EQUIPMENT_SLOT_BODY: u8 = 3;
+pub const EQUIPMENT_SLOT_CHEST: u8 = 4;
+pub const EQUIPMENT_SLOT_WAIST: u8 = 5;
+pub const EQUIPMENT_SLOT_LEGS: u8 = 6;
+pub const EQUIPMENT_SLOT_FEET: u8 = 7;
+pub const EQUIPMENT_SLOT_WRISTS: u8 = 8;
+pub const EQUIPMENT_SLOT_HANDS: u8 = 9;
+pub const EQUIPMENT_SLOT_FINGER1: u8 = 10;
+pub const EQUIPMENT_SLOT_FINGER2: u8 = 11;
+pub const EQUIPMENT_SLOT_TRINKET1: u8 = 12;
+pub const EQUIPMENT_SLOT_TRINKET2: u8 = 13;
+pub const EQUIPMENT_SLOT_BACK: u8 = 14;
+pub const EQUIPMENT_SLOT_MAINHAND: u8 = 15;
+pub const EQUIPMENT_SLOT_OFFHAND: u8 = 16;
+pub const EQUIPMENT_SLOT_RANGED: u8 = 17;
+pub const EQUIPMENT_SLOT_TABARD: u8 = 18;
+pub const EQUIPMENT_SLOT_END: u8 = 19;
+
+/// InventorySlots in TC / AC, the equipped bags
+pub const INVENTORY_SLOT_BAG_START: u8 = 19;
+pub const INVENTORY_SLOT_BAG_END: u8 = 23;
+
+/// InventoryPackSlots in TC / AC, the backpack
+pub const INVENTORY_SLOT_ITEM_START: u8 = 23;
+pub const INVENTORY_SLOT_ITEM_END: u8 = 39;
@@ -2,6 +2,20 @@ use std::ops;
 
 use bevy::prelude::Resource;
 
+/// UnitMoveType in TC / AC
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnitMoveType {
+    Walk,
+    Run,
+    RunBack,
+    Swim,
+    SwimBack,
+    TurnRate,
+    Flight,
+    FlightBack,
+    PitchRate,
+}
+
 #[derive(Clone, Copy)]
 pub struct MoveSpeed {
     walk:        f32,
@@ -30,6 +44,21 @@ impl MoveSpeed {
             pitch_rate: 3.14,
         }
     }
+
+    /// Unit::GetSpeed in TC / AC
+    pub fn get(&self, mtype: UnitMoveType) -> f32 {
+        match mtype {
+            UnitMoveType::Walk => self.walk,
+            UnitMoveType::Run => self.run,
+            UnitMoveType::RunBack => self.run_back,
+            UnitMoveType::Swim => self.swim,
+            UnitMoveType::SwimBack => self.swim_back,
+            UnitMoveType::TurnRate => self.turn_rate,
+            UnitMoveType::Flight => self.flight,
+            UnitMoveType::FlightBack => self.flight_back,
+            UnitMoveType::PitchRate => self.pitch_rate,
+        }
+    }
 }
 
 impl Default for MoveSpeed {
@@ -1,5 +1,5 @@
 use std::{
-    collections::BTreeSet,
+    collections::{BTreeMap, BTreeSet},
     sync::atomic::{AtomicU32, AtomicU64},
     time::Instant,
 };
@@ -7,14 +7,17 @@ use std::{
 use azothacore_common::{
     bevy_app::{AzStartupFailedEvent, TokioRuntime},
     deref_boilerplate,
+    utils::{is_basic_latin_character, is_cyrillic_character, is_east_asian_character, is_extended_latin_character, is_string_of},
     AzContext,
     AzResult,
+    Locale,
 };
 use azothacore_database::{
     args,
     database_env::{CharacterDatabase, WorldDatabase},
 };
 use bevy::prelude::{Commands, EventWriter, In, Res, Resource};
+use flagset::{flags, FlagSet};
 use sqlx::{query_as, query_with};
 use tracing::{error, info, warn};
 
@@ -23,8 +26,13 @@ use crate::{
         battlegrounds::ArenaTeamIDGenerator,
         entities::object::object_guid::{HighGuidItem, HighGuidPlayer, HighGuidTransport, ObjectGuidLowGenerator},
         guilds::GuildIDGenerator,
+        world::{RealmZone, StrictName, WorldConfig},
+    },
+    shared::{
+        data_stores::{validate_name, NameReservedRegexContainer, NameValidationRegexContainer},
+        id_generators::{DBIDGenerator, IDGenerator, IDGeneratorTrait},
+        shared_defines::ResponseCodes,
     },
-    shared::id_generators::{DBIDGenerator, IDGenerator, IDGeneratorTrait},
 };
 
 /// max allowed by client name length
@@ -121,9 +129,10 @@ pub fn set_highest_guids(mut commands: Commands, char_db: Res<CharacterDatabase>
 
     let guid_gen = ObjectGuidLowGenerator::<HighGuidItem>::new_db_generator(&*char_db, &rt).with_context(|| "error retrieving max item guid")?;
     let next_item_guid = guid_gen.next_after_max_used();
+    // Cleanup other tables from nonexistent guids ( >= _hiItemGuid), this has to finish before the item
+    // guid generator can be used, otherwise items created from it could be removed right away
     rt.block_on(async {
-        // Cleanup other tables from nonexistent guids ( >= _hiItemGuid)
-        // all one time queries
+        let mut txn = char_db.begin().await?;
         for q in [
             "DELETE FROM character_inventory WHERE item >= ?",
             "DELETE FROM mail_items WHERE item_guid >= ?",
@@ -131,10 +140,11 @@ pub fn set_highest_guids(mut commands: Commands, char_db: Res<CharacterDatabase>
             "DELETE FROM guild_bank_item WHERE item_guid >= ?",
         ] {
             query_with(q, args!(next_item_guid)?)
-                .execute(&**char_db)
+                .execute(&mut *txn)
                 .await
                 .with_context(|| format!("error clearing item guids for statement: '{q}' for item_guids above {next_item_guid}"))?;
         }
+        txn.commit().await?;
         AzResult::Ok(())
     })?;
     commands.insert_resource(guid_gen);
@@ -226,3 +236,247 @@ pub fn load_script_names(
     info!(target:"server.loading", ">> Loaded {} ScriptNames in {} ms", name_store.len(), elapsed.as_millis());
     commands.insert_resource(name_store);
 }
+
+/// PlayerInfo in TC / AC, only the create position for now
+#[derive(Debug, Clone)]
+pub struct PlayerInfo {
+    pub map_id:      u16,
+    pub zone_id:     u32,
+    pub position_x:  f32,
+    pub position_y:  f32,
+    pub position_z:  f32,
+    pub orientation: f32,
+}
+
+/// equivalent to ObjectMgr::_playerInfo in TC / AC, keyed by race and class
+#[derive(Resource, Default)]
+pub struct PlayerInfoContainer(BTreeMap<(u8, u8), PlayerInfo>);
+
+deref_boilerplate!(PlayerInfoContainer, BTreeMap<(u8, u8), PlayerInfo>, 0);
+
+/// equivalent to ObjectMgr::LoadPlayerInfo() in TC / AC
+///
+/// TODO: Implement me: only the create positions from `playercreateinfo` are loaded, items, spells, actions and level stats still need loading
+pub fn load_player_info(mut commands: Commands, rt: Res<TokioRuntime>, world_db: Res<WorldDatabase>, mut ev_startup_failed: EventWriter<AzStartupFailedEvent>) {
+    info!(target:"server::loading", "Loading Player Create Info Data...");
+    let old_ms_time = Instant::now();
+    let stmt = query_as::<_, (u8, u8, u16, u32, f32, f32, f32, f32)>(
+        "SELECT race, class, map, zone, position_x, position_y, position_z, orientation FROM playercreateinfo",
+    );
+    let res = match rt.block_on(stmt.fetch_all(&**world_db)) {
+        Err(e) => {
+            ev_startup_failed.send_default();
+            error!(target:"server::loading", cause=?e, "error retrieving player create info");
+            return;
+        },
+        Ok(r) => r,
+    };
+    if res.is_empty() {
+        error!(target:"server::loading", ">> Loaded 0 player create definitions. DB table `playercreateinfo` is empty.");
+        ev_startup_failed.send_default();
+        return;
+    }
+    let mut m = PlayerInfoContainer::default();
+    m.extend(
+        res.into_iter()
+            .map(|(race, class, map_id, zone_id, position_x, position_y, position_z, orientation)| {
+                let info = PlayerInfo {
+                    map_id,
+                    zone_id,
+                    position_x,
+                    position_y,
+                    position_z,
+                    orientation,
+                };
+                ((race, class), info)
+            }),
+    );
+    let elapsed = Instant::now() - old_ms_time;
+    info!(target:"server::loading", ">> Loaded {} player create definitions in {} ms", m.len(), elapsed.as_millis());
+    commands.insert_resource(m);
+}
+
+/// equivalent to ObjectMgr::_reservedNamesStore in TC / AC, stores the names lowercased
+#[derive(Resource, Default)]
+pub struct ReservedNamesContainer(BTreeSet<String>);
+
+deref_boilerplate!(ReservedNamesContainer, BTreeSet<String>, 0);
+
+impl ReservedNamesContainer {
+    /// ObjectMgr::IsReservedName in TC / AC
+    pub fn is_reserved_name(&self, name: &str) -> bool {
+        self.contains(&name.to_lowercase())
+    }
+}
+
+/// equivalent to ObjectMgr::LoadReservedPlayersNames() in TC / AC
+pub fn load_reserved_players_names(
+    mut commands: Commands,
+    rt: Res<TokioRuntime>,
+    char_db: Res<CharacterDatabase>,
+    mut ev_startup_failed: EventWriter<AzStartupFailedEvent>,
+) {
+    info!(target:"server::loading", "Loading Reserved Player Names...");
+    let old_ms_time = Instant::now();
+    let mut store = ReservedNamesContainer::default();
+    let res = match rt.block_on(query_as::<_, (String,)>("SELECT name FROM reserved_name").fetch_all(&**char_db)) {
+        Err(e) => {
+            ev_startup_failed.send_default();
+            error!(target:"server::loading", cause=?e, "error retrieving reserved player names");
+            return;
+        },
+        Ok(r) => r,
+    };
+    for (name,) in res {
+        if normalize_player_name(&name).is_none() {
+            error!(target:"sql.sql", "Table `reserved_name` has invalid name: {name}");
+            continue;
+        }
+        store.insert(name.to_lowercase());
+    }
+    let elapsed = Instant::now() - old_ms_time;
+    info!(target:"server::loading", ">> Loaded {} reserved player names in {} ms", store.len(), elapsed.as_millis());
+    commands.insert_resource(store);
+}
+
+/// normalizePlayerName in TC / AC, lowercases the name except for its first character which is uppercased.
+///
+/// Returns [None] if the name is empty.
+pub fn normalize_player_name(name: &str) -> Option<String> {
+    let mut chars = name.chars();
+    let first = chars.next()?;
+    Some(first.to_uppercase().chain(chars.flat_map(char::to_lowercase)).collect())
+}
+
+flags! {
+    /// LanguageType in TC / AC
+    enum LanguageType: u8 {
+        ExtendedLatin = 0x1,
+        Cyrillic      = 0x2,
+        EastAsia      = 0x4,
+    }
+}
+
+/// GetRealmLanguageType in TC / AC, an empty set means only basic latin is allowed.
+fn realm_language_type(realm_zone: &RealmZone, create: bool) -> FlagSet<LanguageType> {
+    match realm_zone {
+        // any language
+        RealmZone::Unknown | RealmZone::Development | RealmZone::TestServer | RealmZone::TestServer2 | RealmZone::QaServer => FlagSet::full(),
+        // extended-Latin
+        RealmZone::UnitedStates
+        | RealmZone::Oceanic
+        | RealmZone::LatinAmerica
+        | RealmZone::English
+        | RealmZone::German
+        | RealmZone::French
+        | RealmZone::Spanish => LanguageType::ExtendedLatin.into(),
+        // East-Asian
+        RealmZone::Korea | RealmZone::Taiwan | RealmZone::China => LanguageType::EastAsia.into(),
+        // Cyrillic
+        RealmZone::Russian => LanguageType::Cyrillic.into(),
+        // basic-Latin at create, any at login
+        _ if create => FlagSet::default(),
+        _ => FlagSet::full(),
+    }
+}
+
+/// isValidString in TC / AC
+fn is_valid_string(s: &str, strict_mask: FlagSet<StrictName>, realm_zone: &RealmZone, numeric_or_space: bool, create: bool) -> bool {
+    // any language, ignore realm
+    if strict_mask.is_empty() {
+        return is_string_of(s, numeric_or_space, is_extended_latin_character)
+            || is_string_of(s, numeric_or_space, is_cyrillic_character)
+            || is_string_of(s, numeric_or_space, is_east_asian_character);
+    }
+    if strict_mask.contains(StrictName::RealmSpecific) {
+        let lt = realm_language_type(realm_zone, create);
+        if lt.contains(LanguageType::ExtendedLatin) && is_string_of(s, numeric_or_space, is_extended_latin_character) {
+            return true;
+        }
+        if lt.contains(LanguageType::Cyrillic) && is_string_of(s, numeric_or_space, is_cyrillic_character) {
+            return true;
+        }
+        if lt.contains(LanguageType::EastAsia) && is_string_of(s, numeric_or_space, is_east_asian_character) {
+            return true;
+        }
+    }
+    strict_mask.contains(StrictName::Latin) && is_string_of(s, numeric_or_space, is_basic_latin_character)
+}
+
+/// ObjectMgr::CheckPlayerName in TC / AC
+pub fn check_player_name(
+    name: &str,
+    locale: Locale,
+    create: bool,
+    cfg: &WorldConfig,
+    name_validators: &NameValidationRegexContainer,
+    name_reserved_validators: &NameReservedRegexContainer,
+) -> ResponseCodes {
+    let num_chars = name.chars().count();
+    if num_chars > MAX_PLAYER_NAME.into() {
+        return ResponseCodes::CharNameTooLong;
+    }
+    if num_chars < *cfg.MinPlayerName as usize {
+        return ResponseCodes::CharNameTooShort;
+    }
+    if !is_valid_string(name, cfg.StrictPlayerNames, &cfg.RealmZone, false, create) {
+        return ResponseCodes::CharNameMixedLanguages;
+    }
+    let name = name.to_lowercase();
+    let chars = name.chars().collect::<Vec<_>>();
+    if chars.windows(3).any(|w| w[0] == w[1] && w[1] == w[2]) {
+        return ResponseCodes::CharNameThreeConsecutive;
+    }
+    validate_name(&name, locale, name_validators, name_reserved_validators)
+}
+
+#[cfg(test)]
+mod tests {
+    use azothacore_common::Locale;
+    use regex::RegexBuilder;
+
+    use super::{check_player_name, normalize_player_name};
+    use crate::{
+        game::world::{RealmZone, StrictName, WorldConfig},
+        shared::{
+            data_stores::{NameReservedRegexContainer, NameValidationRegexContainer},
+            shared_defines::ResponseCodes,
+        },
+    };
+
+    #[test]
+    fn normalize_player_name_capitalises_first_character_only() {
+        assert_eq!(normalize_player_name(""), None);
+        assert_eq!(normalize_player_name("tHRALL").as_deref(), Some("Thrall"));
+        assert_eq!(normalize_player_name("ärger").as_deref(), Some("Ärger"));
+    }
+
+    #[test]
+    fn check_player_name_follows_config_and_name_validators() {
+        let mut cfg = WorldConfig::default();
+        let mut profane = NameValidationRegexContainer::default();
+        profane.insert(Locale::enUS, vec![RegexBuilder::new("^darn").case_insensitive(true).build().unwrap()]);
+        let mut reserved = NameReservedRegexContainer::default();
+        reserved.insert(Locale::enUS, vec![RegexBuilder::new("^thrall$").case_insensitive(true).build().unwrap()]);
+
+        let check = |name: &str, cfg: &WorldConfig| check_player_name(name, Locale::enUS, true, cfg, &profane, &reserved);
+        assert_eq!(check("Jaina", &cfg), ResponseCodes::CharNameSuccess);
+        assert_eq!(check("J", &cfg), ResponseCodes::CharNameTooShort);
+        assert_eq!(check("Abcdefghijklm", &cfg), ResponseCodes::CharNameTooLong);
+        assert_eq!(check("Jai na", &cfg), ResponseCodes::CharNameMixedLanguages);
+        assert_eq!(check("Jaaaina", &cfg), ResponseCodes::CharNameThreeConsecutive);
+        assert_eq!(check("Darnit", &cfg), ResponseCodes::CharNameProfane);
+        assert_eq!(check("Thrall", &cfg), ResponseCodes::CharNameReserved);
+        // Any single script is fine when any language is allowed, as long as it isn't mixed with another one
+        assert_eq!(check("Джайна", &cfg), ResponseCodes::CharNameSuccess);
+        assert_eq!(check("Jайна", &cfg), ResponseCodes::CharNameMixedLanguages);
+        // Extended latin is fine when any language is allowed, but not when strictly latin
+        assert_eq!(check("Jäina", &cfg), ResponseCodes::CharNameSuccess);
+        cfg.StrictPlayerNames = StrictName::Latin.into();
+        assert_eq!(check("Jäina", &cfg), ResponseCodes::CharNameMixedLanguages);
+        cfg.StrictPlayerNames = StrictName::RealmSpecific.into();
+        cfg.RealmZone = RealmZone::German;
+        assert_eq!(check("Jäina", &cfg), ResponseCodes::CharNameSuccess);
+        assert_eq!(check("Джайна", &cfg), ResponseCodes::CharNameMixedLanguages);
+    }
+}
@@ -1,5 +1,7 @@
 use std::time::Duration;
 
 pub mod grid_defines;
+pub mod grid_notifiers;
+pub mod ngrid;
 
 pub const DEFAULT_VISIBILITY_NOTIFY_PERIOD: Duration = Duration::from_millis(1000);
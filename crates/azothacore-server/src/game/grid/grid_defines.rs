@@ -60,13 +60,17 @@ pub const MAP_RESOLUTION: usize = ADT_GRID_SIZE;
 pub const MAP_SIZE: f32 = SIZE_OF_GRIDS * MAX_NUMBER_OF_GRIDS as f32;
 pub const MAP_HALFSIZE: f32 = MAP_SIZE / 2.0;
 
-#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct CoordPair<const LIMIT: usize> {
     pub x_coord: usize,
     pub y_coord: usize,
 }
 
 impl<const LIMIT: usize> CoordPair<LIMIT> {
+    pub fn new(x_coord: usize, y_coord: usize) -> Self {
+        Self { x_coord, y_coord }
+    }
+
     pub fn dec_x(&mut self, val: usize) {
         if self.x_coord > val {
             self.x_coord -= val;
@@ -109,13 +113,20 @@ impl<const LIMIT: usize> CoordPair<LIMIT> {
 pub type GridCoord = CoordPair<MAX_NUMBER_OF_GRIDS>;
 pub type CellCoord = CoordPair<TOTAL_NUMBER_OF_CELLS_PER_MAP>;
 
+impl CellCoord {
+    /// The coordinate of the grid that the cell is in
+    pub fn grid_coord(&self) -> GridCoord {
+        GridCoord::new(self.x_coord / MAX_NUMBER_OF_CELLS, self.y_coord / MAX_NUMBER_OF_CELLS)
+    }
+}
+
 fn compute<const LIMIT: usize, const CENTER_VAL: usize>(x: f32, y: f32, center_offset: f32, size: f32) -> CoordPair<LIMIT> {
     // calculate and store temporary values in double format for having same result as same mySQL calculations
     let x_offset = (f64::from(x) - f64::from(center_offset)) / f64::from(size);
     let y_offset = (f64::from(y) - f64::from(center_offset)) / f64::from(size);
 
-    let x_coord = (x_offset + 0.5) as usize + CENTER_VAL;
-    let y_coord = (y_offset + 0.5) as usize + CENTER_VAL;
+    let x_coord = (x_offset + CENTER_VAL as f64 + 0.5) as usize;
+    let y_coord = (y_offset + CENTER_VAL as f64 + 0.5) as usize;
     CoordPair::<LIMIT> { x_coord, y_coord }
 }
 
@@ -133,8 +144,8 @@ pub fn compute_cell_coord2(x: f32, y: f32, x_off: &mut f32, y_off: &mut f32) ->
     let x_offset = (f64::from(x) - f64::from(CENTER_GRID_CELL_OFFSET)) / f64::from(SIZE_OF_GRID_CELL);
     let y_offset = (f64::from(y) - f64::from(CENTER_GRID_CELL_OFFSET)) / f64::from(SIZE_OF_GRID_CELL);
 
-    let x_coord = (x_offset + 0.5) as usize + CENTER_GRID_CELL_ID;
-    let y_coord = (y_offset + 0.5) as usize + CENTER_GRID_CELL_ID;
+    let x_coord = (x_offset + CENTER_GRID_CELL_ID as f64 + 0.5) as usize;
+    let y_coord = (y_offset + CENTER_GRID_CELL_ID as f64 + 0.5) as usize;
     *x_off = (x_offset as f32 - x_coord as f32 + CENTER_GRID_CELL_ID as f32) * SIZE_OF_GRID_CELL;
     *y_off = (y_offset as f32 - y_coord as f32 + CENTER_GRID_CELL_ID as f32) * SIZE_OF_GRID_CELL;
     CellCoord { x_coord, y_coord }
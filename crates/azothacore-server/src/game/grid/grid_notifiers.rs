@@ -0,0 +1,383 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    time::Duration,
+};
+
+use azothacore_common::bevy_app::az_startup_succeeded;
+use bevy::{
+    prelude::{App, Commands, Component, Entity, IntoSystemConfigs, Query, Res, SystemSet, Update},
+    time::{Time, Timer, TimerMode},
+};
+use flagset::FlagSet;
+use tracing::{debug, error};
+
+use crate::game::{
+    entities::{
+        object::{
+            object_guid::{ObjectGuid, TypeId},
+            update_data::{MovementUpdate, UpdateData, UpdateFlag},
+            update_fields::UpdateFields,
+            Position,
+        },
+        unit::{PlayerBaseMoveSpeed, BASE_MOVE_SPEED},
+    },
+    map::{map_mgr::MapMgrUpdateSet, CurrentMap, Map},
+    server::{
+        world_packets::ServerPacket,
+        world_session::{UpdateWorldSessionsSet, WorldSession},
+        world_socket::WorldSocket,
+    },
+};
+
+/// Player::m_clientGUIDs in TC / AC, the objects that the client of a player has been sent the creation of.
+///
+/// Also keeps track of when the next visibility update of the player is due, which happens every
+/// `Visibility.NotifyPeriod*` of the map that the player is in.
+#[derive(Component, Default)]
+pub struct ClientGuids {
+    guids:            BTreeMap<Entity, ObjectGuid>,
+    /// The map that the objects were seen in
+    map:              Option<Entity>,
+    visibility_timer: Timer,
+}
+
+impl ClientGuids {
+    /// Player::HaveAtClient in TC / AC
+    pub fn have_at_client(&self, obj: Entity) -> bool {
+        self.guids.contains_key(&obj)
+    }
+
+    pub fn guids(&self) -> impl Iterator<Item = &ObjectGuid> {
+        self.guids.values()
+    }
+}
+
+/// Builds and sends the `SMSG_UPDATE_OBJECT` of each player in world once per tick
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VisibilityUpdateSet;
+
+pub fn grid_notifiers_plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        update_visibility_and_send_object_updates
+            .run_if(az_startup_succeeded())
+            .in_set(VisibilityUpdateSet)
+            .after(MapMgrUpdateSet)
+            .after(UpdateWorldSessionsSet),
+    );
+}
+
+/// The movement part of the create block of an object, i.e. Object::m_updateFlag in TC along with its position
+// TODO: Implement me: Speed rates of units
+fn movement_update(fields: &UpdateFields, position: &Position, player_base_move_speed: &PlayerBaseMoveSpeed) -> MovementUpdate {
+    let (flags, speeds) = match fields.get_type_id() {
+        TypeId::Player => (UpdateFlag::Living.into(), player_base_move_speed.0),
+        TypeId::Unit => (UpdateFlag::Living.into(), BASE_MOVE_SPEED),
+        TypeId::Gameobject => (UpdateFlag::StationaryPosition | UpdateFlag::Rotation, BASE_MOVE_SPEED),
+        _ => (FlagSet::default(), BASE_MOVE_SPEED),
+    };
+    MovementUpdate {
+        flags,
+        position: *position,
+        speeds,
+        packed_rotation: 0,
+    }
+}
+
+fn is_within_dist(a: &Position, b: &Position, dist: f32) -> bool {
+    let (dx, dy, dz) = (a.x - b.x, a.y - b.y, a.z - b.z);
+    dx * dx + dy * dy + dz * dz <= dist * dist
+}
+
+/// VisibleNotifier / Player::UpdateVisibilityOf along with the update object building of ObjectAccessor::Update in TC / AC.
+///
+/// Every tick, each player in world receives a single `SMSG_UPDATE_OBJECT` with what [update_visibility_of] adds for
+/// it. The changes of all objects are cleared afterwards.
+#[expect(clippy::too_many_arguments)]
+fn update_visibility_and_send_object_updates(
+    mut commands: Commands,
+    time: Res<Time>,
+    player_base_move_speed: Res<PlayerBaseMoveSpeed>,
+    sessions: Query<(Entity, &WorldSession)>,
+    sockets: Query<&WorldSocket>,
+    maps: Query<&Map>,
+    mut observers: Query<(&CurrentMap, &Position, Option<&mut ClientGuids>)>,
+    mut objects: Query<(&mut UpdateFields, &Position, Option<&CurrentMap>)>,
+) {
+    for (session_entity, session) in &sessions {
+        let Some(player) = session.player() else {
+            continue;
+        };
+        let Ok((current_map, position, client_guids)) = observers.get_mut(player) else {
+            continue;
+        };
+        let Ok((player_fields, ..)) = objects.get(player) else {
+            continue;
+        };
+        let Ok(map) = maps.get(current_map.0) else {
+            error!(target:"maps", "player {player} is in map {} which does not exist", current_map.0);
+            continue;
+        };
+        let target = player_fields.get_guid();
+        let mut new_client_guids = None;
+        let client_guids = match client_guids {
+            Some(c) => c.into_inner(),
+            None => new_client_guids.insert(ClientGuids::default()),
+        };
+        // NOTE: The update blocks only carry what UpdateFields supports, i.e. the dynamic fields are always sent as
+        // unchanged and the active player fields of the player itself as a single block, see UpdateFields.
+        let mut data = UpdateData::new(map.get_id() as u16);
+        update_visibility_of(
+            player,
+            current_map,
+            map,
+            position,
+            client_guids,
+            time.delta(),
+            &player_base_move_speed,
+            &objects,
+            &mut data,
+        );
+
+        if let Some(c) = new_client_guids {
+            commands.entity(player).insert(c);
+        }
+        if !data.has_data() {
+            continue;
+        }
+        let Some(sock) = session.socket_for(session_entity, UpdateData::OPCODE).and_then(|e| sockets.get(e).ok()) else {
+            debug!(target:"network", "prevented sending of SMSG_UPDATE_OBJECT to non existent socket of player {target:?}");
+            continue;
+        };
+        if let Err(e) = sock.send_packet(&data.to_world_packet()) {
+            debug!(target:"network", cause=?e, "unable to send SMSG_UPDATE_OBJECT to player {target:?}");
+        }
+    }
+
+    for (mut fields, ..) in &mut objects {
+        if fields.has_changes() {
+            fields.clear_changes();
+        }
+    }
+}
+
+/// Adds what the client of the player needs to be sent this tick to `data`, i.e.
+///
+/// - the destruction of the objects it saw that have since been despawned, and the out of range GUIDs of those that
+///   moved to another map.
+/// - the creation of the objects within the visibility range of its map that it has yet to see, along with the
+///   out of range GUIDs of those that it no longer sees. This is only updated every visibility notify period of the map.
+/// - the changed update fields of all objects it does see.
+#[expect(clippy::too_many_arguments)]
+fn update_visibility_of(
+    player: Entity,
+    current_map: &CurrentMap,
+    map: &Map,
+    position: &Position,
+    client_guids: &mut ClientGuids,
+    delta: Duration,
+    player_base_move_speed: &PlayerBaseMoveSpeed,
+    objects: &Query<(&mut UpdateFields, &Position, Option<&CurrentMap>)>,
+    data: &mut UpdateData,
+) {
+    let Ok((player_fields, ..)) = objects.get(player) else {
+        return;
+    };
+    let target = player_fields.get_guid();
+
+    // Objects that have been despawned or moved to another map since
+    client_guids.guids.retain(|obj, guid| match objects.get(*obj) {
+        Err(_) => {
+            data.add_destroy_object(*guid);
+            false
+        },
+        Ok((_, _, obj_map)) if obj_map != Some(current_map) => {
+            data.add_out_of_range_guid(*guid);
+            false
+        },
+        Ok(_) => true,
+    });
+
+    let mut created = BTreeSet::new();
+    client_guids.visibility_timer.tick(delta);
+    if client_guids.map != Some(current_map.0) || client_guids.visibility_timer.just_finished() {
+        if client_guids.map != Some(current_map.0) {
+            client_guids.map = Some(current_map.0);
+            client_guids.visibility_timer = Timer::new(map.get_visibility_notify_period(), TimerMode::Repeating);
+        }
+        let range = map.get_visibility_range();
+        let visible = map
+            .get_objects_in_cell_area(position.x, position.y, range)
+            .chain(std::iter::once(player))
+            .filter_map(|obj| {
+                let (fields, obj_pos, _) = objects.get(obj).ok()?;
+                (obj == player || is_within_dist(position, obj_pos, range)).then_some((obj, fields, obj_pos))
+            })
+            .collect::<Vec<_>>();
+        for &(obj, fields, obj_pos) in &visible {
+            if client_guids.have_at_client(obj) {
+                continue;
+            }
+            let movement = movement_update(fields, obj_pos, player_base_move_speed);
+            fields.build_create_update_block_for_player(&movement, &target, data);
+            client_guids.guids.insert(obj, fields.get_guid());
+            created.insert(obj);
+        }
+        let visible = visible.into_iter().map(|(obj, ..)| obj).collect::<BTreeSet<_>>();
+        client_guids.guids.retain(|obj, guid| {
+            let keep = visible.contains(obj);
+            if !keep {
+                data.add_out_of_range_guid(*guid);
+            }
+            keep
+        });
+    }
+
+    for obj in client_guids.guids.keys().filter(|obj| !created.contains(*obj)) {
+        let Ok((fields, ..)) = objects.get(*obj) else {
+            continue;
+        };
+        if fields.has_changes() {
+            fields.build_values_update_block_for_player(&target, data);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::system::SystemState, prelude::World};
+
+    use super::*;
+    use crate::game::{
+        entities::object::update_fields::{tests::player_guid, UNIT_FIELD_LEVEL},
+        grid::{grid_defines::SIZE_OF_GRIDS, DEFAULT_VISIBILITY_NOTIFY_PERIOD},
+        world::WorldConfig,
+    };
+
+    /// The world coordinate at the middle of the grid with the `CENTER_GRID_ID` coordinates
+    const GRID_CENTRE: f32 = SIZE_OF_GRIDS / 2.0;
+
+    fn spawn_map(world: &mut World, id: u32) -> Entity {
+        world
+            .spawn(Map::new(&WorldConfig::default(), id, 0, 100.0, DEFAULT_VISIBILITY_NOTIFY_PERIOD))
+            .id()
+    }
+
+    fn spawn_player(world: &mut World, map: Entity, counter: u64, x: f32) -> Entity {
+        let fields = UpdateFields::new(player_guid(counter), TypeId::Player, 0).unwrap();
+        let obj = world.spawn((fields, Position::new(x, GRID_CENTRE, 0.0, 0.0), CurrentMap(map))).id();
+        relocate(world, obj, map, x);
+        obj
+    }
+
+    fn relocate(world: &mut World, obj: Entity, map: Entity, x: f32) {
+        if let Some(CurrentMap(old_map)) = world.get::<CurrentMap>(obj).copied() {
+            world.get_mut::<Map>(old_map).unwrap().remove_object(obj);
+        }
+        assert!(world.get_mut::<Map>(map).unwrap().add_or_relocate_object(obj, x, GRID_CENTRE, true));
+        world.entity_mut(obj).insert((Position::new(x, GRID_CENTRE, 0.0, 0.0), CurrentMap(map)));
+    }
+
+    /// Runs [update_visibility_of] for the player, clearing the changes of all objects afterwards
+    fn update(world: &mut World, player: Entity, client_guids: &mut ClientGuids, delta: Duration) -> UpdateData {
+        let mut state = SystemState::<(
+            Query<&Map>,
+            Query<(&CurrentMap, &Position)>,
+            Query<(&mut UpdateFields, &Position, Option<&CurrentMap>)>,
+        )>::new(world);
+        let (maps, players, mut objects) = state.get_mut(world);
+        let (current_map, position) = players.get(player).unwrap();
+        let map = maps.get(current_map.0).unwrap();
+        let mut data = UpdateData::new(map.get_id() as u16);
+        update_visibility_of(
+            player,
+            current_map,
+            map,
+            position,
+            client_guids,
+            delta,
+            &PlayerBaseMoveSpeed(BASE_MOVE_SPEED),
+            &objects,
+            &mut data,
+        );
+        for (mut fields, ..) in &mut objects {
+            fields.clear_changes();
+        }
+        data
+    }
+
+    #[test]
+    fn it_creates_objects_in_range_and_sends_their_changes() {
+        let mut world = World::new();
+        let map = spawn_map(&mut world, 0);
+        let player = spawn_player(&mut world, map, 1, GRID_CENTRE);
+        let near = spawn_player(&mut world, map, 2, GRID_CENTRE + 10.0);
+        let far = spawn_player(&mut world, map, 3, GRID_CENTRE + 250.0);
+        let mut client_guids = ClientGuids::default();
+
+        // The player itself and the object nearby are created right away
+        let data = update(&mut world, player, &mut client_guids, Duration::ZERO);
+        assert_eq!(data.get_block_count(), 2);
+        assert!(data.get_out_of_range_guids().is_empty());
+        assert!(client_guids.have_at_client(player) && client_guids.have_at_client(near));
+        assert!(!client_guids.have_at_client(far));
+
+        // Nothing to send until something changes
+        let data = update(&mut world, player, &mut client_guids, Duration::ZERO);
+        assert!(!data.has_data());
+
+        world.get_mut::<UpdateFields>(near).unwrap().set_u32_value(UNIT_FIELD_LEVEL, 10);
+        world.get_mut::<UpdateFields>(far).unwrap().set_u32_value(UNIT_FIELD_LEVEL, 10);
+        let data = update(&mut world, player, &mut client_guids, Duration::ZERO);
+        assert_eq!(data.get_block_count(), 1);
+    }
+
+    #[test]
+    fn it_updates_visibility_every_notify_period() {
+        let mut world = World::new();
+        let map = spawn_map(&mut world, 0);
+        let player = spawn_player(&mut world, map, 1, GRID_CENTRE);
+        let near = spawn_player(&mut world, map, 2, GRID_CENTRE + 10.0);
+        let far = spawn_player(&mut world, map, 3, GRID_CENTRE + 250.0);
+        let mut client_guids = ClientGuids::default();
+        update(&mut world, player, &mut client_guids, Duration::ZERO);
+
+        // The objects swap places, which is only noticed once the notify period has passed
+        relocate(&mut world, near, map, GRID_CENTRE + 250.0);
+        relocate(&mut world, far, map, GRID_CENTRE + 10.0);
+        let data = update(&mut world, player, &mut client_guids, DEFAULT_VISIBILITY_NOTIFY_PERIOD / 2);
+        assert!(!data.has_data());
+        assert!(client_guids.have_at_client(near) && !client_guids.have_at_client(far));
+
+        let data = update(&mut world, player, &mut client_guids, DEFAULT_VISIBILITY_NOTIFY_PERIOD / 2);
+        assert_eq!(data.get_block_count(), 1);
+        assert_eq!(data.get_out_of_range_guids().iter().collect::<Vec<_>>(), [&player_guid(2)]);
+        assert!(!client_guids.have_at_client(near) && client_guids.have_at_client(far));
+    }
+
+    #[test]
+    fn it_destroys_despawned_objects_and_forgets_those_in_other_maps() {
+        let mut world = World::new();
+        let (map, other_map) = (spawn_map(&mut world, 0), spawn_map(&mut world, 1));
+        let player = spawn_player(&mut world, map, 1, GRID_CENTRE);
+        let despawned = spawn_player(&mut world, map, 2, GRID_CENTRE + 10.0);
+        let teleported = spawn_player(&mut world, map, 3, GRID_CENTRE + 20.0);
+        let mut client_guids = ClientGuids::default();
+        assert_eq!(update(&mut world, player, &mut client_guids, Duration::ZERO).get_block_count(), 3);
+
+        // Both are sent right away, without waiting for the notify period
+        world.get_mut::<Map>(map).unwrap().remove_object(despawned);
+        world.despawn(despawned);
+        relocate(&mut world, teleported, other_map, GRID_CENTRE + 20.0);
+        let data = update(&mut world, player, &mut client_guids, Duration::ZERO);
+        assert_eq!(data.get_destroy_guids().iter().collect::<Vec<_>>(), [&player_guid(2)]);
+        assert_eq!(data.get_out_of_range_guids().iter().collect::<Vec<_>>(), [&player_guid(3)]);
+        assert_eq!(client_guids.guids().collect::<Vec<_>>(), [&player_guid(1)]);
+
+        // Following it into the other map, the player is sent its creation again
+        relocate(&mut world, player, other_map, GRID_CENTRE);
+        let data = update(&mut world, player, &mut client_guids, Duration::ZERO);
+        assert_eq!(data.get_block_count(), 1);
+        assert!(client_guids.have_at_client(teleported));
+    }
+}
@@ -0,0 +1,169 @@
+use std::{collections::BTreeSet, time::Duration};
+
+use bevy::{
+    prelude::Entity,
+    time::{Timer, TimerMode},
+};
+
+use crate::game::{
+    grid::grid_defines::{CellCoord, GridCoord, MAX_NUMBER_OF_CELLS, MAX_NUMBER_OF_GRIDS},
+    map::GridMap,
+};
+
+/// grid_state_t in TC / AC
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GridState {
+    Invalid,
+    /// There are players or active objects within visibility range of the grid
+    Active,
+    /// No players or active objects are near, the grid is about to be scheduled for removal
+    Idle,
+    /// The grid is unloaded once its time tracker expires, unless it is locked
+    Removal,
+}
+
+/// GridInfo in TC / AC
+pub struct GridInfo {
+    /// i_timer in TC / AC
+    timer:                    Timer,
+    unload_active_lock_count: u16,
+    unload_explicit_lock:     bool,
+}
+
+impl GridInfo {
+    pub fn new(expiry: Duration, unload: bool) -> Self {
+        Self {
+            timer:                    Timer::new(expiry, TimerMode::Once),
+            unload_active_lock_count: 0,
+            unload_explicit_lock:     !unload,
+        }
+    }
+
+    pub fn time_tracker(&self) -> &Timer {
+        &self.timer
+    }
+
+    pub fn get_unload_lock(&self) -> bool {
+        self.unload_active_lock_count > 0 || self.unload_explicit_lock
+    }
+
+    pub fn set_unload_explicit_lock(&mut self, on: bool) {
+        self.unload_explicit_lock = on;
+    }
+
+    pub fn inc_unload_active_lock(&mut self) {
+        self.unload_active_lock_count += 1;
+    }
+
+    pub fn dec_unload_active_lock(&mut self) {
+        self.unload_active_lock_count = self.unload_active_lock_count.saturating_sub(1);
+    }
+
+    pub fn reset_time_tracker(&mut self, interval: Duration) {
+        self.timer = Timer::new(interval, TimerMode::Once);
+    }
+
+    pub fn update_time_tracker(&mut self, diff: Duration) {
+        self.timer.tick(diff);
+    }
+}
+
+/// Grid in TC / AC, the world objects within a single cell of an [NGrid].
+#[derive(Default)]
+pub struct GridCell {
+    objects: BTreeSet<Entity>,
+}
+
+impl GridCell {
+    pub fn objects(&self) -> &BTreeSet<Entity> {
+        &self.objects
+    }
+}
+
+/// NGrid in TC / AC, a single grid of a map split into [`MAX_NUMBER_OF_CELLS`] x [`MAX_NUMBER_OF_CELLS`] cells.
+///
+/// Also holds the terrain data of the grid, i.e. the [GridMap] loaded from its .map file, if there is any.
+pub struct NGrid {
+    grid_id:  usize,
+    x:        usize,
+    y:        usize,
+    info:     GridInfo,
+    state:    GridState,
+    cells:    [[GridCell; MAX_NUMBER_OF_CELLS]; MAX_NUMBER_OF_CELLS],
+    grid_map: Option<GridMap>,
+}
+
+impl NGrid {
+    pub fn new(coord: GridCoord, expiry: Duration, unload: bool, grid_map: Option<GridMap>) -> Self {
+        Self {
+            grid_id: coord.x_coord * MAX_NUMBER_OF_GRIDS + coord.y_coord,
+            x: coord.x_coord,
+            y: coord.y_coord,
+            info: GridInfo::new(expiry, unload),
+            state: GridState::Invalid,
+            cells: Default::default(),
+            grid_map,
+        }
+    }
+
+    pub fn get_grid_id(&self) -> usize {
+        self.grid_id
+    }
+
+    pub fn get_x(&self) -> usize {
+        self.x
+    }
+
+    pub fn get_y(&self) -> usize {
+        self.y
+    }
+
+    pub fn get_grid_state(&self) -> GridState {
+        self.state
+    }
+
+    pub fn set_grid_state(&mut self, state: GridState) {
+        self.state = state;
+    }
+
+    pub fn get_grid_info(&self) -> &GridInfo {
+        &self.info
+    }
+
+    pub fn get_grid_info_mut(&mut self) -> &mut GridInfo {
+        &mut self.info
+    }
+
+    pub fn get_grid_map(&self) -> Option<&GridMap> {
+        self.grid_map.as_ref()
+    }
+
+    /// NGrid::GetGridType in TC / AC, the cell is given in map wide cell coordinates
+    pub fn get_grid_type(&self, cell: CellCoord) -> &GridCell {
+        &self.cells[cell.x_coord % MAX_NUMBER_OF_CELLS][cell.y_coord % MAX_NUMBER_OF_CELLS]
+    }
+
+    fn get_grid_type_mut(&mut self, cell: CellCoord) -> &mut GridCell {
+        &mut self.cells[cell.x_coord % MAX_NUMBER_OF_CELLS][cell.y_coord % MAX_NUMBER_OF_CELLS]
+    }
+
+    /// NGrid::AddWorldObject in TC / AC
+    pub fn add_world_object(&mut self, cell: CellCoord, obj: Entity) {
+        self.get_grid_type_mut(cell).objects.insert(obj);
+    }
+
+    /// NGrid::RemoveWorldObject in TC / AC
+    pub fn remove_world_object(&mut self, cell: CellCoord, obj: Entity) -> bool {
+        self.get_grid_type_mut(cell).objects.remove(&obj)
+    }
+
+    /// NGrid::GetWorldObjectCountInNGrid in TC / AC
+    pub fn get_world_object_count_in_ngrid(&self) -> usize {
+        self.cells.iter().flatten().map(|c| c.objects.len()).sum()
+    }
+
+    /// All world objects in the grid
+    pub fn world_objects(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.cells.iter().flatten().flat_map(|c| c.objects.iter().copied())
+    }
+}
@@ -0,0 +1 @@
+pub mod character_handler;
@@ -0,0 +1,763 @@
+use std::collections::BTreeSet;
+
+use azothacore_common::{az_error, bevy_app::TokioRuntime, configuration::ConfigMgr, AccountTypes, AzResult};
+use azothacore_database::{
+    args,
+    database_env::{CharacterDatabase, CharacterPreparedStmts, LoginDatabase, LoginPreparedStmts},
+    DbDriver,
+};
+use bevy::prelude::{App, Commands, In, Query, Res, ResMut};
+use flagset::FlagSet;
+use num::FromPrimitive;
+use sqlx::Pool;
+use tracing::{debug, info, warn};
+
+use crate::{
+    game::{
+        cache::character_cache::{CharacterCache, CharacterCacheEntry},
+        entities::{
+            item::InventoryType,
+            object::object_guid::{
+                HighGuidGuild,
+                HighGuidItem,
+                HighGuidPlayer,
+                ObjectGuid,
+                ObjectGuidLowGenerator,
+                ObjectGuidRealmSpecific,
+                RealmSpecificObjectGuidGenerator,
+            },
+            player::{
+                AtLoginFlags,
+                PlayerFlags,
+                EQUIPMENT_SLOT_END,
+                INVENTORY_SLOT_BAG_END,
+                INVENTORY_SLOT_BAG_START,
+                INVENTORY_SLOT_ITEM_END,
+                INVENTORY_SLOT_ITEM_START,
+            },
+        },
+        globals::object_mgr::{check_player_name, normalize_player_name, PlayerInfo, PlayerInfoContainer, ReservedNamesContainer},
+        scripting::script_mgr::ScriptMgr,
+        server::{
+            protocol::opcodes::OpcodeClient,
+            world_packets::{
+                character_packets::{
+                    CharDelete,
+                    CharacterCreateInfo,
+                    CharacterInfo,
+                    CreateChar,
+                    CreateCharacter,
+                    DeleteChar,
+                    EnumCharacters,
+                    EnumCharactersResult,
+                    PetInfo,
+                    VisualItemInfo,
+                },
+                ClientPacket,
+                ServerPacket,
+            },
+            world_session::{add_client_opcode_handler, WorldSession, WorldSessionPacket},
+            world_socket::WorldSocket,
+        },
+        world::{CharDeleteMethod, CharacterCreateFactionDisabled, CurrentRealm, WorldConfig},
+    },
+    shared::{
+        data_stores::{dbc_enums::Class, DB2Mgr},
+        id_generators::IDGeneratorTrait,
+        shared_defines::{Expansion, ResponseCodes, MAX_CHARACTERS_PER_REALM},
+    },
+};
+
+pub fn character_handler_plugin(app: &mut App) {
+    add_client_opcode_handler(app, OpcodeClient::EnumCharacters, handle_char_enum);
+    add_client_opcode_handler(app, OpcodeClient::CreateCharacter, handle_char_create);
+    add_client_opcode_handler(app, OpcodeClient::CharDelete, handle_char_delete);
+}
+
+/// CharacterFlags in TC/AC
+const CHARACTER_FLAG_HIDE_HELM: u32 = 0x00000400;
+const CHARACTER_FLAG_HIDE_CLOAK: u32 = 0x00000800;
+const CHARACTER_FLAG_GHOST: u32 = 0x00002000;
+const CHARACTER_FLAG_RENAME: u32 = 0x00004000;
+const CHARACTER_FLAG_LOCKED_FOR_TRANSFER: u32 = 0x00000004;
+const CHARACTER_FLAG_LOCKED_BY_BILLING: u32 = 0x01000000;
+
+/// CharacterCustomizeFlags in TC/AC
+const CHAR_CUSTOMIZE_FLAG_CUSTOMIZE: u32 = 0x00000001;
+const CHAR_CUSTOMIZE_FLAG_FACTION: u32 = 0x00010000;
+const CHAR_CUSTOMIZE_FLAG_RACE: u32 = 0x00100000;
+
+/// ITEM_FIELD_FLAG_SOULBOUND in TC/AC
+const ITEM_FIELD_FLAG_SOULBOUND: u32 = 0x00000001;
+
+/// A row of [CharacterPreparedStmts::sel_enum]
+#[derive(sqlx::FromRow)]
+struct CharacterEnumRow {
+    guid:                u64,
+    name:                String,
+    race:                u8,
+    class:               u8,
+    gender:              u8,
+    skin:                u8,
+    face:                u8,
+    hair_style:          u8,
+    hair_color:          u8,
+    facial_style:        u8,
+    custom_display1:     u8,
+    custom_display2:     u8,
+    custom_display3:     u8,
+    level:               u8,
+    zone:                u16,
+    map:                 u16,
+    position_x:          f32,
+    position_y:          f32,
+    position_z:          f32,
+    guild_id:            Option<u64>,
+    player_flags:        u32,
+    at_login:            u16,
+    pet_entry:           Option<u32>,
+    pet_model_id:        Option<u32>,
+    pet_level:           Option<u16>,
+    equipment_cache:     Option<String>,
+    banned_guid:         Option<u64>,
+    slot:                u8,
+    logout_time:         u32,
+    active_talent_group: u8,
+    last_login_build:    u32,
+}
+
+/// Parses the visible equipment stored in `characters.equipmentCache`, i.e. `inventoryType displayId displayEnchantId`
+/// for every slot up to the bags. Missing or malformed values are left as 0.
+fn visual_items_from_equipment_cache(equipment_cache: &str) -> [VisualItemInfo; INVENTORY_SLOT_BAG_END as usize] {
+    let mut tokens = equipment_cache.split_whitespace().map(|t| t.parse::<u32>().unwrap_or_default());
+    let mut visual_items = [VisualItemInfo::default(); INVENTORY_SLOT_BAG_END as usize];
+    for v in visual_items.iter_mut() {
+        v.inventory_type = tokens.next().unwrap_or_default() as u8;
+        v.display_id = tokens.next().unwrap_or_default();
+        v.display_enchant_id = tokens.next().unwrap_or_default();
+    }
+    visual_items
+}
+
+/// WorldPackets::Character::EnumCharactersResult::CharacterInfo::CharacterInfo in TC/AC
+fn character_info_from_row(row: CharacterEnumRow, current_realm: &CurrentRealm, db2: &DB2Mgr) -> CharacterInfo {
+    let at_login = FlagSet::<AtLoginFlags>::new_truncated(row.at_login);
+    let mut player_flags = FlagSet::<PlayerFlags>::new_truncated(row.player_flags);
+    if at_login.contains(AtLoginFlags::Resurrect) {
+        player_flags -= PlayerFlags::Ghost;
+    }
+
+    let mut flags = 0;
+    if player_flags.contains(PlayerFlags::HideHelm) {
+        flags |= CHARACTER_FLAG_HIDE_HELM;
+    }
+    if player_flags.contains(PlayerFlags::HideCloak) {
+        flags |= CHARACTER_FLAG_HIDE_CLOAK;
+    }
+    if player_flags.contains(PlayerFlags::Ghost) {
+        flags |= CHARACTER_FLAG_GHOST;
+    }
+    if at_login.contains(AtLoginFlags::Rename) {
+        flags |= CHARACTER_FLAG_RENAME;
+    }
+    if row.banned_guid.is_some() {
+        flags |= CHARACTER_FLAG_LOCKED_BY_BILLING;
+    }
+    // TODO: Implement me: CHARACTER_FLAG_DECLINED once declined names are selected along with the enum
+
+    let customization_flag = if at_login.contains(AtLoginFlags::Customize) {
+        CHAR_CUSTOMIZE_FLAG_CUSTOMIZE
+    } else if at_login.contains(AtLoginFlags::ChangeFaction) {
+        CHAR_CUSTOMIZE_FLAG_FACTION
+    } else if at_login.contains(AtLoginFlags::ChangeRace) {
+        CHAR_CUSTOMIZE_FLAG_RACE
+    } else {
+        0
+    };
+
+    let class = Class::try_from(row.class).ok();
+    // show pet at selection character in character list only for non-ghost character
+    let mut pet = PetInfo::default();
+    if row.pet_entry.is_some() && !player_flags.contains(PlayerFlags::Ghost) && matches!(class, Some(Class::Warlock | Class::Hunter | Class::DeathKnight)) {
+        // TODO: Implement me: Check that the pet entry has a creature template and set the CreatureFamily from it
+        pet.creature_display_id = row.pet_model_id.unwrap_or_default();
+        pet.level = row.pet_level.unwrap_or_default().into();
+    }
+
+    let spec_id = class
+        .and_then(|c| db2.chr_specializations_by_index.get(&c))
+        .and_then(|specs| specs.get(&(row.active_talent_group as i8)))
+        .map_or(0, |spec| spec.id as u16);
+
+    CharacterInfo {
+        guid: ObjectGuid::<HighGuidPlayer>::realm_specific(current_realm, row.guid),
+        name: row.name,
+        list_position: row.slot,
+        race_id: row.race,
+        class_id: row.class,
+        sex_id: row.gender,
+        skin_id: row.skin,
+        face_id: row.face,
+        hair_style: row.hair_style,
+        hair_color: row.hair_color,
+        facial_hair_style: row.facial_style,
+        custom_display: [row.custom_display1, row.custom_display2, row.custom_display3],
+        exp_level: row.level,
+        zone_id: row.zone.into(),
+        map_id: row.map.into(),
+        pre_load_position: [row.position_x, row.position_y, row.position_z],
+        guild_guid: row
+            .guild_id
+            .map_or(ObjectGuid::EMPTY, |g| ObjectGuid::<HighGuidGuild>::realm_specific(current_realm, g).into()),
+        flags,
+        customization_flag,
+        flags3: 0,
+        flags4: 0,
+        first_login: at_login.contains(AtLoginFlags::First),
+        boost_in_progress: false,
+        unk_wod61x: 0,
+        pet,
+        profession_ids: [0; 2],
+        visual_items: visual_items_from_equipment_cache(row.equipment_cache.as_deref().unwrap_or_default()),
+        last_played_time: row.logout_time,
+        spec_id,
+        unknown703: 0,
+        last_login_build: row.last_login_build,
+    }
+}
+
+/// HandleCharEnumOpcode and HandleCharEnum in TC/AC
+#[expect(clippy::too_many_arguments)]
+fn handle_char_enum(
+    In((e, packet)): In<WorldSessionPacket>,
+    cfg: Res<ConfigMgr<WorldConfig>>,
+    rt: Res<TokioRuntime>,
+    char_db: Res<CharacterDatabase>,
+    current_realm: Res<CurrentRealm>,
+    db2: DB2Mgr,
+    mut character_cache: ResMut<CharacterCache>,
+    mut sessions: Query<(&mut WorldSession, &WorldSocket)>,
+) -> AzResult<()> {
+    EnumCharacters::from_world_packet(packet)?;
+    let Ok((mut session, sock)) = sessions.get_mut(e) else {
+        return Err(az_error!("no world session found for entity {e}"));
+    };
+
+    // get all the data necessary for loading all characters (along with their pets) on the account
+    let rows = rt.block_on(CharacterDatabase::sel_enum::<_, CharacterEnumRow>(
+        &**char_db,
+        args!(0u8, session.account_id())?,
+    ))?;
+
+    // TODO: Implement me: RBAC_PERM_SKIP_CHECK_CHARACTER_CREATION_DEMON_HUNTER
+    let mut can_always_create_demon_hunter = false;
+    let mut demon_hunter_count = 0;
+    let mut legit_characters = BTreeSet::new();
+    let mut characters = Vec::with_capacity(rows.len());
+    for row in rows {
+        let char_info = character_info_from_row(row, &current_realm, &db2);
+        debug!(target:"network", "Loading {} from account {}.", char_info.name, session.account_id());
+        if !character_cache.has_character_cache_entry(char_info.guid) {
+            character_cache.add_character_cache_entry(CharacterCacheEntry {
+                guid:       char_info.guid,
+                name:       char_info.name.clone(),
+                account_id: session.account_id(),
+                race:       char_info.race_id,
+                class:      char_info.class_id,
+                sex:        char_info.sex_id,
+                level:      char_info.exp_level,
+                guild_id:   char_info.guild_guid.counter(),
+                is_deleted: false,
+            });
+        }
+        if char_info.class_id == Class::DemonHunter.to_num::<u8>() {
+            demon_hunter_count += 1;
+        }
+        if u32::from(char_info.exp_level) >= *cfg.CharacterCreating.MinLevelForDemonHunter {
+            can_always_create_demon_hunter = true;
+        }
+        // Do not allow locked characters to login
+        if char_info.flags & (CHARACTER_FLAG_LOCKED_FOR_TRANSFER | CHARACTER_FLAG_LOCKED_BY_BILLING) == 0 {
+            legit_characters.insert(char_info.guid);
+        }
+        characters.push(char_info);
+    }
+    session.set_legit_characters(legit_characters);
+
+    let demon_hunters_per_realm = *cfg.DemonHuntersPerRealm;
+    let char_enum = EnumCharactersResult {
+        success: true,
+        is_deleted_characters: false,
+        is_demon_hunter_creation_allowed: session.expansion() >= Expansion::Legion as u8 || can_always_create_demon_hunter,
+        has_demon_hunter_on_realm: demon_hunters_per_realm > 0 && demon_hunter_count >= demon_hunters_per_realm,
+        unknown7x: false,
+        is_allied_races_creation_allowed: session.expansion() >= Expansion::BattleForAzeroth as u8,
+        max_character_level: 1,
+        disabled_classes_mask: Some(cfg.CharacterCreating.DisabledClassMask.bits()),
+        characters,
+        // TODO: Implement me: RaceUnlockData from the race unlock requirements
+        race_unlock_data: vec![],
+    };
+    sock.send_packet(&char_enum.to_world_packet())?;
+    Ok(())
+}
+
+fn send_char_create(sock: &WorldSocket, code: ResponseCodes, guid: ObjectGuid) -> AzResult<()> {
+    sock.send_packet(&CreateChar { code, guid }.to_world_packet())?;
+    Ok(())
+}
+
+/// HandleCharCreateOpcode in TC/AC, along with the initial setup and save done by Player::Create
+#[expect(clippy::too_many_arguments)]
+fn handle_char_create(
+    In((e, packet)): In<WorldSessionPacket>,
+    cfg: Res<ConfigMgr<WorldConfig>>,
+    rt: Res<TokioRuntime>,
+    char_db: Res<CharacterDatabase>,
+    login_db: Res<LoginDatabase>,
+    current_realm: Res<CurrentRealm>,
+    db2: DB2Mgr,
+    reserved_names: Res<ReservedNamesContainer>,
+    player_infos: Res<PlayerInfoContainer>,
+    player_guid_gen: RealmSpecificObjectGuidGenerator<HighGuidPlayer>,
+    item_guid_gen: Res<ObjectGuidLowGenerator<HighGuidItem>>,
+    mut character_cache: ResMut<CharacterCache>,
+    sessions: Query<(&WorldSession, &WorldSocket)>,
+    mut commands: Commands,
+    script_mgr: ScriptMgr,
+) -> AzResult<()> {
+    let CreateCharacter { mut create_info } = CreateCharacter::from_world_packet(packet)?;
+    let Ok((session, sock)) = sessions.get(e) else {
+        return Err(az_error!("no world session found for entity {e}"));
+    };
+
+    if !db2.stores.chr_classes_store.contains_key(&u32::from(create_info.class_id)) {
+        info!(target:"network", "Class ({}) not found in DBC while creating new char for account (ID: {}): wrong DBC files or cheater?", create_info.class_id, session.account_id());
+        return send_char_create(sock, ResponseCodes::CharCreateFailed, ObjectGuid::EMPTY);
+    }
+    let Some(race_entry) = db2.stores.chr_races_store.get(&u32::from(create_info.race_id)) else {
+        info!(target:"network", "Race ({}) not found in DBC while creating new char for account (ID: {}): wrong DBC files or cheater?", create_info.race_id, session.account_id());
+        return send_char_create(sock, ResponseCodes::CharCreateFailed, ObjectGuid::EMPTY);
+    };
+    let Some(player_info) = player_infos.get(&(create_info.race_id, create_info.class_id)) else {
+        info!(target:"network", "Player::Create: Account {} tried to create a character with an invalid race/class pair ({}/{}) - refusing to do so.", session.account_id(), create_info.race_id, create_info.class_id);
+        return send_char_create(sock, ResponseCodes::CharCreateFailed, ObjectGuid::EMPTY);
+    };
+    if create_info.sex > 1 {
+        info!(target:"network", "Player::Create: Account {} tried to create a character with an invalid gender ({})", session.account_id(), create_info.sex);
+        return send_char_create(sock, ResponseCodes::CharCreateFailed, ObjectGuid::EMPTY);
+    }
+    // TODO: Implement me: prevent character creating Expansion race / class without Expansion account
+    // TODO: Implement me: customization validation, i.e. Player::ValidateAppearance
+
+    // TODO: Implement me: use RBAC_PERM_SKIP_CHECK_CHARACTER_CREATION_TEAMMASK, RACEMASK and CLASSMASK instead once there is RBAC
+    if session.security() == AccountTypes::SecPlayer {
+        let disabled_faction = cfg.CharacterCreating.DisabledFaction;
+        let team_disabled = match race_entry.alliance {
+            0 => disabled_faction.contains(CharacterCreateFactionDisabled::Alliance),
+            1 => disabled_faction.contains(CharacterCreateFactionDisabled::Horde),
+            2 => disabled_faction.contains(CharacterCreateFactionDisabled::Neutral),
+            _ => false,
+        };
+        if team_disabled {
+            return send_char_create(sock, ResponseCodes::CharCreateDisabled, ObjectGuid::EMPTY);
+        }
+        let race_mask = 1u32.checked_shl(u32::from(create_info.race_id).saturating_sub(1)).unwrap_or_default();
+        if cfg.CharacterCreating.DisabledRaceMask.bits() & race_mask != 0 {
+            return send_char_create(sock, ResponseCodes::CharCreateDisabled, ObjectGuid::EMPTY);
+        }
+        let class_mask = 1u32.checked_shl(u32::from(create_info.class_id).saturating_sub(1)).unwrap_or_default();
+        if cfg.CharacterCreating.DisabledClassMask.bits() & class_mask != 0 {
+            return send_char_create(sock, ResponseCodes::CharCreateDisabled, ObjectGuid::EMPTY);
+        }
+    }
+
+    // prevent character creating with invalid name
+    let Some(name) = normalize_player_name(&create_info.name) else {
+        info!(target:"entities::player::cheat", "Account:[{}] but tried to Create character with empty [name] ", session.account_id());
+        return send_char_create(sock, ResponseCodes::CharNameNoName, ObjectGuid::EMPTY);
+    };
+    create_info.name = name;
+
+    // check name limitations
+    let res = check_player_name(
+        &create_info.name,
+        session.session_db_locale_index(),
+        true,
+        &cfg,
+        &db2.name_validators,
+        &db2.name_reserved_validators,
+    );
+    if res != ResponseCodes::CharNameSuccess {
+        return send_char_create(sock, res, ObjectGuid::EMPTY);
+    }
+    // TODO: Implement me: use RBAC_PERM_SKIP_CHECK_CHARACTER_CREATION_RESERVEDNAME instead once there is RBAC
+    if session.security() == AccountTypes::SecPlayer && reserved_names.is_reserved_name(&create_info.name) {
+        return send_char_create(sock, ResponseCodes::CharNameReserved, ObjectGuid::EMPTY);
+    }
+
+    rt.block_on(async {
+        if CharacterDatabase::sel_check_name::<_, (i64,)>(&**char_db, args!(&create_info.name)?)
+            .await?
+            .is_some()
+        {
+            return send_char_create(sock, ResponseCodes::CharCreateNameInUse, ObjectGuid::EMPTY);
+        }
+
+        let acct_char_count = LoginDatabase::sel_sum_realm_characters::<_, (u64,)>(&**login_db, args!(session.account_id())?)
+            .await?
+            .map_or(0, |c| c.0);
+        if acct_char_count >= u64::from(cfg.CharactersPerAccount) {
+            return send_char_create(sock, ResponseCodes::CharCreateAccountLimit, ObjectGuid::EMPTY);
+        }
+
+        let char_count = CharacterDatabase::sel_sum_chars::<_, (u64,)>(&**char_db, args!(session.account_id())?)
+            .await?
+            .map_or(0, |c| c.0);
+        if char_count >= u64::from(*cfg.CharactersPerRealm) {
+            return send_char_create(sock, ResponseCodes::CharCreateServerLimit, ObjectGuid::EMPTY);
+        }
+
+        // TODO: Implement me: use RBAC_PERM_SKIP_CHECK_CHARACTER_CREATION_DEMON_HUNTER instead once there is RBAC
+        if create_info.class_id == Class::DemonHunter.to_num::<u8>() && session.security() == AccountTypes::SecPlayer {
+            let chars = CharacterDatabase::sel_char_create_info::<_, (u8, u8, u8)>(&**char_db, args!(session.account_id(), MAX_CHARACTERS_PER_REALM)?).await?;
+            let demon_hunters_per_realm = *cfg.DemonHuntersPerRealm;
+            let demon_hunter_count = chars.iter().filter(|(_, _, class)| *class == Class::DemonHunter.to_num::<u8>()).count();
+            if demon_hunters_per_realm > 0 && demon_hunter_count >= demon_hunters_per_realm as usize {
+                return send_char_create(sock, ResponseCodes::CharCreateFailed, ObjectGuid::EMPTY);
+            }
+            let min_level = *cfg.CharacterCreating.MinLevelForDemonHunter;
+            if !chars.iter().any(|(level, _, _)| u32::from(*level) >= min_level) {
+                return send_char_create(sock, ResponseCodes::CharCreateLevelRequirementDemonHunter, ObjectGuid::EMPTY);
+            }
+        }
+
+        let guid = player_guid_gen.generate()?;
+        let level = create_new_character(
+            &char_db,
+            &cfg,
+            &db2,
+            &item_guid_gen,
+            session.account_id(),
+            u8::try_from(char_count).unwrap_or(u8::MAX),
+            guid,
+            &create_info,
+            player_info,
+        )
+        .await?;
+        character_cache.add_character_cache_entry(CharacterCacheEntry {
+            guid,
+            name: create_info.name.clone(),
+            account_id: session.account_id(),
+            race: create_info.race_id,
+            class: create_info.class_id,
+            sex: create_info.sex,
+            level,
+            guild_id: 0,
+            is_deleted: false,
+        });
+        update_realm_char_count(&char_db, &login_db, session.account_id(), current_realm.id.realm).await?;
+        send_char_create(sock, ResponseCodes::CharCreateSuccess, guid.into())?;
+        info!(target:"entities::player::character", "Account: {} Create Character: {} {}", session.account_id(), create_info.name, guid.counter());
+        script_mgr.on_player_create(&mut commands, guid.into());
+        Ok(())
+    })
+}
+
+/// Player::Create followed by Player::SaveToDB(true) in TC/AC, for a character that is not yet in world.
+/// Returns the level the character was created with.
+///
+/// TODO: Implement me: spells, skills, reputations, action buttons, taxi nodes and playercreateinfo_item are not set up yet
+#[expect(clippy::too_many_arguments)]
+async fn create_new_character(
+    char_db: &Pool<DbDriver>,
+    cfg: &WorldConfig,
+    db2: &DB2Mgr<'_>,
+    item_guid_gen: &ObjectGuidLowGenerator<HighGuidItem>,
+    account_id: u32,
+    slot: u8,
+    guid: ObjectGuid<HighGuidPlayer>,
+    create_info: &CharacterCreateInfo,
+    player_info: &PlayerInfo,
+) -> AzResult<u8> {
+    let is_death_knight = create_info.class_id == Class::DeathKnight.to_num::<u8>();
+    let is_demon_hunter = create_info.class_id == Class::DemonHunter.to_num::<u8>();
+    let (level, money) = if is_death_knight {
+        (
+            (*cfg.StartPlayerLevel).max(*cfg.StartDeathKnightPlayerLevel),
+            (*cfg.StartPlayerMoney).max(*cfg.StartDeathKnightPlayerMoney),
+        )
+    } else if is_demon_hunter {
+        (
+            (*cfg.StartPlayerLevel).max(*cfg.StartDemonHunterPlayerLevel),
+            (*cfg.StartPlayerMoney).max(*cfg.StartDemonHunterPlayerMoney),
+        )
+    } else {
+        (*cfg.StartPlayerLevel, *cfg.StartPlayerMoney)
+    };
+
+    // Starting outfit, i.e. Player::StoreNewItemInBestSlots for each of the items in CharStartOutfit
+    let mut inventory: [Option<u32>; INVENTORY_SLOT_ITEM_END as usize] = [None; INVENTORY_SLOT_ITEM_END as usize];
+    if let Some(outfit) = db2.char_start_outfits.get(&(create_info.race_id, create_info.class_id, create_info.sex)) {
+        for item_id in outfit.item_id.iter().filter_map(|i| u32::try_from(*i).ok()).filter(|i| *i > 0) {
+            let Some(item) = db2.stores.item_store.get(&item_id) else {
+                continue;
+            };
+            let inventory_type = InventoryType::from_u8(item.inventory_type).unwrap_or(InventoryType::NonEquip);
+            let free = |mut slots: std::ops::Range<u8>| slots.find(|s| inventory[*s as usize].is_none());
+            let slot = inventory_type
+                .equip_slots()
+                .iter()
+                .copied()
+                .find(|s| inventory[*s as usize].is_none())
+                .or_else(|| {
+                    if inventory_type == InventoryType::Bag {
+                        free(INVENTORY_SLOT_BAG_START..INVENTORY_SLOT_BAG_END)
+                    } else {
+                        None
+                    }
+                })
+                .or_else(|| free(INVENTORY_SLOT_ITEM_START..INVENTORY_SLOT_ITEM_END));
+            let Some(slot) = slot else {
+                debug!(target:"entities::player::items", "STORAGE: Can't equip or store initial item {item_id} for race {} class {}, not enough space", create_info.race_id, create_info.class_id);
+                continue;
+            };
+            inventory[slot as usize] = Some(item_id);
+        }
+    }
+
+    let mut equipment_cache = String::new();
+    for slot in 0..INVENTORY_SLOT_BAG_END {
+        let (inventory_type, display_id) = inventory[slot as usize].map_or((0, 0), |item_id| {
+            let inventory_type = db2.stores.item_store.get(&item_id).map_or(0, |i| i.inventory_type);
+            (inventory_type, db2.get_item_display_id(item_id, 0))
+        });
+        equipment_cache.push_str(&format!("{inventory_type} {display_id} 0 "));
+    }
+
+    let mut txn = char_db.begin().await?;
+    CharacterDatabase::ins_new_character(
+        &mut *txn,
+        args!(
+            guid.counter(),
+            account_id,
+            &create_info.name,
+            slot,
+            create_info.race_id,
+            create_info.class_id,
+            create_info.sex,
+            level,
+            money,
+            create_info.skin,
+            create_info.face,
+            create_info.hair_style,
+            create_info.hair_color,
+            create_info.facial_hair_style,
+            create_info.custom_display[0],
+            create_info.custom_display[1],
+            create_info.custom_display[2],
+            player_info.map_id,
+            player_info.zone_id,
+            player_info.position_x,
+            player_info.position_y,
+            player_info.position_z,
+            player_info.orientation,
+            // TODO: Implement me: starting taxi nodes
+            "",
+            FlagSet::from(AtLoginFlags::First).bits(),
+            equipment_cache
+        )?,
+    )
+    .await?;
+    for (slot, item_id) in inventory.iter().enumerate().filter_map(|(s, i)| i.map(|i| (s, i))) {
+        let item_guid = item_guid_gen.generate()?;
+        let (count, bonding) = db2
+            .stores
+            .item_sparse_store
+            .get(&item_id)
+            .map_or((1, 0), |i| (i.vendor_stack_count.max(1), i.bonding));
+        // BIND_ON_ACQUIRE and BIND_QUEST bind when stored, BIND_ON_EQUIP only once equipped
+        let flags = if bonding == 1 || bonding == 4 || (bonding == 2 && slot < usize::from(EQUIPMENT_SLOT_END)) {
+            ITEM_FIELD_FLAG_SOULBOUND
+        } else {
+            0
+        };
+        CharacterDatabase::rep_item_instance(
+            &mut *txn,
+            args!(
+                item_id,
+                guid.counter(),
+                0u64,
+                0u64,
+                count,
+                0i32,
+                "0 0 0 0 0 ",
+                flags,
+                "0 0 0 ".repeat(13),
+                0u32,
+                // TODO: Implement me: durability from the item's max durability
+                0u16,
+                0u32,
+                "",
+                item_guid
+            )?,
+        )
+        .await?;
+        CharacterDatabase::rep_inventory_item(&mut *txn, args!(guid.counter(), 0u64, slot as u8, item_guid)?).await?;
+    }
+    txn.commit().await?;
+    Ok(u8::try_from(level).unwrap_or(u8::MAX))
+}
+
+/// World::UpdateRealmCharCount in TC/AC
+async fn update_realm_char_count(char_db: &Pool<DbDriver>, login_db: &Pool<DbDriver>, account_id: u32, realm_id: u32) -> AzResult<()> {
+    let char_count = CharacterDatabase::sel_sum_chars::<_, (u64,)>(char_db, args!(account_id)?)
+        .await?
+        .map_or(0, |c| c.0);
+    let mut txn = login_db.begin().await?;
+    LoginDatabase::del_realm_characters_by_realm(&mut *txn, args!(account_id, realm_id)?).await?;
+    LoginDatabase::ins_realm_characters(&mut *txn, args!(char_count, account_id, realm_id)?).await?;
+    txn.commit().await?;
+    Ok(())
+}
+
+/// HandleCharDeleteOpcode in TC/AC, along with Player::DeleteFromDB
+#[expect(clippy::too_many_arguments)]
+fn handle_char_delete(
+    In((e, packet)): In<WorldSessionPacket>,
+    cfg: Res<ConfigMgr<WorldConfig>>,
+    rt: Res<TokioRuntime>,
+    char_db: Res<CharacterDatabase>,
+    login_db: Res<LoginDatabase>,
+    current_realm: Res<CurrentRealm>,
+    mut character_cache: ResMut<CharacterCache>,
+    sessions: Query<(&WorldSession, &WorldSocket)>,
+    mut commands: Commands,
+    script_mgr: ScriptMgr,
+) -> AzResult<()> {
+    let CharDelete { guid } = CharDelete::from_world_packet(packet)?;
+    let Ok((session, sock)) = sessions.get(e) else {
+        return Err(az_error!("no world session found for entity {e}"));
+    };
+
+    // TODO: Implement me: can't delete loaded character
+    // TODO: Implement me: CHAR_DELETE_FAILED_GUILD_LEADER and CHAR_DELETE_FAILED_ARENA_CAPTAIN checks
+
+    rt.block_on(async {
+        let Some((_, account_id, name, _, _, class, level)) =
+            CharacterDatabase::sel_data_by_guid::<_, (u64, u32, String, u8, u8, u8, u8)>(&**char_db, args!(guid.counter())?).await?
+        else {
+            return Ok(());
+        };
+        // prevent deleting other players' characters using cheating tools
+        if account_id != session.account_id() {
+            return Ok(());
+        }
+        info!(target:"entities::player::character", "Account: {account_id} Delete Character:[{name}] ({}) Level: {level}", guid.counter());
+        script_mgr.on_player_delete(&mut commands, guid.into(), account_id);
+        // TODO: Implement me: remove guild finder requests and calendar events
+
+        let char_delete_min_level = if class == Class::DeathKnight.to_num::<u8>() {
+            *cfg.CharDelete.DeathKnightMinLevel
+        } else if class == Class::DemonHunter.to_num::<u8>() {
+            *cfg.CharDelete.DemonHunterMinLevel
+        } else {
+            *cfg.CharDelete.MinLevel
+        };
+        // if the character does not meet the level requirement it is always removed
+        let method = if u32::from(level) < char_delete_min_level {
+            CharDeleteMethod::RemoveFromDB
+        } else {
+            cfg.CharDelete.Method
+        };
+
+        let guid_low = guid.counter();
+        let mut txn = char_db.begin().await?;
+        match method {
+            CharDeleteMethod::RemoveFromDB => {
+                // TODO: Implement me: return mails to their senders, delete pets properly and remove the character from groups
+                CharacterDatabase::del_character(&mut *txn, args!(guid_low)?).await?;
+                CharacterDatabase::del_char_declined_name(&mut *txn, args!(guid_low)?).await?;
+                CharacterDatabase::del_char_action(&mut *txn, args!(guid_low)?).await?;
+                CharacterDatabase::del_char_aura(&mut *txn, args!(guid_low)?).await?;
+                CharacterDatabase::del_char_gift(&mut *txn, args!(guid_low)?).await?;
+                CharacterDatabase::del_player_homebind(&mut *txn, args!(guid_low)?).await?;
+                CharacterDatabase::del_char_instance(&mut *txn, args!(guid_low)?).await?;
+                CharacterDatabase::del_char_inventory(&mut *txn, args!(guid_low)?).await?;
+                CharacterDatabase::del_item_instance_by_owner(&mut *txn, args!(guid_low)?).await?;
+                CharacterDatabase::del_char_queststatus(&mut *txn, args!(guid_low)?).await?;
+                CharacterDatabase::del_char_queststatus_rewarded(&mut *txn, args!(guid_low)?).await?;
+                CharacterDatabase::del_char_reputation(&mut *txn, args!(guid_low)?).await?;
+                CharacterDatabase::del_char_spell(&mut *txn, args!(guid_low)?).await?;
+                CharacterDatabase::del_char_spell_cooldown(&mut *txn, args!(guid_low)?).await?;
+                CharacterDatabase::del_char_social_by_guid(&mut *txn, args!(guid_low)?).await?;
+                CharacterDatabase::del_char_social_by_friend(&mut *txn, args!(guid_low)?).await?;
+                CharacterDatabase::del_mail(&mut *txn, args!(guid_low)?).await?;
+                CharacterDatabase::del_mail_items(&mut *txn, args!(guid_low)?).await?;
+                CharacterDatabase::del_char_pet_by_owner(&mut *txn, args!(guid_low)?).await?;
+                CharacterDatabase::del_char_pet_declinedname_by_owner(&mut *txn, args!(guid_low)?).await?;
+                CharacterDatabase::del_char_achievement(&mut *txn, args!(guid_low)?).await?;
+                CharacterDatabase::del_char_achievement_progress(&mut *txn, args!(guid_low)?).await?;
+                CharacterDatabase::del_char_equipmentsets(&mut *txn, args!(guid_low)?).await?;
+                CharacterDatabase::del_char_talent(&mut *txn, args!(guid_low)?).await?;
+                CharacterDatabase::del_char_skills(&mut *txn, args!(guid_low)?).await?;
+                CharacterDatabase::del_char_stats(&mut *txn, args!(guid_low)?).await?;
+            },
+            CharDeleteMethod::UnlinkFromAccount => {
+                CharacterDatabase::upd_delete_info(&mut *txn, args!(guid_low)?).await?;
+            },
+        }
+        txn.commit().await?;
+        match method {
+            CharDeleteMethod::RemoveFromDB => {
+                character_cache.delete_character_cache_entry(guid);
+            },
+            CharDeleteMethod::UnlinkFromAccount => {
+                // Mirrors upd_delete_info, which moves the character off its account
+                if let Err(e) = character_cache
+                    .update_character_info_deleted(guid, true, Some(""))
+                    .and_then(|_| character_cache.update_character_account_id(guid, 0))
+                {
+                    warn!(target:"entities::player::character", cause=?e, "unable to mark character {} as deleted in the character cache", guid.counter());
+                }
+            },
+        }
+        update_realm_char_count(&char_db, &login_db, account_id, current_realm.id.realm).await?;
+        sock.send_packet(
+            &DeleteChar {
+                code: ResponseCodes::CharDeleteSuccess,
+            }
+            .to_world_packet(),
+        )?;
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::visual_items_from_equipment_cache;
+    use crate::game::server::world_packets::character_packets::VisualItemInfo;
+
+    #[test]
+    fn equipment_cache_is_read_per_slot_and_defaults_to_empty() {
+        let visual_items = visual_items_from_equipment_cache("1 1234 0 0 0 0 0 0 0 0 0 0 5 42 7 ");
+        assert_eq!(
+            visual_items[0],
+            VisualItemInfo {
+                inventory_type:     1,
+                display_id:         1234,
+                display_enchant_id: 0,
+            }
+        );
+        assert_eq!(
+            visual_items[4],
+            VisualItemInfo {
+                inventory_type:     5,
+                display_id:         42,
+                display_enchant_id: 7,
+            }
+        );
+        assert!(visual_items[5..].iter().all(|v| *v == VisualItemInfo::default()));
+        assert!(visual_items_from_equipment_cache("").iter().all(|v| *v == VisualItemInfo::default()));
+    }
+}
@@ -1,16 +1,48 @@
 use std::{
+    collections::{BTreeMap, HashMap},
     fmt::Display,
     path::{Path, PathBuf},
+    time::Duration,
 };
 
-use azothacore_common::utils::buffered_file_open;
+use azothacore_common::{
+    az_error,
+    collision::management::{vmap_mgr2::VmapConfig, VMapMgr, VmapLoadError, VMAP_INVALID_HEIGHT_VALUE},
+    configuration::DataDirConfig,
+    utils::buffered_file_open,
+};
 pub use azothacore_common::{AzResult, MapLiquidTypeFlag};
-use flagset::FlagSet;
-use map_file::MapFile;
-use nalgebra::DMatrix;
+use bevy::prelude::{Component, Entity};
+use flagset::{flags, FlagSet};
+use map_file::{MapFile, MapHeightData, MapHeightFlightBox};
+use nalgebra::{DMatrix, SMatrix, Vector3};
 use num::Num;
+use tracing::{debug, error};
 
-use crate::game::{grid::grid_defines::ADT_CELLS_PER_GRID, world::WorldConfig};
+use crate::{
+    game::{
+        grid::{
+            grid_defines::{
+                compute_cell_coord,
+                compute_grid_coord,
+                CellCoord,
+                GridCoord,
+                ADT_CELLS_PER_GRID,
+                ADT_GRID_SIZE,
+                ADT_GRID_SIZE_PLUS_ONE,
+                CENTER_GRID_ID,
+                MAP_RESOLUTION,
+                MAX_NUMBER_OF_CELLS,
+                MAX_NUMBER_OF_GRIDS,
+                SIZE_OF_GRIDS,
+                SIZE_OF_GRID_CELL,
+            },
+            ngrid::{GridState, NGrid},
+        },
+        world::WorldConfig,
+    },
+    shared::data_stores::db2_structure::{AreaTable, LiquidType},
+};
 
 pub mod map_file;
 pub mod map_mgr;
@@ -21,6 +53,9 @@ pub struct MapLiquidData {
     pub map_liquid_entry_flags: Result<MapLiquidDataEntryFlags, MapLiquidDataGlobalEntryFlags>,
     pub offset_x:               u8,
     pub offset_y:               u8,
+    /// The size of the liquid area from the offsets, also kept for a single liquid level which covers only that area
+    pub width:                  u8,
+    pub height:                 u8,
     /// height is nrows, width is ncols
     pub liquid_height_details:  Result<DMatrix<f32>, f32>,
 }
@@ -53,7 +88,120 @@ pub struct MapLiquidDataEntryFlags {
     pub liquid_flags: [[FlagSet<MapLiquidTypeFlag>; ADT_CELLS_PER_GRID]; ADT_CELLS_PER_GRID],
 }
 
-pub struct GridMap {}
+/// MAX_HEIGHT in TC / AC
+pub const MAX_HEIGHT: f32 = 100000.0;
+/// INVALID_HEIGHT in TC / AC, the height returned when there is no height at the position
+pub const INVALID_HEIGHT: f32 = -100000.0;
+/// DEFAULT_HEIGHT_SEARCH in TC / AC
+pub const DEFAULT_HEIGHT_SEARCH: f32 = 50.0;
+/// GROUND_HEIGHT_TOLERANCE in TC / AC, tolerance used when comparing liquid levels to the ground
+pub const GROUND_HEIGHT_TOLERANCE: f32 = 0.05;
+/// The min height returned by GridMap::getMinHeight in TC when the grid has no flight box
+const DEFAULT_MIN_HEIGHT: f32 = -500.0;
+
+flags! {
+    /// ZLiquidStatus in TC / AC, LIQUID_MAP_NO_WATER is represented by the absence of a status instead
+    pub enum ZLiquidStatus: u8 {
+        AboveWater = 0x01,
+        WaterWalk  = 0x02,
+        InWater    = 0x04,
+        UnderWater = 0x08,
+        /// MAP_LIQUID_STATUS_SWIMMING in TC / AC
+        Swimming   = (ZLiquidStatus::InWater | ZLiquidStatus::UnderWater).bits(),
+        /// MAP_LIQUID_STATUS_IN_CONTACT in TC / AC
+        InContact  = (ZLiquidStatus::InWater | ZLiquidStatus::UnderWater | ZLiquidStatus::WaterWalk).bits(),
+    }
+}
+
+/// LiquidData in TC / AC
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LiquidData {
+    pub type_flags:  FlagSet<MapLiquidTypeFlag>,
+    /// the LiquidType entry
+    pub entry:       u32,
+    pub level:       f32,
+    /// the ground height below the liquid
+    pub depth_level: f32,
+}
+
+/// A plane given by its normal and distance to the origin, the same as G3D::Plane in TC.
+#[derive(Debug, Clone, Copy)]
+struct HeightPlane {
+    normal:   Vector3<f32>,
+    distance: f32,
+}
+
+impl HeightPlane {
+    fn from_points(p0: Vector3<f32>, p1: Vector3<f32>, p2: Vector3<f32>) -> Self {
+        let normal = (p1 - p0).cross(&(p2 - p0)).normalize();
+        Self {
+            normal,
+            distance: normal.dot(&p0),
+        }
+    }
+
+    fn distance(&self, p: &Vector3<f32>) -> f32 {
+        self.normal.dot(p) - self.distance
+    }
+}
+
+/// The flight box min heights are split into 8 triangles, each made up of 3 of the 3x3 min heights.
+const MIN_HEIGHT_PLANE_INDICES: [usize; 24] = [3, 0, 4, 0, 1, 4, 1, 2, 4, 2, 5, 4, 5, 8, 4, 8, 7, 4, 7, 6, 4, 6, 3, 4];
+const MIN_HEIGHT_BOUND_GRID_COORDS: [[f32; 2]; 9] = [
+    [0.0, 0.0],
+    [0.0, -SIZE_OF_GRIDS / 2.0],
+    [0.0, -SIZE_OF_GRIDS],
+    [-SIZE_OF_GRIDS / 2.0, 0.0],
+    [-SIZE_OF_GRIDS / 2.0, -SIZE_OF_GRIDS / 2.0],
+    [-SIZE_OF_GRIDS / 2.0, -SIZE_OF_GRIDS],
+    [-SIZE_OF_GRIDS, 0.0],
+    [-SIZE_OF_GRIDS, -SIZE_OF_GRIDS / 2.0],
+    [-SIZE_OF_GRIDS, -SIZE_OF_GRIDS],
+];
+
+/// Converts a world coordinate to the grid local coordinate at the given resolution,
+/// returning the integer part (wrapped within the grid) and the fractional part.
+fn grid_local_coord(v: f32, resolution: usize) -> (usize, f32) {
+    let v = resolution as f32 * (CENTER_GRID_ID as f32 - v / SIZE_OF_GRIDS);
+    let v_int = v as i32;
+    ((v_int & (resolution as i32 - 1)) as usize, v - v_int as f32)
+}
+
+type V9V8Heights = (
+    SMatrix<f32, ADT_GRID_SIZE_PLUS_ONE, ADT_GRID_SIZE_PLUS_ONE>,
+    SMatrix<f32, ADT_GRID_SIZE, ADT_GRID_SIZE>,
+);
+
+/// GridMap in TC / AC, the terrain data of a single grid as extracted into its .map file.
+pub struct GridMap {
+    area_data:         Result<[[u16; ADT_CELLS_PER_GRID]; ADT_CELLS_PER_GRID], u16>,
+    grid_height:       f32,
+    /// The V9 / V8 heights, unpacked into floats. `None` if the grid is flat at `grid_height`
+    heights:           Option<Box<V9V8Heights>>,
+    /// _minHeightPlanes in TC, taken from the flight box
+    min_height_planes: Option<[HeightPlane; 8]>,
+    liquid_data:       Option<MapLiquidData>,
+    holes:             Option<[[[u8; 8]; 16]; 16]>,
+}
+
+impl From<MapFile> for GridMap {
+    fn from(value: MapFile) -> Self {
+        let MapHeightData {
+            grid_height,
+            grid_max_height,
+            map_heights,
+            flight_box,
+        } = value.map_height_data;
+        Self {
+            area_data: value.map_area_data,
+            grid_height,
+            heights: map_heights.map(|h| Box::new(h.to_v9v8(grid_height, grid_max_height))),
+            min_height_planes: flight_box.as_ref().map(Self::min_height_planes),
+            liquid_data: value.map_liquid_data,
+            holes: value.map_holes,
+        }
+    }
+}
 
 impl GridMap {
     /// replaces Map::ExistMap in TC/AC
@@ -77,13 +225,844 @@ impl GridMap {
     {
         maps_dir.as_ref().join(format!("{map_id:04}_{gx:02}_{gy:02}.map"))
     }
+
+    /// GridMap::loadData in TC / AC
+    pub fn load<P, M, X, Y>(maps_dir: P, map_id: M, gx: X, gy: Y) -> AzResult<Self>
+    where
+        P: AsRef<Path>,
+        M: Num + Display,
+        X: Num + Display,
+        Y: Num + Display,
+    {
+        let file_name = GridMap::file_name(maps_dir, map_id, gx, gy);
+        let map_file = MapFile::read(&mut buffered_file_open(&file_name)?).map_err(|e| e.context(format!("error loading map file {}", file_name.display())))?;
+        Ok(map_file.into())
+    }
+
+    fn min_height_planes(flight_box: &MapHeightFlightBox) -> [HeightPlane; 8] {
+        std::array::from_fn(|quarter_index| {
+            let [p0, p1, p2] = std::array::from_fn(|i| {
+                let idx = MIN_HEIGHT_PLANE_INDICES[quarter_index * 3 + i];
+                let [x, y] = MIN_HEIGHT_BOUND_GRID_COORDS[idx];
+                // The flight box min heights are stored row by row
+                Vector3::new(x, y, f32::from(flight_box.min[(idx / 3, idx % 3)]))
+            });
+            HeightPlane::from_points(p0, p1, p2)
+        })
+    }
+
+    /// GridMap::getArea in TC / AC
+    pub fn get_area_id(&self, x: f32, y: f32) -> u16 {
+        match &self.area_data {
+            Err(grid_area) => *grid_area,
+            Ok(area_map) => {
+                let (lx, _) = grid_local_coord(x, ADT_CELLS_PER_GRID);
+                let (ly, _) = grid_local_coord(y, ADT_CELLS_PER_GRID);
+                area_map[lx][ly]
+            },
+        }
+    }
+
+    /// GridMap::isHole in TC / AC
+    fn is_hole(&self, row: usize, col: usize) -> bool {
+        let Some(holes) = &self.holes else {
+            return false;
+        };
+        let (cell_row, cell_col) = (row / 8, col / 8); // 8 squares per cell
+        let (hole_row, hole_col) = (row % 8, col % 8);
+        holes[cell_row][cell_col][hole_row] & (1 << hole_col) != 0
+    }
+
+    /// GridMap::getHeight in TC / AC, the terrain height at the given position.
+    ///
+    /// Heights are interpolated from the triangles formed by the V9 / V8 height maps, returns
+    /// [`INVALID_HEIGHT`] if the position is in a terrain hole.
+    pub fn get_height(&self, x: f32, y: f32) -> f32 {
+        let Some(heights) = &self.heights else {
+            return self.grid_height;
+        };
+        let (v9, v8) = &**heights;
+        let (x_int, x) = grid_local_coord(x, MAP_RESOLUTION);
+        let (y_int, y) = grid_local_coord(y, MAP_RESOLUTION);
+        if self.is_hole(x_int, y_int) {
+            return INVALID_HEIGHT;
+        }
+
+        // Height stored as: h5 - its v8 grid, h1-h4 - its v9 grid
+        // +--------------> X
+        // | h1-------h2     Coordinates is:
+        // | | \  1  / |     h1 0,0
+        // | |  \   /  |     h2 0,1
+        // | | 2  h5 3 |     h3 1,0
+        // | |  /   \  |     h4 1,1
+        // | | /  4  \ |     h5 1/2,1/2
+        // | h3-------h4
+        // V Y
+        // For find height need
+        // 1 - detect triangle
+        // 2 - solve linear equation from triangle points
+        // Calculate coefficients for solve h = a*x + b*y + c
+        let h5 = 2.0 * v8[(x_int, y_int)];
+        let (a, b, c) = if x + y < 1.0 {
+            if x > y {
+                // 1 triangle (h1, h2, h5 points)
+                let h1 = v9[(x_int, y_int)];
+                let h2 = v9[(x_int + 1, y_int)];
+                (h2 - h1, h5 - h1 - h2, h1)
+            } else {
+                // 2 triangle (h1, h3, h5 points)
+                let h1 = v9[(x_int, y_int)];
+                let h3 = v9[(x_int, y_int + 1)];
+                (h5 - h1 - h3, h3 - h1, h1)
+            }
+        } else if x > y {
+            // 3 triangle (h2, h4, h5 points)
+            let h2 = v9[(x_int + 1, y_int)];
+            let h4 = v9[(x_int + 1, y_int + 1)];
+            (h2 + h4 - h5, h4 - h2, h5 - h4)
+        } else {
+            // 4 triangle (h3, h4, h5 points)
+            let h3 = v9[(x_int, y_int + 1)];
+            let h4 = v9[(x_int + 1, y_int + 1)];
+            (h4 - h3, h3 + h4 - h5, h5 - h4)
+        };
+        // Calculate height
+        a * x + b * y + c
+    }
+
+    /// GridMap::getMinHeight in TC, the lowest height allowed at the position as given by the grid's flight box.
+    pub fn get_min_height(&self, x: f32, y: f32) -> f32 {
+        let Some(planes) = &self.min_height_planes else {
+            return DEFAULT_MIN_HEIGHT;
+        };
+        let cell = compute_cell_coord(x, y);
+        let (grid_x, cell_x) = (cell.x_coord / MAX_NUMBER_OF_CELLS, cell.x_coord % MAX_NUMBER_OF_CELLS);
+        let (grid_y, cell_y) = (cell.y_coord / MAX_NUMBER_OF_CELLS, cell.y_coord % MAX_NUMBER_OF_CELLS);
+        let gx = x - (grid_x as f32 - CENTER_GRID_ID as f32 + 1.0) * SIZE_OF_GRIDS;
+        let gy = y - (grid_y as f32 - CENTER_GRID_ID as f32 + 1.0) * SIZE_OF_GRIDS;
+
+        let quarter_index = if cell_y < MAX_NUMBER_OF_CELLS / 2 {
+            if cell_x < MAX_NUMBER_OF_CELLS / 2 {
+                4 + usize::from(gy > gx)
+            } else {
+                2 + usize::from((-SIZE_OF_GRIDS - gx) > gy)
+            }
+        } else if cell_x < MAX_NUMBER_OF_CELLS / 2 {
+            6 + usize::from((-SIZE_OF_GRIDS - gx) <= gy)
+        } else {
+            usize::from(gx > gy)
+        };
+        planes[quarter_index].distance(&Vector3::new(gx, gy, 0.0))
+    }
+
+    /// The liquid height at the given position of the height map, `None` if outside of the liquid's bounds.
+    fn liquid_height_at(liquid_data: &MapLiquidData, x_int: usize, y_int: usize) -> Option<f32> {
+        let lx = x_int.checked_sub(usize::from(liquid_data.offset_y))?;
+        let ly = y_int.checked_sub(usize::from(liquid_data.offset_x))?;
+        if lx >= usize::from(liquid_data.height) || ly >= usize::from(liquid_data.width) {
+            return None;
+        }
+        match &liquid_data.liquid_height_details {
+            Err(level) => Some(*level),
+            Ok(liquid_map) => liquid_map.get((lx, ly)).copied(),
+        }
+    }
+
+    /// GridMap::getLiquidLevel in TC / AC, returns [`INVALID_HEIGHT`] if there is no liquid at the position.
+    pub fn get_liquid_level(&self, x: f32, y: f32) -> f32 {
+        let Some(liquid_data) = &self.liquid_data else {
+            return INVALID_HEIGHT;
+        };
+        let (x_int, _) = grid_local_coord(x, MAP_RESOLUTION);
+        let (y_int, _) = grid_local_coord(y, MAP_RESOLUTION);
+        Self::liquid_height_at(liquid_data, x_int, y_int).unwrap_or(INVALID_HEIGHT)
+    }
+
+    /// GridMap::GetLiquidStatus in TC / AC, returns `None` if there is no liquid at the position (i.e. LIQUID_MAP_NO_WATER),
+    /// or if the liquid does not match the requested liquid type.
+    ///
+    /// The liquid type stores are the DB2 stores, they can be passed in directly from [`crate::shared::data_stores::DB2Storage`].
+    #[expect(clippy::too_many_arguments)]
+    pub fn get_liquid_status(
+        &self,
+        liquid_type_store: &BTreeMap<u32, LiquidType>,
+        area_table_store: &BTreeMap<u32, AreaTable>,
+        x: f32,
+        y: f32,
+        z: f32,
+        req_liquid_type: Option<FlagSet<MapLiquidTypeFlag>>,
+        collision_height: f32,
+    ) -> Option<(ZLiquidStatus, LiquidData)> {
+        // Check water type (if no water return)
+        let liquid_data = self.liquid_data.as_ref()?;
+        // Get cell
+        let (x_int, _) = grid_local_coord(x, MAP_RESOLUTION);
+        let (y_int, _) = grid_local_coord(y, MAP_RESOLUTION);
+
+        // Check water type in cell
+        let (entry, mut type_flags) = liquid_data.get_liquid_entry_flags(x_int >> 3, y_int >> 3);
+        let mut entry = u32::from(entry);
+        if let Some(liquid_entry) = liquid_type_store.get(&entry) {
+            type_flags &= MapLiquidTypeFlag::DarkWater;
+            let mut liq_type_idx = liquid_entry.sound_bank;
+            if entry < 21 {
+                if let Some(area) = area_table_store.get(&u32::from(self.get_area_id(x, y))) {
+                    let liquid_type_by_sound_bank = |a: &AreaTable| a.liquid_type_id.get(usize::from(liquid_entry.sound_bank)).copied().unwrap_or(0);
+                    let mut override_liquid = liquid_type_by_sound_bank(area);
+                    if override_liquid == 0 && area.parent_area_id != 0 {
+                        if let Some(parent_area) = area_table_store.get(&u32::from(area.parent_area_id)) {
+                            override_liquid = liquid_type_by_sound_bank(parent_area);
+                        }
+                    }
+                    if let Some(liq) = liquid_type_store.get(&u32::from(override_liquid)) {
+                        entry = u32::from(override_liquid);
+                        liq_type_idx = liq.sound_bank;
+                    }
+                }
+            }
+            type_flags |= MapLiquidTypeFlag::from_liquid_type_sound_bank_unchecked(liq_type_idx);
+        }
+        if type_flags.is_empty() {
+            return None;
+        }
+        // Check req liquid type mask
+        if req_liquid_type.is_some_and(|req| (req & type_flags).is_empty()) {
+            return None;
+        }
+
+        // Check water level:
+        // Check water height map
+        let liquid_level = Self::liquid_height_at(liquid_data, x_int, y_int)?;
+        // Get ground level
+        let ground_level = self.get_height(x, y);
+        // Check water level and ground level
+        if liquid_level < (ground_level - GROUND_HEIGHT_TOLERANCE) || z < (ground_level - GROUND_HEIGHT_TOLERANCE) {
+            return None;
+        }
+
+        // All ok in water -> store data
+        let data = LiquidData {
+            type_flags,
+            entry,
+            level: liquid_level,
+            depth_level: ground_level,
+        };
+        let delta = liquid_level - z;
+        let status = if delta > collision_height {
+            // Under water
+            ZLiquidStatus::UnderWater
+        } else if delta > 0.0 {
+            // In water
+            ZLiquidStatus::InWater
+        } else if delta > -0.1 {
+            // Walk on water
+            ZLiquidStatus::WaterWalk
+        } else {
+            // Above water
+            ZLiquidStatus::AboveWater
+        };
+        Some((status, data))
+    }
 }
 
-pub struct Map {}
+/// A change in the grids loaded by a [Map], used to load / unload the vmap and mmap tiles of the grid, which unlike the
+/// [GridMap] are shared with all other maps that have the same ID.
+///
+/// The grid coordinates are the ones used by the map, vmap and mmap files, i.e. the inverse of the [GridCoord].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GridTerrainChange {
+    Loaded { gx: usize, gy: usize },
+    Unloaded { gx: usize, gy: usize },
+}
+
+/// WorldObject::m_currMap in TC / AC, the [Map] entity that the world object is in.
+///
+/// The world object is placed in the cell of the map given by its [Position].
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CurrentMap(pub Entity);
+
+/// Marks world objects that keep the grids around them loaded, i.e. players and WorldObject::isActiveObject in TC / AC
+#[derive(Component, Debug, Default)]
+pub struct ActiveObject;
+
+/// InstanceMap in TC / AC, marks the [Map] of a dungeon, raid or scenario instance.
+#[derive(Component, Debug)]
+pub struct InstanceMap;
+
+/// Map in TC / AC, owns the [NGrid]s of a single map instance.
+///
+/// Base maps (i.e. continents) have an instance ID of 0, see [`crate::game::map::map_mgr::MapMgr`] for how they are created.
+#[derive(Component)]
+pub struct Map {
+    id: u32,
+    instance_id: u32,
+    /// m_VisibleDistance in TC / AC
+    visibility_range: f32,
+    /// m_VisibilityNotifyPeriod in TC / AC
+    visibility_notify_period: Duration,
+    /// i_gridExpiry in TC / AC
+    grid_expiry: Duration,
+    /// GridUnload config, if this is off grids are never unloaded once created
+    grid_unload: bool,
+    maps_dir: PathBuf,
+    /// i_grids in TC / AC, indexed by [`GridCoord::get_id`]
+    grids: Vec<Option<NGrid>>,
+    /// The cell that each world object in the map is in
+    object_cells: HashMap<Entity, CellCoord>,
+    terrain_changes: Vec<GridTerrainChange>,
+    /// Objects that were in grids that have since been unloaded
+    unloaded_objects: Vec<Entity>,
+}
 
 impl Map {
-    /// Map::ExistVMap
-    fn exist_v_map(cfg: &WorldConfig, map_id: u32, grid_x: usize, grid_y: usize) -> AzResult<()> {
-        todo!()
+    pub fn new(cfg: &WorldConfig, id: u32, instance_id: u32, visibility_range: f32, visibility_notify_period: Duration) -> Self {
+        Self {
+            id,
+            instance_id,
+            visibility_range,
+            visibility_notify_period,
+            grid_expiry: *cfg.GridCleanUpDelay,
+            grid_unload: cfg.GridUnload,
+            maps_dir: cfg.maps_dir(),
+            grids: std::iter::repeat_with(|| None).take(MAX_NUMBER_OF_GRIDS * MAX_NUMBER_OF_GRIDS).collect(),
+            object_cells: HashMap::new(),
+            terrain_changes: vec![],
+            unloaded_objects: vec![],
+        }
+    }
+
+    pub fn get_id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn get_instance_id(&self) -> u32 {
+        self.instance_id
+    }
+
+    pub fn get_visibility_range(&self) -> f32 {
+        self.visibility_range
+    }
+
+    pub fn get_visibility_notify_period(&self) -> Duration {
+        self.visibility_notify_period
+    }
+
+    pub fn get_ngrid(&self, coord: GridCoord) -> Option<&NGrid> {
+        self.grids.get(coord.get_id()).and_then(Option::as_ref)
+    }
+
+    fn get_ngrid_mut(&mut self, coord: GridCoord) -> Option<&mut NGrid> {
+        self.grids.get_mut(coord.get_id()).and_then(Option::as_mut)
+    }
+
+    /// Map::IsGridLoaded in TC / AC
+    pub fn is_grid_loaded(&self, coord: GridCoord) -> bool {
+        self.get_ngrid(coord).is_some()
+    }
+
+    pub fn loaded_grids(&self) -> impl Iterator<Item = &NGrid> {
+        self.grids.iter().flatten()
+    }
+
+    /// The [GridMap] of the grid at the given position, if the grid is loaded and has terrain data
+    pub fn get_grid_map(&self, x: f32, y: f32) -> Option<&GridMap> {
+        self.get_ngrid(compute_grid_coord(x, y)).and_then(NGrid::get_grid_map)
+    }
+
+    /// Map::GetStaticHeight in TC / Map::GetHeight in AC, using the terrain data of the grids loaded by the map
+    pub fn get_height<V: VMapMgr>(&self, vmgr: Option<&V>, x: f32, y: f32, z: f32, max_search_dist: f32) -> f32 {
+        Self::get_static_height(self.get_grid_map(x, y), vmgr, self.id, x, y, z, max_search_dist)
+    }
+
+    /// Map::EnsureGridCreated in TC / AC, also loads the grid's [GridMap] like Map::LoadMapAndVMap.
+    ///
+    /// Returns true if the grid was created.
+    fn ensure_grid_created(&mut self, coord: GridCoord) -> bool {
+        if self.is_grid_loaded(coord) {
+            return false;
+        }
+        // Terrain data is stored w/ the inverse grid coordinates
+        let gx = (MAX_NUMBER_OF_GRIDS - 1) - coord.x_coord;
+        let gy = (MAX_NUMBER_OF_GRIDS - 1) - coord.y_coord;
+        // NOTE: Unlike TC / AC, instance maps load their own copy of the grid map instead of referencing the one from
+        // their base map
+        let file_name = GridMap::file_name(&self.maps_dir, self.id, gx, gy);
+        let grid_map = if file_name.exists() {
+            debug!(target:"maps", "Loading map {}", file_name.display());
+            GridMap::load(&self.maps_dir, self.id, gx, gy)
+                .inspect_err(|e| error!(target:"maps", cause=?e, "Error loading map file: {}", file_name.display()))
+                .ok()
+        } else {
+            None
+        };
+        let mut grid = NGrid::new(coord, self.grid_expiry, self.grid_unload, grid_map);
+        grid.set_grid_state(GridState::Idle);
+        self.grids[coord.get_id()] = Some(grid);
+        self.terrain_changes.push(GridTerrainChange::Loaded { gx, gy });
+        debug!(target:"maps", "Created grid [{}, {}] for map {} instance {}", coord.x_coord, coord.y_coord, self.id, self.instance_id);
+        true
+    }
+
+    /// Map::EnsureGridLoaded in TC / AC, returns true if the grid was loaded.
+    ///
+    // TODO: Implement me: Loading of the grid's objects, i.e. ObjectGridLoader in TC / AC
+    pub fn ensure_grid_loaded(&mut self, cell: CellCoord) -> bool {
+        self.ensure_grid_created(cell.grid_coord())
+    }
+
+    /// Map::EnsureGridLoadedForActiveObject in TC / AC, along with loading all other grids within
+    /// visibility range of the position that TC / AC load when visiting the cells around an active object.
+    pub fn ensure_grid_loaded_for_active_object(&mut self, x: f32, y: f32) {
+        let cell = compute_cell_coord(x, y);
+        if !cell.is_coord_valid() {
+            return;
+        }
+        let range = self.visibility_range;
+        let grid_min = compute_cell_coord(x - range, y - range).normalize().grid_coord();
+        let grid_max = compute_cell_coord(x + range, y + range).normalize().grid_coord();
+        for grid_x in grid_min.x_coord..=grid_max.x_coord {
+            for grid_y in grid_min.y_coord..=grid_max.y_coord {
+                self.ensure_grid_created(GridCoord::new(grid_x, grid_y));
+            }
+        }
+        self.ensure_grid_loaded(cell);
+        let grid_expiry = self.grid_expiry;
+        let grid = self.get_ngrid_mut(cell.grid_coord()).expect("grid must have been loaded");
+        // refresh grid state & timer
+        if grid.get_grid_state() != GridState::Active {
+            grid.get_grid_info_mut().reset_time_tracker(grid_expiry.mul_f32(0.1));
+            grid.set_grid_state(GridState::Active);
+        }
+    }
+
+    /// Map::LoadAllCells in TC / AC, used when BaseMapLoadAllGrids / InstanceMapLoadAllGrids are enabled
+    pub fn load_all_grids(&mut self) {
+        for grid_x in 0..MAX_NUMBER_OF_GRIDS {
+            for grid_y in 0..MAX_NUMBER_OF_GRIDS {
+                self.ensure_grid_created(GridCoord::new(grid_x, grid_y));
+            }
+        }
+    }
+
+    pub fn contains_object(&self, obj: Entity) -> bool {
+        self.object_cells.contains_key(&obj)
+    }
+
+    /// Map::AddToMap / Map::*Relocation in TC / AC, places the object in the cell at the given position,
+    /// loading the grid if necessary. Returns false if the position is outside of the map.
+    pub fn add_or_relocate_object(&mut self, obj: Entity, x: f32, y: f32, is_active: bool) -> bool {
+        let cell = compute_cell_coord(x, y);
+        if !cell.is_coord_valid() {
+            error!(target:"maps", "Map::AddToMap: object {obj} has invalid coordinates X:{x} Y:{y} grid cell [{}:{}]", cell.x_coord, cell.y_coord);
+            return false;
+        }
+        if is_active {
+            self.ensure_grid_loaded_for_active_object(x, y);
+        } else {
+            self.ensure_grid_loaded(cell);
+        }
+        let old_cell = self.object_cells.insert(obj, cell);
+        if old_cell == Some(cell) {
+            return true;
+        }
+        if let Some(old_cell) = old_cell {
+            if let Some(old_grid) = self.get_ngrid_mut(old_cell.grid_coord()) {
+                old_grid.remove_world_object(old_cell, obj);
+            }
+        }
+        self.get_ngrid_mut(cell.grid_coord())
+            .expect("grid must have been loaded")
+            .add_world_object(cell, obj);
+        true
+    }
+
+    /// Map::RemoveFromMap in TC / AC, returns false if the object was not in the map
+    pub fn remove_object(&mut self, obj: Entity) -> bool {
+        let Some(cell) = self.object_cells.remove(&obj) else {
+            return false;
+        };
+        if let Some(grid) = self.get_ngrid_mut(cell.grid_coord()) {
+            grid.remove_world_object(cell, obj);
+        }
+        true
+    }
+
+    /// The objects in the cells around the given position that are within `radius`, i.e. the cells of the CellArea that
+    /// Cell::VisitAllObjects visits in TC / AC.
+    ///
+    /// As only the cells are checked, objects that are further away may be included as well.
+    pub fn get_objects_in_cell_area(&self, x: f32, y: f32, radius: f32) -> impl Iterator<Item = Entity> + '_ {
+        let cell_min = compute_cell_coord(x - radius, y - radius).normalize();
+        let cell_max = compute_cell_coord(x + radius, y + radius).normalize();
+        (cell_min.x_coord..=cell_max.x_coord)
+            .flat_map(move |cell_x| (cell_min.y_coord..=cell_max.y_coord).map(move |cell_y| CellCoord::new(cell_x, cell_y)))
+            .filter_map(|cell| Some(self.get_ngrid(cell.grid_coord())?.get_grid_type(cell).objects()))
+            .flatten()
+            .copied()
+    }
+
+    /// Map::ActiveObjectsNearGrid in TC / AC, the positions of all players / active objects in the map are given.
+    pub fn active_objects_near_grid(&self, grid: &NGrid, active_objects: &[(f32, f32)]) -> bool {
+        let mut cell_min = CellCoord::new(grid.get_x() * MAX_NUMBER_OF_CELLS, grid.get_y() * MAX_NUMBER_OF_CELLS);
+        let mut cell_max = CellCoord::new(cell_min.x_coord + MAX_NUMBER_OF_CELLS, cell_min.y_coord + MAX_NUMBER_OF_CELLS);
+        // we must find visible range in cells so we unload only non-visible cells...
+        let cell_range = (self.visibility_range / SIZE_OF_GRID_CELL).ceil() as usize + 1;
+        cell_min.dec_x(cell_range);
+        cell_min.dec_y(cell_range);
+        cell_max.inc_x(cell_range);
+        cell_max.inc_y(cell_range);
+        active_objects.iter().any(|(x, y)| {
+            let p = compute_cell_coord(*x, *y);
+            (cell_min.x_coord..=cell_max.x_coord).contains(&p.x_coord) && (cell_min.y_coord..=cell_max.y_coord).contains(&p.y_coord)
+        })
+    }
+
+    /// Map::Update and Map::DelayedUpdate in TC / AC, updates the grid states, unloading grids once they expire.
+    ///
+    /// The positions of all players / active objects in the map are given.
+    // TODO: Implement me: Updating of the map's objects and relocation notifies
+    pub fn update(&mut self, diff: Duration, active_objects: &[(f32, f32)]) {
+        for id in 0..self.grids.len() {
+            let Some(grid) = &self.grids[id] else {
+                continue;
+            };
+            let coord = GridCoord::new(grid.get_x(), grid.get_y());
+            match grid.get_grid_state() {
+                GridState::Invalid => {},
+                GridState::Active => {
+                    let active_near = self.active_objects_near_grid(grid, active_objects);
+                    let grid_expiry = self.grid_expiry;
+                    let grid = self.get_ngrid_mut(coord).expect("grid is loaded");
+                    // Only check grid activity every (grid_expiry/10) ms, because it's really useless to do it every cycle
+                    grid.get_grid_info_mut().update_time_tracker(diff);
+                    if grid.get_grid_info().time_tracker().finished() {
+                        if active_near {
+                            grid.get_grid_info_mut().reset_time_tracker(grid_expiry.mul_f32(0.1));
+                        } else {
+                            grid.set_grid_state(GridState::Idle);
+                            debug!(target:"maps", "Grid[{}, {}] on map {} moved to IDLE state", coord.x_coord, coord.y_coord, self.id);
+                        }
+                    }
+                },
+                GridState::Idle => {
+                    let grid_expiry = self.grid_expiry;
+                    let grid = self.get_ngrid_mut(coord).expect("grid is loaded");
+                    grid.get_grid_info_mut().reset_time_tracker(grid_expiry);
+                    grid.set_grid_state(GridState::Removal);
+                    debug!(target:"maps", "Grid[{}, {}] on map {} moved to REMOVAL state", coord.x_coord, coord.y_coord, self.id);
+                },
+                GridState::Removal => {
+                    if grid.get_grid_info().get_unload_lock() {
+                        continue;
+                    }
+                    let grid_expiry = self.grid_expiry;
+                    let grid = self.get_ngrid_mut(coord).expect("grid is loaded");
+                    grid.get_grid_info_mut().update_time_tracker(diff);
+                    if grid.get_grid_info().time_tracker().finished() && !self.unload_grid(coord, false, active_objects) {
+                        debug!(target:"maps", "Grid[{}, {}] for map {} differed unloading due to players or active objects nearby", coord.x_coord, coord.y_coord, self.id);
+                        let grid = self.get_ngrid_mut(coord).expect("grid is loaded");
+                        grid.get_grid_info_mut().reset_time_tracker(grid_expiry.mul_f32(0.1));
+                    }
+                },
+            }
+        }
+    }
+
+    /// Map::UnloadGrid in TC / AC, returns false if the grid could not be unloaded.
+    ///
+    /// The objects in the unloaded grid are removed from the map and can be taken with [`Map::drain_unloaded_objects`].
+    pub fn unload_grid(&mut self, coord: GridCoord, unload_all: bool, active_objects: &[(f32, f32)]) -> bool {
+        let Some(grid) = self.get_ngrid(coord) else {
+            return false;
+        };
+        if !unload_all && self.active_objects_near_grid(grid, active_objects) {
+            return false;
+        }
+        let grid = self.grids[coord.get_id()].take().expect("grid is loaded");
+        for obj in grid.world_objects() {
+            self.object_cells.remove(&obj);
+            self.unloaded_objects.push(obj);
+        }
+        let gx = (MAX_NUMBER_OF_GRIDS - 1) - coord.x_coord;
+        let gy = (MAX_NUMBER_OF_GRIDS - 1) - coord.y_coord;
+        self.terrain_changes.push(GridTerrainChange::Unloaded { gx, gy });
+        debug!(target:"maps", "Unloading grid[{}, {}] for map {} finished", coord.x_coord, coord.y_coord, self.id);
+        true
+    }
+
+    /// Changes to the grids loaded since the last call
+    pub fn drain_terrain_changes(&mut self) -> impl Iterator<Item = GridTerrainChange> + '_ {
+        self.terrain_changes.drain(..)
+    }
+
+    /// Objects that were removed from the map as their grids were unloaded since the last call
+    pub fn drain_unloaded_objects(&mut self) -> impl Iterator<Item = Entity> + '_ {
+        self.unloaded_objects.drain(..)
+    }
+
+    /// Map::ExistMap in TC / AC
+    pub fn exist_map(cfg: &WorldConfig, map_id: u32, grid_x: usize, grid_y: usize) -> AzResult<()> {
+        GridMap::exists(cfg.maps_dir(), map_id, grid_x, grid_y).map_err(|e| {
+            e.context(format!(
+                "Map file '{}' does not exist or is invalid! Please place MAP-files (*.map) in the appropriate directory ({}), or correct the DataDir setting in your worldserver.conf file.",
+                GridMap::file_name(cfg.maps_dir(), map_id, grid_x, grid_y).display(),
+                cfg.maps_dir().display(),
+            ))
+        })
+    }
+
+    /// Map::ExistVMap in TC / AC
+    pub fn exist_v_map<V: VMapMgr>(cfg: &WorldConfig, vmgr: &V, map_id: u32, grid_x: usize, grid_y: usize) -> AzResult<()> {
+        if !vmgr.is_map_loading_enabled() {
+            return Ok(());
+        }
+        let (x, y) = (grid_x.try_into()?, grid_y.try_into()?);
+        match vmgr.exists_map_tile(map_id, x, y) {
+            Ok(()) => Ok(()),
+            Err(VmapLoadError::FileNotFound(e)) => Err(az_error!(
+                "VMap file for map {map_id:04} [{grid_x:02}, {grid_y:02}] does not exist: {e}. Please place VMAP files (*.vmtree and *.vmtile) in the vmap directory ({}), or correct the DataDir setting in your worldserver.conf file.",
+                cfg.vmaps_dir().display(),
+            )),
+            Err(e @ VmapLoadError::VersionMismatched { .. }) => Err(az_error!(
+                "VMap file for map {map_id:04} [{grid_x:02}, {grid_y:02}] couldn't be loaded: {e}. This is because the version of the VMap file and the version of this module are different, please re-extract the maps with the tools compiled with this module."
+            )),
+        }
+    }
+
+    /// Map::GetStaticHeight in TC, Map::GetHeight in AC. Selects the more appropriate height out of the terrain height
+    /// from the grid map and the vmap height at the position.
+    ///
+    /// `vmgr` is `None` if vmap heights should not be checked.
+    pub fn get_static_height<V: VMapMgr>(grid_map: Option<&GridMap>, vmgr: Option<&V>, map_id: u32, x: f32, y: f32, z: f32, max_search_dist: f32) -> f32 {
+        // find raw .map surface under Z coordinates
+        let map_height = grid_map.map_or(VMAP_INVALID_HEIGHT_VALUE, |gmap| {
+            let grid_height = gmap.get_height(x, y);
+            // look from a bit higher pos to find the floor, ignore under surface case
+            if z + 2.0 > grid_height {
+                grid_height
+            } else {
+                VMAP_INVALID_HEIGHT_VALUE
+            }
+        });
+        // look from a bit higher pos to find the floor
+        let vmap_height = vmgr.map_or(VMAP_INVALID_HEIGHT_VALUE, |vmgr| vmgr.get_height(map_id, x, y, z + 2.0, max_search_dist));
+        select_static_height(map_height, vmap_height, z)
+    }
+}
+
+/// The height selection part of Map::GetStaticHeight in TC / Map::GetHeight in AC
+fn select_static_height(map_height: f32, vmap_height: f32, z: f32) -> f32 {
+    // mapHeight set for any above raw ground Z or <= INVALID_HEIGHT
+    // vmapheight set for any under Z value or <= INVALID_HEIGHT
+    if vmap_height > INVALID_HEIGHT {
+        if map_height > INVALID_HEIGHT {
+            // we have mapheight and vmapheight and must select more appropriate
+
+            // vmap height above map height
+            // or if the distance of the vmap height is less the land height distance
+            if vmap_height > map_height || (map_height - z).abs() > (vmap_height - z).abs() {
+                return vmap_height;
+            }
+            // better use .map surface height
+            return map_height;
+        }
+        // we have only vmapHeight (if have)
+        return vmap_height;
+    }
+    // explicitly use map data
+    map_height
+}
+
+#[cfg(test)]
+mod tests {
+    use map_file::MapFilev9v8;
+
+    use super::*;
+    use crate::game::grid::DEFAULT_VISIBILITY_NOTIFY_PERIOD;
+
+    fn map_file(map_heights: Option<MapFilev9v8>, map_liquid_data: Option<MapLiquidData>, map_holes: Option<[[[u8; 8]; 16]; 16]>) -> MapFile {
+        MapFile {
+            map_build_magic: 0,
+            map_area_data: Err(12),
+            map_height_data: MapHeightData {
+                grid_height: 0.0,
+                grid_max_height: 0.0,
+                map_heights,
+                flight_box: None,
+            },
+            map_liquid_data,
+            map_holes,
+        }
+    }
+
+    /// The world coordinate at the middle of the first height map square of the grid
+    const SQUARE_CENTRE: f32 = -SIZE_OF_GRIDS / (2 * MAP_RESOLUTION) as f32;
+
+    #[test]
+    fn get_height_interpolates_v9_v8_triangles_and_respects_holes() {
+        let mut v8 = SMatrix::zeros();
+        v8[(0, 0)] = 4.0;
+        let mut holes = [[[0u8; 8]; 16]; 16];
+        holes[0][0][0] = 1 << 1;
+        let gmap = GridMap::from(map_file(Some(MapFilev9v8::F32 { v9: SMatrix::zeros(), v8 }), None, Some(holes)));
+
+        assert_eq!(gmap.get_area_id(SQUARE_CENTRE, SQUARE_CENTRE), 12);
+        // The centre of the square is the V8 height
+        assert!((gmap.get_height(SQUARE_CENTRE, SQUARE_CENTRE) - 4.0).abs() < 1e-3);
+        // Halfway between a corner and the centre
+        assert!((gmap.get_height(SQUARE_CENTRE / 2.0, SQUARE_CENTRE / 2.0) - 2.0).abs() < 1e-3);
+        // The next square along y is a hole
+        assert_eq!(gmap.get_height(SQUARE_CENTRE, 3.0 * SQUARE_CENTRE), INVALID_HEIGHT);
+        // No height map means the whole grid is flat
+        assert_eq!(GridMap::from(map_file(None, None, None)).get_height(SQUARE_CENTRE, SQUARE_CENTRE), 0.0);
+    }
+
+    #[test]
+    fn get_liquid_status_compares_against_liquid_level() {
+        let liquid = MapLiquidData {
+            map_liquid_entry_flags: Err(MapLiquidDataGlobalEntryFlags {
+                liquid_flags: MapLiquidTypeFlag::Water.into(),
+                liquid_type:  0,
+            }),
+            offset_x:               0,
+            offset_y:               0,
+            width:                  ADT_GRID_SIZE_PLUS_ONE as u8,
+            height:                 ADT_GRID_SIZE_PLUS_ONE as u8,
+            liquid_height_details:  Err(5.0),
+        };
+        let gmap = GridMap::from(map_file(None, Some(liquid), None));
+        let status = |z, req| {
+            gmap.get_liquid_status(&BTreeMap::new(), &BTreeMap::new(), SQUARE_CENTRE, SQUARE_CENTRE, z, req, 2.0)
+                .map(|(s, _)| s)
+        };
+        assert_eq!(gmap.get_liquid_level(SQUARE_CENTRE, SQUARE_CENTRE), 5.0);
+        assert_eq!(status(1.0, None), Some(ZLiquidStatus::UnderWater));
+        assert_eq!(status(4.0, None), Some(ZLiquidStatus::InWater));
+        assert_eq!(status(5.05, None), Some(ZLiquidStatus::WaterWalk));
+        assert_eq!(status(10.0, None), Some(ZLiquidStatus::AboveWater));
+        // Below the ground
+        assert_eq!(status(-1.0, None), None);
+        assert_eq!(status(4.0, Some(MapLiquidTypeFlag::Magma.into())), None);
+        let (_, data) = gmap
+            .get_liquid_status(&BTreeMap::new(), &BTreeMap::new(), SQUARE_CENTRE, SQUARE_CENTRE, 4.0, None, 2.0)
+            .unwrap();
+        assert_eq!(
+            data,
+            LiquidData {
+                type_flags:  MapLiquidTypeFlag::Water.into(),
+                entry:       0,
+                level:       5.0,
+                depth_level: 0.0,
+            }
+        );
+    }
+
+    #[test]
+    fn get_liquid_level_is_bounded_by_the_liquid_area() {
+        let liquid = MapLiquidData {
+            map_liquid_entry_flags: Err(MapLiquidDataGlobalEntryFlags {
+                liquid_flags: MapLiquidTypeFlag::Water.into(),
+                liquid_type:  0,
+            }),
+            offset_x:               2,
+            offset_y:               0,
+            width:                  4,
+            height:                 4,
+            liquid_height_details:  Err(5.0),
+        };
+        let gmap = GridMap::from(map_file(None, Some(liquid), None));
+        // The centre of the height map square with the given indices
+        let level = |x_int: usize, y_int: usize| gmap.get_liquid_level((2 * x_int + 1) as f32 * SQUARE_CENTRE, (2 * y_int + 1) as f32 * SQUARE_CENTRE);
+        assert_eq!(level(0, 2), 5.0);
+        assert_eq!(level(3, 5), 5.0);
+        // Before the offset, past the height and past the width of the liquid
+        assert_eq!(level(0, 1), INVALID_HEIGHT);
+        assert_eq!(level(4, 2), INVALID_HEIGHT);
+        assert_eq!(level(0, 6), INVALID_HEIGHT);
+        let status = gmap.get_liquid_status(&BTreeMap::new(), &BTreeMap::new(), SQUARE_CENTRE, SQUARE_CENTRE, 4.0, None, 2.0);
+        assert_eq!(status, None);
+    }
+
+    #[test]
+    fn select_static_height_prefers_closest_floor() {
+        // vmap above the terrain, e.g. standing on a bridge
+        assert_eq!(select_static_height(10.0, 12.0, 13.0), 12.0);
+        // vmap below the terrain and further away
+        assert_eq!(select_static_height(10.0, 5.0, 11.0), 10.0);
+        assert_eq!(select_static_height(VMAP_INVALID_HEIGHT_VALUE, 5.0, 11.0), 5.0);
+        assert_eq!(select_static_height(10.0, VMAP_INVALID_HEIGHT_VALUE, 11.0), 10.0);
+    }
+
+    /// The world coordinate at the middle of the grid with the [`CENTER_GRID_ID`] coordinates
+    const GRID_CENTRE: f32 = SIZE_OF_GRIDS / 2.0;
+
+    #[test]
+    fn grids_load_around_active_objects_and_unload_once_expired() {
+        let cfg = WorldConfig::default();
+        let expiry = *cfg.GridCleanUpDelay;
+        let mut map = Map::new(&cfg, 0, 0, 300.0, DEFAULT_VISIBILITY_NOTIFY_PERIOD);
+        let (player, creature) = (Entity::from_raw(1), Entity::from_raw(2));
+        let centre = GridCoord::new(CENTER_GRID_ID, CENTER_GRID_ID);
+
+        assert!(map.add_or_relocate_object(player, GRID_CENTRE, GRID_CENTRE, true));
+        // The grid the player is in and the ones next to it within visibility range
+        assert_eq!(map.loaded_grids().count(), 9);
+        assert_eq!(map.get_ngrid(centre).unwrap().get_grid_state(), GridState::Active);
+        assert_eq!(map.drain_terrain_changes().count(), 9);
+        assert!(map.add_or_relocate_object(creature, GRID_CENTRE + 10.0, GRID_CENTRE + 10.0, false));
+        assert_eq!(map.get_ngrid(centre).unwrap().get_world_object_count_in_ngrid(), 2);
+        assert_eq!(map.get_objects_in_cell_area(GRID_CENTRE, GRID_CENTRE, 20.0).count(), 2);
+        assert_eq!(map.get_objects_in_cell_area(GRID_CENTRE + 200.0, GRID_CENTRE + 200.0, 20.0).count(), 0);
+
+        // Grids are kept while the player is around
+        map.update(expiry, &[(GRID_CENTRE, GRID_CENTRE)]);
+        assert_eq!(map.loaded_grids().count(), 9);
+        assert_eq!(map.get_ngrid(centre).unwrap().get_grid_state(), GridState::Active);
+
+        // Then go idle, get scheduled for removal and unload once the grid expires
+        assert!(map.remove_object(player));
+        map.update(expiry, &[]);
+        assert_eq!(map.get_ngrid(centre).unwrap().get_grid_state(), GridState::Idle);
+        map.update(expiry / 2, &[]);
+        assert_eq!(map.get_ngrid(centre).unwrap().get_grid_state(), GridState::Removal);
+        map.update(expiry / 2, &[]);
+        assert!(map.is_grid_loaded(centre));
+        map.update(expiry / 2, &[]);
+        assert_eq!(map.loaded_grids().count(), 0);
+        assert_eq!(map.drain_unloaded_objects().collect::<Vec<_>>(), vec![creature]);
+        assert!(!map.contains_object(creature));
+        assert!(map.drain_terrain_changes().all(|c| matches!(c, GridTerrainChange::Unloaded { .. })));
+    }
+
+    #[test]
+    fn grids_are_kept_without_grid_unload() {
+        let mut cfg = WorldConfig::default();
+        cfg.GridUnload = false;
+        let expiry = *cfg.GridCleanUpDelay;
+        let mut map = Map::new(&cfg, 0, 0, 100.0, DEFAULT_VISIBILITY_NOTIFY_PERIOD);
+        let obj = Entity::from_raw(1);
+        assert!(map.add_or_relocate_object(obj, GRID_CENTRE, GRID_CENTRE, true));
+        // Moving into the next grid along
+        assert!(map.add_or_relocate_object(obj, GRID_CENTRE + SIZE_OF_GRIDS, GRID_CENTRE, true));
+        assert_eq!(map.loaded_grids().count(), 2);
+        assert_eq!(
+            map.get_ngrid(GridCoord::new(CENTER_GRID_ID, CENTER_GRID_ID))
+                .unwrap()
+                .get_world_object_count_in_ngrid(),
+            0
+        );
+        assert_eq!(
+            map.get_ngrid(GridCoord::new(CENTER_GRID_ID + 1, CENTER_GRID_ID))
+                .unwrap()
+                .get_world_object_count_in_ngrid(),
+            1
+        );
+        assert!(!map.add_or_relocate_object(obj, MAX_HEIGHT, GRID_CENTRE, true));
+
+        map.remove_object(obj);
+        for _ in 0..4 {
+            map.update(expiry, &[]);
+        }
+        assert_eq!(map.loaded_grids().count(), 2);
     }
 }
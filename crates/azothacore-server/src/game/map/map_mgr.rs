@@ -1,16 +1,51 @@
+use std::collections::{BTreeMap, HashMap};
+
+use azothacore_common::{
+    bevy_app::az_startup_succeeded,
+    collision::management::{mmap_mgr::MMapMgr, vmap_mgr2::VMapMgr2, VMapMgr, VmapFactoryLoadError},
+    configuration::{ConfigMgr, DataDirConfig},
+};
 use bevy::{
-    prelude::Resource,
-    time::{Timer, TimerMode},
+    prelude::{
+        App,
+        Changed,
+        Commands,
+        DetectChanges,
+        Entity,
+        Has,
+        IntoSystemConfigs,
+        Or,
+        Query,
+        Ref,
+        RemovedComponents,
+        Res,
+        ResMut,
+        Resource,
+        SystemSet,
+        Update,
+        With,
+    },
+    time::{Time, Timer, TimerMode},
 };
+use tracing::{debug, error};
 
-use crate::game::{
-    grid::grid_defines::{compute_grid_coord, MAX_NUMBER_OF_GRIDS},
-    map::{GridMap, Map},
-    world::WorldConfig,
+use crate::{
+    game::{
+        conditions::disable_mgr::DisableMgr,
+        entities::object::Position,
+        grid::grid_defines::{compute_grid_coord, MAX_NUMBER_OF_GRIDS},
+        map::{ActiveObject, CurrentMap, GridTerrainChange, InstanceMap, Map},
+        world::WorldConfig,
+    },
+    shared::data_stores::{db2_structure::LiquidType, DB2Storage},
 };
 
+/// MapManager in TC / AC, keeps track of the [Map] entities that have been created.
+#[derive(Resource, Default)]
 pub struct MapMgr {
-    grid_update_timer: Timer,
+    /// i_maps in TC / AC along with the instances of MapInstanced, keyed by map ID and instance ID.
+    /// Base maps have an instance ID of 0.
+    maps: BTreeMap<(u32, u32), Entity>,
 }
 
 #[derive(Resource)]
@@ -32,18 +67,193 @@ impl From<&WorldConfig> for MapUpdateTimer {
 }
 
 impl MapMgr {
-    /// ExistMapAndVMap
-    fn exist_map_and_vmap(cfg: &WorldConfig, map_id: u32, x: f32, y: f32) -> bool {
+    /// MapManager::FindMap in TC / AC
+    pub fn find_map(&self, map_id: u32, instance_id: u32) -> Option<Entity> {
+        self.maps.get(&(map_id, instance_id)).copied()
+    }
+
+    /// MapManager::CreateBaseMap in TC / AC, returns the existing base map if it has already been created
+    pub fn create_base_map(&mut self, commands: &mut Commands, cfg: &WorldConfig, map_id: u32) -> Entity {
+        *self.maps.entry((map_id, 0)).or_insert_with(|| {
+            let mut map = Map::new(cfg, map_id, 0, *cfg.Visibility.DistanceContinents, *cfg.Visibility.NotifyPeriodOnContinents);
+            if cfg.BaseMapLoadAllGrids {
+                map.load_all_grids();
+            }
+            debug!(target:"maps", "MapInstanced::CreateBaseMap: created base map {map_id}");
+            commands.spawn(map).id()
+        })
+    }
+
+    /// MapInstanced::CreateInstance in TC / AC, returns the existing instance if it has already been created
+    // TODO: Implement me: BattlegroundMap, which uses the Visibility.DistanceBGArenas / NotifyPeriodInBGArenas configs instead
+    pub fn create_instance_map(&mut self, commands: &mut Commands, cfg: &WorldConfig, map_id: u32, instance_id: u32) -> Entity {
+        assert_ne!(instance_id, 0, "instance ID 0 is reserved for base maps");
+        *self.maps.entry((map_id, instance_id)).or_insert_with(|| {
+            let mut map = Map::new(
+                cfg,
+                map_id,
+                instance_id,
+                *cfg.Visibility.DistanceInstances,
+                *cfg.Visibility.NotifyPeriodInInstances,
+            );
+            if cfg.InstanceMapLoadAllGrids {
+                map.load_all_grids();
+            }
+            debug!(target:"maps", "MapInstanced::CreateInstance: map instance {instance_id} for map {map_id} created");
+            commands.spawn((map, InstanceMap)).id()
+        })
+    }
+
+    /// MapManager::ExistMapAndVMap in TC / AC
+    pub fn exist_map_and_vmap<V: VMapMgr>(cfg: &WorldConfig, vmgr: &V, map_id: u32, x: f32, y: f32) -> bool {
         let p = compute_grid_coord(x, y);
         let grid_x = (MAX_NUMBER_OF_GRIDS - 1) - p.x_coord;
         let grid_y = (MAX_NUMBER_OF_GRIDS - 1) - p.y_coord;
 
-        todo!()
-        // NOTE: Maybe replace Map::ExistMap w/ the one below? its clearer that we're checking on this
-        // GridMap::exists(cfg.maps_dir(), map_id, grid_x, grid_y)?;
+        if let Err(e) = Map::exist_map(cfg, map_id, grid_x, grid_y) {
+            error!(target:"maps", cause=?e, "map {map_id} does not exist at grid [{grid_x}, {grid_y}]");
+            return false;
+        }
+        if let Err(e) = Map::exist_v_map(cfg, vmgr, map_id, grid_x, grid_y) {
+            error!(target:"maps", cause=?e, "vmap {map_id} does not exist at grid [{grid_x}, {grid_y}]");
+            return false;
+        }
+        true
+    }
+}
+
+/// MapManager::Update in TC / AC
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MapMgrUpdateSet;
+
+/// The number of maps that have each vmap / mmap tile loaded, keyed by map ID and grid coordinates. These tiles are shared
+/// between all instances of a map.
+#[derive(Resource, Default)]
+struct LoadedTerrainTiles(HashMap<(u32, usize, usize), usize>);
 
-        // return Map::exist_map(cfg, map_id, grid_x, grid_y) && Map::exist_v_map(cfg, map_id, grid_x, grid_y);
+/// Places world objects with a [CurrentMap] in their maps, loads the grids around them and runs the map updates
+/// every `MapUpdateInterval`.
+pub fn map_mgr_plugin(app: &mut App) {
+    app.init_resource::<MapMgr>()
+        .init_resource::<MMapMgr>()
+        .init_resource::<LoadedTerrainTiles>()
+        .add_systems(
+            Update,
+            (remove_map_objects, relocate_map_objects, update_maps, load_and_unload_grid_terrain)
+                .chain()
+                .run_if(az_startup_succeeded())
+                .in_set(MapMgrUpdateSet),
+        );
+}
+
+/// Map::RemoveFromMap in TC / AC, for world objects that are no longer in a map or have been despawned
+fn remove_map_objects(mut removed: RemovedComponents<CurrentMap>, mut maps: Query<&mut Map>) {
+    for obj in removed.read() {
+        for mut map in &mut maps {
+            map.remove_object(obj);
+        }
+    }
+}
+
+/// Map::AddToMap / Map::PlayerRelocation in TC / AC, moves world objects into the cell of their current position
+#[expect(clippy::type_complexity)]
+fn relocate_map_objects(
+    mut maps: Query<(Entity, &mut Map)>,
+    objects: Query<(Entity, Ref<CurrentMap>, &Position, Has<ActiveObject>), Or<(Changed<CurrentMap>, Changed<Position>)>>,
+) {
+    for (obj, current_map, pos, is_active) in &objects {
+        if current_map.is_changed() {
+            for (_, mut map) in maps.iter_mut().filter(|(e, m)| *e != current_map.0 && m.contains_object(obj)) {
+                map.remove_object(obj);
+            }
+        }
+        let Ok((_, mut map)) = maps.get_mut(current_map.0) else {
+            error!(target:"maps", "world object {obj} is in map {} which does not exist", current_map.0);
+            continue;
+        };
+        map.add_or_relocate_object(obj, pos.x, pos.y, is_active);
+    }
+}
+
+/// MapManager::Update in TC / AC, updates all maps in parallel every `MapUpdateInterval`
+fn update_maps(
+    time: Res<Time>,
+    mut timer: ResMut<MapUpdateTimer>,
+    mut maps: Query<(Entity, &mut Map)>,
+    active_objects: Query<(&CurrentMap, &Position), With<ActiveObject>>,
+) {
+    timer.0.tick(time.delta());
+    if !timer.0.just_finished() {
+        return;
+    }
+    let diff = timer.0.duration() * timer.0.times_finished_this_tick();
+    let mut active_positions = HashMap::<_, Vec<_>>::new();
+    for (current_map, pos) in &active_objects {
+        active_positions.entry(current_map.0).or_default().push((pos.x, pos.y));
+    }
+    maps.par_iter_mut().for_each(|(e, mut map)| {
+        map.update(diff, active_positions.get(&e).map_or(&[], Vec::as_slice));
+    });
+}
 
-        // return Map::ExistMap(mapid, grid_x, grid_y) && Map::ExistVMap(mapid, grid_x, grid_y);
+/// Map::LoadMapAndVMap / Map::UnloadGrid in TC / AC, loads and unloads the vmap and mmap tiles of the grids that maps have loaded or
+/// unloaded, along with despawning the objects of the unloaded grids.
+fn load_and_unload_grid_terrain(
+    mut commands: Commands,
+    cfg: Res<ConfigMgr<WorldConfig>>,
+    mut maps: Query<&mut Map>,
+    mut vmgr: VMapMgr2<WorldConfig, DB2Storage<LiquidType>, DisableMgr>,
+    mut mmgr: ResMut<MMapMgr>,
+    mut loaded_tiles: ResMut<LoadedTerrainTiles>,
+) {
+    for mut map in &mut maps {
+        let map_id = map.get_id();
+        if map.is_added() && cfg.MoveMaps.Enabled {
+            if let Err(e) = mmgr.load_map_instance(cfg.mmaps_dir(), map_id, map.get_instance_id()) {
+                debug!(target:"maps", cause=?e, "Could not load MMAP instance {} for map {map_id}", map.get_instance_id());
+            }
+        }
+        let changes = map.drain_terrain_changes().collect::<Vec<_>>();
+        for change in changes {
+            match change {
+                GridTerrainChange::Loaded { gx, gy } => {
+                    let count = loaded_tiles.0.entry((map_id, gx, gy)).or_default();
+                    *count += 1;
+                    if *count > 1 {
+                        continue;
+                    }
+                    match vmgr.load_map_tile(map_id, gx as u16, gy as u16) {
+                        Ok(()) => debug!(target:"maps", "VMAP loaded name:{map_id}, id:{map_id}, x:{gx}, y:{gy} (vmap rep.: x:{gx}, y:{gy})"),
+                        Err(VmapFactoryLoadError::Ignored) => {
+                            debug!(target:"maps", "Ignored VMAP name:{map_id}, id:{map_id}, x:{gx}, y:{gy} (vmap rep.: x:{gx}, y:{gy})")
+                        },
+                        Err(e) => error!(target:"maps", cause=?e, "Could not load VMAP name:{map_id}, id:{map_id}, x:{gx}, y:{gy} (vmap rep.: x:{gx}, y:{gy})"),
+                    }
+                    if cfg.MoveMaps.Enabled {
+                        if let Err(e) = mmgr.load_map_tile(cfg.mmaps_dir(), map_id, gx as u16, gy as u16) {
+                            error!(target:"maps", cause=?e, "Could not load MMAP name:{map_id}, id:{map_id}, x:{gx}, y:{gy} (mmap rep.: x:{gx}, y:{gy})");
+                        }
+                    }
+                },
+                GridTerrainChange::Unloaded { gx, gy } => {
+                    let Some(count) = loaded_tiles.0.get_mut(&(map_id, gx, gy)) else {
+                        continue;
+                    };
+                    *count -= 1;
+                    if *count > 0 {
+                        continue;
+                    }
+                    loaded_tiles.0.remove(&(map_id, gx, gy));
+                    vmgr.unload_map_tile(map_id, gx as u16, gy as u16);
+                    mmgr.unload_map_tile(map_id, gx as u16, gy as u16);
+                },
+            }
+        }
+        // ObjectGridUnloader in TC / AC, objects of unloaded grids are removed along with the grid
+        for obj in map.drain_unloaded_objects() {
+            if let Some(mut obj) = commands.get_entity(obj) {
+                obj.despawn();
+            }
+        }
     }
 }
@@ -1,7 +1,12 @@
-use bevy::{app::App, prelude::SystemSet};
-use script_mgr::ScriptMgr;
+use bevy::{
+    app::App,
+    prelude::{IntoSystemConfigs, Startup, SystemSet},
+};
+use script_mgr::add_after_load_scripts;
 use tracing::info;
 
+use crate::game::{globals::object_mgr::load_script_names, world::WorldSets};
+
 pub mod script_defines;
 pub mod script_mgr;
 pub mod script_object;
@@ -11,7 +16,7 @@ pub struct ScriptsInitSet;
 
 /// TODO: Move this to its own crate as well, don't compile along with "azerothcore-server"
 /// crate
-pub fn scripts_plugin(_app: &mut App) {
+pub fn scripts_plugin(app: &mut App) {
     info!(target:"server::loading", "Initializing Scripts...");
     // Adding scripts first, then they can load modules
     // let mut script_mgr = ScriptMgr::default();
@@ -19,6 +24,12 @@ pub fn scripts_plugin(_app: &mut App) {
     // scripts::add_scripts(app.world_mut(), &mut script_mgr);
     // azothacore_module_scripts::add_scripts(app.world_mut(), &mut script_mgr);
     // app.insert_resource(script_mgr);
+
+    // Database bound scripts can only be added once the script names are known
+    app.add_systems(
+        Startup,
+        add_after_load_scripts.after(load_script_names).in_set(WorldSets::SetInitialWorldSettings),
+    );
 }
 
 // pub mod mod_skeleton_example;
@@ -1,5 +1,13 @@
 pub mod account_script;
+pub mod area_trigger_script;
+pub mod condition_script;
+pub mod creature_script;
 pub mod database_script;
+pub mod game_object_script;
+pub mod instance_map_script;
+pub mod item_script;
+pub mod player_script;
+pub mod spell_script;
 pub mod world_script;
 
 #[macro_export]
@@ -10,4 +18,10 @@ macro_rules! input_script_non {
         _sys = None;
         _sys
     }};
+    ( $input_ty:ty => $output_ty:ty ) => {{
+        use bevy::prelude::IntoSystem;
+        let mut _sys = Some(IntoSystem::into_system(|_inp: $input_ty| -> $output_ty { Default::default() }));
+        _sys = None;
+        _sys
+    }};
 }
@@ -0,0 +1,39 @@
+use bevy::{
+    ecs::system::SystemId,
+    prelude::{Component, Entity, In, System, World},
+};
+
+use crate::{
+    game::scripting::script_object::{IntoScriptObject, Script, ScriptObjectTrait},
+    input_script_non,
+};
+
+/// AreaTriggerScript in TC / AC, bound to the `ScriptName` of `areatrigger_scripts`.
+pub trait AreaTriggerScript: Script {
+    /// Called when the area trigger is activated by a player, with the player, the area trigger ID and whether the player entered it.
+    ///
+    /// Returning true prevents the core from handling the area trigger itself.
+    fn on_trigger(&self) -> Option<impl System<In = In<(Entity, u32, bool)>, Out = bool>> {
+        input_script_non!(In<(Entity, u32, bool)> => bool)
+    }
+}
+
+#[derive(Component, Clone)]
+#[expect(clippy::type_complexity)]
+pub struct AreaTriggerScriptObject {
+    pub on_trigger: Option<SystemId<In<(Entity, u32, bool)>, bool>>,
+}
+
+impl<S: AreaTriggerScript> IntoScriptObject<S, AreaTriggerScriptObject> for S {
+    fn create_from_systems(bevy_world: &mut World, s: &S) -> AreaTriggerScriptObject {
+        AreaTriggerScriptObject {
+            on_trigger: s.on_trigger().map(|sys| bevy_world.register_system(sys)),
+        }
+    }
+}
+
+impl ScriptObjectTrait for AreaTriggerScriptObject {
+    fn remove_systems_from_bevy(&self, bevy_world: &mut World) {
+        _ = self.on_trigger.map(|s| bevy_world.unregister_system(s));
+    }
+}
@@ -0,0 +1,51 @@
+use bevy::{
+    ecs::system::SystemId,
+    prelude::{Component, Entity, In, System, World},
+};
+
+use crate::{
+    game::scripting::script_object::{IntoScriptObject, Script, ScriptObjectTrait},
+    input_script_non,
+};
+
+/// MAX_CONDITION_TARGETS in TC / AC
+pub const MAX_CONDITION_TARGETS: usize = 3;
+
+/// The condition checked along with the objects it is checked against, Condition and ConditionSourceInfo in TC / AC
+#[derive(Clone, Debug)]
+pub struct ConditionSourceInfo {
+    pub source_type:       u32,
+    pub source_group:      u32,
+    pub source_entry:      i32,
+    pub source_id:         u32,
+    pub else_group:        u32,
+    pub condition_target:  u8,
+    pub condition_objects: [Option<Entity>; MAX_CONDITION_TARGETS],
+}
+
+/// ConditionScript in TC / AC, bound to the `ScriptName` of `conditions`.
+pub trait ConditionScript: Script {
+    /// Called when a single condition is checked for a player, returns whether the condition is met.
+    fn on_condition_check(&self) -> Option<impl System<In = In<ConditionSourceInfo>, Out = bool>> {
+        input_script_non!(In<ConditionSourceInfo> => bool)
+    }
+}
+
+#[derive(Component, Clone)]
+pub struct ConditionScriptObject {
+    pub on_condition_check: Option<SystemId<In<ConditionSourceInfo>, bool>>,
+}
+
+impl<S: ConditionScript> IntoScriptObject<S, ConditionScriptObject> for S {
+    fn create_from_systems(bevy_world: &mut World, s: &S) -> ConditionScriptObject {
+        ConditionScriptObject {
+            on_condition_check: s.on_condition_check().map(|sys| bevy_world.register_system(sys)),
+        }
+    }
+}
+
+impl ScriptObjectTrait for ConditionScriptObject {
+    fn remove_systems_from_bevy(&self, bevy_world: &mut World) {
+        _ = self.on_condition_check.map(|s| bevy_world.unregister_system(s));
+    }
+}
@@ -0,0 +1,36 @@
+use bevy::{
+    ecs::system::SystemId,
+    prelude::{Component, Entity, In, System, World},
+};
+
+use crate::{
+    game::scripting::script_object::{IntoScriptObject, Script, ScriptObjectTrait},
+    input_script_non,
+};
+
+/// CreatureScript in TC / AC, bound to the `ScriptName` of `creature_template` and `creature`.
+pub trait CreatureScript: Script {
+    /// Called when the creature is created, to set up the AI of the creature, i.e. insert its AI components.
+    fn get_ai(&self) -> Option<impl System<In = In<Entity>, Out = ()>> {
+        input_script_non!(In<Entity>)
+    }
+}
+
+#[derive(Component, Clone)]
+pub struct CreatureScriptObject {
+    pub get_ai: Option<SystemId<In<Entity>>>,
+}
+
+impl<S: CreatureScript> IntoScriptObject<S, CreatureScriptObject> for S {
+    fn create_from_systems(bevy_world: &mut World, s: &S) -> CreatureScriptObject {
+        CreatureScriptObject {
+            get_ai: s.get_ai().map(|sys| bevy_world.register_system(sys)),
+        }
+    }
+}
+
+impl ScriptObjectTrait for CreatureScriptObject {
+    fn remove_systems_from_bevy(&self, bevy_world: &mut World) {
+        _ = self.get_ai.map(|s| bevy_world.unregister_system(s));
+    }
+}
@@ -0,0 +1,36 @@
+use bevy::{
+    ecs::system::SystemId,
+    prelude::{Component, Entity, In, System, World},
+};
+
+use crate::{
+    game::scripting::script_object::{IntoScriptObject, Script, ScriptObjectTrait},
+    input_script_non,
+};
+
+/// GameObjectScript in TC / AC, bound to the `ScriptName` of `gameobject_template` and `gameobject`.
+pub trait GameObjectScript: Script {
+    /// Called when the gameobject is created, to set up the AI of the gameobject, i.e. insert its AI components.
+    fn get_ai(&self) -> Option<impl System<In = In<Entity>, Out = ()>> {
+        input_script_non!(In<Entity>)
+    }
+}
+
+#[derive(Component, Clone)]
+pub struct GameObjectScriptObject {
+    pub get_ai: Option<SystemId<In<Entity>>>,
+}
+
+impl<S: GameObjectScript> IntoScriptObject<S, GameObjectScriptObject> for S {
+    fn create_from_systems(bevy_world: &mut World, s: &S) -> GameObjectScriptObject {
+        GameObjectScriptObject {
+            get_ai: s.get_ai().map(|sys| bevy_world.register_system(sys)),
+        }
+    }
+}
+
+impl ScriptObjectTrait for GameObjectScriptObject {
+    fn remove_systems_from_bevy(&self, bevy_world: &mut World) {
+        _ = self.get_ai.map(|s| bevy_world.unregister_system(s));
+    }
+}
@@ -0,0 +1,94 @@
+use bevy::{
+    ecs::system::SystemId,
+    prelude::{Component, Entity, In, System, World},
+};
+
+use crate::{
+    game::scripting::script_object::{IntoScriptObject, Script, ScriptObjectTrait},
+    input_script_non,
+};
+
+/// InstanceMapScript in TC / AC, bound to the `script` of `instance_template`.
+///
+/// Instance maps are passed as their [Entity].
+pub trait InstanceMapScript: Script {
+    /// Called when the instance map is created, to set up the instance script of the map, i.e. insert its components.
+    fn get_instance_script(&self) -> Option<impl System<In = In<Entity>, Out = ()>> {
+        input_script_non!(In<Entity>)
+    }
+
+    /// Called when the map is created.
+    fn on_create(&self) -> Option<impl System<In = In<Entity>, Out = ()>> {
+        input_script_non!(In<Entity>)
+    }
+
+    /// Called just before the map is destroyed.
+    fn on_destroy(&self) -> Option<impl System<In = In<Entity>, Out = ()>> {
+        input_script_non!(In<Entity>)
+    }
+
+    /// Called when a grid map is loaded, with the grid X and Y.
+    fn on_load_grid_map(&self) -> Option<impl System<In = In<(Entity, u32, u32)>, Out = ()>> {
+        input_script_non!(In<(Entity, u32, u32)>)
+    }
+
+    /// Called when a grid map is unloaded, with the grid X and Y.
+    fn on_unload_grid_map(&self) -> Option<impl System<In = In<(Entity, u32, u32)>, Out = ()>> {
+        input_script_non!(In<(Entity, u32, u32)>)
+    }
+
+    /// Called when a player enters the map.
+    fn on_player_enter(&self) -> Option<impl System<In = In<(Entity, Entity)>, Out = ()>> {
+        input_script_non!(In<(Entity, Entity)>)
+    }
+
+    /// Called when a player leaves the map.
+    fn on_player_leave(&self) -> Option<impl System<In = In<(Entity, Entity)>, Out = ()>> {
+        input_script_non!(In<(Entity, Entity)>)
+    }
+
+    /// Called on every map update tick, with the diff in ms.
+    fn on_update(&self) -> Option<impl System<In = In<(Entity, u32)>, Out = ()>> {
+        input_script_non!(In<(Entity, u32)>)
+    }
+}
+
+#[derive(Component, Clone)]
+pub struct InstanceMapScriptObject {
+    pub get_instance_script: Option<SystemId<In<Entity>>>,
+    pub on_create:           Option<SystemId<In<Entity>>>,
+    pub on_destroy:          Option<SystemId<In<Entity>>>,
+    pub on_load_grid_map:    Option<SystemId<In<(Entity, u32, u32)>>>,
+    pub on_unload_grid_map:  Option<SystemId<In<(Entity, u32, u32)>>>,
+    pub on_player_enter:     Option<SystemId<In<(Entity, Entity)>>>,
+    pub on_player_leave:     Option<SystemId<In<(Entity, Entity)>>>,
+    pub on_update:           Option<SystemId<In<(Entity, u32)>>>,
+}
+
+impl<S: InstanceMapScript> IntoScriptObject<S, InstanceMapScriptObject> for S {
+    fn create_from_systems(bevy_world: &mut World, s: &S) -> InstanceMapScriptObject {
+        InstanceMapScriptObject {
+            get_instance_script: s.get_instance_script().map(|sys| bevy_world.register_system(sys)),
+            on_create:           s.on_create().map(|sys| bevy_world.register_system(sys)),
+            on_destroy:          s.on_destroy().map(|sys| bevy_world.register_system(sys)),
+            on_load_grid_map:    s.on_load_grid_map().map(|sys| bevy_world.register_system(sys)),
+            on_unload_grid_map:  s.on_unload_grid_map().map(|sys| bevy_world.register_system(sys)),
+            on_player_enter:     s.on_player_enter().map(|sys| bevy_world.register_system(sys)),
+            on_player_leave:     s.on_player_leave().map(|sys| bevy_world.register_system(sys)),
+            on_update:           s.on_update().map(|sys| bevy_world.register_system(sys)),
+        }
+    }
+}
+
+impl ScriptObjectTrait for InstanceMapScriptObject {
+    fn remove_systems_from_bevy(&self, bevy_world: &mut World) {
+        _ = self.get_instance_script.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_create.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_destroy.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_load_grid_map.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_unload_grid_map.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_player_enter.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_player_leave.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_update.map(|s| bevy_world.unregister_system(s));
+    }
+}
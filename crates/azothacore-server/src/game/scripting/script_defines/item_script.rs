@@ -0,0 +1,71 @@
+use bevy::{
+    ecs::system::SystemId,
+    prelude::{Component, Entity, In, System, World},
+};
+
+use crate::{
+    game::scripting::script_object::{IntoScriptObject, Script, ScriptObjectTrait},
+    input_script_non,
+};
+
+/// ItemScript in TC / AC, bound to the `ScriptName` of `item_script_names`.
+///
+/// Returning true from a hook prevents the core from handling the event itself.
+pub trait ItemScript: Script {
+    /// Called when a player accepts a quest from the item, with the player, the item and the quest ID.
+    fn on_quest_accept(&self) -> Option<impl System<In = In<(Entity, Entity, u32)>, Out = bool>> {
+        input_script_non!(In<(Entity, Entity, u32)> => bool)
+    }
+
+    /// Called when a player uses the item, with the player and the item.
+    fn on_use(&self) -> Option<impl System<In = In<(Entity, Entity)>, Out = bool>> {
+        input_script_non!(In<(Entity, Entity)> => bool)
+    }
+
+    /// Called when the item expires (is destroyed), with the player and the item entry.
+    fn on_expire(&self) -> Option<impl System<In = In<(Entity, u32)>, Out = bool>> {
+        input_script_non!(In<(Entity, u32)> => bool)
+    }
+
+    /// Called when the item is destroyed, with the player and the item.
+    fn on_remove(&self) -> Option<impl System<In = In<(Entity, Entity)>, Out = bool>> {
+        input_script_non!(In<(Entity, Entity)> => bool)
+    }
+
+    /// Called before casting a combat spell from the item chance on hit proc, with the player, the victim, the spell ID and the item.
+    fn on_cast_item_combat_spell(&self) -> Option<impl System<In = In<(Entity, Entity, u32, Entity)>, Out = bool>> {
+        input_script_non!(In<(Entity, Entity, u32, Entity)> => bool)
+    }
+}
+
+#[derive(Component, Clone)]
+#[expect(clippy::type_complexity)]
+pub struct ItemScriptObject {
+    pub on_quest_accept:           Option<SystemId<In<(Entity, Entity, u32)>, bool>>,
+    pub on_use:                    Option<SystemId<In<(Entity, Entity)>, bool>>,
+    pub on_expire:                 Option<SystemId<In<(Entity, u32)>, bool>>,
+    pub on_remove:                 Option<SystemId<In<(Entity, Entity)>, bool>>,
+    pub on_cast_item_combat_spell: Option<SystemId<In<(Entity, Entity, u32, Entity)>, bool>>,
+}
+
+impl<S: ItemScript> IntoScriptObject<S, ItemScriptObject> for S {
+    fn create_from_systems(bevy_world: &mut World, s: &S) -> ItemScriptObject {
+        ItemScriptObject {
+            on_quest_accept:           s.on_quest_accept().map(|sys| bevy_world.register_system(sys)),
+            on_use:                    s.on_use().map(|sys| bevy_world.register_system(sys)),
+            on_expire:                 s.on_expire().map(|sys| bevy_world.register_system(sys)),
+            on_remove:                 s.on_remove().map(|sys| bevy_world.register_system(sys)),
+            on_cast_item_combat_spell: s.on_cast_item_combat_spell().map(|sys| bevy_world.register_system(sys)),
+        }
+    }
+}
+
+impl ScriptObjectTrait for ItemScriptObject {
+    fn remove_systems_from_bevy(&self, bevy_world: &mut World) {
+        _ = self.on_quest_accept.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_use.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_expire.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_remove.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_cast_item_combat_spell.map(|s| bevy_world.unregister_system(s));
+    }
+}
@@ -0,0 +1,218 @@
+use bevy::{
+    ecs::system::SystemId,
+    prelude::{Component, Entity, In, System, World},
+};
+
+use crate::{
+    game::{
+        entities::object::object_guid::ObjectGuid,
+        scripting::script_object::{IntoScriptObject, Script, ScriptObjectTrait},
+    },
+    input_script_non,
+};
+
+/// PlayerScript in TC / AC, hooks on every player.
+///
+/// Players in world are passed as their [Entity], the ones that are not as their [ObjectGuid].
+pub trait PlayerScript: Script {
+    /// Called when a player kills another player, with the killer and the killed player.
+    fn on_pvp_kill(&self) -> Option<impl System<In = In<(Entity, Entity)>, Out = ()>> {
+        input_script_non!(In<(Entity, Entity)>)
+    }
+
+    /// Called when a player kills a creature, with the killer and the killed creature.
+    fn on_creature_kill(&self) -> Option<impl System<In = In<(Entity, Entity)>, Out = ()>> {
+        input_script_non!(In<(Entity, Entity)>)
+    }
+
+    /// Called when a player is killed by a creature, with the killer and the killed player.
+    fn on_player_killed_by_creature(&self) -> Option<impl System<In = In<(Entity, Entity)>, Out = ()>> {
+        input_script_non!(In<(Entity, Entity)>)
+    }
+
+    /// Called when a player's level changes (after the level is applied), with the old level.
+    fn on_level_changed(&self) -> Option<impl System<In = In<(Entity, u8)>, Out = ()>> {
+        input_script_non!(In<(Entity, u8)>)
+    }
+
+    /// Called when a player's free talent points change (right before the change is applied), with the new points.
+    fn on_free_talent_points_changed(&self) -> Option<impl System<In = In<(Entity, u32)>, Out = ()>> {
+        input_script_non!(In<(Entity, u32)>)
+    }
+
+    /// Called when a player's talent points are reset (right before the reset is done), with whether it was free.
+    fn on_talents_reset(&self) -> Option<impl System<In = In<(Entity, bool)>, Out = ()>> {
+        input_script_non!(In<(Entity, bool)>)
+    }
+
+    /// Called when a player's money is modified (before the modification is done), with the amount.
+    fn on_money_changed(&self) -> Option<impl System<In = In<(Entity, i64)>, Out = ()>> {
+        input_script_non!(In<(Entity, i64)>)
+    }
+
+    /// Called when a player's reputation changes (before it is actually changed), with the faction ID, the standing and whether it is incremental.
+    fn on_reputation_change(&self) -> Option<impl System<In = In<(Entity, u32, i32, bool)>, Out = ()>> {
+        input_script_non!(In<(Entity, u32, i32, bool)>)
+    }
+
+    /// Called when a player sends a chat message, with the chat message type, the language and the message.
+    fn on_chat(&self) -> Option<impl System<In = In<(Entity, u32, u32, String)>, Out = ()>> {
+        input_script_non!(In<(Entity, u32, u32, String)>)
+    }
+
+    /// Called on the emote opcode, with the emote.
+    fn on_emote(&self) -> Option<impl System<In = In<(Entity, u32)>, Out = ()>> {
+        input_script_non!(In<(Entity, u32)>)
+    }
+
+    /// Called on the text emote opcode, with the text emote, the emote number and the target.
+    fn on_text_emote(&self) -> Option<impl System<In = In<(Entity, u32, u32, ObjectGuid)>, Out = ()>> {
+        input_script_non!(In<(Entity, u32, u32, ObjectGuid)>)
+    }
+
+    /// Called in Spell::Cast, with the spell ID and whether the cast checks are skipped.
+    fn on_spell_cast(&self) -> Option<impl System<In = In<(Entity, u32, bool)>, Out = ()>> {
+        input_script_non!(In<(Entity, u32, bool)>)
+    }
+
+    /// Called when a player logs in, with whether it is the first login of the character.
+    fn on_login(&self) -> Option<impl System<In = In<(Entity, bool)>, Out = ()>> {
+        input_script_non!(In<(Entity, bool)>)
+    }
+
+    /// Called when a player logs out.
+    fn on_logout(&self) -> Option<impl System<In = In<Entity>, Out = ()>> {
+        input_script_non!(In<Entity>)
+    }
+
+    /// Called when a player is created.
+    fn on_create(&self) -> Option<impl System<In = In<ObjectGuid>, Out = ()>> {
+        input_script_non!(In<ObjectGuid>)
+    }
+
+    /// Called when a player is deleted, with the account ID the player belonged to.
+    fn on_delete(&self) -> Option<impl System<In = In<(ObjectGuid, u32)>, Out = ()>> {
+        input_script_non!(In<(ObjectGuid, u32)>)
+    }
+
+    /// Called when a player is saved to the database.
+    fn on_save(&self) -> Option<impl System<In = In<Entity>, Out = ()>> {
+        input_script_non!(In<Entity>)
+    }
+
+    /// Called when a player is bound to an instance, with the difficulty, the map ID and whether the bind is permanent.
+    fn on_bind_to_instance(&self) -> Option<impl System<In = In<(Entity, u8, u32, bool)>, Out = ()>> {
+        input_script_non!(In<(Entity, u8, u32, bool)>)
+    }
+
+    /// Called when a player switches to a new zone, with the new zone and area IDs.
+    fn on_update_zone(&self) -> Option<impl System<In = In<(Entity, u32, u32)>, Out = ()>> {
+        input_script_non!(In<(Entity, u32, u32)>)
+    }
+
+    /// Called when a player changes to a new map (after moving to new map).
+    fn on_map_changed(&self) -> Option<impl System<In = In<Entity>, Out = ()>> {
+        input_script_non!(In<Entity>)
+    }
+
+    /// Called after a player's quest status has been changed, with the quest ID.
+    fn on_quest_status_change(&self) -> Option<impl System<In = In<(Entity, u32)>, Out = ()>> {
+        input_script_non!(In<(Entity, u32)>)
+    }
+
+    /// Called when a player completes a movie, with the movie ID.
+    fn on_movie_complete(&self) -> Option<impl System<In = In<(Entity, u32)>, Out = ()>> {
+        input_script_non!(In<(Entity, u32)>)
+    }
+
+    /// Called when a player choose to release their spirit.
+    fn on_player_repop(&self) -> Option<impl System<In = In<Entity>, Out = ()>> {
+        input_script_non!(In<Entity>)
+    }
+}
+
+#[derive(Component, Clone)]
+#[expect(clippy::type_complexity)]
+pub struct PlayerScriptObject {
+    pub on_pvp_kill: Option<SystemId<In<(Entity, Entity)>>>,
+    pub on_creature_kill: Option<SystemId<In<(Entity, Entity)>>>,
+    pub on_player_killed_by_creature: Option<SystemId<In<(Entity, Entity)>>>,
+    pub on_level_changed: Option<SystemId<In<(Entity, u8)>>>,
+    pub on_free_talent_points_changed: Option<SystemId<In<(Entity, u32)>>>,
+    pub on_talents_reset: Option<SystemId<In<(Entity, bool)>>>,
+    pub on_money_changed: Option<SystemId<In<(Entity, i64)>>>,
+    pub on_reputation_change: Option<SystemId<In<(Entity, u32, i32, bool)>>>,
+    pub on_chat: Option<SystemId<In<(Entity, u32, u32, String)>>>,
+    pub on_emote: Option<SystemId<In<(Entity, u32)>>>,
+    pub on_text_emote: Option<SystemId<In<(Entity, u32, u32, ObjectGuid)>>>,
+    pub on_spell_cast: Option<SystemId<In<(Entity, u32, bool)>>>,
+    pub on_login: Option<SystemId<In<(Entity, bool)>>>,
+    pub on_logout: Option<SystemId<In<Entity>>>,
+    pub on_create: Option<SystemId<In<ObjectGuid>>>,
+    pub on_delete: Option<SystemId<In<(ObjectGuid, u32)>>>,
+    pub on_save: Option<SystemId<In<Entity>>>,
+    pub on_bind_to_instance: Option<SystemId<In<(Entity, u8, u32, bool)>>>,
+    pub on_update_zone: Option<SystemId<In<(Entity, u32, u32)>>>,
+    pub on_map_changed: Option<SystemId<In<Entity>>>,
+    pub on_quest_status_change: Option<SystemId<In<(Entity, u32)>>>,
+    pub on_movie_complete: Option<SystemId<In<(Entity, u32)>>>,
+    pub on_player_repop: Option<SystemId<In<Entity>>>,
+}
+
+impl<S: PlayerScript> IntoScriptObject<S, PlayerScriptObject> for S {
+    fn create_from_systems(bevy_world: &mut World, s: &S) -> PlayerScriptObject {
+        PlayerScriptObject {
+            on_pvp_kill: s.on_pvp_kill().map(|sys| bevy_world.register_system(sys)),
+            on_creature_kill: s.on_creature_kill().map(|sys| bevy_world.register_system(sys)),
+            on_player_killed_by_creature: s.on_player_killed_by_creature().map(|sys| bevy_world.register_system(sys)),
+            on_level_changed: s.on_level_changed().map(|sys| bevy_world.register_system(sys)),
+            on_free_talent_points_changed: s.on_free_talent_points_changed().map(|sys| bevy_world.register_system(sys)),
+            on_talents_reset: s.on_talents_reset().map(|sys| bevy_world.register_system(sys)),
+            on_money_changed: s.on_money_changed().map(|sys| bevy_world.register_system(sys)),
+            on_reputation_change: s.on_reputation_change().map(|sys| bevy_world.register_system(sys)),
+            on_chat: s.on_chat().map(|sys| bevy_world.register_system(sys)),
+            on_emote: s.on_emote().map(|sys| bevy_world.register_system(sys)),
+            on_text_emote: s.on_text_emote().map(|sys| bevy_world.register_system(sys)),
+            on_spell_cast: s.on_spell_cast().map(|sys| bevy_world.register_system(sys)),
+            on_login: s.on_login().map(|sys| bevy_world.register_system(sys)),
+            on_logout: s.on_logout().map(|sys| bevy_world.register_system(sys)),
+            on_create: s.on_create().map(|sys| bevy_world.register_system(sys)),
+            on_delete: s.on_delete().map(|sys| bevy_world.register_system(sys)),
+            on_save: s.on_save().map(|sys| bevy_world.register_system(sys)),
+            on_bind_to_instance: s.on_bind_to_instance().map(|sys| bevy_world.register_system(sys)),
+            on_update_zone: s.on_update_zone().map(|sys| bevy_world.register_system(sys)),
+            on_map_changed: s.on_map_changed().map(|sys| bevy_world.register_system(sys)),
+            on_quest_status_change: s.on_quest_status_change().map(|sys| bevy_world.register_system(sys)),
+            on_movie_complete: s.on_movie_complete().map(|sys| bevy_world.register_system(sys)),
+            on_player_repop: s.on_player_repop().map(|sys| bevy_world.register_system(sys)),
+        }
+    }
+}
+
+impl ScriptObjectTrait for PlayerScriptObject {
+    fn remove_systems_from_bevy(&self, bevy_world: &mut World) {
+        _ = self.on_pvp_kill.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_creature_kill.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_player_killed_by_creature.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_level_changed.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_free_talent_points_changed.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_talents_reset.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_money_changed.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_reputation_change.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_chat.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_emote.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_text_emote.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_spell_cast.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_login.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_logout.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_create.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_delete.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_save.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_bind_to_instance.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_update_zone.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_map_changed.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_quest_status_change.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_movie_complete.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_player_repop.map(|s| bevy_world.unregister_system(s));
+    }
+}
@@ -0,0 +1,172 @@
+use bevy::{
+    ecs::system::SystemId,
+    prelude::{Component, Entity, In, System, World},
+};
+
+use crate::{
+    game::scripting::script_object::{IntoScriptObject, Script, ScriptObjectTrait},
+    input_script_non,
+};
+
+/// SpellScriptLoader in TC / AC, bound to the `ScriptName` of `spell_script_names`.
+///
+/// Rather than creating a SpellScript for each spell cast, the hooks of the SpellScript are declared here and
+/// take the spell being cast as its [Entity].
+pub trait SpellScriptLoader: Script {
+    /// Called before the spell is cast.
+    fn before_cast(&self) -> Option<impl System<In = In<Entity>, Out = ()>> {
+        input_script_non!(In<Entity>)
+    }
+
+    /// Called when the spell is cast, before the spell is sent to the client.
+    fn on_cast(&self) -> Option<impl System<In = In<Entity>, Out = ()>> {
+        input_script_non!(In<Entity>)
+    }
+
+    /// Called after the spell is cast and sent to the client.
+    fn after_cast(&self) -> Option<impl System<In = In<Entity>, Out = ()>> {
+        input_script_non!(In<Entity>)
+    }
+
+    /// Called when the effect of the spell is launched, with the effect index.
+    fn on_effect_launch(&self) -> Option<impl System<In = In<(Entity, u8)>, Out = ()>> {
+        input_script_non!(In<(Entity, u8)>)
+    }
+
+    /// Called when the effect of the spell hits its destination, with the effect index.
+    fn on_effect_hit(&self) -> Option<impl System<In = In<(Entity, u8)>, Out = ()>> {
+        input_script_non!(In<(Entity, u8)>)
+    }
+
+    /// Called before the spell hits a target, with the target.
+    fn before_hit(&self) -> Option<impl System<In = In<(Entity, Entity)>, Out = ()>> {
+        input_script_non!(In<(Entity, Entity)>)
+    }
+
+    /// Called when the spell hits a target, with the target.
+    fn on_hit(&self) -> Option<impl System<In = In<(Entity, Entity)>, Out = ()>> {
+        input_script_non!(In<(Entity, Entity)>)
+    }
+
+    /// Called after the spell hits a target, with the target.
+    fn after_hit(&self) -> Option<impl System<In = In<(Entity, Entity)>, Out = ()>> {
+        input_script_non!(In<(Entity, Entity)>)
+    }
+}
+
+#[derive(Component, Clone)]
+pub struct SpellScriptLoaderObject {
+    pub before_cast:      Option<SystemId<In<Entity>>>,
+    pub on_cast:          Option<SystemId<In<Entity>>>,
+    pub after_cast:       Option<SystemId<In<Entity>>>,
+    pub on_effect_launch: Option<SystemId<In<(Entity, u8)>>>,
+    pub on_effect_hit:    Option<SystemId<In<(Entity, u8)>>>,
+    pub before_hit:       Option<SystemId<In<(Entity, Entity)>>>,
+    pub on_hit:           Option<SystemId<In<(Entity, Entity)>>>,
+    pub after_hit:        Option<SystemId<In<(Entity, Entity)>>>,
+}
+
+impl<S: SpellScriptLoader> IntoScriptObject<S, SpellScriptLoaderObject> for S {
+    fn create_from_systems(bevy_world: &mut World, s: &S) -> SpellScriptLoaderObject {
+        SpellScriptLoaderObject {
+            before_cast:      s.before_cast().map(|sys| bevy_world.register_system(sys)),
+            on_cast:          s.on_cast().map(|sys| bevy_world.register_system(sys)),
+            after_cast:       s.after_cast().map(|sys| bevy_world.register_system(sys)),
+            on_effect_launch: s.on_effect_launch().map(|sys| bevy_world.register_system(sys)),
+            on_effect_hit:    s.on_effect_hit().map(|sys| bevy_world.register_system(sys)),
+            before_hit:       s.before_hit().map(|sys| bevy_world.register_system(sys)),
+            on_hit:           s.on_hit().map(|sys| bevy_world.register_system(sys)),
+            after_hit:        s.after_hit().map(|sys| bevy_world.register_system(sys)),
+        }
+    }
+}
+
+impl ScriptObjectTrait for SpellScriptLoaderObject {
+    fn remove_systems_from_bevy(&self, bevy_world: &mut World) {
+        _ = self.before_cast.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_cast.map(|s| bevy_world.unregister_system(s));
+        _ = self.after_cast.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_effect_launch.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_effect_hit.map(|s| bevy_world.unregister_system(s));
+        _ = self.before_hit.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_hit.map(|s| bevy_world.unregister_system(s));
+        _ = self.after_hit.map(|s| bevy_world.unregister_system(s));
+    }
+}
+
+/// AuraScript in TC / AC, bound to the `ScriptName` of `spell_script_names` like [SpellScriptLoader].
+///
+/// The hooks take the aura as its [Entity].
+pub trait AuraScript: Script {
+    /// Called when the aura is dispelled by a unit.
+    fn on_dispel(&self) -> Option<impl System<In = In<Entity>, Out = ()>> {
+        input_script_non!(In<Entity>)
+    }
+
+    /// Called after the aura is dispelled by a unit.
+    fn after_dispel(&self) -> Option<impl System<In = In<Entity>, Out = ()>> {
+        input_script_non!(In<Entity>)
+    }
+
+    /// Called when the effect of the aura is applied, with the effect index.
+    fn on_effect_apply(&self) -> Option<impl System<In = In<(Entity, u8)>, Out = ()>> {
+        input_script_non!(In<(Entity, u8)>)
+    }
+
+    /// Called after the effect of the aura is applied, with the effect index.
+    fn after_effect_apply(&self) -> Option<impl System<In = In<(Entity, u8)>, Out = ()>> {
+        input_script_non!(In<(Entity, u8)>)
+    }
+
+    /// Called when the effect of the aura is removed, with the effect index.
+    fn on_effect_remove(&self) -> Option<impl System<In = In<(Entity, u8)>, Out = ()>> {
+        input_script_non!(In<(Entity, u8)>)
+    }
+
+    /// Called after the effect of the aura is removed, with the effect index.
+    fn after_effect_remove(&self) -> Option<impl System<In = In<(Entity, u8)>, Out = ()>> {
+        input_script_non!(In<(Entity, u8)>)
+    }
+
+    /// Called when the periodic effect of the aura ticks, with the effect index.
+    fn on_effect_periodic(&self) -> Option<impl System<In = In<(Entity, u8)>, Out = ()>> {
+        input_script_non!(In<(Entity, u8)>)
+    }
+}
+
+#[derive(Component, Clone)]
+pub struct AuraScriptObject {
+    pub on_dispel:           Option<SystemId<In<Entity>>>,
+    pub after_dispel:        Option<SystemId<In<Entity>>>,
+    pub on_effect_apply:     Option<SystemId<In<(Entity, u8)>>>,
+    pub after_effect_apply:  Option<SystemId<In<(Entity, u8)>>>,
+    pub on_effect_remove:    Option<SystemId<In<(Entity, u8)>>>,
+    pub after_effect_remove: Option<SystemId<In<(Entity, u8)>>>,
+    pub on_effect_periodic:  Option<SystemId<In<(Entity, u8)>>>,
+}
+
+impl<S: AuraScript> IntoScriptObject<S, AuraScriptObject> for S {
+    fn create_from_systems(bevy_world: &mut World, s: &S) -> AuraScriptObject {
+        AuraScriptObject {
+            on_dispel:           s.on_dispel().map(|sys| bevy_world.register_system(sys)),
+            after_dispel:        s.after_dispel().map(|sys| bevy_world.register_system(sys)),
+            on_effect_apply:     s.on_effect_apply().map(|sys| bevy_world.register_system(sys)),
+            after_effect_apply:  s.after_effect_apply().map(|sys| bevy_world.register_system(sys)),
+            on_effect_remove:    s.on_effect_remove().map(|sys| bevy_world.register_system(sys)),
+            after_effect_remove: s.after_effect_remove().map(|sys| bevy_world.register_system(sys)),
+            on_effect_periodic:  s.on_effect_periodic().map(|sys| bevy_world.register_system(sys)),
+        }
+    }
+}
+
+impl ScriptObjectTrait for AuraScriptObject {
+    fn remove_systems_from_bevy(&self, bevy_world: &mut World) {
+        _ = self.on_dispel.map(|s| bevy_world.unregister_system(s));
+        _ = self.after_dispel.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_effect_apply.map(|s| bevy_world.unregister_system(s));
+        _ = self.after_effect_apply.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_effect_remove.map(|s| bevy_world.unregister_system(s));
+        _ = self.after_effect_remove.map(|s| bevy_world.unregister_system(s));
+        _ = self.on_effect_periodic.map(|s| bevy_world.unregister_system(s));
+    }
+}
@@ -1,21 +1,33 @@
-use std::{collections::BTreeMap, marker::PhantomData};
+use std::{any::TypeId, collections::BTreeMap, marker::PhantomData};
 
 use azothacore_common::{configuration::DatabaseType, deref_boilerplate};
 use bevy::{
-    ecs::{system::SystemParam, world::Command},
+    ecs::{
+        system::{SystemId, SystemParam},
+        world::Command,
+    },
     prelude::*,
 };
 use flagset::FlagSet;
 
 use crate::game::{
+    entities::object::object_guid::ObjectGuid,
     globals::object_mgr::DBScriptNameStore,
     scripting::{
         script_defines::{
             account_script::{AccountScript, AccountScriptObject},
+            area_trigger_script::{AreaTriggerScript, AreaTriggerScriptObject},
+            condition_script::{ConditionScript, ConditionScriptObject, ConditionSourceInfo},
+            creature_script::{CreatureScript, CreatureScriptObject},
             database_script::{DatabaseScript, DatabaseScriptObject},
+            game_object_script::{GameObjectScript, GameObjectScriptObject},
+            instance_map_script::{InstanceMapScript, InstanceMapScriptObject},
+            item_script::{ItemScript, ItemScriptObject},
+            player_script::{PlayerScript, PlayerScriptObject},
+            spell_script::{AuraScript, AuraScriptObject, SpellScriptLoader, SpellScriptLoaderObject},
             world_script::{WorldScript, WorldScriptObject},
         },
-        script_object::{AfterLoadScriptObject, IntoScriptObject, Script, ScriptObject, ScriptObjectTrait},
+        script_object::{AfterLoadScriptObject, DatabaseBoundScript, IntoScriptObject, Script, ScriptObject, ScriptObjectTrait},
     },
 };
 
@@ -26,9 +38,15 @@ use crate::game::{
 
 #[derive(SystemParam)]
 pub struct ScriptMgr<'w, 's> {
-    world:    ScriptRegistry<'w, 's, WorldScriptObject>,
-    database: ScriptRegistry<'w, 's, DatabaseScriptObject>,
-    account:  ScriptRegistry<'w, 's, AccountScriptObject>,
+    world:        ScriptRegistry<'w, 's, WorldScriptObject>,
+    database:     ScriptRegistry<'w, 's, DatabaseScriptObject>,
+    account:      ScriptRegistry<'w, 's, AccountScriptObject>,
+    player:       ScriptRegistry<'w, 's, PlayerScriptObject>,
+    creature:     ScriptRegistry<'w, 's, CreatureScriptObject>,
+    game_object:  ScriptRegistry<'w, 's, GameObjectScriptObject>,
+    spell:        ScriptRegistry<'w, 's, SpellScriptLoaderObject>,
+    aura:         ScriptRegistry<'w, 's, AuraScriptObject>,
+    instance_map: ScriptRegistry<'w, 's, InstanceMapScriptObject>,
     // command:  ScriptRegistry<dyn CommandScript>,
 }
 
@@ -240,6 +258,488 @@ impl ScriptMgr<'_, '_> {
     }
 }
 
+/// PlayerScript functions
+impl ScriptMgr<'_, '_> {
+    pub fn register_player_script<S>(commands: &mut Commands, script_sys: S)
+    where
+        S: PlayerScript + IntoScriptObject<S, PlayerScriptObject> + Send + Sync + 'static,
+    {
+        commands.queue(AddScript::new(script_sys));
+    }
+
+    pub fn on_pvp_kill(&self, commands: &mut Commands, killer: Entity, killed: Entity) {
+        for (_, script) in &self.player.script_pointer_list {
+            if let Some(i) = script.on_pvp_kill {
+                commands.run_system_with_input(i, (killer, killed))
+            }
+        }
+    }
+
+    pub fn on_creature_kill(&self, commands: &mut Commands, killer: Entity, killed: Entity) {
+        for (_, script) in &self.player.script_pointer_list {
+            if let Some(i) = script.on_creature_kill {
+                commands.run_system_with_input(i, (killer, killed))
+            }
+        }
+    }
+
+    pub fn on_player_killed_by_creature(&self, commands: &mut Commands, killer: Entity, killed: Entity) {
+        for (_, script) in &self.player.script_pointer_list {
+            if let Some(i) = script.on_player_killed_by_creature {
+                commands.run_system_with_input(i, (killer, killed))
+            }
+        }
+    }
+
+    pub fn on_player_level_changed(&self, commands: &mut Commands, player: Entity, old_level: u8) {
+        for (_, script) in &self.player.script_pointer_list {
+            if let Some(i) = script.on_level_changed {
+                commands.run_system_with_input(i, (player, old_level))
+            }
+        }
+    }
+
+    pub fn on_player_free_talent_points_changed(&self, commands: &mut Commands, player: Entity, new_points: u32) {
+        for (_, script) in &self.player.script_pointer_list {
+            if let Some(i) = script.on_free_talent_points_changed {
+                commands.run_system_with_input(i, (player, new_points))
+            }
+        }
+    }
+
+    pub fn on_player_talents_reset(&self, commands: &mut Commands, player: Entity, no_cost: bool) {
+        for (_, script) in &self.player.script_pointer_list {
+            if let Some(i) = script.on_talents_reset {
+                commands.run_system_with_input(i, (player, no_cost))
+            }
+        }
+    }
+
+    pub fn on_player_money_changed(&self, commands: &mut Commands, player: Entity, amount: i64) {
+        for (_, script) in &self.player.script_pointer_list {
+            if let Some(i) = script.on_money_changed {
+                commands.run_system_with_input(i, (player, amount))
+            }
+        }
+    }
+
+    pub fn on_player_reputation_change(&self, commands: &mut Commands, player: Entity, faction_id: u32, standing: i32, incremental: bool) {
+        for (_, script) in &self.player.script_pointer_list {
+            if let Some(i) = script.on_reputation_change {
+                commands.run_system_with_input(i, (player, faction_id, standing, incremental))
+            }
+        }
+    }
+
+    pub fn on_player_chat(&self, commands: &mut Commands, player: Entity, chat_type: u32, lang: u32, msg: &str) {
+        for (_, script) in &self.player.script_pointer_list {
+            if let Some(i) = script.on_chat {
+                commands.run_system_with_input(i, (player, chat_type, lang, msg.to_string()))
+            }
+        }
+    }
+
+    pub fn on_player_emote(&self, commands: &mut Commands, player: Entity, emote: u32) {
+        for (_, script) in &self.player.script_pointer_list {
+            if let Some(i) = script.on_emote {
+                commands.run_system_with_input(i, (player, emote))
+            }
+        }
+    }
+
+    pub fn on_player_text_emote(&self, commands: &mut Commands, player: Entity, text_emote: u32, emote_num: u32, guid: ObjectGuid) {
+        for (_, script) in &self.player.script_pointer_list {
+            if let Some(i) = script.on_text_emote {
+                commands.run_system_with_input(i, (player, text_emote, emote_num, guid))
+            }
+        }
+    }
+
+    pub fn on_player_spell_cast(&self, commands: &mut Commands, player: Entity, spell_id: u32, skip_check: bool) {
+        for (_, script) in &self.player.script_pointer_list {
+            if let Some(i) = script.on_spell_cast {
+                commands.run_system_with_input(i, (player, spell_id, skip_check))
+            }
+        }
+    }
+
+    pub fn on_player_login(&self, commands: &mut Commands, player: Entity, first_login: bool) {
+        for (_, script) in &self.player.script_pointer_list {
+            if let Some(i) = script.on_login {
+                commands.run_system_with_input(i, (player, first_login))
+            }
+        }
+    }
+
+    pub fn on_player_logout(&self, commands: &mut Commands, player: Entity) {
+        for (_, script) in &self.player.script_pointer_list {
+            if let Some(i) = script.on_logout {
+                commands.run_system_with_input(i, player)
+            }
+        }
+    }
+
+    pub fn on_player_create(&self, commands: &mut Commands, guid: ObjectGuid) {
+        for (_, script) in &self.player.script_pointer_list {
+            if let Some(i) = script.on_create {
+                commands.run_system_with_input(i, guid)
+            }
+        }
+    }
+
+    pub fn on_player_delete(&self, commands: &mut Commands, guid: ObjectGuid, account_id: u32) {
+        for (_, script) in &self.player.script_pointer_list {
+            if let Some(i) = script.on_delete {
+                commands.run_system_with_input(i, (guid, account_id))
+            }
+        }
+    }
+
+    pub fn on_player_save(&self, commands: &mut Commands, player: Entity) {
+        for (_, script) in &self.player.script_pointer_list {
+            if let Some(i) = script.on_save {
+                commands.run_system_with_input(i, player)
+            }
+        }
+    }
+
+    pub fn on_player_bind_to_instance(&self, commands: &mut Commands, player: Entity, difficulty: u8, map_id: u32, permanent: bool) {
+        for (_, script) in &self.player.script_pointer_list {
+            if let Some(i) = script.on_bind_to_instance {
+                commands.run_system_with_input(i, (player, difficulty, map_id, permanent))
+            }
+        }
+    }
+
+    pub fn on_player_update_zone(&self, commands: &mut Commands, player: Entity, new_zone: u32, new_area: u32) {
+        for (_, script) in &self.player.script_pointer_list {
+            if let Some(i) = script.on_update_zone {
+                commands.run_system_with_input(i, (player, new_zone, new_area))
+            }
+        }
+    }
+
+    pub fn on_player_map_changed(&self, commands: &mut Commands, player: Entity) {
+        for (_, script) in &self.player.script_pointer_list {
+            if let Some(i) = script.on_map_changed {
+                commands.run_system_with_input(i, player)
+            }
+        }
+    }
+
+    pub fn on_quest_status_change(&self, commands: &mut Commands, player: Entity, quest_id: u32) {
+        for (_, script) in &self.player.script_pointer_list {
+            if let Some(i) = script.on_quest_status_change {
+                commands.run_system_with_input(i, (player, quest_id))
+            }
+        }
+    }
+
+    pub fn on_movie_complete(&self, commands: &mut Commands, player: Entity, movie_id: u32) {
+        for (_, script) in &self.player.script_pointer_list {
+            if let Some(i) = script.on_movie_complete {
+                commands.run_system_with_input(i, (player, movie_id))
+            }
+        }
+    }
+
+    pub fn on_player_repop(&self, commands: &mut Commands, player: Entity) {
+        for (_, script) in &self.player.script_pointer_list {
+            if let Some(i) = script.on_player_repop {
+                commands.run_system_with_input(i, player)
+            }
+        }
+    }
+}
+
+/// CreatureScript functions
+impl ScriptMgr<'_, '_> {
+    pub fn register_creature_script<S>(commands: &mut Commands, script_sys: S)
+    where
+        S: CreatureScript + IntoScriptObject<S, CreatureScriptObject> + Send + Sync + 'static,
+    {
+        commands.queue(AddScript::new_database_bound(script_sys));
+    }
+
+    /// Sets up the AI of `creature` from the script bound to `script_name`, returns false if there is no such script
+    /// so that the default AI is used instead
+    pub fn get_creature_ai(&self, commands: &mut Commands, script_name: &str, creature: Entity) -> bool {
+        let Some(i) = self.creature.get_by_name(script_name).and_then(|s| s.get_ai) else {
+            return false;
+        };
+        commands.run_system_with_input(i, creature);
+        true
+    }
+}
+
+/// GameObjectScript functions
+impl ScriptMgr<'_, '_> {
+    pub fn register_game_object_script<S>(commands: &mut Commands, script_sys: S)
+    where
+        S: GameObjectScript + IntoScriptObject<S, GameObjectScriptObject> + Send + Sync + 'static,
+    {
+        commands.queue(AddScript::new_database_bound(script_sys));
+    }
+
+    /// Sets up the AI of `gameobject` from the script bound to `script_name`, returns false if there is no such script
+    /// so that the default AI is used instead
+    pub fn get_game_object_ai(&self, commands: &mut Commands, script_name: &str, gameobject: Entity) -> bool {
+        let Some(i) = self.game_object.get_by_name(script_name).and_then(|s| s.get_ai) else {
+            return false;
+        };
+        commands.run_system_with_input(i, gameobject);
+        true
+    }
+}
+
+/// ItemScript functions
+impl ScriptMgr<'_, '_> {
+    pub fn register_item_script<S>(commands: &mut Commands, script_sys: S)
+    where
+        S: ItemScript + IntoScriptObject<S, ItemScriptObject> + Send + Sync + 'static,
+    {
+        commands.queue(AddScript::new_database_bound(script_sys));
+    }
+
+    pub fn on_quest_accept(bevy_world: &mut World, script_name: &str, player: Entity, item: Entity, quest_id: u32) -> bool {
+        run_bound_script_hook(
+            bevy_world,
+            script_name,
+            |s: &ItemScriptObject| s.on_quest_accept,
+            (player, item, quest_id),
+            false,
+        )
+    }
+
+    pub fn on_item_use(bevy_world: &mut World, script_name: &str, player: Entity, item: Entity) -> bool {
+        run_bound_script_hook(bevy_world, script_name, |s: &ItemScriptObject| s.on_use, (player, item), false)
+    }
+
+    pub fn on_item_expire(bevy_world: &mut World, script_name: &str, player: Entity, item_entry: u32) -> bool {
+        run_bound_script_hook(bevy_world, script_name, |s: &ItemScriptObject| s.on_expire, (player, item_entry), false)
+    }
+
+    pub fn on_item_remove(bevy_world: &mut World, script_name: &str, player: Entity, item: Entity) -> bool {
+        run_bound_script_hook(bevy_world, script_name, |s: &ItemScriptObject| s.on_remove, (player, item), false)
+    }
+
+    pub fn on_cast_item_combat_spell(bevy_world: &mut World, script_name: &str, player: Entity, victim: Entity, spell_id: u32, item: Entity) -> bool {
+        run_bound_script_hook(
+            bevy_world,
+            script_name,
+            |s: &ItemScriptObject| s.on_cast_item_combat_spell,
+            (player, victim, spell_id, item),
+            false,
+        )
+    }
+}
+
+/// AreaTriggerScript functions
+impl ScriptMgr<'_, '_> {
+    pub fn register_area_trigger_script<S>(commands: &mut Commands, script_sys: S)
+    where
+        S: AreaTriggerScript + IntoScriptObject<S, AreaTriggerScriptObject> + Send + Sync + 'static,
+    {
+        commands.queue(AddScript::new_database_bound(script_sys));
+    }
+
+    pub fn on_area_trigger(bevy_world: &mut World, script_name: &str, player: Entity, trigger_id: u32, entered: bool) -> bool {
+        run_bound_script_hook(
+            bevy_world,
+            script_name,
+            |s: &AreaTriggerScriptObject| s.on_trigger,
+            (player, trigger_id, entered),
+            false,
+        )
+    }
+}
+
+/// SpellScriptLoader functions
+impl ScriptMgr<'_, '_> {
+    pub fn register_spell_script_loader<S>(commands: &mut Commands, script_sys: S)
+    where
+        S: SpellScriptLoader + IntoScriptObject<S, SpellScriptLoaderObject> + Send + Sync + 'static,
+    {
+        commands.queue(AddScript::new_database_bound(script_sys));
+    }
+
+    pub fn on_spell_before_cast(&self, commands: &mut Commands, script_name: &str, spell: Entity) {
+        if let Some(i) = self.spell.get_by_name(script_name).and_then(|s| s.before_cast) {
+            commands.run_system_with_input(i, spell)
+        }
+    }
+
+    pub fn on_spell_cast(&self, commands: &mut Commands, script_name: &str, spell: Entity) {
+        if let Some(i) = self.spell.get_by_name(script_name).and_then(|s| s.on_cast) {
+            commands.run_system_with_input(i, spell)
+        }
+    }
+
+    pub fn on_spell_after_cast(&self, commands: &mut Commands, script_name: &str, spell: Entity) {
+        if let Some(i) = self.spell.get_by_name(script_name).and_then(|s| s.after_cast) {
+            commands.run_system_with_input(i, spell)
+        }
+    }
+
+    pub fn on_spell_effect_launch(&self, commands: &mut Commands, script_name: &str, spell: Entity, eff_index: u8) {
+        if let Some(i) = self.spell.get_by_name(script_name).and_then(|s| s.on_effect_launch) {
+            commands.run_system_with_input(i, (spell, eff_index))
+        }
+    }
+
+    pub fn on_spell_effect_hit(&self, commands: &mut Commands, script_name: &str, spell: Entity, eff_index: u8) {
+        if let Some(i) = self.spell.get_by_name(script_name).and_then(|s| s.on_effect_hit) {
+            commands.run_system_with_input(i, (spell, eff_index))
+        }
+    }
+
+    pub fn on_spell_before_hit(&self, commands: &mut Commands, script_name: &str, spell: Entity, target: Entity) {
+        if let Some(i) = self.spell.get_by_name(script_name).and_then(|s| s.before_hit) {
+            commands.run_system_with_input(i, (spell, target))
+        }
+    }
+
+    pub fn on_spell_hit(&self, commands: &mut Commands, script_name: &str, spell: Entity, target: Entity) {
+        if let Some(i) = self.spell.get_by_name(script_name).and_then(|s| s.on_hit) {
+            commands.run_system_with_input(i, (spell, target))
+        }
+    }
+
+    pub fn on_spell_after_hit(&self, commands: &mut Commands, script_name: &str, spell: Entity, target: Entity) {
+        if let Some(i) = self.spell.get_by_name(script_name).and_then(|s| s.after_hit) {
+            commands.run_system_with_input(i, (spell, target))
+        }
+    }
+}
+
+/// AuraScript functions
+impl ScriptMgr<'_, '_> {
+    pub fn register_aura_script<S>(commands: &mut Commands, script_sys: S)
+    where
+        S: AuraScript + IntoScriptObject<S, AuraScriptObject> + Send + Sync + 'static,
+    {
+        commands.queue(AddScript::new_database_bound(script_sys));
+    }
+
+    pub fn on_aura_dispel(&self, commands: &mut Commands, script_name: &str, aura: Entity) {
+        if let Some(i) = self.aura.get_by_name(script_name).and_then(|s| s.on_dispel) {
+            commands.run_system_with_input(i, aura)
+        }
+    }
+
+    pub fn on_aura_after_dispel(&self, commands: &mut Commands, script_name: &str, aura: Entity) {
+        if let Some(i) = self.aura.get_by_name(script_name).and_then(|s| s.after_dispel) {
+            commands.run_system_with_input(i, aura)
+        }
+    }
+
+    pub fn on_aura_effect_apply(&self, commands: &mut Commands, script_name: &str, aura: Entity, eff_index: u8) {
+        if let Some(i) = self.aura.get_by_name(script_name).and_then(|s| s.on_effect_apply) {
+            commands.run_system_with_input(i, (aura, eff_index))
+        }
+    }
+
+    pub fn on_aura_after_effect_apply(&self, commands: &mut Commands, script_name: &str, aura: Entity, eff_index: u8) {
+        if let Some(i) = self.aura.get_by_name(script_name).and_then(|s| s.after_effect_apply) {
+            commands.run_system_with_input(i, (aura, eff_index))
+        }
+    }
+
+    pub fn on_aura_effect_remove(&self, commands: &mut Commands, script_name: &str, aura: Entity, eff_index: u8) {
+        if let Some(i) = self.aura.get_by_name(script_name).and_then(|s| s.on_effect_remove) {
+            commands.run_system_with_input(i, (aura, eff_index))
+        }
+    }
+
+    pub fn on_aura_after_effect_remove(&self, commands: &mut Commands, script_name: &str, aura: Entity, eff_index: u8) {
+        if let Some(i) = self.aura.get_by_name(script_name).and_then(|s| s.after_effect_remove) {
+            commands.run_system_with_input(i, (aura, eff_index))
+        }
+    }
+
+    pub fn on_aura_effect_periodic(&self, commands: &mut Commands, script_name: &str, aura: Entity, eff_index: u8) {
+        if let Some(i) = self.aura.get_by_name(script_name).and_then(|s| s.on_effect_periodic) {
+            commands.run_system_with_input(i, (aura, eff_index))
+        }
+    }
+}
+
+/// InstanceMapScript functions
+impl ScriptMgr<'_, '_> {
+    pub fn register_instance_map_script<S>(commands: &mut Commands, script_sys: S)
+    where
+        S: InstanceMapScript + IntoScriptObject<S, InstanceMapScriptObject> + Send + Sync + 'static,
+    {
+        commands.queue(AddScript::new_database_bound(script_sys));
+    }
+
+    /// Sets up the instance script of `map` from the script bound to `script_name`, returns false if there is no such script
+    pub fn create_instance_data(&self, commands: &mut Commands, script_name: &str, map: Entity) -> bool {
+        let Some(i) = self.instance_map.get_by_name(script_name).and_then(|s| s.get_instance_script) else {
+            return false;
+        };
+        commands.run_system_with_input(i, map);
+        true
+    }
+
+    pub fn on_create_map(&self, commands: &mut Commands, script_name: &str, map: Entity) {
+        if let Some(i) = self.instance_map.get_by_name(script_name).and_then(|s| s.on_create) {
+            commands.run_system_with_input(i, map)
+        }
+    }
+
+    pub fn on_destroy_map(&self, commands: &mut Commands, script_name: &str, map: Entity) {
+        if let Some(i) = self.instance_map.get_by_name(script_name).and_then(|s| s.on_destroy) {
+            commands.run_system_with_input(i, map)
+        }
+    }
+
+    pub fn on_load_grid_map(&self, commands: &mut Commands, script_name: &str, map: Entity, gx: u32, gy: u32) {
+        if let Some(i) = self.instance_map.get_by_name(script_name).and_then(|s| s.on_load_grid_map) {
+            commands.run_system_with_input(i, (map, gx, gy))
+        }
+    }
+
+    pub fn on_unload_grid_map(&self, commands: &mut Commands, script_name: &str, map: Entity, gx: u32, gy: u32) {
+        if let Some(i) = self.instance_map.get_by_name(script_name).and_then(|s| s.on_unload_grid_map) {
+            commands.run_system_with_input(i, (map, gx, gy))
+        }
+    }
+
+    pub fn on_player_enter_map(&self, commands: &mut Commands, script_name: &str, map: Entity, player: Entity) {
+        if let Some(i) = self.instance_map.get_by_name(script_name).and_then(|s| s.on_player_enter) {
+            commands.run_system_with_input(i, (map, player))
+        }
+    }
+
+    pub fn on_player_leave_map(&self, commands: &mut Commands, script_name: &str, map: Entity, player: Entity) {
+        if let Some(i) = self.instance_map.get_by_name(script_name).and_then(|s| s.on_player_leave) {
+            commands.run_system_with_input(i, (map, player))
+        }
+    }
+
+    pub fn on_map_update(&self, commands: &mut Commands, script_name: &str, map: Entity, diff: u32) {
+        if let Some(i) = self.instance_map.get_by_name(script_name).and_then(|s| s.on_update) {
+            commands.run_system_with_input(i, (map, diff))
+        }
+    }
+}
+
+/// ConditionScript functions
+impl ScriptMgr<'_, '_> {
+    pub fn register_condition_script<S>(commands: &mut Commands, script_sys: S)
+    where
+        S: ConditionScript + IntoScriptObject<S, ConditionScriptObject> + Send + Sync + 'static,
+    {
+        commands.queue(AddScript::new_database_bound(script_sys));
+    }
+
+    /// Conditions with a script are only met if the script bound to `script_name` says so, or if there is no such script
+    pub fn on_condition_check(bevy_world: &mut World, script_name: &str, source_info: ConditionSourceInfo) -> bool {
+        run_bound_script_hook(bevy_world, script_name, |s: &ConditionScriptObject| s.on_condition_check, source_info, true)
+    }
+}
+
 // /// CommandScript functions
 // impl ScriptMgr {
 //     pub fn register_command_script(&mut self, script: Arc<dyn CommandScript>) {
@@ -308,19 +808,75 @@ where
     /// The actual list of scripts. This will be accessed concurrently, so it must not be modified
     /// after server startup.
     script_pointer_list: Query<'w, 's, (Entity, &'static Sc)>,
+    script_mapping:      Option<Res<'w, RegisteredScriptMapping>>,
+}
+
+impl<Sc> ScriptRegistry<'_, '_, Sc>
+where
+    Sc: Component,
+{
+    /// The script named `script_name`, i.e. the one bound to that `ScriptName` in the DB for database bound scripts
+    fn get_by_name(&self, script_name: &str) -> Option<&Sc> {
+        let e = self.script_mapping.as_ref()?.get_by_name::<Sc>(script_name)?;
+        self.script_pointer_list.get(e).ok().map(|(_, s)| s)
+    }
+}
+
+/// Runs the hook of the script named `script_name` that returns a value, falling back to `default` if there is no
+/// such script or it does not implement the hook
+fn run_bound_script_hook<O, I, R>(bevy_world: &mut World, script_name: &str, hook: impl FnOnce(&O) -> Option<SystemId<In<I>, R>>, input: I, default: R) -> R
+where
+    O: Component,
+    I: 'static,
+    R: 'static,
+{
+    let Some(sys) = bevy_world
+        .get_resource::<RegisteredScriptMapping>()
+        .and_then(|m| m.get_by_name::<O>(script_name))
+        .and_then(|e| bevy_world.get::<O>(e))
+        .and_then(hook)
+    else {
+        return default;
+    };
+    match bevy_world.run_system_with_input(sys, input) {
+        Err(e) => {
+            error!(cause=?e, script_name, "error running script hook");
+            default
+        },
+        Ok(r) => r,
+    }
 }
 
-/// A mapping of script names to their EntityID in bevy
+/// A mapping of script names to their EntityID in bevy, by the type of script object they were registered as.
+///
+/// Scripts of different types can share a name, i.e. the SpellScriptLoader and the AuraScript of the same spell.
 #[derive(Resource, Default)]
-struct RegisteredScriptMapping(BTreeMap<String, Entity>);
+pub struct RegisteredScriptMapping(BTreeMap<(TypeId, String), Entity>);
 
-deref_boilerplate!(RegisteredScriptMapping, BTreeMap<String, Entity>, 0);
+deref_boilerplate!(RegisteredScriptMapping, BTreeMap<(TypeId, String), Entity>, 0);
 
-struct AddScript<S, O>(S, PhantomData<O>);
+impl RegisteredScriptMapping {
+    /// The script of type `O` named `script_name`
+    fn get_by_name<O: Component>(&self, script_name: &str) -> Option<Entity> {
+        self.get(&(TypeId::of::<O>(), script_name.to_string())).copied()
+    }
+
+    /// If a script of any type is named `script_name`
+    fn contains_name(&self, script_name: &str) -> bool {
+        self.keys().any(|(_, n)| n == script_name)
+    }
+}
+
+/// Adds the script, with whether it belongs to one of the families that are always database bound
+struct AddScript<S, O>(S, bool, PhantomData<O>);
 
 impl<S, O> AddScript<S, O> {
     fn new(script_sys: S) -> Self {
-        Self(script_sys, PhantomData)
+        Self(script_sys, false, PhantomData)
+    }
+
+    fn new_database_bound(script_sys: S) -> Self {
+        Self(script_sys, true, PhantomData)
     }
 }
 
@@ -330,9 +886,13 @@ where
     O: ScriptObjectTrait,
 {
     fn apply(self, bevy_world: &mut World) {
-        let Self(script_sys, _) = self;
+        let Self(script_sys, database_bound, _) = self;
         let base = ScriptObject::create_from_systems(bevy_world, &script_sys);
         let obj = S::create_from_systems(bevy_world, &script_sys);
+        if database_bound {
+            bevy_world.spawn((base, AfterLoadScriptObject(Some(obj)), DatabaseBoundScript));
+            return;
+        }
         if bevy_world.run_system(base.is_afterload_script).unwrap() {
             bevy_world.spawn((base, AfterLoadScriptObject(Some(obj))));
             return;
@@ -364,7 +924,7 @@ where
         },
     };
     let mut script_mappings = bevy_world.get_resource_or_insert_with(|| RegisteredScriptMapping::default());
-    if let Some(old_e) = script_mappings.insert(script_name, e) {
+    if let Some(old_e) = script_mappings.insert((TypeId::of::<O>(), script_name), e) {
         bevy_world.despawn(old_e);
     }
 }
@@ -403,12 +963,13 @@ where
                 bevy_world.despawn(e);
                 continue;
             }
-            if !bevy_world.run_system(base.is_database_bound).unwrap() {
+            let database_bound = bevy_world.get::<DatabaseBoundScript>(e).is_some() || bevy_world.run_system(base.is_database_bound).unwrap();
+            if !database_bound {
                 // We're dealing with a code-only script; just add it.
                 register_script_mappings(bevy_world, script, ScriptBase::Existing(base.name, e));
                 continue;
             }
-            if !bevy_world.resource::<DBScriptNameStore>().contains(&base.name) {
+            if !bevy_world.get_resource::<DBScriptNameStore>().is_some_and(|s| s.contains(&base.name)) {
                 error!(target:"sql::sql","Script named '{}' is not assigned in the database, not adding", base.name);
                 base.remove_systems_from_bevy(bevy_world);
                 script.remove_systems_from_bevy(bevy_world);
@@ -419,3 +980,224 @@ where
         }
     }
 }
+
+/// Adds the afterload scripts of every script type once the script names are loaded from the DB, then reports the
+/// script names in the DB that no script is bound to.
+/// Equivalent to the AddALScripts calls and CheckIfScriptsInDatabaseExist of ScriptMgr::Initialize in AC
+pub fn add_after_load_scripts(mut commands: Commands) {
+    commands.queue(AddAfterLoadScripts::<WorldScriptObject>(PhantomData));
+    commands.queue(AddAfterLoadScripts::<DatabaseScriptObject>(PhantomData));
+    commands.queue(AddAfterLoadScripts::<AccountScriptObject>(PhantomData));
+    commands.queue(AddAfterLoadScripts::<PlayerScriptObject>(PhantomData));
+    commands.queue(AddAfterLoadScripts::<CreatureScriptObject>(PhantomData));
+    commands.queue(AddAfterLoadScripts::<GameObjectScriptObject>(PhantomData));
+    commands.queue(AddAfterLoadScripts::<ItemScriptObject>(PhantomData));
+    commands.queue(AddAfterLoadScripts::<AreaTriggerScriptObject>(PhantomData));
+    commands.queue(AddAfterLoadScripts::<SpellScriptLoaderObject>(PhantomData));
+    commands.queue(AddAfterLoadScripts::<AuraScriptObject>(PhantomData));
+    commands.queue(AddAfterLoadScripts::<InstanceMapScriptObject>(PhantomData));
+    commands.queue(AddAfterLoadScripts::<ConditionScriptObject>(PhantomData));
+    commands.queue(|bevy_world: &mut World| {
+        let Some(script_names) = bevy_world.get_resource::<DBScriptNameStore>() else {
+            return;
+        };
+        let script_mappings = bevy_world.get_resource::<RegisteredScriptMapping>();
+        for name in script_names.iter() {
+            if !script_mappings.is_some_and(|m| m.contains_name(name)) {
+                error!(target:"sql::sql", "Script '{name}' is referenced by the database, but does not exist in the core!");
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+
+    /// The hooks run by the test scripts, in order
+    #[derive(Resource, Default)]
+    struct HookCalls(Vec<String>);
+
+    fn record(calls: &mut HookCalls, hook: &str, input: impl std::fmt::Debug) {
+        calls.0.push(format!("{hook} {input:?}"));
+    }
+
+    struct NpcAssigned;
+
+    impl Script for NpcAssigned {}
+
+    impl CreatureScript for NpcAssigned {
+        fn get_ai(&self) -> Option<impl System<In = In<Entity>, Out = ()>> {
+            Some(IntoSystem::into_system(|In(creature): In<Entity>, mut calls: ResMut<HookCalls>| {
+                record(&mut calls, "get_ai", creature)
+            }))
+        }
+    }
+
+    struct NpcUnassigned;
+
+    impl Script for NpcUnassigned {}
+
+    impl CreatureScript for NpcUnassigned {}
+
+    struct ConditionNeverMet;
+
+    impl Script for ConditionNeverMet {}
+
+    impl ConditionScript for ConditionNeverMet {
+        fn on_condition_check(&self) -> Option<impl System<In = In<ConditionSourceInfo>, Out = bool>> {
+            Some(IntoSystem::into_system(|_: In<ConditionSourceInfo>| false))
+        }
+    }
+
+    /// Both the SpellScriptLoader and the AuraScript of the same spell
+    struct SpellBothFamilies;
+
+    impl Script for SpellBothFamilies {}
+
+    impl SpellScriptLoader for SpellBothFamilies {
+        fn on_cast(&self) -> Option<impl System<In = In<Entity>, Out = ()>> {
+            Some(IntoSystem::into_system(|In(spell): In<Entity>, mut calls: ResMut<HookCalls>| {
+                record(&mut calls, "on_cast", spell)
+            }))
+        }
+    }
+
+    impl AuraScript for SpellBothFamilies {
+        fn on_dispel(&self) -> Option<impl System<In = In<Entity>, Out = ()>> {
+            Some(IntoSystem::into_system(|In(aura): In<Entity>, mut calls: ResMut<HookCalls>| {
+                record(&mut calls, "on_dispel", aura)
+            }))
+        }
+    }
+
+    struct AccountLogins;
+
+    impl Script for AccountLogins {}
+
+    impl AccountScript for AccountLogins {
+        fn on_account_login(&self) -> Option<impl System<In = In<u32>, Out = ()> + 'static> {
+            Some(IntoSystem::into_system(|In(account_id): In<u32>, mut calls: ResMut<HookCalls>| {
+                record(&mut calls, "on_account_login", account_id)
+            }))
+        }
+
+        fn on_failed_account_login(&self) -> Option<impl System<In = In<u32>, Out = ()>> {
+            Some(IntoSystem::into_system(|In(account_id): In<u32>, mut calls: ResMut<HookCalls>| {
+                record(&mut calls, "on_failed_account_login", account_id)
+            }))
+        }
+    }
+
+    /// A world with the scripts registered by `register` added, as if `script_names` were the `ScriptName`s in the DB
+    fn world_with_scripts(script_names: &[&str], register: impl FnOnce(&mut Commands)) -> World {
+        let mut world = World::new();
+        world.init_resource::<HookCalls>();
+        let mut store = DBScriptNameStore::default();
+        store.extend(script_names.iter().map(|n| n.to_string()));
+        world.insert_resource(store);
+        register(&mut world.commands());
+        world.flush();
+        world.run_system_once(add_after_load_scripts).unwrap();
+        world
+    }
+
+    fn condition_source_info() -> ConditionSourceInfo {
+        ConditionSourceInfo {
+            source_type:       22,
+            source_group:      1,
+            source_entry:      0,
+            source_id:         0,
+            else_group:        0,
+            condition_target:  0,
+            condition_objects: [None; 3],
+        }
+    }
+
+    #[test]
+    fn it_rejects_database_bound_scripts_not_assigned_in_the_database() {
+        let mut world = world_with_scripts(&["NpcAssigned"], |commands| {
+            ScriptMgr::register_creature_script(commands, NpcAssigned);
+            ScriptMgr::register_creature_script(commands, NpcUnassigned);
+        });
+
+        let names = world.query::<&ScriptObject>().iter(&world).map(|s| s.name.clone()).collect::<Vec<_>>();
+        assert_eq!(names, ["NpcAssigned"]);
+        assert_eq!(world.query::<&CreatureScriptObject>().iter(&world).count(), 1);
+        let mapping = world.resource::<RegisteredScriptMapping>();
+        assert!(mapping.contains_name("NpcAssigned"));
+        assert!(!mapping.contains_name("NpcUnassigned"));
+    }
+
+    #[test]
+    fn it_resolves_bound_scripts_by_name() {
+        let mut world = world_with_scripts(&["NpcAssigned", "ConditionNeverMet"], |commands| {
+            ScriptMgr::register_creature_script(commands, NpcAssigned);
+            ScriptMgr::register_condition_script(commands, ConditionNeverMet);
+        });
+        let creature = world.spawn_empty().id();
+
+        let found = world
+            .run_system_once(move |mut commands: Commands, script_mgr: ScriptMgr| {
+                [
+                    script_mgr.get_creature_ai(&mut commands, "NpcAssigned", creature),
+                    script_mgr.get_creature_ai(&mut commands, "NpcUnassigned", creature),
+                    // A script of another type with the same name
+                    script_mgr.get_creature_ai(&mut commands, "ConditionNeverMet", creature),
+                ]
+            })
+            .unwrap();
+        assert_eq!(found, [true, false, false]);
+        assert_eq!(world.resource::<HookCalls>().0, [format!("get_ai {creature:?}")]);
+
+        assert!(!ScriptMgr::on_condition_check(&mut world, "ConditionNeverMet", condition_source_info()));
+    }
+
+    #[test]
+    fn it_meets_conditions_without_a_script() {
+        let mut world = world_with_scripts(&["NpcAssigned"], |commands| {
+            ScriptMgr::register_creature_script(commands, NpcAssigned);
+        });
+
+        assert!(ScriptMgr::on_condition_check(&mut world, "ConditionMissing", condition_source_info()));
+        // Bound to a script of another type
+        assert!(ScriptMgr::on_condition_check(&mut world, "NpcAssigned", condition_source_info()));
+    }
+
+    #[test]
+    fn it_keeps_spell_and_aura_scripts_sharing_a_name() {
+        let mut world = world_with_scripts(&["SpellBothFamilies"], |commands| {
+            ScriptMgr::register_spell_script_loader(commands, SpellBothFamilies);
+            ScriptMgr::register_aura_script(commands, SpellBothFamilies);
+        });
+        let spell = world.spawn_empty().id();
+        let aura = world.spawn_empty().id();
+
+        world
+            .run_system_once(move |mut commands: Commands, script_mgr: ScriptMgr| {
+                script_mgr.on_spell_cast(&mut commands, "SpellBothFamilies", spell);
+                script_mgr.on_aura_dispel(&mut commands, "SpellBothFamilies", aura);
+            })
+            .unwrap();
+        assert_eq!(world.resource::<HookCalls>().0, [format!("on_cast {spell:?}"), format!("on_dispel {aura:?}")]);
+    }
+
+    #[test]
+    fn it_runs_account_login_hooks() {
+        let mut world = world_with_scripts(&[], |commands| {
+            ScriptMgr::register_account_script(commands, AccountLogins);
+        });
+
+        world
+            .run_system_once(|mut commands: Commands, script_mgr: ScriptMgr| {
+                script_mgr.on_account_login(&mut commands, 1);
+                script_mgr.on_failed_account_login(&mut commands, 2);
+                // Not implemented by the script
+                script_mgr.on_password_change(&mut commands, 3);
+            })
+            .unwrap();
+        assert_eq!(world.resource::<HookCalls>().0, ["on_account_login 1", "on_failed_account_login 2"]);
+    }
+}
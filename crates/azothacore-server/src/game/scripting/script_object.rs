@@ -8,6 +8,11 @@ use bevy::{
 #[derive(Component)]
 pub struct AfterLoadScriptObject<O>(pub Option<O>);
 
+/// Component marking scripts of the families that are always bound to a `ScriptName` in the database,
+/// i.e. the ones whose `IsDatabaseBound()` returns true in TC / AC, regardless of [Script::is_database_bound]
+#[derive(Component)]
+pub struct DatabaseBoundScript;
+
 pub trait Script {
     fn name(&self) -> String {
         let original = std::any::type_name::<Self>();
@@ -1 +1,5 @@
+pub mod protocol;
+pub mod world_packet;
 pub mod world_packets;
+pub mod world_session;
+pub mod world_socket;
@@ -0,0 +1,153 @@
+use num_derive::{FromPrimitive, ToPrimitive};
+
+/// ConnectionType in TC/AC
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ConnectionType {
+    Realm = 0,
+    Instance = 1,
+}
+
+/// SessionStatus in TC/AC, the state a session must be in for an opcode to be handled
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SessionStatus {
+    /// Player not in world (directly after the auth handshake or after logging out)
+    Authed,
+    /// Player in world
+    LoggedIn,
+    /// Player transferring to another map
+    Transfer,
+    /// Player in world or in the process of logging out
+    LoggedInOrRecentlyLogout,
+    /// Opcode is handled by the socket itself and never reaches the session
+    Never,
+    /// Opcode is known but not handled yet
+    Unhandled,
+}
+
+/// PacketProcessing in TC/AC, where the handler of a client opcode should be run.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PacketProcessing {
+    /// Handled as soon as the packet is received, i.e. in the world socket
+    Inplace,
+    /// Handled when the session is updated by the world, where it is safe to touch other sessions
+    /// and maps
+    ThreadUnsafe,
+    /// Handled when the session is updated by the map it is in
+    ThreadSafe,
+}
+
+/// ClientOpcodeHandler in TC/AC
+#[derive(Copy, Clone, Debug)]
+pub struct ClientOpcodeHandler {
+    pub name:       &'static str,
+    pub status:     SessionStatus,
+    pub processing: PacketProcessing,
+}
+
+/// ServerOpcodeHandler in TC/AC
+#[derive(Copy, Clone, Debug)]
+pub struct ServerOpcodeHandler {
+    pub name:            &'static str,
+    pub status:          SessionStatus,
+    pub connection_type: ConnectionType,
+}
+
+/// OpcodeClient in TC/AC, opcodes sent from the client to the server.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, FromPrimitive, ToPrimitive)]
+#[repr(u16)]
+pub enum OpcodeClient {
+    AuthContinuedSession = 0x3766,
+    AuthSession = 0x3765,
+    CharDelete = 0x369C,
+    CreateCharacter = 0x3644,
+    EnableEncryptionAck = 0x3767,
+    EnumCharacters = 0x35E8,
+    KeepAlive = 0x3681,
+    LogDisconnect = 0x3769,
+    Ping = 0x3768,
+    PlayerLogin = 0x35EA,
+}
+
+/// OpcodeServer in TC/AC, opcodes sent from the server to the client.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, FromPrimitive, ToPrimitive)]
+#[repr(u16)]
+pub enum OpcodeServer {
+    AuthChallenge = 0x3048,
+    AuthResponse = 0x256C,
+    ConnectTo = 0x304D,
+    CreateChar = 0x2745,
+    DeleteChar = 0x2746,
+    EnableEncryption = 0x3049,
+    EnumCharactersResult = 0x2580,
+    Pong = 0x304E,
+    ResumeComms = 0x304B,
+    UpdateObject = 0x27C1,
+}
+
+impl OpcodeClient {
+    pub fn from_raw(opcode: u16) -> Option<Self> {
+        num::FromPrimitive::from_u16(opcode)
+    }
+
+    /// The entry for this opcode in the client opcode table, opcodeTable in TC/AC
+    pub const fn handler(self) -> ClientOpcodeHandler {
+        use PacketProcessing::*;
+        use SessionStatus::*;
+
+        let (name, status, processing) = match self {
+            Self::AuthContinuedSession => ("CMSG_AUTH_CONTINUED_SESSION", Never, ThreadUnsafe),
+            Self::AuthSession => ("CMSG_AUTH_SESSION", Never, ThreadUnsafe),
+            Self::CharDelete => ("CMSG_CHAR_DELETE", Authed, ThreadUnsafe),
+            Self::CreateCharacter => ("CMSG_CREATE_CHARACTER", Authed, ThreadUnsafe),
+            Self::EnableEncryptionAck => ("CMSG_ENABLE_ENCRYPTION_ACK", Never, ThreadUnsafe),
+            Self::EnumCharacters => ("CMSG_ENUM_CHARACTERS", Authed, ThreadUnsafe),
+            Self::KeepAlive => ("CMSG_KEEP_ALIVE", Never, ThreadUnsafe),
+            Self::LogDisconnect => ("CMSG_LOG_DISCONNECT", Never, ThreadUnsafe),
+            Self::Ping => ("CMSG_PING", Never, ThreadUnsafe),
+            Self::PlayerLogin => ("CMSG_PLAYER_LOGIN", Unhandled, ThreadUnsafe),
+        };
+        ClientOpcodeHandler { name, status, processing }
+    }
+}
+
+impl OpcodeServer {
+    pub fn from_raw(opcode: u16) -> Option<Self> {
+        num::FromPrimitive::from_u16(opcode)
+    }
+
+    /// The entry for this opcode in the server opcode table, opcodeTable in TC/AC
+    pub const fn handler(self) -> ServerOpcodeHandler {
+        use ConnectionType::*;
+        use SessionStatus::*;
+
+        let (name, status, connection_type) = match self {
+            Self::AuthChallenge => ("SMSG_AUTH_CHALLENGE", Never, Realm),
+            Self::AuthResponse => ("SMSG_AUTH_RESPONSE", Never, Realm),
+            Self::ConnectTo => ("SMSG_CONNECT_TO", Never, Realm),
+            Self::CreateChar => ("SMSG_CREATE_CHAR", Never, Realm),
+            Self::DeleteChar => ("SMSG_DELETE_CHAR", Never, Realm),
+            Self::EnableEncryption => ("SMSG_ENABLE_ENCRYPTION", Never, Realm),
+            Self::EnumCharactersResult => ("SMSG_ENUM_CHARACTERS_RESULT", Never, Realm),
+            Self::Pong => ("SMSG_PONG", Never, Realm),
+            Self::ResumeComms => ("SMSG_RESUME_COMMS", Never, Realm),
+            Self::UpdateObject => ("SMSG_UPDATE_OBJECT", Never, Instance),
+        };
+        ServerOpcodeHandler { name, status, connection_type }
+    }
+}
+
+/// GetOpcodeNameForLogging in TC/AC
+pub fn client_opcode_name_for_logging(opcode: u16) -> String {
+    match OpcodeClient::from_raw(opcode) {
+        Some(o) => format!("[{} {opcode:#06X}]", o.handler().name),
+        None => format!("[UNKNOWN OPCODE {opcode:#06X}]"),
+    }
+}
+
+/// GetOpcodeNameForLogging in TC/AC
+pub fn server_opcode_name_for_logging(opcode: u16) -> String {
+    match OpcodeServer::from_raw(opcode) {
+        Some(o) => format!("[{} {opcode:#06X}]", o.handler().name),
+        None => format!("[UNKNOWN OPCODE {opcode:#06X}]"),
+    }
+}
@@ -0,0 +1,105 @@
+use bytes::{BufMut, Bytes, BytesMut};
+
+use crate::{
+    game::server::protocol::opcodes::{client_opcode_name_for_logging, server_opcode_name_for_logging, OpcodeClient, OpcodeServer},
+    shared::networking::packet_log::{Direction, LoggedFrame},
+};
+
+/// Size of [WorldPacketHeader] on the wire
+pub const WORLD_PACKET_HEADER_SIZE: usize = 6;
+
+/// PacketHeader in TC/AC
+///
+/// `size` is the size of the packet data that follows the header, excluding
+/// the header itself.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct WorldPacketHeader {
+    pub size:    u32,
+    pub command: u16,
+}
+
+impl WorldPacketHeader {
+    pub fn from_bytes(b: [u8; WORLD_PACKET_HEADER_SIZE]) -> Self {
+        Self {
+            size:    u32::from_le_bytes([b[0], b[1], b[2], b[3]]),
+            command: u16::from_le_bytes([b[4], b[5]]),
+        }
+    }
+
+    pub fn to_bytes(self) -> [u8; WORLD_PACKET_HEADER_SIZE] {
+        let mut b = [0; WORLD_PACKET_HEADER_SIZE];
+        b[..4].copy_from_slice(&self.size.to_le_bytes());
+        b[4..].copy_from_slice(&self.command.to_le_bytes());
+        b
+    }
+
+    pub fn is_valid_size(&self) -> bool {
+        self.size < 0x40000
+    }
+}
+
+/// WorldPacket in TC/AC
+#[derive(Clone, Debug, PartialEq)]
+pub struct WorldPacket {
+    opcode: u16,
+    data:   Bytes,
+}
+
+impl WorldPacket {
+    pub fn new(opcode: u16, data: impl Into<Bytes>) -> Self {
+        Self { opcode, data: data.into() }
+    }
+
+    pub fn server(opcode: OpcodeServer, data: impl Into<Bytes>) -> Self {
+        Self::new(opcode as u16, data)
+    }
+
+    pub fn client(opcode: OpcodeClient, data: impl Into<Bytes>) -> Self {
+        Self::new(opcode as u16, data)
+    }
+
+    pub fn opcode(&self) -> u16 {
+        self.opcode
+    }
+
+    pub fn data(&self) -> &Bytes {
+        &self.data
+    }
+
+    pub fn into_data(self) -> Bytes {
+        self.data
+    }
+
+    pub fn header(&self) -> WorldPacketHeader {
+        WorldPacketHeader {
+            size:    self.data.len().try_into().expect("world packet data should never exceed u32::MAX"),
+            command: self.opcode,
+        }
+    }
+
+    /// The packet as it is written on the wire, header followed by the packet data.
+    pub fn to_frame(&self) -> Bytes {
+        let mut b = BytesMut::with_capacity(WORLD_PACKET_HEADER_SIZE + self.data.len());
+        b.put_slice(&self.header().to_bytes());
+        b.put_slice(&self.data);
+        b.freeze()
+    }
+}
+
+impl LoggedFrame for WorldPacket {
+    fn to_log_bytes(&self) -> Bytes {
+        self.to_frame()
+    }
+
+    fn describe(direction: Direction, frame: &[u8]) -> String {
+        let Some(raw_header) = frame.first_chunk::<WORLD_PACKET_HEADER_SIZE>() else {
+            return format!("truncated header of {} bytes", frame.len());
+        };
+        let header = WorldPacketHeader::from_bytes(*raw_header);
+        let opcode = match direction {
+            Direction::ClientToServer => client_opcode_name_for_logging(header.command),
+            Direction::ServerToClient => server_opcode_name_for_logging(header.command),
+        };
+        format!("{opcode} size={}", header.size)
+    }
+}
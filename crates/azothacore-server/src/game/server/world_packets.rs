@@ -1 +1,43 @@
+pub mod auth_packets;
+pub mod character_packets;
 pub mod lfg_packets_common;
+
+use azothacore_common::utils::BufferResult;
+use tracing::debug;
+
+use crate::{
+    game::server::{
+        protocol::opcodes::{OpcodeClient, OpcodeServer},
+        world_packet::WorldPacket,
+    },
+    shared::packets::byte_buffer::ByteBuffer,
+};
+
+/// WorldPackets::ClientPacket in TC/AC
+pub trait ClientPacket: Sized {
+    const OPCODE: OpcodeClient;
+
+    fn read(buf: &mut ByteBuffer) -> BufferResult<Self>;
+
+    fn from_world_packet(packet: WorldPacket) -> BufferResult<Self> {
+        let mut buf = ByteBuffer::from(packet.into_data());
+        let p = Self::read(&mut buf)?;
+        if !buf.is_empty() {
+            debug!(target:"network", "{} has {} bytes of unprocessed tail data", Self::OPCODE.handler().name, buf.len());
+        }
+        Ok(p)
+    }
+}
+
+/// WorldPackets::ServerPacket in TC/AC
+pub trait ServerPacket {
+    const OPCODE: OpcodeServer;
+
+    fn write(&self, buf: &mut ByteBuffer);
+
+    fn to_world_packet(&self) -> WorldPacket {
+        let mut buf = ByteBuffer::new();
+        self.write(&mut buf);
+        WorldPacket::server(Self::OPCODE, buf.into_bytes())
+    }
+}
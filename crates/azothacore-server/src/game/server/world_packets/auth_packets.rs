@@ -0,0 +1,184 @@
+use azothacore_common::utils::BufferResult;
+use bnet_rpc::BattlenetRpcErrorCode;
+
+use crate::{
+    game::server::{
+        protocol::opcodes::{OpcodeClient, OpcodeServer},
+        world_packets::{ClientPacket, ServerPacket},
+    },
+    shared::packets::byte_buffer::ByteBuffer,
+};
+
+/// WorldPackets::Auth::Ping in TC/AC
+pub struct Ping {
+    pub serial:  u32,
+    pub latency: u32,
+}
+
+impl ClientPacket for Ping {
+    const OPCODE: OpcodeClient = OpcodeClient::Ping;
+
+    fn read(buf: &mut ByteBuffer) -> BufferResult<Self> {
+        Ok(Self {
+            serial:  buf.read_u32()?,
+            latency: buf.read_u32()?,
+        })
+    }
+}
+
+/// WorldPackets::Auth::Pong in TC/AC
+pub struct Pong {
+    pub serial: u32,
+}
+
+impl ServerPacket for Pong {
+    const OPCODE: OpcodeServer = OpcodeServer::Pong;
+
+    fn write(&self, buf: &mut ByteBuffer) {
+        buf.write_u32(self.serial);
+    }
+}
+
+/// WorldPackets::Auth::AuthChallenge in TC/AC
+pub struct AuthChallenge {
+    pub dos_challenge: [u8; 32],
+    pub challenge:     [u8; 16],
+    pub dos_zero_bits: u8,
+}
+
+impl ServerPacket for AuthChallenge {
+    const OPCODE: OpcodeServer = OpcodeServer::AuthChallenge;
+
+    fn write(&self, buf: &mut ByteBuffer) {
+        buf.write_bytes(&self.dos_challenge).write_bytes(&self.challenge).write_u8(self.dos_zero_bits);
+    }
+}
+
+/// WorldPackets::Auth::AuthSession in TC/AC
+pub struct AuthSession {
+    pub dos_response:      u64,
+    pub region_id:         u32,
+    pub battlegroup_id:    u32,
+    pub realm_id:          u32,
+    pub local_challenge:   [u8; 16],
+    pub digest:            [u8; 24],
+    pub use_ipv6:          bool,
+    pub realm_join_ticket: String,
+}
+
+impl ClientPacket for AuthSession {
+    const OPCODE: OpcodeClient = OpcodeClient::AuthSession;
+
+    fn read(buf: &mut ByteBuffer) -> BufferResult<Self> {
+        let dos_response = buf.read_u64()?;
+        let region_id = buf.read_u32()?;
+        let battlegroup_id = buf.read_u32()?;
+        let realm_id = buf.read_u32()?;
+        let local_challenge = buf.read_array()?;
+        let digest = buf.read_array()?;
+        let use_ipv6 = buf.read_bit()?;
+        let realm_join_ticket_size = buf.read_u32()?;
+        let realm_join_ticket = buf.read_string(realm_join_ticket_size as usize)?;
+        Ok(Self {
+            dos_response,
+            region_id,
+            battlegroup_id,
+            realm_id,
+            local_challenge,
+            digest,
+            use_ipv6,
+            realm_join_ticket,
+        })
+    }
+}
+
+/// WorldPackets::Auth::AuthContinuedSession in TC/AC
+pub struct AuthContinuedSession {
+    pub dos_response:    u64,
+    /// ConnectToKey in TC/AC
+    pub key:             u64,
+    pub local_challenge: [u8; 16],
+    pub digest:          [u8; 24],
+}
+
+impl ClientPacket for AuthContinuedSession {
+    const OPCODE: OpcodeClient = OpcodeClient::AuthContinuedSession;
+
+    fn read(buf: &mut ByteBuffer) -> BufferResult<Self> {
+        Ok(Self {
+            dos_response:    buf.read_u64()?,
+            key:             buf.read_u64()?,
+            local_challenge: buf.read_array()?,
+            digest:          buf.read_array()?,
+        })
+    }
+}
+
+/// WorldPackets::Auth::AuthResponse in TC/AC
+///
+/// Only the result code is sent for now, SuccessInfo and WaitInfo are always left out.
+pub struct AuthResponse {
+    pub result: BattlenetRpcErrorCode,
+}
+
+impl ServerPacket for AuthResponse {
+    const OPCODE: OpcodeServer = OpcodeServer::AuthResponse;
+
+    fn write(&self, buf: &mut ByteBuffer) {
+        buf.write_u32(self.result as u32);
+        // SuccessInfo
+        buf.write_bit(false);
+        // WaitInfo
+        buf.write_bit(false);
+        buf.flush_bits();
+    }
+}
+
+/// WorldPackets::Auth::EnableEncryption in TC/AC
+pub struct EnableEncryption;
+
+impl ServerPacket for EnableEncryption {
+    const OPCODE: OpcodeServer = OpcodeServer::EnableEncryption;
+
+    fn write(&self, _buf: &mut ByteBuffer) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::{BufMut, BytesMut};
+
+    use super::*;
+    use crate::game::server::world_packet::WorldPacket;
+
+    #[test]
+    fn auth_session_reads_bit_packed_ipv6_flag_and_join_ticket() {
+        let mut data = BytesMut::new();
+        data.put_u64_le(7);
+        data.put_u32_le(2);
+        data.put_u32_le(1);
+        data.put_u32_le(3);
+        data.put_slice(&[0xAA; 16]);
+        data.put_slice(&[0xBB; 24]);
+        data.put_u8(0b1000_0000);
+        data.put_u32_le(3);
+        data.put_slice(b"1#1");
+
+        let p = AuthSession::from_world_packet(WorldPacket::client(OpcodeClient::AuthSession, data)).unwrap();
+        assert_eq!(p.dos_response, 7);
+        assert_eq!(p.realm_id, 3);
+        assert_eq!(p.local_challenge, [0xAA; 16]);
+        assert_eq!(p.digest, [0xBB; 24]);
+        assert!(p.use_ipv6);
+        assert_eq!(p.realm_join_ticket, "1#1");
+    }
+
+    #[test]
+    fn auth_response_error_has_no_success_or_wait_info() {
+        let p = AuthResponse {
+            result: BattlenetRpcErrorCode::Denied,
+        }
+        .to_world_packet();
+        assert_eq!(p.opcode(), OpcodeServer::AuthResponse as u16);
+        assert_eq!(&p.data()[..], &[3, 0, 0, 0, 0]);
+    }
+}
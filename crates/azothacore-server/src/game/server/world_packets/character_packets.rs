@@ -0,0 +1,334 @@
+use azothacore_common::utils::BufferResult;
+
+use crate::{
+    game::{
+        entities::{
+            object::object_guid::{HighGuidPlayer, ObjectGuid},
+            player::INVENTORY_SLOT_BAG_END,
+        },
+        server::{
+            protocol::opcodes::{OpcodeClient, OpcodeServer},
+            world_packets::{ClientPacket, ServerPacket},
+        },
+    },
+    shared::{packets::byte_buffer::ByteBuffer, shared_defines::ResponseCodes},
+};
+
+/// WorldPackets::Character::EnumCharacters in TC/AC, also used for CMSG_ENUM_CHARACTERS_DELETED_BY_CLIENT
+pub struct EnumCharacters;
+
+impl ClientPacket for EnumCharacters {
+    const OPCODE: OpcodeClient = OpcodeClient::EnumCharacters;
+
+    fn read(_buf: &mut ByteBuffer) -> BufferResult<Self> {
+        Ok(Self)
+    }
+}
+
+/// WorldPackets::Character::EnumCharactersResult::CharacterInfo::VisualItemInfo in TC/AC
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct VisualItemInfo {
+    pub display_id:         u32,
+    pub display_enchant_id: u32,
+    pub inventory_type:     u8,
+}
+
+/// WorldPackets::Character::EnumCharactersResult::CharacterInfo::PetInfo in TC/AC
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PetInfo {
+    pub creature_display_id: u32,
+    pub level:               u32,
+    pub creature_family:     u32,
+}
+
+/// WorldPackets::Character::EnumCharactersResult::CharacterInfo in TC/AC
+#[derive(Debug, Clone, PartialEq)]
+pub struct CharacterInfo {
+    pub guid:               ObjectGuid<HighGuidPlayer>,
+    pub name:               String,
+    pub list_position:      u8,
+    pub race_id:            u8,
+    pub class_id:           u8,
+    pub sex_id:             u8,
+    pub skin_id:            u8,
+    pub face_id:            u8,
+    pub hair_style:         u8,
+    pub hair_color:         u8,
+    pub facial_hair_style:  u8,
+    pub custom_display:     [u8; 3],
+    pub exp_level:          u8,
+    pub zone_id:            i32,
+    pub map_id:             i32,
+    pub pre_load_position:  [f32; 3],
+    pub guild_guid:         ObjectGuid,
+    /// CharacterFlags in TC/AC
+    pub flags:              u32,
+    /// CharacterCustomizeFlags in TC/AC
+    pub customization_flag: u32,
+    pub flags3:             u32,
+    pub flags4:             u32,
+    pub first_login:        bool,
+    pub boost_in_progress:  bool,
+    pub unk_wod61x:         u8,
+    pub pet:                PetInfo,
+    pub profession_ids:     [u32; 2],
+    pub visual_items:       [VisualItemInfo; INVENTORY_SLOT_BAG_END as usize],
+    pub last_played_time:   u32,
+    pub spec_id:            u16,
+    pub unknown703:         u32,
+    pub last_login_build:   u32,
+}
+
+impl CharacterInfo {
+    fn write(&self, buf: &mut ByteBuffer) {
+        buf.write_packed_guid(&self.guid)
+            .write_u8(self.list_position)
+            .write_u8(self.race_id)
+            .write_u8(self.class_id)
+            .write_u8(self.sex_id)
+            .write_u8(self.skin_id)
+            .write_u8(self.face_id)
+            .write_u8(self.hair_style)
+            .write_u8(self.hair_color)
+            .write_u8(self.facial_hair_style);
+        for d in self.custom_display {
+            buf.write_u8(d);
+        }
+        buf.write_u8(self.exp_level)
+            .write_i32(self.zone_id)
+            .write_i32(self.map_id)
+            .write_f32(self.pre_load_position[0])
+            .write_f32(self.pre_load_position[1])
+            .write_f32(self.pre_load_position[2])
+            .write_packed_guid(&self.guild_guid)
+            .write_u32(self.flags)
+            .write_u32(self.customization_flag)
+            .write_u32(self.flags3)
+            .write_u32(self.pet.creature_display_id)
+            .write_u32(self.pet.level)
+            .write_u32(self.pet.creature_family)
+            .write_u32(self.profession_ids[0])
+            .write_u32(self.profession_ids[1]);
+        for v in &self.visual_items {
+            buf.write_u32(v.display_id).write_u32(v.display_enchant_id).write_u8(v.inventory_type);
+        }
+        buf.write_u32(self.last_played_time)
+            .write_u16(self.spec_id)
+            .write_u32(self.unknown703)
+            .write_u32(self.last_login_build)
+            .write_u32(self.flags4);
+        buf.write_bits(self.name.len() as u32, 6);
+        buf.write_bit(self.first_login);
+        buf.write_bit(self.boost_in_progress);
+        buf.write_bits(self.unk_wod61x.into(), 5);
+        buf.flush_bits();
+        buf.write_string(&self.name);
+    }
+}
+
+/// WorldPackets::Character::EnumCharactersResult::RaceUnlock in TC/AC
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RaceUnlock {
+    pub race_id:            i32,
+    pub has_expansion:      bool,
+    pub has_achievement:    bool,
+    pub has_heritage_armor: bool,
+}
+
+/// WorldPackets::Character::EnumCharactersResult in TC/AC
+#[derive(Debug, Clone, Default)]
+pub struct EnumCharactersResult {
+    pub success: bool,
+    pub is_deleted_characters: bool,
+    pub is_demon_hunter_creation_allowed: bool,
+    pub has_demon_hunter_on_realm: bool,
+    pub unknown7x: bool,
+    pub is_allied_races_creation_allowed: bool,
+    pub max_character_level: i32,
+    pub disabled_classes_mask: Option<u32>,
+    pub characters: Vec<CharacterInfo>,
+    pub race_unlock_data: Vec<RaceUnlock>,
+}
+
+impl ServerPacket for EnumCharactersResult {
+    const OPCODE: OpcodeServer = OpcodeServer::EnumCharactersResult;
+
+    fn write(&self, buf: &mut ByteBuffer) {
+        buf.write_bit(self.success);
+        buf.write_bit(self.is_deleted_characters);
+        buf.write_bit(self.is_demon_hunter_creation_allowed);
+        buf.write_bit(self.has_demon_hunter_on_realm);
+        buf.write_bit(self.unknown7x);
+        buf.write_bit(self.disabled_classes_mask.is_some());
+        buf.write_bit(self.is_allied_races_creation_allowed);
+        buf.write_u32(self.characters.len() as u32)
+            .write_i32(self.max_character_level)
+            .write_u32(self.race_unlock_data.len() as u32);
+        if let Some(mask) = self.disabled_classes_mask {
+            buf.write_u32(mask);
+        }
+        for c in &self.characters {
+            c.write(buf);
+        }
+        for r in &self.race_unlock_data {
+            buf.write_i32(r.race_id);
+            buf.write_bit(r.has_expansion);
+            buf.write_bit(r.has_achievement);
+            buf.write_bit(r.has_heritage_armor);
+            buf.flush_bits();
+        }
+    }
+}
+
+/// WorldPackets::Character::CharacterCreateInfo in TC/AC
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CharacterCreateInfo {
+    pub race_id:           u8,
+    pub class_id:          u8,
+    pub sex:               u8,
+    pub skin:              u8,
+    pub face:              u8,
+    pub hair_style:        u8,
+    pub hair_color:        u8,
+    pub facial_hair_style: u8,
+    pub custom_display:    [u8; 3],
+    pub outfit_id:         u8,
+    pub name:              String,
+    pub template_set:      Option<i32>,
+}
+
+/// WorldPackets::Character::CreateCharacter in TC/AC
+pub struct CreateCharacter {
+    pub create_info: CharacterCreateInfo,
+}
+
+impl ClientPacket for CreateCharacter {
+    const OPCODE: OpcodeClient = OpcodeClient::CreateCharacter;
+
+    fn read(buf: &mut ByteBuffer) -> BufferResult<Self> {
+        let name_length = buf.read_bits(6)?;
+        let has_template_set = buf.read_bit()?;
+        let race_id = buf.read_u8()?;
+        let class_id = buf.read_u8()?;
+        let sex = buf.read_u8()?;
+        let skin = buf.read_u8()?;
+        let face = buf.read_u8()?;
+        let hair_style = buf.read_u8()?;
+        let hair_color = buf.read_u8()?;
+        let facial_hair_style = buf.read_u8()?;
+        let outfit_id = buf.read_u8()?;
+        let custom_display = buf.read_array()?;
+        let name = buf.read_string(name_length as usize)?;
+        let template_set = if has_template_set { Some(buf.read_i32()?) } else { None };
+        Ok(Self {
+            create_info: CharacterCreateInfo {
+                race_id,
+                class_id,
+                sex,
+                skin,
+                face,
+                hair_style,
+                hair_color,
+                facial_hair_style,
+                custom_display,
+                outfit_id,
+                name,
+                template_set,
+            },
+        })
+    }
+}
+
+/// WorldPackets::Character::CreateChar in TC/AC
+pub struct CreateChar {
+    pub code: ResponseCodes,
+    pub guid: ObjectGuid,
+}
+
+impl ServerPacket for CreateChar {
+    const OPCODE: OpcodeServer = OpcodeServer::CreateChar;
+
+    fn write(&self, buf: &mut ByteBuffer) {
+        buf.write_u8(self.code as u8).write_packed_guid(&self.guid);
+    }
+}
+
+/// WorldPackets::Character::CharDelete in TC/AC
+pub struct CharDelete {
+    pub guid: ObjectGuid<HighGuidPlayer>,
+}
+
+impl ClientPacket for CharDelete {
+    const OPCODE: OpcodeClient = OpcodeClient::CharDelete;
+
+    fn read(buf: &mut ByteBuffer) -> BufferResult<Self> {
+        Ok(Self {
+            guid: buf.read_packed_guid_typed()?,
+        })
+    }
+}
+
+/// WorldPackets::Character::DeleteChar in TC/AC
+pub struct DeleteChar {
+    pub code: ResponseCodes,
+}
+
+impl ServerPacket for DeleteChar {
+    const OPCODE: OpcodeServer = OpcodeServer::DeleteChar;
+
+    fn write(&self, buf: &mut ByteBuffer) {
+        buf.write_u8(self.code as u8);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::{BufMut, BytesMut};
+
+    use super::*;
+    use crate::game::server::world_packet::WorldPacket;
+
+    #[test]
+    fn create_character_reads_bit_packed_name_length_and_template_set() {
+        let mut data = BytesMut::new();
+        // 6 bits name length (5), template set bit, padding
+        data.put_u8(0b0001_0110);
+        data.put_slice(&[1, 2, 0, 3, 4, 5, 6, 7, 0]);
+        data.put_slice(&[8, 9, 10]);
+        data.put_slice(b"Jaina");
+        data.put_i32_le(42);
+
+        let p = CreateCharacter::from_world_packet(WorldPacket::client(OpcodeClient::CreateCharacter, data)).unwrap();
+        assert_eq!(
+            p.create_info,
+            CharacterCreateInfo {
+                race_id:           1,
+                class_id:          2,
+                sex:               0,
+                skin:              3,
+                face:              4,
+                hair_style:        5,
+                hair_color:        6,
+                facial_hair_style: 7,
+                custom_display:    [8, 9, 10],
+                outfit_id:         0,
+                name:              "Jaina".into(),
+                template_set:      Some(42),
+            }
+        );
+    }
+
+    #[test]
+    fn enum_characters_result_without_characters() {
+        let p = EnumCharactersResult {
+            success: true,
+            is_demon_hunter_creation_allowed: true,
+            max_character_level: 110,
+            disabled_classes_mask: Some(0x20),
+            ..Default::default()
+        }
+        .to_world_packet();
+        assert_eq!(p.opcode(), OpcodeServer::EnumCharactersResult as u16);
+        assert_eq!(&p.data()[..], &[0b1010_0100, 0, 0, 0, 0, 110, 0, 0, 0, 0, 0, 0, 0, 0x20, 0, 0, 0]);
+    }
+}
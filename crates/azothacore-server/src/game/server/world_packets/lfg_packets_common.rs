@@ -1,8 +1,13 @@
+use azothacore_common::utils::{BufferDecodeError, BufferResult};
+use num_derive::FromPrimitive;
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
-use crate::game::entities::object::object_guid::{HighGuidPlayer, ObjectGuid};
+use crate::{
+    game::entities::object::object_guid::{HighGuidPlayer, ObjectGuid},
+    shared::packets::byte_buffer::ByteBuffer,
+};
 
-#[derive(Serialize_repr, Deserialize_repr)]
+#[derive(Serialize_repr, Deserialize_repr, Clone, Copy, Debug, PartialEq, Eq, FromPrimitive)]
 #[repr(u32)]
 pub enum RideType {
     None = 0,
@@ -20,3 +25,26 @@ pub struct LFGRideTicket {
     /// WorldPackets::RideTicket::Time in TC
     time:           i32,
 }
+
+impl LFGRideTicket {
+    pub fn read(buf: &mut ByteBuffer) -> BufferResult<Self> {
+        let requester_guid = buf.read_packed_guid_typed()?;
+        let queue_id = buf.read_u32()?;
+        let typ = buf.read_u32()?;
+        let typ = num::FromPrimitive::from_u32(typ).ok_or_else(|| BufferDecodeError::UnexpectedDecode(format!("invalid ride ticket type {typ}")))?;
+        let time = buf.read_i32()?;
+        Ok(Self {
+            requester_guid,
+            queue_id,
+            typ,
+            time,
+        })
+    }
+
+    pub fn write(&self, buf: &mut ByteBuffer) {
+        buf.write_packed_guid(&self.requester_guid)
+            .write_u32(self.queue_id)
+            .write_u32(self.typ as u32)
+            .write_i32(self.time);
+    }
+}
@@ -0,0 +1,465 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    time::Duration,
+};
+
+use azothacore_common::{bevy_app::az_startup_succeeded, configuration::ConfigMgr, AccountTypes, AzResult, Locale};
+use bevy::{
+    ecs::system::SystemId,
+    prelude::{Added, App, Commands, Component, Entity, In, IntoSystem, IntoSystemConfigs, Query, Res, Resource, SystemSet, Update, With, World},
+    time::{Time, Timer, TimerMode},
+};
+use tracing::{debug, error, info};
+
+use crate::{
+    game::{
+        entities::object::object_guid::{HighGuidPlayer, ObjectGuid},
+        server::{
+            protocol::opcodes::{client_opcode_name_for_logging, ConnectionType, OpcodeClient, OpcodeServer, PacketProcessing, SessionStatus},
+            world_packet::WorldPacket,
+            world_socket::{InstanceSocket, WorldSocket, WorldSocketAccountInfo, WorldSocketAuth, WorldSocketReadPacketsSet},
+        },
+        world::WorldConfig,
+    },
+    shared::networking::socket_mgr::SocketMgrSet,
+};
+
+/// MAX_PROCESSED_PACKETS_IN_SAME_WORLDSESSION_UPDATE in TC/AC
+const MAX_PROCESSED_PACKETS_IN_SAME_WORLDSESSION_UPDATE: usize = 150;
+
+/// The input given to client opcode handlers, the entity of the [WorldSession] that received the packet
+/// along with the packet itself.
+pub type WorldSessionPacket = (Entity, WorldPacket);
+
+/// Systems registered as handlers for client opcodes, keyed by the opcode they handle.
+///
+/// Handlers are run from [WorldSession] updates, i.e. after the opcode's [SessionStatus] has been checked
+/// against the state of the session.
+#[derive(Resource, Default)]
+pub struct ClientOpcodeHandlers(BTreeMap<OpcodeClient, SystemId<In<WorldSessionPacket>, AzResult<()>>>);
+
+/// Registers `handler` to handle packets with the given `opcode`.
+///
+/// Panics if a handler has already been registered for the opcode, or if the opcode is not meant to be
+/// handled by a [WorldSession] at all.
+pub fn add_client_opcode_handler<M>(app: &mut App, opcode: OpcodeClient, handler: impl IntoSystem<In<WorldSessionPacket>, AzResult<()>, M> + 'static) {
+    let status = opcode.handler().status;
+    assert!(
+        !matches!(status, SessionStatus::Never | SessionStatus::Unhandled),
+        "opcode {} has status {status:?} and should not have a session handler",
+        opcode.handler().name
+    );
+    let id = app.register_system(handler);
+    let mut handlers = app.world_mut().get_resource_or_init::<ClientOpcodeHandlers>();
+    if handlers.0.insert(opcode, id).is_some() {
+        panic!("opcode {} already has a handler registered", opcode.handler().name);
+    }
+}
+
+/// WorldSession in TC/AC
+///
+/// Lives on the same entity as the realm [WorldSocket] it was created for and is removed along with it.
+/// Packets that the socket does not handle itself are queued on the session and handled on
+/// the next session update.
+#[derive(Component)]
+pub struct WorldSession {
+    account_id:             u32,
+    account_name:           String,
+    bnet_account_id:        Option<u32>,
+    security:               AccountTypes,
+    expansion:              u8,
+    locale:                 Locale,
+    mute_time:              i64,
+    recruiter_id:           u32,
+    is_recruiter:           bool,
+    os:                     String,
+    latency:                u32,
+    /// _recvQueue in TC/AC
+    recv_queue:             VecDeque<WorldPacket>,
+    /// m_timeOutTime in TC/AC, when this finishes the connection is considered idle
+    time_out:               Timer,
+    /// _player in TC/AC, the entity of the player currently in world for this session
+    player:                 Option<Entity>,
+    player_recently_logout: bool,
+    /// The socket connected to the instance server port, if any
+    instance_socket:        Option<Entity>,
+    /// _legitCharacters in TC/AC, the characters sent in the last character enum
+    legit_characters:       BTreeSet<ObjectGuid<HighGuidPlayer>>,
+}
+
+/// Links a socket connected to the instance server port to the entity of the [WorldSession] it belongs to.
+#[derive(Component)]
+pub struct LinkedWorldSession(pub Entity);
+
+impl WorldSession {
+    pub fn new(info: &WorldSocketAccountInfo, cfg: &WorldConfig) -> Self {
+        let mut s = Self {
+            account_id:             info.id,
+            account_name:           info.name.clone(),
+            bnet_account_id:        info.bnet_account_id,
+            security:               info.security,
+            expansion:              info.expansion,
+            locale:                 info.locale,
+            mute_time:              info.mute_time,
+            recruiter_id:           info.recruiter,
+            is_recruiter:           info.is_recruiter,
+            os:                     info.os.clone(),
+            latency:                0,
+            recv_queue:             VecDeque::new(),
+            time_out:               Timer::new(Duration::ZERO, TimerMode::Once),
+            player:                 None,
+            player_recently_logout: false,
+            instance_socket:        None,
+            legit_characters:       BTreeSet::new(),
+        };
+        s.reset_time_out_time(false, cfg);
+        s
+    }
+
+    pub fn account_id(&self) -> u32 {
+        self.account_id
+    }
+
+    pub fn account_name(&self) -> &str {
+        &self.account_name
+    }
+
+    pub fn bnet_account_id(&self) -> Option<u32> {
+        self.bnet_account_id
+    }
+
+    pub fn security(&self) -> AccountTypes {
+        self.security
+    }
+
+    pub fn expansion(&self) -> u8 {
+        self.expansion
+    }
+
+    pub fn session_db_locale_index(&self) -> Locale {
+        self.locale
+    }
+
+    pub fn mute_time(&self) -> i64 {
+        self.mute_time
+    }
+
+    pub fn recruiter_id(&self) -> u32 {
+        self.recruiter_id
+    }
+
+    pub fn is_a_recruiter(&self) -> bool {
+        self.is_recruiter
+    }
+
+    pub fn os(&self) -> &str {
+        &self.os
+    }
+
+    pub fn latency(&self) -> u32 {
+        self.latency
+    }
+
+    pub fn set_latency(&mut self, latency: u32) {
+        self.latency = latency;
+    }
+
+    pub fn player(&self) -> Option<Entity> {
+        self.player
+    }
+
+    pub fn set_player(&mut self, player: Option<Entity>) {
+        if self.player.is_some() && player.is_none() {
+            self.player_recently_logout = true;
+        }
+        self.player = player;
+    }
+
+    pub fn set_legit_characters(&mut self, legit_characters: BTreeSet<ObjectGuid<HighGuidPlayer>>) {
+        self.legit_characters = legit_characters;
+    }
+
+    /// IsLegitCharacterForAccount in TC/AC
+    pub fn is_legit_character_for_account(&self, guid: &ObjectGuid<HighGuidPlayer>) -> bool {
+        self.legit_characters.contains(guid)
+    }
+
+    pub fn instance_socket(&self) -> Option<Entity> {
+        self.instance_socket
+    }
+
+    /// The socket entity that packets with the given opcode are sent through, as picked by WorldSession::SendPacket in TC/AC.
+    ///
+    /// `session` is the entity of this session, i.e. the one of its realm socket. Returns [None] for packets meant for the
+    /// instance connection if the client has yet to connect to the instance server port.
+    pub fn socket_for(&self, session: Entity, opcode: OpcodeServer) -> Option<Entity> {
+        match opcode.handler().connection_type {
+            ConnectionType::Realm => Some(session),
+            ConnectionType::Instance => self.instance_socket,
+        }
+    }
+
+    /// QueuePacket in TC/AC, adds a packet to be handled on the next session update.
+    pub fn queue_packet(&mut self, packet: WorldPacket) {
+        self.recv_queue.push_back(packet);
+    }
+
+    /// ResetTimeOutTime in TC/AC
+    ///
+    /// Sessions with a player in world use `SocketTimeOutTimeActive`, otherwise `SocketTimeOutTime`.
+    /// If `only_active` is set, the timer is only reset for sessions that have a player in world,
+    /// which is what the client's `CMSG_KEEP_ALIVE`s do.
+    pub fn reset_time_out_time(&mut self, only_active: bool, cfg: &WorldConfig) {
+        if self.player.is_some() {
+            self.time_out = Timer::new(*cfg.SocketTimeOutTimeActive, TimerMode::Once);
+        } else if !only_active {
+            self.time_out = Timer::new(*cfg.SocketTimeOutTime, TimerMode::Once);
+        }
+    }
+
+    /// UpdateTimeOutTime in TC/AC
+    pub fn update_time_out_time(&mut self, diff: Duration) {
+        self.time_out.tick(diff);
+    }
+
+    /// IsConnectionIdle in TC/AC
+    pub fn is_connection_idle(&self) -> bool {
+        self.time_out.finished()
+    }
+
+    /// Whether the session should handle a packet with the given processing now, or leave it in the queue.
+    ///
+    /// WorldSessionFilter::Process in TC/AC, thread safe packets are left to the map the player is in
+    /// once the player is in world.
+    fn should_process_in_world_update(&self, processing: PacketProcessing) -> bool {
+        match processing {
+            PacketProcessing::Inplace | PacketProcessing::ThreadUnsafe => true,
+            PacketProcessing::ThreadSafe => self.player.is_none(),
+        }
+    }
+}
+
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AddWorldSessionSet;
+
+/// World::UpdateSessions in TC/AC
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UpdateWorldSessionsSet;
+
+/// Creates a [WorldSession] for every newly authenticated [WorldSocket] and updates them every [Update], handling
+/// the packets that they have queued up.
+pub fn world_session_plugin(app: &mut App) {
+    app.init_resource::<ClientOpcodeHandlers>()
+        .add_systems(
+            Update,
+            add_world_sessions
+                .run_if(az_startup_succeeded())
+                .in_set(AddWorldSessionSet)
+                .after(SocketMgrSet::<WorldSocket>::handle_received_socket())
+                .after(SocketMgrSet::<InstanceSocket>::handle_received_socket())
+                .before(WorldSocketReadPacketsSet),
+        )
+        .add_systems(
+            Update,
+            update_world_sessions
+                .run_if(az_startup_succeeded())
+                .in_set(UpdateWorldSessionsSet)
+                .after(WorldSocketReadPacketsSet),
+        );
+}
+
+/// World::AddSession_ in TC/AC, along with the session lookup done when the client connects
+/// to the instance server port via `CMSG_AUTH_CONTINUED_SESSION`.
+fn add_world_sessions(
+    mut commands: Commands,
+    cfg: Res<ConfigMgr<WorldConfig>>,
+    new_sockets: Query<(Entity, &WorldSocket), Added<WorldSocket>>,
+    mut sessions: Query<(Entity, &mut WorldSession, &WorldSocket)>,
+) {
+    for (e, sock) in &new_sockets {
+        match sock.auth() {
+            WorldSocketAuth::Session(info) => {
+                // Kick any existing session of the same account, RemoveSession in TC/AC
+                for (old_e, _, old_sock) in sessions.iter().filter(|(old_e, s, _)| *old_e != e && s.account_id == info.id) {
+                    info!(target:"network", "account {} ({}) logged in again from {}, kicking session {old_e}", info.name, info.id, sock.remote_name());
+                    old_sock.close();
+                }
+                commands.entity(e).insert(WorldSession::new(info, &cfg));
+            },
+            WorldSocketAuth::ContinuedSession { account_id, account_name, .. } => {
+                let Some((session_e, mut session, _)) = sessions.iter_mut().find(|(_, s, _)| s.account_id == *account_id) else {
+                    error!(target:"network", "account {account_name} ({account_id}) connected to the instance server port from {} without a session, closing", sock.remote_name());
+                    sock.close();
+                    continue;
+                };
+                session.instance_socket = Some(e);
+                commands.entity(e).insert(LinkedWorldSession(session_e));
+            },
+        }
+    }
+}
+
+/// World::UpdateSessions in TC/AC
+fn update_world_sessions(world: &mut World) {
+    let diff = world.resource::<Time>().delta();
+    let close_idle_connections = world.resource::<ConfigMgr<WorldConfig>>().CloseIdleConnections;
+    let sessions = world.query_filtered::<Entity, With<WorldSession>>().iter(world).collect::<Vec<_>>();
+    for e in sessions {
+        update_world_session(world, e, diff, close_idle_connections);
+    }
+}
+
+/// WorldSession::Update in TC/AC, using WorldSessionFilter.
+fn update_world_session(world: &mut World, e: Entity, diff: Duration, close_idle_connections: bool) {
+    let Some(mut session) = world.get_mut::<WorldSession>(e) else {
+        return;
+    };
+    session.update_time_out_time(diff);
+
+    // Before we process anything:
+    // If necessary, kick the player because the client didn't send anything for too long
+    // (or they've been idling in character select)
+    if close_idle_connections && session.is_connection_idle() {
+        info!(target:"network", "kicking idle session of account {} ({})", session.account_name, session.account_id);
+        if let Some(sock) = world.get::<WorldSocket>(e) {
+            sock.close();
+        }
+        return;
+    }
+
+    let account = format!("{} ({})", session.account_name, session.account_id);
+    let mut packets = std::mem::take(&mut session.recv_queue);
+    let mut requeue_packets = VecDeque::new();
+    let mut processed_packets = 0;
+    while let Some(packet) = packets.pop_front() {
+        let Some(opcode) = OpcodeClient::from_raw(packet.opcode()) else {
+            // Unknown opcodes are dropped by the socket, so should never get here
+            continue;
+        };
+        let handler = opcode.handler();
+        let Some(mut session) = world.get_mut::<WorldSession>(e) else {
+            return;
+        };
+        if !session.should_process_in_world_update(handler.processing) {
+            requeue_packets.push_back(packet);
+            continue;
+        }
+        let call = match handler.status {
+            SessionStatus::LoggedIn => {
+                if session.player.is_none() {
+                    // skip STATUS_LOGGEDIN opcode unexpected errors if player logout sometime ago - this can be network lag delayed packets
+                    // If player didn't log out a while ago, it means packets are being sent while the server does not recognize
+                    // the client to be in world yet. We will re-add the packets to the bottom of the queue and process them later.
+                    if !session.player_recently_logout {
+                        debug!(target:"network", "re-enqueueing packet with opcode {} with status STATUS_LOGGEDIN, player is currently not in world yet", handler.name);
+                        requeue_packets.push_back(packet);
+                    }
+                    continue;
+                }
+                true
+            },
+            SessionStatus::LoggedInOrRecentlyLogout => {
+                if session.player.is_none() && !session.player_recently_logout {
+                    log_unexpected_opcode(&account, opcode, handler.status, "the player has not logged in yet and not recently logout");
+                    false
+                } else {
+                    true
+                }
+            },
+            SessionStatus::Transfer => {
+                if session.player.is_none() {
+                    log_unexpected_opcode(&account, opcode, handler.status, "the player has not logged in yet");
+                } else {
+                    log_unexpected_opcode(&account, opcode, handler.status, "the player is still in world");
+                }
+                false
+            },
+            SessionStatus::Authed => {
+                // some auth opcodes can be recieved before STATUS_LOGGEDIN_OR_RECENTLY_LOGGOUT opcodes
+                // however when we recieve CMSG_ENUM_CHARACTERS we are surely no longer during the logout process.
+                if opcode == OpcodeClient::EnumCharacters {
+                    session.player_recently_logout = false;
+                }
+                true
+            },
+            SessionStatus::Never => {
+                error!(target:"network", "received not allowed opcode {} from account {account}", client_opcode_name_for_logging(packet.opcode()));
+                false
+            },
+            SessionStatus::Unhandled => {
+                debug!(target:"network", "received not handled opcode {} from account {account}", client_opcode_name_for_logging(packet.opcode()));
+                false
+            },
+        };
+        if call {
+            let Some(handler_id) = world.resource::<ClientOpcodeHandlers>().0.get(&opcode).copied() else {
+                debug!(target:"network", "received opcode {} that has no handler registered, dropping", client_opcode_name_for_logging(packet.opcode()));
+                continue;
+            };
+            match world.run_system_with_input(handler_id, (e, packet)) {
+                Err(err) => error!(target:"network", cause=?err, "unable to run handler for opcode {}", handler.name),
+                Ok(Err(err)) => error!(target:"network", cause=?err, "error handling opcode {} for account {account}", handler.name),
+                Ok(Ok(())) => {},
+            }
+        }
+        processed_packets += 1;
+        if processed_packets >= MAX_PROCESSED_PACKETS_IN_SAME_WORLDSESSION_UPDATE {
+            break;
+        }
+    }
+    let Some(mut session) = world.get_mut::<WorldSession>(e) else {
+        return;
+    };
+    // Packets that have not been processed go back to the front, in the order they were received
+    requeue_packets.append(&mut packets);
+    requeue_packets.append(&mut session.recv_queue);
+    session.recv_queue = requeue_packets;
+}
+
+/// LogUnexpectedOpcode in TC/AC
+fn log_unexpected_opcode(account: &str, opcode: OpcodeClient, status: SessionStatus, reason: &str) {
+    error!(target:"network", "received unexpected opcode {} Status: {status:?} Reason: {reason} from account {account}", opcode.handler().name);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use azothacore_common::{AccountTypes, Locale};
+
+    use super::WorldSession;
+    use crate::game::{server::world_socket::WorldSocketAccountInfo, world::WorldConfig};
+
+    fn test_session(cfg: &WorldConfig) -> WorldSession {
+        let info = WorldSocketAccountInfo {
+            id:              1,
+            name:            "1#1".to_string(),
+            bnet_account_id: Some(1),
+            security:        AccountTypes::SecPlayer,
+            expansion:       6,
+            mute_time:       0,
+            locale:          Locale::enUS,
+            recruiter:       0,
+            os:              "Win".to_string(),
+            is_recruiter:    false,
+        };
+        WorldSession::new(&info, cfg)
+    }
+
+    #[test]
+    fn world_session_idles_out_after_socket_time_out_time() {
+        let cfg = WorldConfig::default();
+        let mut s = test_session(&cfg);
+        assert!(!s.is_connection_idle());
+
+        s.update_time_out_time(*cfg.SocketTimeOutTime - Duration::from_secs(1));
+        assert!(!s.is_connection_idle());
+        // Keep alives only count for sessions with a player in world
+        s.reset_time_out_time(true, &cfg);
+        s.update_time_out_time(Duration::from_secs(1));
+        assert!(s.is_connection_idle());
+
+        s.reset_time_out_time(false, &cfg);
+        assert!(!s.is_connection_idle());
+    }
+}
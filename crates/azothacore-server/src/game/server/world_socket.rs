@@ -0,0 +1,709 @@
+use std::{fmt, io};
+
+use azothacore_common::{
+    az_error,
+    bevy_app::{az_startup_succeeded, TokioRuntime},
+    configuration::ConfigMgr,
+    crypto::{session_key_generator::SessionKeyGenerator, world_packet_crypt::WorldPacketCrypt},
+    deref_boilerplate,
+    utils::{BufferDecodeError, BufferResult, MessageBuffer},
+    AccountTypes,
+    AzError,
+    AzResult,
+    Locale,
+};
+use azothacore_database::{
+    args,
+    database_env::{LoginDatabase, LoginPreparedStmts},
+};
+use bevy::{
+    ecs::{system::RunSystemOnce, world::CommandQueue},
+    prelude::{App, Commands, Component, Entity, FixedUpdate, In, IntoSystemConfigs, Query, Res, ResMut, SystemSet, Update, World},
+};
+use bnet_rpc::BattlenetRpcErrorCode;
+use bytes::{Buf, BytesMut};
+use hmac::{Hmac, Mac};
+use rand::{rngs::OsRng, RngCore, TryRngCore};
+use sha2::{Digest, Sha256};
+use sqlx::Pool;
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    net::TcpStream,
+    runtime::Handle,
+    sync::OwnedSemaphorePermit,
+};
+use tracing::{debug, error, warn};
+
+use crate::{
+    game::{
+        scripting::script_mgr::ScriptMgr,
+        server::{
+            protocol::opcodes::{client_opcode_name_for_logging, ConnectionType, OpcodeClient},
+            world_packet::{WorldPacket, WorldPacketHeader, WORLD_PACKET_HEADER_SIZE},
+            world_packets::{
+                auth_packets::{AuthChallenge, AuthContinuedSession, AuthResponse, AuthSession, EnableEncryption, Ping, Pong},
+                ClientPacket,
+                ServerPacket,
+            },
+            world_session::{LinkedWorldSession, WorldSession},
+        },
+        world::{CurrentRealm, WorldConfig},
+    },
+    shared::{
+        networking::{
+            packet_log::PacketLog,
+            socket::{AddressOrName, Socket, SocketCodec},
+            socket_mgr::{ConnectionComponent, NewTcpConnection, RunStartTcpSocketTask, SocketReceiver},
+        },
+        realms::Realm,
+    },
+};
+
+const SERVER_CONNECTION_INITIALIZE: &[u8] = b"WORLD OF WARCRAFT CONNECTION - SERVER TO CLIENT\n";
+const CLIENT_CONNECTION_INITIALIZE: &[u8] = b"WORLD OF WARCRAFT CONNECTION - CLIENT TO SERVER\n";
+
+const AUTH_CHECK_SEED: [u8; 16] = [0xC5, 0xC6, 0x98, 0x95, 0x76, 0x3F, 0x1D, 0xCD, 0xB6, 0xA1, 0x37, 0x28, 0xB3, 0x12, 0xFF, 0x8A];
+const SESSION_KEY_SEED: [u8; 16] = [0x58, 0xCB, 0xCF, 0x40, 0xFE, 0x2E, 0xCE, 0xA6, 0x5A, 0x90, 0xB8, 0x01, 0x68, 0x6C, 0x28, 0x0B];
+const CONTINUED_SESSION_SEED: [u8; 16] = [0x16, 0xAD, 0x0C, 0xD4, 0x46, 0xF9, 0x4F, 0xB2, 0xEF, 0x7D, 0xEA, 0x2A, 0x17, 0x66, 0x4D, 0x2F];
+
+/// Size of the session key generated from the bnet `join_realm` key data once `CMSG_AUTH_SESSION`
+/// succeeds, which is what the header encryption is keyed off.
+const WORLD_SESSION_KEY_SIZE: usize = 40;
+/// The client only sends the first 24 bytes of its HMAC-SHA256 digests.
+const AUTH_DIGEST_SIZE: usize = 24;
+
+/// Context of the errors of `CMSG_AUTH_SESSION` for a known account being refused, for which
+/// [ScriptMgr::on_failed_account_login] is called
+#[derive(Debug)]
+struct FailedAccountLogin(u32);
+
+impl fmt::Display for FailedAccountLogin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed login of account {}", self.0)
+    }
+}
+
+/// The account info loaded by `CMSG_AUTH_SESSION`, AccountInfo in TC/AC
+#[derive(Clone, Debug)]
+pub struct WorldSocketAccountInfo {
+    pub id:              u32,
+    pub name:            String,
+    pub bnet_account_id: Option<u32>,
+    pub security:        AccountTypes,
+    pub expansion:       u8,
+    pub mute_time:       i64,
+    pub locale:          Locale,
+    pub recruiter:       u32,
+    pub os:              String,
+    pub is_recruiter:    bool,
+}
+
+/// How the [WorldSocket] was authenticated
+#[derive(Clone, Debug)]
+pub enum WorldSocketAuth {
+    /// Authenticated via `CMSG_AUTH_SESSION`, i.e. the client has just come from the realm list
+    Session(WorldSocketAccountInfo),
+    /// Authenticated via `CMSG_AUTH_CONTINUED_SESSION`, i.e. the client was redirected to
+    /// this socket by the server via `SMSG_CONNECT_TO`
+    ContinuedSession {
+        account_id:   u32,
+        account_name: String,
+        key:          u64,
+    },
+}
+
+impl WorldSocketAuth {
+    pub fn account_id(&self) -> u32 {
+        match self {
+            Self::Session(a) => a.id,
+            Self::ContinuedSession { account_id, .. } => *account_id,
+        }
+    }
+
+    pub fn account_name(&self) -> &str {
+        match self {
+            Self::Session(a) => &a.name,
+            Self::ContinuedSession { account_name, .. } => account_name,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct DbAccountInfo {
+    id:                   u32,
+    session_key:          String,
+    last_ip:              Option<String>,
+    is_locked_to_ip:      Option<bool>,
+    expansion:            u8,
+    mute_time:            i64,
+    locale:               Option<u8>,
+    recruiter:            u32,
+    os:                   String,
+    bnet_account_id:      Option<u32>,
+    security:             Option<u8>,
+    is_bnet_banned:       Option<bool>,
+    is_banned:            Option<bool>,
+    recruited_account_id: Option<u32>,
+}
+
+impl DbAccountInfo {
+    fn is_banned(&self) -> bool {
+        self.is_bnet_banned.is_some_and(|b| b) || self.is_banned.is_some_and(|b| b)
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct DbContinuedSessionInfo {
+    username:   String,
+    sessionkey: String,
+}
+
+/// The [SocketCodec] for world sockets once encryption has been switched on.
+///
+/// Only the packet header is encrypted, so the codec holds on to a decrypted header until
+/// the rest of its packet data has been read in.
+struct WorldSocketCodec {
+    crypt:          WorldPacketCrypt,
+    pending_header: Option<WorldPacketHeader>,
+}
+
+impl SocketCodec<WorldPacket> for WorldSocketCodec {
+    fn decode(&mut self, buffer: &mut MessageBuffer) -> BufferResult<WorldPacket> {
+        let header = match self.pending_header {
+            Some(h) => h,
+            None => {
+                if buffer.len() < WORLD_PACKET_HEADER_SIZE {
+                    return Err(BufferDecodeError::InsufficientBytes {
+                        have:   buffer.len(),
+                        wanted: WORLD_PACKET_HEADER_SIZE,
+                    });
+                }
+                let mut raw_header = [0u8; WORLD_PACKET_HEADER_SIZE];
+                buffer.copy_to_slice(&mut raw_header);
+                self.crypt.decrypt_recv(&mut raw_header);
+                let header = WorldPacketHeader::from_bytes(raw_header);
+                if !header.is_valid_size() {
+                    return Err(BufferDecodeError::UnexpectedDecode(format!(
+                        "client sent malformed packet (size: {}, cmd: {})",
+                        header.size, header.command
+                    )));
+                }
+                self.pending_header = Some(header);
+                header
+            },
+        };
+        let size = header.size as usize;
+        if buffer.len() < size {
+            return Err(BufferDecodeError::InsufficientBytes {
+                have:   buffer.len(),
+                wanted: size,
+            });
+        }
+        self.pending_header = None;
+        Ok(WorldPacket::new(header.command, buffer.split_to(size).freeze()))
+    }
+
+    fn encode(&mut self, frame: bytes::Bytes) -> bytes::Bytes {
+        let mut frame = BytesMut::from(frame);
+        self.crypt.encrypt_send(&mut frame[..WORLD_PACKET_HEADER_SIZE]);
+        frame.freeze()
+    }
+}
+
+/// WorldSocket in TC/AC
+///
+/// Only inserted as a component once the connection handshake has completed, i.e. the client
+/// has been authenticated and header encryption is switched on.
+#[derive(Component)]
+pub struct WorldSocket {
+    _permit:         Option<OwnedSemaphorePermit>,
+    socket:          Socket<WorldPacket>,
+    connection_type: ConnectionType,
+    auth:            WorldSocketAuth,
+}
+
+deref_boilerplate!(WorldSocket, Socket<WorldPacket>, socket);
+
+impl ConnectionComponent for WorldSocket {}
+
+/// Marker for connections accepted on `InstanceServerPort`, these are turned into a [WorldSocket]
+/// once their handshake completes.
+#[derive(Component)]
+pub struct InstanceSocket;
+
+impl ConnectionComponent for InstanceSocket {}
+
+impl WorldSocket {
+    pub fn connection_type(&self) -> ConnectionType {
+        self.connection_type
+    }
+
+    pub fn auth(&self) -> &WorldSocketAuth {
+        &self.auth
+    }
+
+    pub fn send_packet(&self, packet: &WorldPacket) -> io::Result<()> {
+        self.socket.write(packet.to_frame())
+    }
+
+    async fn start(
+        login_db: Pool<azothacore_database::DbDriver>,
+        realm: Realm,
+        _permit: Option<OwnedSemaphorePermit>,
+        name: AddressOrName,
+        tcp_conn: TcpStream,
+        max_frame_size: Option<usize>,
+        packet_log: Option<PacketLog>,
+    ) -> AzResult<Self> {
+        // CheckIpCallback routine
+        let ip_address = name.ip_str_or_name();
+        LoginDatabase::del_expired_ip_bans(&login_db, args!()?).await?;
+        for fields in LoginDatabase::sel_ip_info(&login_db, args!(ip_address.clone())?).await? {
+            let banned = sqlx::Row::get::<u32, _>(&fields, "banned") != 0;
+            if banned {
+                return Err(az_error!("[CheckIpCallback] Banned ip '{}' tries to login!", name));
+            }
+        }
+
+        let (mut rd, mut wr) = tcp_conn.into_split();
+        wr.write_all(SERVER_CONNECTION_INITIALIZE).await?;
+
+        // ReadHeaderHandler, before the client has sent its connection initialisation string
+        let mut client_init = [0u8; CLIENT_CONNECTION_INITIALIZE.len()];
+        rd.read_exact(&mut client_init).await?;
+        if client_init != CLIENT_CONNECTION_INITIALIZE {
+            return Err(az_error!(
+                "client {name} sent wrong connection initialisation string: {}",
+                String::from_utf8_lossy(&client_init)
+            ));
+        }
+
+        // HandleSendAuthSession
+        let mut challenge = AuthChallenge {
+            dos_challenge: [0; 32],
+            challenge:     [0; 16],
+            dos_zero_bits: 1,
+        };
+        OsRng.unwrap_err().fill_bytes(&mut challenge.challenge);
+        OsRng.unwrap_err().fill_bytes(&mut challenge.dos_challenge);
+        let server_challenge = challenge.challenge;
+        write_plain_packet(&mut wr, &challenge.to_world_packet()).await?;
+
+        let packet = read_plain_packet(&mut rd).await?;
+        let (session_key, connection_type, auth) = match OpcodeClient::from_raw(packet.opcode()) {
+            Some(OpcodeClient::AuthSession) => {
+                let auth_session = AuthSession::from_world_packet(packet)?;
+                match handle_auth_session(&login_db, &realm, &name, &server_challenge, auth_session).await? {
+                    Ok((key, info)) => (key, ConnectionType::Realm, WorldSocketAuth::Session(info)),
+                    Err((code, e)) => {
+                        send_auth_response_error(&mut wr, code).await?;
+                        return Err(e);
+                    },
+                }
+            },
+            Some(OpcodeClient::AuthContinuedSession) => {
+                let auth_session = AuthContinuedSession::from_world_packet(packet)?;
+                match handle_auth_continued_session(&login_db, &server_challenge, auth_session).await? {
+                    Ok((key, auth)) => (key, ConnectionType::Instance, auth),
+                    Err((code, e)) => {
+                        send_auth_response_error(&mut wr, code).await?;
+                        return Err(e);
+                    },
+                }
+            },
+            _ => {
+                return Err(az_error!(
+                    "client {name} sent unexpected opcode {} before being authenticated",
+                    client_opcode_name_for_logging(packet.opcode())
+                ));
+            },
+        };
+
+        // Encryption is only switched on once the client acknowledges that it will start
+        // encrypting as well, everything up to that point is in plain text.
+        write_plain_packet(&mut wr, &EnableEncryption.to_world_packet()).await?;
+        let packet = read_plain_packet(&mut rd).await?;
+        if OpcodeClient::from_raw(packet.opcode()) != Some(OpcodeClient::EnableEncryptionAck) {
+            return Err(az_error!(
+                "client {name} sent opcode {} instead of CMSG_ENABLE_ENCRYPTION_ACK",
+                client_opcode_name_for_logging(packet.opcode())
+            ));
+        }
+        debug!(target:"network", "client {name} authenticated successfully as {}", auth.account_name());
+
+        let codec = WorldSocketCodec {
+            crypt:          WorldPacketCrypt::new(&session_key),
+            pending_header: None,
+        };
+        let socket = Socket::new_with_codec(&Handle::current(), name, rd, wr, codec, max_frame_size, packet_log);
+        Ok(Self {
+            _permit,
+            socket,
+            connection_type,
+            auth,
+        })
+    }
+}
+
+/// Result of the auth handlers, the outer error closes the socket without replying while the inner
+/// error replies with `SMSG_AUTH_RESPONSE` with the given error code before closing the socket.
+type AuthResult<T> = AzResult<Result<T, (BattlenetRpcErrorCode, AzError)>>;
+
+fn hmac_sha256(key: &[u8], parts: &[&[u8]]) -> [u8; 32] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC can take key of any size");
+    for p in parts {
+        mac.update(p);
+    }
+    mac.finalize().into_bytes().into()
+}
+
+/// HandleAuthSession / HandleAuthSessionCallback in TC/AC
+async fn handle_auth_session(
+    login_db: &Pool<azothacore_database::DbDriver>,
+    realm: &Realm,
+    name: &AddressOrName,
+    server_challenge: &[u8; 16],
+    auth_session: AuthSession,
+) -> AuthResult<([u8; WORLD_SESSION_KEY_SIZE], WorldSocketAccountInfo)> {
+    let account_name = auth_session.realm_join_ticket;
+    // Get the account information from the auth database
+    let Some(account) = LoginDatabase::sel_account_info_by_name::<_, DbAccountInfo>(login_db, args!(realm.id.realm, &account_name)?).await? else {
+        // We can not log here, as we do not know the account. Thus, no accountId.
+        return Ok(Err((
+            BattlenetRpcErrorCode::Denied,
+            az_error!("[HandleAuthSession] Sent Auth Response (unknown account)."),
+        )));
+    };
+    let bnet_key_data = hex::decode(&account.session_key)?;
+
+    // TODO: Win64AuthSeed and Mac64AuthSeed depends on the OS of the client, these are only
+    // available via BuildInfo which is not implemented yet.
+    let digest_key_hash = Sha256::digest(&bnet_key_data);
+    let digest = hmac_sha256(&digest_key_hash, &[&auth_session.local_challenge, server_challenge, &AUTH_CHECK_SEED]);
+    if digest[..AUTH_DIGEST_SIZE] != auth_session.digest {
+        return Err(az_error!(
+            "[HandleAuthSession] Authentication failed for account: {} ('{account_name}') address: {name}",
+            account.id
+        )
+        .context(FailedAccountLogin(account.id)));
+    }
+
+    let session_key_hmac = hmac_sha256(&digest_key_hash, &[server_challenge, &auth_session.local_challenge, &SESSION_KEY_SEED]);
+    let mut session_key = [0u8; WORLD_SESSION_KEY_SIZE];
+    SessionKeyGenerator::<Sha256>::new(&session_key_hmac).generate(&mut session_key);
+
+    let address = name.ip_str_or_name();
+    // As we don't know if attempted login process by ip works, we update last_attempt_ip right away
+    LoginDatabase::upd_last_attempt_ip(login_db, args!(&address, &account_name)?).await?;
+    // This also allows to check for possible "hack" attempts on account
+    LoginDatabase::upd_account_info_continued_session(login_db, args!(hex::encode_upper(session_key), account.id)?).await?;
+
+    // First reject the connection if packet contains invalid data or realm state doesn't allow logging in
+    if auth_session.realm_id != realm.id.realm {
+        return Ok(Err((
+            BattlenetRpcErrorCode::Denied,
+            az_error!(
+                "[HandleAuthSession] Client {address} requested connecting with realm id {} but this realm has id {}",
+                auth_session.realm_id,
+                realm.id.realm
+            ),
+        )));
+    }
+
+    // Must be done before WorldSession is created
+    if account.is_locked_to_ip.is_some_and(|b| b) && account.last_ip.as_ref().is_none_or(|ip| *ip != address) {
+        return Ok(Err((
+            BattlenetRpcErrorCode::RiskAccountLocked,
+            az_error!(
+                "[HandleAuthSession] Sent Auth Response (Account IP differs. Original IP: {:?}, new IP: {address}).",
+                account.last_ip
+            )
+            .context(FailedAccountLogin(account.id)),
+        )));
+    }
+
+    if account.is_banned() {
+        return Ok(Err((
+            BattlenetRpcErrorCode::GameAccountBanned,
+            az_error!("[HandleAuthSession] Sent Auth Response (Account banned).").context(FailedAccountLogin(account.id)),
+        )));
+    }
+
+    let security = account
+        .security
+        .map(AccountTypes::try_from)
+        .transpose()
+        .map_err(AzError::from)?
+        .unwrap_or(AccountTypes::SecPlayer);
+    // Check locked state for server
+    let allowed_account_type = realm.allowed_security_level;
+    if allowed_account_type > AccountTypes::SecPlayer && security < allowed_account_type {
+        return Ok(Err((
+            BattlenetRpcErrorCode::Denied,
+            az_error!("[HandleAuthSession] User tries to login but his security level is not enough").context(FailedAccountLogin(account.id)),
+        )));
+    }
+
+    let locale = account.locale.and_then(|l| Locale::try_from(u32::from(l)).ok()).unwrap_or(Locale::enUS);
+    Ok(Ok((
+        session_key,
+        WorldSocketAccountInfo {
+            id: account.id,
+            name: account_name,
+            bnet_account_id: account.bnet_account_id,
+            security,
+            expansion: account.expansion,
+            mute_time: account.mute_time,
+            locale,
+            recruiter: account.recruiter,
+            os: account.os,
+            is_recruiter: account.recruited_account_id.is_some(),
+        },
+    )))
+}
+
+/// HandleAuthContinuedSession / HandleAuthContinuedSessionCallback in TC/AC
+async fn handle_auth_continued_session(
+    login_db: &Pool<azothacore_database::DbDriver>,
+    server_challenge: &[u8; 16],
+    auth_session: AuthContinuedSession,
+) -> AuthResult<([u8; WORLD_SESSION_KEY_SIZE], WorldSocketAuth)> {
+    // ConnectToKey in TC/AC, AccountId is the lower 32 bits, followed by a single bit of ConnectionType
+    let account_id = auth_session.key as u32;
+    if (auth_session.key >> 32) & 1 != ConnectionType::Instance as u64 {
+        return Ok(Err((
+            BattlenetRpcErrorCode::Denied,
+            az_error!("[HandleAuthContinuedSession] account {account_id} tried to continue a session that is not for an instance connection"),
+        )));
+    }
+
+    let Some(info) = LoginDatabase::sel_account_info_continued_session::<_, DbContinuedSessionInfo>(login_db, args!(account_id)?).await? else {
+        return Ok(Err((
+            BattlenetRpcErrorCode::Denied,
+            az_error!("[HandleAuthContinuedSession] unknown account {account_id}"),
+        )));
+    };
+    let session_key: [u8; WORLD_SESSION_KEY_SIZE] = hex::decode(&info.sessionkey)?
+        .try_into()
+        .map_err(|k: Vec<u8>| az_error!("continued session key has unexpected length {}", k.len()))?;
+
+    let digest = hmac_sha256(
+        &session_key,
+        &[
+            &auth_session.key.to_le_bytes(),
+            &auth_session.local_challenge,
+            server_challenge,
+            &CONTINUED_SESSION_SEED,
+        ],
+    );
+    if digest[..AUTH_DIGEST_SIZE] != auth_session.digest {
+        return Err(az_error!(
+            "[HandleAuthContinuedSession] Authentication failed for account: {account_id} ('{}')",
+            info.username
+        ));
+    }
+    Ok(Ok((
+        session_key,
+        WorldSocketAuth::ContinuedSession {
+            account_id,
+            account_name: info.username,
+            key: auth_session.key,
+        },
+    )))
+}
+
+async fn send_auth_response_error<W: AsyncWrite + Unpin>(wr: &mut W, result: BattlenetRpcErrorCode) -> AzResult<()> {
+    write_plain_packet(wr, &AuthResponse { result }.to_world_packet()).await
+}
+
+async fn write_plain_packet<W: AsyncWrite + Unpin>(wr: &mut W, packet: &WorldPacket) -> AzResult<()> {
+    wr.write_all(&packet.to_frame()).await?;
+    wr.flush().await?;
+    Ok(())
+}
+
+async fn read_plain_packet<R: AsyncRead + Unpin>(rd: &mut R) -> AzResult<WorldPacket> {
+    let mut raw_header = [0u8; WORLD_PACKET_HEADER_SIZE];
+    rd.read_exact(&mut raw_header).await?;
+    let header = WorldPacketHeader::from_bytes(raw_header);
+    if !header.is_valid_size() {
+        return Err(az_error!("client sent malformed packet (size: {}, cmd: {})", header.size, header.command));
+    }
+    let mut data = vec![0u8; header.size as usize];
+    rd.read_exact(&mut data).await?;
+    Ok(WorldPacket::new(header.command, data))
+}
+
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AddWorldSocketSet;
+
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WorldSocketReadPacketsSet;
+
+/// Handles new connections from both the world and instance [socket_mgr_plugin](crate::shared::networking::socket_mgr::socket_mgr_plugin)s,
+/// inserting a [WorldSocket] component for each of them that successfully completes the handshake.
+pub fn world_socket_handling_plugin(app: &mut App) {
+    app.add_systems(
+        FixedUpdate,
+        (
+            handle_new_world_socket::<WorldSocket>.run_if(az_startup_succeeded()),
+            handle_new_world_socket::<InstanceSocket>.run_if(az_startup_succeeded()),
+        )
+            .in_set(AddWorldSocketSet),
+    )
+    .add_systems(Update, read_handler.run_if(az_startup_succeeded()).in_set(WorldSocketReadPacketsSet));
+}
+
+fn handle_new_world_socket<S: ConnectionComponent>(
+    mut commands: Commands,
+    login_db: Res<LoginDatabase>,
+    realm: Res<CurrentRealm>,
+    rt: Res<TokioRuntime>,
+    packet_log: Option<Res<PacketLog>>,
+    mut sock_recv: ResMut<SocketReceiver<S>>,
+) {
+    while let Ok(NewTcpConnection {
+        permit,
+        name,
+        conn,
+        max_frame_size,
+    }) = sock_recv.0.try_recv()
+    {
+        let entity = commands.spawn_empty().id();
+        let login_db = (**login_db).clone();
+        let realm = (**realm).clone();
+        let packet_log = packet_log.as_deref().cloned();
+
+        let task = rt.spawn(async move {
+            let sock = match WorldSocket::start(login_db, realm, permit, name, conn, max_frame_size, packet_log).await {
+                Err(e) => {
+                    error!(target:"network", cause=?e, "error starting world socket from new TCP connection");
+                    let failed_login = e.downcast_ref::<FailedAccountLogin>().map(|f| f.0);
+                    let mut command_queue = CommandQueue::default();
+                    command_queue.push(move |world: &mut World| {
+                        world.despawn(entity);
+                        if let Some(account_id) = failed_login {
+                            run_account_login_hook(world, account_id, false);
+                        }
+                    });
+                    return command_queue;
+                },
+                Ok(s) => s,
+            };
+
+            let login = match sock.auth() {
+                WorldSocketAuth::Session(info) => Some(info.id),
+                WorldSocketAuth::ContinuedSession { .. } => None,
+            };
+            let mut command_queue = CommandQueue::default();
+            command_queue.push(move |world: &mut World| {
+                world.entity_mut(entity).insert(sock).remove::<RunStartTcpSocketTask<S>>();
+                if let Some(account_id) = login {
+                    run_account_login_hook(world, account_id, true);
+                }
+            });
+            command_queue
+        });
+        commands.entity(entity).insert(RunStartTcpSocketTask::<S>::new(task));
+    }
+}
+
+/// Calls [ScriptMgr::on_account_login] or [ScriptMgr::on_failed_account_login] once `CMSG_AUTH_SESSION` is handled
+fn run_account_login_hook(world: &mut World, account_id: u32, succeeded: bool) {
+    let res = world.run_system_once_with(account_id, move |In(account_id): In<u32>, mut commands: Commands, script_mgr: ScriptMgr| {
+        if succeeded {
+            script_mgr.on_account_login(&mut commands, account_id);
+        } else {
+            script_mgr.on_failed_account_login(&mut commands, account_id);
+        }
+    });
+    if let Err(e) = res {
+        error!(target:"network", cause=?e, account_id, "error running account login script hooks");
+    }
+}
+
+/// ReadDataHandler in TC/AC
+///
+/// Handles the opcodes that the socket handles itself, queueing everything else onto the [WorldSession]
+/// the socket belongs to.
+fn read_handler(
+    mut commands: Commands,
+    cfg: Res<ConfigMgr<WorldConfig>>,
+    mut sockets: Query<(Entity, &mut WorldSocket, Option<&LinkedWorldSession>)>,
+    mut sessions: Query<&mut WorldSession>,
+) {
+    for (e, mut sock, linked_session) in &mut sockets {
+        let packets = match sock.receive(None) {
+            Err(err) => {
+                debug!(target:"network", cause=?err, "world socket closed, removing");
+                sock.close();
+                commands.entity(e).despawn();
+                continue;
+            },
+            Ok(p) => p,
+        };
+        let session_entity = linked_session.map_or(e, |l| l.0);
+        for packet in packets {
+            let opcode = packet.opcode();
+            let Some(client_opcode) = OpcodeClient::from_raw(opcode) else {
+                warn!(target:"network", "client {} sent {}, dropping", sock.remote_name(), client_opcode_name_for_logging(opcode));
+                continue;
+            };
+            let mut session = sessions.get_mut(session_entity).ok();
+            let res = match client_opcode {
+                OpcodeClient::Ping => Ping::from_world_packet(packet)
+                    .map_err(AzError::from)
+                    .and_then(|p| handle_ping(&sock, session.as_deref_mut(), p)),
+                OpcodeClient::KeepAlive => {
+                    if let Some(session) = &mut session {
+                        session.reset_time_out_time(true, &cfg);
+                    }
+                    Ok(())
+                },
+                OpcodeClient::LogDisconnect => {
+                    // contains uint32 disconnectReason;
+                    let reason = packet.into_data().try_get_u32_le().unwrap_or_default();
+                    debug!(target:"network", "client {} logged disconnect with reason {reason}", sock.remote_name());
+                    Ok(())
+                },
+                OpcodeClient::AuthSession | OpcodeClient::AuthContinuedSession | OpcodeClient::EnableEncryptionAck => Err(az_error!(
+                    "client {} sent {} after having already been authenticated",
+                    sock.remote_name(),
+                    client_opcode_name_for_logging(opcode)
+                )),
+                _ => match &mut session {
+                    None => Err(az_error!(
+                        "client {} sent {} but is not authenticated or got recently kicked",
+                        sock.remote_name(),
+                        client_opcode_name_for_logging(opcode)
+                    )),
+                    Some(session) => {
+                        // Our Idle timer will reset on any non PING opcodes on login screen, allowing us to catch people idling.
+                        session.reset_time_out_time(false, &cfg);
+                        session.queue_packet(packet);
+                        Ok(())
+                    },
+                },
+            };
+            if let Err(err) = res {
+                error!(target:"network", cause=?err, "error handling world packet, closing socket");
+                sock.close();
+                commands.entity(e).despawn();
+                break;
+            }
+        }
+    }
+}
+
+/// HandlePing in TC/AC
+fn handle_ping(sock: &WorldSocket, session: Option<&mut WorldSession>, ping: Ping) -> AzResult<()> {
+    let Some(session) = session else {
+        return Err(az_error!(
+            "client {} sent CMSG_PING, but is not authenticated or got recently kicked",
+            sock.remote_name()
+        ));
+    };
+    session.set_latency(ping.latency);
+    sock.send_packet(&Pong { serial: ping.serial }.to_world_packet())?;
+    Ok(())
+}
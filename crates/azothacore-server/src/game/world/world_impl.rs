@@ -3,7 +3,8 @@ use std::time::Instant;
 use azothacore_common::{
     bevy_app::TokioRuntime,
     collision::management::vmap_mgr2::{vmap_mgr2_plugin, VMapManager2InitSet, VmapConfig},
-    configuration::{ConfigMgr, DataDirConfig},
+    configuration::{ConfigMgr, ConfigReloadFinishedEvent, DataDirConfig},
+    log::LogGuard,
     AccountTypes,
 };
 use azothacore_database::{
@@ -21,10 +22,12 @@ use tracing::{error, info};
 
 use crate::{
     game::{
+        cache::character_cache::CharacterCache,
         conditions::disable_mgr::{disable_mgr_plugin, DisableMgr, DisableMgrInitialLoadSet},
         entities::unit::{PlayerBaseMoveSpeed, BASE_MOVE_SPEED},
-        globals::object_mgr::{handle_set_highest_guids_error, set_highest_guids},
-        map::map_mgr::{GridCleanupTimer, MapUpdateTimer},
+        globals::object_mgr::{handle_set_highest_guids_error, load_player_info, load_reserved_players_names, load_script_names, set_highest_guids},
+        grid::grid_notifiers::grid_notifiers_plugin,
+        map::map_mgr::{map_mgr_plugin, GridCleanupTimer, MapUpdateTimer},
         scripting::script_mgr::ScriptMgr,
         time::WorldUpdateTime,
         world::{AllowedSecurityLevel, CurrentRealm, WorldSets, WorldTrait},
@@ -94,6 +97,8 @@ fn add_set_initial_world_settings_system(app: &mut App) {
         //- Initialize VMapManager function pointers (to untangle game/collision circular deps)
         disable_mgr_plugin,
         vmap_mgr2_plugin::<WorldConfig, DB2Storage<LiquidType>, DisableMgr>,
+        map_mgr_plugin,
+        grid_notifiers_plugin,
     ))
     .add_systems(
         Startup,
@@ -104,6 +109,14 @@ fn add_set_initial_world_settings_system(app: &mut App) {
             World::load_db_allowed_security_level,
             // Init highest guids before any table loading to prevent using not initialized guids in some code.
             set_highest_guids.pipe(handle_set_highest_guids_error),
+            // Loading Reserved Names
+            load_reserved_players_names,
+            // Loading Script Names
+            load_script_names,
+            // Loading Player Create Data
+            load_player_info,
+            // Loading Player Name data
+            CharacterCache::load,
         )
             .in_set(WorldSets::SetInitialWorldSettings),),
     )
@@ -162,23 +175,32 @@ impl WorldTrait<WorldConfig> for World {
         }
     }
 
-    fn load_config_settings(In(reload): In<bool>, mut commands: Commands, mut cfg: ResMut<ConfigMgr<WorldConfig>>, script_mgr: ScriptMgr) {
+    fn load_config_settings(
+        In(reload): In<bool>,
+        mut commands: Commands,
+        mut cfg: ResMut<ConfigMgr<WorldConfig>>,
+        log_guard: Option<Res<LogGuard>>,
+        script_mgr: ScriptMgr,
+    ) {
         // Happens earlier than AC b/c config reload is built into Config object directly
         script_mgr.on_before_config_load(&mut commands, reload);
         if reload {
-            if let Err(e) = cfg.reload_from_path() {
-                error!(target:"server.loading", cause=?e, "World settings reload fail: can't read settings.");
-                return;
-            }
-            // TODO: Implement me! => Right now appenders / loggers logic is buggy and also coupled together such that
-            //       we cannot do reloads easily.
-            // Use our log::init()
-            // sLog->LoadFromConfig();
+            let changes = match cfg.reload_from_path() {
+                Err(e) => {
+                    error!(target:"server.loading", cause=?e, "World settings reload fail: can't read settings.");
+                    return;
+                },
+                Ok(c) => c,
+            };
+            // Appenders and loggers are reloaded by the logging plugin from this event, i.e. sLog->LoadFromConfig() in TC / AC
+            commands.send_event(ConfigReloadFinishedEvent::<WorldConfig>::new(Ok(changes)));
+            // TODO: Implement me!
             // sMetric->LoadFromConfigs();
         }
-        // TODO: Implement me!
-        // // Set realm id and enable db logging
-        // sLog->SetRealmId(realm.Id.Realm);
+        // Set realm id and enable db logging
+        if let Some(log_guard) = &log_guard {
+            log_guard.set_realm_id(cfg.RealmID);
+        }
 
         info!(target:"server.loading", "Using {dbc:?} DBC Locale", dbc=cfg.DBCLocale);
         // load update time related configs
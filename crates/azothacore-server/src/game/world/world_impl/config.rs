@@ -1,14 +1,9 @@
-use std::{
-    mem,
-    net::IpAddr,
-    path::{Path, PathBuf},
-    time::Duration,
-};
+use std::{net::IpAddr, path::PathBuf, time::Duration};
 
 use azothacore_common::{
     bounded_nums::{LowerBoundedNum, LowerBoundedNumMissingDefault, RangedBoundedNum, UpperBoundedNum},
     collision::management::vmap_mgr2::VmapConfig,
-    configuration::{from_env_toml, Config, DataDirConfig, DatabaseInfo, DbUpdates, LogAppender, LogFlags, LogLevel, LogLoggerConfig},
+    configuration::{Config, DataDirConfig, DatabaseInfo, DbUpdates, LogAppender, LogFlags, LogLevel, LogLevelOverride, LogLoggerConfig},
     durationb,
     durationb_days,
     durationb_hours,
@@ -26,6 +21,7 @@ use flagset::FlagSet;
 use serde::{Deserialize, Serialize};
 use serde_default::DefaultFromSerde;
 use serde_inline_default::serde_inline_default;
+use tokio::net::ToSocketAddrs;
 use tracing::error;
 
 use crate::{
@@ -47,6 +43,7 @@ use crate::{
             grid_defines::{MIN_GRID_DELAY, MIN_MAP_UPDATE_DELAY},
             DEFAULT_VISIBILITY_NOTIFY_PERIOD,
         },
+        server::world_socket::{InstanceSocket, WorldSocket},
         world::{
             ArenaQueueAnnouncerDetail,
             CharDeleteMethod,
@@ -66,6 +63,7 @@ use crate::{
     },
     shared::{
         data_stores::dbc_enums::{LEVEL_LIMIT_MAX, LEVEL_LIMIT_MAX_DEFAULT},
+        networking::{packet_log::PacketLogConfig, socket_mgr::SocketMgrConfig},
         realms::{realm_list::RealmListConfig, RealmType},
         shared_defines::{
             AccountPasswordChangeSecurityPolicy,
@@ -111,25 +109,34 @@ pub fn default_worldserver_log_appenders() -> Vec<LogAppender> {
             // ],
         },
         LogAppender::File {
-            name:      String::from("Server"),
-            min_level: Warning,
-            max_level: Error,
-            flags:     AddLogLevel | AddLogFilter | TruncateFile | BackupBeforeOverwrite | AddLogTimestamps,
-            file:      String::from("Server.log"),
+            name:          String::from("Server"),
+            min_level:     Warning,
+            max_level:     Error,
+            flags:         AddLogLevel | AddLogFilter | TruncateFile | BackupBeforeOverwrite | AddLogTimestamps,
+            file:          String::from("Server.log"),
+            max_file_size: 0,
+            max_files:     0,
+            compress:      false,
         },
         LogAppender::File {
-            name:      String::from("GM"),
-            min_level: Warning,
-            max_level: Error,
-            flags:     AddLogTimestamps | AddLogLevel | AddLogFilter | AppendFileTimestamps | TruncateFile | BackupBeforeOverwrite,
-            file:      String::from("gm.log"),
+            name:          String::from("GM"),
+            min_level:     Warning,
+            max_level:     Error,
+            flags:         AddLogTimestamps | AddLogLevel | AddLogFilter | AppendFileTimestamps | TruncateFile | BackupBeforeOverwrite,
+            file:          String::from("gm.log"),
+            max_file_size: 0,
+            max_files:     0,
+            compress:      false,
         },
         LogAppender::File {
-            name:      String::from("DBErrors"),
-            min_level: Warning,
-            max_level: Error,
-            flags:     TruncateFile | BackupBeforeOverwrite,
-            file:      String::from("DBErrors.log"),
+            name:          String::from("DBErrors"),
+            min_level:     Warning,
+            max_level:     Error,
+            flags:         TruncateFile | BackupBeforeOverwrite,
+            file:          String::from("DBErrors.log"),
+            max_file_size: 0,
+            max_files:     0,
+            compress:      false,
         },
     ]
 }
@@ -212,6 +219,13 @@ pub struct WorldConfig {
     #[serde_inline_default(format!("{BASE_DIR}/logs").into())] pub LogsDir: PathBuf,
     #[serde(default="default_worldserver_log_appenders")] pub Appender: Vec<LogAppender>,
     #[serde(default="default_worldserver_log_configs")] pub Logger: Vec<LogLoggerConfig>,
+    /// Targets logged at a more verbose level for a while, i.e. `{ target = "server::rest", level = "Debug", duration = "10m" }`.
+    /// Applied on startup and whenever they change on a config reload.
+    #[serde(default)] pub LogLevelOverride: Vec<LogLevelOverride>,
+    /// Packet logging file for the world server. Filename is relative to LogsDir.
+    ///
+    /// Default "" - (Disabled), "World.pkt" - (Enabled)
+    #[serde(default)] pub PacketLogFile: String,
     /// Time between realm list updates.
     #[serde(default)] pub RealmsStateUpdateDelay: LowerBoundedNum<Duration, { durationb_s!(0) }, { durationb_s!(10) }>,
     #[serde_inline_default(1)] pub RealmID: u32,
@@ -521,9 +535,13 @@ pub struct WorldConfig {
     /// Time for weather update interval.
     #[serde(default)] pub ChangeWeatherInterval: LowerBoundedNum<Duration, { durationb_s!(0) }, { durationb_mins!(10) }>,
     /// TCP port to reach the world server.
-    #[serde_inline_default(8085)] pub WorldServerPort: u32,
+    #[serde_inline_default(8085)] pub WorldServerPort: u16,
     /// TCP port to for second world connection.
     #[serde_inline_default(8086)] pub InstanceServerPort: u16,
+    /// Largest frame in bytes that a client may send on either world connection before it is disconnected.
+    ///
+    /// Default 262150, 0 - (Unlimited)
+    #[serde_inline_default(0x40006)] pub MaxFrameSize: usize,
     /// Time after which a connection being idle on the character
     /// selection screen is disconnected.
     #[serde(default)] pub SocketTimeOutTime: LowerBoundedNum<Duration, { durationb_s!(0) }, { durationb_mins!(15) }>,
@@ -1085,8 +1103,28 @@ macro_rules! clamp_cfg_min {
 }
 
 impl Config for WorldConfig {
-    fn load<P: AsRef<Path>>(config_toml: P) -> AzResult<Self> {
-        let mut cfg: Self = from_env_toml(config_toml)?;
+    const RESTART_REQUIRED_KEYS: &'static [&'static str] = &[
+        "LoginDatabaseInfo",
+        "WorldDatabaseInfo",
+        "CharacterDatabaseInfo",
+        "HotfixDatabaseInfo",
+        "Updates",
+        "DataDir",
+        "LogsDir",
+        "PacketLogFile",
+        "RealmID",
+        "BindIP",
+        "WorldServerPort",
+        "InstanceServerPort",
+        "MaxFrameSize",
+        "GameType",
+        "RealmZone",
+        "MaxPlayerLevel",
+        "Expansion",
+    ];
+
+    fn validate(self) -> AzResult<Self> {
+        let mut cfg = self;
         // Load the rest of the stuff that cannot be set on deserialisation time
         if cfg.BaseMapLoadAllGrids && cfg.GridUnload {
             error!(target:"server.loading", "BaseMapLoadAllGrids enabled, but GridUnload also enabled. GridUnload must be disabled to enable base map pre-loading. Base map pre-loading disabled");
@@ -1122,38 +1160,6 @@ impl Config for WorldConfig {
         }
         Ok(cfg)
     }
-
-    fn reload(&mut self, new: Self) {
-        let old = mem::replace(self, new);
-        if self.WorldServerPort != old.WorldServerPort {
-            error!(target:"server.loading", "WorldServerPort option can't be changed on reload, using current value ({}).", old.WorldServerPort);
-            self.WorldServerPort = old.WorldServerPort
-        }
-        if self.InstanceServerPort != old.InstanceServerPort {
-            error!(target:"server.loading", "InstanceServerPort option can't be changed on reload, using current value ({}).", old.InstanceServerPort);
-            self.InstanceServerPort = old.InstanceServerPort
-        }
-        if self.GameType != old.GameType {
-            error!(target:"server.loading", "GameType option can't be changed on reload, using current value ({:?}).", old.GameType);
-            self.GameType = old.GameType;
-        }
-        if self.RealmZone != old.RealmZone {
-            error!(target:"server.loading", "RealmZone option can't be changed on reload, using current value ({:?}).", old.RealmZone);
-            self.RealmZone = old.RealmZone;
-        }
-        if self.MaxPlayerLevel != old.MaxPlayerLevel {
-            error!(target:"server.loading", "MaxPlayerLevel option can't be changed at config reload, using current value ({}).", old.MaxPlayerLevel);
-            self.MaxPlayerLevel = old.MaxPlayerLevel;
-        }
-        if self.Expansion != old.Expansion {
-            error!(target:"server.loading", "Expansion option can't be changed at config reload, using current value ({:?}).", old.Expansion);
-            self.Expansion = old.Expansion;
-        }
-        if self.DataDir != old.DataDir {
-            error!(target:"server.loading", "DataDir option can't be changed at config reload, using current value ({:?}).", old.DataDir);
-            self.DataDir = old.DataDir;
-        }
-    }
 }
 
 impl DataDirConfig for WorldConfig {
@@ -1204,6 +1210,16 @@ impl LoggingConfig for WorldConfig {
     fn retrieve_logs_dir(&self) -> PathBuf {
         self.LogsDir.clone()
     }
+
+    fn retrieve_level_overrides(&self) -> &[LogLevelOverride] {
+        &self.LogLevelOverride
+    }
+}
+
+impl PacketLogConfig for WorldConfig {
+    fn packet_log_file(&self) -> Option<PathBuf> {
+        (!self.PacketLogFile.is_empty()).then(|| self.LogsDir.join(&self.PacketLogFile))
+    }
 }
 
 impl RealmListConfig for WorldConfig {
@@ -1211,3 +1227,83 @@ impl RealmListConfig for WorldConfig {
         *self.RealmsStateUpdateDelay
     }
 }
+
+impl SocketMgrConfig<WorldSocket> for WorldConfig {
+    fn retrieve_bind_addr(&self) -> impl ToSocketAddrs {
+        (self.BindIP, self.WorldServerPort)
+    }
+
+    fn retrieve_max_frame_size(&self) -> Option<usize> {
+        (self.MaxFrameSize > 0).then_some(self.MaxFrameSize)
+    }
+}
+
+impl SocketMgrConfig<InstanceSocket> for WorldConfig {
+    fn retrieve_bind_addr(&self) -> impl ToSocketAddrs {
+        (self.BindIP, self.InstanceServerPort)
+    }
+
+    fn retrieve_max_frame_size(&self) -> Option<usize> {
+        (self.MaxFrameSize > 0).then_some(self.MaxFrameSize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use azothacore_common::configuration::{ConfigChanges, ConfigMgr};
+
+    use super::*;
+
+    #[test]
+    fn it_keeps_every_restart_required_key_on_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let cfg_path = dir.path().join("worldserver.toml");
+        fs::write(&cfg_path, "").unwrap();
+        let mut cfg_mgr = ConfigMgr::<WorldConfig>::load(cfg_path.clone(), None, false).unwrap();
+        let before = (*cfg_mgr).clone();
+
+        fs::write(
+            &cfg_path,
+            r#"
+DataDir = "/srv/azcore/data"
+LogsDir = "/srv/azcore/logs"
+PacketLogFile = "World.pkt"
+RealmID = 2
+BindIP = "127.0.0.1"
+WorldServerPort = 9085
+InstanceServerPort = 9086
+MaxFrameSize = 1024
+GameType = "Pvp"
+RealmZone = "UnitedStates"
+MaxPlayerLevel = 60
+Expansion = "Classic"
+
+[LoginDatabaseInfo]
+Address = "db:3306"
+
+[WorldDatabaseInfo]
+Address = "db:3306"
+
+[CharacterDatabaseInfo]
+Address = "db:3306"
+
+[HotfixDatabaseInfo]
+Address = "db:3306"
+
+[Updates]
+AutoSetup = false
+"#,
+        )
+        .unwrap();
+        let changes = cfg_mgr.reload_from_path().unwrap();
+        for key in WorldConfig::RESTART_REQUIRED_KEYS {
+            assert!(
+                changes.restart_required.iter().any(|k| k == key || k.starts_with(&format!("{key}."))),
+                "{key} is not a key of the config, or was not changed by the test"
+            );
+        }
+        assert_eq!(ConfigChanges::between(&before, &*cfg_mgr).unwrap(), ConfigChanges::default());
+    }
+}
@@ -1,6 +1,7 @@
 pub mod data_stores;
 pub mod id_generators;
 pub mod networking;
+pub mod packets;
 pub mod realms;
 pub mod secrets;
 pub mod shared_defines;
@@ -10,14 +11,14 @@ use std::{
     panic,
 };
 
-use azothacore_common::bevy_app::TokioRuntime;
+use azothacore_common::{bevy_app::TokioRuntime, configuration::ConfigReloadEvent};
 use bevy::{app::AppExit, prelude::*, tasks::poll_once};
 use thiserror::Error;
 use tokio::{
     sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
     task::JoinHandle,
 };
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 pub fn bnetrpc_zcompress(mut json: Vec<u8>) -> io::Result<Vec<u8>> {
     use flate2::{write::ZlibEncoder, Compression};
@@ -99,9 +100,12 @@ pub async fn signal_handler(signal_broadcast_snd: UnboundedSender<String>) -> Re
     Ok(sig)
 }
 
+/// Exits the app on the stop signals, and sends a [ConfigReloadEvent] on SIGHUP
 pub fn tokio_signal_handling_bevy_plugin(app: &mut App) {
     app.add_systems(PreStartup, overwrite_signal_handlers)
-        .add_systems(FixedUpdate, try_receive_signal);
+        .add_systems(FixedUpdate, (try_receive_signal, try_receive_reload_signal));
+    #[cfg(target_os = "linux")]
+    app.add_systems(PreStartup, listen_for_reload_signal);
 }
 
 #[derive(Component)]
@@ -147,3 +151,46 @@ fn try_receive_signal(
     // app exit, we will forcefully panic instead.
     commands.entity(e).remove::<SignalHandlerTokioTask>();
 }
+
+/// Receives the reload signals, i.e. SIGHUP
+#[derive(Resource)]
+struct ReloadSignalReceiver(UnboundedReceiver<()>);
+
+#[cfg(target_os = "linux")]
+fn listen_for_reload_signal(mut commands: Commands, rt: Res<TokioRuntime>) {
+    let (snd, rcv) = unbounded_channel();
+    rt.spawn(async move {
+        let mut sig_hangup = match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup()) {
+            Err(e) => {
+                error!(cause=%SignalError::reg_error("sig_hangup", e), "error init signal handler");
+                return;
+            },
+            Ok(s) => s,
+        };
+        while sig_hangup.recv().await.is_some() {
+            if snd.send(()).is_err() {
+                return;
+            }
+        }
+    });
+    commands.insert_resource(ReloadSignalReceiver(rcv));
+}
+
+fn try_receive_reload_signal(receiver: Option<ResMut<ReloadSignalReceiver>>, ev_reload: Option<ResMut<Events<ConfigReloadEvent>>>) {
+    let Some(mut receiver) = receiver else {
+        return;
+    };
+    let mut received = false;
+    while receiver.0.try_recv().is_ok() {
+        received = true;
+    }
+    if !received {
+        return;
+    }
+    let Some(mut ev_reload) = ev_reload else {
+        warn!(signal = "SIGHUP", "no configs to reload");
+        return;
+    };
+    info!(signal = "SIGHUP", "Reloading configs due to receiving a reload signal");
+    ev_reload.send(ConfigReloadEvent);
+}
@@ -74,7 +74,10 @@ use wow_db2::DB2;
 
 use crate::{
     game::world::WorldConfig,
-    shared::data_stores::{db2_loader::DB2FileLoader, db2_structure::*, dbc_enums::BATTLE_PET_SPECIES_MAX_ID},
+    shared::{
+        data_stores::{db2_loader::DB2FileLoader, db2_structure::*, dbc_enums::BATTLE_PET_SPECIES_MAX_ID},
+        shared_defines::ResponseCodes,
+    },
 };
 
 #[derive(Resource)]
@@ -1076,6 +1079,39 @@ pub struct DB2Mgr<'w> {
     pub alliance_taxi_nodes_mask: Res<'w, AllianceTaxiNodesMask>,
 }
 
+impl DB2Mgr<'_> {
+    /// DB2Manager::GetItemModifiedAppearance in TC
+    pub fn get_item_modified_appearance(&self, item_id: u32, appearance_mod_id: u8) -> Option<&ItemModifiedAppearance> {
+        self.item_modified_appearances_by_item
+            .get(&(item_id, appearance_mod_id))
+            // Fall back to unmodified appearance
+            .or_else(|| self.item_modified_appearances_by_item.get(&(item_id, 0)))
+    }
+
+    /// DB2Manager::GetItemDisplayId in TC
+    pub fn get_item_display_id(&self, item_id: u32, appearance_mod_id: u8) -> u32 {
+        self.get_item_modified_appearance(item_id, appearance_mod_id)
+            .and_then(|m| self.stores.item_appearance_store.get(&u32::from(m.item_appearance_id)))
+            .map_or(0, |a| a.item_display_info_id as u32)
+    }
+}
+
+/// DB2Manager::ValidateName in TC, expects the name to already be lowercased.
+pub fn validate_name(
+    name: &str,
+    locale: Locale,
+    name_validators: &NameValidationRegexContainer,
+    name_reserved_validators: &NameReservedRegexContainer,
+) -> ResponseCodes {
+    if name_validators.get(&locale).is_some_and(|v| v.iter().any(|r| r.is_match(name))) {
+        return ResponseCodes::CharNameProfane;
+    }
+    if name_reserved_validators.get(&locale).is_some_and(|v| v.iter().any(|r| r.is_match(name))) {
+        return ResponseCodes::CharNameReserved;
+    }
+    ResponseCodes::CharNameSuccess
+}
+
 fn load_db2_store_after(
     mut commands: Commands,
     stores: DB2Stores,
@@ -1,2 +1,3 @@
+pub mod packet_log;
 pub mod socket;
 pub mod socket_mgr;
@@ -0,0 +1,314 @@
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{mpsc, Arc},
+    thread::JoinHandle,
+    time::Duration,
+};
+
+use azothacore_common::{
+    az_error,
+    bevy_app::AzStartupFailedEvent,
+    configuration::ConfigMgr,
+    hex_str,
+    utils::{unix_now, BufferDecodeError, DecodeValueFromBytes, MessageBuffer},
+    AzError,
+    AzResult,
+};
+use bevy::prelude::{App, Commands, EventWriter, IntoSystemConfigs, Res, Resource, Startup, SystemSet};
+use bytes::Bytes;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tracing::{error, info};
+
+/// Direction of a frame in the [PacketLog]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    ClientToServer,
+    ServerToClient,
+}
+
+impl Direction {
+    fn tag(self) -> &'static str {
+        match self {
+            Direction::ClientToServer => "C2S",
+            Direction::ServerToClient => "S2C",
+        }
+    }
+}
+
+/// The packet types that a [super::socket::Socket] decodes into, so that their frames can be written to
+/// the [PacketLog].
+pub trait LoggedFrame {
+    /// The decoded frame as bytes, which is the same form that outgoing frames are handed to
+    /// [super::socket::Socket::write] in, i.e. before any encryption.
+    fn to_log_bytes(&self) -> Bytes;
+
+    /// Human readable summary of a frame given in the form of [LoggedFrame::to_log_bytes], usually its header.
+    fn describe(direction: Direction, frame: &[u8]) -> String;
+}
+
+pub trait PacketLogConfig: Send + Sync + 'static {
+    /// PacketLogFile in TC / AC, the file to capture the packets of every socket in. None if disabled.
+    fn packet_log_file(&self) -> Option<PathBuf>;
+}
+
+/// PacketLog in TC / AC
+///
+/// Unlike TC, which writes the binary format that sniff parsers read, each frame is a line of text:
+///
+/// `<unix time in micros>\t<socket>\t<C2S|S2C>\t<description>\t<frame as hex>`
+///
+/// Frames are logged decoded, so no TLS keys or session keys are needed to make sense of them, and a capture can
+/// be fed back into a session with [replay_client].
+///
+/// The frames are written by a dedicated thread, so that the socket tasks logging them never wait on the file.
+#[derive(Resource, Clone)]
+pub struct PacketLog {
+    sender:  mpsc::Sender<PacketLogEntry>,
+    /// Dropped after `sender`, so that the writer thread is done by the time it is joined
+    _writer: Arc<PacketLogWriter>,
+}
+
+/// The thread writing the entries of a [PacketLog], joined once the last clone of the [PacketLog] is gone so that
+/// every entry logged makes it to the file
+struct PacketLogWriter(Option<JoinHandle<()>>);
+
+impl Drop for PacketLogWriter {
+    fn drop(&mut self) {
+        if let Some(handle) = self.0.take() {
+            _ = handle.join();
+        }
+    }
+}
+
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PacketLogInitSet;
+
+pub fn packet_log_plugin<C: PacketLogConfig>(app: &mut App) {
+    app.add_systems(Startup, init_packet_log::<C>.in_set(PacketLogInitSet));
+}
+
+fn init_packet_log<C: PacketLogConfig>(mut commands: Commands, cfg: Res<ConfigMgr<C>>, mut ev_startup_failed: EventWriter<AzStartupFailedEvent>) {
+    let Some(path) = cfg.packet_log_file() else {
+        return;
+    };
+    match PacketLog::create(&path) {
+        Ok(packet_log) => {
+            info!(target:"server::loading", "Logging all packets to {}", path.display());
+            commands.insert_resource(packet_log);
+        },
+        Err(e) => {
+            error!(target:"server::loading", cause=?e, "unable to open packet log file {}", path.display());
+            ev_startup_failed.send_default();
+        },
+    }
+}
+
+impl PacketLog {
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let mut w = BufWriter::new(File::create(path)?);
+        let (sender, receiver) = mpsc::channel::<PacketLogEntry>();
+        let handle = std::thread::Builder::new().name("packet-log".to_string()).spawn(move || {
+            // Runs until every sender is dropped. The file is flushed whenever there is nothing left to write, so that
+            // nothing is lost if the server goes down
+            while let Ok(entry) = receiver.recv() {
+                let res = writeln!(w, "{entry}")
+                    .and_then(|_| receiver.try_iter().try_for_each(|e| writeln!(w, "{e}")))
+                    .and_then(|_| w.flush());
+                if let Err(e) = res {
+                    error!(target:"network", cause=?e, "unable to write to packet log");
+                }
+            }
+        })?;
+        Ok(Self {
+            sender,
+            _writer: Arc::new(PacketLogWriter(Some(handle))),
+        })
+    }
+
+    /// Queues a single frame to be written, without waiting for it to be written
+    pub fn log(&self, socket: &str, direction: Direction, description: &str, frame: &[u8]) {
+        let entry = PacketLogEntry {
+            timestamp: unix_now(),
+            socket: socket.to_string(),
+            direction,
+            description: description.to_string(),
+            frame: Bytes::copy_from_slice(frame),
+        };
+        if self.sender.send(entry).is_err() {
+            error!(target:"network", socket, "unable to write to packet log, its writer is gone");
+        }
+    }
+
+    /// Logs a frame of a packet type that knows how to describe itself
+    pub fn log_frame<P: LoggedFrame>(&self, socket: &str, direction: Direction, frame: &[u8]) {
+        self.log(socket, direction, &P::describe(direction, frame), frame)
+    }
+
+    /// Reads back every entry of a packet log file
+    pub fn read_entries<P: AsRef<Path>>(path: P) -> AzResult<Vec<PacketLogEntry>> {
+        BufReader::new(File::open(path)?)
+            .lines()
+            .filter(|l| !l.as_ref().is_ok_and(|l| l.is_empty()))
+            .map(|l| l?.parse())
+            .collect()
+    }
+}
+
+/// A single line of the [PacketLog]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PacketLogEntry {
+    /// Time since the unix epoch, with microsecond precision
+    pub timestamp:   Duration,
+    pub socket:      String,
+    pub direction:   Direction,
+    pub description: String,
+    pub frame:       Bytes,
+}
+
+impl std::fmt::Display for PacketLogEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Tabs separate the columns, so they cannot be part of any column themselves
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}",
+            self.timestamp.as_micros(),
+            self.socket.replace('\t', " "),
+            self.direction.tag(),
+            self.description.replace('\t', " "),
+            hex_str!(&self.frame[..])
+        )
+    }
+}
+
+impl FromStr for PacketLogEntry {
+    type Err = AzError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut columns = s.splitn(5, '\t');
+        let mut next_column = |name| columns.next().ok_or_else(|| az_error!("packet log entry is missing the {name} column: {s}"));
+        let timestamp = Duration::from_micros(next_column("timestamp")?.parse()?);
+        let socket = next_column("socket")?.to_string();
+        let direction = match next_column("direction")? {
+            "C2S" => Direction::ClientToServer,
+            "S2C" => Direction::ServerToClient,
+            d => return Err(az_error!("unknown packet log direction {d}")),
+        };
+        let description = next_column("description")?.to_string();
+        let frame = hex::decode(next_column("frame")?)?.into();
+        Ok(Self {
+            timestamp,
+            socket,
+            direction,
+            description,
+            frame,
+        })
+    }
+}
+
+/// Plays the client side of a capture against a server over `stream`, i.e. an in-memory [tokio::io::duplex].
+///
+/// Every client frame in `entries` is written out in order while the frames that the server answers with are read
+/// until as many as the capture holds have arrived, or until nothing else arrives within `idle_timeout`. The
+/// answers are returned in the form of [LoggedFrame::to_log_bytes] so that they can be compared against the server
+/// frames of the capture.
+pub async fn replay_client<P, S>(stream: S, entries: &[PacketLogEntry], idle_timeout: Duration) -> io::Result<Vec<Bytes>>
+where
+    P: DecodeValueFromBytes + LoggedFrame,
+    S: AsyncRead + AsyncWrite,
+{
+    let (mut rd, mut wr) = tokio::io::split(stream);
+    let expected_answers = entries.iter().filter(|e| e.direction == Direction::ServerToClient).count();
+
+    let write_requests = async {
+        for e in entries.iter().filter(|e| e.direction == Direction::ClientToServer) {
+            wr.write_all(&e.frame).await?;
+        }
+        wr.flush().await
+    };
+    let read_answers = async {
+        let mut answers = Vec::with_capacity(expected_answers);
+        let mut buffer = MessageBuffer::default();
+        loop {
+            loop {
+                match P::decode_from_bytes(&mut buffer) {
+                    Ok(p) => answers.push(p.to_log_bytes()),
+                    Err(BufferDecodeError::InsufficientBytes { .. }) => break,
+                    Err(e) => return Err(io::Error::new(io::ErrorKind::InvalidData, e.to_string())),
+                }
+            }
+            if answers.len() >= expected_answers {
+                break;
+            }
+            match tokio::time::timeout(idle_timeout, rd.read_buf(&mut *buffer)).await {
+                Err(_) | Ok(Ok(0)) => break,
+                Ok(res) => _ = res?,
+            }
+        }
+        Ok(answers)
+    };
+    let (written, answers) = tokio::join!(write_requests, read_answers);
+    written?;
+    answers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_roundtrips_packet_log_entries() {
+        let entry = PacketLogEntry {
+            timestamp:   Duration::from_micros(1_700_000_000_123_456),
+            socket:      "127.0.0.1:1119".to_string(),
+            direction:   Direction::ServerToClient,
+            description: "token=1\tstatus=0".to_string(),
+            frame:       Bytes::from_static(&[0, 2, 0xAB, 0xCD]),
+        };
+        let line = entry.to_string();
+        assert_eq!(line, "1700000000123456\t127.0.0.1:1119\tS2C\ttoken=1 status=0\t0002ABCD");
+        assert_eq!(
+            line.parse::<PacketLogEntry>().unwrap(),
+            PacketLogEntry {
+                description: "token=1 status=0".to_string(),
+                ..entry
+            }
+        );
+        assert!("1700000000123456\t127.0.0.1:1119\tXXX\t\t00".parse::<PacketLogEntry>().is_err());
+        assert!("1700000000123456\t127.0.0.1:1119".parse::<PacketLogEntry>().is_err());
+    }
+
+    #[test]
+    fn it_writes_every_frame_logged_by_its_clones() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("World.pkt");
+        let packet_log = PacketLog::create(&path).unwrap();
+        let sockets = (0..4)
+            .map(|i| {
+                let packet_log = packet_log.clone();
+                std::thread::spawn(move || {
+                    for frame in 0..50u8 {
+                        packet_log.log(&format!("127.0.0.1:{i}"), Direction::ClientToServer, "frame", &[i, frame]);
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        sockets.into_iter().for_each(|s| s.join().unwrap());
+        // Waits for the writer to be done
+        drop(packet_log);
+
+        let entries = PacketLog::read_entries(&path).unwrap();
+        assert_eq!(entries.len(), 200);
+        for i in 0..4u8 {
+            let frames = entries
+                .iter()
+                .filter(|e| e.socket == format!("127.0.0.1:{i}"))
+                .map(|e| e.frame.clone())
+                .collect::<Vec<_>>();
+            // In the order that each socket logged them
+            assert_eq!(frames, (0..50u8).map(|f| Bytes::copy_from_slice(&[i, f])).collect::<Vec<_>>());
+        }
+    }
+}
@@ -2,20 +2,49 @@ use std::{
     fmt::Debug,
     io,
     net::SocketAddr,
-    sync::{atomic::AtomicBool, Arc},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
     time::Duration,
 };
 
-use azothacore_common::utils::{BufferDecodeError, DecodeValueFromBytes, MessageBuffer};
+use azothacore_common::utils::{BufferDecodeError, BufferResult, DecodeValueFromBytes, MessageBuffer};
 use tokio::{
     io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
     runtime::Handle,
     sync::mpsc,
 };
-use tracing::{debug, error, instrument, warn};
+use tracing::{debug, error, instrument};
 
-pub struct Socket<P: DecodeValueFromBytes> {
+use crate::shared::networking::packet_log::{Direction, LoggedFrame, PacketLog};
+
+/// Framing used by [Socket] to turn the incoming byte stream into packets of type `P`, and
+/// to transform outgoing bytes before they are written out.
+///
+/// The codec is owned by the socket's IO task, so it is free to hold state that advances
+/// as the stream is read and written (i.e. header encryption). Each [Socket::write] is
+/// passed to [SocketCodec::encode] as a single frame.
+pub trait SocketCodec<P>: Send + 'static {
+    fn decode(&mut self, buffer: &mut MessageBuffer) -> BufferResult<P>;
+
+    fn encode(&mut self, frame: bytes::Bytes) -> bytes::Bytes {
+        frame
+    }
+}
+
+/// The default [SocketCodec], frames packets with [DecodeValueFromBytes] and writes bytes as is.
+pub struct PlainCodec;
+
+impl<P: DecodeValueFromBytes> SocketCodec<P> for PlainCodec {
+    fn decode(&mut self, buffer: &mut MessageBuffer) -> BufferResult<P> {
+        P::decode_from_bytes(buffer)
+    }
+}
+
+pub struct Socket<P> {
     name:              AddressOrName,
+    traffic:           Arc<SocketTraffic>,
     snd_write_packets: mpsc::UnboundedSender<bytes::Bytes>,
     rcv_read_packets:  mpsc::UnboundedReceiver<P>,
     exited_flag:       Arc<AtomicBool>,
@@ -36,13 +65,44 @@ impl SocketStatus {
     }
 }
 
-impl<P: DecodeValueFromBytes + Send + 'static> Socket<P> {
-    pub fn new<R, W>(tokio_handler: &Handle, name: AddressOrName, rd: R, wr: W) -> Self
+/// Running totals of the raw bytes that have gone through a [Socket], i.e. as they are on the wire
+#[derive(Default)]
+struct SocketTraffic {
+    bytes_read:    AtomicU64,
+    bytes_written: AtomicU64,
+}
+
+impl<P: DecodeValueFromBytes + LoggedFrame + Send + 'static> Socket<P> {
+    pub fn new<R, W>(tokio_handler: &Handle, name: AddressOrName, rd: R, wr: W, max_frame_size: Option<usize>, packet_log: Option<PacketLog>) -> Self
     where
         R: AsyncRead + Unpin + Send + 'static,
         W: AsyncWrite + Unpin + Send + 'static,
+    {
+        Self::new_with_codec(tokio_handler, name, rd, wr, PlainCodec, max_frame_size, packet_log)
+    }
+}
+
+impl<P: LoggedFrame + Send + 'static> Socket<P> {
+    /// Starts the IO task of the socket. If a [PacketLog] is given, every frame that goes through the socket
+    /// is written to it in its decoded form.
+    ///
+    /// The connection is closed once the client sends a frame larger than `max_frame_size` bytes, if given.
+    pub fn new_with_codec<R, W, C>(
+        tokio_handler: &Handle,
+        name: AddressOrName,
+        rd: R,
+        wr: W,
+        codec: C,
+        max_frame_size: Option<usize>,
+        packet_log: Option<PacketLog>,
+    ) -> Self
+    where
+        R: AsyncRead + Unpin + Send + 'static,
+        W: AsyncWrite + Unpin + Send + 'static,
+        C: SocketCodec<P>,
     {
         let exited_flag = Arc::new(AtomicBool::new(false));
+        let traffic = Arc::new(SocketTraffic::default());
         let (snd_write_packets, rcv_write_packets) = mpsc::unbounded_channel();
         let (snd_read_packets, rcv_read_packets) = mpsc::unbounded_channel();
         let (snd_exiting, rcv_exiting) = mpsc::unbounded_channel();
@@ -50,6 +110,10 @@ impl<P: DecodeValueFromBytes + Send + 'static> Socket<P> {
             name.to_string(),
             rd,
             wr,
+            codec,
+            max_frame_size,
+            packet_log,
+            traffic.clone(),
             rcv_write_packets,
             snd_read_packets,
             exited_flag.clone(),
@@ -58,6 +122,7 @@ impl<P: DecodeValueFromBytes + Send + 'static> Socket<P> {
 
         Self {
             name,
+            traffic,
             snd_write_packets,
             rcv_read_packets,
             exited_flag,
@@ -67,9 +132,9 @@ impl<P: DecodeValueFromBytes + Send + 'static> Socket<P> {
     }
 
     pub fn status(&self) -> SocketStatus {
-        if self.exited_flag.load(std::sync::atomic::Ordering::SeqCst) {
+        if self.exited_flag.load(Ordering::SeqCst) {
             SocketStatus::Closed
-        } else if self.exiting_flag.load(std::sync::atomic::Ordering::SeqCst) {
+        } else if self.exiting_flag.load(Ordering::SeqCst) {
             SocketStatus::Closing
         } else {
             SocketStatus::Running
@@ -77,7 +142,7 @@ impl<P: DecodeValueFromBytes + Send + 'static> Socket<P> {
     }
 
     pub fn close(&self) {
-        self.exiting_flag.store(self.snd_exiting.send(()).is_err(), std::sync::atomic::Ordering::SeqCst);
+        self.exiting_flag.store(self.snd_exiting.send(()).is_err(), Ordering::SeqCst);
     }
 
     pub fn receive(&mut self, num_packets: Option<usize>) -> io::Result<Vec<P>> {
@@ -135,13 +200,79 @@ impl<P: DecodeValueFromBytes + Send + 'static> Socket<P> {
     pub fn remote_name(&self) -> &AddressOrName {
         &self.name
     }
+
+    /// Number of bytes read from the client so far
+    pub fn bytes_read(&self) -> u64 {
+        self.traffic.bytes_read.load(Ordering::Relaxed)
+    }
+
+    /// Number of bytes written to the client so far
+    pub fn bytes_written(&self) -> u64 {
+        self.traffic.bytes_written.load(Ordering::Relaxed)
+    }
 }
 
+/// Decodes every complete frame in `read_buffer`, sending them on to be received from [Socket::receive].
+///
+/// Stops once the buffer runs out of bytes for the next frame, leaving the partial frame in the buffer
+/// for the next read. Errors if the frame cannot be decoded or is larger than `max_frame_size`.
+fn decode_frames<P, C>(
+    name: &str,
+    codec: &mut C,
+    read_buffer: &mut MessageBuffer,
+    max_frame_size: Option<usize>,
+    packet_log: Option<&PacketLog>,
+    snd_read_packets: &mpsc::UnboundedSender<P>,
+) -> io::Result<()>
+where
+    P: LoggedFrame,
+    C: SocketCodec<P>,
+{
+    let max_frame_size = max_frame_size.unwrap_or(usize::MAX);
+    loop {
+        let len_before = read_buffer.len();
+        let packet = match codec.decode(read_buffer) {
+            Err(BufferDecodeError::InsufficientBytes { have, wanted }) => {
+                if wanted > max_frame_size {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("frame of {wanted} bytes exceeds the max frame size of {max_frame_size} bytes"),
+                    ));
+                }
+                if have > 0 {
+                    debug!("insufficient bytes, continuing to read more, have {have} but wanted {wanted}");
+                }
+                return Ok(());
+            },
+            Err(e) => return Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+            Ok(p) => p,
+        };
+        let frame_size = len_before - read_buffer.len();
+        if frame_size > max_frame_size {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("frame of {frame_size} bytes exceeds the max frame size of {max_frame_size} bytes"),
+            ));
+        }
+        if let Some(packet_log) = packet_log {
+            packet_log.log_frame::<P>(name, Direction::ClientToServer, &packet.to_log_bytes());
+        }
+        if snd_read_packets.send(packet).is_err() {
+            return Err(io::Error::new(io::ErrorKind::BrokenPipe, "unable to send read packets back"));
+        }
+    }
+}
+
+#[expect(clippy::too_many_arguments)]
 #[instrument(skip_all, fields(target="network", name=%name))]
-async fn handle_socket<R, W, P>(
+async fn handle_socket<R, W, P, C>(
     name: String,
     mut rd: R,
     mut wr: W,
+    mut codec: C,
+    max_frame_size: Option<usize>,
+    packet_log: Option<PacketLog>,
+    traffic: Arc<SocketTraffic>,
     mut rcv_write_packets: mpsc::UnboundedReceiver<bytes::Bytes>,
     snd_read_packets: mpsc::UnboundedSender<P>,
     exited_flag: Arc<AtomicBool>,
@@ -149,7 +280,8 @@ async fn handle_socket<R, W, P>(
 ) where
     R: AsyncRead + Unpin + Send + 'static,
     W: AsyncWrite + Unpin + Send + 'static,
-    P: DecodeValueFromBytes,
+    P: LoggedFrame,
+    C: SocketCodec<P>,
 {
     let mut write_flush_interval = tokio::time::interval(Duration::from_secs(1));
     write_flush_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
@@ -179,20 +311,14 @@ async fn handle_socket<R, W, P>(
                         error!(cause=?e, "shutdown read socket due to error in reading from stream");
                         break;
                     },
-                    Ok(_) => {
-                        let packet = match P::decode_from_bytes(&mut read_buffer) {
-                            Err(BufferDecodeError::InsufficientBytes{have, wanted}) => {
-                                warn!("insufficient bytes, continuing to read more, have {have} but wanted {wanted}");
-                                continue;
-                            },
-                            Err(e) => {
-                                error!(cause=?e, "error decoding data, possible socket corrruption? terminating");
-                                break;
-                            },
-                            Ok(p) => p,
-                        };
-                        if snd_read_packets.send(packet).is_err() {
-                            error!("unable to send read packets back, breaking out of handle socket loop");
+                    Ok(0) => {
+                        debug!("client closed the connection, shutting down");
+                        break;
+                    },
+                    Ok(n) => {
+                        traffic.bytes_read.fetch_add(n as u64, Ordering::Relaxed);
+                        if let Err(e) = decode_frames(&name, &mut codec, &mut read_buffer, max_frame_size, packet_log.as_ref(), &snd_read_packets) {
+                            error!(cause=?e, "error decoding data, possible socket corrruption? terminating");
                             break;
                         }
                     },
@@ -204,11 +330,17 @@ async fn handle_socket<R, W, P>(
                         error!("shutdown write socket to due to write receiver channel closing");
                         break;
                     },
-                    Some(mut b) => {
+                    Some(b) => {
+                        if let Some(packet_log) = &packet_log {
+                            packet_log.log_frame::<P>(&name, Direction::ServerToClient, &b);
+                        }
+                        let mut b = codec.encode(b);
+                        let len = b.len();
                         if let Err(e) = wr.write_all_buf(&mut b).await {
                             error!(cause=?e, "shutdown write socket to due to write receiver channel closing");
                             break;
                         }
+                        traffic.bytes_written.fetch_add(len as u64, Ordering::Relaxed);
                     },
                 }
             },
@@ -219,7 +351,7 @@ async fn handle_socket<R, W, P>(
     if let Err(e) = wr.shutdown().await {
         error!(cause=?e, "shutdown write socket error: {e}");
     }
-    exited_flag.store(true, std::sync::atomic::Ordering::SeqCst);
+    exited_flag.store(true, Ordering::SeqCst);
 }
 
 #[derive(Clone)]
@@ -266,3 +398,72 @@ impl std::fmt::Display for AddressOrName {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bytes::{Buf, Bytes};
+
+    use super::*;
+
+    /// Frames of a single length byte followed by the frame data
+    #[derive(Debug, PartialEq)]
+    struct TestFrame(Bytes);
+
+    impl DecodeValueFromBytes for TestFrame {
+        fn decode_from_bytes(buffer: &mut MessageBuffer) -> BufferResult<Self> {
+            let wanted = 1 + usize::from(*buffer.first().ok_or(BufferDecodeError::InsufficientBytes { have: 0, wanted: 1 })?);
+            if buffer.len() < wanted {
+                return Err(BufferDecodeError::InsufficientBytes { have: buffer.len(), wanted });
+            }
+            buffer.advance(1);
+            Ok(Self(buffer.split_to(wanted - 1).freeze()))
+        }
+    }
+
+    impl LoggedFrame for TestFrame {
+        fn to_log_bytes(&self) -> Bytes {
+            [&[self.0.len() as u8], &self.0[..]].concat().into()
+        }
+
+        fn describe(_direction: Direction, frame: &[u8]) -> String {
+            format!("size={}", frame.len())
+        }
+    }
+
+    async fn receive_until(socket: &mut Socket<TestFrame>, num_packets: usize) -> Vec<TestFrame> {
+        let mut packets = vec![];
+        while packets.len() < num_packets && socket.status().is_running() {
+            packets.extend(socket.receive(None).unwrap());
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        packets
+    }
+
+    #[tokio::test]
+    async fn it_decodes_every_frame_of_a_read() {
+        let (mut client, server) = tokio::io::duplex(1024);
+        let (rd, wr) = tokio::io::split(server);
+        let mut socket = Socket::<TestFrame>::new(&Handle::current(), "test".to_string().into(), rd, wr, Some(4), None);
+
+        // Two whole frames and the start of a third in a single write
+        client.write_all(&[2, b'a', b'b', 1, b'c', 3, b'd']).await.unwrap();
+        let packets = tokio::time::timeout(Duration::from_secs(1), receive_until(&mut socket, 2)).await.unwrap();
+        assert_eq!(packets, vec![TestFrame(Bytes::from_static(b"ab")), TestFrame(Bytes::from_static(b"c"))]);
+
+        client.write_all(&[b'e', b'f']).await.unwrap();
+        let packets = tokio::time::timeout(Duration::from_secs(1), receive_until(&mut socket, 1)).await.unwrap();
+        assert_eq!(packets, vec![TestFrame(Bytes::from_static(b"def"))]);
+
+        socket.write(Bytes::from_static(&[1, b'g'])).unwrap();
+        let mut answer = [0; 2];
+        client.read_exact(&mut answer).await.unwrap();
+        assert_eq!(answer, [1, b'g']);
+        assert_eq!(socket.bytes_read(), 9);
+        assert_eq!(socket.bytes_written(), 2);
+
+        // Frames over the max frame size close the connection
+        client.write_all(&[4]).await.unwrap();
+        tokio::time::timeout(Duration::from_secs(1), receive_until(&mut socket, 1)).await.unwrap();
+        assert!(matches!(socket.status(), SocketStatus::Closed));
+    }
+}
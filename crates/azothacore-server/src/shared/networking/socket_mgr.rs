@@ -28,6 +28,10 @@ pub trait SocketMgrConfig<S>: Send + Sync + 'static {
     fn retrieve_max_connections(&self) -> Option<usize> {
         None
     }
+    /// Largest frame in bytes that a client may send before its connection is closed, None if unlimited.
+    fn retrieve_max_frame_size(&self) -> Option<usize> {
+        None
+    }
 }
 
 /// Marker component trait for [socket_mgr_plugin] to identify bevy queries for
@@ -39,9 +43,10 @@ pub trait ConnectionComponent: Component {
 }
 
 pub struct NewTcpConnection {
-    pub permit: Option<OwnedSemaphorePermit>,
-    pub name:   AddressOrName,
-    pub conn:   TcpStream,
+    pub permit:         Option<OwnedSemaphorePermit>,
+    pub name:           AddressOrName,
+    pub conn:           TcpStream,
+    pub max_frame_size: Option<usize>,
 }
 
 #[derive(SystemSet)]
@@ -196,9 +201,9 @@ where
             Update,
             receive_spawned_sockets::<S>
                 .run_if(az_startup_succeeded())
-                .in_set(SocketMgrSet::<C>::handle_received_socket()),
+                .in_set(SocketMgrSet::<S>::handle_received_socket()),
         )
-        .add_systems(PostUpdate, handle_terminate_network::<S>.in_set(SocketMgrSet::<C>::network_termination()));
+        .add_systems(PostUpdate, handle_terminate_network::<S>.in_set(SocketMgrSet::<S>::network_termination()));
 }
 
 #[derive(Resource)]
@@ -228,7 +233,8 @@ where
     };
 
     let sem = cfg.retrieve_max_connections().map(|max| Arc::new(Semaphore::new(max)));
-    rt.spawn(accept_sockets::<S>(acceptor, sem, term_rcv, sock_snd));
+    let max_frame_size = cfg.retrieve_max_frame_size();
+    rt.spawn(accept_sockets::<S>(acceptor, sem, max_frame_size, term_rcv, sock_snd));
 }
 
 fn handle_terminate_network<S: ConnectionComponent>(mut app_exit_events: EventReader<AppExit>, term_snds: Res<TermSender<S>>) {
@@ -272,6 +278,7 @@ fn receive_spawned_sockets<S: ConnectionComponent>(mut commands: Commands, rt: R
 async fn accept_sockets<S: ConnectionComponent>(
     acceptor: TcpListener,
     maybe_sem: Option<Arc<Semaphore>>,
+    max_frame_size: Option<usize>,
     mut term: UnboundedReceiver<()>,
     send_sockets: UnboundedSender<NewTcpConnection>,
 ) {
@@ -314,6 +321,7 @@ async fn accept_sockets<S: ConnectionComponent>(
             conn,
             permit: sem_perm,
             name: AddressOrName::Addr(addr),
+            max_frame_size,
         }) {
             // Dont treat this as an error for now, mainly b/c it could be that the app is properly quitting
             debug!(cause=?e, "send error, receiving socket receiving channel half may be dropped or closed, quitting network accept loop");
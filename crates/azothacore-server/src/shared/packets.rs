@@ -0,0 +1 @@
+pub mod byte_buffer;
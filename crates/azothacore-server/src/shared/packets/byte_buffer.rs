@@ -0,0 +1,309 @@
+use azothacore_common::utils::{BufferDecodeError, BufferResult};
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+use crate::game::entities::object::object_guid::{HighGuidTrait, ObjectGuid};
+
+macro_rules! byte_buffer_primitives {
+    ( $( $ty:ty => $read:ident, $write:ident, $get:ident, $put:ident; )* ) => {
+        $(
+            pub fn $read(&mut self) -> BufferResult<$ty> {
+                self.reset_bit_pos();
+                self.ensure_remaining(size_of::<$ty>())?;
+                Ok(self.storage.$get())
+            }
+
+            pub fn $write(&mut self, value: $ty) -> &mut Self {
+                self.flush_bits();
+                self.storage.$put(value);
+                self
+            }
+        )*
+    };
+}
+
+/// ByteBuffer in TC/AC
+///
+/// All values are little endian. Individual bits are packed most significant bit first into
+/// a single byte, which is only appended (or a new one read in) once a full byte is done or
+/// a regular value is written (read) after it, i.e. any pending bits are flushed.
+///
+/// Reads consume from the front of the buffer, writes append to the back.
+#[derive(Clone, Debug)]
+pub struct ByteBuffer {
+    storage:   BytesMut,
+    bitpos:    u8,
+    curbitval: u8,
+}
+
+impl Default for ByteBuffer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<Bytes> for ByteBuffer {
+    fn from(value: Bytes) -> Self {
+        Self {
+            storage:   value.into(),
+            bitpos:    8,
+            curbitval: 0,
+        }
+    }
+}
+
+impl ByteBuffer {
+    byte_buffer_primitives! {
+        u8 => read_u8, write_u8, get_u8, put_u8;
+        u16 => read_u16, write_u16, get_u16_le, put_u16_le;
+        u32 => read_u32, write_u32, get_u32_le, put_u32_le;
+        u64 => read_u64, write_u64, get_u64_le, put_u64_le;
+        i8 => read_i8, write_i8, get_i8, put_i8;
+        i16 => read_i16, write_i16, get_i16_le, put_i16_le;
+        i32 => read_i32, write_i32, get_i32_le, put_i32_le;
+        i64 => read_i64, write_i64, get_i64_le, put_i64_le;
+        f32 => read_f32, write_f32, get_f32_le, put_f32_le;
+        f64 => read_f64, write_f64, get_f64_le, put_f64_le;
+    }
+
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            storage:   BytesMut::with_capacity(capacity),
+            bitpos:    8,
+            curbitval: 0,
+        }
+    }
+
+    /// Number of bytes left in the buffer, excluding any pending bits that have yet to be flushed.
+    pub fn len(&self) -> usize {
+        self.storage.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.storage.is_empty()
+    }
+
+    /// Flushes any pending bits and returns the bytes written.
+    pub fn into_bytes(mut self) -> Bytes {
+        self.flush_bits();
+        self.storage.freeze()
+    }
+
+    fn ensure_remaining(&self, wanted: usize) -> BufferResult<()> {
+        if self.storage.remaining() < wanted {
+            return Err(BufferDecodeError::InsufficientBytes {
+                have: self.storage.remaining(),
+                wanted,
+            });
+        }
+        Ok(())
+    }
+
+    /// Writes the given bit, returning it back for convenience when writing optional fields.
+    pub fn write_bit(&mut self, bit: bool) -> bool {
+        self.bitpos -= 1;
+        if bit {
+            self.curbitval |= 1 << self.bitpos;
+        }
+        if self.bitpos == 0 {
+            self.bitpos = 8;
+            self.storage.put_u8(self.curbitval);
+            self.curbitval = 0;
+        }
+        bit
+    }
+
+    /// Writes the lowest `bits` bits of `value`, most significant bit first.
+    pub fn write_bits(&mut self, value: u32, bits: u8) -> &mut Self {
+        for i in (0..bits).rev() {
+            self.write_bit((value >> i) & 1 == 1);
+        }
+        self
+    }
+
+    /// Appends any pending bits as a full byte.
+    pub fn flush_bits(&mut self) {
+        if self.bitpos == 8 {
+            return;
+        }
+        self.bitpos = 8;
+        self.storage.put_u8(self.curbitval);
+        self.curbitval = 0;
+    }
+
+    /// Whether there are bits that have been written but not yet flushed.
+    pub fn has_unfinished_bits(&self) -> bool {
+        self.bitpos != 8
+    }
+
+    pub fn read_bit(&mut self) -> BufferResult<bool> {
+        if self.bitpos == 8 {
+            self.ensure_remaining(1)?;
+            self.curbitval = self.storage.get_u8();
+            self.bitpos = 0;
+        }
+        let bit = (self.curbitval >> (7 - self.bitpos)) & 1 == 1;
+        self.bitpos += 1;
+        Ok(bit)
+    }
+
+    pub fn read_bits(&mut self, bits: u8) -> BufferResult<u32> {
+        let mut value = 0;
+        for i in (0..bits).rev() {
+            if self.read_bit()? {
+                value |= 1 << i;
+            }
+        }
+        Ok(value)
+    }
+
+    /// Discards the rest of the byte currently being read bit by bit.
+    pub fn reset_bit_pos(&mut self) {
+        self.bitpos = 8;
+        self.curbitval = 0;
+    }
+
+    pub fn write_bytes(&mut self, value: &[u8]) -> &mut Self {
+        self.flush_bits();
+        self.storage.put_slice(value);
+        self
+    }
+
+    pub fn read_bytes(&mut self, len: usize) -> BufferResult<Bytes> {
+        self.reset_bit_pos();
+        self.ensure_remaining(len)?;
+        Ok(self.storage.split_to(len).freeze())
+    }
+
+    pub fn read_array<const N: usize>(&mut self) -> BufferResult<[u8; N]> {
+        self.reset_bit_pos();
+        self.ensure_remaining(N)?;
+        let mut value = [0; N];
+        self.storage.copy_to_slice(&mut value);
+        Ok(value)
+    }
+
+    /// WriteString in TC/AC, writes the string without its length nor a terminating null. The
+    /// length is usually written beforehand as bits.
+    pub fn write_string(&mut self, value: &str) -> &mut Self {
+        self.write_bytes(value.as_bytes())
+    }
+
+    /// ReadString in TC/AC, see [Self::write_string]
+    pub fn read_string(&mut self, len: usize) -> BufferResult<String> {
+        let b = self.read_bytes(len)?;
+        String::from_utf8(b.to_vec()).map_err(|e| BufferDecodeError::UnexpectedDecode(format!("invalid utf8 string: {e}")))
+    }
+
+    /// Writes a null-terminated string
+    pub fn write_cstring(&mut self, value: &str) -> &mut Self {
+        self.write_bytes(value.as_bytes()).write_u8(0)
+    }
+
+    /// Reads a null-terminated string
+    pub fn read_cstring(&mut self) -> BufferResult<String> {
+        self.reset_bit_pos();
+        let Some(len) = self.storage.iter().position(|b| *b == 0) else {
+            return Err(BufferDecodeError::InsufficientBytes {
+                have:   self.storage.len(),
+                wanted: self.storage.len() + 1,
+            });
+        };
+        let s = self.read_string(len)?;
+        self.storage.advance(1);
+        Ok(s)
+    }
+
+    /// Writes the GUID packed, see [ObjectGuid::pack_into]
+    pub fn write_packed_guid<H>(&mut self, guid: &ObjectGuid<H>) -> &mut Self {
+        self.flush_bits();
+        guid.pack_into(&mut self.storage);
+        self
+    }
+
+    /// Reads a packed GUID of any type, see [ObjectGuid::unpack_from]
+    pub fn read_packed_guid(&mut self) -> BufferResult<ObjectGuid> {
+        self.reset_bit_pos();
+        self.ensure_remaining(2)?;
+        let packed_len = self.storage[0].count_ones() + self.storage[1].count_ones();
+        self.ensure_remaining(2 + packed_len as usize)?;
+        Ok(ObjectGuid::unpack_from(&mut self.storage))
+    }
+
+    /// Reads a packed GUID, erroring if it is not of the given high type
+    pub fn read_packed_guid_typed<H: HighGuidTrait>(&mut self) -> BufferResult<ObjectGuid<H>> {
+        self.read_packed_guid()?
+            .try_into()
+            .map_err(|e| BufferDecodeError::UnexpectedDecode(format!("unexpected GUID type: {e}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use azothacore_common::utils::BufferDecodeError;
+    use bytes::Bytes;
+
+    use super::ByteBuffer;
+    use crate::game::entities::object::object_guid::{HighGuidPlayer, HighGuidWowAccount, ObjectGuid, ObjectGuidGlobal};
+
+    #[test]
+    fn byte_buffer_bits_are_packed_msb_first_and_flushed_before_values() {
+        let mut buf = ByteBuffer::new();
+        buf.write_bit(true);
+        buf.write_bits(0b101, 3);
+        buf.write_u16(0x1234);
+        buf.write_bits(0x1FF, 9);
+        assert!(buf.has_unfinished_bits());
+        let b = buf.into_bytes();
+        assert_eq!(&b[..], &[0b1101_0000, 0x34, 0x12, 0xFF, 0b1000_0000]);
+
+        let mut buf = ByteBuffer::from(b);
+        assert!(buf.read_bit().unwrap());
+        assert_eq!(buf.read_bits(3).unwrap(), 0b101);
+        // reading a value discards the rest of the partially read byte
+        assert_eq!(buf.read_u16().unwrap(), 0x1234);
+        assert_eq!(buf.read_bits(9).unwrap(), 0x1FF);
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn byte_buffer_strings_and_guids_round_trip() {
+        let guid = ObjectGuid::<HighGuidWowAccount>::global(1111);
+        let mut buf = ByteBuffer::new();
+        buf.write_bits(5, 6)
+            .write_string("hello")
+            .write_cstring("world")
+            .write_packed_guid(&guid)
+            .write_f32(1.5);
+
+        let mut buf = ByteBuffer::from(buf.into_bytes());
+        let len = buf.read_bits(6).unwrap();
+        assert_eq!(buf.read_string(len as usize).unwrap(), "hello");
+        assert_eq!(buf.read_cstring().unwrap(), "world");
+        assert_eq!(buf.read_packed_guid_typed::<HighGuidWowAccount>().unwrap(), guid);
+        assert_eq!(buf.read_f32().unwrap(), 1.5);
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn byte_buffer_reads_error_instead_of_panicking() {
+        let mut buf = ByteBuffer::from(Bytes::from_static(&[1, 2, 3]));
+        assert!(matches!(buf.read_u32(), Err(BufferDecodeError::InsufficientBytes { have: 3, wanted: 4 })));
+
+        // Masks claim 2 bytes of packed GUID data, but only 1 follows
+        let mut buf = ByteBuffer::from(Bytes::from_static(&[0b11, 0, 1]));
+        assert!(matches!(buf.read_packed_guid(), Err(BufferDecodeError::InsufficientBytes { .. })));
+
+        let guid = ObjectGuid::<HighGuidWowAccount>::global(1);
+        let mut buf = ByteBuffer::new();
+        buf.write_packed_guid(&guid);
+        let mut buf = ByteBuffer::from(buf.into_bytes());
+        assert!(matches!(
+            buf.read_packed_guid_typed::<HighGuidPlayer>(),
+            Err(BufferDecodeError::UnexpectedDecode(_))
+        ));
+    }
+}
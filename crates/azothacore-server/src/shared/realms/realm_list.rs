@@ -16,6 +16,7 @@ use azothacore_common::{
 use azothacore_database::{
     args,
     database_env::{LoginDatabase, LoginPreparedStmts},
+    query_processor::DbQueryQueue,
     DbDriver,
 };
 use bevy::prelude::{App, Commands, IntoSystemConfigs, Real, Res, ResMut, Resource, Startup, SystemSet, Time, Timer, TimerMode, Update};
@@ -351,6 +352,7 @@ pub fn realm_list_plugin<C: RealmListConfig>(app: &mut App) {
         .add_systems(Update, update_realmlists.run_if(az_startup_succeeded()).in_set(RealmListUpdateSet));
 }
 
+/// The first load of the realms happens right away, so that the realm list is complete once startup has finished
 fn init_realm_list<C: RealmListConfig>(mut commands: Commands, cfg: Res<ConfigMgr<C>>, rt: Res<TokioRuntime>, login_db: Option<Res<LoginDatabase>>) {
     let mut realm_list = RealmList::new(cfg.realms_state_update_delay());
     if let Some(db) = login_db {
@@ -359,33 +361,41 @@ fn init_realm_list<C: RealmListConfig>(mut commands: Commands, cfg: Res<ConfigMg
     commands.insert_resource(realm_list);
 }
 
-fn update_realmlists(time: Res<Time<Real>>, rt: Res<TokioRuntime>, login_db: Res<LoginDatabase>, mut realm_list: ResMut<RealmList>) {
+fn update_realmlists(time: Res<Time<Real>>, login_db_queue: Res<DbQueryQueue<LoginDatabase>>, mut realm_list: ResMut<RealmList>) {
     realm_list.update_timer.tick(time.delta());
-    if !realm_list.has_run_once {
-        realm_list.has_run_once = true;
-        rt.block_on(realm_list.update(login_db.clone()));
-    }
-    if !realm_list.update_timer.finished() {
+    // Ticks are skipped until the callback of the last update has run, so that updates do not pile up on a slow DB
+    if realm_list.update_in_flight || !realm_list.update_timer.finished() {
         return;
     }
-    rt.block_on(realm_list.update(login_db.clone()));
+    realm_list.update_in_flight = true;
+    let existing_realms = realm_list.existing_realm_names();
+    login_db_queue.query(
+        |login_db| RealmList::fetch_realms(existing_realms, login_db),
+        |(sub_regions, realms), world| {
+            let mut realm_list = world.resource_mut::<RealmList>();
+            realm_list.sub_regions = sub_regions;
+            realm_list.realms = realms;
+            realm_list.update_in_flight = false;
+        },
+    );
 }
 
 #[derive(Resource)]
 pub struct RealmList {
-    pub realms:      BTreeMap<BnetRealmHandle, Realm>,
-    pub sub_regions: BTreeSet<String>,
-    has_run_once:    bool,
-    update_timer:    Timer,
+    pub realms:       BTreeMap<BnetRealmHandle, Realm>,
+    pub sub_regions:  BTreeSet<String>,
+    /// Whether an update has been queued whose callback has yet to run
+    update_in_flight: bool,
+    update_timer:     Timer,
 }
 
 impl RealmList {
     pub fn new(update_delay: Duration) -> Self {
         Self {
-            realms:       BTreeMap::new(),
-            sub_regions:  BTreeSet::new(),
-            has_run_once: false,
-            update_timer: Timer::new(update_delay, TimerMode::Repeating),
+            realms:           BTreeMap::new(),
+            sub_regions:      BTreeSet::new(),
+            update_in_flight: false,
+            update_timer:     Timer::new(update_delay, TimerMode::Repeating),
         }
     }
 
@@ -475,12 +485,23 @@ impl RealmList {
     }
 
     pub async fn update(&mut self, login_db: LoginDatabase) {
+        let (sub_regions, realms) = Self::fetch_realms(self.existing_realm_names(), login_db).await;
+        self.sub_regions = sub_regions;
+        self.realms = realms;
+    }
+
+    fn existing_realm_names(&self) -> BTreeMap<BnetRealmHandle, String> {
+        self.realms.iter().map(|(id, r)| (*id, r.name.clone())).collect()
+    }
+
+    /// Reads the realms from the login DB, logging the realms that were added, updated or removed
+    /// compared to `existing_realms`. Returns the new sub regions and realms.
+    async fn fetch_realms(
+        mut existing_realms: BTreeMap<BnetRealmHandle, String>,
+        login_db: LoginDatabase,
+    ) -> (BTreeSet<String>, BTreeMap<BnetRealmHandle, Realm>) {
         info!(target:"realmlist", "Updating Realm List...");
 
-        let mut existing_realms = BTreeMap::new();
-        for p in self.realms.iter() {
-            existing_realms.insert(*p.0, p.1.name.clone());
-        }
         let mut new_sub_regions = BTreeSet::new();
         let mut new_realms = BTreeMap::new();
 
@@ -513,7 +534,6 @@ impl RealmList {
         for r in existing_realms.values() {
             info!(target:"realmlist", "Removed realm \"{r}\".");
         }
-        self.sub_regions = new_sub_regions;
-        self.realms = new_realms;
+        (new_sub_regions, new_realms)
     }
 }
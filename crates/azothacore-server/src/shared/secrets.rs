@@ -1,25 +1,197 @@
-// use super::shared_defines::{ServerProcessType, ThisServerProcess};
+use azothacore_common::{
+    az_error,
+    bevy_app::{AzStartupFailedEvent, TokioRuntime},
+    configuration::ConfigMgr,
+    crypto::aes,
+    hex_str,
+    AzResult,
+};
+use azothacore_database::{
+    args,
+    database_env::{LoginDatabase, LoginPreparedStmts},
+    DbAcquire,
+};
+use bevy::prelude::{App, Commands, EventWriter, IntoSystemConfigs, Res, Resource, Startup, SystemSet};
+use sha2::{Digest, Sha256};
+use tracing::{error, info, warn};
 
-// struct SecretInfo {
-//     owner: ServerProcessType,
-// }
+/// Secrets in TC, the ID of each secret in the `secret_digest` table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Secrets {
+    TotpMasterKey = 0,
+}
 
-// impl SecretInfo {
-//     fn defer_load(&self) -> bool {
-//         self.owner != ThisServerProcess::get()
-//     }
-// }
+pub trait SecretMgrConfig: Send + Sync + 'static {
+    /// TOTPMasterSecret in TC, the hex encoded 128 bit key that TOTP secrets are encrypted with. Empty if unset.
+    fn totp_master_secret(&self) -> &str;
+    /// TOTPOldMasterSecret in TC, the previous TOTPMasterSecret, which is only needed on the first startup after
+    /// TOTPMasterSecret has been changed.
+    fn totp_old_master_secret(&self) -> &str;
+}
 
-// const TOTP_SECRET_INFO: SecretInfo = SecretInfo {
-//     owner: ServerProcessType::Authserver,
-// };
+/// SecretMgr in TC / AC
+///
+/// Only the TOTP master key is handled for now, which is what the TOTP secrets of Battle.net accounts are encrypted
+/// with in the auth DB. The key itself only ever lives in config, the auth DB holds a digest of it so that a changed
+/// key is detected on startup.
+#[derive(Resource, Clone, Default)]
+pub struct SecretMgr {
+    totp_master_key: Option<aes::Key>,
+}
 
-// pub struct SecretMgr;
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SecretMgrInitSet;
 
-// SecretFlags::AUTHSERVER_DEFER_LOAD = 2^0 = 1;
-// SecretFlags::WORLDSERVER_DEFER_LOAD = 2^16 = 65536;
-// SecretFlags::SECRET_FLAG_DEFER_LOAD = 2^0 = 1;
+pub fn secret_mgr_plugin<C: SecretMgrConfig>(app: &mut App) {
+    app.add_systems(Startup, init_secret_mgr::<C>.in_set(SecretMgrInitSet));
+}
 
-// _flags = WORLDSERVER_DEFER_LOAD
-// THIS_SERVER_PROCESS =     Authserver = 0 or Worldserver = 1,
-// _flags >> (16*THIS_SERVER_PROCESS)
+fn init_secret_mgr<C: SecretMgrConfig>(
+    mut commands: Commands,
+    cfg: Res<ConfigMgr<C>>,
+    rt: Res<TokioRuntime>,
+    login_db: Option<Res<LoginDatabase>>,
+    mut ev_startup_failed: EventWriter<AzStartupFailedEvent>,
+) {
+    let Some(login_db) = login_db else {
+        return;
+    };
+    match rt.block_on(SecretMgr::initialize(&**login_db, cfg.totp_master_secret(), cfg.totp_old_master_secret())) {
+        Ok(secret_mgr) => commands.insert_resource(secret_mgr),
+        Err(e) => {
+            error!(target:"server::loading", cause=?e, "unable to initialise secrets");
+            ev_startup_failed.send_default();
+        },
+    }
+}
+
+impl SecretMgr {
+    pub fn new(totp_master_key: Option<aes::Key>) -> Self {
+        Self { totp_master_key }
+    }
+
+    /// SecretMgr::Initialize in TC, loads the TOTP master key from config.
+    ///
+    /// If the key differs from the one that the auth DB was last used with, TOTPOldMasterSecret must be set to that
+    /// previous key so that the TOTP secrets of all accounts are re-encrypted with the new key.
+    pub async fn initialize<'a, A: DbAcquire<'a>>(login_db: A, master_secret: &str, old_master_secret: &str) -> AzResult<Self> {
+        let new_key = parse_key(master_secret).map_err(|e| e.context("invalid TOTPMasterSecret"))?;
+        let old_key = parse_key(old_master_secret).map_err(|e| e.context("invalid TOTPOldMasterSecret"))?;
+
+        let mut login_db = login_db.acquire().await?;
+        let digest = LoginDatabase::sel_secret_digest::<_, (String,)>(&mut *login_db, args!(Secrets::TotpMasterKey as u32)?)
+            .await?
+            .map(|d| d.0);
+
+        let mut secret_mgr = Self::default();
+        match (digest, new_key) {
+            (None, None) => {},
+            (None, Some(new_key)) => secret_mgr.rotate_totp_master_key(&mut *login_db, new_key).await?,
+            (Some(_), None) => {
+                return Err(az_error!(
+                    "TOTPMasterSecret is unset while the TOTP secrets in the auth DB are encrypted with one, it cannot be removed"
+                ));
+            },
+            (Some(digest), Some(new_key)) if digest == key_digest(&new_key) => {
+                if old_key.is_some() {
+                    warn!(target:"server::loading", "TOTPOldMasterSecret is set but not needed, it can be removed from config");
+                }
+                secret_mgr.totp_master_key = Some(new_key);
+            },
+            (Some(digest), Some(new_key)) => {
+                let Some(old_key) = old_key.filter(|k| digest == key_digest(k)) else {
+                    return Err(az_error!(
+                        "TOTPMasterSecret has changed, set TOTPOldMasterSecret to the previous one so that TOTP secrets can be re-encrypted"
+                    ));
+                };
+                secret_mgr.totp_master_key = Some(old_key);
+                secret_mgr.rotate_totp_master_key(&mut *login_db, new_key).await?;
+            },
+        }
+        Ok(secret_mgr)
+    }
+
+    /// SecretMgr::AttemptTransition in TC, re-encrypts the TOTP secrets of all accounts with the new key and stores the
+    /// digest of the new key, all in one transaction.
+    pub async fn rotate_totp_master_key<'a, A: DbAcquire<'a>>(&mut self, login_db: A, new_key: aes::Key) -> AzResult<()> {
+        let mut txn = login_db.begin().await?;
+        let secrets = LoginDatabase::sel_bnet_totp_secrets::<_, (u32, Vec<u8>)>(&mut *txn, args!()?).await?;
+        let count = secrets.len();
+        for (account_id, encrypted) in secrets {
+            let Some(secret) = self.decrypt_totp_secret(&encrypted) else {
+                return Err(az_error!(
+                    "unable to decrypt the TOTP secret of Battle.net account {account_id} with the current TOTP master key"
+                ));
+            };
+            let encrypted = aes::encrypt_with_random_iv(&secret, &new_key);
+            LoginDatabase::upd_bnet_totp_secret(&mut *txn, args!(encrypted, account_id)?).await?;
+        }
+        let id = Secrets::TotpMasterKey as u32;
+        LoginDatabase::del_secret_digest(&mut *txn, args!(id)?).await?;
+        LoginDatabase::ins_secret_digest(&mut *txn, args!(id, key_digest(&new_key))?).await?;
+        txn.commit().await?;
+
+        info!(target:"server::loading", "TOTP master key has been changed, re-encrypted {count} TOTP secrets");
+        self.totp_master_key = Some(new_key);
+        Ok(())
+    }
+
+    pub fn has_totp_master_key(&self) -> bool {
+        self.totp_master_key.is_some()
+    }
+
+    /// Encrypts a TOTP secret for storage, None if there is no TOTP master key
+    pub fn encrypt_totp_secret(&self, secret: &[u8]) -> Option<Vec<u8>> {
+        Some(aes::encrypt_with_random_iv(secret, self.totp_master_key.as_ref()?))
+    }
+
+    /// Decrypts a stored TOTP secret, None if there is no TOTP master key or if the secret was not encrypted with it
+    pub fn decrypt_totp_secret(&self, encrypted: &[u8]) -> Option<Vec<u8>> {
+        aes::decrypt(encrypted, self.totp_master_key.as_ref()?)
+    }
+}
+
+/// Parses a hex encoded key from config, None if it is empty
+fn parse_key(hex_key: &str) -> AzResult<Option<aes::Key>> {
+    if hex_key.is_empty() {
+        return Ok(None);
+    }
+    let key = hex::decode(hex_key)?;
+    let key = aes::Key::try_from(key.as_slice()).map_err(|_| az_error!("expected a {} bit key, got {} bits", aes::KEY_SIZE_BYTES * 8, key.len() * 8))?;
+    Ok(Some(key))
+}
+
+/// The digest of a key as stored in the `secret_digest` table.
+///
+/// TC uses Argon2 here, a plain SHA256 is enough as the keys are random and too large to brute force.
+fn key_digest(key: &aes::Key) -> String {
+    hex_str!(&Sha256::digest(key)[..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_config_keys() {
+        assert_eq!(parse_key("").unwrap(), None);
+        assert_eq!(
+            parse_key("000102030405060708090A0B0C0D0E0F").unwrap(),
+            Some([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15])
+        );
+        assert!(parse_key("0001").is_err());
+        assert!(parse_key("not hex").is_err());
+    }
+
+    #[test]
+    fn totp_secrets_need_the_master_key() {
+        let secret = b"12345678901234567890";
+        assert_eq!(SecretMgr::default().encrypt_totp_secret(secret), None);
+
+        let secret_mgr = SecretMgr::new(Some([1; aes::KEY_SIZE_BYTES]));
+        let encrypted = secret_mgr.encrypt_totp_secret(secret).unwrap();
+        assert_eq!(secret_mgr.decrypt_totp_secret(&encrypted).as_deref(), Some(&secret[..]));
+        assert_eq!(SecretMgr::new(Some([2; aes::KEY_SIZE_BYTES])).decrypt_totp_secret(&encrypted), None);
+        assert_eq!(SecretMgr::default().decrypt_totp_secret(&encrypted), None);
+    }
+}
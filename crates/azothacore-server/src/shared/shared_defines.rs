@@ -191,6 +191,114 @@ pub enum FactionID {
     Other = 0, // if ReputationListId > 0 && Flags != FACTION_FLAG_TEAM_HEADER
 }
 
+/// ResponseCodes in TC / AC
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromPrimitive, ToPrimitive)]
+#[repr(u8)]
+pub enum ResponseCodes {
+    ResponseSuccess = 0,
+    ResponseFailure = 1,
+    ResponseCancelled = 2,
+    ResponseDisconnected = 3,
+    ResponseFailedToConnect = 4,
+    ResponseConnected = 5,
+    ResponseVersionMismatch = 6,
+
+    CstatusConnecting = 7,
+    CstatusNegotiatingSecurity = 8,
+    CstatusNegotiationComplete = 9,
+    CstatusNegotiationFailed = 10,
+    CstatusAuthenticating = 11,
+
+    RealmListInProgress = 12,
+    RealmListSuccess = 13,
+    RealmListFailed = 14,
+    RealmListInvalid = 15,
+    RealmListRealmNotFound = 16,
+
+    AccountCreateInProgress = 17,
+    AccountCreateSuccess = 18,
+    AccountCreateFailed = 19,
+
+    CharListRetrieving = 20,
+    CharListRetrieved = 21,
+    CharListFailed = 22,
+
+    CharCreateInProgress = 23,
+    CharCreateSuccess = 24,
+    CharCreateError = 25,
+    CharCreateFailed = 26,
+    CharCreateNameInUse = 27,
+    CharCreateDisabled = 28,
+    CharCreatePvpTeamsViolation = 29,
+    CharCreateServerLimit = 30,
+    CharCreateAccountLimit = 31,
+    CharCreateServerQueue = 32,
+    CharCreateOnlyExisting = 33,
+    CharCreateExpansion = 34,
+    CharCreateExpansionClass = 35,
+    CharCreateCharacterInGuild = 36,
+    CharCreateRestrictedRaceclass = 37,
+    CharCreateCharacterChooseRace = 38,
+    CharCreateCharacterArenaLeader = 39,
+    CharCreateCharacterDeleteMail = 40,
+    CharCreateCharacterSwapFaction = 41,
+    CharCreateCharacterRaceOnly = 42,
+    CharCreateCharacterGoldLimit = 43,
+    CharCreateForceLogin = 44,
+    CharCreateTrial = 45,
+    CharCreateTimeout = 46,
+    CharCreateThrottle = 47,
+    CharCreateAlliedRaceAchievement = 48,
+    CharCreateLevelRequirementDemonHunter = 49,
+
+    CharDeleteInProgress = 50,
+    CharDeleteSuccess = 51,
+    CharDeleteFailed = 52,
+    CharDeleteFailedLockedForTransfer = 53,
+    CharDeleteFailedGuildLeader = 54,
+    CharDeleteFailedArenaCaptain = 55,
+    CharDeleteFailedHasHeirloomOrMail = 56,
+    CharDeleteFailedUpgradeInProgress = 57,
+    CharDeleteFailedHasWowToken = 58,
+    CharDeleteFailedVasTransactionInProgress = 59,
+
+    CharLoginInProgress = 60,
+    CharLoginSuccess = 61,
+    CharLoginNoWorld = 62,
+    CharLoginDuplicateCharacter = 63,
+    CharLoginNoInstances = 64,
+    CharLoginFailed = 65,
+    CharLoginDisabled = 66,
+    CharLoginNoCharacter = 67,
+    CharLoginLockedForTransfer = 68,
+    CharLoginLockedByBilling = 69,
+    CharLoginLockedByMobileAh = 70,
+    CharLoginTemporaryGmLock = 71,
+    CharLoginLockedByCharacterUpgrade = 72,
+    CharLoginLockedByRevokedCharacterUpgrade = 73,
+    CharLoginLockedByRevokedVasTransaction = 74,
+    CharLoginLockedByRestriction = 75,
+
+    CharNameSuccess = 76,
+    CharNameFailure = 77,
+    CharNameNoName = 78,
+    CharNameTooShort = 79,
+    CharNameTooLong = 80,
+    CharNameInvalidCharacter = 81,
+    CharNameMixedLanguages = 82,
+    CharNameProfane = 83,
+    CharNameReserved = 84,
+    CharNameInvalidApostrophe = 85,
+    CharNameMultipleApostrophes = 86,
+    CharNameThreeConsecutive = 87,
+    CharNameInvalidSpace = 88,
+    CharNameConsecutiveSpaces = 89,
+    CharNameRussianConsecutiveSilentCharacters = 90,
+    CharNameRussianSilentCharacterAtBeginningOrEnd = 91,
+    CharNameDeclensionDoesntMatchBaseName = 92,
+    CharNameSpacesDisallowed = 93,
+}
+
 #[cfg(test)]
 mod tests {
     use bevy::prelude::{App, Commands, Startup};
@@ -1,12 +1,23 @@
 use azothacore_common::configuration::{DatabaseInfo, DatabaseType, DbUpdates};
-use azothacore_database::{database_loader::DatabaseLoader, DbDriver};
+use azothacore_database::{
+    database_loader::DatabaseLoader,
+    dialect::{DbDialect, SQLITE_IN_MEMORY_ADDRESS},
+    DbDriver,
+    DB_DIALECT,
+};
 use flagset::FlagSet;
 use rand::{distr::Alphanumeric, rngs::OsRng, Rng, TryRngCore};
 use tokio::sync::Semaphore;
 
 fn default_db_info() -> DatabaseInfo {
+    // SQLite needs no external service, so the test databases are kept in memory
+    let address = if DB_DIALECT == DbDialect::Sqlite {
+        SQLITE_IN_MEMORY_ADDRESS
+    } else {
+        "localhost:8893"
+    };
     DatabaseInfo {
-        Address:      "localhost:8893".to_string(),
+        Address:      address.to_string(),
         User:         "root".to_string(),
         Password:     "password".to_string(),
         DatabaseName: "".to_string(),
@@ -736,6 +736,8 @@ pub fn convert_adt(
         break Some(MapLiquidData {
             offset_x,
             offset_y,
+            width,
+            height,
             map_liquid_entry_flags,
             liquid_height_details,
         });
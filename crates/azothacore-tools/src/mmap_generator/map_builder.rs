@@ -323,7 +323,7 @@ impl MapBuilder<'_> {
         info!("Creating nav_mesh...");
         let (nav_mesh, _) = DetourNavMesh::init(&nav_mesh_params)?;
 
-        let file_name = self.args.output_mmap_path().join(format!("{map_id:04}.mmap"));
+        let file_name = MmapTileFile::mmap_filepath(self.args.output_mmap_path(), map_id);
         let mut file = buffered_file_create(file_name)?;
         // now that we know nav_mesh params are valid, we can write them to file
         // TODO: Do the dedup logic here, if a navmesh file exists, we load the navmesh from there.
@@ -44,11 +44,14 @@ pub fn default_dbimport_log_appenders() -> Vec<LogAppender> {
             flags:     AddLogLevel | AddLogFilter | TruncateFile | BackupBeforeOverwrite,
         },
         LogAppender::File {
-            name:      String::from("DBImport"),
-            min_level: Warning,
-            max_level: Error,
-            flags:     AddLogLevel | AddLogFilter | TruncateFile | BackupBeforeOverwrite | AddLogTimestamps,
-            file:      String::from("DBImport.log"),
+            name:          String::from("DBImport"),
+            min_level:     Warning,
+            max_level:     Error,
+            flags:         AddLogLevel | AddLogFilter | TruncateFile | BackupBeforeOverwrite | AddLogTimestamps,
+            file:          String::from("DBImport.log"),
+            max_file_size: 0,
+            max_files:     0,
+            compress:      false,
         },
     ]
 }
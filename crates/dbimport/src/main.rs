@@ -1,41 +1,357 @@
-use std::path::Path;
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
 
 use azothacore_common::{
     banner,
     bevy_app::{bevy_app, TokioRuntime},
-    configuration::{config_mgr_plugin, ConfigMgr, ConfigMgrSet, DatabaseType},
+    configuration::{config_mgr_plugin, ConfigMgr, ConfigMgrSet, DatabaseType, LogAppender},
     log::{logging_plugin, LoggingSetupSet},
     AZOTHA_DB_IMPORT_CONFIG,
     CONF_DIR,
 };
-use azothacore_database::{database_loader::DatabaseLoader, database_loader_utils::DatabaseLoaderError};
+use azothacore_database::{
+    database_loader::{DatabaseLoader, PlannedUpdate, UpdatePlan, UpdateStatus, UpdateStatusKind},
+    database_loader_utils::DatabaseLoaderError,
+    dialect::DbDialect,
+    pending_updates::plan_pending_updates_release,
+};
 use azothacore_modules::MODULES_LIST;
 use azothacore_server::shared::{tokio_signal_handling_bevy_plugin, SignalReceiver};
-use bevy::prelude::{IntoSystemConfigs, IntoSystemSetConfigs, PreStartup, Res, ResMut, Startup, SystemSet};
+use bevy::prelude::{resource_equals, Commands, IntoSystemConfigs, IntoSystemSetConfigs, PreStartup, Res, ResMut, Resource, Startup, SystemSet};
+use chrono::{NaiveDate, Utc};
 use clap::Parser;
 use dbimport::DbImportConfig;
-use tracing::{error, info, info_span};
+use serde::Serialize;
+use tracing::{error, info, info_span, warn};
 fn main() {
     let vm = ConsoleArgs::parse();
     let rt = tokio::runtime::Builder::new_multi_thread().enable_all().build().unwrap();
+    let command = vm.command.unwrap_or(DbImportCommand::Update);
 
     let mut app = bevy_app();
     app.insert_resource(TokioRuntime(rt))
+        .insert_resource(command.clone())
         .add_plugins((
             tokio_signal_handling_bevy_plugin,
             config_mgr_plugin::<DbImportConfig, _>(vm.config, false),
             logging_plugin::<DbImportConfig>,
         ))
-        .add_systems(Startup, (show_banner.in_set(DbImportSet::ShowBanner), start_db.in_set(DbImportSet::StartDB)))
+        .add_systems(
+            PreStartup,
+            silence_console_for_json_stdout
+                .after(ConfigMgrSet::<DbImportConfig>::load_initial())
+                .before(LoggingSetupSet),
+        )
+        .add_systems(
+            Startup,
+            (
+                show_banner.in_set(DbImportSet::ShowBanner),
+                start_db.run_if(resource_equals(DbImportCommand::Update)).in_set(DbImportSet::StartDB),
+                release_pending.in_set(DbImportSet::ReleasePending),
+                inspect_databases.in_set(DbImportSet::InspectDB),
+            ),
+        )
         // Init logging right after config management
         .configure_sets(PreStartup, ConfigMgrSet::<DbImportConfig>::load_initial().before(LoggingSetupSet))
         .update();
+    if app.world().contains_resource::<DbImportFailed>() {
+        std::process::exit(1);
+    }
 }
 
 #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
 pub enum DbImportSet {
     ShowBanner,
     StartDB,
+    ReleasePending,
+    InspectDB,
+}
+
+/// Inserted when the command failed, to exit with a non-zero status
+#[derive(Resource)]
+struct DbImportFailed;
+
+#[derive(clap::ValueEnum, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum DbImportDatabase {
+    Login,
+    Character,
+    World,
+    Hotfix,
+}
+
+impl From<DbImportDatabase> for DatabaseType {
+    fn from(value: DbImportDatabase) -> Self {
+        match value {
+            DbImportDatabase::Login => DatabaseType::Login,
+            DbImportDatabase::Character => DatabaseType::Character,
+            DbImportDatabase::World => DatabaseType::World,
+            DbImportDatabase::Hotfix => DatabaseType::Hotfix,
+        }
+    }
+}
+
+impl From<DatabaseType> for DbImportDatabase {
+    fn from(value: DatabaseType) -> Self {
+        match value {
+            DatabaseType::Login => DbImportDatabase::Login,
+            DatabaseType::Character => DbImportDatabase::Character,
+            DatabaseType::World => DbImportDatabase::World,
+            DatabaseType::Hotfix => DbImportDatabase::Hotfix,
+        }
+    }
+}
+
+/// The selected databases as their types, all of them if none were selected
+fn selected_databases(databases: &[DbImportDatabase]) -> Vec<DatabaseType> {
+    if databases.is_empty() {
+        vec![DatabaseType::Login, DatabaseType::Character, DatabaseType::World, DatabaseType::Hotfix]
+    } else {
+        databases.iter().map(|d| (*d).into()).collect()
+    }
+}
+
+#[derive(clap::Args, Debug, Clone, PartialEq)]
+struct InspectArgs {
+    /// The databases to inspect. Defaults to all of them
+    #[arg(long, value_enum, value_delimiter = ',')]
+    databases: Vec<DbImportDatabase>,
+    /// Also write the result as JSON to this file, `-` writes it to stdout instead of the console logs
+    #[arg(long)]
+    json:      Option<PathBuf>,
+}
+
+#[derive(clap::Subcommand, Resource, Debug, Clone, PartialEq)]
+enum DbImportCommand {
+    /// Create the databases if needed and apply their updates. This is the default.
+    Update,
+    /// Release the pending updates of the databases into dated, ordered release updates.
+    ///
+    /// Each dialect's `pending_db-*` update directory is renamed in order of file names to follow the latest released
+    /// update. Fails without moving anything if update names are duplicated or out of order.
+    Squash {
+        /// The date the updates are released on, as YYYY-MM-DD. Defaults to today (UTC)
+        #[arg(long)]
+        date:           Option<NaiveDate>,
+        /// Move released updates dated before this date, as YYYY-MM-DD, to the archive directory
+        #[arg(long)]
+        archive_before: Option<NaiveDate>,
+        /// The databases to release pending updates for. Defaults to all of them
+        #[arg(long, value_enum, value_delimiter = ',')]
+        databases:      Vec<DbImportDatabase>,
+    },
+    /// List the updates on disk and the updates applied to the databases, with their hashes and whether they match
+    Status(InspectArgs),
+    /// Print what updating the databases would apply, rename or clean up, without changing them
+    Plan(InspectArgs),
+    /// Record the hash of applied updates that have none if `Updates.AllowRehash` is set, without applying them again
+    Rehash(InspectArgs),
+    /// Check that every applied update still exists on disk with the same hash. Fails if any doesn't
+    Verify(InspectArgs),
+}
+
+/// The JSON written by the status, plan, rehash and verify commands
+#[derive(Serialize, Default)]
+struct InspectReport {
+    /// If the command succeeded for every database, and for verify if every database passed
+    ok:        bool,
+    databases: Vec<DatabaseReport>,
+}
+
+#[derive(Serialize)]
+struct DatabaseReport {
+    database: DbImportDatabase,
+    #[serde(flatten)]
+    result:   DatabaseReportResult,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum DatabaseReportResult {
+    Status(Vec<UpdateStatus>),
+    Plan(UpdatePlan),
+    /// The updates that were re-hashed
+    Rehash(Vec<PlannedUpdate>),
+    /// The applied updates that failed verification
+    Verify(Vec<UpdateStatus>),
+    Error(String),
+}
+
+/// Drops the console appenders when the JSON report is written to stdout, so that the logs don't end up in it.
+/// The other appenders, i.e. DBImport.log, still get them.
+fn silence_console_for_json_stdout(command: Res<DbImportCommand>, cfg: Option<ResMut<ConfigMgr<DbImportConfig>>>) {
+    let (DbImportCommand::Status(args) | DbImportCommand::Plan(args) | DbImportCommand::Rehash(args) | DbImportCommand::Verify(args)) = &*command else {
+        return;
+    };
+    let Some(mut cfg) = cfg else {
+        return;
+    };
+    if args.json.as_deref() != Some(Path::new("-")) {
+        return;
+    }
+    cfg.Appender.retain(|a| !matches!(a, LogAppender::Console { .. }));
+}
+
+/// Runs the status, plan, rehash or verify commands against the selected databases
+fn inspect_databases(
+    mut commands: Commands,
+    command: Res<DbImportCommand>,
+    cfg: Res<ConfigMgr<DbImportConfig>>,
+    rt: Res<TokioRuntime>,
+    mut signal: ResMut<SignalReceiver>,
+) {
+    let (DbImportCommand::Status(args) | DbImportCommand::Plan(args) | DbImportCommand::Rehash(args) | DbImportCommand::Verify(args)) = &*command else {
+        return;
+    };
+    let modules: Vec<_> = MODULES_LIST.iter().map(|s| s.to_string()).collect();
+    let mut report = InspectReport {
+        ok: true,
+        ..Default::default()
+    };
+    for db_type in selected_databases(&args.databases) {
+        let db_info = match db_type {
+            DatabaseType::Login => &cfg.LoginDatabaseInfo,
+            DatabaseType::Character => &cfg.CharacterDatabaseInfo,
+            DatabaseType::World => &cfg.WorldDatabaseInfo,
+            DatabaseType::Hotfix => &cfg.HotfixDatabaseInfo,
+        };
+        let span = info_span!(target:"dbimport", "inspect_db", db=?db_type, name=%db_info.DatabaseName);
+        let _span_guard = span.enter();
+        let loader = DatabaseLoader::new(db_type, db_info.clone(), cfg.Updates.clone(), modules.clone());
+        let res = rt.block_on(async {
+            tokio::select! {
+                r = inspect_database(&loader, &command) => r,
+                _ = signal.0.recv() => {
+                    Err(DatabaseLoaderError::Generic { msg: "signal termination detected!".to_string() })
+                }
+            }
+        });
+        let result = match res {
+            Err(e) => {
+                error!(target:"dbimport", cause=?e, "error inspecting DB");
+                report.ok = false;
+                DatabaseReportResult::Error(e.to_string())
+            },
+            Ok(r) => {
+                log_database_report(&r);
+                if matches!(&r, DatabaseReportResult::Verify(failures) if !failures.is_empty()) {
+                    report.ok = false;
+                }
+                r
+            },
+        };
+        report.databases.push(DatabaseReport {
+            database: db_type.into(),
+            result,
+        });
+    }
+    if let Some(path) = &args.json {
+        if let Err(e) = write_json_report(path, &report) {
+            error!(target:"dbimport", cause=?e, path=%path.display(), "error writing JSON report");
+            report.ok = false;
+        }
+    }
+    if !report.ok {
+        commands.insert_resource(DbImportFailed);
+    }
+}
+
+async fn inspect_database(loader: &DatabaseLoader, command: &DbImportCommand) -> Result<DatabaseReportResult, DatabaseLoaderError> {
+    let pool = loader.connect().await?;
+    let res = match command {
+        DbImportCommand::Status(_) => loader.status(pool.clone()).await.map(DatabaseReportResult::Status),
+        DbImportCommand::Plan(_) => loader.plan_updates(pool.clone()).await.map(DatabaseReportResult::Plan),
+        DbImportCommand::Rehash(_) => loader.rehash(pool.clone()).await.map(DatabaseReportResult::Rehash),
+        _ => loader.verify(pool.clone()).await.map(DatabaseReportResult::Verify),
+    };
+    pool.close().await;
+    res
+}
+
+fn log_database_report(report: &DatabaseReportResult) {
+    match report {
+        DatabaseReportResult::Status(updates) => {
+            for u in updates {
+                info!(target:"dbimport", name=%u.name, state=?u.state, applied_state=?u.applied_state, hash=?u.hash, applied_hash=?u.applied_hash, "{:?}", u.status);
+            }
+            let applied = updates.iter().filter(|u| u.status == UpdateStatusKind::Applied).count();
+            info!(target:"dbimport", "{applied} of {} updates are applied and up to date", updates.len());
+        },
+        DatabaseReportResult::Plan(plan) => {
+            for action in plan.actions.iter() {
+                info!(target:"dbimport", "would {action:?}");
+            }
+            info!(target:"dbimport", "{} steps to update, {} recent and {} archived updates are applied", plan.actions.len(), plan.recent, plan.archived);
+        },
+        DatabaseReportResult::Rehash(rehashed) => {
+            info!(target:"dbimport", "re-hashed {} updates", rehashed.len());
+        },
+        DatabaseReportResult::Verify(failures) => {
+            for u in failures {
+                warn!(target:"dbimport", name=%u.name, path=?u.path, hash=?u.hash, applied_hash=?u.applied_hash, "{:?}", u.status);
+            }
+            if failures.is_empty() {
+                info!(target:"dbimport", "every applied update exists and matches its hash");
+            } else {
+                error!(target:"dbimport", "{} applied updates are missing or changed", failures.len());
+            }
+        },
+        DatabaseReportResult::Error(_) => {},
+    }
+}
+
+fn write_json_report(path: &Path, report: &InspectReport) -> io::Result<()> {
+    let json = serde_json::to_string_pretty(report)?;
+    if path == Path::new("-") {
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(json.as_bytes())?;
+        writeln!(stdout)
+    } else {
+        fs::write(path, json)
+    }
+}
+
+/// Releases the pending updates of the selected databases, for every dialect. Nothing is moved unless the pending
+/// updates of all of them can be released.
+fn release_pending(mut commands: Commands, command: Res<DbImportCommand>) {
+    let DbImportCommand::Squash {
+        date,
+        archive_before,
+        databases,
+    } = &*command
+    else {
+        return;
+    };
+    let date = date.unwrap_or_else(|| Utc::now().date_naive());
+    let mut releases = vec![];
+    for db_type in selected_databases(databases) {
+        for dialect in DbDialect::ALL {
+            let span = info_span!(target:"dbimport", "release_pending", db=?db_type, dialect=dialect.dir_name());
+            let _span_guard = span.enter();
+            match plan_pending_updates_release(Path::new("."), dialect.dir_name(), db_type, date, *archive_before) {
+                Err(e) => {
+                    error!(target:"dbimport", cause=?e, "error releasing pending updates, none of the updates of any database were moved");
+                    commands.insert_resource(DbImportFailed);
+                    return;
+                },
+                Ok(res) => releases.push((span.clone(), res)),
+            }
+        }
+    }
+    for (span, res) in releases {
+        let _span_guard = span.enter();
+        if let Err(e) = res.apply() {
+            error!(target:"dbimport", cause=?e, "error moving the released updates, check the update directories by hand");
+            commands.insert_resource(DbImportFailed);
+            return;
+        }
+        if !res.released.is_empty() || !res.archived.is_empty() {
+            info!(target:"dbimport", "released {} pending updates and archived {} updates", res.released.len(), res.archived.len());
+        }
+    }
 }
 
 fn show_banner(cfg: Res<ConfigMgr<DbImportConfig>>) {
@@ -154,4 +470,6 @@ struct ConsoleArgs {
     config:  String,
     #[arg(short, long, default_value_t = String::new())]
     service: String,
+    #[command(subcommand)]
+    command: Option<DbImportCommand>,
 }
@@ -30,11 +30,14 @@ fn full_extractor_log_cfg() -> (Vec<LogAppender>, Vec<LogLoggerConfig>) {
                 flags:     LogFlags::AddLogLevel | LogFlags::AddLogFilter,
             },
             LogAppender::File {
-                name:      String::from("full_extractor"),
-                min_level: LogLevel::Info,
-                max_level: LogLevel::Error,
-                flags:     LogFlags::AddLogLevel | LogFlags::AddLogFilter | LogFlags::AddLogTimestamps,
-                file:      String::from("full_extractor.log"),
+                name:          String::from("full_extractor"),
+                min_level:     LogLevel::Info,
+                max_level:     LogLevel::Error,
+                flags:         LogFlags::AddLogLevel | LogFlags::AddLogFilter | LogFlags::AddLogTimestamps,
+                file:          String::from("full_extractor.log"),
+                max_file_size: 0,
+                max_files:     0,
+                compress:      false,
             },
         ],
         vec![LogLoggerConfig {
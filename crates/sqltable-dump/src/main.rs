@@ -37,7 +37,7 @@ fn main() {
     let user = std::env::var("DBUSER").ok().unwrap_or("azcore".into());
     let password = std::env::var("DBPASSWORD").ok().unwrap_or("azcore".into());
     let host = std::env::var("DBHOST").ok().unwrap_or("127.0.0.1".into());
-    let output_dir = std::env::var("OUTPUT_DIR").ok().unwrap_or("data/sql/base".into());
+    let output_dir = std::env::var("OUTPUT_DIR").ok().unwrap_or("data/sql/base/mysql".into());
 
     let rt_handler = rt.handle().clone();
     rt.block_on(async move {
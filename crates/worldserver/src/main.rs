@@ -1,10 +1,10 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use azothacore_common::{
     az_error,
     banner,
     bevy_app::{bevy_app, AzStartupFailedEvent, TokioRuntime},
-    configuration::{config_mgr_plugin, ConfigMgr, ConfigMgrSet, DatabaseType},
+    configuration::{config_mgr_plugin_with_includes, config_watcher_plugin, ConfigMgr, ConfigMgrSet, DatabaseType, DEFAULT_CONFIG_WATCH_DEBOUNCE},
     log::{logging_plugin, LoggingSetupSet},
     AzResult,
     AZOTHA_CORE_CONFIG,
@@ -17,15 +17,26 @@ use azothacore_database::{
     database_env::{CharacterDatabase, HotfixDatabase, LoginDatabase, WorldDatabase},
     database_loader::DatabaseLoader,
     database_loader_utils::DatabaseLoaderError,
+    db_log::db_log_plugin,
+    query_processor::{db_query_queue_plugin, DbQueryQueue},
     query_with,
 };
 use azothacore_modules::{modules_plugin, ModulesInitSet, MODULES_LIST};
 use azothacore_server::{
     game::{
+        handlers::character_handler::character_handler_plugin,
         scripting::{script_mgr::ScriptMgr, scripts_plugin, ScriptsInitSet},
+        server::{
+            world_session::world_session_plugin,
+            world_socket::{world_socket_handling_plugin, InstanceSocket, WorldSocket},
+        },
         world::{world_plugin, CurrentRealm, WorldConfig, WorldDbVersion, WorldSets},
     },
     shared::{
+        networking::{
+            packet_log::{packet_log_plugin, PacketLogInitSet},
+            socket_mgr::{socket_mgr_plugin, SocketMgrStartNetworkSet},
+        },
         realms::{
             realm_list::{realm_list_plugin, RealmList, RealmListStartSet},
             RealmFlags,
@@ -63,19 +74,33 @@ fn main() {
     let rt = tokio::runtime::Builder::new_multi_thread().enable_all().build().unwrap();
 
     let mut app = bevy_app();
+    if vm.watch_config {
+        app.add_plugins(config_watcher_plugin::<WorldConfig>(DEFAULT_CONFIG_WATCH_DEBOUNCE));
+    }
     app.insert_resource(TokioRuntime(rt))
         .add_plugins((
             FrameTimeDiagnosticsPlugin,
             tokio_signal_handling_bevy_plugin,
-            config_mgr_plugin::<WorldConfig, _>(vm.config, vm.dry_run),
+            config_mgr_plugin_with_includes::<WorldConfig, _>(vm.config, vm.config_dir, vm.dry_run),
             logging_plugin::<WorldConfig>,
+            packet_log_plugin::<WorldConfig>,
             // Get the list of realms for the server
             realm_list_plugin::<WorldConfig>,
             modules_plugin,
             scripts_plugin,
             world_plugin,
-            // socket_mgr_plugin::<WorldConfig, SessionInner>,
-            // bnet_session_handling_plugin,
+            socket_mgr_plugin::<WorldConfig, WorldSocket>,
+            socket_mgr_plugin::<WorldConfig, InstanceSocket>,
+            world_socket_handling_plugin,
+            world_session_plugin,
+            character_handler_plugin,
+            (
+                db_query_queue_plugin::<LoginDatabase>,
+                db_query_queue_plugin::<WorldDatabase>,
+                db_query_queue_plugin::<CharacterDatabase>,
+                db_query_queue_plugin::<HotfixDatabase>,
+                db_log_plugin,
+            ),
             // // TODO: Impl me? Init Secret Manager
             // sSecretMgr->Initialize();
         ))
@@ -89,6 +114,9 @@ fn main() {
                     .pipe(handle_startup_errors)
                     .in_set(WorldserverMainSets::SetRealmNotConnectable),
                 load_realm_info.pipe(handle_startup_errors).in_set(WorldserverMainSets::LoadCurrentRealm),
+                set_server_connectable
+                    .pipe(handle_startup_errors)
+                    .in_set(WorldserverMainSets::SetRealmConnectable),
             ),
         )
         // Init logging right after config management
@@ -106,6 +134,12 @@ fn main() {
                 RealmListStartSet,
                 WorldserverMainSets::LoadCurrentRealm,
                 WorldSets::SetInitialWorldSettings,
+                PacketLogInitSet,
+                (
+                    SocketMgrStartNetworkSet::<WorldConfig, WorldSocket>::default(),
+                    SocketMgrStartNetworkSet::<WorldConfig, InstanceSocket>::default(),
+                ),
+                WorldserverMainSets::SetRealmConnectable,
             )
                 .chain(),),
         )
@@ -150,6 +184,7 @@ pub enum WorldserverMainSets {
     LoadScript,
     SetRealmNotConnectable,
     LoadCurrentRealm,
+    SetRealmConnectable,
 }
 
 fn show_banner(cfg: Res<ConfigMgr<WorldConfig>>) {
@@ -273,6 +308,10 @@ fn start_db(
     script_mgr.on_after_databases_loaded(&mut commands, updates.EnableDatabases);
 
     // Register DBs as resources
+    commands.insert_resource(DbQueryQueue::new(auth_db.clone(), rt.handle().clone()));
+    commands.insert_resource(DbQueryQueue::new(world_db.clone(), rt.handle().clone()));
+    commands.insert_resource(DbQueryQueue::new(characters_db.clone(), rt.handle().clone()));
+    commands.insert_resource(DbQueryQueue::new(hotfix_db.clone(), rt.handle().clone()));
     commands.insert_resource(auth_db);
     commands.insert_resource(world_db);
     commands.insert_resource(characters_db);
@@ -298,6 +337,20 @@ fn set_server_unconnectable(rt: Res<TokioRuntime>, login_db: Res<LoginDatabase>,
     Ok(())
 }
 
+/// Allow clients to connect to the realm once the world and instance sockets are listening
+fn set_server_connectable(rt: Res<TokioRuntime>, login_db: Res<LoginDatabase>, realm: Res<CurrentRealm>) -> AzResult<()> {
+    info!("setting worldserver as connectable");
+    rt.block_on(async {
+        query_with(
+            "UPDATE realmlist SET flag = flag & ~?, population = 0 WHERE id = ?",
+            args!(FlagSet::from(RealmFlags::Offline).bits(), realm.id.realm)?,
+        )
+        .execute(&**login_db)
+        .await
+    })?;
+    Ok(())
+}
+
 fn stop_db(
     rt: Res<TokioRuntime>,
     login_db: Option<Res<LoginDatabase>>,
@@ -378,10 +431,16 @@ fn load_realm_info(mut commands: Commands, cfg: Res<ConfigMgr<WorldConfig>>, rea
 struct ConsoleArgs {
     /// Dry run
     #[arg(short, long = "dry-run")]
-    dry_run: bool,
+    dry_run:      bool,
     /// use <arg> as configuration file
     #[arg(short, long, default_value_t = Path::new(CONF_DIR).join(AZOTHA_CORE_CONFIG).to_str().unwrap().to_string())]
-    config:  String,
+    config:       String,
+    /// Directory of toml files merged over the configuration file in order of their names, i.e. conf.d
+    #[arg(long)]
+    config_dir:   Option<PathBuf>,
+    /// Reload the configuration when the configuration file or the files in the configuration directory change
+    #[arg(long)]
+    watch_config: bool,
     #[arg(short, long, default_value_t = String::new())]
-    service: String,
+    service:      String,
 }
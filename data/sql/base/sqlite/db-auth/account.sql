@@ -0,0 +1,32 @@
+DROP TABLE IF EXISTS `account`;
+CREATE TABLE `account` (
+  `id` INTEGER PRIMARY KEY AUTOINCREMENT,
+  `username` TEXT COLLATE NOCASE NOT NULL DEFAULT '',
+  `sha_pass_hash` TEXT COLLATE NOCASE NOT NULL DEFAULT '',
+  `sessionkey` TEXT COLLATE NOCASE NOT NULL DEFAULT '',
+  `v` TEXT COLLATE NOCASE NOT NULL DEFAULT '',
+  `s` TEXT COLLATE NOCASE NOT NULL DEFAULT '',
+  `token_key` TEXT COLLATE NOCASE NOT NULL DEFAULT '',
+  `email` TEXT COLLATE NOCASE NOT NULL DEFAULT '',
+  `reg_mail` TEXT COLLATE NOCASE NOT NULL DEFAULT '',
+  `joindate` TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  `last_ip` TEXT COLLATE NOCASE NOT NULL DEFAULT '127.0.0.1',
+  `last_attempt_ip` TEXT COLLATE NOCASE NOT NULL DEFAULT '127.0.0.1',
+  `failed_logins` INTEGER NOT NULL DEFAULT 0,
+  `locked` INTEGER NOT NULL DEFAULT 0,
+  `lock_country` TEXT COLLATE NOCASE NOT NULL DEFAULT '00',
+  `last_login` TIMESTAMP NULL DEFAULT NULL,
+  `online` INTEGER NOT NULL DEFAULT 0,
+  `expansion` INTEGER NOT NULL DEFAULT 6,
+  `mutetime` INTEGER NOT NULL DEFAULT 0,
+  `mutereason` TEXT COLLATE NOCASE NOT NULL DEFAULT '',
+  `muteby` TEXT COLLATE NOCASE NOT NULL DEFAULT '',
+  `locale` INTEGER NOT NULL DEFAULT 0,
+  `os` TEXT COLLATE NOCASE NOT NULL DEFAULT '',
+  `recruiter` INTEGER NOT NULL DEFAULT 0,
+  `battlenet_account` INTEGER DEFAULT NULL,
+  `battlenet_index` INTEGER DEFAULT NULL,
+  UNIQUE (`username`),
+  UNIQUE (`battlenet_account`,`battlenet_index`),
+  CONSTRAINT `fk_bnet_acc` FOREIGN KEY (`battlenet_account`) REFERENCES `battlenet_accounts` (`id`)
+);
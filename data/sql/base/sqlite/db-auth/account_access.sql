@@ -0,0 +1,7 @@
+DROP TABLE IF EXISTS `account_access`;
+CREATE TABLE `account_access` (
+  `id` INTEGER NOT NULL,
+  `gmlevel` INTEGER NOT NULL,
+  `RealmID` INTEGER NOT NULL DEFAULT -1,
+  PRIMARY KEY (`id`,`RealmID`)
+);
@@ -0,0 +1,10 @@
+DROP TABLE IF EXISTS `account_banned`;
+CREATE TABLE `account_banned` (
+  `id` INTEGER NOT NULL DEFAULT 0,
+  `bandate` INTEGER NOT NULL DEFAULT 0,
+  `unbandate` INTEGER NOT NULL DEFAULT 0,
+  `bannedby` TEXT COLLATE NOCASE NOT NULL,
+  `banreason` TEXT COLLATE NOCASE NOT NULL,
+  `active` INTEGER NOT NULL DEFAULT 1,
+  PRIMARY KEY (`id`,`bandate`)
+);
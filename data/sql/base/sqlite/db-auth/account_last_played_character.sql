@@ -0,0 +1,11 @@
+DROP TABLE IF EXISTS `account_last_played_character`;
+CREATE TABLE `account_last_played_character` (
+  `accountId` INTEGER NOT NULL,
+  `region` INTEGER NOT NULL,
+  `battlegroup` INTEGER NOT NULL,
+  `realmId` INTEGER DEFAULT NULL,
+  `characterName` TEXT COLLATE NOCASE DEFAULT NULL,
+  `characterGUID` INTEGER DEFAULT NULL,
+  `lastPlayedTime` INTEGER DEFAULT NULL,
+  PRIMARY KEY (`accountId`,`region`,`battlegroup`)
+);
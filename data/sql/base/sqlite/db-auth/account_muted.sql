@@ -0,0 +1,9 @@
+DROP TABLE IF EXISTS `account_muted`;
+CREATE TABLE `account_muted` (
+  `guid` INTEGER NOT NULL DEFAULT 0,
+  `mutedate` INTEGER NOT NULL DEFAULT 0,
+  `mutetime` INTEGER NOT NULL DEFAULT 0,
+  `mutedby` TEXT COLLATE NOCASE NOT NULL,
+  `mutereason` TEXT COLLATE NOCASE NOT NULL,
+  PRIMARY KEY (`guid`,`mutedate`)
+);
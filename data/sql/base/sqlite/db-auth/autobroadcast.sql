@@ -0,0 +1,8 @@
+DROP TABLE IF EXISTS `autobroadcast`;
+CREATE TABLE `autobroadcast` (
+  `realmid` INTEGER NOT NULL DEFAULT -1,
+  `id` INTEGER PRIMARY KEY AUTOINCREMENT,
+  `weight` INTEGER DEFAULT 1,
+  `text` TEXT COLLATE NOCASE NOT NULL,
+  PRIMARY KEY (`id`,`realmid`)
+);
@@ -0,0 +1,8 @@
+DROP TABLE IF EXISTS `battle_pet_slots`;
+CREATE TABLE `battle_pet_slots` (
+  `id` INTEGER NOT NULL,
+  `battlenetAccountId` INTEGER NOT NULL,
+  `battlePetGuid` INTEGER NOT NULL,
+  `locked` INTEGER NOT NULL DEFAULT 1,
+  PRIMARY KEY (`id`,`battlenetAccountId`)
+);
@@ -0,0 +1,14 @@
+DROP TABLE IF EXISTS `battle_pets`;
+CREATE TABLE `battle_pets` (
+  `guid` INTEGER NOT NULL,
+  `battlenetAccountId` INTEGER NOT NULL,
+  `species` INTEGER NOT NULL,
+  `breed` INTEGER NOT NULL,
+  `level` INTEGER NOT NULL DEFAULT 1,
+  `exp` INTEGER NOT NULL DEFAULT 0,
+  `health` INTEGER NOT NULL DEFAULT 1,
+  `quality` INTEGER NOT NULL DEFAULT 0,
+  `flags` INTEGER NOT NULL DEFAULT 0,
+  `name` TEXT COLLATE NOCASE NOT NULL,
+  PRIMARY KEY (`guid`)
+);
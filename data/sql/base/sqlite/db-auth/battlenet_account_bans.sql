@@ -0,0 +1,9 @@
+DROP TABLE IF EXISTS `battlenet_account_bans`;
+CREATE TABLE `battlenet_account_bans` (
+  `id` INTEGER NOT NULL DEFAULT 0,
+  `bandate` INTEGER NOT NULL DEFAULT 0,
+  `unbandate` INTEGER NOT NULL DEFAULT 0,
+  `bannedby` TEXT COLLATE NOCASE NOT NULL,
+  `banreason` TEXT COLLATE NOCASE NOT NULL,
+  PRIMARY KEY (`id`,`bandate`)
+);
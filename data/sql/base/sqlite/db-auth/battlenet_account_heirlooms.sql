@@ -0,0 +1,7 @@
+DROP TABLE IF EXISTS `battlenet_account_heirlooms`;
+CREATE TABLE `battlenet_account_heirlooms` (
+  `accountId` INTEGER NOT NULL,
+  `itemId` INTEGER NOT NULL DEFAULT 0,
+  `flags` INTEGER NOT NULL DEFAULT 0,
+  PRIMARY KEY (`accountId`,`itemId`)
+);
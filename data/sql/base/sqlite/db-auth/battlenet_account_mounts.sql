@@ -0,0 +1,7 @@
+DROP TABLE IF EXISTS `battlenet_account_mounts`;
+CREATE TABLE `battlenet_account_mounts` (
+  `battlenetAccountId` INTEGER NOT NULL,
+  `mountSpellId` INTEGER NOT NULL,
+  `flags` INTEGER NOT NULL DEFAULT 0,
+  PRIMARY KEY (`battlenetAccountId`,`mountSpellId`)
+);
@@ -0,0 +1,7 @@
+DROP TABLE IF EXISTS `battlenet_account_toys`;
+CREATE TABLE `battlenet_account_toys` (
+  `accountId` INTEGER NOT NULL,
+  `itemId` INTEGER NOT NULL DEFAULT 0,
+  `isFavourite` INTEGER DEFAULT 0,
+  PRIMARY KEY (`accountId`,`itemId`)
+);
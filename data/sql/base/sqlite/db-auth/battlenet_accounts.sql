@@ -0,0 +1,18 @@
+DROP TABLE IF EXISTS `battlenet_accounts`;
+CREATE TABLE `battlenet_accounts` (
+  `id` INTEGER PRIMARY KEY AUTOINCREMENT,
+  `email` TEXT COLLATE NOCASE NOT NULL,
+  `sha_pass_hash` TEXT COLLATE NOCASE NOT NULL DEFAULT '',
+  `joindate` TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  `last_ip` TEXT COLLATE NOCASE NOT NULL DEFAULT '127.0.0.1',
+  `failed_logins` INTEGER NOT NULL DEFAULT 0,
+  `locked` INTEGER NOT NULL DEFAULT 0,
+  `lock_country` TEXT COLLATE NOCASE NOT NULL DEFAULT '00',
+  `last_login` TIMESTAMP NULL DEFAULT NULL,
+  `online` INTEGER NOT NULL DEFAULT 0,
+  `locale` INTEGER NOT NULL DEFAULT 0,
+  `os` TEXT COLLATE NOCASE NOT NULL DEFAULT '',
+  `LastCharacterUndelete` INTEGER NOT NULL DEFAULT 0,
+  `LoginTicket` TEXT COLLATE NOCASE DEFAULT NULL,
+  `LoginTicketExpiry` INTEGER DEFAULT NULL
+);
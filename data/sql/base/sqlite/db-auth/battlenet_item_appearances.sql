@@ -0,0 +1,8 @@
+DROP TABLE IF EXISTS `battlenet_item_appearances`;
+CREATE TABLE `battlenet_item_appearances` (
+  `battlenetAccountId` INTEGER NOT NULL,
+  `blobIndex` INTEGER NOT NULL,
+  `appearanceMask` INTEGER NOT NULL DEFAULT 0,
+  PRIMARY KEY (`battlenetAccountId`,`blobIndex`),
+  CONSTRAINT `fk_battlenet_item_appearances` FOREIGN KEY (`battlenetAccountId`) REFERENCES `battlenet_accounts` (`id`)
+);
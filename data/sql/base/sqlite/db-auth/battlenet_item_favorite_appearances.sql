@@ -0,0 +1,7 @@
+DROP TABLE IF EXISTS `battlenet_item_favorite_appearances`;
+CREATE TABLE `battlenet_item_favorite_appearances` (
+  `battlenetAccountId` INTEGER NOT NULL,
+  `itemModifiedAppearanceId` INTEGER NOT NULL,
+  PRIMARY KEY (`battlenetAccountId`,`itemModifiedAppearanceId`),
+  CONSTRAINT `fk_battlenet_item_favorite_appearances` FOREIGN KEY (`battlenetAccountId`) REFERENCES `battlenet_accounts` (`id`)
+);
@@ -0,0 +1,6 @@
+DROP TABLE IF EXISTS `ip2nation`;
+CREATE TABLE `ip2nation` (
+  `ip` INTEGER NOT NULL DEFAULT 0,
+  `country` TEXT COLLATE NOCASE NOT NULL DEFAULT ''
+);
+CREATE INDEX `ip2nation_ip` ON `ip2nation` (`ip`);
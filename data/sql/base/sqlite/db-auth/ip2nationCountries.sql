@@ -0,0 +1,12 @@
+DROP TABLE IF EXISTS `ip2nationCountries`;
+CREATE TABLE `ip2nationCountries` (
+  `code` TEXT COLLATE NOCASE NOT NULL DEFAULT '',
+  `iso_code_2` TEXT COLLATE NOCASE NOT NULL DEFAULT '',
+  `iso_code_3` TEXT COLLATE NOCASE DEFAULT '',
+  `iso_country` TEXT COLLATE NOCASE NOT NULL DEFAULT '',
+  `country` TEXT COLLATE NOCASE NOT NULL DEFAULT '',
+  `lat` REAL NOT NULL DEFAULT 0,
+  `lon` REAL NOT NULL DEFAULT 0,
+  PRIMARY KEY (`code`)
+);
+CREATE INDEX `ip2nationCountries_code` ON `ip2nationCountries` (`code`);
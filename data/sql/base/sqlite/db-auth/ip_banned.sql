@@ -0,0 +1,9 @@
+DROP TABLE IF EXISTS `ip_banned`;
+CREATE TABLE `ip_banned` (
+  `ip` TEXT COLLATE NOCASE NOT NULL DEFAULT '127.0.0.1',
+  `bandate` INTEGER NOT NULL,
+  `unbandate` INTEGER NOT NULL,
+  `bannedby` TEXT COLLATE NOCASE NOT NULL DEFAULT '[Console]',
+  `banreason` TEXT COLLATE NOCASE NOT NULL DEFAULT 'no reason',
+  PRIMARY KEY (`ip`,`bandate`)
+);
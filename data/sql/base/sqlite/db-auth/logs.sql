@@ -0,0 +1,8 @@
+DROP TABLE IF EXISTS `logs`;
+CREATE TABLE `logs` (
+  `time` INTEGER NOT NULL,
+  `realm` INTEGER NOT NULL,
+  `type` TEXT COLLATE NOCASE NOT NULL,
+  `level` INTEGER NOT NULL DEFAULT 0,
+  `string` TEXT COLLATE NOCASE CHARACTER SET latin1 COLLATE latin1_swedish_ci DEFAULT NULL
+);
@@ -0,0 +1,12 @@
+DROP TABLE IF EXISTS `logs_ip_actions`;
+CREATE TABLE `logs_ip_actions` (
+  `id` INTEGER PRIMARY KEY AUTOINCREMENT,
+  `account_id` INTEGER NOT NULL,
+  `character_guid` INTEGER NOT NULL,
+  `type` INTEGER NOT NULL,
+  `ip` TEXT COLLATE NOCASE NOT NULL DEFAULT '127.0.0.1',
+  `systemnote` TEXT COLLATE NOCASE DEFAULT NULL,
+  `unixtime` INTEGER NOT NULL,
+  `time` TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  `comment` TEXT COLLATE NOCASE DEFAULT NULL
+);
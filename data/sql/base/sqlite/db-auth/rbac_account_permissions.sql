@@ -0,0 +1,11 @@
+DROP TABLE IF EXISTS `rbac_account_permissions`;
+CREATE TABLE `rbac_account_permissions` (
+  `accountId` INTEGER NOT NULL,
+  `permissionId` INTEGER NOT NULL,
+  `granted` INTEGER NOT NULL DEFAULT 1,
+  `realmId` INTEGER NOT NULL DEFAULT -1,
+  PRIMARY KEY (`accountId`,`permissionId`,`realmId`),
+  CONSTRAINT `fk__rbac_account_permissions__account` FOREIGN KEY (`accountId`) REFERENCES `account` (`id`) ON DELETE CASCADE,
+  CONSTRAINT `fk__rbac_account_roles__rbac_permissions` FOREIGN KEY (`permissionId`) REFERENCES `rbac_permissions` (`id`) ON DELETE CASCADE
+);
+CREATE INDEX `rbac_account_permissions_fk__rbac_account_roles__rbac_permissions` ON `rbac_account_permissions` (`permissionId`);
@@ -0,0 +1,15 @@
+DROP TABLE IF EXISTS `rbac_default_permissions`;
+CREATE TABLE `rbac_default_permissions` (
+  `secId` INTEGER NOT NULL,
+  `permissionId` INTEGER NOT NULL,
+  `realmId` INTEGER NOT NULL DEFAULT -1,
+  PRIMARY KEY (`secId`,`permissionId`,`realmId`),
+  CONSTRAINT `fk__rbac_default_permissions__rbac_permissions` FOREIGN KEY (`permissionId`) REFERENCES `rbac_permissions` (`id`)
+);
+CREATE INDEX `rbac_default_permissions_fk__rbac_default_permissions__rbac_permissions` ON `rbac_default_permissions` (`permissionId`);
+
+INSERT INTO `rbac_default_permissions` VALUES
+(0,195,-1),
+(1,194,-1),
+(2,193,-1),
+(3,192,-1);
@@ -0,0 +1,8 @@
+DROP TABLE IF EXISTS `realmcharacters`;
+CREATE TABLE `realmcharacters` (
+  `realmid` INTEGER NOT NULL DEFAULT 0,
+  `acctid` INTEGER NOT NULL,
+  `numchars` INTEGER NOT NULL DEFAULT 0,
+  PRIMARY KEY (`realmid`,`acctid`)
+);
+CREATE INDEX `realmcharacters_acctid` ON `realmcharacters` (`acctid`);
@@ -0,0 +1,21 @@
+DROP TABLE IF EXISTS `realmlist`;
+CREATE TABLE `realmlist` (
+  `id` INTEGER PRIMARY KEY AUTOINCREMENT,
+  `name` TEXT COLLATE NOCASE NOT NULL DEFAULT '',
+  `address` TEXT COLLATE NOCASE NOT NULL DEFAULT '127.0.0.1',
+  `localAddress` TEXT COLLATE NOCASE NOT NULL DEFAULT '127.0.0.1',
+  `localSubnetMask` TEXT COLLATE NOCASE NOT NULL DEFAULT '255.255.255.0',
+  `port` INTEGER NOT NULL DEFAULT 8085,
+  `icon` INTEGER NOT NULL DEFAULT 0,
+  `flag` INTEGER NOT NULL DEFAULT 2,
+  `timezone` INTEGER NOT NULL DEFAULT 0,
+  `allowedSecurityLevel` INTEGER NOT NULL DEFAULT 0,
+  `population` REAL NOT NULL DEFAULT 0,
+  `gamebuild` INTEGER NOT NULL DEFAULT 26972,
+  `Region` INTEGER NOT NULL DEFAULT 1,
+  `Battlegroup` INTEGER NOT NULL DEFAULT 1,
+  UNIQUE (`name`)
+);
+
+INSERT INTO `realmlist` VALUES
+(1,'Azothacore','127.0.0.1','127.0.0.1','255.255.255.0',8085,0,0,1,0,0,26972,1,1);
@@ -0,0 +1,9 @@
+DROP TABLE IF EXISTS `updates`;
+CREATE TABLE `updates` (
+  `name` TEXT COLLATE NOCASE NOT NULL,
+  `hash` TEXT COLLATE NOCASE NOT NULL,
+  `state` TEXT COLLATE NOCASE NOT NULL DEFAULT 'RELEASED',
+  `timestamp` TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  `speed` INTEGER NOT NULL DEFAULT 0,
+  PRIMARY KEY (`name`)
+);
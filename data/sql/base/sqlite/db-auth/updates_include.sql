@@ -0,0 +1,10 @@
+DROP TABLE IF EXISTS `updates_include`;
+CREATE TABLE `updates_include` (
+  `path` TEXT COLLATE NOCASE NOT NULL,
+  `state` TEXT COLLATE NOCASE NOT NULL DEFAULT 'RELEASED',
+  PRIMARY KEY (`path`)
+);
+
+INSERT INTO `updates_include` VALUES
+('$/data/sql/custom/db-auth','RELEASED'),
+('$/data/sql/updates/db-auth','RELEASED');
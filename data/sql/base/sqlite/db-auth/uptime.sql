@@ -0,0 +1,9 @@
+DROP TABLE IF EXISTS `uptime`;
+CREATE TABLE `uptime` (
+  `realmid` INTEGER NOT NULL,
+  `starttime` INTEGER NOT NULL DEFAULT 0,
+  `uptime` INTEGER NOT NULL DEFAULT 0,
+  `maxplayers` INTEGER NOT NULL DEFAULT 0,
+  `revision` TEXT COLLATE NOCASE NOT NULL DEFAULT 'Azothacore',
+  PRIMARY KEY (`realmid`,`starttime`)
+);
@@ -14,10 +14,10 @@ UPDATE character_banned SET active = 0 WHERE unbandate <= UNIX_TIMESTAMP() AND u
 -- :name sel_data_by_name
 SELECT guid, account, name, gender, race, class, level FROM characters WHERE deleteDate IS NULL AND name = ?;
 
--- :name sel_data_by_guid
+-- :name sel_data_by_guid :typed :?
 SELECT guid, account, name, gender, race, class, level FROM characters WHERE deleteDate IS NULL AND guid = ?;
 
--- :name sel_check_name
+-- :name sel_check_name :typed :?
 SELECT 1 FROM characters WHERE name = ?;
 
 -- :name sel_check_guid
@@ -26,7 +26,7 @@ SELECT 1 FROM characters WHERE guid = ?;
 -- :name sel_sum_chars :typed :?
 SELECT COUNT(guid) FROM characters WHERE account = ?;
 
--- :name sel_char_create_info
+-- :name sel_char_create_info :typed :*
 SELECT level, race, class FROM characters WHERE account = ? LIMIT 0, ?;
 
 -- :name ins_character_ban
@@ -50,12 +50,13 @@ SELECT bandate, unbandate, bannedby, banreason FROM character_banned WHERE guid
 -- :name sel_banned_name
 SELECT characters.name FROM characters, character_banned WHERE character_banned.guid = ? AND character_banned.guid = characters.guid;
 
--- :name sel_enum
-SELECT c.guid, c.name, c.race, c.class, c.gender, c.skin, c.face, c.hairStyle, c.hairColor, c.facialStyle, c.level, c.zone, c.map, c.position_x, c.position_y, c.position_z,
-  gm.guildid, c.playerFlags, c.at_login, cp.entry, cp.modelid, cp.level, c.equipmentCache, cb.guid, c.extra_flags
+-- :name sel_enum :typed :*
+SELECT c.guid, c.name, c.race, c.class, c.gender, c.skin, c.face, c.hairStyle AS hair_style, c.hairColor AS hair_color, c.facialStyle AS facial_style,
+  c.customDisplay1 AS custom_display1, c.customDisplay2 AS custom_display2, c.customDisplay3 AS custom_display3, c.level, c.zone, c.map, c.position_x, c.position_y, c.position_z,
+  gm.guildid AS guild_id, c.playerFlags AS player_flags, c.at_login, cp.entry AS pet_entry, cp.modelid AS pet_model_id, cp.level AS pet_level, c.equipmentCache AS equipment_cache,
+  cb.guid AS banned_guid, c.slot, c.logout_time, c.activeTalentGroup AS active_talent_group, c.lastLoginBuild AS last_login_build
   FROM characters AS c LEFT JOIN character_pet AS cp ON c.guid = cp.owner AND cp.slot = ? LEFT JOIN guild_member AS gm ON c.guid = gm.guid
-  LEFT JOIN character_banned AS cb ON c.guid = cb.guid AND cb.active = 1 WHERE c.account = ? AND c.deleteInfos_Name IS NULL ORDER BY COALESCE(c.order, c.guid);
-
+  LEFT JOIN character_banned AS cb ON c.guid = cb.guid AND cb.active = 1 WHERE c.account = ? AND c.deleteInfos_Name IS NULL;
 
 -- :name sel_enum_declined_name
 SELECT c.guid, c.name, c.race, c.class, c.gender, c.skin, c.face, c.hairStyle, c.hairColor, c.facialStyle, c.level, c.zone, c.map, 
@@ -788,6 +789,12 @@ INSERT INTO characters (guid, account, name, race, class, gender, level, xp, mon
                   ammoId, knownTitles, actionBars, grantableLevels, innTriggerId) VALUES 
                   (?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?);
 
+-- :name ins_new_character
+-- :doc Only the columns known at character creation, the rest take their defaults until the player is saved
+INSERT INTO characters (guid, account, name, slot, race, class, gender, level, money, skin, face, hairStyle, hairColor, facialStyle, customDisplay1, customDisplay2, customDisplay3,
+                  map, zone, position_x, position_y, position_z, orientation, taximask, at_login, equipmentCache)
+                  VALUES (?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?);
+
 -- :name upd_character
 UPDATE characters SET name=?,race=?,class=?,gender=?,level=?,xp=?,money=?,skin=?,face=?,hairStyle=?,hairColor=?,facialStyle=?,bankSlots=?,restState=?,playerFlags=?,
                   map=?,instance_id=?,instance_mode_mask=?,position_x=?,position_y=?,position_z=?,orientation=?,trans_x=?,trans_y=?,trans_z=?,trans_o=?,transguid=?,taximask=?,cinematic=?,totaltime=?,leveltime=?,rest_bonus=?,
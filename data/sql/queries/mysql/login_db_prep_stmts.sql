@@ -2,6 +2,10 @@
 -- :doc Returns all realms from DB ordered by name
 SELECT id, name, address, localAddress, localSubnetMask, port, icon, flag, timezone, allowedSecurityLevel, population, gamebuild, Region, Battlegroup FROM realmlist WHERE flag <> 3 ORDER BY name;
 
+-- :name sel_realmlist_all :typed :*
+-- :doc All realms regardless of their flags, for the admin REST service
+SELECT id, name, address, port, flag, gamebuild, allowedSecurityLevel, population FROM realmlist ORDER BY id;
+
 -- :name del_expired_ip_bans
 -- :doc Removes expired IP bans from DB
 DELETE FROM ip_banned WHERE unbandate<>bandate AND unbandate<=UNIX_TIMESTAMP();
@@ -19,7 +23,7 @@ SELECT unbandate > UNIX_TIMESTAMP() OR unbandate = bandate AS banned, NULL as co
 -- :name ins_ip_auto_banned
 INSERT INTO ip_banned (ip, bandate, unbandate, bannedby, banreason) VALUES (?, UNIX_TIMESTAMP(), UNIX_TIMESTAMP()+?, 'Azothacore Auth', 'Failed login autoban');
 
--- :name sel_ip_banned_all
+-- :name sel_ip_banned_all :typed :*
 SELECT ip, bandate, unbandate, bannedby, banreason FROM ip_banned WHERE (bandate = unbandate OR unbandate > UNIX_TIMESTAMP()) ORDER BY unbandate;
 
 -- :name sel_ip_banned_by_ip
@@ -37,21 +41,19 @@ DELETE FROM account_banned WHERE id = ?;
 -- :name upd_account_info_continued_session
 UPDATE account SET sessionkey = ? WHERE id = ?;
 
--- :name sel_account_info_continued_session
+-- :name sel_account_info_continued_session :typed :?
 SELECT username, sessionkey FROM account WHERE id = ?;
 
--- :name upd_vs
-UPDATE account SET v = ?, s = ? WHERE username = ?;
-
 -- :name sel_account_id_by_name
 SELECT id FROM account WHERE username = ?;
 
 -- :name sel_account_list_by_name
 SELECT id, username FROM account WHERE username = ?;
 
--- :name sel_account_info_by_name
-SELECT a.id, a.sessionkey, ba.last_ip, ba.locked, ba.lock_country, a.expansion, a.mutetime, ba.locale, a.recruiter, a.os, ba.id, aa.gmLevel,
-bab.unbandate > UNIX_TIMESTAMP() OR bab.unbandate = bab.bandate, ab.unbandate > UNIX_TIMESTAMP() OR ab.unbandate = ab.bandate, r.id
+-- :name sel_account_info_by_name :typed :?
+SELECT a.id as id, a.sessionkey as session_key, ba.last_ip as last_ip, ba.locked as is_locked_to_ip, ba.lock_country as lock_country,
+a.expansion as expansion, a.mutetime as mute_time, ba.locale as locale, a.recruiter as recruiter, a.os as os, ba.id as bnet_account_id, aa.gmLevel as security,
+bab.unbandate > UNIX_TIMESTAMP() OR bab.unbandate = bab.bandate as is_bnet_banned, ab.unbandate > UNIX_TIMESTAMP() OR ab.unbandate = ab.bandate as is_banned, r.id as recruited_account_id
 FROM account a LEFT JOIN account r ON a.id = r.recruiter LEFT JOIN battlenet_accounts ba ON a.battlenet_account = ba.id
 LEFT JOIN account_access aa ON a.id = aa.id AND aa.RealmID IN (-1, ?) LEFT JOIN battlenet_account_bans bab ON ba.id = bab.id LEFT JOIN account_banned ab ON a.id = ab.id AND ab.active = 1
 WHERE a.username = ? ORDER BY aa.RealmID DESC LIMIT 1;
@@ -86,11 +88,11 @@ DELETE FROM realmcharacters WHERE acctid = ?;
 -- :name ins_realm_characters
 INSERT INTO realmcharacters (numchars, acctid, realmid) VALUES (?, ?, ?);
 
--- :name sel_sum_realm_characters
-SELECT SUM(numchars) FROM realmcharacters WHERE acctid = ?;
+-- :name sel_sum_realm_characters :typed :?
+SELECT CAST(COALESCE(SUM(numchars), 0) AS UNSIGNED INT) FROM realmcharacters WHERE acctid = ?;
 
 -- :name ins_account
-INSERT INTO account(username, sha_pass_hash, reg_mail, email, joindate, battlenet_account, battlenet_index) VALUES(?, ?, ?, ?, NOW(), ?, ?);
+INSERT INTO account(username, salt, verifier, reg_mail, email, joindate, battlenet_account, battlenet_index) VALUES(?, ?, ?, ?, ?, NOW(), ?, ?);
 
 -- :name ins_realm_characters_init
 INSERT INTO realmcharacters (realmid, acctid, numchars) SELECT realmlist.id, account.id, 0 FROM realmlist, account LEFT JOIN realmcharacters ON acctid=account.id WHERE acctid IS NULL;
@@ -105,10 +107,10 @@ UPDATE account SET locked = ? WHERE id = ?;
 UPDATE account SET lock_country = ? WHERE id = ?;
 
 -- :name upd_username
-UPDATE account SET v = 0, s = 0, username = ?, sha_pass_hash = ? WHERE id = ?;
+UPDATE account SET username = ?, salt = ?, verifier = ?, sha_pass_hash = '' WHERE id = ?;
 
 -- :name upd_password
-UPDATE account SET v = 0, s = 0, sha_pass_hash = ? WHERE id = ?;
+UPDATE account SET salt = ?, verifier = ?, sha_pass_hash = '' WHERE id = ?;
 
 -- :name upd_email
 UPDATE account SET email = ? WHERE id = ?;
@@ -131,6 +133,9 @@ UPDATE account SET last_attempt_ip = ? WHERE username = ?;
 -- :name upd_account_online
 UPDATE account SET online = ? WHERE id = ?;
 
+-- :name sel_account_online_by_bnet :typed :?
+SELECT online FROM account WHERE id = ? AND battlenet_account = ?;
+
 -- :name upd_uptime_players
 UPDATE uptime SET uptime = ?, maxplayers = ? WHERE realmid = ? AND starttime = ?;
 
@@ -155,11 +160,11 @@ SELECT gmlevel FROM account_access WHERE id = ? AND (RealmID = ? OR RealmID = -1
 -- :name get_username_by_id :typed :?
 SELECT username FROM account WHERE id = ?;
 
--- :name sel_check_password :?
-SELECT 1 FROM account WHERE id = ? AND sha_pass_hash = ?;
+-- :name sel_check_password :typed :?
+SELECT salt, verifier, sha_pass_hash FROM account WHERE id = ?;
 
--- :name sel_check_password_by_name
-SELECT 1 FROM account WHERE username = ? AND sha_pass_hash = ?;
+-- :name sel_check_password_by_name :typed :?
+SELECT salt, verifier, sha_pass_hash FROM account WHERE username = ?;
 
 -- :name sel_pinfo
 SELECT a.username, aa.gmlevel, a.email, a.reg_mail, a.last_ip, DATE_FORMAT(a.last_login, '%Y-%m-%d %T'), a.mutetime, a.mutereason, a.muteby, a.failed_logins, a.locked, a.OS FROM account a LEFT JOIN account_access aa ON (a.id = aa.id AND (aa.RealmID = ? OR aa.RealmID = -1)) WHERE a.id = ?;
@@ -238,7 +243,11 @@ DELETE FROM account_muted WHERE guid = ?;
 -- :name sel_bnet_authentication :typed :?
 SELECT
     ba.id as account_id,
-    ba.sha_pass_hash as pass_hash,
+    ba.srp_version as srp_version,
+    ba.salt as salt,
+    ba.verifier as verifier,
+    ba.sha_pass_hash as sha_pass_hash,
+    ba.totp_secret as totp_secret,
     ba.failed_logins as failed_logins,
     ba.LoginTicket as login_ticket,
     ba.LoginTicketExpiry as login_ticket_expiry,
@@ -314,7 +323,7 @@ DELETE FROM account_last_played_character WHERE accountId = ? AND region = ? AND
 INSERT INTO account_last_played_character (accountId, region, battlegroup, realmId, characterName, characterGUID, lastPlayedTime) VALUES (?,?,?,?,?,?,?);
 
 -- :name ins_bnet_account
-INSERT INTO battlenet_accounts (`email`,`sha_pass_hash`) VALUES (?, ?);
+INSERT INTO battlenet_accounts (`email`,`srp_version`,`salt`,`verifier`) VALUES (?, ?, ?, ?);
 
 -- :name sel_bnet_account_email_by_id :typed :?
 SELECT email FROM battlenet_accounts WHERE id = ?;
@@ -323,10 +332,28 @@ SELECT email FROM battlenet_accounts WHERE id = ?;
 SELECT id FROM battlenet_accounts WHERE email = ?;
 
 -- :name upd_bnet_password
-UPDATE battlenet_accounts SET sha_pass_hash = ? WHERE id = ?;
+UPDATE battlenet_accounts SET srp_version = ?, salt = ?, verifier = ?, sha_pass_hash = '' WHERE id = ?;
+
+-- :name sel_bnet_check_password :typed :?
+SELECT srp_version, salt, verifier, sha_pass_hash FROM battlenet_accounts WHERE id = ?;
+
+-- :name sel_bnet_totp_secret :typed :?
+SELECT totp_secret FROM battlenet_accounts WHERE id = ?;
+
+-- :name sel_bnet_totp_secrets :typed :*
+SELECT id, totp_secret FROM battlenet_accounts WHERE totp_secret IS NOT NULL;
 
--- :name sel_bnet_check_password :?
-SELECT 1 FROM battlenet_accounts WHERE id = ? AND sha_pass_hash = ?;
+-- :name upd_bnet_totp_secret
+UPDATE battlenet_accounts SET totp_secret = ? WHERE id = ?;
+
+-- :name sel_secret_digest :typed :?
+SELECT digest FROM secret_digest WHERE id = ?;
+
+-- :name ins_secret_digest
+INSERT INTO secret_digest (id, digest) VALUES (?, ?);
+
+-- :name del_secret_digest
+DELETE FROM secret_digest WHERE id = ?;
 
 -- :name upd_bnet_account_lock
 UPDATE battlenet_accounts SET locked = ? WHERE id = ?;
@@ -355,6 +382,9 @@ UPDATE battlenet_accounts SET failed_logins = failed_logins + 1 WHERE id = ?;
 -- :name ins_bnet_account_auto_banned
 INSERT INTO battlenet_account_bans(id, bandate, unbandate, bannedby, banreason) VALUES(?, UNIX_TIMESTAMP(), UNIX_TIMESTAMP()+?, 'Azothacore Auth', 'Failed login autoban');
 
+-- :name ins_bnet_report
+INSERT INTO battlenet_reports (account_id, game_account_id, target_game_account_id, report_type, report_qos, attributes, report_time) VALUES (?, ?, ?, ?, ?, ?, UNIX_TIMESTAMP());
+
 -- :name del_bnet_expired_account_banned
 DELETE FROM battlenet_account_bans WHERE unbandate<>bandate AND unbandate<=UNIX_TIMESTAMP();
 
@@ -433,9 +463,12 @@ INSERT INTO battlenet_item_favorite_appearances (battlenetAccountId, itemModifie
 -- :name del_bnet_item_favorite_appearance
 DELETE FROM battlenet_item_favorite_appearances WHERE battlenetAccountId = ? AND itemModifiedAppearanceId = ?;
 
+-- :name ins_log
+-- :doc Used by the Db log appenders
+INSERT INTO logs (time, realm, type, level, string) VALUES (?, ?, ?, ?, ?);
+
 -- NOTE: IGNORED QUERIES FROM TC BECAUSE WE DONT WANT TO IMPL stuff, such as IP2NATION, or DB Logging or TOTP etc first
 --          0) LOGIN_SEL_LOGON_COUNTRY, "SELECT country FROM ip2nation WHERE ip < ? ORDER BY ip DESC LIMIT 0,1", CONNECTION_SYNCH);
---          1) LOGIN_INS_LOG INSERT INTO logs (time, realm, type, level, string) VALUES (?, ?, ?, ?, ?);
 --          2) LOGIN_DEL_OLD_LOGS, "DELETE FROM logs WHERE (time + ?) < ? AND realm = ?", CONNECTION_ASYNC);
 --          3) LOGIN_SEL_IP2NATION_COUNTRY, "SELECT c.country FROM ip2nationCountries c, ip2nation i WHERE i.ip < ? AND c.code = i.country ORDER BY i.ip DESC LIMIT 0,1", CONNECTION_SYNCH);
 --          4) 
@@ -0,0 +1,15 @@
+-- Salted SRP6 verifiers for game and Battle.net accounts.
+--
+-- `sha_pass_hash` is kept for existing accounts, which are upgraded to a salt and verifier (and have their
+-- `sha_pass_hash` cleared) on their next successful login.
+ALTER TABLE `account`
+  DROP COLUMN `v`,
+  DROP COLUMN `s`,
+  ADD COLUMN `salt` binary(32) DEFAULT NULL AFTER `sha_pass_hash`,
+  ADD COLUMN `verifier` binary(32) DEFAULT NULL AFTER `salt`;
+
+-- srp_version: 0 = legacy unsalted `sha_pass_hash`, 1 = case insensitive password, 2 = case sensitive password
+ALTER TABLE `battlenet_accounts`
+  ADD COLUMN `srp_version` tinyint(3) unsigned NOT NULL DEFAULT 0 AFTER `sha_pass_hash`,
+  ADD COLUMN `salt` binary(32) DEFAULT NULL AFTER `srp_version`,
+  ADD COLUMN `verifier` blob DEFAULT NULL AFTER `salt`;
@@ -0,0 +1,11 @@
+-- Digests of the secrets that the servers are configured with, so that a changed secret can be detected
+DROP TABLE IF EXISTS `secret_digest`;
+CREATE TABLE `secret_digest` (
+  `id` int(10) unsigned NOT NULL,
+  `digest` varchar(100) NOT NULL,
+  PRIMARY KEY (`id`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb3 COLLATE=utf8mb3_general_ci;
+
+-- TOTP secrets of Battle.net accounts with an authenticator, encrypted with the TOTP master key
+ALTER TABLE `battlenet_accounts`
+  ADD COLUMN `totp_secret` varbinary(128) DEFAULT NULL AFTER `verifier`;
@@ -0,0 +1,14 @@
+-- Reports sent by clients through the Battle.net ReportService
+DROP TABLE IF EXISTS `battlenet_reports`;
+CREATE TABLE `battlenet_reports` (
+  `id` int(10) unsigned NOT NULL AUTO_INCREMENT,
+  `account_id` int(10) unsigned NOT NULL COMMENT 'Reporting Battle.net account',
+  `game_account_id` int(10) unsigned DEFAULT NULL COMMENT 'Reporting game account',
+  `target_game_account_id` int(10) unsigned DEFAULT NULL,
+  `report_type` varchar(64) NOT NULL DEFAULT '',
+  `report_qos` int(10) DEFAULT NULL,
+  `attributes` text NOT NULL,
+  `report_time` int(10) unsigned NOT NULL,
+  PRIMARY KEY (`id`),
+  KEY `idx_account_id` (`account_id`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_general_ci;
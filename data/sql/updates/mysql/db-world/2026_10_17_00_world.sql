@@ -0,0 +1,10 @@
+-- Pending updates are applied from `pending_db-world` with the PENDING state until they are released by dbimport,
+-- which can also move old released updates into the ARCHIVED `archive` directory.
+ALTER TABLE `updates`
+  MODIFY COLUMN `state` enum('RELEASED','CUSTOM','MODULE','ARCHIVED','PENDING') NOT NULL DEFAULT 'RELEASED' COMMENT 'defines if an update is released or archived.';
+ALTER TABLE `updates_include`
+  MODIFY COLUMN `state` enum('RELEASED','CUSTOM','MODULE','ARCHIVED','PENDING') NOT NULL DEFAULT 'RELEASED' COMMENT 'defines if the directory contains released or archived updates.';
+
+INSERT INTO `updates_include` (`path`, `state`) VALUES
+('$/data/sql/updates/pending_db-world','PENDING'),
+('$/data/sql/archive/db-world','ARCHIVED');
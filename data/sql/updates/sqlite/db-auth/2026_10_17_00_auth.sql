@@ -0,0 +1,13 @@
+-- Salted SRP6 verifiers for game and Battle.net accounts.
+--
+-- `sha_pass_hash` is kept for existing accounts, which are upgraded to a salt and verifier (and have their
+-- `sha_pass_hash` cleared) on their next successful login.
+ALTER TABLE `account` DROP COLUMN `v`;
+ALTER TABLE `account` DROP COLUMN `s`;
+ALTER TABLE `account` ADD COLUMN `salt` BLOB DEFAULT NULL;
+ALTER TABLE `account` ADD COLUMN `verifier` BLOB DEFAULT NULL;
+
+-- srp_version: 0 = legacy unsalted `sha_pass_hash`, 1 = case insensitive password, 2 = case sensitive password
+ALTER TABLE `battlenet_accounts` ADD COLUMN `srp_version` INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE `battlenet_accounts` ADD COLUMN `salt` BLOB DEFAULT NULL;
+ALTER TABLE `battlenet_accounts` ADD COLUMN `verifier` BLOB DEFAULT NULL;
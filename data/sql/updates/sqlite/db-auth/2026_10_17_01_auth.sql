@@ -0,0 +1,10 @@
+-- Digests of the secrets that the servers are configured with, so that a changed secret can be detected
+DROP TABLE IF EXISTS `secret_digest`;
+CREATE TABLE `secret_digest` (
+  `id` INTEGER NOT NULL,
+  `digest` TEXT COLLATE NOCASE NOT NULL,
+  PRIMARY KEY (`id`)
+);
+
+-- TOTP secrets of Battle.net accounts with an authenticator, encrypted with the TOTP master key
+ALTER TABLE `battlenet_accounts` ADD COLUMN `totp_secret` BLOB DEFAULT NULL;
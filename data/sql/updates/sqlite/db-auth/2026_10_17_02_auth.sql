@@ -0,0 +1,13 @@
+-- Reports sent by clients through the Battle.net ReportService
+DROP TABLE IF EXISTS `battlenet_reports`;
+CREATE TABLE `battlenet_reports` (
+  `id` INTEGER PRIMARY KEY AUTOINCREMENT,
+  `account_id` INTEGER NOT NULL,
+  `game_account_id` INTEGER DEFAULT NULL,
+  `target_game_account_id` INTEGER DEFAULT NULL,
+  `report_type` TEXT COLLATE NOCASE NOT NULL DEFAULT '',
+  `report_qos` INTEGER DEFAULT NULL,
+  `attributes` TEXT COLLATE NOCASE NOT NULL,
+  `report_time` INTEGER NOT NULL
+);
+CREATE INDEX `battlenet_reports_idx_account_id` ON `battlenet_reports` (`account_id`);
@@ -0,0 +1,5 @@
+-- Pending updates are applied from `pending_db-auth` with the PENDING state until they are released by dbimport,
+-- which can also move old released updates into the ARCHIVED `archive` directory.
+INSERT INTO `updates_include` (`path`, `state`) VALUES
+('$/data/sql/updates/pending_db-auth','PENDING'),
+('$/data/sql/archive/db-auth','ARCHIVED');